@@ -0,0 +1,43 @@
+//! Benchmarks comparing GMP vs the native-fft NTT for one LLR step,
+//! x² − 2 mod 3·2^n − 1.
+//!
+//! Run with: `cargo bench --features native-fft --bench fft_bench`
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rug::ops::RemRounding;
+use rug::Integer;
+
+fn bench_llr_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("llr_step");
+    group.sample_size(10);
+
+    for &n in &[10_000u32, 100_000, 1_000_000] {
+        let modulus = (Integer::from(3) << n) - 1u32;
+        let x = Integer::from(&modulus - 12_345u32);
+
+        group.bench_function(format!("gmp_{}", n), |b| {
+            b.iter(|| {
+                let mut u = black_box(&x).clone();
+                u.square_mut();
+                u -= 2u32;
+                u.rem_euc(&modulus)
+            })
+        });
+
+        #[cfg(feature = "native-fft")]
+        {
+            let ctx = darkreach::fft::NttModulus::new(&modulus);
+            group.bench_function(format!("ntt_{}", n), |b| {
+                b.iter(|| {
+                    let u = ctx.square_mod(black_box(&x)) - 2u32;
+                    u.rem_euc(&modulus)
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_llr_step);
+criterion_main!(benches);
@@ -33,7 +33,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{info, warn};
 
-use crate::db::Database;
+use crate::db::{CostCalibrationRow, Database};
 use crate::project;
 use crate::strategy;
 
@@ -456,6 +456,7 @@ impl AiEngine {
         if let Err(e) = project::orchestrate_tick(db).await {
             warn!(error = %e, "ai_engine: project orchestration failed");
         }
+        crate::strategy::adapt_block_sizes(db).await;
 
         // ORIENT: score forms, detect drift
         let analysis = self.orient(&snapshot);
@@ -1276,6 +1277,21 @@ pub fn fit_power_law(observations: &[CostObservation]) -> Option<(f64, f64, f64)
     Some((a, b, mape))
 }
 
+/// Refit one form's cost model from recent completed blocks and persist it.
+///
+/// Backs `POST /api/strategy/calibrate`, so operators can refresh the model
+/// after hardware changes instead of waiting for the next LEARN phase.
+/// Returns `None` when there are too few usable samples to fit.
+pub async fn recalibrate_form(db: &Database, form: &str) -> Result<Option<CostCalibrationRow>> {
+    let samples = db.get_block_timing_samples(form).await?;
+    let Some((a, b, mape)) = fit_power_law(&samples) else {
+        return Ok(None);
+    };
+    db.upsert_cost_calibration(form, a, b, samples.len() as i64, Some(mape))
+        .await?;
+    db.get_cost_calibration(form).await
+}
+
 // ── Tests ───────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -0,0 +1,575 @@
+//! # Candidate — Structured Candidate Construction
+//!
+//! One [`CandidateForm`] implementation per prime form owns the three pieces
+//! of per-form knowledge that used to be spread across the engines and
+//! [`crate::verify`]: building the `rug::Integer` for a search index,
+//! rendering the stored expression string, and parsing that string back.
+//!
+//! ## Index Convention
+//!
+//! Each form carries its fixed search parameters in the implementing struct
+//! and takes the value the search iterates over as the index:
+//!
+//! | Form | Parameters | Index | Expression |
+//! |------|------------|-------|------------|
+//! | [`Factorial`] | sign | n | `"73! + 1"` |
+//! | [`Primorial`] | sign | p | `"31# - 1"` |
+//! | [`Kbn`] | k, b, c | n | `"3*2^31 + 1"`, `"1*2^12 + 3"` |
+//! | [`CullenWoodall`] | sign | n | `"141*2^141 + 1"` |
+//! | [`Wagstaff`] | — | p | `"(2^127+1)/3"` |
+//! | [`CarolKynea`] | sign | n | `"(2^7-1)^2-2"` |
+//! | [`Twin`] | k, b | n | `"3*2^100 +/- 1"` (constructs the −1 member) |
+//! | [`SophieGermain`] | k, b | n | `"3*2^100-1"` |
+//! | [`Repunit`] | b | n | `"R(10, 19)"` |
+//! | [`GenFermat`] | n | b | `"6^(2^3) + 1"` |
+//! | [`NearRepdigit`] | d, m | k | `"10^5 - 1 - 4*(10^3 + 10^1)"` |
+//!
+//! Palindromic primes are stored as their decimal value, which has no
+//! small index, so the registry rebuilds them by parsing the digits.
+//! Gaussian primes `a+bi` are not rational integers; the registry rebuilds
+//! the norm a² + b² whose primality decides them.
+//!
+//! ## Registry
+//!
+//! [`REGISTRY`] maps every stored form name (including the `cullen`/`woodall`
+//! and `carol`/`kynea` aliases) to a reconstruction function;
+//! [`crate::verify::reconstruct_candidate`] dispatches through it.
+//! [`parse_family`] splits an expression into its parameters and index the
+//! same way, for queries over one family such as [`crate::db::Database::prime_n_gaps`].
+
+use rug::ops::Pow;
+use rug::Integer;
+
+use crate::{checked_u32, gaussian, near_repdigit};
+
+/// A prime form with fixed parameters, indexed by the value a search steps.
+pub trait CandidateForm: Sized {
+    /// The candidate integer at `index`.
+    fn construct(&self, index: u64) -> Integer;
+
+    /// The stored expression for the candidate at `index`.
+    fn expression(&self, index: u64) -> String;
+
+    /// Recover the form parameters and index from a stored expression, or
+    /// `None` if the string is not in this form's format.
+    fn parse_expression(expr: &str) -> Option<(Self, u64)>;
+}
+
+/// n! ± 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Factorial {
+    pub plus: bool,
+}
+
+/// p# ± 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Primorial {
+    pub plus: bool,
+}
+
+/// k·b^n + c, with c = ±1 for the classic Proth/Riesel forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Kbn {
+    pub k: u64,
+    pub base: u32,
+    pub c: i64,
+}
+
+/// n·2^n + 1 (Cullen) or n·2^n − 1 (Woodall).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CullenWoodall {
+    pub plus: bool,
+}
+
+/// (2^p + 1)/3
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wagstaff;
+
+/// (2^n − 1)² − 2 (Carol) or (2^n + 1)² − 2 (Kynea).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CarolKynea {
+    pub plus: bool,
+}
+
+/// The pair k·b^n ± 1; constructs the smaller member k·b^n − 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Twin {
+    pub k: u64,
+    pub base: u32,
+}
+
+/// k·b^n − 1 with 2(k·b^n − 1) + 1 also prime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SophieGermain {
+    pub k: u64,
+    pub base: u32,
+}
+
+/// (b^n − 1)/(b − 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repunit {
+    pub base: u32,
+}
+
+/// b^(2^n) + 1, indexed by the base b.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenFermat {
+    pub fermat_n: u32,
+}
+
+/// 10^(2k+1) − 1 − d·(10^(k+m) + 10^(k−m)), indexed by k.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NearRepdigit {
+    pub d: u32,
+    pub m: u64,
+}
+
+fn sign_char(plus: bool) -> char {
+    if plus {
+        '+'
+    } else {
+        '-'
+    }
+}
+
+fn plus_minus_one(value: Integer, plus: bool) -> Integer {
+    if plus {
+        value + 1u32
+    } else {
+        value - 1u32
+    }
+}
+
+/// Parse an exponent-like index; anything past `u32::MAX` could not be
+/// constructed, so it is rejected here rather than panicking later.
+fn parse_exponent(s: &str) -> Option<u64> {
+    s.trim().parse::<u32>().ok().map(u64::from)
+}
+
+/// Parse the "± 1" tail shared by the factorial and primorial formats.
+fn parse_unit_offset(s: &str) -> Option<bool> {
+    let s = s.trim();
+    let (plus, rest) = if let Some(rest) = s.strip_prefix('+') {
+        (true, rest)
+    } else {
+        (false, s.strip_prefix('-')?)
+    };
+    (rest.trim() == "1").then_some(plus)
+}
+
+/// Split "k*b^n" into (k, b, n).
+fn parse_power_term(s: &str) -> Option<(u64, u32, u64)> {
+    let (k, rest) = s.split_once('*')?;
+    let (base, n) = rest.split_once('^')?;
+    Some((
+        k.trim().parse().ok()?,
+        base.trim().parse().ok()?,
+        parse_exponent(n)?,
+    ))
+}
+
+/// Split "k*b^n ± c" into (k, b, n, c).
+fn parse_kbn_parts(expr: &str) -> Option<(u64, u32, u64, i64)> {
+    let (term, c) = if let Some((term, c)) = expr.rsplit_once(" + ") {
+        (term, c.trim().parse::<i64>().ok()?)
+    } else {
+        let (term, c) = expr.rsplit_once(" - ")?;
+        (term, -c.trim().parse::<i64>().ok()?)
+    };
+    let (k, base, n) = parse_power_term(term)?;
+    Some((k, base, n, c))
+}
+
+impl CandidateForm for Factorial {
+    fn construct(&self, n: u64) -> Integer {
+        plus_minus_one(Integer::from(Integer::factorial(checked_u32(n))), self.plus)
+    }
+
+    fn expression(&self, n: u64) -> String {
+        format!("{}! {} 1", n, sign_char(self.plus))
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let (n, rest) = expr.split_once('!')?;
+        let plus = parse_unit_offset(rest)?;
+        Some((Factorial { plus }, parse_exponent(n)?))
+    }
+}
+
+impl CandidateForm for Primorial {
+    fn construct(&self, p: u64) -> Integer {
+        plus_minus_one(Integer::from(Integer::primorial(checked_u32(p))), self.plus)
+    }
+
+    fn expression(&self, p: u64) -> String {
+        format!("{}# {} 1", p, sign_char(self.plus))
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let (p, rest) = expr.split_once('#')?;
+        let plus = parse_unit_offset(rest)?;
+        Some((Primorial { plus }, parse_exponent(p)?))
+    }
+}
+
+impl CandidateForm for Kbn {
+    fn construct(&self, n: u64) -> Integer {
+        Integer::from(self.k) * Integer::from(self.base).pow(checked_u32(n)) + self.c
+    }
+
+    fn expression(&self, n: u64) -> String {
+        let sign = sign_char(self.c >= 0);
+        format!(
+            "{}*{}^{} {} {}",
+            self.k,
+            self.base,
+            n,
+            sign,
+            self.c.unsigned_abs()
+        )
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let (k, base, n, c) = parse_kbn_parts(expr)?;
+        Some((Kbn { k, base, c }, n))
+    }
+}
+
+impl CandidateForm for CullenWoodall {
+    fn construct(&self, n: u64) -> Integer {
+        plus_minus_one(Integer::from(n) << checked_u32(n), self.plus)
+    }
+
+    fn expression(&self, n: u64) -> String {
+        format!("{}*2^{} {} 1", n, n, sign_char(self.plus))
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let (k, base, n, c) = parse_kbn_parts(expr)?;
+        if k != n || base != 2 || c.abs() != 1 {
+            return None;
+        }
+        Some((CullenWoodall { plus: c > 0 }, n))
+    }
+}
+
+impl CandidateForm for Wagstaff {
+    fn construct(&self, p: u64) -> Integer {
+        ((Integer::from(1u32) << checked_u32(p)) + 1u32) / 3u32
+    }
+
+    fn expression(&self, p: u64) -> String {
+        format!("(2^{}+1)/3", p)
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let p = expr.strip_prefix("(2^")?.strip_suffix("+1)/3")?;
+        Some((Wagstaff, parse_exponent(p)?))
+    }
+}
+
+impl CandidateForm for CarolKynea {
+    fn construct(&self, n: u64) -> Integer {
+        plus_minus_one(Integer::from(1u32) << checked_u32(n), self.plus).square() - 2u32
+    }
+
+    fn expression(&self, n: u64) -> String {
+        format!("(2^{}{}1)^2-2", n, sign_char(self.plus))
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let inner = expr.strip_prefix("(2^")?.strip_suffix("1)^2-2")?;
+        let (n, plus) = if let Some(n) = inner.strip_suffix('+') {
+            (n, true)
+        } else {
+            (inner.strip_suffix('-')?, false)
+        };
+        Some((CarolKynea { plus }, parse_exponent(n)?))
+    }
+}
+
+impl CandidateForm for Twin {
+    fn construct(&self, n: u64) -> Integer {
+        Integer::from(self.k) * Integer::from(self.base).pow(checked_u32(n)) - 1u32
+    }
+
+    fn expression(&self, n: u64) -> String {
+        format!("{}*{}^{} +/- 1", self.k, self.base, n)
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let (k, base, n) = parse_power_term(expr.strip_suffix(" +/- 1")?)?;
+        Some((Twin { k, base }, n))
+    }
+}
+
+impl CandidateForm for SophieGermain {
+    fn construct(&self, n: u64) -> Integer {
+        Integer::from(self.k) * Integer::from(self.base).pow(checked_u32(n)) - 1u32
+    }
+
+    fn expression(&self, n: u64) -> String {
+        format!("{}*{}^{}-1", self.k, self.base, n)
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let (k, base, n) = parse_power_term(expr.strip_suffix("-1")?)?;
+        Some((SophieGermain { k, base }, n))
+    }
+}
+
+impl CandidateForm for Repunit {
+    fn construct(&self, n: u64) -> Integer {
+        (Integer::from(self.base).pow(checked_u32(n)) - 1u32) / (self.base - 1)
+    }
+
+    fn expression(&self, n: u64) -> String {
+        format!("R({}, {})", self.base, n)
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let inner = expr.strip_prefix("R(")?.strip_suffix(')')?;
+        let (base, n) = inner.split_once(',')?;
+        let base: u32 = base.trim().parse().ok()?;
+        if base < 2 {
+            return None;
+        }
+        Some((Repunit { base }, parse_exponent(n)?))
+    }
+}
+
+impl CandidateForm for GenFermat {
+    fn construct(&self, b: u64) -> Integer {
+        Integer::from(b).pow(1u32 << self.fermat_n) + 1u32
+    }
+
+    fn expression(&self, b: u64) -> String {
+        format!("{}^(2^{}) + 1", b, self.fermat_n)
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        let (b, rest) = expr.split_once("^(2^")?;
+        let fermat_n: u32 = rest.strip_suffix(") + 1")?.trim().parse().ok()?;
+        if fermat_n >= 32 {
+            return None;
+        }
+        Some((GenFermat { fermat_n }, b.trim().parse().ok()?))
+    }
+}
+
+impl CandidateForm for NearRepdigit {
+    fn construct(&self, k: u64) -> Integer {
+        near_repdigit::build_candidate(k, self.d, self.m)
+    }
+
+    fn expression(&self, k: u64) -> String {
+        near_repdigit::format_expression(k, self.d, self.m)
+    }
+
+    fn parse_expression(expr: &str) -> Option<(Self, u64)> {
+        // "10^D - 1 - 2d*10^k" (m == 0) or "10^D - 1 - d*(10^(k+m) + 10^(k-m))"
+        let (digit_count, modifier) = expr.strip_prefix("10^")?.split_once(" - 1 - ")?;
+        let digit_count: u64 = digit_count.trim().parse().ok()?;
+        if digit_count.is_multiple_of(2) {
+            return None;
+        }
+        let k = digit_count / 2;
+        let (coeff, powers) = modifier.split_once('*')?;
+        let coeff: u32 = coeff.trim().parse().ok()?;
+        if let Some(pair) = powers.strip_prefix('(') {
+            let (hi, lo) = pair.strip_suffix(')')?.split_once(" + ")?;
+            let hi: u64 = hi.strip_prefix("10^")?.parse().ok()?;
+            let lo: u64 = lo.strip_prefix("10^")?.parse().ok()?;
+            let m = hi.checked_sub(k)?;
+            (m > 0 && k.checked_sub(m) == Some(lo)).then_some((NearRepdigit { d: coeff, m }, k))
+        } else {
+            let p: u64 = powers.strip_prefix("10^")?.parse().ok()?;
+            (p == k && coeff.is_multiple_of(2)).then_some((NearRepdigit { d: coeff / 2, m: 0 }, k))
+        }
+    }
+}
+
+/// Rebuilds a stored expression, or `None` if it is not in the form's format.
+pub type Reconstruct = fn(&str) -> Option<Integer>;
+
+fn via<F: CandidateForm>(expr: &str) -> Option<Integer> {
+    F::parse_expression(expr.trim()).map(|(form, index)| form.construct(index))
+}
+
+fn decimal(expr: &str) -> Option<Integer> {
+    Integer::parse(expr.trim()).ok().map(Integer::from)
+}
+
+/// Stored form name → reconstruction, covering every form the engines log.
+pub const REGISTRY: &[(&str, Reconstruct)] = &[
+    ("factorial", via::<Factorial>),
+    ("primorial", via::<Primorial>),
+    ("kbn", via::<Kbn>),
+    ("palindromic", decimal),
+    ("near_repdigit", via::<NearRepdigit>),
+    ("cullen", via::<CullenWoodall>),
+    ("woodall", via::<CullenWoodall>),
+    ("cullen_woodall", via::<CullenWoodall>),
+    ("wagstaff", via::<Wagstaff>),
+    ("carol", via::<CarolKynea>),
+    ("kynea", via::<CarolKynea>),
+    ("carol_kynea", via::<CarolKynea>),
+    ("twin", via::<Twin>),
+    ("sophie_germain", via::<SophieGermain>),
+    ("repunit", via::<Repunit>),
+    ("gen_fermat", via::<GenFermat>),
+    ("gaussian", gaussian::rational_candidate),
+];
+
+/// The registered reconstruction for `form`, if the form is known.
+pub fn reconstructor(form: &str) -> Option<Reconstruct> {
+    REGISTRY
+        .iter()
+        .find(|(name, _)| *name == form)
+        .map(|&(_, rebuild)| rebuild)
+}
+
+/// Split a stored expression into its family and index: the form's fixed
+/// parameters as a JSON object keyed by the struct's field names
+/// (`{"k": 3, "base": 2, "c": 1}` for kbn) and the value the search steps.
+/// `None` for palindromic primes, unknown forms and unparsable expressions.
+pub fn parse_family(form: &str, expr: &str) -> Option<(serde_json::Value, u64)> {
+    use serde_json::json;
+    let expr = expr.trim();
+    match form {
+        "factorial" => Factorial::parse_expression(expr).map(|(f, i)| (json!({"plus": f.plus}), i)),
+        "primorial" => Primorial::parse_expression(expr).map(|(f, i)| (json!({"plus": f.plus}), i)),
+        "kbn" => Kbn::parse_expression(expr)
+            .map(|(f, i)| (json!({"k": f.k, "base": f.base, "c": f.c}), i)),
+        "cullen" | "woodall" | "cullen_woodall" => {
+            CullenWoodall::parse_expression(expr).map(|(f, i)| (json!({"plus": f.plus}), i))
+        }
+        "wagstaff" => Wagstaff::parse_expression(expr).map(|(_, i)| (json!({}), i)),
+        "carol" | "kynea" | "carol_kynea" => {
+            CarolKynea::parse_expression(expr).map(|(f, i)| (json!({"plus": f.plus}), i))
+        }
+        "twin" => Twin::parse_expression(expr).map(|(f, i)| (json!({"k": f.k, "base": f.base}), i)),
+        "sophie_germain" => SophieGermain::parse_expression(expr)
+            .map(|(f, i)| (json!({"k": f.k, "base": f.base}), i)),
+        "repunit" => Repunit::parse_expression(expr).map(|(f, i)| (json!({"base": f.base}), i)),
+        "gen_fermat" => {
+            GenFermat::parse_expression(expr).map(|(f, i)| (json!({"fermat_n": f.fermat_n}), i))
+        }
+        "near_repdigit" => {
+            NearRepdigit::parse_expression(expr).map(|(f, i)| (json!({"d": f.d, "m": f.m}), i))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `parse_expression(expression(i))` returns `(form, i)`, and the
+    /// registry rebuilds the same Integer that `construct(i)` does.
+    fn assert_round_trip<F>(name: &str, form: F, index: u64)
+    where
+        F: CandidateForm + Copy + PartialEq + std::fmt::Debug,
+    {
+        let expr = form.expression(index);
+        let parsed = F::parse_expression(&expr);
+        assert_eq!(parsed, Some((form, index)), "{}", expr);
+        let rebuilt = reconstructor(name).unwrap()(&expr);
+        assert_eq!(rebuilt, Some(form.construct(index)), "{}", expr);
+    }
+
+    #[test]
+    fn factorial_and_primorial_round_trip() {
+        for plus in [true, false] {
+            assert_round_trip("factorial", Factorial { plus }, 11);
+            assert_round_trip("primorial", Primorial { plus }, 31);
+        }
+        assert_eq!(Factorial { plus: true }.construct(5), 121);
+        assert_eq!(Primorial { plus: false }.construct(5), 29);
+    }
+
+    #[test]
+    fn kbn_round_trip_with_offsets() {
+        for c in [1, -1, 3, -3] {
+            assert_round_trip("kbn", Kbn { k: 5, base: 2, c }, 7);
+        }
+        let form = |k, base, c| Kbn { k, base, c };
+        assert_round_trip("kbn", form(3, 6, 1), 40);
+        assert_eq!(form(3, 2, 1).construct(5), 97);
+        assert_eq!(form(5, 2, -3).construct(7), 637);
+    }
+
+    #[test]
+    fn cullen_woodall_round_trip() {
+        for name in ["cullen", "woodall", "cullen_woodall"] {
+            assert_round_trip(name, CullenWoodall { plus: true }, 141);
+            assert_round_trip(name, CullenWoodall { plus: false }, 6);
+        }
+        assert_eq!(CullenWoodall { plus: false }.construct(3), 23);
+        // k must equal n for a Cullen/Woodall number.
+        assert_eq!(CullenWoodall::parse_expression("3*2^5 + 1"), None);
+    }
+
+    #[test]
+    fn wagstaff_and_carol_kynea_round_trip() {
+        assert_round_trip("wagstaff", Wagstaff, 127);
+        assert_eq!(Wagstaff.construct(11), 683);
+        for plus in [true, false] {
+            assert_round_trip("carol_kynea", CarolKynea { plus }, 7);
+            // Past 32 bits, where a u32 shift would overflow.
+            assert_round_trip("carol_kynea", CarolKynea { plus }, 40);
+        }
+        assert_eq!(CarolKynea { plus: false }.construct(7), 16127);
+        assert_eq!(CarolKynea { plus: true }.construct(7), 16639);
+    }
+
+    #[test]
+    fn twin_and_sophie_germain_round_trip() {
+        assert_round_trip("twin", Twin { k: 3, base: 2 }, 100);
+        assert_round_trip("sophie_germain", SophieGermain { k: 3, base: 2 }, 100);
+        assert_eq!(Twin { k: 1, base: 2 }.construct(6), 63);
+        assert_eq!(SophieGermain { k: 3, base: 2 }.construct(4), 47);
+    }
+
+    #[test]
+    fn repunit_and_gen_fermat_round_trip() {
+        assert_round_trip("repunit", Repunit { base: 10 }, 19);
+        assert_round_trip("repunit", Repunit { base: 7 }, 5);
+        assert_eq!(Repunit { base: 10 }.construct(7), 1111111);
+        assert_eq!(Repunit::parse_expression("R(1, 5)"), None);
+        assert_round_trip("gen_fermat", GenFermat { fermat_n: 3 }, 6);
+        assert_eq!(GenFermat { fermat_n: 3 }.construct(6), 1679617);
+    }
+
+    #[test]
+    fn near_repdigit_round_trip() {
+        for (d, m) in [(4, 0), (4, 1), (8, 2), (1, 3)] {
+            assert_round_trip("near_repdigit", NearRepdigit { d, m }, 5);
+        }
+        assert_eq!(NearRepdigit { d: 4, m: 0 }.construct(1), 919);
+        assert_eq!(NearRepdigit { d: 4, m: 1 }.construct(2), 95959);
+        // Asymmetric powers have no (d, m, k) form.
+        let asymmetric = "10^5 - 1 - 4*(10^3 + 10^0)";
+        assert_eq!(NearRepdigit::parse_expression(asymmetric), None);
+    }
+
+    #[test]
+    fn registry_covers_palindromic_and_rejects_unknown() {
+        let palindrome = reconstructor("palindromic").unwrap()("10301");
+        assert_eq!(palindrome, Some(Integer::from(10301)));
+        assert!(reconstructor("mersenne").is_none());
+        assert_eq!(reconstructor("kbn").unwrap()("3*2^5"), None);
+    }
+
+    #[test]
+    fn parse_family_splits_parameters_from_index() {
+        let (params, n) = parse_family("kbn", " 3*2^31 - 1").unwrap();
+        assert_eq!(params, serde_json::json!({"k": 3, "base": 2, "c": -1}));
+        assert_eq!(n, 31);
+        let (params, n) = parse_family("woodall", "141*2^141 - 1").unwrap();
+        assert_eq!((params, n), (serde_json::json!({"plus": false}), 141));
+        assert_eq!(parse_family("palindromic", "10301"), None);
+        assert_eq!(parse_family("kbn", "73! + 1"), None);
+    }
+}
@@ -17,16 +17,24 @@
 //!
 //! ## Algorithm
 //!
-//! 1. **Incremental modular sieve**: For each sieve prime q > 3, tracks
-//!    g2 = 2^n mod q and g4 = 4^n mod q. Carol is composite when
-//!    g4 ≡ 2·g2 + 1 (mod q); Kynea when g4 + 2·g2 ≡ 1 (mod q).
+//! 1. **BSGS sieve** ([`sieve`]): with x = 2^n the forms are (x ∓ 1)² − 2,
+//!    so q divides one exactly when x ≡ ±1 + √2 (mod q). Only primes
+//!    q ≡ ±1 (mod 8) have √2; for those, each root's discrete log n0 to base
+//!    2 eliminates the progression n ≡ n0 (mod ord_q(2)).
 //!
 //! 2. **LLR test**: Uses `kbn::llr_test` for n ≤ 64 (k fits in u64) and
 //!    `llr_test_big` with `proof::lucas_v_big` for n > 64 (k exceeds u64).
 //!
+//! 3. **Morrison certificate**: N+1 = 2^(n+1)·(2^(n−1) ∓ 1) is known from the
+//!    closed form, and 2^(n+1) alone exceeds sqrt(N) ≈ 2^n. Primes with n too
+//!    small for LLR are certified with `proof::morrison_auto` over the
+//!    structural factor 2. LLR-proven primes are not re-proved: for n ≤ 64
+//!    they carry an `Llr` certificate, above that (k exceeds u64) none.
+//!
 //! ## Complexity
 //!
-//! - Sieve: O(π(L) · (max_n − min_n)) — linear scan per prime.
+//! - Sieve: O(π(L) · (√q + (max_n − min_n)/ord_q(2))) — half the primes
+//!   are skipped outright, the rest cost one BSGS per root.
 //! - LLR test: O((n+1) · M(n)) per survivor (n+1 = number of squarings).
 //!
 //! ## References
@@ -47,33 +55,33 @@ use std::time::Instant;
 
 use tracing::{debug, info};
 
+use crate::candidate::{CandidateForm, CarolKynea};
+use crate::certificate::PrimalityCertificate;
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::kbn;
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::proof;
 use crate::CoordinationClient;
 use crate::{exact_digits, mr_screened_test, sieve};
 
-/// Sieve Carol/Kynea candidates using incremental modular arithmetic.
+/// Sieve Carol (`plus = false`) or Kynea (`plus = true`) candidates over
+/// `min_n..=max_n`. Bit `n - min_n` of the result is set when n survives.
 ///
-/// Carol_n = (2^n - 1)^2 - 2 = 4^n - 2·2^n - 1
-/// Kynea_n = (2^n + 1)^2 - 2 = 4^n + 2·2^n - 1
+/// With x = 2^n, Carol_n = (x - 1)^2 - 2 and Kynea_n = (x + 1)^2 - 2, so a
+/// prime q divides the candidate exactly when x ≡ ±1 + s (mod q) for a square
+/// root s of 2 (Carol takes +1, Kynea -1). Only q ≡ ±1 (mod 8) have one. Each
+/// root r that is a power of 2 gives n0 = log_2 r by BSGS, and q then divides
+/// every n ≡ n0 (mod ord_q(2)), the same stepping [`kbn::bsgs_sieve`] uses.
 ///
-/// For each sieve prime q > 3, tracks g2 = 2^n mod q and g4 = 4^n mod q.
-/// Carol composite when g4 ≡ 2·g2 + 1 (mod q).
-/// Kynea composite when g4 + 2·g2 ≡ 1 (mod q).
-fn sieve_carol_kynea(
-    min_n: u64,
-    max_n: u64,
-    sieve_primes: &[u64],
-    sieve_min_n: u64,
-) -> (Vec<bool>, Vec<bool>) {
+/// n is never eliminated while the candidate could still equal a sieve prime
+/// (see [`sieve_min_n`]).
+pub fn sieve(min_n: u64, max_n: u64, plus: bool, sieve_primes: &[u64]) -> sieve::BitSieve {
     let range = (max_n - min_n + 1) as usize;
-    let mut carol_survives = vec![true; range];
-    let mut kynea_survives = vec![true; range];
+    let mut survives = sieve::BitSieve::new_all_set(range);
+    let sieve_min_n = sieve_min_n(sieve_primes.last().copied().unwrap_or(0));
 
     let total_primes = sieve_primes.len();
     let log_interval = (total_primes / 20).max(1);
@@ -81,39 +89,85 @@ fn sieve_carol_kynea(
     for (pi, &q) in sieve_primes.iter().enumerate() {
         if pi % log_interval == 0 && pi > 0 {
             let pct = pi as f64 / total_primes as f64 * 100.0;
-            debug!(pi, total_primes, pct, "Carol/Kynea sieve progress");
+            debug!(pi, total_primes, pct, plus, "Carol/Kynea sieve progress");
         }
 
-        // Carol and Kynea are never divisible by 2 or 3
-        if q <= 3 {
+        let Some(root) = sqrt_two_mod(q) else {
             continue;
+        };
+        let order = sieve::multiplicative_order(2, q);
+        let shift = if plus { q - 1 } else { 1 };
+        for r in [(shift + root) % q, (shift + q - root) % q] {
+            let Some(n0) = sieve::discrete_log_bsgs(2, r, q, order) else {
+                continue;
+            };
+            let start = min_n.max(sieve_min_n);
+            let mut n = if n0 >= start {
+                n0
+            } else {
+                n0 + (start - n0).div_ceil(order) * order
+            };
+            while n <= max_n {
+                survives.clear((n - min_n) as usize);
+                n += order;
+            }
         }
+    }
 
-        let mut g2 = sieve::pow_mod(2, min_n, q);
-        let mut g4 = sieve::pow_mod(4, min_n, q);
-
-        for n in min_n..=max_n {
-            if n >= sieve_min_n {
-                let idx = (n - min_n) as usize;
-                let two_g2 = 2 * g2 % q;
+    survives
+}
 
-                // Carol: g4 ≡ 2·g2 + 1 (mod q) means Carol_n divisible by q
-                if carol_survives[idx] && g4 == (two_g2 + 1) % q {
-                    carol_survives[idx] = false;
-                }
+/// Smallest n ≥ 2 with Carol_n = (2^n - 1)^2 - 2 above `limit`. Below it a
+/// candidate may itself be a sieve prime, so the sieve leaves it alone.
+fn sieve_min_n(limit: u64) -> u64 {
+    let mut n = 2u64;
+    while n <= 63 {
+        let two_n: u128 = 1 << n;
+        if (two_n - 1) * (two_n - 1) - 2 > limit as u128 {
+            break;
+        }
+        n += 1;
+    }
+    n
+}
 
-                // Kynea: g4 + 2·g2 ≡ 1 (mod q) means Kynea_n divisible by q
-                if kynea_survives[idx] && (g4 + two_g2) % q == 1 {
-                    kynea_survives[idx] = false;
-                }
-            }
+/// A square root of 2 modulo the odd prime `q` by Tonelli–Shanks, or `None`
+/// when 2 is a non-residue (q ≢ ±1 mod 8), in which case q divides neither
+/// form.
+fn sqrt_two_mod(q: u64) -> Option<u64> {
+    if q % 8 != 1 && q % 8 != 7 {
+        return None;
+    }
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % q as u128) as u64;
+    if q % 4 == 3 {
+        return Some(sieve::pow_mod(2, q.div_ceil(4), q));
+    }
 
-            g2 = 2 * g2 % q;
-            g4 = 4 * g4 % q;
+    // q - 1 = odd · 2^twos, with z a quadratic non-residue
+    let twos = (q - 1).trailing_zeros();
+    let odd = (q - 1) >> twos;
+    let z = (3..q).find(|&z| sieve::pow_mod(z, (q - 1) / 2, q) == q - 1)?;
+    let mut m = twos;
+    let mut c = sieve::pow_mod(z, odd, q);
+    let mut t = sieve::pow_mod(2, odd, q);
+    let mut root = sieve::pow_mod(2, odd.div_ceil(2), q);
+    while t != 1 {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != 1 {
+            t2 = mul(t2, t2);
+            i += 1;
         }
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = mul(b, b);
+        }
+        m = i;
+        c = mul(b, b);
+        t = mul(t, c);
+        root = mul(root, b);
     }
-
-    (carol_survives, kynea_survives)
+    Some(root)
 }
 
 /// LLR test for N = k·2^exp - 1 where k is an arbitrary-precision Integer.
@@ -145,93 +199,146 @@ fn llr_test_big(candidate: &Integer, k: &Integer, exp: u64) -> Option<bool> {
     Some(u == 0u32)
 }
 
+/// Structural factorization of N+1 for Carol (`plus = false`) or Kynea
+/// (`plus = true`): N+1 = 2^(n+1) · (2^(n-1) ∓ 1). Returns the power of two
+/// and the cofactor. Requires n ≥ 1.
+fn np1_structure(n: u64, plus: bool) -> (u64, Integer) {
+    let half = Integer::from(1u32) << crate::checked_u32(n - 1);
+    let cofactor = if plus { half + 1u32 } else { half - 1u32 };
+    (n + 1, cofactor)
+}
+
+/// Morrison N+1 certificate from the structural factorization of N+1.
+///
+/// The factor 2^(n+1) exceeds sqrt(N) + 1, so the single prime 2 satisfies
+/// Morrison's threshold and the cofactor never needs factoring.
+fn structural_certificate(candidate: &Integer, n: u64, plus: bool) -> Option<PrimalityCertificate> {
+    if n < 1 {
+        return None;
+    }
+    let (exp, cofactor) = np1_structure(n, plus);
+    debug_assert_eq!(
+        Integer::from(&cofactor << crate::checked_u32(exp)),
+        Integer::from(candidate + 1u32)
+    );
+    debug!(
+        n,
+        exp,
+        cofactor_bits = cofactor.significant_bits(),
+        "Carol/Kynea N+1 structure"
+    );
+    proof::morrison_auto(candidate, &[Integer::from(2u32)])
+}
+
 /// Test primality of a Carol number (2^n - 1)^2 - 2 using LLR.
 ///
 /// Carol_n = (2^(n-1) - 1) · 2^(n+1) - 1, so k = 2^(n-1) - 1 (odd for n ≥ 2), exp = n+1.
-fn test_carol(candidate: &Integer, n: u64, mr_rounds: u32) -> (IsPrime, &'static str) {
+/// Primes below the LLR range fall back to a Morrison certificate over the
+/// structural N+1 factorization.
+fn test_carol(
+    candidate: &Integer,
+    n: u64,
+    mr_rounds: u32,
+) -> (IsPrime, &'static str, Option<PrimalityCertificate>) {
     if n >= 2 {
         // Quick MR pre-screen (1 round) rejects ~75% of composites before
         // the expensive O(n-2) LLR squaring loop.
         if candidate.is_probably_prime(1) == IsPrime::No {
-            return (IsPrime::No, "");
+            return (IsPrime::No, "", None);
         }
         let exp = n + 1;
         if n <= 64 {
             let k = (1u64 << (n - 1)) - 1;
-            if let Some((result, _)) = kbn::llr_test(candidate, k, exp) {
+            if let Some((result, seed)) = kbn::llr_test(candidate, k, exp) {
                 return if result {
-                    (IsPrime::Yes, "deterministic (LLR)")
+                    let cert = PrimalityCertificate::Llr {
+                        k,
+                        n: exp,
+                        seed: seed.unwrap_or_default(),
+                    };
+                    (IsPrime::Yes, "deterministic (LLR)", Some(cert))
                 } else {
-                    (IsPrime::No, "")
+                    (IsPrime::No, "", None)
                 };
             }
         } else {
             let k = (Integer::from(1u32) << (n - 1) as u32) - 1u32;
             if let Some(result) = llr_test_big(candidate, &k, exp) {
                 return if result {
-                    (IsPrime::Yes, "deterministic (LLR)")
+                    (IsPrime::Yes, "deterministic (LLR)", None)
                 } else {
-                    (IsPrime::No, "")
+                    (IsPrime::No, "", None)
                 };
             }
         }
     }
 
+    // LLR does not apply (n too small); N+1 is still fully structural
+    if let Some(cert) = structural_certificate(candidate, n, false) {
+        return (IsPrime::Yes, "deterministic (Morrison N+1)", Some(cert));
+    }
+
     let r = mr_screened_test(candidate, mr_rounds);
-    let cert = match r {
-        IsPrime::Yes => "deterministic",
-        IsPrime::Probably => "probabilistic",
-        IsPrime::No => "",
-    };
-    (r, cert)
+    (r, crate::certainty_label(candidate, r), None)
 }
 
 /// Test primality of a Kynea number (2^n + 1)^2 - 2 using LLR.
 ///
 /// Kynea_n = (2^(n-1) + 1) · 2^(n+1) - 1, so k = 2^(n-1) + 1 (odd for n ≥ 2), exp = n+1.
-fn test_kynea(candidate: &Integer, n: u64, mr_rounds: u32) -> (IsPrime, &'static str) {
+/// Primes below the LLR range fall back to a Morrison certificate over the
+/// structural N+1 factorization.
+fn test_kynea(
+    candidate: &Integer,
+    n: u64,
+    mr_rounds: u32,
+) -> (IsPrime, &'static str, Option<PrimalityCertificate>) {
     if n >= 2 {
         // Quick MR pre-screen (1 round) rejects ~75% of composites before
         // the expensive O(n-2) LLR squaring loop.
         if candidate.is_probably_prime(1) == IsPrime::No {
-            return (IsPrime::No, "");
+            return (IsPrime::No, "", None);
         }
         let exp = n + 1;
         if n <= 64 {
             let k = (1u64 << (n - 1)) + 1;
-            if let Some((result, _)) = kbn::llr_test(candidate, k, exp) {
+            if let Some((result, seed)) = kbn::llr_test(candidate, k, exp) {
                 return if result {
-                    (IsPrime::Yes, "deterministic (LLR)")
+                    let cert = PrimalityCertificate::Llr {
+                        k,
+                        n: exp,
+                        seed: seed.unwrap_or_default(),
+                    };
+                    (IsPrime::Yes, "deterministic (LLR)", Some(cert))
                 } else {
-                    (IsPrime::No, "")
+                    (IsPrime::No, "", None)
                 };
             }
         } else {
             let k = (Integer::from(1u32) << (n - 1) as u32) + 1u32;
             if let Some(result) = llr_test_big(candidate, &k, exp) {
                 return if result {
-                    (IsPrime::Yes, "deterministic (LLR)")
+                    (IsPrime::Yes, "deterministic (LLR)", None)
                 } else {
-                    (IsPrime::No, "")
+                    (IsPrime::No, "", None)
                 };
             }
         }
     }
 
+    // LLR does not apply (n too small); N+1 is still fully structural
+    if let Some(cert) = structural_certificate(candidate, n, true) {
+        return (IsPrime::Yes, "deterministic (Morrison N+1)", Some(cert));
+    }
+
     let r = mr_screened_test(candidate, mr_rounds);
-    let cert = match r {
-        IsPrime::Yes => "deterministic",
-        IsPrime::Probably => "probabilistic",
-        IsPrime::No => "",
-    };
-    (r, cert)
+    (r, crate::certainty_label(candidate, r), None)
 }
 
 pub fn search(
     min_n: u64,
     max_n: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -240,6 +347,9 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params = serde_json::json!({"min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("carol_kynea", &params)?;
+
     // Resolve sieve_limit: auto-tune if 0
     // Carol/Kynea: (2^n ± 1)^2 - 2 has ~2*max_n bits
     let candidate_bits = 2 * max_n;
@@ -261,18 +371,7 @@ pub fn search(
         _ => min_n,
     };
 
-    // Minimum n where Carol_n = (2^n-1)^2-2 > sieve_limit
-    let sieve_min_n = {
-        let mut n = 2u64;
-        while n <= 63 {
-            let two_n: u128 = 1 << n;
-            if (two_n - 1) * (two_n - 1) - 2 > sieve_limit as u128 {
-                break;
-            }
-            n += 1;
-        }
-        n
-    };
+    let sieve_min_n = sieve_min_n(sieve_primes.last().copied().unwrap_or(0));
     info!(sieve_min_n, "sieve active");
 
     info!(
@@ -281,10 +380,10 @@ pub fn search(
         candidates = max_n - resume_from + 1,
         "running Carol/Kynea sieve"
     );
-    let (carol_survives, kynea_survives) =
-        sieve_carol_kynea(resume_from, max_n, &sieve_primes, sieve_min_n);
-    let carol_survivors: u64 = carol_survives.iter().filter(|&&b| b).count() as u64;
-    let kynea_survivors: u64 = kynea_survives.iter().filter(|&&b| b).count() as u64;
+    let carol_survives = sieve(resume_from, max_n, false, &sieve_primes);
+    let kynea_survives = sieve(resume_from, max_n, true, &sieve_primes);
+    let carol_survivors = carol_survives.count_ones() as u64;
+    let kynea_survivors = kynea_survives.count_ones() as u64;
     let total_range = max_n - resume_from + 1;
     info!(
         carol_survivors,
@@ -296,6 +395,7 @@ pub fn search(
     );
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut block_start = resume_from;
     let mut total_sieved: u64 = 0;
 
@@ -310,8 +410,8 @@ pub fn search(
         let survivors: Vec<(u64, bool, bool)> = (block_start..=block_end)
             .filter_map(|n| {
                 let idx = (n - resume_from) as usize;
-                let tc = carol_survives[idx] && n >= 2; // Carol invalid for n < 2
-                let tk = kynea_survives[idx];
+                let tc = carol_survives.get(idx) && n >= 2; // Carol invalid for n < 2
+                let tk = kynea_survives.get(idx);
                 if tc || tk {
                     Some((n, tc, tk))
                 } else {
@@ -321,15 +421,18 @@ pub fn search(
             .collect();
 
         total_sieved += block_len - survivors.len() as u64;
+        progress.record_survivors(survivors.iter().map(|s| s.0));
 
         let found_primes: Vec<_> = survivors
             .into_par_iter()
             .flat_map_iter(|(n, test_carol_flag, test_kynea_flag)| {
+                let _busy = crate::progress::busy();
                 let two_n = Integer::from(1u32) << crate::checked_u32(n);
 
                 let carol_result = if test_carol_flag {
                     let carol = Integer::from(&two_n - 1u32).pow(2) - 2u32;
-                    let expr = format!("(2^{}-1)^2-2", n);
+                    let expr = CarolKynea { plus: false }.expression(n);
+                    let _testing = progress.testing(&expr, &carol);
 
                     match pfgw::try_test(&expr, &carol, pfgw::PfgwMode::Prp) {
                         Some(pfgw::PfgwResult::Prime {
@@ -342,18 +445,21 @@ pub fn search(
                                 "probabilistic".to_string()
                             };
                             let digits = exact_digits(&carol);
-                            Some((expr, digits, cert, "carol"))
+                            Some((expr, digits, cert, None, "carol"))
                         }
                         Some(pfgw::PfgwResult::Composite) => None,
                         _ => {
                             // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                            if crate::p1::adaptive_p1_filter(&carol) {
+                            if crate::p1::adaptive_p1_filter_for_form(&carol, "carol_kynea") {
                                 None
                             } else {
-                                let (r, cert) = test_carol(&carol, n, mr_rounds);
+                                let (r, cert, certificate) = test_carol(&carol, n, mr_rounds);
                                 if r != IsPrime::No {
                                     let digits = exact_digits(&carol);
-                                    Some((expr, digits, cert.to_string(), "carol"))
+                                    let cert_json = certificate
+                                        .as_ref()
+                                        .and_then(|c| serde_json::to_string(c).ok());
+                                    Some((expr, digits, cert.to_string(), cert_json, "carol"))
                                 } else {
                                     None
                                 }
@@ -366,7 +472,8 @@ pub fn search(
 
                 let kynea_result = if test_kynea_flag {
                     let kynea = Integer::from(&two_n + 1u32).pow(2) - 2u32;
-                    let expr = format!("(2^{}+1)^2-2", n);
+                    let expr = CarolKynea { plus: true }.expression(n);
+                    let _testing = progress.testing(&expr, &kynea);
 
                     match pfgw::try_test(&expr, &kynea, pfgw::PfgwMode::Prp) {
                         Some(pfgw::PfgwResult::Prime {
@@ -379,18 +486,21 @@ pub fn search(
                                 "probabilistic".to_string()
                             };
                             let digits = exact_digits(&kynea);
-                            Some((expr, digits, cert, "kynea"))
+                            Some((expr, digits, cert, None, "kynea"))
                         }
                         Some(pfgw::PfgwResult::Composite) => None,
                         _ => {
                             // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                            if crate::p1::adaptive_p1_filter(&kynea) {
+                            if crate::p1::adaptive_p1_filter_for_form(&kynea, "carol_kynea") {
                                 None
                             } else {
-                                let (r, cert) = test_kynea(&kynea, n, mr_rounds);
+                                let (r, cert, certificate) = test_kynea(&kynea, n, mr_rounds);
                                 if r != IsPrime::No {
                                     let digits = exact_digits(&kynea);
-                                    Some((expr, digits, cert.to_string(), "kynea"))
+                                    let cert_json = certificate
+                                        .as_ref()
+                                        .and_then(|c| serde_json::to_string(c).ok());
+                                    Some((expr, digits, cert.to_string(), cert_json, "kynea"))
                                 } else {
                                     None
                                 }
@@ -407,7 +517,20 @@ pub fn search(
 
         progress.tested.fetch_add(block_len * 2, Ordering::Relaxed);
 
-        for (expr, digits, certainty, form) in found_primes {
+        for (expr, digits, certainty, cert_json, form) in found_primes {
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                form,
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -415,8 +538,14 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                // Projects track the combined form, so the record is kept
+                // under that key rather than the per-prime "carol"/"kynea"
+                eb.check_milestone("carol_kynea", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "carol_kynea").ok().flatten()
+                });
             } else {
                 info!(
                     form,
@@ -426,13 +555,20 @@ pub fn search(
                     "prime found"
                 );
             }
-            db.insert_prime_sync(rt, form, &expr, digits, search_params, &certainty, None)?;
-            if let Some(wc) = worker_client {
-                wc.report_prime(form, &expr, digits, search_params, &certainty);
-            }
+            buffer.push(
+                form,
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+                cert_json.as_deref(),
+            )?;
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::CarolKynea {
@@ -446,6 +582,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::CarolKynea {
@@ -461,6 +598,7 @@ pub fn search(
         block_start = block_end + 1;
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!(total_sieved, "Carol/Kynea search complete");
     Ok(())
@@ -541,7 +679,7 @@ mod tests {
     fn llr_proves_carol() {
         for &n in &[2u64, 3, 4, 6, 7, 10, 12, 15, 18, 19] {
             let c = carol(n);
-            let (r, cert) = test_carol(&c, n, 25);
+            let (r, cert, _) = test_carol(&c, n, 25);
             assert_eq!(r, IsPrime::Yes, "Carol({}) should be prime", n);
             assert!(
                 cert.contains("deterministic"),
@@ -557,7 +695,7 @@ mod tests {
         // Skip n=0,1 since LLR requires n >= 2 (exp >= 3)
         for &n in &[2u64, 3, 5, 8, 9, 12, 15, 17, 18, 21, 23] {
             let k = kynea(n);
-            let (r, cert) = test_kynea(&k, n, 25);
+            let (r, cert, _) = test_kynea(&k, n, 25);
             assert_eq!(r, IsPrime::Yes, "Kynea({}) should be prime", n);
             assert!(
                 cert.contains("deterministic"),
@@ -571,23 +709,14 @@ mod tests {
     #[test]
     fn sieve_correctly_eliminates() {
         let sieve_primes = sieve::generate_primes(10_000);
-        let sieve_min_n = {
-            let mut n = 2u64;
-            while n <= 63 {
-                let two_n: u128 = 1 << n;
-                if (two_n - 1) * (two_n - 1) - 2 > 10_000 {
-                    break;
-                }
-                n += 1;
-            }
-            n
-        };
+        let sieve_min_n = sieve_min_n(10_000);
 
-        let (carol_surv, kynea_surv) = sieve_carol_kynea(2, 200, &sieve_primes, sieve_min_n);
+        let carol_surv = super::sieve(2, 200, false, &sieve_primes);
+        let kynea_surv = super::sieve(2, 200, true, &sieve_primes);
 
         for n in sieve_min_n..=200 {
             let idx = (n - 2) as usize;
-            if !carol_surv[idx] {
+            if !carol_surv.get(idx) {
                 let c = carol(n);
                 assert_eq!(
                     c.is_probably_prime(15),
@@ -596,7 +725,7 @@ mod tests {
                     n
                 );
             }
-            if !kynea_surv[idx] {
+            if !kynea_surv.get(idx) {
                 let k = kynea(n);
                 assert_eq!(
                     k.is_probably_prime(15),
@@ -608,6 +737,54 @@ mod tests {
         }
     }
 
+    /// Every n the sieve eliminates over 1..300 is genuinely composite by
+    /// `mr_screened_test`, and the sieve misses nothing: above
+    /// `sieve_min_n`, n is eliminated exactly when some sieve prime divides
+    /// the candidate.
+    #[test]
+    fn sieve_eliminates_exactly_the_divisible_candidates() {
+        let sieve_primes = sieve::generate_primes(2_000);
+        let sieve_min_n = sieve_min_n(*sieve_primes.last().unwrap());
+        for plus in [false, true] {
+            let survives = super::sieve(1, 300, plus, &sieve_primes);
+            for n in 1..=300u64 {
+                let candidate = if plus { kynea(n) } else { carol(n) };
+                let eliminated = !survives.get((n - 1) as usize);
+                if eliminated {
+                    assert_eq!(
+                        mr_screened_test(&candidate, 15),
+                        IsPrime::No,
+                        "sieve eliminated prime n={} plus={}",
+                        n,
+                        plus
+                    );
+                }
+                let divisible = sieve_primes
+                    .iter()
+                    .any(|&q| candidate.is_divisible_u(q as u32));
+                assert_eq!(
+                    eliminated,
+                    divisible && n >= sieve_min_n,
+                    "n={} plus={}",
+                    n,
+                    plus
+                );
+            }
+        }
+    }
+
+    /// Square roots of 2 exist exactly for q ≡ ±1 (mod 8), covering both
+    /// Tonelli–Shanks branches (q ≡ 3 and q ≡ 1 mod 4).
+    #[test]
+    fn sqrt_two_mod_matches_residuosity() {
+        for &q in &sieve::generate_primes(5_000)[1..] {
+            match sqrt_two_mod(q) {
+                Some(s) => assert_eq!(s * s % q, 2, "q={}", q),
+                None => assert!(q % 8 == 3 || q % 8 == 5, "q={}", q),
+            }
+        }
+    }
+
     #[test]
     fn carol_kynea_decomposition() {
         // Verify k*2^exp - 1 decomposition for LLR
@@ -700,7 +877,7 @@ mod tests {
         // Carol(9) = (2^9-1)^2 - 2 = 511^2-2 = 261119 (composite)
         for &n in &[5u64, 8, 9] {
             let c = carol(n);
-            let (r, _) = test_carol(&c, n, 25);
+            let (r, _, _) = test_carol(&c, n, 25);
             assert_eq!(r, IsPrime::No, "Carol({}) = {} should be composite", n, c);
         }
     }
@@ -712,7 +889,7 @@ mod tests {
         // Kynea(7) = (2^7+1)^2 - 2 = 129^2-2 = 16639 (composite)
         for &n in &[4u64, 6, 7] {
             let k = kynea(n);
-            let (r, _) = test_kynea(&k, n, 25);
+            let (r, _, _) = test_kynea(&k, n, 25);
             assert_eq!(r, IsPrime::No, "Kynea({}) = {} should be composite", n, k);
         }
     }
@@ -737,4 +914,75 @@ mod tests {
             "LLR small-k and big-k paths should agree for Carol(10)"
         );
     }
+
+    #[test]
+    fn np1_structure_matches_closed_form() {
+        // N+1 = 2^(n+1) · (2^(n-1) ∓ 1) for both forms, including n=1
+        for n in 1u64..=70 {
+            for (plus, value) in [(false, carol(n)), (true, kynea(n))] {
+                let (exp, cofactor) = np1_structure(n, plus);
+                assert_eq!(exp, n + 1);
+                assert_eq!(
+                    Integer::from(&cofactor << crate::checked_u32(exp)),
+                    Integer::from(&value + 1u32),
+                    "N+1 structure wrong for n={} plus={}",
+                    n,
+                    plus
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn carol_llr_prime_gets_llr_certificate() {
+        // Carol(19) = 274876858367 (OEIS A091515): k = 2^18 - 1, exp = 20
+        let n = 19u64;
+        let (r, cert, certificate) = test_carol(&carol(n), n, 25);
+        assert_eq!(r, IsPrime::Yes);
+        assert_eq!(cert, "deterministic (LLR)");
+        match certificate {
+            Some(PrimalityCertificate::Llr { k, n, seed }) => {
+                assert_eq!(k, (1 << 18) - 1);
+                assert_eq!(n, 20);
+                assert!(!seed.is_empty());
+            }
+            other => panic!("expected LLR certificate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn kynea_prime_gets_deterministic_certificate() {
+        // Kynea(23) (OEIS A091514) is proved by LLR
+        let (r, cert, certificate) = test_kynea(&kynea(23), 23, 25);
+        assert_eq!(r, IsPrime::Yes);
+        assert_eq!(cert, "deterministic (LLR)");
+        assert!(matches!(
+            certificate,
+            Some(PrimalityCertificate::Llr { n: 24, .. })
+        ));
+
+        // Kynea(1) = 7 is below the LLR range and falls back to Morrison
+        let (r, cert, certificate) = test_kynea(&kynea(1), 1, 25);
+        assert_eq!(r, IsPrime::Yes);
+        assert_eq!(cert, "deterministic (Morrison N+1)");
+        match certificate {
+            Some(PrimalityCertificate::Morrison { factors, .. }) => {
+                assert_eq!(factors.len(), 1);
+                assert_eq!(factors[0].factor, "2");
+            }
+            other => panic!("expected Morrison certificate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn composites_get_no_certificate() {
+        for &n in &[5u64, 8, 9, 13, 16] {
+            let (_, _, certificate) = test_carol(&carol(n), n, 25);
+            assert!(certificate.is_none(), "Carol({}) is composite", n);
+        }
+        for &n in &[4u64, 6, 7] {
+            let (_, _, certificate) = test_kynea(&kynea(n), n, 25);
+            assert!(certificate.is_none(), "Kynea({}) is composite", n);
+        }
+    }
 }
@@ -18,6 +18,7 @@
 //!   Fermat numbers.
 //! - **MillerRabin**: Round count only (probabilistic, no deterministic witness).
 //! - **Pfgw** / **Prst**: Method string from external tool verification.
+//! - **Twin**: One certificate per member of a twin pair k·b^n ± 1.
 //!
 //! ## Serialization
 //!
@@ -25,6 +26,16 @@
 //! `#[serde(tag = "type")]` for the top-level enum so JSON includes a `"type"`
 //! discriminator field.
 //!
+//! ## Storage
+//!
+//! With `CERT_COMPRESS=1`, [`encode_for_storage`] gzips the JSON before it is
+//! written to `primes.certificate`, which pays off for Pocklington and BLS
+//! proofs with hundreds of witnesses. The column stays JSONB: a compressed
+//! certificate is stored as the JSON string `"gz1:<base64 gzip>"`, where the
+//! `gz1:` prefix names the encoding and its version. [`decode_stored`]
+//! inflates such strings and passes plaintext certificates through, so rows
+//! written before compression was enabled read back unchanged.
+//!
 //! ## References
 //!
 //! - François Proth, "Théorèmes sur les nombres premiers", 1878.
@@ -34,7 +45,14 @@
 //! - Brillhart, Lehmer, Selfridge, "New Primality Criteria and Factorizations
 //!   of 2^m ± 1", 1975.
 
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
 
 /// Exportable primality certificate containing witness data sufficient
 /// for independent verification without re-running the full test.
@@ -81,6 +99,20 @@ pub enum PrimalityCertificate {
 
     /// PRST external verification.
     Prst { method: String },
+
+    /// Twin pair k·b^n ± 1: a proof for each member, typically Proth or
+    /// Pocklington N−1 for the +1 side and LLR or Morrison N+1 for the −1 side.
+    Twin {
+        plus: Box<PrimalityCertificate>,
+        minus: Box<PrimalityCertificate>,
+    },
+
+    /// Sophie Germain pair p = k·b^n − 1 and 2p + 1: a proof for each member,
+    /// typically LLR for p and Morrison N+1 for the safe prime 2p + 1.
+    SophieGermain {
+        prime: Box<PrimalityCertificate>,
+        safe: Box<PrimalityCertificate>,
+    },
 }
 
 /// Witness for one prime factor in a Pocklington N−1 proof.
@@ -99,17 +131,76 @@ pub struct PocklingtonWitness {
 /// For prime factor `q` of `N+1` and Lucas parameter `P`, the witness confirms:
 /// - `V_{N+1}(P, 1) ≡ 2 (mod N)`
 /// - `gcd(V_{(N+1)/q}(P, 1) - 2, N) = 1`
+///
+/// Proofs with a general Lucas `Q` (see `proof::morrison_auto`) record it in
+/// `q_value`; the conditions become `U_{N+1}(P, Q) ≡ 0` and
+/// `gcd(U_{(N+1)/q}(P, Q), N) = 1`. Older certificates omit it and mean `Q = 1`.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct MorrisonWitness {
     pub factor: String,
     pub p_value: u32,
+    #[serde(default = "default_lucas_q")]
+    pub q_value: i32,
+}
+
+fn default_lucas_q() -> i32 {
+    1
+}
+
+/// Prefix of a compressed certificate string: format `gz1`, gzip + base64.
+pub const COMPRESSED_PREFIX: &str = "gz1:";
+
+/// Whether `CERT_COMPRESS` asks for certificates to be stored compressed.
+pub fn compression_enabled() -> bool {
+    std::env::var("CERT_COMPRESS")
+        .is_ok_and(|v| matches!(v.to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+}
+
+/// Certificate JSON as it should be bound to `primes.certificate`:
+/// [`compress`]ed when [`compression_enabled`], unchanged otherwise.
+pub fn encode_for_storage(json: &str) -> Result<String> {
+    if compression_enabled() {
+        compress(json)
+    } else {
+        Ok(json.to_string())
+    }
+}
+
+/// Gzip certificate JSON into a JSON string literal holding
+/// [`COMPRESSED_PREFIX`] and the base64 of the compressed bytes.
+pub fn compress(json: &str) -> Result<String> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(json.as_bytes())?;
+    let packed = COMPRESSED_PREFIX.to_string() + &STANDARD.encode(encoder.finish()?);
+    Ok(serde_json::to_string(&packed)?)
+}
+
+/// Certificate as read from `primes.certificate`, inflating a compressed
+/// value. Plaintext certificates are returned as they are.
+pub fn decode_stored(stored: serde_json::Value) -> Result<serde_json::Value> {
+    let packed = match stored.as_str() {
+        Some(s) if s.starts_with(COMPRESSED_PREFIX) => &s[COMPRESSED_PREFIX.len()..],
+        Some(s) if s.starts_with("gz") && s[2..].contains(':') => {
+            let encoding = s.split(':').next().unwrap();
+            bail!("unsupported certificate encoding {:?}", encoding)
+        }
+        _ => return Ok(stored),
+    };
+    let bytes = STANDARD
+        .decode(packed)
+        .context("compressed certificate is not base64")?;
+    let mut json = String::new();
+    GzDecoder::new(bytes.as_slice())
+        .read_to_string(&mut json)
+        .context("compressed certificate does not inflate")?;
+    Ok(serde_json::from_str(&json)?)
 }
 
 #[cfg(test)]
 mod tests {
     //! # Primality Certificate Serialization Tests
     //!
-    //! Validates the JSON serialization and deserialization of all 9
+    //! Validates the JSON serialization and deserialization of all 11
     //! `PrimalityCertificate` variants and their associated witness structs.
     //!
     //! Certificates are the exportable proof artifacts that allow independent
@@ -128,7 +219,7 @@ mod tests {
     //!
     //! - **Roundtrip tests**: Serialize to JSON, then deserialize and check equality
     //!   for every variant (Proth, Llr, Pocklington, Morrison, Bls, Pepin,
-    //!   MillerRabin, Pfgw, Prst).
+    //!   MillerRabin, Pfgw, Prst, Twin).
     //! - **Edge cases**: u32::MAX/u64::MAX field values, empty witness vectors,
     //!   empty method strings, zero MR rounds.
     //! - **Error handling**: Unknown `"type"` discriminator must fail deserialization.
@@ -208,6 +299,7 @@ mod tests {
             factors: vec![MorrisonWitness {
                 factor: "3".to_string(),
                 p_value: 5,
+                q_value: 1,
             }],
         };
         let json = serde_json::to_string(&cert).unwrap();
@@ -229,10 +321,12 @@ mod tests {
                 MorrisonWitness {
                     factor: "2".to_string(),
                     p_value: 7,
+                    q_value: 1,
                 },
                 MorrisonWitness {
                     factor: "5".to_string(),
                     p_value: 7,
+                    q_value: 1,
                 },
             ],
             factored_bits: 12,
@@ -244,9 +338,33 @@ mod tests {
         assert_eq!(cert, decoded);
     }
 
+    /// Twin certificate: nests one tagged certificate per member, so each
+    /// side keeps its own `"type"` discriminator inside `plus` / `minus`.
+    #[test]
+    fn certificate_json_roundtrip_twin() {
+        let cert = PrimalityCertificate::Twin {
+            plus: Box::new(PrimalityCertificate::Pocklington {
+                factors: vec![PocklingtonWitness {
+                    factor: "2".to_string(),
+                    base: 5,
+                }],
+            }),
+            minus: Box::new(PrimalityCertificate::Morrison {
+                p_value: 1,
+                factors: vec![],
+            }),
+        };
+        let json = serde_json::to_string(&cert).unwrap();
+        assert!(json.contains(r#""type":"Twin""#));
+        assert!(json.contains(r#""plus":{"type":"Pocklington""#), "{}", json);
+        assert!(json.contains(r#""minus":{"type":"Morrison""#), "{}", json);
+        let decoded: PrimalityCertificate = serde_json::from_str(&json).unwrap();
+        assert_eq!(cert, decoded);
+    }
+
     // ── Comprehensive Roundtrip Test ───────────────────────────────────
 
-    /// Exhaustive roundtrip test covering all 9 certificate variants in a single
+    /// Exhaustive roundtrip test covering all 11 certificate variants in a single
     /// loop. Each variant is serialized to JSON and deserialized back, verifying
     /// exact equality via the derived PartialEq. This catches any variant that
     /// might have been added to the enum but forgotten in the serde configuration.
@@ -278,6 +396,25 @@ mod tests {
             PrimalityCertificate::Prst {
                 method: "k=1*2^31-1".to_string(),
             },
+            PrimalityCertificate::Twin {
+                plus: Box::new(PrimalityCertificate::Proth { base: 3 }),
+                minus: Box::new(PrimalityCertificate::Llr {
+                    k: 3,
+                    n: 6,
+                    seed: "4".to_string(),
+                }),
+            },
+            PrimalityCertificate::SophieGermain {
+                prime: Box::new(PrimalityCertificate::Llr {
+                    k: 3,
+                    n: 3,
+                    seed: "4".to_string(),
+                }),
+                safe: Box::new(PrimalityCertificate::Morrison {
+                    p_value: 3,
+                    factors: vec![],
+                }),
+            },
         ];
 
         for cert in variants {
@@ -321,9 +458,9 @@ mod tests {
         let cert = PrimalityCertificate::Morrison {
             p_value: 11,
             factors: vec![
-                MorrisonWitness { factor: "2".to_string(), p_value: 11 },
-                MorrisonWitness { factor: "3".to_string(), p_value: 11 },
-                MorrisonWitness { factor: "5".to_string(), p_value: 11 },
+                MorrisonWitness { factor: "2".to_string(), p_value: 11, q_value: 1 },
+                MorrisonWitness { factor: "3".to_string(), p_value: 11, q_value: 1 },
+                MorrisonWitness { factor: "5".to_string(), p_value: 11, q_value: 1 },
             ],
         };
         let json = serde_json::to_string(&cert).unwrap();
@@ -341,7 +478,7 @@ mod tests {
         let cert = PrimalityCertificate::Bls {
             p_value: 5,
             factors: vec![
-                MorrisonWitness { factor: "2".to_string(), p_value: 5 },
+                MorrisonWitness { factor: "2".to_string(), p_value: 5, q_value: 1 },
             ],
             factored_bits: 100,
             total_bits: 200,
@@ -444,13 +581,21 @@ mod tests {
     /// divides N+1 and the Lucas parameter P=13 was used.
     #[test]
     fn morrison_witness_clone_and_debug() {
-        let w = MorrisonWitness { factor: "7".to_string(), p_value: 13 };
+        let w = MorrisonWitness { factor: "7".to_string(), p_value: 13, q_value: 1 };
         let cloned = w.clone();
         assert_eq!(w, cloned);
         let debug = format!("{:?}", w);
         assert!(debug.contains("MorrisonWitness"));
     }
 
+    /// Certificates stored before `q_value` existed deserialize with Q = 1,
+    /// the classical Morrison parameter.
+    #[test]
+    fn morrison_witness_missing_q_defaults_to_one() {
+        let w: MorrisonWitness = serde_json::from_str(r#"{"factor":"7","p_value":13}"#).unwrap();
+        assert_eq!(w.q_value, 1);
+    }
+
     // ── External Tool Certificates ─────────────────────────────────────
 
     /// PFGW certificate with an empty method string. While real PFGW certificates
@@ -504,4 +649,46 @@ mod tests {
             );
         }
     }
+
+    // ── Compressed Storage ────────────────────────────────────────────
+
+    /// A Pocklington certificate with 500 witnesses round-trips through
+    /// [`compress`] and [`decode_stored`]: the stored value is a JSON string
+    /// carrying the `gz1:` prefix, it is smaller than the plaintext, and it
+    /// inflates to exactly the original JSON.
+    #[test]
+    fn compressed_certificate_roundtrips() {
+        let cert = PrimalityCertificate::Pocklington {
+            factors: (0..500)
+                .map(|i| PocklingtonWitness {
+                    factor: format!("{}", 1_000_000_007u64 + 2 * i),
+                    base: 2 + (i % 7) as u32,
+                })
+                .collect(),
+        };
+        let json = serde_json::to_string(&cert).unwrap();
+        let stored = compress(&json).unwrap();
+        assert!(stored.len() < json.len() / 2, "{}", stored);
+
+        let value: serde_json::Value = serde_json::from_str(&stored).unwrap();
+        assert!(value.as_str().unwrap().starts_with(COMPRESSED_PREFIX));
+        let original: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let decoded = decode_stored(value).unwrap();
+        assert_eq!(decoded, original);
+        let restored: PrimalityCertificate = serde_json::from_value(decoded).unwrap();
+        assert_eq!(restored, cert);
+    }
+
+    /// Certificates written before compression existed are JSON objects and
+    /// read back unchanged; an unknown encoding version is an error rather
+    /// than garbage.
+    #[test]
+    fn legacy_and_unknown_stored_certificates() {
+        let legacy = serde_json::json!({"type": "Proth", "base": 3});
+        assert_eq!(decode_stored(legacy.clone()).unwrap(), legacy);
+
+        let future = serde_json::json!("gz9:AAAA");
+        let err = decode_stored(future).unwrap_err();
+        assert!(err.to_string().contains("gz9"), "{}", err);
+    }
 }
@@ -19,8 +19,31 @@
 //!
 //! One variant per search form (Factorial, Palindromic, Kbn, Primorial,
 //! CullenWoodall, Wagstaff, CarolKynea, Twin, SophieGermain, Repunit,
-//! GenFermat, NearRepdigit). Each stores the minimum state needed to
+//! GenFermat, NearRepdigit, Gaussian), plus KbnK for fixed-n kbn searches, which
+//! resume on k rather than n, and KbnC for kbn searches with an offset
+//! c ≠ ±1, which also record c. Each stores the minimum state needed to
 //! resume without re-sieving or re-computing intermediate values.
+//!
+//! Kbn checkpoints also carry the BSGS survivor bitmaps and the sieve limit
+//! they were built at ([`SieveState`]), so a resume with a raised
+//! `--sieve-limit` only sieves the additional primes.
+//!
+//! ## Format Versions
+//!
+//! The envelope records the format version ([`CHECKPOINT_VERSION`]); files
+//! written before versioning count as version 1. On load, older data is
+//! passed through the registered upgrade steps in order before it is
+//! deserialized, so a binary that changes a variant incompatibly still
+//! resumes from an old file instead of starting over. Additive changes need
+//! no step: new fields are `#[serde(default)]`. A file from a newer binary
+//! is skipped.
+//!
+//! ## Per-Type Files
+//!
+//! With `--checkpoint-dir`, each search type gets its own file,
+//! `<dir>/<search_type>.checkpoint` (see [`path_for`]), so runs of different
+//! forms never load each other's state. `save`/`load`/`clear` always take the
+//! resolved path.
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -32,6 +55,14 @@ use tracing::{info, warn};
 /// Number of backup generations to keep.
 const GENERATIONS: usize = 3;
 
+/// Format version written by [`save`].
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// `UPGRADES[i]` turns version `i + 1` data into version `i + 2`.
+const UPGRADES: &[fn(&mut serde_json::Value)] = &[];
+
+const _: () = assert!(UPGRADES.len() + 1 == CHECKPOINT_VERSION as usize);
+
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Checkpoint {
@@ -44,7 +75,11 @@ pub enum Checkpoint {
     },
     Palindromic {
         digit_count: u64,
+        /// Next half to test, as a position in `order`.
         half_value: String,
+        /// `--order` the walk was in; absent means "ascending".
+        #[serde(default)]
+        order: Option<String>,
         #[serde(default)]
         min_digits: Option<u64>,
         #[serde(default)]
@@ -56,6 +91,28 @@ pub enum Checkpoint {
         min_n: Option<u64>,
         #[serde(default)]
         max_n: Option<u64>,
+        #[serde(default)]
+        sieve: Option<SieveState>,
+    },
+    KbnC {
+        last_n: u64,
+        #[serde(default)]
+        c: Option<i64>,
+        #[serde(default)]
+        min_n: Option<u64>,
+        #[serde(default)]
+        max_n: Option<u64>,
+    },
+    KbnK {
+        last_k: u64,
+        #[serde(default)]
+        n: Option<u64>,
+        #[serde(default)]
+        base: Option<u32>,
+        #[serde(default)]
+        min_k: Option<u64>,
+        #[serde(default)]
+        max_k: Option<u64>,
     },
     NearRepdigit {
         digit_count: u64,
@@ -134,15 +191,122 @@ pub enum Checkpoint {
         #[serde(default)]
         max_base: Option<u64>,
     },
+    Gaussian {
+        last_a: u64,
+        #[serde(default)]
+        imag: Option<u64>,
+        #[serde(default)]
+        min_a: Option<u64>,
+        #[serde(default)]
+        max_a: Option<u64>,
+    },
+}
+
+impl Checkpoint {
+    /// The `search_type` tag of the search that wrote this checkpoint.
+    pub fn search_type(&self) -> &'static str {
+        match self {
+            Checkpoint::Factorial { .. } => "factorial",
+            Checkpoint::Palindromic { .. } => "palindromic",
+            Checkpoint::Kbn { .. } | Checkpoint::KbnC { .. } | Checkpoint::KbnK { .. } => "kbn",
+            Checkpoint::NearRepdigit { .. } => "near_repdigit",
+            Checkpoint::Primorial { .. } => "primorial",
+            Checkpoint::CullenWoodall { .. } => "cullen_woodall",
+            Checkpoint::Wagstaff { .. } => "wagstaff",
+            Checkpoint::CarolKynea { .. } => "carol_kynea",
+            Checkpoint::Twin { .. } => "twin",
+            Checkpoint::SophieGermain { .. } => "sophie_germain",
+            Checkpoint::Repunit { .. } => "repunit",
+            Checkpoint::GenFermat { .. } => "gen_fermat",
+            Checkpoint::Gaussian { .. } => "gaussian",
+        }
+    }
+
+    /// The last index the search finished, for forms that checkpoint in the
+    /// same index their work blocks range over. `None` for the digit-count
+    /// walks (palindromic, near-repdigit), which stop partway through one.
+    pub fn last_index(&self) -> Option<u64> {
+        match *self {
+            Checkpoint::Factorial { last_n, .. }
+            | Checkpoint::Kbn { last_n, .. }
+            | Checkpoint::KbnC { last_n, .. }
+            | Checkpoint::CullenWoodall { last_n, .. }
+            | Checkpoint::CarolKynea { last_n, .. }
+            | Checkpoint::Twin { last_n, .. }
+            | Checkpoint::SophieGermain { last_n, .. }
+            | Checkpoint::Repunit { last_n, .. } => Some(last_n),
+            Checkpoint::KbnK { last_k, .. } => Some(last_k),
+            Checkpoint::Primorial { last_prime, .. } => Some(last_prime),
+            Checkpoint::Wagstaff { last_exp, .. } => Some(last_exp),
+            Checkpoint::GenFermat { last_base, .. } => Some(last_base),
+            Checkpoint::Gaussian { last_a, .. } => Some(last_a),
+            Checkpoint::Palindromic { .. } | Checkpoint::NearRepdigit { .. } => None,
+        }
+    }
+
+    /// The checkpoint's fields as JSON plus its `search_type`, for display.
+    ///
+    /// Kbn survivor bitmaps are replaced by their `sieve_limit`; they can run
+    /// to megabytes and say nothing about where the search stands.
+    pub fn summary(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = value.as_object_mut() {
+            if let Some(sieve) = fields.remove("sieve").filter(|s| !s.is_null()) {
+                fields.insert("sieve_limit".into(), sieve["sieve_limit"].clone());
+            }
+            fields.insert("search_type".into(), self.search_type().into());
+        }
+        value
+    }
+}
+
+/// BSGS survivor bitmaps saved with a kbn checkpoint.
+///
+/// Lets a resume skip the sieve entirely, or — when `--sieve-limit` was
+/// raised — sieve only the primes above `sieve_limit`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SieveState {
+    /// Every prime up to this bound has been applied.
+    pub sieve_limit: u64,
+    /// n of bit 0; the bitmaps run to the search's `max_n`.
+    pub min_n: u64,
+    /// Number of candidates (bits) per bitmap.
+    pub len: usize,
+    /// k·b^n+1 survivors, as [`crate::sieve::BitSieve::to_base64`].
+    pub plus: String,
+    /// k·b^n−1 survivors.
+    pub minus: String,
 }
 
 /// Wrapper that includes a SHA-256 checksum for integrity verification.
 #[derive(Serialize, Deserialize)]
 struct CheckpointEnvelope {
+    /// Format version of `data`; absent before versioning (version 1).
+    #[serde(default = "unversioned")]
+    version: u32,
     checksum: String,
     data: serde_json::Value,
 }
 
+fn unversioned() -> u32 {
+    1
+}
+
+/// Bring `data` written at `version` up to [`CHECKPOINT_VERSION`].
+fn upgrade(version: u32, mut data: serde_json::Value) -> Option<serde_json::Value> {
+    if version == 0 || version > CHECKPOINT_VERSION {
+        warn!(version, "Checkpoint format not supported by this binary");
+        return None;
+    }
+    for step in &UPGRADES[version as usize - 1..] {
+        step(&mut data);
+    }
+    if version < CHECKPOINT_VERSION {
+        info!(from = version, "Upgraded checkpoint to the current format");
+    }
+    Some(data)
+}
+
 /// Compute SHA-256 hex digest of a string.
 fn sha256_hex(data: &str) -> String {
     let mut hasher = Sha256::new();
@@ -161,6 +325,11 @@ fn generation_path(base: &Path, gen: usize) -> PathBuf {
     }
 }
 
+/// Checkpoint file for `search_type` inside a `--checkpoint-dir`.
+pub fn path_for(dir: &Path, search_type: &str) -> PathBuf {
+    dir.join(format!("{}.checkpoint", search_type))
+}
+
 /// Save a checkpoint with integrity checksum and rotating generations.
 ///
 /// Rotation: current → .1 → .2 (oldest .2 is discarded).
@@ -180,7 +349,11 @@ pub fn save(path: &Path, checkpoint: &Checkpoint) -> Result<()> {
     let data_str = serde_json::to_string_pretty(&data)?;
     let checksum = sha256_hex(&data_str);
 
-    let envelope = CheckpointEnvelope { checksum, data };
+    let envelope = CheckpointEnvelope {
+        version: CHECKPOINT_VERSION,
+        checksum,
+        data,
+    };
     let json = serde_json::to_string_pretty(&envelope)?;
 
     // Atomic write: write to .tmp then rename
@@ -209,7 +382,8 @@ pub fn load(path: &Path) -> Option<Checkpoint> {
 
     // Legacy fallback: try loading without envelope (pre-hardening checkpoints)
     let data = fs::read_to_string(path).ok()?;
-    let cp: Checkpoint = serde_json::from_str(&data).ok()?;
+    let data = upgrade(1, serde_json::from_str(&data).ok()?)?;
+    let cp: Checkpoint = serde_json::from_value(data).ok()?;
     info!("Loaded legacy checkpoint (no checksum)");
     Some(cp)
 }
@@ -232,7 +406,8 @@ fn load_single(path: &Path) -> Option<Checkpoint> {
         return None;
     }
 
-    serde_json::from_value(envelope.data).ok()
+    let data = upgrade(envelope.version, envelope.data)?;
+    serde_json::from_value(data).ok()
 }
 
 /// Clear all checkpoint files (current + all generations).
@@ -362,6 +537,7 @@ mod tests {
                 last_n: 100,
                 min_n: Some(1),
                 max_n: Some(1000),
+                sieve: None,
             },
         )
         .unwrap();
@@ -371,6 +547,7 @@ mod tests {
                 last_n: 200,
                 min_n: Some(1),
                 max_n: Some(1000),
+                sieve: None,
             },
         )
         .unwrap();
@@ -416,6 +593,63 @@ mod tests {
         }
     }
 
+    /// A file written before versioning and before palindromic checkpoints
+    /// recorded their order loads as-is, as an ascending walk.
+    #[test]
+    fn v1_checkpoint_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let v1 = r#"{
+  "checksum": "3a8b55f1f04a06b4fdb7b07d44e3827028871013f243cab9017b864444462b2f",
+  "data": {
+    "digit_count": 7,
+    "half_value": "1234",
+    "max_digits": 9,
+    "min_digits": 1,
+    "type": "Palindromic"
+  }
+}"#;
+        fs::write(&path, v1).unwrap();
+
+        match load(&path) {
+            Some(Checkpoint::Palindromic {
+                digit_count,
+                half_value,
+                order,
+                max_digits,
+                ..
+            }) => {
+                assert_eq!(digit_count, 7);
+                assert_eq!(half_value, "1234");
+                assert_eq!(order, None);
+                assert_eq!(max_digits, Some(9));
+            }
+            _ => panic!("v1 checkpoint did not load"),
+        }
+
+        // Saving again records the version.
+        save(&path, &load(&path).unwrap()).unwrap();
+        let raw = fs::read_to_string(&path).unwrap();
+        let raw: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(raw["version"], CHECKPOINT_VERSION);
+    }
+
+    /// Data from a newer binary is not guessed at.
+    #[test]
+    fn future_checkpoint_version_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let data = serde_json::json!({ "type": "Kbn", "last_n": 10 });
+        let checksum = sha256_hex(&serde_json::to_string_pretty(&data).unwrap());
+        let envelope = serde_json::json!({
+            "version": CHECKPOINT_VERSION + 1,
+            "checksum": checksum,
+            "data": data,
+        });
+        fs::write(&path, envelope.to_string()).unwrap();
+        assert!(load(&path).is_none());
+    }
+
     // ── All-Variants Exhaustive ──────────────────────────────────
 
     /// Exhaustive round-trip test for all 12 checkpoint variants. Each form
@@ -439,6 +673,7 @@ mod tests {
                 Checkpoint::Palindromic {
                     digit_count: 7,
                     half_value: "1234".into(),
+                    order: Some("minimal-change".into()),
                     min_digits: Some(1),
                     max_digits: Some(99),
                 },
@@ -449,6 +684,7 @@ mod tests {
                     last_n: 500,
                     min_n: Some(1),
                     max_n: Some(1000),
+                    sieve: None,
                 },
             ),
             (
@@ -531,6 +767,15 @@ mod tests {
                     max_base: Some(10000),
                 },
             ),
+            (
+                "gaussian",
+                Checkpoint::Gaussian {
+                    last_a: 24,
+                    imag: Some(1),
+                    min_a: Some(1),
+                    max_a: Some(1000),
+                },
+            ),
         ];
 
         for (name, cp) in &variants {
@@ -666,6 +911,62 @@ mod tests {
         assert_eq!(generation_path(base, 2), PathBuf::from("/tmp/checkpoint.json.2"));
     }
 
+    /// Each search type resolves to its own file inside the checkpoint dir.
+    #[test]
+    fn path_for_is_per_search_type() {
+        let dir = Path::new("/var/lib/darkreach");
+        assert_eq!(
+            path_for(dir, "factorial"),
+            PathBuf::from("/var/lib/darkreach/factorial.checkpoint")
+        );
+        assert_ne!(path_for(dir, "factorial"), path_for(dir, "kbn"));
+    }
+
+    /// Two search types sharing a checkpoint dir write distinct files, and
+    /// loading one type's path never yields the other's variant.
+    #[test]
+    fn per_type_checkpoints_do_not_collide() {
+        let dir = tempfile::tempdir().unwrap();
+        let factorial_path = path_for(dir.path(), "factorial");
+        let kbn_path = path_for(dir.path(), "kbn");
+
+        save(
+            &factorial_path,
+            &Checkpoint::Factorial {
+                last_n: 500,
+                start: Some(1),
+                end: Some(1000),
+            },
+        )
+        .unwrap();
+        save(
+            &kbn_path,
+            &Checkpoint::Kbn {
+                last_n: 77,
+                min_n: Some(1),
+                max_n: Some(100),
+                sieve: None,
+            },
+        )
+        .unwrap();
+        assert!(factorial_path.exists());
+        assert!(kbn_path.exists());
+
+        assert!(matches!(
+            load(&factorial_path),
+            Some(Checkpoint::Factorial { last_n: 500, .. })
+        ));
+        assert!(matches!(
+            load(&kbn_path),
+            Some(Checkpoint::Kbn { last_n: 77, .. })
+        ));
+
+        // Clearing one type leaves the other intact
+        clear(&factorial_path);
+        assert!(load(&factorial_path).is_none());
+        assert!(matches!(load(&kbn_path), Some(Checkpoint::Kbn { .. })));
+    }
+
     // ── Edge Cases ──────────────────────────────────────────────
 
     /// Loading a nonexistent file must return None (fresh search, no resume).
@@ -727,6 +1028,7 @@ mod tests {
                 last_n: 500,
                 min_n: Some(1),
                 max_n: Some(1000),
+                sieve: None,
             },
         )
         .unwrap();
@@ -872,4 +1174,36 @@ mod tests {
         clear(&path);
         assert!(!tmp.exists(), ".tmp file should be removed by clear()");
     }
+
+    /// The summary names the search type and drops the survivor bitmaps.
+    #[test]
+    fn summary_reports_search_type_without_bitmaps() {
+        let cp = Checkpoint::Kbn {
+            last_n: 500,
+            min_n: Some(1),
+            max_n: Some(1000),
+            sieve: Some(SieveState {
+                sieve_limit: 1_000_000,
+                min_n: 1,
+                len: 1000,
+                plus: "AAAA".into(),
+                minus: "AAAA".into(),
+            }),
+        };
+        let summary = cp.summary();
+        assert_eq!(summary["search_type"], "kbn");
+        assert_eq!(summary["type"], "Kbn");
+        assert_eq!(summary["last_n"], 500);
+        assert_eq!(summary["sieve_limit"], 1_000_000);
+        assert!(summary.get("sieve").is_none());
+
+        let unsieved = Checkpoint::Kbn {
+            last_n: 7,
+            min_n: None,
+            max_n: None,
+            sieve: None,
+        };
+        assert!(unsieved.summary().get("sieve").is_none());
+        assert!(unsieved.summary().get("sieve_limit").is_none());
+    }
 }
@@ -6,22 +6,36 @@
 
 use anyhow::Result;
 use darkreach::{
-    carol_kynea, cullen_woodall, db, events, factorial, gen_fermat, kbn, near_repdigit,
-    palindromic, pg_worker, primorial, progress, project, repunit, sophie_germain, twin, verify,
-    wagstaff, CoordinationClient,
+    carol_kynea, checkpoint, cullen_woodall, db, events, factorial, gaussian, gen_fermat, kbn,
+    near_repdigit, palindromic, pg_worker, prime_sink, primorial, progress, project, repunit,
+    search_params, sophie_germain, truncate_display, twin, verify, wagstaff, CoordinationClient,
 };
+use prime_sink::PrimeSink;
 use std::sync::Arc;
 use tracing::{info, info_span, warn};
 
-use super::{Cli, Commands, ProjectAction};
+use super::{Cli, Commands, DiagnoseCheck, ProjectAction};
 
 // ── Search Dispatch ─────────────────────────────────────────────
 
 /// Run a search subcommand (all 12 forms). Handles DB connection, worker
 /// coordination, search dispatch, progress reporting, and cleanup.
+///
+/// With `--output-file` and no database URL, runs offline instead: results
+/// go to a JSONL file and no coordination client is registered.
 pub fn run_search(cli: &Cli) -> Result<()> {
+    match (&cli.database_url, &cli.output_file) {
+        (None, Some(path)) => return run_offline_search(cli, path),
+        (Some(_), Some(path)) => warn!(
+            output_file = %path.display(),
+            "--output-file ignored because a database URL is set"
+        ),
+        _ => {}
+    }
     let database_url = cli.database_url.as_deref().ok_or_else(|| {
-        anyhow::anyhow!("DATABASE_URL is required (set via --database-url or env)")
+        anyhow::anyhow!(
+            "DATABASE_URL is required (set via --database-url or env, or use --output-file)"
+        )
     })?;
 
     let num_cores = rayon::current_num_threads();
@@ -43,7 +57,6 @@ pub fn run_search(cli: &Cli) -> Result<()> {
 
     let progress = progress::Progress::new();
     let reporter_handle = progress.start_reporter();
-    let event_bus = Arc::new(events::EventBus::new());
 
     let search_type = search_type_for(&cli.command);
     let search_params = search_params_for(&cli.command);
@@ -59,34 +72,116 @@ pub fn run_search(cli: &Cli) -> Result<()> {
         &search_params,
     );
 
-    sync_progress_to_atomics(&progress, &pg_client.tested, &pg_client.found, &pg_client.current);
+    sync_progress_to_atomics(
+        &progress,
+        &pg_client.tested,
+        &pg_client.found,
+        &pg_client.current,
+        &pg_client.current_candidate,
+    );
 
     let heartbeat_handle = Some(pg_client.start_heartbeat());
 
     let coord: Option<&dyn CoordinationClient> = Some(&pg_client);
 
-    let mr = cli.mr_rounds;
-    let sl = cli.sieve_limit;
-    let eb = Some(event_bus.as_ref() as &events::EventBus);
+    let result = run_with_events(
+        cli,
+        &progress,
+        db.as_ref(),
+        &rt_handle,
+        search_type,
+        &search_params,
+        coord,
+    );
+
+    progress.stop();
+    let _ = reporter_handle.join();
+    progress.print_status();
+
+    pg_client.deregister();
+    if let Some(handle) = heartbeat_handle {
+        let _ = handle.join();
+    }
+
+    info!("Search complete");
+    result
+}
+
+/// Run a search offline, appending found primes to a JSONL file at `path`.
+///
+/// No database connection is made: there is no heartbeat, stop-check, or
+/// stored record history, and the `work` subcommand is unavailable.
+fn run_offline_search(cli: &Cli, path: &std::path::Path) -> Result<()> {
+    if let Commands::Work { .. } = &cli.command {
+        anyhow::bail!("the work subcommand requires DATABASE_URL");
+    }
+    let sink = prime_sink::FilePrimeSink::open(path)?;
+
+    info!(
+        cores = rayon::current_num_threads(),
+        mr_rounds = cli.mr_rounds,
+        output_file = %sink.path().display(),
+        "darkreach starting (offline)"
+    );
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let progress = progress::Progress::new();
+    let reporter_handle = progress.start_reporter();
+
+    let search_type = search_type_for(&cli.command);
+    let search_params = search_params_for(&cli.command);
+
+    let result = run_with_events(
+        cli,
+        &progress,
+        &sink,
+        rt.handle(),
+        search_type,
+        &search_params,
+        None,
+    );
+
+    progress.stop();
+    let _ = reporter_handle.join();
+    progress.print_status();
+
+    info!("Search complete");
+    result
+}
+
+/// Dispatch the search bracketed by `SearchStarted` / `SearchCompleted` events.
+fn run_with_events(
+    cli: &Cli,
+    progress: &Arc<progress::Progress>,
+    sink: &dyn PrimeSink,
+    rt_handle: &tokio::runtime::Handle,
+    search_type: &str,
+    search_params: &str,
+    coord: Option<&dyn CoordinationClient>,
+) -> Result<()> {
+    let event_bus = events::EventBus::new();
+    let search_job_id = coord.and_then(|c| c.search_job_id());
 
     event_bus.emit(events::Event::SearchStarted {
         search_type: search_type.to_string(),
-        params: search_params.clone(),
+        params: search_params.to_string(),
+        search_job_id,
         timestamp: std::time::Instant::now(),
     });
 
+    let checkpoint_path = checkpoint_path_for(cli, search_type)?;
     let search_start = std::time::Instant::now();
     let result = dispatch_search(
         &cli.command,
-        &progress,
-        &db,
-        &rt_handle,
-        &cli.checkpoint,
-        &search_params,
-        mr,
-        sl,
+        progress,
+        sink,
+        rt_handle,
+        &checkpoint_path,
+        search_params,
+        cli.mr_rounds,
+        cli.sieve_limit,
         coord,
-        eb,
+        Some(&event_bus),
     );
 
     event_bus.emit(events::Event::SearchCompleted {
@@ -94,20 +189,11 @@ pub fn run_search(cli: &Cli) -> Result<()> {
         tested: progress.tested.load(std::sync::atomic::Ordering::Relaxed),
         found: progress.found.load(std::sync::atomic::Ordering::Relaxed),
         elapsed_secs: search_start.elapsed().as_secs_f64(),
+        search_job_id,
         timestamp: std::time::Instant::now(),
     });
     event_bus.flush();
 
-    progress.stop();
-    let _ = reporter_handle.join();
-    progress.print_status();
-
-    pg_client.deregister();
-    if let Some(handle) = heartbeat_handle {
-        let _ = handle.join();
-    }
-
-    info!("Search complete");
     result
 }
 
@@ -126,24 +212,42 @@ fn search_type_for(cmd: &Commands) -> &'static str {
         Commands::SophieGermain { .. } => "sophie_germain",
         Commands::Repunit { .. } => "repunit",
         Commands::GenFermat { .. } => "gen_fermat",
+        Commands::Gaussian { .. } => "gaussian",
         Commands::Dashboard { .. }
         | Commands::Work { .. }
+        | Commands::Migrate { .. }
         | Commands::Verify { .. }
+        | Commands::Test { .. }
+        | Commands::Diagnose { .. }
         | Commands::Project { .. }
         | Commands::Register { .. }
-        | Commands::Run => {
+        | Commands::Run { .. } => {
             unreachable!()
         }
     }
 }
 
+/// Resolve the checkpoint file for `search_type`.
+///
+/// With `--checkpoint-dir` this is `<dir>/<search_type>.checkpoint` (the
+/// directory is created if missing); otherwise the single `--checkpoint` path.
+fn checkpoint_path_for(cli: &Cli, search_type: &str) -> Result<std::path::PathBuf> {
+    match &cli.checkpoint_dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)?;
+            Ok(checkpoint::path_for(dir, search_type))
+        }
+        None => Ok(cli.checkpoint.clone()),
+    }
+}
+
 /// Serialize command parameters to a JSON search_params string.
 fn search_params_for(cmd: &Commands) -> String {
     match cmd {
         Commands::Factorial { start, end } => {
             serde_json::json!({"form": "factorial", "start": start, "end": end}).to_string()
         }
-        Commands::Palindromic { base, min_digits, max_digits } => serde_json::json!({
+        Commands::Palindromic { base, min_digits, max_digits, .. } => serde_json::json!({
             "form": "palindromic", "base": base, "min_digits": min_digits, "max_digits": max_digits
         }).to_string(),
         Commands::Kbn { k, base, min_n, max_n } => serde_json::json!({
@@ -176,12 +280,18 @@ fn search_params_for(cmd: &Commands) -> String {
         Commands::GenFermat { fermat_exp, min_base, max_base } => serde_json::json!({
             "form": "gen_fermat", "fermat_exp": fermat_exp, "min_base": min_base, "max_base": max_base
         }).to_string(),
+        Commands::Gaussian { imag, min_real, max_real } => serde_json::json!({
+            "form": "gaussian", "imag": imag, "min_real": min_real, "max_real": max_real
+        }).to_string(),
         Commands::Dashboard { .. }
         | Commands::Work { .. }
+        | Commands::Migrate { .. }
         | Commands::Verify { .. }
+        | Commands::Test { .. }
+        | Commands::Diagnose { .. }
         | Commands::Project { .. }
         | Commands::Register { .. }
-        | Commands::Run => {
+        | Commands::Run { .. } => {
             unreachable!()
         }
     }
@@ -191,7 +301,7 @@ fn search_params_for(cmd: &Commands) -> String {
 fn dispatch_search(
     cmd: &Commands,
     progress: &Arc<progress::Progress>,
-    db: &Arc<db::Database>,
+    db: &dyn PrimeSink,
     rt_handle: &tokio::runtime::Handle,
     checkpoint_path: &std::path::Path,
     search_params: &str,
@@ -200,6 +310,7 @@ fn dispatch_search(
     coord: Option<&dyn CoordinationClient>,
     eb: Option<&events::EventBus>,
 ) -> Result<()> {
+    let mr = search_params::effective_mr_rounds(search_type_for(cmd), mr);
     match cmd {
         Commands::Factorial { start, end } => factorial::search(
             *start,
@@ -218,10 +329,12 @@ fn dispatch_search(
             base,
             min_digits,
             max_digits,
+            order,
         } => palindromic::search(
             *base,
             *min_digits,
             *max_digits,
+            *order,
             progress,
             db,
             rt_handle,
@@ -392,12 +505,33 @@ fn dispatch_search(
             coord,
             eb,
         ),
+        Commands::Gaussian {
+            imag,
+            min_real,
+            max_real,
+        } => gaussian::search(
+            *imag,
+            *min_real,
+            *max_real,
+            progress,
+            db,
+            rt_handle,
+            checkpoint_path,
+            search_params,
+            mr,
+            sl,
+            coord,
+            eb,
+        ),
         Commands::Dashboard { .. }
         | Commands::Work { .. }
+        | Commands::Migrate { .. }
         | Commands::Verify { .. }
+        | Commands::Test { .. }
+        | Commands::Diagnose { .. }
         | Commands::Project { .. }
         | Commands::Register { .. }
-        | Commands::Run => {
+        | Commands::Run { .. } => {
             unreachable!()
         }
     }
@@ -409,10 +543,12 @@ fn sync_progress_to_atomics(
     tested: &Arc<std::sync::atomic::AtomicU64>,
     found: &Arc<std::sync::atomic::AtomicU64>,
     current: &Arc<std::sync::Mutex<String>>,
+    current_candidate: &Arc<std::sync::Mutex<pg_worker::CurrentCandidate>>,
 ) {
     let wc_tested = Arc::clone(tested);
     let wc_found = Arc::clone(found);
     let wc_current = Arc::clone(current);
+    let wc_candidate = Arc::clone(current_candidate);
     let progress_ref = Arc::clone(progress);
     std::thread::spawn(move || loop {
         std::thread::sleep(std::time::Duration::from_secs(5));
@@ -429,6 +565,10 @@ fn sync_progress_to_atomics(
             std::sync::atomic::Ordering::Relaxed,
         );
         *wc_current.lock().unwrap() = progress_ref.current.lock().unwrap().clone();
+        *wc_candidate.lock().unwrap() = progress_ref.current_candidate().map(|(label, elapsed)| {
+            let elapsed = chrono::Duration::from_std(elapsed).unwrap_or_default();
+            (label, chrono::Utc::now() - elapsed)
+        });
     });
 }
 
@@ -446,6 +586,11 @@ fn get_hostname() -> String {
 // ── Work Loop ───────────────────────────────────────────────────
 
 /// Block-claiming work loop for the `work` subcommand.
+///
+/// SIGINT/SIGTERM and a dashboard stop end the loop the same way: the
+/// current DB write finishes, the interrupted block's checkpoint is stored
+/// (see [`pg_worker::release_checkpoint`]), and it plus any still queued are
+/// released (see [`pg_worker::BlockOutcome`]).
 pub fn run_work_loop(
     cli: &Cli,
     db: &Arc<db::Database>,
@@ -473,7 +618,8 @@ pub fn run_work_loop(
         &worker_id,
         &job.search_type,
         &search_params_str,
-    );
+    )
+    .with_search_job(search_job_id);
 
     let progress = progress::Progress::new();
     let reporter_handle = progress.start_reporter();
@@ -483,17 +629,31 @@ pub fn run_work_loop(
         &pg_client.tested,
         &pg_client.found,
         &pg_client.current,
+        &pg_client.current_candidate,
     );
 
     let heartbeat_handle = pg_client.start_heartbeat();
     let coord: Option<&dyn CoordinationClient> = Some(&pg_client);
+    // The coordinator only persists its own bus, so the worker writes the
+    // events its searches emit to `system_logs` itself.
+    let event_bus = events::EventBus::new();
+    let mut last_event_id = 0u64;
+    let stop = Arc::clone(&pg_client.stop_requested);
+    if let Err(e) = pg_worker::stop_on_signal(rt_handle, stop) {
+        warn!(error = %e, "Could not install signal handler");
+    }
 
     let mr = cli.mr_rounds;
     let sl = cli.sieve_limit;
+    let checkpoint_path = checkpoint_path_for(cli, &job.search_type)?;
+    let memory_guard = pg_worker::MemoryGuard::new(cli.min_free_mem_mb);
+    let mut memory_waits = 0u32;
     let mut blocks_completed = 0u64;
     let batch_size = 5;
     let mut pending_blocks: std::collections::VecDeque<db::WorkBlockWithCheckpoint> =
         std::collections::VecDeque::new();
+    // (block_id, tested, found, block_checkpoint) of a block stopped early.
+    let mut interrupted: Option<(i64, i64, i64, serde_json::Value)> = None;
 
     loop {
         if pg_client.is_stop_requested() {
@@ -514,7 +674,50 @@ pub fn run_work_loop(
             pending_blocks.extend(blocks);
         }
 
-        let block = pending_blocks.pop_front().unwrap();
+        // Blocks too big for the memory left wait at the back of the queue.
+        let block = match &memory_guard {
+            None => pending_blocks.pop_front().unwrap(),
+            Some(guard) => {
+                let available = pg_worker::MemoryGuard::available_memory();
+                let fits = |b: &db::WorkBlockWithCheckpoint| {
+                    let end = b.block_end as u64;
+                    let digits = project::estimate_digits_for_form(&job.search_type, end);
+                    let refusal = guard.refusal(digits, available);
+                    if let Some(reason) = &refusal {
+                        warn!(block_id = b.block_id, %reason, "Deferring block, low memory");
+                    }
+                    refusal.is_none()
+                };
+                match pg_worker::next_fitting(&mut pending_blocks, fits) {
+                    Some(block) => {
+                        memory_waits = 0;
+                        block
+                    }
+                    None if memory_waits < pg_worker::MEMORY_RETRY_PASSES => {
+                        memory_waits += 1;
+                        info!(
+                            queued = pending_blocks.len(),
+                            attempt = memory_waits,
+                            "No queued block fits in memory, waiting"
+                        );
+                        let deadline =
+                            std::time::Instant::now() + pg_worker::MEMORY_RETRY_INTERVAL;
+                        while std::time::Instant::now() < deadline && !pg_client.is_stop_requested()
+                        {
+                            std::thread::sleep(std::time::Duration::from_secs(1));
+                        }
+                        continue;
+                    }
+                    None => {
+                        warn!(
+                            queued = pending_blocks.len(),
+                            "No queued block fits in memory, releasing them"
+                        );
+                        break;
+                    }
+                }
+            }
+        };
 
         // Tell the heartbeat thread which block we're working on
         *pg_client.current_block_id.lock().unwrap() = Some(block.block_id);
@@ -550,6 +753,9 @@ pub fn run_work_loop(
         progress
             .found
             .store(0, std::sync::atomic::Ordering::Relaxed);
+        progress
+            .checksum
+            .store(0, std::sync::atomic::Ordering::Relaxed);
 
         let span = info_span!(
             "search_block",
@@ -566,42 +772,84 @@ pub fn run_work_loop(
                 effective_start,
                 block.block_end,
                 &progress,
-                db,
+                db.as_ref(),
                 rt_handle,
-                &cli.checkpoint,
+                &checkpoint_path,
                 mr,
                 sl,
                 coord,
+                Some(&event_bus),
             )
         });
 
         *pg_client.current_block_id.lock().unwrap() = None;
+        persist_worker_events(db, rt_handle, &event_bus, &mut last_event_id, &worker_id);
 
         let tested = progress.tested.load(std::sync::atomic::Ordering::Relaxed);
         let found = progress.found.load(std::sync::atomic::Ordering::Relaxed);
-
-        match block_result {
-            Ok(()) => {
-                rt_handle.block_on(db.complete_work_block(
+        // A resumed block only saw part of its survivors, so its checksum
+        // can't be compared against a full re-run.
+        let checksum = (effective_start == block.block_start)
+            .then(|| progress.checksum.load(std::sync::atomic::Ordering::Relaxed));
+
+        let stopped = pg_client.is_stop_requested();
+        match pg_worker::BlockOutcome::of(&block_result, stopped) {
+            pg_worker::BlockOutcome::Complete => {
+                rt_handle.block_on(db.complete_work_block_with_checksum(
                     block.block_id,
                     tested as i64,
                     found as i64,
+                    checksum,
                 ))?;
                 blocks_completed += 1;
                 info!(
                     block_id = block.block_id,
-                    tested,
-                    found,
-                    "Block completed"
+                    tested, found, checksum, "Block completed"
                 );
             }
-            Err(e) => {
-                warn!(block_id = block.block_id, error = %e, "Block failed");
+            pg_worker::BlockOutcome::Release => {
+                info!(block_id = block.block_id, tested, "Block stopped early");
+                interrupted = pg_worker::release_checkpoint(
+                    &checkpoint_path,
+                    effective_start,
+                    block.block_end,
+                )
+                .map(|cp| (block.block_id, tested as i64, found as i64, cp));
+                // Released with the rest of the queue below.
+                pending_blocks.push_front(block);
+            }
+            pg_worker::BlockOutcome::Fail => {
+                if let Err(e) = block_result {
+                    warn!(block_id = block.block_id, error = %e, "Block failed");
+                }
                 rt_handle.block_on(db.fail_work_block(block.block_id))?;
             }
         }
     }
 
+    // Claimed but unfinished blocks go back to the pool now rather than
+    // waiting for the stale-block reclaim.
+    if !pending_blocks.is_empty() {
+        let ids: Vec<i64> = pending_blocks.iter().map(|b| b.block_id).collect();
+        let release = async {
+            if let Some((block_id, tested, found, cp)) = &interrupted {
+                if let Err(e) = db
+                    .update_block_progress(*block_id, *tested, *found, Some(cp))
+                    .await
+                {
+                    warn!(block_id, error = %e, "Failed to store block checkpoint");
+                }
+            }
+            db.release_work_blocks(&worker_id, &ids).await
+        };
+        let grace = pg_worker::SHUTDOWN_GRACE;
+        match rt_handle.block_on(async { tokio::time::timeout(grace, release).await }) {
+            Ok(Ok(released)) => info!(released, "Released unfinished blocks"),
+            Ok(Err(e)) => warn!(error = %e, "Failed to release blocks, leaving them to reclaim"),
+            Err(_) => warn!("Timed out releasing blocks, leaving them to reclaim"),
+        }
+    }
+
     progress.stop();
     let _ = reporter_handle.join();
     pg_client.deregister();
@@ -618,24 +866,161 @@ pub fn run_work_loop(
     Ok(())
 }
 
+/// Write the events `event_bus` recorded after `last_event_id` to
+/// `system_logs`, tagged with this worker and their search job, and advance
+/// `last_event_id`. A failed write is logged and the events are dropped.
+fn persist_worker_events(
+    db: &db::Database,
+    rt_handle: &tokio::runtime::Handle,
+    event_bus: &events::EventBus,
+    last_event_id: &mut u64,
+    worker_id: &str,
+) {
+    let since = event_bus.recent_events_since(*last_event_id, event_bus.capacity());
+    if since.dropped > 0 {
+        warn!(dropped = since.dropped, "event ring lapped between blocks");
+    }
+    let Some(last) = since.events.last() else {
+        return;
+    };
+    *last_event_id = last.id;
+    let logs = worker_log_entries(since.events, worker_id);
+    if let Err(e) = rt_handle.block_on(db.insert_system_logs(&logs)) {
+        warn!(error = %e, "failed to persist worker events");
+    }
+}
+
+/// `system_logs` rows for events a `work` loop's searches emitted.
+fn worker_log_entries(events: Vec<events::EventRecord>, worker_id: &str) -> Vec<db::SystemLogEntry> {
+    events
+        .into_iter()
+        .map(|e| db::SystemLogEntry::from_event(e, "worker", Some(worker_id)))
+        .collect()
+}
+
+// ── Batch Runs ──────────────────────────────────────────────────
+
+/// Run a TOML batch of searches in order (`darkreach run --config`).
+///
+/// Each `[[search]]` entry goes through [`run_search_block`] with its own
+/// checkpoint and the global flags. Results go to the database, or to the
+/// `--output-file` JSONL sink when no database URL is set. No worker is
+/// registered, so there is no heartbeat or remote stop.
+pub fn run_batch(cli: &Cli, config_path: &std::path::Path) -> Result<()> {
+    let batch = project::parse_batch_toml_file(config_path)?;
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let sink: Box<dyn PrimeSink> = match (&cli.database_url, &cli.output_file) {
+        (Some(url), _) => Box::new(rt.block_on(db::Database::connect(url))?),
+        (None, Some(path)) => Box::new(prime_sink::FilePrimeSink::open(path)?),
+        (None, None) => anyhow::bail!(
+            "DATABASE_URL is required (set via --database-url or env, or use --output-file)"
+        ),
+    };
+
+    info!(
+        config = %config_path.display(),
+        searches = batch.searches.len(),
+        cores = rayon::current_num_threads(),
+        mr_rounds = cli.mr_rounds,
+        "batch starting"
+    );
+
+    let progress = progress::Progress::new();
+    let reporter_handle = progress.start_reporter();
+
+    let result = batch.run(|label, search, params| {
+        let checkpoint_path = batch_checkpoint_path(cli, label)?;
+        progress
+            .tested
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        progress
+            .found
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        let span = info_span!(
+            "batch_search",
+            search = label,
+            form = %search.form,
+            range_start = search.start,
+            range_end = search.end,
+        );
+        let block_start = i64::try_from(search.start)
+            .map_err(|_| anyhow::anyhow!("start {} is out of range", search.start))?;
+        let block_end = i64::try_from(search.end)
+            .map_err(|_| anyhow::anyhow!("end {} is out of range", search.end))?;
+        span.in_scope(|| {
+            run_search_block(
+                &search.form,
+                params,
+                block_start,
+                block_end,
+                &progress,
+                sink.as_ref(),
+                rt.handle(),
+                &checkpoint_path,
+                cli.mr_rounds,
+                cli.sieve_limit,
+                None,
+                None,
+            )?;
+            info!(
+                tested = progress.tested.load(std::sync::atomic::Ordering::Relaxed),
+                found = progress.found.load(std::sync::atomic::Ordering::Relaxed),
+                "batch search complete"
+            );
+            Ok(())
+        })
+    });
+
+    progress.stop();
+    let _ = reporter_handle.join();
+    progress.print_status();
+
+    info!("Batch complete");
+    result
+}
+
+/// Checkpoint for one batch search: `<dir>/<label>.checkpoint` with
+/// `--checkpoint-dir`, otherwise `--checkpoint` suffixed with `.<label>` so
+/// the searches never share a file.
+fn batch_checkpoint_path(cli: &Cli, label: &str) -> Result<std::path::PathBuf> {
+    if cli.checkpoint_dir.is_some() {
+        return checkpoint_path_for(cli, label);
+    }
+    let mut path = cli.checkpoint.clone().into_os_string();
+    path.push(format!(".{}", label));
+    Ok(path.into())
+}
+
 /// Dispatch a single block to the appropriate search function.
+///
+/// The engine folds each sieve survivor into `progress.checksum`; the caller
+/// resets it before the block and stores it on completion. Parameters go
+/// through [`search_params::validate_block`] first, so a misconfigured job
+/// fails its block with the reason rather than running with defaults.
+///
+/// Events go to `event_bus` tagged with `coord`'s search job; the `work`
+/// loop persists them with [`persist_worker_events`].
+#[allow(clippy::too_many_arguments)]
 fn run_search_block(
     search_type: &str,
     params: &serde_json::Value,
     block_start: i64,
     block_end: i64,
     progress: &Arc<progress::Progress>,
-    db: &Arc<db::Database>,
+    db: &dyn PrimeSink,
     rt_handle: &tokio::runtime::Handle,
     checkpoint_path: &std::path::Path,
     mr: u32,
     sl: u64,
     coord: Option<&dyn CoordinationClient>,
+    eb: Option<&events::EventBus>,
 ) -> Result<()> {
+    search_params::validate_block(search_type, params, block_start, block_end)?;
+    let mr = search_params::effective_mr_rounds(search_type, mr);
     let sp = serde_json::to_string(params)?;
     let start = block_start as u64;
     let end = block_end as u64;
-    let eb: Option<&events::EventBus> = None;
 
     match search_type {
         "factorial" => factorial::search(
@@ -670,6 +1055,7 @@ fn run_search_block(
                 base,
                 start,
                 end,
+                palindromic::PalindromeOrder::Ascending,
                 progress,
                 db,
                 rt_handle,
@@ -828,9 +1214,51 @@ fn run_search_block(
     }
 }
 
+/// Test a kbn block whose survivors the coordinator already sieved.
+#[allow(clippy::too_many_arguments)]
+fn run_presieved_kbn_block(
+    params: &serde_json::Value,
+    block_start: i64,
+    block_end: i64,
+    survivors: &kbn::SieveSurvivors,
+    progress: &Arc<progress::Progress>,
+    db: &dyn PrimeSink,
+    rt_handle: &tokio::runtime::Handle,
+    checkpoint_path: &std::path::Path,
+    mr: u32,
+) -> Result<()> {
+    search_params::validate_block("kbn", params, block_start, block_end)?;
+    let mr = search_params::effective_mr_rounds("kbn", mr);
+    let sp = serde_json::to_string(params)?;
+    let k = params["k"].as_u64().unwrap_or(1);
+    let base = params["base"].as_u64().unwrap_or(2) as u32;
+    kbn::search_survivors(
+        k,
+        base,
+        block_start as u64,
+        block_end as u64,
+        survivors,
+        progress,
+        db,
+        rt_handle,
+        checkpoint_path,
+        &sp,
+        mr,
+        None,
+        None,
+    )
+}
+
 // ── Verification ────────────────────────────────────────────────
 
-/// Run the verify subcommand.
+/// Run the verify subcommand. With `parallel`, the fetched batch is verified
+/// across rayon threads before the (serial) DB updates. Fails if any prime
+/// fails verification, after printing every result.
+///
+/// With `resumable` (`--all` only) the whole backlog is paged through in
+/// `batch_size` fetches under a `verification_runs` row whose position is
+/// saved after each prime, so an interrupted run picks up where it stopped.
+#[allow(clippy::too_many_arguments)]
 pub fn run_verify(
     rt: &tokio::runtime::Runtime,
     db: &db::Database,
@@ -840,7 +1268,14 @@ pub fn run_verify(
     batch_size: i64,
     force: bool,
     tool: &str,
+    parallel: bool,
+    format: verify::VerifyFormat,
+    resumable: bool,
 ) -> Result<()> {
+    let json = format == verify::VerifyFormat::Json;
+    if resumable {
+        return run_verify_resumable(rt, db, form, batch_size, force, tool, parallel, json);
+    }
     let primes = if let Some(id) = id {
         match rt.block_on(db.get_prime_by_id(id))? {
             Some(p) => vec![p],
@@ -850,7 +1285,7 @@ pub fn run_verify(
             }
         }
     } else if all || form.is_some() {
-        rt.block_on(db.get_unverified_primes_filtered(batch_size, form, force))?
+        rt.block_on(db.get_unverified_primes_filtered(batch_size, form, force, 0))?
     } else {
         eprintln!("Specify --id <ID>, --all, or --form <FORM>");
         return Ok(());
@@ -858,38 +1293,132 @@ pub fn run_verify(
 
     if primes.is_empty() {
         eprintln!("No primes to verify");
+        if json {
+            println!("[]");
+        }
         return Ok(());
     }
 
     eprintln!("Verifying {} primes...", primes.len());
+    print_verify_header();
+    let mut tally = VerifyTally::default();
+    let mut records = Vec::with_capacity(primes.len());
+    verify_primes(
+        rt,
+        db,
+        &primes,
+        tool,
+        parallel,
+        None,
+        &mut tally,
+        json.then_some(&mut records),
+    )?;
+    finish_verify(&tally, json.then_some(&records))
+}
+
+/// `verify --all --resumable`: create or resume the run for these filters and
+/// page through every prime above its saved position.
+#[allow(clippy::too_many_arguments)]
+fn run_verify_resumable(
+    rt: &tokio::runtime::Runtime,
+    db: &db::Database,
+    form: Option<&str>,
+    batch_size: i64,
+    force: bool,
+    tool: &str,
+    parallel: bool,
+    json: bool,
+) -> Result<()> {
+    let run = rt.block_on(db.create_or_resume_verification_run(form, force, tool))?;
+    if run.last_processed_id > 0 {
+        eprintln!(
+            "Resuming verification run {} after prime id {} ({} verified, {} failed, {} skipped so far)",
+            run.id, run.last_processed_id, run.verified, run.failed, run.skipped
+        );
+    } else {
+        eprintln!("Starting verification run {}", run.id);
+    }
+
+    let mut tally = VerifyTally {
+        verified: run.verified.max(0) as u64,
+        failed: run.failed.max(0) as u64,
+        skipped: run.skipped.max(0) as u64,
+    };
+    let mut records = Vec::new();
+    let mut after_id = run.last_processed_id;
+    let mut headed = false;
+    loop {
+        let primes =
+            rt.block_on(db.get_unverified_primes_filtered(batch_size, form, force, after_id))?;
+        let Some(last) = primes.last() else {
+            break;
+        };
+        after_id = last.id;
+        if !headed {
+            print_verify_header();
+            headed = true;
+        }
+        verify_primes(
+            rt,
+            db,
+            &primes,
+            tool,
+            parallel,
+            Some(run.id),
+            &mut tally,
+            json.then_some(&mut records),
+        )?;
+    }
+    rt.block_on(db.complete_verification_run(run.id))?;
+    if !headed {
+        eprintln!("No primes to verify");
+    }
+    finish_verify(&tally, json.then_some(&records))
+}
+
+/// Outcome counts for one `verify` invocation (or resumed run).
+#[derive(Default)]
+struct VerifyTally {
+    verified: u64,
+    failed: u64,
+    skipped: u64,
+}
+
+fn print_verify_header() {
     eprintln!(
         "{:<8} {:<40} {:<12} {:<12} Status",
         "ID", "Expression", "Tier 1", "Tier 2"
     );
     eprintln!("{}", "-".repeat(90));
+}
 
-    let mut verified = 0u64;
-    let mut failed = 0u64;
-    let mut skipped = 0u64;
-
-    for prime in &primes {
-        let result = if tool == "pfgw" {
-            let candidate = verify::reconstruct_candidate(&prime.form, &prime.expression);
-            match candidate {
-                Ok(c) => verify::verify_pfgw(&prime.form, &prime.expression, &c),
-                Err(e) => verify::VerifyResult::Failed {
-                    reason: format!("Cannot reconstruct: {}", e),
-                },
-            }
-        } else {
-            verify::verify_prime(prime)
+/// Verify `primes` in order, recording each outcome on the prime and, with
+/// `run_id`, advancing that verification run past it.
+#[allow(clippy::too_many_arguments)]
+fn verify_primes(
+    rt: &tokio::runtime::Runtime,
+    db: &db::Database,
+    primes: &[db::PrimeDetail],
+    tool: &str,
+    parallel: bool,
+    run_id: Option<i64>,
+    tally: &mut VerifyTally,
+    mut records: Option<&mut Vec<verify::VerifyRecord>>,
+) -> Result<()> {
+    // Parallel mode verifies the whole batch up front; the DB updates below
+    // stay serial either way so they fit the small connection pool.
+    let batch = parallel.then(|| verify::verify_batch(primes, tool, true));
+
+    for (i, prime) in primes.iter().enumerate() {
+        let result = match &batch {
+            Some(results) => results[i].1.clone(),
+            None => verify::verify_with_tool(prime, tool),
         };
 
-        let expr_display = if prime.expression.len() > 38 {
-            format!("{}...", &prime.expression[..35])
-        } else {
-            prime.expression.clone()
-        };
+        let expr_display = truncate_display(&prime.expression, 38);
+        if let Some(records) = records.as_deref_mut() {
+            records.push(verify::VerifyRecord::new(prime, &result));
+        }
 
         match &result {
             verify::VerifyResult::Verified { method, tier } => {
@@ -900,35 +1429,197 @@ pub fn run_verify(
                     prime.id, expr_display, t1, t2, method
                 );
                 rt.block_on(db.mark_verified(prime.id, method, *tier as i16))?;
-                verified += 1;
+                tally.verified += 1;
             }
-            verify::VerifyResult::Failed { reason } => {
+            verify::VerifyResult::Failed {
+                reason,
+                reason_code,
+            } => {
                 eprintln!(
                     "{:<8} {:<40} {:<12} {:<12} FAILED: {}",
                     prime.id, expr_display, "FAIL", "-", reason
                 );
-                rt.block_on(db.mark_verification_failed(prime.id, reason))?;
-                failed += 1;
+                rt.block_on(db.mark_verification_failed(prime.id, reason, reason_code.as_str()))?;
+                tally.failed += 1;
             }
             verify::VerifyResult::Skipped { reason } => {
                 eprintln!(
                     "{:<8} {:<40} {:<12} {:<12} SKIPPED: {}",
                     prime.id, expr_display, "skip", "-", reason
                 );
-                skipped += 1;
+                tally.skipped += 1;
+            }
+            verify::VerifyResult::Errored {
+                reason,
+                reason_code,
+            } => {
+                eprintln!(
+                    "{:<8} {:<40} {:<12} {:<12} ERROR: {}",
+                    prime.id, expr_display, "-", "-", reason
+                );
+                rt.block_on(db.record_verification_error(prime.id, reason_code.as_str()))?;
+                tally.skipped += 1;
             }
         }
+
+        if let Some(run_id) = run_id {
+            rt.block_on(db.record_verification_run_progress(
+                run_id,
+                prime.id,
+                tally.verified as i64,
+                tally.failed as i64,
+                tally.skipped as i64,
+            ))?;
+        }
     }
+    Ok(())
+}
 
+/// Print the summary (and the JSON array, if requested); fail if any prime
+/// failed.
+fn finish_verify(tally: &VerifyTally, records: Option<&Vec<verify::VerifyRecord>>) -> Result<()> {
     eprintln!(
         "\nSummary: {} verified, {} failed, {} skipped",
-        verified, failed, skipped
+        tally.verified, tally.failed, tally.skipped
+    );
+    if let Some(records) = records {
+        println!("{}", serde_json::to_string_pretty(records)?);
+    }
+    if tally.failed > 0 {
+        anyhow::bail!("{} primes failed verification", tally.failed);
+    }
+    Ok(())
+}
+
+/// Run `verify --replay --id N`: print each pipeline stage's outcome and timing.
+pub fn run_replay(rt: &tokio::runtime::Runtime, db: &db::Database, id: i64) -> Result<()> {
+    let Some(prime) = rt.block_on(db.get_prime_by_id(id))? else {
+        eprintln!("Prime with id {} not found", id);
+        return Ok(());
+    };
+
+    let report = verify::replay(&prime.form, &prime.expression);
+    eprintln!(
+        "Replaying #{} {} ({})",
+        prime.id, prime.expression, prime.form
+    );
+    if let Some(digits) = report.digits {
+        eprintln!("Digits: {} (stored: {})", digits, prime.digits);
+    }
+    print_stages(&report);
+    eprintln!(
+        "\nReplay {}",
+        if report.passed() { "passed" } else { "FAILED" }
     );
     Ok(())
 }
 
+/// Print a replay report's stage table (stage, result, time, detail).
+fn print_stages(report: &verify::ReplayReport) {
+    eprintln!("{:<16} {:<6} {:>12}  Detail", "Stage", "Result", "Time");
+    eprintln!("{}", "-".repeat(60));
+    for stage in &report.stages {
+        let outcome = match stage.outcome {
+            verify::StageOutcome::Pass => "PASS",
+            verify::StageOutcome::Fail => "FAIL",
+            verify::StageOutcome::Skip => "skip",
+        };
+        eprintln!(
+            "{:<16} {:<6} {:>12}  {}",
+            stage.name,
+            outcome,
+            format!("{:.3?}", stage.elapsed),
+            stage.detail
+        );
+    }
+}
+
+/// Run `test`: build one explicit candidate, run the full pipeline on it and
+/// print every stage plus the verdict and certificate. Needs no database.
+pub fn run_test(
+    form: &str,
+    expr: Option<&str>,
+    k: Option<u64>,
+    base: Option<u32>,
+    n: Option<u64>,
+    minus: bool,
+) -> Result<()> {
+    let expression = match expr {
+        Some(e) => e.to_string(),
+        None => verify::expression_for(form, k, base, n, minus)?,
+    };
+    let verdict = verify::test_candidate(form, &expression);
+    eprintln!("Testing {} ({})", expression, form);
+    if let Some(digits) = verdict.report.digits {
+        eprintln!("Digits: {}", digits);
+    }
+    print_stages(&verdict.report);
+
+    let result = match (&verdict.proof_method, verdict.prime) {
+        (Some(method), _) => format!("PRIME ({})", method),
+        (None, true) => "PROBABLE PRIME".to_string(),
+        (None, false) => "COMPOSITE".to_string(),
+    };
+    eprintln!("\nResult: {}", result);
+    if let Some(cert) = &verdict.certificate {
+        eprintln!("Certificate: {}", cert);
+    }
+    Ok(())
+}
+
+/// Run `diagnose`. `diagnose sieve` re-tests every candidate the kbn sieve
+/// eliminates and fails if any of them is not composite.
+pub fn run_diagnose(check: &DiagnoseCheck) -> Result<()> {
+    let DiagnoseCheck::Sieve {
+        k,
+        base,
+        min_n,
+        max_n,
+        sieve_limit,
+    } = *check;
+    if min_n == 0 || max_n < min_n {
+        anyhow::bail!("need 1 <= --min-n <= --max-n");
+    }
+    eprintln!(
+        "Sieving {}*{}^n+/-1 for n in [{}, {}] with primes up to {}",
+        k, base, min_n, max_n, sieve_limit
+    );
+    let diagnosis = kbn::diagnose_sieve(k, base, min_n, max_n, sieve_limit);
+    eprintln!("Eliminated candidates checked: {}", diagnosis.checked);
+    for bad in &diagnosis.false_eliminations {
+        let sign = if bad.minus { '-' } else { '+' };
+        eprintln!("WRONGLY ELIMINATED: {}*{}^{}{}1", k, base, bad.n, sign);
+    }
+    match diagnosis.false_eliminations.len() {
+        0 => {
+            eprintln!("\nResult: OK (no false eliminations)");
+            Ok(())
+        }
+        bad => anyhow::bail!("{} false eliminations", bad),
+    }
+}
+
 // ── Project Management ──────────────────────────────────────────
 
+/// Apply pending schema migrations to `DATABASE_URL`.
+pub fn run_migrate(cli: &Cli, supabase: bool) -> Result<()> {
+    let database_url = cli.database_url.as_deref().ok_or_else(|| {
+        anyhow::anyhow!("DATABASE_URL is required (set via --database-url or env)")
+    })?;
+    let rt = tokio::runtime::Runtime::new()?;
+    let database = rt.block_on(db::Database::connect(database_url))?;
+    let applied = rt.block_on(database.migrate(supabase))?;
+    for name in &applied {
+        info!(migration = *name, "migration applied");
+    }
+    info!(
+        applied = applied.len(),
+        total = db::migrations::MIGRATIONS.len(),
+        "schema up to date"
+    );
+    Ok(())
+}
+
 /// Handle the `project` subcommand and its actions.
 pub fn run_project(cli: &Cli, action: &ProjectAction) -> Result<()> {
     // Estimate doesn't need a database connection
@@ -1013,6 +1704,28 @@ pub fn run_project(cli: &Cli, action: &ProjectAction) -> Result<()> {
             eprintln!("  Cost (USD):   ${:.2}", proj.total_cost_usd);
             eprintln!("  Created:     {}", proj.created_at);
 
+            let record_digits = rt
+                .block_on(database.get_records())?
+                .into_iter()
+                .filter(|r| r.form == proj.form)
+                .map(|r| r.digits)
+                .max();
+            if let Some(record) = record_digits {
+                // Primes within 10% of the record's size.
+                let margin = record / 10;
+                let near =
+                    rt.block_on(database.primes_within_digits_of_record(&proj.form, margin))?;
+                eprintln!(
+                    "\nWithin {} digits of the {}-digit record ({}):",
+                    margin,
+                    record,
+                    near.len()
+                );
+                for p in near.iter().take(5) {
+                    eprintln!("  {} ({} digits)", p.expression, p.digits);
+                }
+            }
+
             eprintln!("\nPhases ({}):", phases.len());
             for phase in &phases {
                 let job_str = phase
@@ -1103,11 +1816,7 @@ pub fn run_project(cli: &Cli, action: &ProjectAction) -> Result<()> {
                 );
                 eprintln!("{}", "-".repeat(97));
                 for r in &records {
-                    let expr = if r.expression.len() > 38 {
-                        format!("{}...", &r.expression[..35])
-                    } else {
-                        r.expression.clone()
-                    };
+                    let expr = truncate_display(&r.expression, 38);
                     let our = if r.our_best_digits > 0 {
                         format!("{}", r.our_best_digits)
                     } else {
@@ -1149,7 +1858,7 @@ pub fn run_register(server: &str, username: &str, email: &str) -> Result<()> {
 
 /// Run the operator work loop (claim → compute → submit → repeat).
 pub fn run_operator(cli: &Cli) -> Result<()> {
-    use darkreach::{progress, operator};
+    use darkreach::{operator, progress, prom_metrics};
 
     let config = operator::load_config()?;
     operator::register_worker(&config)?;
@@ -1248,6 +1957,7 @@ pub fn run_operator(cli: &Cli) -> Result<()> {
     }
 
     let mut blocks_completed = 0u64;
+    let pusher = prom_metrics::PushGateway::from_settings(&config.worker_id);
 
     loop {
         if stop.load(std::sync::atomic::Ordering::Relaxed) {
@@ -1278,7 +1988,11 @@ pub fn run_operator(cli: &Cli) -> Result<()> {
             search_type = %assignment.search_type,
             "Claimed work block"
         );
+        // One key per assignment: every submission of this block's result
+        // carries it, so the coordinator applies the result once.
+        let idempotency_key = uuid::Uuid::new_v4();
 
+        let block_start = std::time::Instant::now();
         let prog = progress::Progress::new();
         let reporter = prog.start_reporter();
         let checkpoint = std::path::PathBuf::from(format!(
@@ -1293,20 +2007,32 @@ pub fn run_operator(cli: &Cli) -> Result<()> {
             range_start = assignment.block_start,
             range_end = assignment.block_end,
         );
-        let result = span.in_scope(|| {
-            run_search_block(
+        let result = span.in_scope(|| match &assignment.survivors {
+            Some(survivors) if assignment.search_type == "kbn" => run_presieved_kbn_block(
+                &assignment.params,
+                assignment.block_start,
+                assignment.block_end,
+                survivors,
+                &prog,
+                db.as_ref(),
+                rt.handle(),
+                &checkpoint,
+                cli.mr_rounds,
+            ),
+            _ => run_search_block(
                 &assignment.search_type,
                 &assignment.params,
                 assignment.block_start,
                 assignment.block_end,
                 &prog,
-                &db,
+                db.as_ref(),
                 rt.handle(),
                 &checkpoint,
                 cli.mr_rounds,
                 cli.sieve_limit,
                 None,
-            )
+                None,
+            ),
         });
 
         let tested = prog.tested.load(std::sync::atomic::Ordering::Relaxed);
@@ -1315,14 +2041,26 @@ pub fn run_operator(cli: &Cli) -> Result<()> {
         prog.stop();
         let _ = reporter.join();
 
+        if let Some(pusher) = &pusher {
+            let form = &assignment.search_type;
+            pusher.record_block(form, tested, found, block_start.elapsed());
+            pusher.push_or_warn();
+        }
+
         let submission = operator::ResultSubmission {
             block_id: assignment.block_id,
             tested: tested as i64,
             found: found as i64,
             primes: vec![],
+            idempotency_key: Some(idempotency_key),
+            verification_id: assignment.verification_id,
         };
 
-        match operator::submit_result(&config, &submission) {
+        match operator::submit_result_with_retry(
+            &config,
+            &submission,
+            std::time::Duration::from_secs(5),
+        ) {
             Ok(()) => {
                 blocks_completed += 1;
                 info!(
@@ -1397,3 +2135,58 @@ pub fn configure_rayon(threads: Option<usize>, qos: bool) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Coordination client for a fixed search job that never stops.
+    struct JobClient(i64);
+
+    impl CoordinationClient for JobClient {
+        fn is_stop_requested(&self) -> bool {
+            false
+        }
+        fn report_prime(&self, _: &str, _: &str, _: u64, _: &str, _: &str) {}
+        fn search_job_id(&self) -> Option<i64> {
+            Some(self.0)
+        }
+    }
+
+    /// A prime found by a block run for job 42 reaches the worker's
+    /// `system_logs` rows tagged with that job and worker.
+    #[test]
+    fn work_block_events_persist_with_search_job_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = prime_sink::FilePrimeSink::open(&dir.path().join("primes.jsonl")).unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let bus = events::EventBus::new();
+        run_search_block(
+            "kbn",
+            &serde_json::json!({"k": 3, "base": 2}),
+            1,
+            20,
+            &progress::Progress::new(),
+            &sink,
+            rt.handle(),
+            &dir.path().join("kbn.checkpoint"),
+            25,
+            1_000,
+            Some(&JobClient(42)),
+            Some(&bus),
+        )
+        .unwrap();
+
+        let logs = worker_log_entries(bus.recent_events_since(0, 100).events, "w1");
+        let primes: Vec<_> = logs
+            .iter()
+            .filter(|l| l.context.as_ref().unwrap()["kind"] == "prime")
+            .collect();
+        assert!(!primes.is_empty());
+        for log in primes {
+            assert_eq!(log.search_job_id, Some(42));
+            assert_eq!(log.worker_id.as_deref(), Some("w1"));
+            assert_eq!(log.source, "worker");
+        }
+    }
+}
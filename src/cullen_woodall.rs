@@ -45,11 +45,12 @@ use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, debug};
 
+use crate::candidate::{CandidateForm, CullenWoodall};
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::kbn;
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::CoordinationClient;
 use crate::{exact_digits, mr_screened_test, sieve};
@@ -128,12 +129,7 @@ fn test_cullen(candidate: &Integer, n: u64, mr_rounds: u32) -> (IsPrime, &'stati
 
     // Fallback: Miller-Rabin with 2-round pre-screen
     let r = mr_screened_test(candidate, mr_rounds);
-    let cert = match r {
-        IsPrime::Yes => "deterministic",
-        IsPrime::Probably => "probabilistic",
-        IsPrime::No => "",
-    };
-    (r, cert)
+    (r, crate::certainty_label(candidate, r))
 }
 
 /// Test primality of a Woodall number n*2^n - 1 using LLR.
@@ -165,19 +161,14 @@ fn test_woodall(candidate: &Integer, n: u64, mr_rounds: u32) -> (IsPrime, &'stat
     }
 
     let r = mr_screened_test(candidate, mr_rounds);
-    let cert = match r {
-        IsPrime::Yes => "deterministic",
-        IsPrime::Probably => "probabilistic",
-        IsPrime::No => "",
-    };
-    (r, cert)
+    (r, crate::certainty_label(candidate, r))
 }
 
 pub fn search(
     min_n: u64,
     max_n: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -186,6 +177,9 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params = serde_json::json!({"min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("cullen_woodall", &params)?;
+
     // Resolve sieve_limit: auto-tune if 0
     // Cullen/Woodall: n*2^n has ~max_n + log2(max_n) bits
     let candidate_bits = max_n + (max_n as f64).log2() as u64;
@@ -238,6 +232,7 @@ pub fn search(
     );
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut block_start = resume_from;
     let mut total_sieved: u64 = 0;
 
@@ -263,15 +258,18 @@ pub fn search(
             .collect();
 
         total_sieved += block_len - survivors.len() as u64;
+        progress.record_survivors(survivors.iter().map(|s| s.0));
 
         let found_primes: Vec<_> = survivors
             .into_par_iter()
             .flat_map_iter(|(n, test_cullen_flag, test_woodall_flag)| {
+                let _busy = crate::progress::busy();
                 let n_2_n = Integer::from(n) * Integer::from(2u32).pow(crate::checked_u32(n));
 
                 let cullen_result = if test_cullen_flag {
                     let cullen = Integer::from(&n_2_n + 1u32);
                     let expr = format!("{}*2^{}+1", n, n);
+                    let _testing = progress.testing(&expr, &cullen);
 
                     // Try PFGW acceleration (50-100x faster for large candidates)
                     match pfgw::try_test(&expr, &cullen, pfgw::PfgwMode::Prp) {
@@ -285,20 +283,25 @@ pub fn search(
                                 "probabilistic".to_string()
                             };
                             let digits = exact_digits(&cullen);
-                            Some((format!("{}*2^{} + 1", n, n), digits, cert, "cullen"))
+                            Some((
+                                CullenWoodall { plus: true }.expression(n),
+                                digits,
+                                cert,
+                                "cullen",
+                            ))
                         }
                         Some(pfgw::PfgwResult::Composite) => None,
                         _ => {
                             // Unavailable or not configured — fall through to GMP
                             // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                            if crate::p1::adaptive_p1_filter(&cullen) {
+                            if crate::p1::adaptive_p1_filter_for_form(&cullen, "cullen_woodall") {
                                 None
                             } else {
                                 let (r, cert) = test_cullen(&cullen, n, mr_rounds);
                                 if r != IsPrime::No {
                                     let digits = exact_digits(&cullen);
                                     Some((
-                                        format!("{}*2^{} + 1", n, n),
+                                        CullenWoodall { plus: true }.expression(n),
                                         digits,
                                         cert.to_string(),
                                         "cullen",
@@ -317,6 +320,7 @@ pub fn search(
                     let woodall = Integer::from(&n_2_n - 1u32);
                     if woodall > 0u32 {
                         let expr = format!("{}*2^{}-1", n, n);
+                        let _testing = progress.testing(&expr, &woodall);
 
                         // Try PFGW acceleration
                         match pfgw::try_test(&expr, &woodall, pfgw::PfgwMode::Prp) {
@@ -330,20 +334,28 @@ pub fn search(
                                     "probabilistic".to_string()
                                 };
                                 let digits = exact_digits(&woodall);
-                                Some((format!("{}*2^{} - 1", n, n), digits, cert, "woodall"))
+                                Some((
+                                    CullenWoodall { plus: false }.expression(n),
+                                    digits,
+                                    cert,
+                                    "woodall",
+                                ))
                             }
                             Some(pfgw::PfgwResult::Composite) => None,
                             _ => {
                                 // Unavailable or not configured — fall through to GMP
                                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                                if crate::p1::adaptive_p1_filter(&woodall) {
+                                if crate::p1::adaptive_p1_filter_for_form(
+                                    &woodall,
+                                    "cullen_woodall",
+                                ) {
                                     None
                                 } else {
                                     let (r, cert) = test_woodall(&woodall, n, mr_rounds);
                                     if r != IsPrime::No {
                                         let digits = exact_digits(&woodall);
                                         Some((
-                                            format!("{}*2^{} - 1", n, n),
+                                            CullenWoodall { plus: false }.expression(n),
                                             digits,
                                             cert.to_string(),
                                             "woodall",
@@ -368,6 +380,20 @@ pub fn search(
         progress.tested.fetch_add(block_len * 2, Ordering::Relaxed);
 
         for (expr, digits, certainty, form) in found_primes {
+            let (certainty, cert_json) = crate::prove_on_find(form, &expr, digits, certainty, None);
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                form,
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -375,8 +401,13 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                // One record for both halves, keyed like the projects table
+                eb.check_milestone("cullen_woodall", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "cullen_woodall").ok().flatten()
+                });
             } else {
                 info!(
                     expression = %expr,
@@ -386,13 +417,20 @@ pub fn search(
                     "*** PRIME FOUND ***"
                 );
             }
-            db.insert_prime_sync(rt, form, &expr, digits, search_params, &certainty, None)?;
-            if let Some(wc) = worker_client {
-                wc.report_prime(form, &expr, digits, search_params, &certainty);
-            }
+            buffer.push(
+                form,
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+                cert_json.as_deref(),
+            )?;
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::CullenWoodall {
@@ -406,6 +444,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::CullenWoodall {
@@ -421,6 +460,7 @@ pub fn search(
         block_start = block_end + 1;
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!(total_sieved, "Cullen/Woodall sieve eliminated candidates");
     Ok(())
@@ -0,0 +1,133 @@
+//! On-disk cache of full decimal expansions for `GET /api/primes/{id}/decimal`.
+//!
+//! Converting a megaprime to base 10 takes seconds, so each expansion is
+//! written to `DECIMAL_CACHE_DIR` (default `$TMPDIR/darkreach-decimal`) under
+//! the SHA-256 of its form and expression. The directory is kept under
+//! `DECIMAL_CACHE_BYTES` (default 1 GiB) by deleting the least recently used
+//! files; a hit refreshes the file's modification time.
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::verify;
+
+/// Default byte budget when `DECIMAL_CACHE_BYTES` is unset.
+pub const DEFAULT_DECIMAL_CACHE_BYTES: u64 = 1 << 30;
+
+pub struct DecimalCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl DecimalCache {
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        DecimalCache { dir, max_bytes }
+    }
+
+    /// Cache configured from `DECIMAL_CACHE_DIR` and `DECIMAL_CACHE_BYTES`.
+    pub fn from_env() -> Self {
+        let dir = std::env::var("DECIMAL_CACHE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| std::env::temp_dir().join("darkreach-decimal"));
+        let max_bytes = std::env::var("DECIMAL_CACHE_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_DECIMAL_CACHE_BYTES);
+        DecimalCache::new(dir, max_bytes)
+    }
+
+    /// Decimal expansion of the candidate, from the cache or rebuilt with
+    /// [`verify::reconstruct_candidate`].
+    pub fn decimal(&self, form: &str, expression: &str) -> Result<String> {
+        let path = self.path(form, expression);
+        if let Ok(text) = fs::read_to_string(&path) {
+            if let Ok(file) = fs::File::options().append(true).open(&path) {
+                let _ = file.set_modified(SystemTime::now());
+            }
+            return Ok(text);
+        }
+        let text = verify::reconstruct_candidate(form, expression)?.to_string();
+        // A failed write only costs a recomputation next time.
+        let _ = self.store(&path, &text);
+        Ok(text)
+    }
+
+    fn path(&self, form: &str, expression: &str) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(form.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(expression.as_bytes());
+        self.dir.join(format!("{:x}.txt", hasher.finalize()))
+    }
+
+    /// Write `text` atomically, then evict old files down to the budget.
+    /// Expansions larger than the whole budget are not cached.
+    fn store(&self, path: &Path, text: &str) -> Result<()> {
+        if text.len() as u64 > self.max_bytes {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, text)?;
+        fs::rename(&tmp, path)?;
+        self.evict()
+    }
+
+    fn evict(&self) -> Result<()> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let meta = entry.metadata()?;
+            let is_txt = entry.path().extension().is_some_and(|e| e == "txt");
+            if meta.is_file() && is_txt {
+                files.push((meta.modified()?, meta.len(), entry.path()));
+            }
+        }
+        let mut total: u64 = files.iter().map(|f| f.1).sum();
+        files.sort();
+        for (_, len, path) in files {
+            if total <= self.max_bytes {
+                break;
+            }
+            fs::remove_file(path)?;
+            total -= len;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// 27! + 1 has 29 digits and 37! + 1 has 44, so a 64-byte cache holds
+    /// one of them: storing the second evicts the first, and a hit is served
+    /// from disk rather than recomputed.
+    #[test]
+    fn cache_is_bounded_by_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DecimalCache::new(dir.path().to_path_buf(), 64);
+
+        let small = cache.decimal("factorial", "27! + 1").unwrap();
+        assert_eq!(small, "10888869450418352160768000001");
+        let small_path = cache.path("factorial", "27! + 1");
+        assert!(small_path.exists());
+
+        // Make the first file clearly older than the second.
+        let old = SystemTime::now() - Duration::from_secs(60);
+        let file = fs::File::options().append(true).open(&small_path).unwrap();
+        file.set_modified(old).unwrap();
+
+        let large = cache.decimal("factorial", "37! + 1").unwrap();
+        assert_eq!(large.len(), 44);
+        assert!(!small_path.exists(), "least recently used file evicted");
+
+        let large_path = cache.path("factorial", "37! + 1");
+        fs::write(&large_path, "cached").unwrap();
+        assert_eq!(cache.decimal("factorial", "37! + 1").unwrap(), "cached");
+    }
+}
@@ -3,8 +3,13 @@
 //! Runs an Axum HTTP server that serves the Next.js frontend, provides REST API
 //! endpoints for prime data, and coordinates the distributed worker fleet via
 //! WebSocket and HTTP heartbeat.
+//!
+//! With `--tls-cert`/`--tls-key` the server terminates TLS itself (see
+//! [`TlsConfig`]). Operator API keys are bearer tokens, so an internet-facing
+//! coordinator must serve operators over HTTPS, here or behind a TLS proxy.
 
 pub(crate) mod middleware_auth;
+mod decimal_cache;
 mod routes_agents;
 mod routes_auth;
 mod routes_docs;
@@ -22,8 +27,12 @@ mod routes_verify;
 mod routes_operator;
 mod routes_primes;
 mod routes_schedules;
+mod snapshot;
+mod tls;
 mod websocket;
 
+pub use tls::TlsConfig;
+
 use crate::{agent, ai_engine, db, events, fleet, metrics, project, prom_metrics, verify};
 use tracing::{info, warn, Instrument};
 use anyhow::Result;
@@ -32,7 +41,7 @@ use axum::http::{HeaderValue, StatusCode};
 use axum::middleware::Next;
 use axum::routing::{get, post};
 use axum::Router;
-use chrono::{DateTime, Timelike, Utc};
+use chrono::{Timelike, Utc};
 use std::path::PathBuf;
 use std::sync::{Mutex, MutexGuard, PoisonError};
 use std::time::Duration;
@@ -108,6 +117,11 @@ impl AppState {
                 let now = chrono::Utc::now();
                 let heartbeat_age = (now - r.last_heartbeat).num_seconds().max(0) as u64;
                 let uptime = (now - r.registered_at).num_seconds().max(0) as u64;
+                let candidate_secs = r
+                    .current_candidate
+                    .as_ref()
+                    .and(r.current_candidate_started_at)
+                    .map(|started| (now - started).num_seconds().max(0) as u64);
                 fleet::WorkerState {
                     worker_id: r.worker_id,
                     hostname: r.hostname,
@@ -119,6 +133,11 @@ impl AppState {
                     current: r.current,
                     checkpoint: r.checkpoint,
                     metrics: r.metrics.and_then(|v| serde_json::from_value(v).ok()),
+                    benchmark_score: r.benchmark_score,
+                    rayon_threads: r.rayon_threads.map(|t| t.max(0) as usize),
+                    threads_busy: r.threads_busy.map(|t| t.max(0) as usize),
+                    current_candidate: r.current_candidate,
+                    current_candidate_secs: candidate_secs,
                     uptime_secs: uptime,
                     last_heartbeat_secs_ago: heartbeat_age,
                     last_heartbeat: std::time::Instant::now(),
@@ -141,10 +160,40 @@ impl AppState {
             coordinator_metrics: Mutex::new(None),
             event_bus: events::EventBus::new(),
             agents: Mutex::new(agent::AgentManager::new()),
-            prom_metrics: prom_metrics::Metrics::new(),
+            prom_metrics: prom_metrics::Metrics::process(),
             ai_engine: tokio::sync::Mutex::new(ai_engine::AiEngine::new()),
         })
     }
+
+    /// Compare a verifier's result with the original block's, raising an
+    /// error event when they disagree and the block is disputed.
+    pub async fn submit_verification_result(
+        &self,
+        verification_id: i64,
+        tested: i64,
+        found: i64,
+        verifier_worker_id: &str,
+    ) -> Result<db::VerificationOutcome> {
+        let outcome = self
+            .db
+            .submit_verification_result(verification_id, tested, found, verifier_worker_id)
+            .await?;
+        if let db::VerificationOutcome::Conflict {
+            original_found,
+            verifier_found,
+        } = &outcome
+        {
+            self.event_bus.emit(events::Event::Error {
+                context: "quorum".to_string(),
+                message: format!(
+                    "verification {} disputed: original found {}, {} found {}",
+                    verification_id, original_found, verifier_worker_id, verifier_found
+                ),
+                timestamp: std::time::Instant::now(),
+            });
+        }
+        Ok(outcome)
+    }
 }
 
 pub(super) fn gethostname() -> String {
@@ -218,15 +267,36 @@ fn normalize_path(path: &str) -> String {
         .join("/")
 }
 
+/// Log one stale-block reclaim pass; quarantined blocks need a human, so they
+/// are logged as warnings.
+fn log_block_reclaim(kind: &str, reclaim: db::BlockReclaim) {
+    if reclaim.requeued > 0 {
+        info!(count = reclaim.requeued, "reclaimed stale {}", kind);
+    }
+    if reclaim.quarantined > 0 {
+        warn!(
+            count = reclaim.quarantined,
+            max_reclaims = db::MAX_BLOCK_RECLAIMS,
+            "quarantined repeatedly stalled {}",
+            kind
+        );
+    }
+}
+
 pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
     let mut app = Router::new()
         .route("/ws", get(websocket::handler_ws))
         .route("/api/status", get(routes_status::handler_api_status))
+        .route(
+            "/api/status/checkpoint",
+            get(routes_status::handler_api_status_checkpoint),
+        )
         .route("/api/docs", get(routes_docs::handler_api_docs))
         .route(
             "/api/docs/search",
             get(routes_docs::handler_api_docs_search),
         )
+        .route("/api/docs/forms", get(routes_docs::handler_api_docs_forms))
         .route(
             "/api/docs/roadmaps/{slug}",
             get(routes_docs::handler_api_doc_roadmap),
@@ -242,6 +312,10 @@ pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
             get(routes_status::handler_api_ws_snapshot),
         )
         .route("/api/fleet", get(routes_fleet::handler_api_fleet))
+        .route(
+            "/api/fleet/topology",
+            get(routes_fleet::handler_api_fleet_topology),
+        )
         .route(
             "/api/searches",
             get(routes_searches::handler_api_searches_list)
@@ -252,6 +326,10 @@ pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
             get(routes_searches::handler_api_searches_get)
                 .delete(routes_searches::handler_api_searches_stop),
         )
+        .route(
+            "/api/searches/stop-by-form",
+            post(routes_searches::handler_api_searches_stop_by_form),
+        )
         .route(
             "/api/searches/{id}/pause",
             post(routes_searches::handler_api_searches_pause),
@@ -260,10 +338,18 @@ pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
             "/api/searches/{id}/resume",
             post(routes_searches::handler_api_searches_resume),
         )
+        .route(
+            "/api/searches/{id}/throughput",
+            get(routes_observability::handler_search_throughput),
+        )
         .route(
             "/api/fleet/workers/{worker_id}/stop",
             post(routes_fleet::handler_fleet_worker_stop),
         )
+        .route(
+            "/api/fleet/workers/{worker_id}/release-blocks",
+            post(routes_fleet::handler_fleet_worker_release_blocks),
+        )
         .route(
             "/api/search_jobs",
             get(routes_jobs::handler_api_search_jobs_list)
@@ -273,6 +359,10 @@ pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
             "/api/search_jobs/{id}",
             get(routes_jobs::handler_api_search_job_get),
         )
+        .route(
+            "/api/search_jobs/{id}/coverage",
+            get(routes_jobs::handler_api_search_job_coverage),
+        )
         .route(
             "/api/search_jobs/{id}/cancel",
             post(routes_jobs::handler_api_search_job_cancel),
@@ -302,6 +392,14 @@ pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
             "/api/observability/catalog",
             get(routes_observability::handler_catalog),
         )
+        .route(
+            "/api/observability/efficiency",
+            get(routes_observability::handler_efficiency),
+        )
+        .route(
+            "/api/observability/snapshot",
+            get(routes_observability::handler_snapshot),
+        )
         .route(
             "/api/agents/tasks",
             get(routes_agents::handler_api_agent_tasks)
@@ -378,6 +476,10 @@ pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
             "/api/projects/{slug}/activate",
             post(routes_projects::handler_api_project_activate),
         )
+        .route(
+            "/api/projects/{slug}/tick",
+            post(routes_projects::handler_api_project_tick),
+        )
         .route(
             "/api/projects/{slug}/pause",
             post(routes_projects::handler_api_project_pause),
@@ -441,6 +543,10 @@ pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
             "/api/strategy/tick",
             post(routes_strategy::handler_strategy_tick),
         )
+        .route(
+            "/api/strategy/calibrate",
+            post(routes_strategy::handler_strategy_calibrate),
+        )
         .route(
             "/api/strategy/ai-engine",
             get(routes_strategy::handler_ai_engine_status),
@@ -467,10 +573,18 @@ pub fn build_router(state: Arc<AppState>, static_dir: Option<&Path>) -> Router {
             "/api/primes",
             get(routes_primes::handler_api_primes_list),
         )
+        .route(
+            "/api/primes/search",
+            get(routes_primes::handler_api_primes_search),
+        )
         .route(
             "/api/primes/{id}",
             get(routes_primes::handler_api_prime_get),
         )
+        .route(
+            "/api/primes/{id}/decimal",
+            get(routes_primes::handler_api_prime_decimal),
+        )
         // Schedule CRUD API (Phase 6: replaces Supabase table access)
         .route(
             "/api/schedules",
@@ -597,6 +711,7 @@ pub async fn run(
     database_url: &str,
     checkpoint_path: &Path,
     static_dir: Option<&Path>,
+    tls: Option<&TlsConfig>,
 ) -> Result<()> {
     let database = db::Database::connect(database_url).await?;
     let (ws_tx, _) = tokio::sync::broadcast::channel::<String>(256);
@@ -632,6 +747,9 @@ pub async fn run(
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(1825);
+        let analyze_enabled = std::env::var("OBS_ANALYZE_ENABLED")
+            .map(|v| !matches!(v.to_lowercase().as_str(), "0" | "false" | "no" | "off"))
+            .unwrap_or(true);
         loop {
             interval.tick().await;
             let tick_now = std::time::Instant::now();
@@ -649,37 +767,44 @@ pub async fn run(
                 Err(e) => warn!(error = %e, "failed to rotate budget periods"),
                 _ => {}
             }
-            match prune_state.db.reclaim_stale_blocks(120).await {
-                Ok(n) if n > 0 => info!(count = n, "reclaimed stale work blocks"),
+            let max_reclaims = db::MAX_BLOCK_RECLAIMS;
+            match prune_state.db.reclaim_stale_blocks(120, max_reclaims).await {
+                Ok(r) => log_block_reclaim("work blocks", r),
                 Err(e) => warn!(error = %e, "failed to reclaim stale blocks"),
-                _ => {}
             }
             // Operator blocks get a 24-hour timeout (86400s) vs 2-min for internal workers
-            match prune_state.db.reclaim_stale_operator_blocks(86400).await {
-                Ok(n) if n > 0 => info!(count = n, "reclaimed stale operator blocks"),
+            match prune_state
+                .db
+                .reclaim_stale_operator_blocks(86400, max_reclaims)
+                .await
+            {
+                Ok(r) => log_block_reclaim("operator blocks", r),
                 Err(e) => warn!(error = %e, "failed to reclaim stale operator blocks"),
-                _ => {}
             }
 
             // ── Verification pipeline: queue unverified operator blocks ──
             match prune_state.db.get_unverified_operator_blocks(20).await {
                 Ok(blocks) => {
                     for block in blocks {
-                        // Look up operator trust level
-                        let trust_level = if let Some(vol_id) = block.volunteer_id {
+                        // Look up operator trust level and materialized reliability
+                        let (trust_level, reliability) = if let Some(vol_id) = block.volunteer_id {
                             prune_state
                                 .db
                                 .get_operator_trust(vol_id)
                                 .await
                                 .ok()
                                 .flatten()
-                                .map(|t| t.trust_level)
-                                .unwrap_or(1)
+                                .map(|t| (t.trust_level, t.reliability_score))
+                                .unwrap_or((1, None))
                         } else {
-                            1
+                            (1, None)
                         };
 
-                        let quorum = verify::required_quorum(trust_level, &block.search_type);
+                        let quorum = verify::required_quorum_with_reliability(
+                            trust_level,
+                            reliability,
+                            &block.search_type,
+                        );
 
                         if quorum >= 2 {
                             // Check if already queued for verification
@@ -743,9 +868,10 @@ pub async fn run(
             // Refresh node reliability scores every 5 minutes
             if last_reliability_refresh.elapsed() >= Duration::from_secs(300) {
                 last_reliability_refresh = std::time::Instant::now();
-                // Node reliability is computed on-the-fly via SQL function,
-                // so no explicit refresh needed. This is a placeholder for
-                // future batch refresh of materialized reliability data.
+                // Materialize the decayed per-operator score read by quorum assignment.
+                if let Err(e) = prune_state.db.refresh_operator_reliability().await {
+                    warn!(error = %e, "failed to refresh operator reliability scores");
+                }
             }
 
             let fleet_workers = prune_state.get_workers_from_pg().await;
@@ -776,9 +902,16 @@ pub async fn run(
             }
             prune_state.event_bus.flush();
             {
-                let events = prune_state
-                    .event_bus
-                    .recent_events_since(last_event_id, 200);
+                let bus = &prune_state.event_bus;
+                let since = bus.recent_events_since(last_event_id, bus.capacity());
+                if since.dropped > 0 {
+                    warn!(
+                        dropped = since.dropped,
+                        capacity = bus.capacity(),
+                        "event ring lapped between log ticks; raise EVENT_BUS_CAPACITY"
+                    );
+                }
+                let events = since.events;
                 if let Some(last) = events.last() {
                     last_event_id = last.id;
                 }
@@ -788,26 +921,7 @@ pub async fn run(
                     }
                     let logs: Vec<db::SystemLogEntry> = events
                         .into_iter()
-                        .map(|e| {
-                            let level = match e.kind.as_str() {
-                                "error" => "error",
-                                "warning" => "warn",
-                                _ => "info",
-                            };
-                            let ts = std::time::SystemTime::UNIX_EPOCH
-                                + std::time::Duration::from_millis(e.timestamp_ms);
-                            db::SystemLogEntry {
-                                ts: DateTime::<Utc>::from(ts),
-                                level: level.to_string(),
-                                source: "coordinator".to_string(),
-                                component: "event_bus".to_string(),
-                                message: e.message,
-                                worker_id: None,
-                                search_job_id: None,
-                                search_id: None,
-                                context: Some(serde_json::json!({"kind": e.kind, "elapsed_secs": e.elapsed_secs})),
-                            }
-                        })
+                        .map(|e| db::SystemLogEntry::from_event(e, "coordinator", None))
                         .collect();
                     if let Err(e) = prune_state.db.insert_system_logs(&logs).await {
                         warn!(error = %e, "failed to persist event logs");
@@ -852,7 +966,6 @@ pub async fn run(
                     .search_jobs_active
                     .set(active as i64);
             }
-            let mut block_summary = None;
             if let Ok(summary) = prune_state.db.get_all_block_summary().await {
                 prune_state
                     .prom_metrics
@@ -862,367 +975,39 @@ pub async fn run(
                     .prom_metrics
                     .work_blocks_claimed
                     .set(summary.claimed);
-                block_summary = Some(summary);
             }
 
             *lock_or_recover(&prune_state.coordinator_metrics) = Some(hw.clone());
 
             if last_metrics_sample.elapsed() >= Duration::from_secs(60) {
                 last_metrics_sample = std::time::Instant::now();
-                let now = Utc::now();
-                let mut samples: Vec<db::MetricSample> = Vec::new();
-
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "coordinator".to_string(),
-                    metric: "coordinator.cpu_usage_percent".to_string(),
-                    value: hw.cpu_usage_percent as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "coordinator".to_string(),
-                    metric: "coordinator.tick_interval_ms".to_string(),
-                    value: tick_interval_ms as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "coordinator".to_string(),
-                    metric: "coordinator.tick_drift_ms".to_string(),
-                    value: tick_drift_ms as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "coordinator".to_string(),
-                    metric: "coordinator.memory_usage_percent".to_string(),
-                    value: hw.memory_usage_percent as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "coordinator".to_string(),
-                    metric: "coordinator.load_avg_1m".to_string(),
-                    value: hw.load_avg_1m,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "coordinator".to_string(),
-                    metric: "coordinator.load_avg_5m".to_string(),
-                    value: hw.load_avg_5m,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "coordinator".to_string(),
-                    metric: "coordinator.load_avg_15m".to_string(),
-                    value: hw.load_avg_15m,
-                    labels: None,
-                });
-
-                let total_cores: i64 = fleet_workers.iter().map(|w| w.cores as i64).sum();
-                let total_tested: i64 = fleet_workers.iter().map(|w| w.tested as i64).sum();
-                let total_found: i64 = fleet_workers.iter().map(|w| w.found as i64).sum();
-                let max_heartbeat_age: i64 = fleet_workers
-                    .iter()
-                    .map(|w| w.last_heartbeat_secs_ago as i64)
-                    .max()
-                    .unwrap_or(0);
-                let avg_heartbeat_age: f64 = if fleet_workers.is_empty() {
-                    0.0
-                } else {
-                    fleet_workers
-                        .iter()
-                        .map(|w| w.last_heartbeat_secs_ago as f64)
-                        .sum::<f64>()
-                        / fleet_workers.len() as f64
-                };
-
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "fleet".to_string(),
-                    metric: "fleet.workers_connected".to_string(),
-                    value: fleet_workers.len() as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "fleet".to_string(),
-                    metric: "fleet.total_cores".to_string(),
-                    value: total_cores as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "fleet".to_string(),
-                    metric: "fleet.total_tested".to_string(),
-                    value: total_tested as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "fleet".to_string(),
-                    metric: "fleet.total_found".to_string(),
-                    value: total_found as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "fleet".to_string(),
-                    metric: "fleet.max_heartbeat_age_secs".to_string(),
-                    value: max_heartbeat_age as f64,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "fleet".to_string(),
-                    metric: "fleet.avg_heartbeat_age_secs".to_string(),
-                    value: avg_heartbeat_age,
-                    labels: None,
-                });
-
-                if let Some(summary) = &block_summary {
-                    samples.push(db::MetricSample {
-                        ts: now,
-                        scope: "fleet".to_string(),
-                        metric: "fleet.work_blocks_available".to_string(),
-                        value: summary.available as f64,
-                        labels: None,
-                    });
-                    samples.push(db::MetricSample {
-                        ts: now,
-                        scope: "fleet".to_string(),
-                        metric: "fleet.work_blocks_claimed".to_string(),
-                        value: summary.claimed as f64,
-                        labels: None,
-                    });
-                    samples.push(db::MetricSample {
-                        ts: now,
-                        scope: "fleet".to_string(),
-                        metric: "fleet.work_blocks_completed".to_string(),
-                        value: summary.completed as f64,
-                        labels: None,
-                    });
-                    samples.push(db::MetricSample {
-                        ts: now,
-                        scope: "fleet".to_string(),
-                        metric: "fleet.work_blocks_failed".to_string(),
-                        value: summary.failed as f64,
-                        labels: None,
-                    });
-                    samples.push(db::MetricSample {
-                        ts: now,
-                        scope: "fleet".to_string(),
-                        metric: "fleet.block_total_tested".to_string(),
-                        value: summary.total_tested as f64,
-                        labels: None,
-                    });
-                    samples.push(db::MetricSample {
-                        ts: now,
-                        scope: "fleet".to_string(),
-                        metric: "fleet.block_total_found".to_string(),
-                        value: summary.total_found as f64,
-                        labels: None,
-                    });
+                let events = snapshot::EventCounts::from_kinds(&event_counts);
+                event_counts.clear();
+                let snapshot = snapshot::MetricsSnapshot::assemble(
+                    &prune_state.db,
+                    &fleet_workers,
+                    Some(hw.clone()),
+                    events,
+                )
+                .await;
+                let include_workers = last_worker_sample.elapsed() >= Duration::from_secs(120);
+                if include_workers {
+                    last_worker_sample = std::time::Instant::now();
                 }
-
-                if let Ok(jobs) = prune_state.db.get_search_jobs().await {
-                    let active = jobs.iter().filter(|j| j.status == "running").count();
+                let mut samples = snapshot.to_samples(include_workers);
+                for (metric, value) in [
+                    ("coordinator.tick_interval_ms", tick_interval_ms),
+                    ("coordinator.tick_drift_ms", tick_drift_ms),
+                ] {
                     samples.push(db::MetricSample {
-                        ts: now,
-                        scope: "fleet".to_string(),
-                        metric: "fleet.search_jobs_active".to_string(),
-                        value: active as f64,
+                        ts: snapshot.ts,
+                        scope: "coordinator".to_string(),
+                        metric: metric.to_string(),
+                        value: value as f64,
                         labels: None,
                     });
                 }
 
-                if let Ok(jobs) = prune_state.db.get_recent_search_jobs(24, 50).await {
-                    for job in jobs {
-                        let summary = match prune_state.db.get_job_block_summary(job.id).await {
-                            Ok(s) => s,
-                            Err(_) => continue,
-                        };
-                        let total_blocks = summary.available
-                            + summary.claimed
-                            + summary.completed
-                            + summary.failed;
-                        let completion_pct = if total_blocks > 0 {
-                            (summary.completed as f64 / total_blocks as f64) * 100.0
-                        } else {
-                            0.0
-                        };
-                        let labels = serde_json::json!({
-                            "job_id": job.id.to_string(),
-                            "search_type": job.search_type,
-                            "status": job.status,
-                        });
-
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "search_job".to_string(),
-                            metric: "search_job.blocks_available".to_string(),
-                            value: summary.available as f64,
-                            labels: Some(labels.clone()),
-                        });
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "search_job".to_string(),
-                            metric: "search_job.blocks_claimed".to_string(),
-                            value: summary.claimed as f64,
-                            labels: Some(labels.clone()),
-                        });
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "search_job".to_string(),
-                            metric: "search_job.blocks_completed".to_string(),
-                            value: summary.completed as f64,
-                            labels: Some(labels.clone()),
-                        });
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "search_job".to_string(),
-                            metric: "search_job.blocks_failed".to_string(),
-                            value: summary.failed as f64,
-                            labels: Some(labels.clone()),
-                        });
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "search_job".to_string(),
-                            metric: "search_job.completion_pct".to_string(),
-                            value: completion_pct,
-                            labels: Some(labels.clone()),
-                        });
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "search_job".to_string(),
-                            metric: "search_job.total_tested".to_string(),
-                            value: summary.total_tested as f64,
-                            labels: Some(labels.clone()),
-                        });
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "search_job".to_string(),
-                            metric: "search_job.total_found".to_string(),
-                            value: summary.total_found as f64,
-                            labels: Some(labels.clone()),
-                        });
-                    }
-                }
-
-                let error_count = *event_counts.get("error").unwrap_or(&0) as f64;
-                let warning_count = *event_counts.get("warning").unwrap_or(&0) as f64;
-                let prime_count = *event_counts.get("prime").unwrap_or(&0) as f64;
-                let milestone_count = *event_counts.get("milestone").unwrap_or(&0) as f64;
-                let search_start_count = *event_counts.get("search_start").unwrap_or(&0) as f64;
-                let search_done_count = *event_counts.get("search_done").unwrap_or(&0) as f64;
-                let total_events: f64 = event_counts.values().copied().sum::<i64>() as f64;
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "events".to_string(),
-                    metric: "events.total_count".to_string(),
-                    value: total_events,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "events".to_string(),
-                    metric: "events.error_count".to_string(),
-                    value: error_count,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "events".to_string(),
-                    metric: "events.warning_count".to_string(),
-                    value: warning_count,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "events".to_string(),
-                    metric: "events.prime_count".to_string(),
-                    value: prime_count,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "events".to_string(),
-                    metric: "events.milestone_count".to_string(),
-                    value: milestone_count,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "events".to_string(),
-                    metric: "events.search_start_count".to_string(),
-                    value: search_start_count,
-                    labels: None,
-                });
-                samples.push(db::MetricSample {
-                    ts: now,
-                    scope: "events".to_string(),
-                    metric: "events.search_done_count".to_string(),
-                    value: search_done_count,
-                    labels: None,
-                });
-                event_counts.clear();
-
-                if last_worker_sample.elapsed() >= Duration::from_secs(120) {
-                    last_worker_sample = std::time::Instant::now();
-                    for w in &fleet_workers {
-                        if let Some(m) = &w.metrics {
-                            let labels = serde_json::json!({
-                                "worker_id": w.worker_id,
-                                "hostname": w.hostname,
-                                "search_type": w.search_type,
-                            });
-                            samples.push(db::MetricSample {
-                                ts: now,
-                                scope: "worker".to_string(),
-                                metric: "worker.cpu_usage_percent".to_string(),
-                                value: m.cpu_usage_percent as f64,
-                                labels: Some(labels.clone()),
-                            });
-                            samples.push(db::MetricSample {
-                                ts: now,
-                                scope: "worker".to_string(),
-                                metric: "worker.memory_usage_percent".to_string(),
-                                value: m.memory_usage_percent as f64,
-                                labels: Some(labels.clone()),
-                            });
-                            samples.push(db::MetricSample {
-                                ts: now,
-                                scope: "worker".to_string(),
-                                metric: "worker.disk_usage_percent".to_string(),
-                                value: m.disk_usage_percent as f64,
-                                labels: Some(labels.clone()),
-                            });
-                        }
-                        let labels = serde_json::json!({
-                            "worker_id": w.worker_id,
-                            "hostname": w.hostname,
-                            "search_type": w.search_type,
-                        });
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "worker".to_string(),
-                            metric: "worker.tested".to_string(),
-                            value: w.tested as f64,
-                            labels: Some(labels.clone()),
-                        });
-                        samples.push(db::MetricSample {
-                            ts: now,
-                            scope: "worker".to_string(),
-                            metric: "worker.found".to_string(),
-                            value: w.found as f64,
-                            labels: Some(labels.clone()),
-                        });
-                    }
-                }
-
                 if let Err(e) = prune_state.db.insert_metric_samples(&samples).await {
                     warn!(error = %e, count = samples.len(), "failed to persist metric samples");
                 }
@@ -1277,6 +1062,11 @@ pub async fn run(
                 if let Err(e) = prune_state.db.refresh_materialized_views().await {
                     warn!(error = %e, "failed to refresh materialized views");
                 }
+                if analyze_enabled {
+                    if let Err(e) = prune_state.db.analyze_hot_tables().await {
+                        warn!(error = %e, "failed to analyze hot tables");
+                    }
+                }
             }
         }
     });
@@ -1314,11 +1104,19 @@ pub async fn run(
                             warn!(prime_id = prime.id, error = %e, "failed to mark prime verified");
                         }
                     }
-                    Ok(verify::VerifyResult::Failed { reason }) => {
-                        warn!(prime_id = prime.id, reason = %reason, "auto-verify failed");
+                    Ok(verify::VerifyResult::Failed {
+                        reason,
+                        reason_code,
+                    }) => {
+                        warn!(
+                            prime_id = prime.id,
+                            reason = %reason,
+                            code = reason_code.as_str(),
+                            "auto-verify failed"
+                        );
                         if let Err(e) = verify_state
                             .db
-                            .mark_verification_failed(prime.id, &reason)
+                            .mark_verification_failed(prime.id, &reason, reason_code.as_str())
                             .await
                         {
                             warn!(prime_id = prime.id, error = %e, "failed to mark prime verification failed");
@@ -1327,6 +1125,24 @@ pub async fn run(
                     Ok(verify::VerifyResult::Skipped { reason }) => {
                         tracing::debug!(prime_id = prime.id, reason = %reason, "auto-verify skipped");
                     }
+                    Ok(verify::VerifyResult::Errored {
+                        reason,
+                        reason_code,
+                    }) => {
+                        warn!(
+                            prime_id = prime.id,
+                            reason = %reason,
+                            code = reason_code.as_str(),
+                            "auto-verify errored"
+                        );
+                        if let Err(e) = verify_state
+                            .db
+                            .record_verification_error(prime.id, reason_code.as_str())
+                            .await
+                        {
+                            warn!(prime_id = prime.id, error = %e, "failed to record verification error");
+                        }
+                    }
                     Err(e) => {
                         warn!(prime_id = prime.id, error = %e, "auto-verify task panicked");
                     }
@@ -1557,11 +1373,21 @@ pub async fn run(
     });
 
     let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
-    info!(port, "dashboard running");
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    info!(port, tls = tls.is_some(), "dashboard running");
+    match tls {
+        Some(tls) => {
+            let listener = tls::TlsListener::bind(addr, tls).await?;
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal())
+                .await?;
+        }
+        None => {
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal())
+                .await?;
+        }
+    }
     info!("dashboard shut down gracefully");
     Ok(())
 }
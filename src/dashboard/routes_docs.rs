@@ -1,4 +1,5 @@
-//! Documentation API — serves markdown docs, roadmaps, and CLAUDE.md agent files.
+//! Documentation API — serves markdown docs, roadmaps, and CLAUDE.md agent files,
+//! plus the per-form parameter schema at `/api/docs/forms`.
 
 use axum::extract::{Path as AxumPath, Query};
 use axum::http::StatusCode;
@@ -176,6 +177,11 @@ pub(super) async fn handler_api_doc_roadmap(AxumPath(slug): AxumPath<String>) ->
     }
 }
 
+/// Parameter schema for every search form; see [`crate::search_params::FORMS`].
+pub(super) async fn handler_api_docs_forms() -> impl IntoResponse {
+    Json(serde_json::json!({ "forms": crate::search_params::FORMS }))
+}
+
 pub(super) async fn handler_api_docs() -> impl IntoResponse {
     let docs_dir = std::path::Path::new("docs");
     if !docs_dir.exists() {
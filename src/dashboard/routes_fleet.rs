@@ -2,16 +2,23 @@
 //!
 //! Provides fleet-level views of the worker network. Deployment management
 //! has been removed — all coordination is now PostgreSQL-backed.
+//!
+//! `POST /api/fleet/workers/{worker_id}/release-blocks` takes work back from a
+//! known-bad worker: its claimed blocks return to the pool and its completions
+//! from the last `reverify_hours` (default 24, 0 = none) are queued for
+//! re-verification by another worker. It requires an admin session.
 
-use axum::extract::{Path as AxumPath, State};
+use axum::extract::{Path as AxumPath, Query, State};
+use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{info, warn};
 
+use super::middleware_auth::RequireAdmin;
 use super::AppState;
-use crate::{fleet, metrics};
+use crate::{fleet, gwnum, metrics};
 
 /// Per-host server summary, grouping workers by hostname and classifying
 /// hosts as either "service" (coordinator) or "compute" (worker nodes).
@@ -26,6 +33,8 @@ pub(super) struct ServerInfo {
     total_tested: u64,
     total_found: u64,
     uptime_secs: u64,
+    /// Best startup benchmark score among the host's workers.
+    benchmark_score: Option<f64>,
 }
 
 #[derive(Serialize)]
@@ -63,6 +72,7 @@ pub(super) fn build_fleet_data(
         total_tested: 0,
         total_found: 0,
         uptime_secs: 0,
+        benchmark_score: None,
     });
 
     // Each unique worker hostname becomes a "compute" server
@@ -85,6 +95,10 @@ pub(super) fn build_fleet_data(
                 .map(|w| w.uptime_secs)
                 .max()
                 .unwrap_or(0),
+            benchmark_score: host_workers
+                .iter()
+                .filter_map(|w| w.benchmark_score)
+                .reduce(f64::max),
         });
     }
 
@@ -108,6 +122,31 @@ pub(super) async fn handler_api_fleet(State(state): State<Arc<AppState>>) -> imp
     ))
 }
 
+/// Fleet topology: the per-host server layout plus the SIMD features the
+/// coordinator detected for GWNUM's FFT code-path selection.
+#[derive(Serialize)]
+pub(super) struct TopologyData {
+    coordinator: String,
+    cpu_features: gwnum::GwFeatures,
+    fft_path: &'static str,
+    servers: Vec<ServerInfo>,
+}
+
+pub(super) async fn handler_api_fleet_topology(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    let workers = state.get_workers_from_pg().await;
+    let coord_metrics = lock_or_recover(&state.coordinator_metrics).clone();
+    let fleet = build_fleet_data(&workers, &state.coordinator_hostname, &coord_metrics);
+    let cpu_features = gwnum::detect_features();
+    Json(TopologyData {
+        coordinator: state.coordinator_hostname.clone(),
+        cpu_features,
+        fft_path: cpu_features.fft_path(),
+        servers: fleet.servers,
+    })
+}
+
 pub(super) async fn handler_fleet_worker_stop(
     State(state): State<Arc<AppState>>,
     AxumPath(worker_id): AxumPath<String>,
@@ -119,6 +158,61 @@ pub(super) async fn handler_fleet_worker_stop(
     Json(serde_json::json!({"ok": true, "worker_id": worker_id}))
 }
 
+/// Longest re-verification window accepted by `release-blocks` (30 days).
+const MAX_REVERIFY_HOURS: i64 = 720;
+
+#[derive(Deserialize)]
+pub(super) struct ReleaseBlocksQuery {
+    reverify_hours: Option<i64>,
+}
+
+pub(super) async fn handler_fleet_worker_release_blocks(
+    _auth: RequireAdmin,
+    State(state): State<Arc<AppState>>,
+    AxumPath(worker_id): AxumPath<String>,
+    Query(params): Query<ReleaseBlocksQuery>,
+) -> impl IntoResponse {
+    let reverify_hours = params.reverify_hours.unwrap_or(24);
+    if !(0..=MAX_REVERIFY_HOURS).contains(&reverify_hours) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": format!("reverify_hours must be between 0 and {}", MAX_REVERIFY_HOURS)
+            })),
+        )
+            .into_response();
+    }
+    match state
+        .db
+        .release_worker_blocks(&worker_id, reverify_hours)
+        .await
+    {
+        Ok(release) => {
+            info!(
+                worker_id,
+                released = release.released.len(),
+                reverify_queued = release.reverify_queued.len(),
+                "released worker blocks"
+            );
+            Json(serde_json::json!({
+                "ok": true,
+                "worker_id": worker_id,
+                "released": release.released,
+                "reverify_queued": release.reverify_queued,
+            }))
+            .into_response()
+        }
+        Err(e) => {
+            warn!(worker_id, error = %e, "failed to release worker blocks");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Failed to release blocks: {}", e)})),
+            )
+                .into_response()
+        }
+    }
+}
+
 fn lock_or_recover<T>(mutex: &std::sync::Mutex<T>) -> std::sync::MutexGuard<'_, T> {
     mutex
         .lock()
@@ -18,12 +18,21 @@ pub(super) struct CreateSearchJobPayload {
     range_end: i64,
     #[serde(default = "default_block_size")]
     block_size: i64,
+    /// Return an identical pending, running or paused job instead of
+    /// creating a duplicate. `false` runs a second copy on purpose, e.g. to
+    /// double-check a range.
+    #[serde(default = "default_reuse_existing")]
+    reuse_existing: bool,
 }
 
 fn default_block_size() -> i64 {
     10_000
 }
 
+fn default_reuse_existing() -> bool {
+    true
+}
+
 pub(super) async fn handler_api_search_jobs_list(
     State(state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
@@ -56,40 +65,73 @@ pub(super) async fn handler_api_search_jobs_create(
             .into_response();
     }
 
-    match state
-        .db
-        .create_search_job(
-            &payload.search_type,
-            &payload.params,
-            payload.range_start,
-            payload.range_end,
-            payload.block_size,
-        )
-        .await
-    {
-        Ok(job_id) => {
-            let num_blocks = ((payload.range_end - payload.range_start) + payload.block_size - 1)
-                / payload.block_size;
-            info!(
-                job_id,
-                search_type = %payload.search_type,
-                range_start = payload.range_start,
-                range_end = payload.range_end,
-                num_blocks,
-                "search job created"
-            );
-            (
-                StatusCode::CREATED,
-                Json(serde_json::json!({"id": job_id, "blocks": num_blocks})),
+    let created = if payload.reuse_existing {
+        state
+            .db
+            .find_or_create_search_job(
+                &payload.search_type,
+                &payload.params,
+                payload.range_start,
+                payload.range_end,
+                payload.block_size,
+            )
+            .await
+    } else {
+        state
+            .db
+            .create_search_job(
+                &payload.search_type,
+                &payload.params,
+                payload.range_start,
+                payload.range_end,
+                payload.block_size,
+            )
+            .await
+            .map(|job_id| (job_id, false))
+    };
+    let (job_id, reused) = match created {
+        Ok(created) => created,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
             )
                 .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": e.to_string()})),
-        )
-            .into_response(),
-    }
+    };
+
+    // A reused job keeps the blocks it was created with, not the payload's.
+    let num_blocks = if reused {
+        match state.db.get_job_block_summary(job_id).await {
+            Ok(s) => s.available + s.claimed + s.completed + s.failed,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": e.to_string()})),
+                )
+                    .into_response()
+            }
+        }
+    } else {
+        ((payload.range_end - payload.range_start) + payload.block_size - 1) / payload.block_size
+    };
+    info!(
+        job_id,
+        search_type = %payload.search_type,
+        range_start = payload.range_start,
+        range_end = payload.range_end,
+        num_blocks,
+        reused,
+        "search job created"
+    );
+    // An identical live job is returned as is, with 200 instead of 201.
+    let status = if reused {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+    let body = serde_json::json!({"id": job_id, "blocks": num_blocks, "reused": reused});
+    (status, Json(body)).into_response()
 }
 
 pub(super) async fn handler_api_search_job_get(
@@ -126,6 +168,29 @@ pub(super) async fn handler_api_search_job_get(
     Json(serde_json::json!({"job": job, "blocks": summary})).into_response()
 }
 
+/// `GET /api/search_jobs/{id}/coverage` — completed, claimed, available and
+/// failed ranges of the job, coalesced, plus any uncovered gaps.
+pub(super) async fn handler_api_search_job_coverage(
+    State(state): State<Arc<AppState>>,
+    AxumPath(id): AxumPath<i64>,
+) -> impl IntoResponse {
+    match state.db.get_job_coverage(id).await {
+        Ok(Some(coverage)) => {
+            Json(serde_json::json!({"id": id, "coverage": coverage})).into_response()
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Search job not found"})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
 pub(super) async fn handler_api_search_job_cancel(
     State(state): State<Arc<AppState>>,
     AxumPath(id): AxumPath<i64>,
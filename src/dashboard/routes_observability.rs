@@ -1,7 +1,8 @@
 //! Observability API — metrics, logs, and reports.
 
+use super::snapshot::{self, EventCounts, MetricsSnapshot};
 use super::AppState;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -31,6 +32,7 @@ pub(super) struct LogsQuery {
     source: Option<String>,
     component: Option<String>,
     worker_id: Option<String>,
+    search_job_id: Option<i64>,
     limit: Option<i64>,
     format: Option<String>,
 }
@@ -48,6 +50,11 @@ pub(super) struct TopWorkersQuery {
     window_minutes: Option<i64>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct ThroughputQuery {
+    window: Option<String>,
+}
+
 fn parse_ts(value: Option<&str>, default: DateTime<Utc>) -> DateTime<Utc> {
     value
         .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
@@ -73,6 +80,22 @@ fn select_rollup(rollup: Option<&str>, from: DateTime<Utc>, to: DateTime<Utc>) -
     }
 }
 
+/// Parse a lookback window like `30m`, `24h`, or `7d`; unparseable values
+/// fall back to 24 hours.
+fn parse_window(window: Option<&str>) -> Duration {
+    let default = Duration::hours(24);
+    let Some(w) = window.map(str::trim).filter(|w| w.len() > 1) else {
+        return default;
+    };
+    let (num, unit) = w.split_at(w.len() - 1);
+    match (num.parse::<i64>(), unit) {
+        (Ok(n), "m") if n > 0 => Duration::minutes(n),
+        (Ok(n), "h") if n > 0 => Duration::hours(n),
+        (Ok(n), "d") if n > 0 => Duration::days(n.min(90)),
+        _ => default,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,6 +129,21 @@ mod tests {
         assert_eq!(select_rollup(Some("day"), from, now), "day");
         assert_eq!(select_rollup(Some("raw"), from, now), "raw");
     }
+
+    #[test]
+    fn parse_window_accepts_minutes_hours_days() {
+        assert_eq!(parse_window(Some("30m")), Duration::minutes(30));
+        assert_eq!(parse_window(Some("24h")), Duration::hours(24));
+        assert_eq!(parse_window(Some("7d")), Duration::days(7));
+    }
+
+    #[test]
+    fn parse_window_defaults_to_24h() {
+        assert_eq!(parse_window(None), Duration::hours(24));
+        assert_eq!(parse_window(Some("h")), Duration::hours(24));
+        assert_eq!(parse_window(Some("-5h")), Duration::hours(24));
+        assert_eq!(parse_window(Some("soon")), Duration::hours(24));
+    }
 }
 pub(super) async fn handler_metrics(
     State(state): State<Arc<AppState>>,
@@ -218,6 +256,7 @@ pub(super) async fn handler_logs(
             q.source.as_deref(),
             q.component.as_deref(),
             q.worker_id.as_deref(),
+            q.search_job_id,
             limit,
         )
         .await;
@@ -280,6 +319,12 @@ pub(super) async fn handler_report(
         .await
         .unwrap_or_default();
 
+    let verification_failures_by_code = state
+        .db
+        .count_verification_failures_by_code(from, to)
+        .await
+        .unwrap_or_default();
+
     let errors_by_level = state
         .db
         .count_system_logs_by_level(from, to)
@@ -342,6 +387,9 @@ pub(super) async fn handler_report(
             "total": primes_total,
             "by_form": primes_by_form,
         },
+        "verification": {
+            "failures_by_code": verification_failures_by_code,
+        },
         "logs": {
             "by_level": errors_by_level,
         },
@@ -382,6 +430,10 @@ pub(super) async fn handler_report(
         for (form, count) in primes_by_form {
             csv.push_str(&format!("{},{}\n", form, count));
         }
+        csv.push_str("\nverification_failure_code,count\n");
+        for (code, count) in verification_failures_by_code {
+            csv.push_str(&format!("{},{}\n", code, count));
+        }
         return (
             StatusCode::OK,
             [("content-type", "text/csv; charset=utf-8")],
@@ -405,8 +457,71 @@ pub(super) async fn handler_top_workers(
     }
 }
 
+pub(super) async fn handler_efficiency(State(state): State<Arc<AppState>>) -> Response {
+    match state.db.get_form_efficiency().await {
+        Ok(forms) => Json(serde_json::json!({ "forms": forms })).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Query failed: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+/// Current coordinator, fleet, job and event metrics in one document, as
+/// the coordinator tick samples them (see [`super::snapshot`]).
+pub(super) async fn handler_snapshot(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let workers = state.get_workers_from_pg().await;
+    let coordinator = super::lock_or_recover(&state.coordinator_metrics).clone();
+    let events = EventCounts::recent(&state.event_bus, snapshot::EVENT_WINDOW_MS);
+    Json(MetricsSnapshot::assemble(&state.db, &workers, coordinator, events).await)
+}
+
 pub(super) async fn handler_catalog() -> impl IntoResponse {
     Json(serde_json::json!({
         "metrics": crate::prom_metrics::Metrics::catalog()
     }))
 }
+
+pub(super) async fn handler_search_throughput(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(q): Query<ThroughputQuery>,
+) -> Response {
+    match state.db.get_search_job(id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Search not found"})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Query failed: {}", e)})),
+            )
+                .into_response()
+        }
+    }
+
+    let to = Utc::now();
+    let from = to - parse_window(q.window.as_deref());
+    match state.db.get_search_throughput(id, from, to).await {
+        Ok(points) => Json(serde_json::json!({
+            "search_id": id,
+            "metric": "search_job.total_tested",
+            "unit": "candidates/sec",
+            "from": from,
+            "to": to,
+            "points": points,
+        }))
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Query failed: {}", e)})),
+        )
+            .into_response(),
+    }
+}
@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use super::middleware_auth::RequireAuth;
 use super::AppState;
-use crate::db::operators::{OperatorRow, WorkerCapabilities};
+use crate::db::operators::{LeaderboardWindow, OperatorRow, OperatorWorkBlock, WorkerCapabilities};
 
 // ── GET /api/volunteer/worker/latest ─────────────────────────────
 
@@ -239,6 +239,8 @@ pub(super) struct WorkerRegisterPayload {
     worker_version: Option<String>,
     #[serde(default)]
     update_channel: Option<String>,
+    #[serde(default)]
+    benchmark_score: Option<f64>,
 }
 
 pub(super) async fn handler_v1_worker_register(
@@ -267,6 +269,7 @@ pub(super) async fn handler_v1_worker_register(
             payload.gpu_vram_gb,
             payload.worker_version.as_deref(),
             payload.update_channel.as_deref(),
+            payload.benchmark_score,
         )
         .await
     {
@@ -333,6 +336,92 @@ pub(super) struct WorkQuery {
     os: Option<String>,
     #[serde(default)]
     arch: Option<String>,
+    /// Registered node making the request; enables core-proportional block
+    /// sizing.
+    #[serde(default)]
+    worker_id: Option<String>,
+    /// Ask for the block's pre-sieved survivors; see [`presieve_block`].
+    #[serde(default)]
+    survivors: Option<bool>,
+}
+
+/// Wall-clock budget for sieving a block inside a work claim, well inside the
+/// router's 30 s request timeout.
+const PRESIEVE_BUDGET: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Sieve a claimed kbn block centrally so the node only runs primality
+/// tests. `None` for other forms, when the sieve fails, or when it is deeper
+/// than [`crate::kbn::DEADLINE_SIEVE_LIMIT`] or does not finish within
+/// [`PRESIEVE_BUDGET`], in which case the node sieves the block itself.
+async fn presieve_block(block: &OperatorWorkBlock) -> Option<crate::kbn::SieveSurvivors> {
+    if block.search_type.as_deref() != Some("kbn") {
+        return None;
+    }
+    let params = block.params.as_ref()?;
+    let k = params["k"].as_u64().unwrap_or(1);
+    let base = params["base"].as_u64().unwrap_or(2) as u32;
+    let sieve_limit = params["sieve_limit"].as_u64().unwrap_or(0);
+    let (start, end) = (block.block_start as u64, block.block_end as u64);
+    let deadline = std::time::Instant::now() + PRESIEVE_BUDGET;
+    let sieved = tokio::task::spawn_blocking(move || {
+        crate::kbn::sieve_survivors(k, base, start, end, sieve_limit, Some(deadline))
+    })
+    .await;
+    match sieved {
+        Ok(Ok(Some(survivors))) => Some(survivors),
+        Ok(Ok(None)) => {
+            tracing::info!(block_id = block.block_id, "Block pre-sieve exceeded its budget");
+            None
+        }
+        Ok(Err(e)) => {
+            tracing::warn!(block_id = block.block_id, error = %e, "Block pre-sieve failed");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(block_id = block.block_id, error = %e, "Block pre-sieve panicked");
+            None
+        }
+    }
+}
+
+/// Claim a block sized to the registered node `node_id`: take the next job
+/// `caps` qualify for and a slice of its `block_size` proportional to the
+/// node's reported cores. Unknown nodes get an unsized claim.
+async fn claim_node_block(
+    state: &AppState,
+    volunteer_id: uuid::Uuid,
+    node_id: &str,
+    caps: &WorkerCapabilities,
+) -> anyhow::Result<Option<OperatorWorkBlock>> {
+    let nodes = state.db.get_operator_nodes(volunteer_id).await?;
+    let Some(node) = nodes.iter().find(|n| n.worker_id == node_id) else {
+        return state.db.claim_operator_block(volunteer_id, caps).await;
+    };
+    let Some(job_id) = state.db.next_operator_job(caps).await? else {
+        return Ok(None);
+    };
+    let Some(job) = state.db.get_search_job(job_id).await? else {
+        return Ok(None);
+    };
+    let cores = node.cores.unwrap_or(caps.cores);
+    let target = crate::db::operators::operator_block_size(job.block_size, cores);
+    state
+        .db
+        .claim_operator_block_sized(job_id, node_id, target)
+        .await
+}
+
+/// Claim a pending verification block for one of the operator's nodes.
+async fn claim_node_verification(
+    state: &AppState,
+    volunteer_id: uuid::Uuid,
+    node_id: &str,
+) -> anyhow::Result<Option<crate::db::VerificationBlock>> {
+    let nodes = state.db.get_operator_nodes(volunteer_id).await?;
+    if !nodes.iter().any(|n| n.worker_id == node_id) {
+        return Ok(None);
+    }
+    state.db.claim_verification_block(node_id).await
 }
 
 pub(super) async fn handler_v1_work(
@@ -353,8 +442,39 @@ pub(super) async fn handler_v1_work(
         arch: query.arch.filter(|v| !v.trim().is_empty()),
     };
 
+    // A node's own operator may not verify it; other operators' completed
+    // blocks awaiting quorum are handed out before fresh work.
+    if let Some(node_id) = query.worker_id.as_deref() {
+        match claim_node_verification(&state, vol.id, node_id).await {
+            Ok(Some(v)) => {
+                return (
+                    StatusCode::OK,
+                    Json(serde_json::json!({
+                        "block_id": v.original_block_id,
+                        "verification_id": v.verification_id,
+                        "search_job_id": v.search_job_id,
+                        "search_type": v.search_type,
+                        "params": v.params,
+                        "block_start": v.block_start,
+                        "block_end": v.block_end,
+                    })),
+                );
+            }
+            Ok(None) => {}
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": format!("Work claim failed: {}", e)})),
+                );
+            }
+        }
+    }
+
     let claim_start = std::time::Instant::now();
-    let claim_result = state.db.claim_operator_block(vol.id, &caps).await;
+    let claim_result = match query.worker_id.as_deref() {
+        Some(node_id) => claim_node_block(&state, vol.id, node_id, &caps).await,
+        None => state.db.claim_operator_block(vol.id, &caps).await,
+    };
     state
         .prom_metrics
         .db_query_duration
@@ -367,22 +487,31 @@ pub(super) async fn handler_v1_work(
             // Set quorum based on volunteer trust level and search form
             if let Some(ref search_type) = block.search_type {
                 let trust = state.db.get_operator_trust(vol.id).await.ok().flatten();
-                let trust_level = trust.map(|t| t.trust_level).unwrap_or(1);
-                let quorum = crate::verify::required_quorum(trust_level, search_type);
+                let (trust_level, reliability) = trust
+                    .map(|t| (t.trust_level, t.reliability_score))
+                    .unwrap_or((1, None));
+                let quorum = crate::verify::required_quorum_with_reliability(
+                    trust_level,
+                    reliability,
+                    search_type,
+                );
                 let _ = state.db.set_block_quorum(block.block_id, quorum).await;
             }
 
-            (
-                StatusCode::OK,
-                Json(serde_json::json!({
-                    "block_id": block.block_id,
-                    "search_job_id": block.search_job_id,
-                    "search_type": block.search_type,
-                    "params": block.params,
-                    "block_start": block.block_start,
-                    "block_end": block.block_end,
-                })),
-            )
+            let mut body = serde_json::json!({
+                "block_id": block.block_id,
+                "search_job_id": block.search_job_id,
+                "search_type": block.search_type,
+                "params": block.params,
+                "block_start": block.block_start,
+                "block_end": block.block_end,
+            });
+            if query.survivors.unwrap_or(false) {
+                if let Some(survivors) = presieve_block(&block).await {
+                    body["survivors"] = serde_json::json!(survivors);
+                }
+            }
+            (StatusCode::OK, Json(body))
         }
         Ok(None) => (StatusCode::NO_CONTENT, Json(serde_json::json!(null))),
         Err(e) => (
@@ -401,6 +530,14 @@ pub(super) struct ResultPayload {
     found: i64,
     #[serde(default)]
     primes: Vec<PrimeReportPayload>,
+    /// Client-generated key; a repeat submission with the same key replays
+    /// the original response without re-applying the result.
+    #[serde(default)]
+    idempotency_key: Option<uuid::Uuid>,
+    /// Set when the block was handed out as a quorum re-run; the result is
+    /// compared with the original instead of completing the block.
+    #[serde(default)]
+    verification_id: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -424,28 +561,78 @@ pub(super) async fn handler_v1_result(
         Err(e) => return e,
     };
 
-    // Complete the work block and record duration histogram
-    let block_timing = match state
-        .db
-        .submit_operator_result(payload.block_id, payload.tested, payload.found)
-        .await
-    {
-        Ok(timing) => timing,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Result submission failed: {}", e)})),
-            );
+    // Reserve the idempotency key; a repeat replays the stored response
+    if let Some(key) = payload.idempotency_key {
+        match state
+            .db
+            .reserve_operator_result_key(vol.id, key, payload.block_id)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                return match state.db.get_operator_result_response(vol.id, key).await {
+                    Ok(Some(response)) => (StatusCode::OK, Json(response)),
+                    Ok(None) => (
+                        StatusCode::CONFLICT,
+                        Json(
+                            serde_json::json!({"error": "Submission with this idempotency_key is in progress"}),
+                        ),
+                    ),
+                    Err(e) => (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(
+                            serde_json::json!({"error": format!("Idempotency lookup failed: {}", e)}),
+                        ),
+                    ),
+                };
+            }
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": format!("Idempotency check failed: {}", e)})),
+                );
+            }
+        }
+    }
+
+    let mut outcome = None;
+    if let Some(verification_id) = payload.verification_id {
+        match submit_node_verification(&state, vol.id, verification_id, &payload).await {
+            Ok(o) => outcome = Some(o),
+            Err(e) => {
+                if let Some(key) = payload.idempotency_key {
+                    let _ = state.db.release_operator_result_key(vol.id, key).await;
+                }
+                return e;
+            }
+        }
+    } else {
+        // Complete the work block and record duration histogram
+        let block_timing = match state
+            .db
+            .submit_operator_result(payload.block_id, payload.tested, payload.found)
+            .await
+        {
+            Ok(timing) => timing,
+            Err(e) => {
+                if let Some(key) = payload.idempotency_key {
+                    let _ = state.db.release_operator_result_key(vol.id, key).await;
+                }
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": format!("Result submission failed: {}", e)})),
+                );
+            }
+        };
+        if let Some((duration_secs, search_type)) = block_timing {
+            state
+                .prom_metrics
+                .work_block_duration
+                .get_or_create(&crate::prom_metrics::FormLabel {
+                    form: search_type,
+                })
+                .observe(duration_secs);
         }
-    };
-    if let Some((duration_secs, search_type)) = block_timing {
-        state
-            .prom_metrics
-            .work_block_duration
-            .get_or_create(&crate::prom_metrics::FormLabel {
-                form: search_type,
-            })
-            .observe(duration_secs);
     }
 
     // Record any discovered primes
@@ -494,10 +681,65 @@ pub(super) async fn handler_v1_result(
         .grant_credit(vol.id, payload.block_id, credit, "block_completed")
         .await;
 
-    // Record valid result for trust scoring
-    let _ = state.db.record_valid_result(vol.id).await;
+    // Record valid result for trust scoring; a disputed re-run has already
+    // reset this operator's trust
+    if !matches!(outcome, Some(crate::db::VerificationOutcome::Conflict { .. })) {
+        let _ = state.db.record_valid_result(vol.id).await;
+    }
 
-    (StatusCode::OK, Json(serde_json::json!({"ok": true})))
+    let response = match outcome {
+        Some(crate::db::VerificationOutcome::Conflict { .. }) => {
+            serde_json::json!({"ok": true, "verification": "conflict"})
+        }
+        Some(crate::db::VerificationOutcome::Matched) => {
+            serde_json::json!({"ok": true, "verification": "matched"})
+        }
+        None => serde_json::json!({"ok": true}),
+    };
+    if let Some(key) = payload.idempotency_key {
+        let _ = state
+            .db
+            .complete_operator_result_key(vol.id, key, &response)
+            .await;
+    }
+    (StatusCode::OK, Json(response))
+}
+
+/// Compare a quorum re-run with the original result.
+///
+/// The verification must still be claimed by one of the operator's nodes.
+async fn submit_node_verification(
+    state: &AppState,
+    volunteer_id: uuid::Uuid,
+    verification_id: i64,
+    payload: &ResultPayload,
+) -> Result<crate::db::VerificationOutcome, (StatusCode, Json<serde_json::Value>)> {
+    let internal = |e: anyhow::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Result submission failed: {}", e)})),
+        )
+    };
+    let worker = state
+        .db
+        .claimed_verification_worker(verification_id)
+        .await
+        .map_err(internal)?;
+    let nodes = state
+        .db
+        .get_operator_nodes(volunteer_id)
+        .await
+        .map_err(internal)?;
+    let Some(worker) = worker.filter(|w| nodes.iter().any(|n| &n.worker_id == w)) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "No verification claimed by this operator"})),
+        ));
+    };
+    state
+        .submit_verification_result(verification_id, payload.tested, payload.found, &worker)
+        .await
+        .map_err(internal)
 }
 
 // ── GET /api/v1/stats ─────────────────────────────────────────────
@@ -535,10 +777,25 @@ pub(super) async fn handler_v1_stats(
 
 // ── GET /api/v1/leaderboard ───────────────────────────────────────
 
+#[derive(Deserialize)]
+pub(super) struct LeaderboardQuery {
+    /// `7d`, `30d` or `all` (the default).
+    #[serde(default)]
+    window: Option<String>,
+}
+
 pub(super) async fn handler_v1_leaderboard(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<LeaderboardQuery>,
 ) -> impl IntoResponse {
-    match state.db.get_operator_leaderboard(100).await {
+    let window = query.window.as_deref().unwrap_or("all");
+    let Some(window) = LeaderboardWindow::parse(window) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "window must be 7d, 30d or all"})),
+        );
+    };
+    match state.db.get_leaderboard_windowed(window, 100).await {
         Ok(entries) => {
             let result: Vec<serde_json::Value> = entries
                 .iter()
@@ -11,11 +11,14 @@
 //! | `GET /api/stats/distribution` | `supabase.rpc("get_digit_distribution")` |
 //! | `GET /api/stats/leaderboard` | `supabase.rpc("get_form_leaderboard")` |
 //! | `GET /api/primes` | `supabase.from("primes").select()` |
+//! | `GET /api/primes/search` | — (new: trigram expression search) |
 //! | `GET /api/primes/{id}` | `supabase.from("primes").eq("id",id)` |
+//! | `GET /api/primes/{id}/decimal` | — (new: full decimal expansion as text) |
 
+use super::decimal_cache::DecimalCache;
 use super::AppState;
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
 use serde::Deserialize;
@@ -174,15 +177,80 @@ pub(super) async fn handler_api_primes_list(
     }
 }
 
+#[derive(Deserialize)]
+pub(super) struct PrimeSearchQuery {
+    q: Option<String>,
+    form: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+/// `GET /api/primes/search?q=3*2^&form=kbn` — Expression substring search.
+///
+/// Matches `q` literally anywhere in the expression via the trigram index,
+/// so `q=3*2^` finds every `3*2^n±1`. Returns full prime details with the
+/// total match count for pagination.
+pub(super) async fn handler_api_primes_search(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<PrimeSearchQuery>,
+) -> impl IntoResponse {
+    let q = params.q.unwrap_or_default();
+    let q = q.trim();
+    if q.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "q is required"})),
+        )
+            .into_response();
+    }
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    let offset = params.offset.unwrap_or(0).max(0);
+    let form = params.form.as_deref();
+
+    let (primes, total) = tokio::join!(
+        state.db.search_primes(q, form, limit, offset),
+        state.db.count_search_primes(q, form),
+    );
+
+    match (primes, total) {
+        (Ok(primes), Ok(total)) => Json(serde_json::json!({
+            "primes": primes,
+            "total": total,
+            "limit": limit,
+            "offset": offset,
+        }))
+        .into_response(),
+        (Err(e), _) | (_, Err(e)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
 /// `GET /api/primes/{id}` — Single prime detail.
 ///
-/// Replaces `supabase.from("primes").eq("id", id).single()`.
+/// Replaces `supabase.from("primes").eq("id", id).single()`. The response
+/// carries the `certificate`, decompressed when it was stored compressed.
 pub(super) async fn handler_api_prime_get(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
 ) -> impl IntoResponse {
-    match state.db.get_prime_by_id(id).await {
-        Ok(Some(prime)) => Json(serde_json::json!(prime)).into_response(),
+    let prime = match state.db.get_prime_by_id(id).await {
+        Ok(Some(prime)) => state
+            .db
+            .get_prime_certificate(id)
+            .await
+            .map(|certificate| Some((prime, certificate))),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+    match prime {
+        Ok(Some((prime, certificate))) => {
+            let mut body = serde_json::json!(prime);
+            body["certificate"] = serde_json::json!(certificate);
+            Json(body).into_response()
+        }
         Ok(None) => (
             StatusCode::NOT_FOUND,
             Json(serde_json::json!({"error": "Prime not found"})),
@@ -195,3 +263,72 @@ pub(super) async fn handler_api_prime_get(
             .into_response(),
     }
 }
+
+/// Largest expansion `/api/primes/{id}/decimal` returns without `?confirm=1`.
+pub const MAX_DECIMAL_DIGITS: i64 = 1_000_000;
+
+#[derive(Deserialize)]
+pub(super) struct DecimalQuery {
+    #[serde(default)]
+    confirm: u8,
+}
+
+/// `GET /api/primes/{id}/decimal` — The prime's full base-10 expansion.
+///
+/// Rebuilt from the stored expression and served as `text/plain` through
+/// the on-disk [`DecimalCache`]. Primes over [`MAX_DECIMAL_DIGITS`] get 413
+/// unless `?confirm=1` is given.
+pub(super) async fn handler_api_prime_decimal(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(query): Query<DecimalQuery>,
+) -> impl IntoResponse {
+    let prime = match state.db.get_prime_by_id(id).await {
+        Ok(Some(prime)) => prime,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Prime not found"})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    };
+    if prime.digits > MAX_DECIMAL_DIGITS && query.confirm == 0 {
+        let error = format!(
+            "{} digits exceeds the {} digit limit; pass ?confirm=1",
+            prime.digits, MAX_DECIMAL_DIGITS
+        );
+        return (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(serde_json::json!({ "error": error })),
+        )
+            .into_response();
+    }
+
+    let decimal = tokio::task::spawn_blocking(move || {
+        DecimalCache::from_env().decimal(&prime.form, &prime.expression)
+    })
+    .await;
+    match decimal {
+        Ok(Ok(text)) => {
+            ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], text).into_response()
+        }
+        Ok(Err(e)) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Conversion panicked: {}", e)})),
+        )
+            .into_response(),
+    }
+}
@@ -254,6 +254,60 @@ pub(super) async fn handler_api_project_activate(
     Json(serde_json::json!({"ok": true, "status": "active"})).into_response()
 }
 
+/// POST /api/projects/{slug}/tick — Run orchestration for this project now.
+///
+/// Returns the phase transitions and search jobs the run produced, so a
+/// freshly activated project can start without waiting for the next tick.
+pub(super) async fn handler_api_project_tick(
+    State(state): State<Arc<AppState>>,
+    AxumPath(slug): AxumPath<String>,
+) -> impl IntoResponse {
+    let proj = match state.db.get_project_by_slug(&slug).await {
+        Ok(Some(p)) => p,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Project not found"})),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    if proj.status != "active" {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": format!("Cannot orchestrate project with status '{}'", proj.status)
+            })),
+        )
+            .into_response();
+    }
+
+    match project::orchestrate_project(&state.db, proj.id).await {
+        Ok(outcome) => {
+            info!(
+                slug,
+                transitions = outcome.transitions.len(),
+                jobs = outcome.created_jobs.len(),
+                "project orchestrated via API"
+            );
+            Json(serde_json::json!(outcome)).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
 /// POST /api/projects/{slug}/pause — Pause project orchestration.
 pub(super) async fn handler_api_project_pause(
     State(state): State<Arc<AppState>>,
@@ -6,6 +6,7 @@ use axum::response::IntoResponse;
 use axum::Json;
 use serde::Deserialize;
 use std::sync::Arc;
+use tracing::warn;
 
 use super::AppState;
 
@@ -175,12 +176,32 @@ pub(super) struct RolloutPayload {
     rollout_percent: i32,
     #[serde(default)]
     changed_by: Option<String>,
+    /// Crash/disconnect rate above which widening a rollout pauses it.
+    #[serde(default = "default_max_crash_rate")]
+    max_crash_rate: f64,
+    #[serde(default = "default_health_window_minutes")]
+    health_window_minutes: i64,
+    /// Skip the health gate.
+    #[serde(default)]
+    force: bool,
 }
 
 fn default_rollout() -> i32 {
     100
 }
 
+fn default_max_crash_rate() -> f64 {
+    0.25
+}
+
+fn default_health_window_minutes() -> i64 {
+    30
+}
+
+/// Nodes that must report on a version before its crash rate can pause a
+/// rollout, so one flaky machine does not halt a canary.
+const HEALTH_GATE_MIN_WORKERS: i64 = 3;
+
 pub(super) async fn handler_releases_rollout(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<RolloutPayload>,
@@ -191,6 +212,67 @@ pub(super) async fn handler_releases_rollout(
             Json(serde_json::json!({"error": "channel and version are required"})),
         );
     }
+
+    // Widening the rollout of the channel's current version is gated on the
+    // health of the nodes already running it; an unhealthy version is
+    // paused at 0% (nodes fall back to the previous release) instead.
+    if !payload.force {
+        let window = payload.health_window_minutes.clamp(1, 24 * 60);
+        let health = match state.db.get_release_health(&payload.channel, window).await {
+            Ok(h) => h,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(
+                        serde_json::json!({"error": format!("failed to check release health: {}", e)}),
+                    ),
+                );
+            }
+        };
+        if let Some(health) = health.filter(|h| {
+            h.version == payload.version
+                && payload.rollout_percent > h.rollout_percent
+                && h.is_unhealthy(payload.max_crash_rate, HEALTH_GATE_MIN_WORKERS)
+        }) {
+            warn!(
+                channel = %health.channel,
+                version = %health.version,
+                crash_rate = health.crash_rate,
+                workers = health.workers,
+                "release health gate tripped, pausing rollout"
+            );
+            return match state
+                .db
+                .set_worker_release_channel(
+                    &health.channel,
+                    &health.version,
+                    0,
+                    Some("health-gate"),
+                )
+                .await
+            {
+                Ok(row) => (
+                    StatusCode::CONFLICT,
+                    Json(serde_json::json!({
+                        "error": format!(
+                            "version {} has a {:.0}% crash rate on {}; rollout paused",
+                            health.version,
+                            health.crash_rate * 100.0,
+                            health.channel
+                        ),
+                        "paused": true,
+                        "health": health,
+                        "channel": row,
+                    })),
+                ),
+                Err(e) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": e.to_string()})),
+                ),
+            };
+        }
+    }
+
     match state
         .db
         .set_worker_release_channel(
@@ -3,11 +3,15 @@
 //! Searches are managed as `search_jobs` + `work_blocks` in the database.
 //! Creating a search creates a job row and generates work blocks for the range.
 //! Nodes claim blocks directly via `FOR UPDATE SKIP LOCKED`.
+//!
+//! `POST /api/searches/stop-by-form` cancels every running job of one form and
+//! sends "stop" to the workers running it (see `Database::request_stop_by_form`).
 
-use axum::extract::{Path as AxumPath, State};
+use axum::extract::{Path as AxumPath, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
+use serde::Deserialize;
 use std::sync::Arc;
 use tracing::info;
 
@@ -27,8 +31,22 @@ pub(super) async fn handler_api_searches_list(
     }
 }
 
+#[derive(Deserialize)]
+pub(super) struct CreateSearchQuery {
+    /// Return an identical pending, running or paused job instead of
+    /// creating a duplicate. `false` runs a second copy on purpose, e.g. to
+    /// double-check a range.
+    #[serde(default = "default_reuse_existing")]
+    reuse_existing: bool,
+}
+
+fn default_reuse_existing() -> bool {
+    true
+}
+
 pub(super) async fn handler_api_searches_create(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<CreateSearchQuery>,
     Json(params): Json<SearchParams>,
 ) -> impl IntoResponse {
     let search_type = params.search_type_name().to_string();
@@ -46,39 +64,73 @@ pub(super) async fn handler_api_searches_create(
     let (range_start, range_end) = params.range();
     let block_size = params.default_block_size();
 
-    match state
-        .db
-        .create_search_job(&search_type, &params_json, range_start, range_end, block_size)
-        .await
-    {
-        Ok(job_id) => {
-            info!(
-                job_id,
-                search_type,
+    let created = if query.reuse_existing {
+        state
+            .db
+            .find_or_create_search_job(
+                &search_type,
+                &params_json,
                 range_start,
                 range_end,
                 block_size,
-                "search job created"
-            );
-            (
-                StatusCode::CREATED,
-                Json(serde_json::json!({
-                    "id": job_id,
-                    "search_type": search_type,
-                    "status": "running",
-                    "range_start": range_start,
-                    "range_end": range_end,
-                    "block_size": block_size,
-                })),
+            )
+            .await
+    } else {
+        state
+            .db
+            .create_search_job(&search_type, &params_json, range_start, range_end, block_size)
+            .await
+            .map(|job_id| (job_id, false))
+    };
+    let (job_id, reused) = match created {
+        Ok(created) => created,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Failed to create search: {}", e)})),
             )
                 .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Failed to create search: {}", e)})),
-        )
-            .into_response(),
-    }
+    };
+
+    // A reused job reports its own state, not the request's.
+    let (status_name, block_size) = if reused {
+        match state.db.get_search_job(job_id).await {
+            Ok(Some(job)) => (job.status, job.block_size),
+            Ok(None) => ("running".to_string(), block_size),
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": format!("Failed to load search: {}", e)})),
+                )
+                    .into_response()
+            }
+        }
+    } else {
+        ("running".to_string(), block_size)
+    };
+    info!(
+        job_id,
+        search_type, range_start, range_end, block_size, reused, "search job created"
+    );
+    let status = if reused {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+    (
+        status,
+        Json(serde_json::json!({
+            "id": job_id,
+            "search_type": search_type,
+            "status": status_name,
+            "range_start": range_start,
+            "range_end": range_end,
+            "block_size": block_size,
+            "reused": reused,
+        })),
+    )
+        .into_response()
 }
 
 pub(super) async fn handler_api_searches_get(
@@ -121,6 +173,47 @@ pub(super) async fn handler_api_searches_stop(
     }
 }
 
+#[derive(serde::Deserialize)]
+pub(super) struct StopByFormRequest {
+    form: String,
+}
+
+/// Stop every running search of one form, e.g. after a sieve bug is found.
+pub(super) async fn handler_api_searches_stop_by_form(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<StopByFormRequest>,
+) -> impl IntoResponse {
+    if !crate::project::VALID_FORMS.contains(&req.form.as_str()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Unknown form '{}'", req.form)})),
+        )
+            .into_response();
+    }
+    match state.db.request_stop_by_form(&req.form).await {
+        Ok(stop) => {
+            info!(
+                form = %req.form,
+                jobs = stop.job_ids.len(),
+                workers = stop.workers_signalled,
+                "search jobs cancelled by form"
+            );
+            Json(serde_json::json!({
+                "ok": true,
+                "form": req.form,
+                "cancelled": stop.job_ids,
+                "workers_signalled": stop.workers_signalled,
+            }))
+            .into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to stop searches: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
 pub(super) async fn handler_api_searches_pause(
     State(state): State<Arc<AppState>>,
     AxumPath(id): AxumPath<i64>,
@@ -40,6 +40,19 @@ pub(super) async fn handler_api_status(State(state): State<Arc<AppState>>) -> Js
     })
 }
 
+/// GET /api/status/checkpoint — Where the coordinator's local search stands.
+///
+/// Returns [`checkpoint::Checkpoint::summary`] of the checkpoint at
+/// `AppState::checkpoint_path`, or 204 when there is none.
+pub(super) async fn handler_api_status_checkpoint(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    match checkpoint::load(&state.checkpoint_path) {
+        Some(cp) => Json(cp.summary()).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct ExportQuery {
     format: Option<String>,
@@ -2,7 +2,7 @@
 //!
 //! All routes require admin authentication via the `RequireAdmin` extractor.
 
-use axum::extract::{Path as AxumPath, State};
+use axum::extract::{Path as AxumPath, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
@@ -11,7 +11,7 @@ use std::sync::Arc;
 
 use super::middleware_auth::RequireAdmin;
 use super::AppState;
-use crate::strategy;
+use crate::{ai_engine, strategy};
 
 /// GET /api/strategy/status — Engine status and last tick info.
 pub(super) async fn handler_strategy_status(
@@ -216,3 +216,47 @@ pub(super) async fn handler_ai_engine_decisions(
             .into_response(),
     }
 }
+
+#[derive(Deserialize)]
+pub(super) struct CalibrateQuery {
+    form: String,
+}
+
+/// POST /api/strategy/calibrate?form=kbn — Refit a form's cost model from
+/// recent completed work blocks and load it into the AI engine.
+pub(super) async fn handler_strategy_calibrate(
+    _auth: RequireAdmin,
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CalibrateQuery>,
+) -> impl IntoResponse {
+    if !strategy::ALL_FORMS.contains(&params.form.as_str()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("unknown form: {}", params.form)})),
+        )
+            .into_response();
+    }
+    match ai_engine::recalibrate_form(&state.db, &params.form).await {
+        Ok(Some(calibration)) => {
+            let mut engine = state.ai_engine.lock().await;
+            engine.cost_model.fitted.insert(
+                calibration.form.clone(),
+                (calibration.coeff_a, calibration.coeff_b),
+            );
+            engine.cost_model.version += 1;
+            Json(serde_json::json!(calibration)).into_response()
+        }
+        Ok(None) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(serde_json::json!({
+                "error": "not enough completed work blocks to fit a cost model"
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
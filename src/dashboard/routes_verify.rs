@@ -53,14 +53,41 @@ pub(super) async fn handler_api_prime_verify(
             }
             Json(serde_json::json!({"ok": true, "result": "verified", "method": method, "tier": tier})).into_response()
         }
-        verify::VerifyResult::Failed { reason } => {
-            let _ = state.db.mark_verification_failed(id, &reason).await;
-            Json(serde_json::json!({"ok": true, "result": "failed", "reason": reason}))
-                .into_response()
+        verify::VerifyResult::Failed {
+            reason,
+            reason_code,
+        } => {
+            let _ = state
+                .db
+                .mark_verification_failed(id, &reason, reason_code.as_str())
+                .await;
+            Json(serde_json::json!({
+                "ok": true,
+                "result": "failed",
+                "reason": reason,
+                "reason_code": reason_code.as_str(),
+            }))
+            .into_response()
         }
         verify::VerifyResult::Skipped { reason } => {
             Json(serde_json::json!({"ok": true, "result": "skipped", "reason": reason}))
                 .into_response()
         }
+        verify::VerifyResult::Errored {
+            reason,
+            reason_code,
+        } => {
+            let _ = state
+                .db
+                .record_verification_error(id, reason_code.as_str())
+                .await;
+            Json(serde_json::json!({
+                "ok": true,
+                "result": "errored",
+                "reason": reason,
+                "reason_code": reason_code.as_str(),
+            }))
+            .into_response()
+        }
     }
 }
@@ -0,0 +1,391 @@
+//! Fleet, job and event metrics assembled into one document.
+//!
+//! Every minute the coordinator tick builds a [`MetricsSnapshot`] and
+//! flattens it into `metric_samples` rows. `GET /api/observability/snapshot`
+//! builds the same document on request, so a new Grafana dashboard can be
+//! checked against live values without waiting for samples to accumulate.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::{db, events, fleet, metrics};
+
+/// Window the snapshot endpoint counts events over, matching the tick's
+/// one-minute sample interval.
+pub(super) const EVENT_WINDOW_MS: u64 = 60_000;
+
+#[derive(Serialize)]
+pub struct MetricsSnapshot {
+    pub ts: DateTime<Utc>,
+    /// Coordinator host metrics; `None` until the first tick has run.
+    pub coordinator: Option<metrics::HardwareMetrics>,
+    pub fleet: FleetSnapshot,
+    pub workers: Vec<WorkerSnapshot>,
+    /// Jobs active in the last 24 hours (at most 50).
+    pub jobs: Vec<JobSnapshot>,
+    pub events: EventCounts,
+}
+
+#[derive(Serialize)]
+pub struct FleetSnapshot {
+    pub workers_connected: usize,
+    pub total_cores: i64,
+    pub total_tested: i64,
+    pub total_found: i64,
+    pub max_heartbeat_age_secs: i64,
+    pub avg_heartbeat_age_secs: f64,
+    /// `None` when the job query failed.
+    pub search_jobs_active: Option<usize>,
+    /// Work blocks across all jobs; `None` when the query failed.
+    pub blocks: Option<db::JobBlockSummary>,
+}
+
+#[derive(Serialize)]
+pub struct WorkerSnapshot {
+    pub worker_id: String,
+    pub hostname: String,
+    pub search_type: String,
+    pub tested: u64,
+    pub found: u64,
+    pub metrics: Option<metrics::HardwareMetrics>,
+}
+
+#[derive(Serialize)]
+pub struct JobSnapshot {
+    pub job_id: i64,
+    pub search_type: String,
+    pub status: String,
+    pub completion_pct: f64,
+    pub blocks: db::JobBlockSummary,
+}
+
+/// Event bus events by kind.
+#[derive(Serialize, Default)]
+pub struct EventCounts {
+    pub total: i64,
+    pub error: i64,
+    pub warning: i64,
+    pub prime: i64,
+    pub milestone: i64,
+    pub search_start: i64,
+    pub search_done: i64,
+}
+
+impl EventCounts {
+    pub(super) fn from_kinds(counts: &HashMap<String, i64>) -> Self {
+        let get = |kind: &str| *counts.get(kind).unwrap_or(&0);
+        EventCounts {
+            total: counts.values().sum(),
+            error: get("error"),
+            warning: get("warning"),
+            prime: get("prime"),
+            milestone: get("milestone"),
+            search_start: get("search_start"),
+            search_done: get("search_done"),
+        }
+    }
+
+    /// Counts over the bus's recent events newer than `window_ms`.
+    pub(super) fn recent(bus: &events::EventBus, window_ms: u64) -> Self {
+        let now_ms = Utc::now().timestamp_millis() as u64;
+        let mut counts = HashMap::new();
+        for e in bus.recent_events(usize::MAX) {
+            if e.timestamp_ms + window_ms >= now_ms {
+                *counts.entry(e.kind).or_insert(0) += 1;
+            }
+        }
+        EventCounts::from_kinds(&counts)
+    }
+}
+
+impl MetricsSnapshot {
+    /// Query block and job state and combine it with what the caller holds.
+    pub(super) async fn assemble(
+        db: &db::Database,
+        workers: &[fleet::WorkerState],
+        coordinator: Option<metrics::HardwareMetrics>,
+        events: EventCounts,
+    ) -> Self {
+        let heartbeat_ages = workers.iter().map(|w| w.last_heartbeat_secs_ago);
+        let avg_heartbeat_age_secs = if workers.is_empty() {
+            0.0
+        } else {
+            heartbeat_ages.clone().sum::<u64>() as f64 / workers.len() as f64
+        };
+        let search_jobs_active = db
+            .get_search_jobs()
+            .await
+            .ok()
+            .map(|jobs| jobs.iter().filter(|j| j.status == "running").count());
+        let fleet = FleetSnapshot {
+            workers_connected: workers.len(),
+            total_cores: workers.iter().map(|w| w.cores as i64).sum(),
+            total_tested: workers.iter().map(|w| w.tested as i64).sum(),
+            total_found: workers.iter().map(|w| w.found as i64).sum(),
+            max_heartbeat_age_secs: heartbeat_ages.max().unwrap_or(0) as i64,
+            avg_heartbeat_age_secs,
+            search_jobs_active,
+            blocks: db.get_all_block_summary().await.ok(),
+        };
+
+        let workers = workers
+            .iter()
+            .map(|w| WorkerSnapshot {
+                worker_id: w.worker_id.clone(),
+                hostname: w.hostname.clone(),
+                search_type: w.search_type.clone(),
+                tested: w.tested,
+                found: w.found,
+                metrics: w.metrics.clone(),
+            })
+            .collect();
+
+        let mut jobs = Vec::new();
+        for job in db.get_recent_search_jobs(24, 50).await.unwrap_or_default() {
+            let Ok(blocks) = db.get_job_block_summary(job.id).await else {
+                continue;
+            };
+            let total_blocks = blocks.available + blocks.claimed + blocks.completed + blocks.failed;
+            let completion_pct = if total_blocks > 0 {
+                (blocks.completed as f64 / total_blocks as f64) * 100.0
+            } else {
+                0.0
+            };
+            jobs.push(JobSnapshot {
+                job_id: job.id,
+                search_type: job.search_type,
+                status: job.status,
+                completion_pct,
+                blocks,
+            });
+        }
+
+        MetricsSnapshot {
+            ts: Utc::now(),
+            coordinator,
+            fleet,
+            workers,
+            jobs,
+            events,
+        }
+    }
+
+    /// `metric_samples` rows for the snapshot. Per-worker rows are only
+    /// included with `include_workers`, since the tick samples workers less
+    /// often than the rest.
+    pub(super) fn to_samples(&self, include_workers: bool) -> Vec<db::MetricSample> {
+        let mut samples = Vec::new();
+        let mut push = |scope: &str, labels: Option<&serde_json::Value>, values: &[(&str, f64)]| {
+            for &(metric, value) in values {
+                samples.push(db::MetricSample {
+                    ts: self.ts,
+                    scope: scope.to_string(),
+                    metric: format!("{}.{}", scope, metric),
+                    value,
+                    labels: labels.cloned(),
+                });
+            }
+        };
+
+        if let Some(hw) = &self.coordinator {
+            push(
+                "coordinator",
+                None,
+                &[
+                    ("cpu_usage_percent", hw.cpu_usage_percent as f64),
+                    ("memory_usage_percent", hw.memory_usage_percent as f64),
+                    ("load_avg_1m", hw.load_avg_1m),
+                    ("load_avg_5m", hw.load_avg_5m),
+                    ("load_avg_15m", hw.load_avg_15m),
+                ],
+            );
+        }
+
+        let fleet = &self.fleet;
+        push(
+            "fleet",
+            None,
+            &[
+                ("workers_connected", fleet.workers_connected as f64),
+                ("total_cores", fleet.total_cores as f64),
+                ("total_tested", fleet.total_tested as f64),
+                ("total_found", fleet.total_found as f64),
+                (
+                    "max_heartbeat_age_secs",
+                    fleet.max_heartbeat_age_secs as f64,
+                ),
+                ("avg_heartbeat_age_secs", fleet.avg_heartbeat_age_secs),
+            ],
+        );
+        if let Some(blocks) = &fleet.blocks {
+            push(
+                "fleet",
+                None,
+                &[
+                    ("work_blocks_available", blocks.available as f64),
+                    ("work_blocks_claimed", blocks.claimed as f64),
+                    ("work_blocks_completed", blocks.completed as f64),
+                    ("work_blocks_failed", blocks.failed as f64),
+                    ("work_blocks_quarantined", blocks.quarantined as f64),
+                    ("block_total_tested", blocks.total_tested as f64),
+                    ("block_total_found", blocks.total_found as f64),
+                ],
+            );
+        }
+        if let Some(active) = fleet.search_jobs_active {
+            push("fleet", None, &[("search_jobs_active", active as f64)]);
+        }
+
+        for job in &self.jobs {
+            let labels = serde_json::json!({
+                "job_id": job.job_id.to_string(),
+                "search_type": job.search_type,
+                "status": job.status,
+            });
+            let blocks = &job.blocks;
+            push(
+                "search_job",
+                Some(&labels),
+                &[
+                    ("blocks_available", blocks.available as f64),
+                    ("blocks_claimed", blocks.claimed as f64),
+                    ("blocks_completed", blocks.completed as f64),
+                    ("blocks_failed", blocks.failed as f64),
+                    ("completion_pct", job.completion_pct),
+                    ("total_tested", blocks.total_tested as f64),
+                    ("total_found", blocks.total_found as f64),
+                ],
+            );
+        }
+
+        let events = &self.events;
+        push(
+            "events",
+            None,
+            &[
+                ("total_count", events.total as f64),
+                ("error_count", events.error as f64),
+                ("warning_count", events.warning as f64),
+                ("prime_count", events.prime as f64),
+                ("milestone_count", events.milestone as f64),
+                ("search_start_count", events.search_start as f64),
+                ("search_done_count", events.search_done as f64),
+            ],
+        );
+
+        if include_workers {
+            for w in &self.workers {
+                let labels = serde_json::json!({
+                    "worker_id": w.worker_id,
+                    "hostname": w.hostname,
+                    "search_type": w.search_type,
+                });
+                if let Some(m) = &w.metrics {
+                    push(
+                        "worker",
+                        Some(&labels),
+                        &[
+                            ("cpu_usage_percent", m.cpu_usage_percent as f64),
+                            ("memory_usage_percent", m.memory_usage_percent as f64),
+                            ("disk_usage_percent", m.disk_usage_percent as f64),
+                        ],
+                    );
+                }
+                let counts = [("tested", w.tested as f64), ("found", w.found as f64)];
+                push("worker", Some(&labels), &counts);
+            }
+        }
+        samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks() -> db::JobBlockSummary {
+        db::JobBlockSummary {
+            available: 2,
+            claimed: 1,
+            completed: 1,
+            failed: 0,
+            quarantined: 0,
+            total_tested: 100,
+            total_found: 1,
+        }
+    }
+
+    /// The tick stores the snapshot under the metric names dashboards were
+    /// built against; per-worker rows only appear when asked for.
+    #[test]
+    fn samples_keep_metric_names() {
+        let worker = WorkerSnapshot {
+            worker_id: "w1".into(),
+            hostname: "host".into(),
+            search_type: "kbn".into(),
+            tested: 10,
+            found: 1,
+            metrics: Some(metrics::HardwareMetrics::default()),
+        };
+        let job = JobSnapshot {
+            job_id: 7,
+            search_type: "kbn".into(),
+            status: "running".into(),
+            completion_pct: 25.0,
+            blocks: blocks(),
+        };
+        let fleet = FleetSnapshot {
+            workers_connected: 1,
+            total_cores: 8,
+            total_tested: 10,
+            total_found: 1,
+            max_heartbeat_age_secs: 3,
+            avg_heartbeat_age_secs: 3.0,
+            search_jobs_active: Some(1),
+            blocks: Some(blocks()),
+        };
+        let snapshot = MetricsSnapshot {
+            ts: Utc::now(),
+            coordinator: Some(metrics::HardwareMetrics::default()),
+            fleet,
+            workers: vec![worker],
+            jobs: vec![job],
+            events: EventCounts::default(),
+        };
+
+        let without_workers = snapshot.to_samples(false);
+        assert_eq!(without_workers.len(), 5 + 6 + 7 + 1 + 7 + 7);
+        assert!(without_workers.iter().all(|s| s.scope != "worker"));
+        let names: Vec<&str> = without_workers.iter().map(|s| s.metric.as_str()).collect();
+        for name in [
+            "coordinator.load_avg_15m",
+            "fleet.avg_heartbeat_age_secs",
+            "fleet.work_blocks_quarantined",
+            "fleet.search_jobs_active",
+            "search_job.completion_pct",
+            "events.search_done_count",
+        ] {
+            assert!(names.contains(&name), "missing {}", name);
+        }
+        let pct = names.iter().position(|&n| n == "search_job.completion_pct");
+        let completion = &without_workers[pct.unwrap()];
+        assert_eq!(completion.value, 25.0);
+        assert_eq!(completion.labels.as_ref().unwrap()["job_id"], "7");
+
+        let with_workers = snapshot.to_samples(true);
+        let worker_rows: Vec<&str> = with_workers
+            .iter()
+            .filter(|s| s.scope == "worker")
+            .map(|s| s.metric.as_str())
+            .collect();
+        let expected = [
+            "worker.cpu_usage_percent",
+            "worker.memory_usage_percent",
+            "worker.disk_usage_percent",
+            "worker.tested",
+            "worker.found",
+        ];
+        assert_eq!(worker_rows, expected);
+    }
+}
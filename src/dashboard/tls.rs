@@ -0,0 +1,177 @@
+//! Optional TLS for the dashboard listener.
+//!
+//! `darkreach dashboard --tls-cert cert.pem --tls-key key.pem` serves HTTPS
+//! directly, for coordinators exposed to the internet without a reverse
+//! proxy; without the flags the server speaks plain HTTP. Operator API keys
+//! travel as bearer tokens, so a coordinator that accepts operator traffic
+//! over an untrusted network must terminate TLS, either here or at a proxy in
+//! front of it.
+
+use anyhow::{Context, Result};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{self, ServerConfig};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tracing::debug;
+
+/// Connections that haven't finished the handshake by then are dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// PEM certificate chain and private key given by `--tls-cert`/`--tls-key`.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+impl TlsConfig {
+    /// Load the PEM files into a rustls server config (ring provider,
+    /// HTTP/1.1 over ALPN).
+    pub fn server_config(&self) -> Result<Arc<ServerConfig>> {
+        let cert_path = self.cert.display();
+        let certs = CertificateDer::pem_file_iter(&self.cert)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .with_context(|| format!("reading TLS certificate {}", cert_path))?;
+        let key = PrivateKeyDer::from_pem_file(&self.key)
+            .with_context(|| format!("reading TLS key {}", self.key.display()))?;
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut config = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        Ok(Arc::new(config))
+    }
+}
+
+/// TCP listener for `axum::serve` that hands over connections only once the
+/// TLS handshake is done. Handshakes run on their own tasks, so a client that
+/// stalls mid-handshake doesn't hold up other accepts.
+pub struct TlsListener {
+    local_addr: SocketAddr,
+    incoming: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+}
+
+impl TlsListener {
+    pub async fn bind(addr: SocketAddr, tls: &TlsConfig) -> Result<Self> {
+        let acceptor = TlsAcceptor::from(tls.server_config()?);
+        let tcp = TcpListener::bind(addr).await?;
+        let local_addr = tcp.local_addr()?;
+        let (tx, incoming) = mpsc::channel(64);
+        tokio::spawn(accept_loop(tcp, acceptor, tx));
+        Ok(TlsListener {
+            local_addr,
+            incoming,
+        })
+    }
+}
+
+/// Accept TCP connections until the [`TlsListener`] is dropped.
+async fn accept_loop(
+    mut tcp: TcpListener,
+    acceptor: TlsAcceptor,
+    tx: mpsc::Sender<(TlsStream<TcpStream>, SocketAddr)>,
+) {
+    while !tx.is_closed() {
+        // Retries and logs accept errors the same way plain HTTP does.
+        let (stream, peer) = axum::serve::Listener::accept(&mut tcp).await;
+        let acceptor = acceptor.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let handshake = acceptor.accept(stream);
+            match tokio::time::timeout(HANDSHAKE_TIMEOUT, handshake).await {
+                Ok(Ok(tls)) => {
+                    let _ = tx.send((tls, peer)).await;
+                }
+                Ok(Err(e)) => debug!(%peer, error = %e, "TLS handshake failed"),
+                Err(_) => debug!(%peer, "TLS handshake timed out"),
+            }
+        });
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.incoming.recv().await {
+            Some(conn) => conn,
+            // The accept loop only exits once this receiver is gone.
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        Ok(self.local_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::rustls::pki_types::ServerName;
+    use tokio_rustls::TlsConnector;
+
+    /// Self-signed P-256 certificate for `localhost`, valid until 2126.
+    fn fixture() -> TlsConfig {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+        TlsConfig {
+            cert: dir.join("cert.pem"),
+            key: dir.join("key.pem"),
+        }
+    }
+
+    /// A client that trusts the fixture certificate completes the handshake
+    /// and gets an HTTP response from a router served on [`TlsListener`].
+    #[tokio::test]
+    async fn serves_https_with_configured_cert() {
+        let tls = fixture();
+        let any_port = SocketAddr::from(([127, 0, 0, 1], 0));
+        let listener = TlsListener::bind(any_port, &tls).await.unwrap();
+        let addr = listener.local_addr;
+        let app = axum::Router::new().route("/", axum::routing::get(|| async { "ok" }));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in CertificateDer::pem_file_iter(&tls.cert).unwrap() {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = TlsConnector::from(Arc::new(config));
+        let tcp = TcpStream::connect(addr).await.unwrap();
+        let name = ServerName::try_from("localhost").unwrap();
+        let mut stream = connector.connect(name, tcp).await.unwrap();
+
+        let request = "GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.ends_with("ok"), "{}", response);
+    }
+
+    #[test]
+    fn missing_key_file_is_an_error() {
+        let tls = TlsConfig {
+            key: PathBuf::from("/nonexistent/key.pem"),
+            ..fixture()
+        };
+        let err = tls.server_config().unwrap_err();
+        assert!(err.to_string().contains("TLS key"), "{}", err);
+    }
+}
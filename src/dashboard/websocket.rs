@@ -3,19 +3,80 @@
 //! Pushes PG-sourced data: fleet status, search jobs, coordinator metrics,
 //! agent status, project state, and notifications. Deployment and subprocess
 //! search data has been removed — all coordination is now PostgreSQL-backed.
+//!
+//! ## Subscriptions
+//!
+//! A client may send `{"subscribe": ["prime", "milestone"]}` to receive only
+//! those frame kinds. Event frames use `Event::kind` names (`prime`, `prp`,
+//! `search_start`, `search_done`, `milestone`, `warning`, `error`); the
+//! periodic snapshot (`"type": "update"` frames) is `stats`, wherever it is
+//! sent from. Without a subscription every frame is sent.
+//! The first snapshot waits briefly so a subscription sent right after
+//! connecting applies from the start.
 
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::State;
 use axum::response::IntoResponse;
+use serde::Deserialize;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::info;
+use tracing::{debug, info};
 
 use super::routes_fleet::build_fleet_data;
 use super::routes_status::StatusResponse;
 use super::{lock_or_recover, AppState};
 use crate::checkpoint;
 
+/// How long a new connection waits for a subscription before the first snapshot.
+const SUBSCRIBE_GRACE: Duration = Duration::from_millis(250);
+
+/// Subscription kind of the `update` snapshot frames built by [`build_update`].
+const STATS_KIND: &str = "stats";
+
+/// Client message selecting which frame kinds to receive.
+#[derive(Deserialize)]
+struct SubscribeMessage {
+    subscribe: Vec<String>,
+}
+
+/// Frame kinds a client asked for; `None` means all.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Subscription(Option<HashSet<String>>);
+
+impl Subscription {
+    /// Parse a subscription message. An empty list resets to all kinds.
+    fn parse(text: &str) -> Option<Self> {
+        let msg: SubscribeMessage = serde_json::from_str(text).ok()?;
+        if msg.subscribe.is_empty() {
+            return Some(Subscription(None));
+        }
+        Some(Subscription(Some(msg.subscribe.into_iter().collect())))
+    }
+
+    fn allows(&self, kind: &str) -> bool {
+        self.0.as_ref().is_none_or(|kinds| kinds.contains(kind))
+    }
+
+    /// Whether a serialized frame passes; frames of unknown kind always do.
+    fn allows_frame(&self, frame: &str) -> bool {
+        frame_kind(frame).is_none_or(|kind| self.allows(&kind))
+    }
+}
+
+/// Kind of a broadcast frame: `prime` for `prime_found`, `stats` for the
+/// `update` snapshot, the notification's kind for `notification`, and the
+/// `type` field otherwise.
+fn frame_kind(frame: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(frame).ok()?;
+    match value["type"].as_str()? {
+        "prime_found" => Some("prime".to_string()),
+        "update" => Some(STATS_KIND.to_string()),
+        "notification" => value["notification"]["kind"].as_str().map(str::to_string),
+        other => Some(other.to_string()),
+    }
+}
+
 pub(super) async fn handler_ws(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
@@ -33,7 +94,22 @@ async fn ws_loop(
     let active = state.prom_metrics.ws_connections_active.get();
     info!(active_connections = active, "websocket client connected");
 
-    if let Some(msg) = build_update(&state).await {
+    let mut subscription = Subscription::default();
+    if let Ok(Some(Ok(Message::Text(text)))) =
+        tokio::time::timeout(SUBSCRIBE_GRACE, socket.recv()).await
+    {
+        if let Some(sub) = Subscription::parse(&text) {
+            debug!(?sub, "websocket subscription set");
+            subscription = sub;
+        }
+    }
+
+    let initial = if subscription.allows(STATS_KIND) {
+        build_update(&state).await
+    } else {
+        None
+    };
+    if let Some(msg) = initial {
         match socket.send(Message::Text(msg.into())).await {
             Ok(_) => {
                 state.prom_metrics.ws_messages_sent.inc();
@@ -53,6 +129,9 @@ async fn ws_loop(
     loop {
         tokio::select! {
             _ = interval.tick() => {
+                if !subscription.allows(STATS_KIND) {
+                    continue;
+                }
                 if let Some(msg) = build_update(&state).await {
                     match socket.send(Message::Text(msg.into())).await {
                         Ok(_) => { state.prom_metrics.ws_messages_sent.inc(); }
@@ -63,6 +142,9 @@ async fn ws_loop(
             result = notif_rx.recv() => {
                 match result {
                     Ok(msg) => {
+                        if !subscription.allows_frame(&msg) {
+                            continue;
+                        }
                         match socket.send(Message::Text(msg.into())).await {
                             Ok(_) => { state.prom_metrics.ws_messages_sent.inc(); }
                             Err(_) => break,
@@ -75,6 +157,12 @@ async fn ws_loop(
             msg = socket.recv() => {
                 match msg {
                     Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(Message::Text(text))) => {
+                        if let Some(sub) = Subscription::parse(&text) {
+                            debug!(?sub, "websocket subscription updated");
+                            subscription = sub;
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -133,3 +221,54 @@ pub(super) async fn build_update(state: &Arc<AppState>) -> Option<String> {
     }))
     .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_parse_and_allow() {
+        let sub = Subscription::parse(r#"{"subscribe": ["prime", "milestone"]}"#).unwrap();
+        assert!(sub.allows("prime"));
+        assert!(sub.allows("milestone"));
+        assert!(!sub.allows("stats"));
+
+        let all = Subscription::parse(r#"{"subscribe": []}"#).unwrap();
+        assert!(all.allows("stats"));
+        assert!(Subscription::default().allows("error"));
+
+        assert!(Subscription::parse("ping").is_none());
+        assert!(Subscription::parse(r#"{"other": 1}"#).is_none());
+    }
+
+    /// A `stats` subscription is what lets `update` snapshot frames through.
+    #[test]
+    fn stats_subscription_allows_update_frames() {
+        let update = r#"{"type":"update","status":{}}"#;
+        let prime = r#"{"type":"prime_found","prime":{"form":"kbn"}}"#;
+        let stats = Subscription::parse(r#"{"subscribe": ["stats"]}"#).unwrap();
+        assert!(stats.allows_frame(update));
+        assert!(!stats.allows_frame(prime));
+        let primes = Subscription::parse(r#"{"subscribe": ["prime"]}"#).unwrap();
+        assert!(!primes.allows_frame(update));
+        assert!(primes.allows_frame(prime));
+        assert!(primes.allows_frame("not json"));
+    }
+
+    #[test]
+    fn frame_kind_maps_broadcast_types() {
+        let prime = r#"{"type":"prime_found","prime":{"form":"kbn"}}"#;
+        assert_eq!(frame_kind(prime).as_deref(), Some("prime"));
+        let notif = r#"{"type":"notification","notification":{"kind":"milestone"}}"#;
+        assert_eq!(frame_kind(notif).as_deref(), Some("milestone"));
+        assert_eq!(
+            frame_kind(r#"{"type":"update"}"#).as_deref(),
+            Some("stats")
+        );
+        assert_eq!(
+            frame_kind(r#"{"type":"coordinator"}"#).as_deref(),
+            Some("coordinator")
+        );
+        assert_eq!(frame_kind("not json"), None);
+    }
+}
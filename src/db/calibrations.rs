@@ -8,11 +8,28 @@
 //!
 //! Coefficients are fitted periodically from completed work block data stored
 //! in the `cost_calibration` table (one row per form). The `cost_observations`
-//! view aggregates timing data from `work_blocks` for the fitting process.
+//! view aggregates timing data from `work_blocks` for the fitting process;
+//! [`Database::get_block_timing_samples`] reads `work_blocks` directly for
+//! on-demand recalibration.
 
 use super::{CostCalibrationRow, Database};
+use crate::ai_engine::CostObservation;
 use anyhow::Result;
 
+/// How far back on-demand recalibration looks for completed blocks.
+const TIMING_SAMPLE_DAYS: i32 = 30;
+
+/// Most recent completed blocks used per recalibration.
+const TIMING_SAMPLE_LIMIT: i64 = 500;
+
+#[derive(sqlx::FromRow)]
+struct BlockTimingRow {
+    block_start: i64,
+    block_end: i64,
+    tested: i64,
+    elapsed_secs: f64,
+}
+
 impl Database {
     /// Get all cost calibration coefficients, one row per form.
     pub async fn get_cost_calibrations(&self) -> Result<Vec<CostCalibrationRow>> {
@@ -37,6 +54,48 @@ impl Database {
         Ok(row)
     }
 
+    /// Timing samples from recently completed work blocks of one form.
+    ///
+    /// Each block yields one `(digits, secs_per_candidate)` point: digits are
+    /// estimated at the block midpoint with
+    /// [`estimate_digits_for_form`](crate::project::estimate_digits_for_form),
+    /// and seconds are the claim-to-completion time divided by `tested`.
+    /// Blocks without a claim time or with nothing tested are skipped.
+    ///
+    /// `metric_samples` is not used: it holds minute-level fleet and worker
+    /// gauges with no per-block elapsed time, and keeps only 7 days.
+    pub async fn get_block_timing_samples(&self, form: &str) -> Result<Vec<CostObservation>> {
+        let rows = sqlx::query_as::<_, BlockTimingRow>(
+            "SELECT wb.block_start, wb.block_end, wb.tested,
+                    EXTRACT(EPOCH FROM (wb.completed_at - wb.claimed_at))::float8 AS elapsed_secs
+             FROM work_blocks wb
+             JOIN search_jobs sj ON sj.id = wb.search_job_id
+             WHERE sj.search_type = $1
+               AND wb.status = 'completed'
+               AND wb.tested > 0
+               AND wb.claimed_at IS NOT NULL
+               AND wb.completed_at > wb.claimed_at
+               AND wb.completed_at > NOW() - ($2 || ' days')::interval
+             ORDER BY wb.completed_at DESC
+             LIMIT $3",
+        )
+        .bind(form)
+        .bind(TIMING_SAMPLE_DAYS.to_string())
+        .bind(TIMING_SAMPLE_LIMIT)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let mid = (r.block_start + r.block_end).max(0) as u64 / 2;
+                CostObservation {
+                    digits: crate::project::estimate_digits_for_form(form, mid) as f64,
+                    secs: r.elapsed_secs / r.tested as f64,
+                }
+            })
+            .collect())
+    }
+
     /// Upsert cost calibration coefficients for a form.
     ///
     /// Called after fitting the power-law model to completed work block data.
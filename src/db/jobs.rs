@@ -7,15 +7,149 @@
 //! ## Lifecycle
 //!
 //! 1. `create_search_job` — inserts job + generates work_blocks in a transaction
-//! 2. Workers call `claim_work_block` to atomically grab available blocks
+//!    (`find_or_create_search_job` instead reuses an identical live job)
+//! 2. Workers call `claim_work_block` to atomically grab available blocks,
+//!    resized by the job's adaptive `block_size_multiplier`
 //! 3. On completion, `complete_work_block_with_cores` records duration and stats
-//! 4. `reclaim_stale_blocks` recovers blocks from crashed workers (runs every 30s)
-//! 5. `get_job_block_summary` aggregates block status for progress reporting
+//! 4. `reclaim_stale_blocks` recovers blocks from crashed workers (runs every 30s);
+//!    a block reclaimed more than [`MAX_BLOCK_RECLAIMS`] times is quarantined
+//! 5. `get_job_block_summary` aggregates block status for progress reporting;
+//!    `get_job_coverage` maps which ranges are in each state
+//! 6. `adapt_block_sizes` (strategy tick) shrinks the multiplier after
+//!    failures and grows it while blocks finish well inside the timeout
 
-use super::{Database, JobBlockSummary, SearchJobRow, WorkBlock, WorkBlockDetails};
+use super::{
+    BlockReclaim, BlockSizeChange, CoverageRange, Database, JobBlockSummary, JobCoverage,
+    SearchJobRow, WorkBlock, WorkBlockDetails, WorkBlockWithCheckpoint,
+};
 use anyhow::Result;
 use serde_json::Value;
 
+/// Stale reclaims a block may go through before it is quarantined as 'failed'
+/// instead of being handed out again.
+pub const MAX_BLOCK_RECLAIMS: i32 = 5;
+
+/// Seconds a claimed block may go without a worker heartbeat before the
+/// coordinator reclaims it (the dashboard loop's `reclaim_stale_blocks` call).
+pub const STALE_BLOCK_SECS: i32 = 120;
+
+/// Bounds on `search_jobs.block_size_multiplier`.
+pub const MIN_BLOCK_SIZE_MULTIPLIER: f64 = 0.125;
+pub const MAX_BLOCK_SIZE_MULTIPLIER: f64 = 4.0;
+
+/// Factor by which the multiplier grows after a round of fast completions.
+pub const BLOCK_SIZE_GROWTH: f64 = 1.25;
+
+/// A block is "fast" when it completes in under this fraction of the reclaim
+/// timeout.
+pub const FAST_BLOCK_FRACTION: f64 = 0.5;
+
+/// Candidates a claim hands out for a job with `block_size` and `multiplier`.
+pub fn effective_block_size(block_size: i64, multiplier: f64) -> i64 {
+    ((block_size as f64 * multiplier).round() as i64).max(1)
+}
+
+/// Coalesce a job's blocks, as (block_start, block_end, status), into
+/// [`JobCoverage`] over [range_start, range_end).
+///
+/// Blocks are half-open like the job range. Same-state blocks that touch or
+/// overlap merge into one range; anything of the job range left uncovered is
+/// reported in `gaps`. Statuses outside the four block states are ignored.
+pub fn coalesce_coverage(
+    range_start: i64,
+    range_end: i64,
+    blocks: &[(i64, i64, String)],
+) -> JobCoverage {
+    let mut sorted: Vec<&(i64, i64, String)> = blocks.iter().collect();
+    sorted.sort_by_key(|b| (b.0, b.1));
+
+    let mut coverage = JobCoverage::default();
+    let mut covered_to = range_start;
+    for &(start, end, ref status) in sorted {
+        if start > covered_to {
+            coverage.gaps.push(CoverageRange {
+                start: covered_to,
+                end: start,
+            });
+        }
+        covered_to = covered_to.max(end);
+
+        let ranges = match status.as_str() {
+            "completed" => &mut coverage.completed,
+            "claimed" => &mut coverage.claimed,
+            "available" => &mut coverage.available,
+            "failed" => &mut coverage.failed,
+            _ => continue,
+        };
+        match ranges.last_mut() {
+            Some(last) if start <= last.end => last.end = last.end.max(end),
+            _ => ranges.push(CoverageRange { start, end }),
+        }
+    }
+    if covered_to < range_end {
+        coverage.gaps.push(CoverageRange {
+            start: covered_to,
+            end: range_end,
+        });
+    }
+    coverage
+}
+
+/// Insert a search job and its work blocks inside `tx`.
+async fn insert_search_job(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    search_type: &str,
+    params: &Value,
+    range_start: i64,
+    range_end: i64,
+    block_size: i64,
+) -> Result<i64> {
+    let job_id: i64 = sqlx::query_scalar(
+        "INSERT INTO search_jobs (search_type, params, status, range_start, range_end, block_size, started_at)
+         VALUES ($1, $2, 'running', $3, $4, $5, NOW())
+         RETURNING id",
+    )
+    .bind(search_type)
+    .bind(params)
+    .bind(range_start)
+    .bind(range_end)
+    .bind(block_size)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    // Estimate block duration from the cost model for dynamic stale timeout
+    let estimated_duration_s: Option<i32> = {
+        use crate::project::{estimate_digits_for_form, secs_per_candidate};
+        let mid = ((range_start + range_end) / 2) as u64;
+        let avg_digits = estimate_digits_for_form(search_type, mid);
+        if avg_digits > 0 {
+            let spc = secs_per_candidate(search_type, avg_digits, false);
+            let candidates_per_block = block_size as f64;
+            let est = (spc * candidates_per_block).ceil() as i32;
+            if est > 0 { Some(est) } else { None }
+        } else {
+            None
+        }
+    };
+
+    let mut start = range_start;
+    while start < range_end {
+        let end = (start + block_size).min(range_end);
+        sqlx::query(
+            "INSERT INTO work_blocks (search_job_id, block_start, block_end, estimated_duration_s)
+             VALUES ($1, $2, $3, $4)",
+        )
+        .bind(job_id)
+        .bind(start)
+        .bind(end)
+        .bind(estimated_duration_s)
+        .execute(&mut **tx)
+        .await?;
+        start = end;
+    }
+    Ok(job_id)
+}
+
 impl Database {
     /// Create a new search job and generate its work blocks in a single transaction.
     ///
@@ -30,51 +164,70 @@ impl Database {
         block_size: i64,
     ) -> Result<i64> {
         let mut tx = self.pool.begin().await?;
-        let job_id: i64 = sqlx::query_scalar(
-            "INSERT INTO search_jobs (search_type, params, status, range_start, range_end, block_size, started_at)
-             VALUES ($1, $2, 'running', $3, $4, $5, NOW())
-             RETURNING id",
+        let job_id = insert_search_job(
+            &mut tx,
+            search_type,
+            params,
+            range_start,
+            range_end,
+            block_size,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(job_id)
+    }
+
+    /// [`Database::create_search_job`], unless a pending, running or paused
+    /// job already runs the same search, in which case its id is returned.
+    /// The flag reports whether the id belongs to an existing job (`true`).
+    ///
+    /// A job is a duplicate when its search type, range and `params` match;
+    /// `params` compares as jsonb, so key order does not matter. A
+    /// transaction-scoped advisory lock on the search serializes concurrent
+    /// creates.
+    pub async fn find_or_create_search_job(
+        &self,
+        search_type: &str,
+        params: &Value,
+        range_start: i64,
+        range_end: i64,
+        block_size: i64,
+    ) -> Result<(i64, bool)> {
+        // serde_json maps keep their keys sorted, so equal params render alike.
+        let dedup_key = format!("search_job:{}:{}", search_type, params);
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(&dedup_key)
+            .execute(&mut *tx)
+            .await?;
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM search_jobs
+             WHERE search_type = $1 AND params = $2 AND range_start = $3 AND range_end = $4
+               AND status IN ('pending', 'running', 'paused')
+             ORDER BY id LIMIT 1",
         )
         .bind(search_type)
         .bind(params)
         .bind(range_start)
         .bind(range_end)
-        .bind(block_size)
-        .fetch_one(&mut *tx)
+        .fetch_optional(&mut *tx)
         .await?;
-
-        // Estimate block duration from the cost model for dynamic stale timeout
-        let estimated_duration_s: Option<i32> = {
-            use crate::project::{estimate_digits_for_form, secs_per_candidate};
-            let mid = ((range_start + range_end) / 2) as u64;
-            let avg_digits = estimate_digits_for_form(search_type, mid);
-            if avg_digits > 0 {
-                let spc = secs_per_candidate(search_type, avg_digits, false);
-                let candidates_per_block = block_size as f64;
-                let est = (spc * candidates_per_block).ceil() as i32;
-                if est > 0 { Some(est) } else { None }
-            } else {
-                None
-            }
-        };
-
-        let mut start = range_start;
-        while start < range_end {
-            let end = (start + block_size).min(range_end);
-            sqlx::query(
-                "INSERT INTO work_blocks (search_job_id, block_start, block_end, estimated_duration_s)
-                 VALUES ($1, $2, $3, $4)",
-            )
-            .bind(job_id)
-            .bind(start)
-            .bind(end)
-            .bind(estimated_duration_s)
-            .execute(&mut *tx)
-            .await?;
-            start = end;
+        if let Some(job_id) = existing {
+            tx.commit().await?;
+            return Ok((job_id, true));
         }
+
+        let job_id = insert_search_job(
+            &mut tx,
+            search_type,
+            params,
+            range_start,
+            range_end,
+            block_size,
+        )
+        .await?;
         tx.commit().await?;
-        Ok(job_id)
+        Ok((job_id, false))
     }
 
     /// List all search jobs, most recent first.
@@ -157,24 +310,160 @@ impl Database {
         Ok(())
     }
 
+    /// Stop every running search of `form` at once.
+    ///
+    /// Cancels the form's running jobs, so no further blocks are claimed from
+    /// them, and sets `pending_command = 'stop'` on every worker running that
+    /// form, which `PgWorkerClient` picks up on its next heartbeat and
+    /// reports through `is_stop_requested`. Other forms are left alone.
+    pub async fn request_stop_by_form(&self, form: &str) -> Result<super::FormStop> {
+        let mut tx = self.pool.begin().await?;
+        let job_ids: Vec<i64> = sqlx::query_scalar(
+            "UPDATE search_jobs SET status = 'cancelled', error = 'Stopped by form via API',
+                    stopped_at = NOW()
+             WHERE search_type = $1 AND status = 'running'
+             RETURNING id",
+        )
+        .bind(form)
+        .fetch_all(&mut *tx)
+        .await?;
+        let workers_signalled =
+            sqlx::query("UPDATE workers SET pending_command = 'stop' WHERE search_type = $1")
+                .bind(form)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        tx.commit().await?;
+        Ok(super::FormStop {
+            job_ids,
+            workers_signalled,
+        })
+    }
+
+    /// Take a known-bad worker's work back.
+    ///
+    /// Every block `worker_id` has claimed but not finished goes back to
+    /// 'available' for other workers. Blocks it completed in the last
+    /// `reverify_hours` hours are queued in `verification_queue`, where
+    /// `claim_verification_block` hands them to a different worker; blocks
+    /// already awaiting verification are not queued twice. Other workers'
+    /// blocks are left alone.
+    pub async fn release_worker_blocks(
+        &self,
+        worker_id: &str,
+        reverify_hours: i64,
+    ) -> Result<super::WorkerRelease> {
+        let mut tx = self.pool.begin().await?;
+        let released: Vec<i64> = sqlx::query_scalar(
+            "UPDATE work_blocks
+             SET status = 'available', claimed_by = NULL, volunteer_id = NULL, claimed_at = NULL
+             WHERE claimed_by = $1 AND status = 'claimed'
+             RETURNING id",
+        )
+        .bind(worker_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let reverify_queued: Vec<i64> = sqlx::query_scalar(
+            "INSERT INTO verification_queue
+                (original_block_id, search_job_id, block_start, block_end,
+                 original_tested, original_found, original_worker, original_volunteer_id)
+             SELECT wb.id, wb.search_job_id, wb.block_start, wb.block_end,
+                    wb.tested, wb.found, wb.claimed_by, wb.volunteer_id
+             FROM work_blocks wb
+             WHERE wb.claimed_by = $1
+               AND wb.status = 'completed'
+               AND wb.completed_at > NOW() - ($2 || ' hours')::interval
+               AND NOT EXISTS (
+                   SELECT 1 FROM verification_queue vq
+                   WHERE vq.original_block_id = wb.id
+                     AND vq.status IN ('pending', 'claimed')
+               )
+             RETURNING original_block_id",
+        )
+        .bind(worker_id)
+        .bind(reverify_hours.to_string())
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(super::WorkerRelease {
+            released,
+            reverify_queued,
+        })
+    }
+
     /// Atomically claim an available work block using `FOR UPDATE SKIP LOCKED`.
     ///
-    /// The PostgreSQL function `claim_work_block(job_id, worker_id)` finds the
-    /// lowest-numbered available block, marks it as 'claimed', and returns it.
+    /// Takes the lowest available block of a running job and resizes it to
+    /// [`effective_block_size`] (see [`Database::adapt_block_sizes`]).
     /// Returns `None` if no blocks are available.
     pub async fn claim_work_block(
         &self,
         job_id: i64,
         worker_id: &str,
     ) -> Result<Option<WorkBlock>> {
-        let row = sqlx::query_as::<_, WorkBlock>(
-            "SELECT block_id, block_start, block_end FROM claim_work_block($1, $2)",
+        let mut tx = self.pool.begin().await?;
+        let block = claim_sized_block(&mut tx, job_id, worker_id).await?;
+        tx.commit().await?;
+        Ok(block.map(|b| WorkBlock {
+            block_id: b.block_id,
+            block_start: b.block_start,
+            block_end: b.block_end,
+        }))
+    }
+
+    /// Adjust every running job's `block_size_multiplier` from the blocks
+    /// that finished since the last call. Returns the jobs that changed.
+    ///
+    /// New failures (stale reclaims or failed blocks) halve the multiplier.
+    /// Otherwise, if every block completed since the last call took less than
+    /// [`FAST_BLOCK_FRACTION`] of [`STALE_BLOCK_SECS`], it grows by
+    /// [`BLOCK_SIZE_GROWTH`]. The result stays within
+    /// [`MIN_BLOCK_SIZE_MULTIPLIER`]..=[`MAX_BLOCK_SIZE_MULTIPLIER`].
+    pub async fn adapt_block_sizes(&self) -> Result<Vec<BlockSizeChange>> {
+        let mut rows = sqlx::query_as::<_, BlockSizeChange>(
+            "WITH stats AS (
+                 SELECT sj.id,
+                        COALESCE(SUM(wb.reclaim_count), 0)
+                          + COUNT(*) FILTER (WHERE wb.status = 'failed'
+                                               AND wb.quarantine_reason IS NULL) AS failures,
+                        COUNT(*) FILTER (WHERE wb.status = 'completed'
+                                           AND wb.completed_at > sj.block_size_adapted_at) AS done,
+                        COUNT(*) FILTER (WHERE wb.status = 'completed'
+                                           AND wb.completed_at > sj.block_size_adapted_at
+                                           AND wb.duration_secs < $1) AS fast
+                 FROM search_jobs sj
+                 JOIN work_blocks wb ON wb.search_job_id = sj.id
+                 WHERE sj.status = 'running'
+                 GROUP BY sj.id
+             ),
+             next AS (
+                 SELECT s.id, s.failures, sj.block_size_multiplier AS old,
+                        CASE
+                            WHEN s.failures > sj.block_failures_seen
+                                THEN GREATEST($2, sj.block_size_multiplier * 0.5)
+                            WHEN s.done > 0 AND s.fast = s.done
+                                THEN LEAST($3, sj.block_size_multiplier * $4)
+                            ELSE sj.block_size_multiplier
+                        END AS new
+                 FROM stats s
+                 JOIN search_jobs sj ON sj.id = s.id
+             )
+             UPDATE search_jobs sj SET
+                 block_size_multiplier = next.new,
+                 block_failures_seen = next.failures,
+                 block_size_adapted_at = NOW()
+             FROM next
+             WHERE sj.id = next.id
+             RETURNING sj.id AS job_id, next.old AS previous, next.new AS multiplier",
         )
-        .bind(job_id)
-        .bind(worker_id)
-        .fetch_optional(&self.pool)
+        .bind(FAST_BLOCK_FRACTION * STALE_BLOCK_SECS as f64)
+        .bind(MIN_BLOCK_SIZE_MULTIPLIER)
+        .bind(MAX_BLOCK_SIZE_MULTIPLIER)
+        .bind(BLOCK_SIZE_GROWTH)
+        .fetch_all(&self.pool)
         .await?;
-        Ok(row)
+        rows.retain(|c| c.multiplier != c.previous);
+        Ok(rows)
     }
 
     /// Complete a work block with default 1 core.
@@ -195,13 +484,30 @@ impl Database {
         found: i64,
         cores_used: i32,
     ) -> Result<()> {
-        sqlx::query("SELECT complete_work_block_with_duration($1, $2, $3, $4)")
+        let mut conn = self.pool.acquire().await?;
+        complete_block(&mut conn, block_id, tested, found, cores_used).await
+    }
+
+    /// Complete a work block and store its survivor checksum.
+    ///
+    /// `checksum` is the block's [`crate::progress::survivor_checksum`],
+    /// stored bit-for-bit as BIGINT; `None` leaves `result_checksum` NULL.
+    pub async fn complete_work_block_with_checksum(
+        &self,
+        block_id: i64,
+        tested: i64,
+        found: i64,
+        checksum: Option<u64>,
+    ) -> Result<()> {
+        // One transaction, so a block is never left completed without its checksum
+        let mut tx = self.pool.begin().await?;
+        complete_block(&mut tx, block_id, tested, found, 1).await?;
+        sqlx::query("UPDATE work_blocks SET result_checksum = $2 WHERE id = $1")
             .bind(block_id)
-            .bind(tested)
-            .bind(found)
-            .bind(cores_used)
-            .execute(&self.pool)
+            .bind(checksum.map(|c| c as i64))
+            .execute(&mut *tx)
             .await?;
+        tx.commit().await?;
         Ok(())
     }
 
@@ -214,22 +520,56 @@ impl Database {
         Ok(())
     }
 
+    /// Hand `worker_id`'s unfinished claimed blocks back to the pool on a
+    /// clean shutdown.
+    ///
+    /// Each block goes back to 'available' with its `block_checkpoint`
+    /// intact, so the next claim resumes where this worker stopped; the work
+    /// loop stores the interrupted block's checkpoint first with
+    /// [`update_block_progress`](Self::update_block_progress). Blocks in
+    /// any other status, or since reclaimed by another worker, are left
+    /// alone. Returns how many were released.
+    pub async fn release_work_blocks(&self, worker_id: &str, block_ids: &[i64]) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE work_blocks
+             SET status = 'available', claimed_by = NULL, volunteer_id = NULL, claimed_at = NULL
+             WHERE id = ANY($1) AND status = 'claimed' AND claimed_by = $2",
+        )
+        .bind(block_ids)
+        .bind(worker_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     /// Reclaim blocks that have been claimed for longer than `stale_seconds`.
     ///
     /// The PostgreSQL function `reclaim_stale_blocks` resets claimed blocks back
     /// to 'available' status, allowing other workers to pick them up. This handles
-    /// the case where a worker crashes without completing its block.
-    pub async fn reclaim_stale_blocks(&self, stale_seconds: i32) -> Result<i32> {
-        let count: i32 = sqlx::query_scalar("SELECT reclaim_stale_blocks($1)")
-            .bind(stale_seconds)
-            .fetch_one(&self.pool)
-            .await?;
-        Ok(count)
+    /// the case where a worker crashes without completing its block. Each reclaim
+    /// bumps the block's `reclaim_count`; a block already reclaimed
+    /// `max_reclaims` times is marked 'failed' with a `quarantine_reason`
+    /// instead, so one that hangs every worker stops being requeued.
+    pub async fn reclaim_stale_blocks(
+        &self,
+        stale_seconds: i32,
+        max_reclaims: i32,
+    ) -> Result<BlockReclaim> {
+        let (requeued, quarantined): (i32, i32) =
+            sqlx::query_as("SELECT requeued, quarantined FROM reclaim_stale_blocks($1, $2)")
+                .bind(stale_seconds)
+                .bind(max_reclaims)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(BlockReclaim {
+            requeued: requeued as i64,
+            quarantined: quarantined as i64,
+        })
     }
 
     /// Get aggregated block status counts for a search job.
     ///
-    /// Returns counts of available/claimed/completed/failed blocks plus
+    /// Returns counts of available/claimed/completed/failed (and quarantined) blocks plus
     /// totals for tested candidates and found primes across completed blocks.
     pub async fn get_job_block_summary(&self, job_id: i64) -> Result<JobBlockSummary> {
         let row = sqlx::query_as::<_, JobBlockSummary>(
@@ -238,6 +578,7 @@ impl Database {
                 COUNT(*) FILTER (WHERE status = 'claimed') AS claimed,
                 COUNT(*) FILTER (WHERE status = 'completed') AS completed,
                 COUNT(*) FILTER (WHERE status = 'failed') AS failed,
+                COUNT(*) FILTER (WHERE quarantine_reason IS NOT NULL) AS quarantined,
                 COALESCE(SUM(tested) FILTER (WHERE status = 'completed'), 0)::BIGINT AS total_tested,
                 COALESCE(SUM(found) FILTER (WHERE status = 'completed'), 0)::BIGINT AS total_found
              FROM work_blocks WHERE search_job_id = $1",
@@ -248,6 +589,23 @@ impl Database {
         Ok(row)
     }
 
+    /// Coverage of a search job's range by block state; see
+    /// [`coalesce_coverage`]. Returns `None` if the job does not exist.
+    pub async fn get_job_coverage(&self, job_id: i64) -> Result<Option<JobCoverage>> {
+        let Some(job) = self.get_search_job(job_id).await? else {
+            return Ok(None);
+        };
+        let blocks: Vec<(i64, i64, String)> = sqlx::query_as(
+            "SELECT block_start, block_end, status FROM work_blocks
+             WHERE search_job_id = $1 ORDER BY block_start",
+        )
+        .bind(job_id)
+        .fetch_all(&self.read_pool)
+        .await?;
+        let coverage = coalesce_coverage(job.range_start, job.range_end, &blocks);
+        Ok(Some(coverage))
+    }
+
     /// Aggregate block counts across all search jobs (for Prometheus metrics).
     ///
     /// Returns a `JobBlockSummary` with global available/claimed counts. Used by
@@ -259,6 +617,7 @@ impl Database {
                 COUNT(*) FILTER (WHERE status = 'claimed') AS claimed,
                 COUNT(*) FILTER (WHERE status = 'completed') AS completed,
                 COUNT(*) FILTER (WHERE status = 'failed') AS failed,
+                COUNT(*) FILTER (WHERE quarantine_reason IS NOT NULL) AS quarantined,
                 COALESCE(SUM(tested) FILTER (WHERE status = 'completed'), 0)::BIGINT AS total_tested,
                 COALESCE(SUM(found) FILTER (WHERE status = 'completed'), 0)::BIGINT AS total_found
              FROM work_blocks",
@@ -308,3 +667,167 @@ impl Database {
         Ok(())
     }
 }
+
+/// Claim the lowest available block of a running job, resized to the job's
+/// [`effective_block_size`].
+///
+/// A wider block is cut down by [`split_block`]. A narrower one absorbs the
+/// following untouched blocks (never claimed, no checkpoint) until it is
+/// wide enough, splitting the last if it overshoots. Blocks with a
+/// checkpoint are claimed as they are.
+pub(super) async fn claim_sized_block(
+    tx: &mut sqlx::PgConnection,
+    job_id: i64,
+    worker_id: &str,
+) -> Result<Option<WorkBlockWithCheckpoint>> {
+    let block: Option<(i64, i64, i64, Option<Value>, i64, f64)> = sqlx::query_as(
+        "SELECT wb.id, wb.block_start, wb.block_end, wb.block_checkpoint,
+                sj.block_size, sj.block_size_multiplier
+         FROM work_blocks wb
+         JOIN search_jobs sj ON sj.id = wb.search_job_id
+         WHERE wb.search_job_id = $1
+           AND wb.status = 'available'
+           AND sj.status = 'running'
+         ORDER BY wb.block_start
+         LIMIT 1
+         FOR UPDATE OF wb SKIP LOCKED",
+    )
+    .bind(job_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some((block_id, start, mut end, checkpoint, block_size, multiplier)) = block else {
+        return Ok(None);
+    };
+
+    let target_size = effective_block_size(block_size, multiplier);
+    if checkpoint.is_none() {
+        let target_end = start + target_size;
+        while end < target_end {
+            let next: Option<(i64, i64)> = sqlx::query_as(
+                "SELECT id, block_end FROM work_blocks
+                 WHERE search_job_id = $1 AND block_start = $2
+                   AND status = 'available' AND reclaim_count = 0
+                   AND claimed_at IS NULL AND block_checkpoint IS NULL
+                 FOR UPDATE SKIP LOCKED",
+            )
+            .bind(job_id)
+            .bind(end)
+            .fetch_optional(&mut *tx)
+            .await?;
+            let Some((next_id, next_end)) = next else {
+                break;
+            };
+            sqlx::query("DELETE FROM work_blocks WHERE id = $1")
+                .bind(next_id)
+                .execute(&mut *tx)
+                .await?;
+            end = next_end;
+        }
+    }
+    let end = split_block(tx, block_id, start, end, checkpoint.as_ref(), target_size).await?;
+
+    let row = sqlx::query_as::<_, WorkBlockWithCheckpoint>(
+        "UPDATE work_blocks SET
+           block_end = $2,
+           status = 'claimed',
+           claimed_by = $3,
+           claimed_at = NOW()
+         WHERE id = $1
+         RETURNING id AS block_id, block_start, block_end, block_checkpoint",
+    )
+    .bind(block_id)
+    .bind(end)
+    .bind(worker_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    Ok(Some(row))
+}
+
+/// Mark `block_id` completed through `complete_work_block_with_duration`,
+/// which derives `duration_secs` from `claimed_at`.
+async fn complete_block(
+    conn: &mut sqlx::PgConnection,
+    block_id: i64,
+    tested: i64,
+    found: i64,
+    cores_used: i32,
+) -> Result<()> {
+    sqlx::query("SELECT complete_work_block_with_duration($1, $2, $3, $4)")
+        .bind(block_id)
+        .bind(tested)
+        .bind(found)
+        .bind(cores_used)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Cut the available block `block_id` spanning [`start`, `end`) down to at
+/// most `target_size` candidates before it is claimed, returning the new end.
+///
+/// The tail goes back into the pool as a new available block. A block with
+/// a checkpoint is never split, since the checkpoint may lie beyond the
+/// shortened end and the tail would then repeat work already done.
+pub(super) async fn split_block(
+    tx: &mut sqlx::PgConnection,
+    block_id: i64,
+    start: i64,
+    end: i64,
+    checkpoint: Option<&Value>,
+    target_size: i64,
+) -> Result<i64> {
+    let target_end = start + target_size.max(1);
+    if checkpoint.is_some() || end <= target_end {
+        return Ok(end);
+    }
+    sqlx::query(
+        "INSERT INTO work_blocks (search_job_id, block_start, block_end, estimated_duration_s)
+         SELECT search_job_id, $2, $3, estimated_duration_s
+         FROM work_blocks WHERE id = $1",
+    )
+    .bind(block_id)
+    .bind(target_end)
+    .bind(end)
+    .execute(&mut *tx)
+    .await?;
+    Ok(target_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: i64, end: i64) -> CoverageRange {
+        CoverageRange { start, end }
+    }
+
+    /// Out-of-order and overlapping blocks still merge per state, and the
+    /// uncovered head and tail of the job range come back as gaps.
+    #[test]
+    fn coalesce_coverage_merges_and_finds_gaps() {
+        let blocks = [
+            (30, 40, "completed".to_string()),
+            (10, 20, "completed".to_string()),
+            (20, 35, "completed".to_string()),
+            (40, 50, "available".to_string()),
+            (50, 60, "unknown".to_string()),
+        ];
+        let coverage = coalesce_coverage(0, 70, &blocks);
+        assert_eq!(coverage.completed, vec![range(10, 40)]);
+        assert_eq!(coverage.available, vec![range(40, 50)]);
+        assert!(coverage.claimed.is_empty() && coverage.failed.is_empty());
+        assert_eq!(coverage.gaps, vec![range(0, 10), range(60, 70)]);
+    }
+
+    /// The dedup lock key relies on serde_json rendering maps in sorted key
+    /// order (no `preserve_order` feature).
+    #[test]
+    fn params_render_independent_of_key_order() {
+        let a: Value =
+            serde_json::from_str(r#"{"k": 3, "opts": {"y": [{"b": 1, "a": 2}], "x": 0}}"#).unwrap();
+        let b: Value =
+            serde_json::from_str(r#"{"opts": {"x": 0, "y": [{"a": 2, "b": 1}]}, "k": 3}"#).unwrap();
+        assert_eq!(a.to_string(), b.to_string());
+        assert_eq!(a.to_string(), r#"{"k":3,"opts":{"x":0,"y":[{"a":2,"b":1}]}}"#);
+    }
+}
@@ -0,0 +1,276 @@
+//! Schema migrations for self-hosted PostgreSQL.
+//!
+//! Production on Supabase applies `supabase/migrations/` out-of-band. For a
+//! plain PostgreSQL server, `darkreach migrate` runs the ordered list in
+//! [`MIGRATIONS`] (embedded in the binary) and records each applied file in
+//! `schema_migrations`, so re-running it only applies what is new.
+//!
+//! Migrations `003`, `026`, `027` and `029`–`032` only run with `--supabase`:
+//! they depend on Supabase's `auth` schema, roles or linter, or on
+//! TimescaleDB. The integration tests build their schema from the same list.
+
+use anyhow::{Context, Result};
+use sqlx::{Connection, PgPool};
+use std::collections::HashSet;
+
+/// One migration file, embedded at compile time.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// File name under `supabase/migrations/`, e.g. `001_create_primes.sql`.
+    pub name: &'static str,
+    pub sql: &'static str,
+    /// Applied only when migrating a Supabase database.
+    pub supabase_only: bool,
+}
+
+macro_rules! migration {
+    ($name:literal) => {
+        migration!($name, false)
+    };
+    ($name:literal, supabase_only) => {
+        migration!($name, true)
+    };
+    ($name:literal, $supabase_only:expr) => {
+        Migration {
+            name: $name,
+            sql: include_str!(concat!("../../supabase/migrations/", $name)),
+            supabase_only: $supabase_only,
+        }
+    };
+}
+
+/// Migrations in the order they are applied.
+pub const MIGRATIONS: &[Migration] = &[
+    migration!("001_create_primes.sql"),
+    migration!("002_create_functions.sql"),
+    migration!("003_rls_policies.sql", supabase_only),
+    migration!("004_coordination_tables.sql"),
+    migration!("005_verification.sql"),
+    migration!("006_agents.sql"),
+    migration!("007_agent_cost_control.sql"),
+    migration!("008_agent_permissions.sql"),
+    migration!("009_agent_memory.sql"),
+    migration!("010_task_decomposition.sql"),
+    migration!("011_projects.sql"),
+    migration!("012_form_leaderboard.sql"),
+    migration!("0121_project_cost_tracking.sql"),
+    migration!("013_agent_roles.sql"),
+    migration!("014_agent_schedules.sql"),
+    migration!("015_add_certificate.sql"),
+    migration!("016_lifecycle_management.sql"),
+    migration!("017_cost_calibration.sql"),
+    migration!("018_agent_observability.sql"),
+    migration!("019_volunteers.sql"),
+    migration!("020_observability.sql"),
+    migration!("021_volunteer_worker_capabilities.sql"),
+    migration!("022_worker_release_channels.sql"),
+    migration!("023_volunteer_worker_release_tracking.sql"),
+    migration!("024_metric_rollups_daily.sql"),
+    migration!("025_operator_rename.sql"),
+    migration!("026_user_profiles.sql", supabase_only),
+    migration!("027_strategy_engine.sql", supabase_only),
+    migration!("028_network_scaling.sql"),
+    migration!("029_security_hardening.sql", supabase_only),
+    migration!("030_materialized_views.sql", supabase_only),
+    migration!("031_timescaledb_hypertables.sql", supabase_only),
+    migration!("032_ai_engine.sql", supabase_only),
+    migration!("033_prp_candidates.sql"),
+    migration!("034_operator_result_keys.sql"),
+    migration!("035_verification_failure_code.sql"),
+    migration!("036_primes_expression_trgm.sql"),
+    migration!("037_worker_benchmark_score.sql"),
+    migration!("038_operator_reliability_score.sql"),
+    migration!("039_worker_thread_utilization.sql"),
+    migration!("040_block_reclaim_quarantine.sql"),
+    migration!("042_work_block_result_checksum.sql"),
+    migration!("043_adaptive_block_size.sql"),
+    migration!("044_quorum_disputes.sql"),
+    migration!("045_operator_credits_granted_at.sql"),
+    migration!("046_worker_current_candidate.sql"),
+    migration!("047_verification_runs.sql"),
+];
+
+/// Advisory lock key held while migrating, so concurrent runs serialize.
+const MIGRATION_LOCK: i64 = 0x6461_726b_7265_6163; // "darkreac"
+
+/// Strip Supabase-specific statements that fail on plain PostgreSQL.
+///
+/// Removes `ALTER PUBLICATION` (Realtime), `ENABLE ROW LEVEL SECURITY` and
+/// `CREATE POLICY` statements (through their terminating `;`, since some span
+/// several lines). Policies reference `auth.uid()`, which only exists on
+/// Supabase.
+pub fn clean_migration_sql(sql: &str) -> String {
+    // A policy may continue onto following lines; skip until its `;`.
+    let mut in_policy = false;
+    sql.lines()
+        .filter(|line| {
+            let t = line.trim();
+            if t.starts_with("CREATE POLICY") {
+                in_policy = true;
+            }
+            if in_policy {
+                in_policy = !t.ends_with(';');
+                return false;
+            }
+            !t.starts_with("ALTER PUBLICATION") && !t.contains("ENABLE ROW LEVEL SECURITY")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drop top-level `BEGIN;` / `COMMIT;` lines, so a file that manages its own
+/// transaction runs inside the one [`run`] opens for it.
+fn strip_transaction_control(sql: &str) -> String {
+    sql.lines()
+        .filter(|line| !matches!(line.trim(), "BEGIN;" | "COMMIT;"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Migrations `run` applies for this target, in order.
+pub fn for_target(supabase: bool) -> impl Iterator<Item = &'static Migration> {
+    MIGRATIONS.iter().filter(move |m| supabase || !m.supabase_only)
+}
+
+/// Apply every migration not yet recorded in `schema_migrations`, in order.
+///
+/// With `supabase` false the Supabase-only files are skipped and the SQL goes
+/// through [`clean_migration_sql`] first. Returns the names applied by this
+/// run; an up-to-date schema returns an empty list. Each file and its
+/// `schema_migrations` row commit in one transaction, so a failed run leaves
+/// nothing half-applied and resumes from the failing file.
+pub async fn run(pool: &PgPool, supabase: bool) -> Result<Vec<&'static str>> {
+    let mut conn = pool.acquire().await?;
+    sqlx::query("SELECT pg_advisory_lock($1)")
+        .bind(MIGRATION_LOCK)
+        .execute(&mut *conn)
+        .await?;
+    let result = apply_pending(&mut conn, supabase).await;
+    sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(MIGRATION_LOCK)
+        .execute(&mut *conn)
+        .await?;
+    result
+}
+
+async fn apply_pending(conn: &mut sqlx::PgConnection, supabase: bool) -> Result<Vec<&'static str>> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+             name TEXT PRIMARY KEY,
+             applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+         )",
+    )
+    .execute(&mut *conn)
+    .await?;
+    let done: HashSet<String> = sqlx::query_scalar("SELECT name FROM schema_migrations")
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut applied = Vec::new();
+    for migration in for_target(supabase).filter(|m| !done.contains(m.name)) {
+        let sql = if supabase {
+            strip_transaction_control(migration.sql)
+        } else {
+            strip_transaction_control(&clean_migration_sql(migration.sql))
+        };
+        let mut tx = conn.begin().await?;
+        if !sql.trim().is_empty() {
+            sqlx::raw_sql(&sql)
+                .execute(&mut *tx)
+                .await
+                .with_context(|| format!("migration {} failed", migration.name))?;
+        }
+        sqlx::query("INSERT INTO schema_migrations (name) VALUES ($1)")
+            .bind(migration.name)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        applied.push(migration.name);
+    }
+    Ok(applied)
+}
+
+impl super::Database {
+    /// Bring the schema up to date; see [`run`].
+    pub async fn migrate(&self, supabase: bool) -> Result<Vec<&'static str>> {
+        run(&self.pool, supabase).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Policies are removed whole, including continuation lines, and the
+    /// statements around them survive.
+    #[test]
+    fn clean_strips_supabase_statements() {
+        let sql = "CREATE TABLE t (id INT);\n\
+                   ALTER TABLE t ENABLE ROW LEVEL SECURITY;\n\
+                   CREATE POLICY \"read\" ON t\n    FOR SELECT USING (true);\n\
+                   CREATE POLICY \"one\" ON t FOR SELECT USING (true);\n\
+                   ALTER PUBLICATION supabase_realtime ADD TABLE t;\n\
+                   CREATE INDEX t_id ON t (id);";
+        let kept = "CREATE TABLE t (id INT);\nCREATE INDEX t_id ON t (id);";
+        assert_eq!(clean_migration_sql(sql), kept);
+    }
+
+    /// Names are unique and in numeric order; `0121` runs between `012` and
+    /// `013`.
+    #[test]
+    fn migrations_are_ordered() {
+        let names: Vec<&str> = MIGRATIONS.iter().map(|m| m.name).collect();
+        let mut sorted = names.clone();
+        sorted.sort_by_key(|n| {
+            let number = n.split('_').next().unwrap();
+            (&number[..3], &number[3..])
+        });
+        assert_eq!(names, sorted);
+        sorted.dedup();
+        assert_eq!(sorted.len(), MIGRATIONS.len());
+    }
+
+    /// Every file in `supabase/migrations/` is listed, so a migration later
+    /// ones depend on (as 038 depends on 028's `node_block_results`) cannot be
+    /// left out silently.
+    #[test]
+    fn migrations_cover_every_file() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/supabase/migrations");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            if !name.ends_with(".sql") {
+                continue;
+            }
+            assert!(
+                MIGRATIONS.iter().any(|m| m.name == name),
+                "{name} is missing from MIGRATIONS"
+            );
+        }
+    }
+
+    /// `--supabase` applies every file; plain PostgreSQL skips exactly the
+    /// documented Supabase-only ones.
+    #[test]
+    fn supabase_only_migrations_run_only_on_supabase() {
+        assert_eq!(for_target(true).count(), MIGRATIONS.len());
+        let skipped: Vec<&str> = MIGRATIONS
+            .iter()
+            .filter(|m| !for_target(false).any(|t| t.name == m.name))
+            .map(|m| &m.name[..3])
+            .collect();
+        assert_eq!(skipped, ["003", "026", "027", "029", "030", "031", "032"]);
+    }
+
+    /// Files that open their own transaction lose only the top-level
+    /// `BEGIN;` / `COMMIT;`; PL/pgSQL block ends stay.
+    #[test]
+    fn strip_transaction_control_keeps_function_bodies() {
+        let sql = "BEGIN;\nCREATE FUNCTION f() RETURNS void AS $$\nBEGIN\nEND;\n$$ LANGUAGE plpgsql;\n  COMMIT;";
+        assert_eq!(
+            strip_transaction_control(sql),
+            "CREATE FUNCTION f() RETURNS void AS $$\nBEGIN\nEND;\n$$ LANGUAGE plpgsql;"
+        );
+    }
+}
@@ -25,6 +25,7 @@
 //! - [`projects`] — Multi-phase project management
 //! - [`calibrations`] — Cost model calibration coefficients
 //! - [`records`] — World record tracking
+//! - [`migrations`] — Embedded schema migrations for self-hosted PostgreSQL
 //!
 //! ## Sync Wrapper
 //!
@@ -38,6 +39,7 @@ mod agents;
 mod calibrations;
 mod jobs;
 mod memory;
+pub mod migrations;
 mod observability;
 mod primes;
 mod projects;
@@ -54,15 +56,26 @@ pub mod volunteers {
 }
 mod user_profiles;
 mod workers;
+
+pub use jobs::{
+    coalesce_coverage, effective_block_size, BLOCK_SIZE_GROWTH, FAST_BLOCK_FRACTION,
+    MAX_BLOCK_RECLAIMS, MAX_BLOCK_SIZE_MULTIPLIER, MIN_BLOCK_SIZE_MULTIPLIER, STALE_BLOCK_SECS,
+};
 pub use user_profiles::UserProfile;
+pub use workers::set_worker_thread_utilization;
 pub use ai_engine::{AiEngineDecisionRow, AiEngineStateRow};
 pub use strategy::{FormYieldRateRow, StrategyConfigRow, StrategyDecisionRow};
-pub use trust::{NodeReliability, VerificationBlock, VerificationOutcome, WorkBlockWithCheckpoint};
+pub use trust::{
+    NodeReliability, ResultDisagreement, VerificationBlock, VerificationOutcome,
+    WorkBlockWithCheckpoint,
+};
 pub use observability::{
-    MetricPoint, MetricSample, MetricSeries, SystemLogEntry, SystemLogRow, WorkerRateRow,
+    FormEfficiencyRow, MetricPoint, MetricSample, MetricSeries, SystemLogEntry, SystemLogRow,
+    WorkerRateRow,
 };
 pub use releases::{
-    WorkerReleaseAdoptionRow, WorkerReleaseChannelRow, WorkerReleaseEventRow, WorkerReleaseRow,
+    ReleaseHealthRow, WorkerReleaseAdoptionRow, WorkerReleaseChannelRow, WorkerReleaseEventRow,
+    WorkerReleaseRow, RELEASE_HEARTBEAT_TIMEOUT_SECS,
 };
 
 use anyhow::Result;
@@ -82,6 +95,18 @@ pub struct PrimeRecord {
     pub proof_method: String,
 }
 
+/// A found prime awaiting insertion, as accumulated by
+/// [`PrimeBuffer`](crate::prime_sink::PrimeBuffer) for a batched write.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPrime {
+    pub form: String,
+    pub expression: String,
+    pub digits: u64,
+    pub search_params: String,
+    pub proof_method: String,
+    pub certificate: Option<String>,
+}
+
 #[derive(Serialize)]
 pub struct FormCount {
     pub form: String,
@@ -99,6 +124,7 @@ pub struct Stats {
 #[derive(Deserialize, Default, Clone)]
 pub struct PrimeFilter {
     pub form: Option<String>,
+    /// Substring match on `expression` (`LIKE '%search%'`, no escaping).
     pub search: Option<String>,
     pub min_digits: Option<i64>,
     pub max_digits: Option<i64>,
@@ -140,6 +166,25 @@ pub struct PrimeDetail {
     pub proof_method: String,
 }
 
+/// A bulk re-verification run; see
+/// [`Database::create_or_resume_verification_run`].
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct VerificationRunRow {
+    pub id: i64,
+    pub form: Option<String>,
+    pub force: bool,
+    pub tool: String,
+    pub status: String,
+    /// Highest prime id already processed; the next fetch starts above it.
+    pub last_processed_id: i64,
+    pub verified: i64,
+    pub failed: i64,
+    pub skipped: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[derive(Serialize)]
 pub struct TimelineBucket {
     pub bucket: String,
@@ -168,6 +213,11 @@ pub struct WorkerRow {
     pub current: String,
     pub checkpoint: Option<String>,
     pub metrics: Option<Value>,
+    pub benchmark_score: Option<f64>,
+    pub rayon_threads: Option<i32>,
+    pub threads_busy: Option<i32>,
+    pub current_candidate: Option<String>,
+    pub current_candidate_started_at: Option<chrono::DateTime<chrono::Utc>>,
     pub registered_at: chrono::DateTime<chrono::Utc>,
     pub last_heartbeat: chrono::DateTime<chrono::Utc>,
 }
@@ -191,6 +241,33 @@ pub struct SearchJobRow {
     pub total_found: i64,
 }
 
+/// A job whose adaptive block size changed in
+/// [`Database::adapt_block_sizes`].
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct BlockSizeChange {
+    pub job_id: i64,
+    pub previous: f64,
+    pub multiplier: f64,
+}
+
+/// Outcome of [`Database::request_stop_by_form`].
+#[derive(Debug, Clone, Serialize)]
+pub struct FormStop {
+    /// Running jobs of the form, now cancelled.
+    pub job_ids: Vec<i64>,
+    /// Workers of the form that were sent a "stop" command.
+    pub workers_signalled: u64,
+}
+
+/// Outcome of [`Database::release_worker_blocks`].
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerRelease {
+    /// Blocks the worker had claimed, now back to 'available'.
+    pub released: Vec<i64>,
+    /// The worker's recently completed blocks, queued for re-verification.
+    pub reverify_queued: Vec<i64>,
+}
+
 #[derive(sqlx::FromRow)]
 pub struct WorkBlock {
     pub block_id: i64,
@@ -215,10 +292,41 @@ pub struct JobBlockSummary {
     pub claimed: i64,
     pub completed: i64,
     pub failed: i64,
+    /// Failed blocks quarantined by stale reclaim (subset of `failed`).
+    pub quarantined: i64,
     pub total_tested: i64,
     pub total_found: i64,
 }
 
+/// Outcome of one stale-block reclaim pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct BlockReclaim {
+    /// Blocks returned to 'available'.
+    pub requeued: i64,
+    /// Blocks past the reclaim limit, marked 'failed' with a quarantine reason.
+    pub quarantined: i64,
+}
+
+/// A half-open range [start, end) of a job's candidate indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CoverageRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Where each part of a job's range stands, built by
+/// [`coalesce_coverage`]. Adjacent blocks in the same state are merged
+/// into one range; `gaps` are stretches of the job's range that no block
+/// covers, which an intact job never has.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct JobCoverage {
+    pub completed: Vec<CoverageRange>,
+    pub claimed: Vec<CoverageRange>,
+    pub available: Vec<CoverageRange>,
+    pub failed: Vec<CoverageRange>,
+    pub gaps: Vec<CoverageRange>,
+}
+
 // ── Agent types ─────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, sqlx::FromRow)]
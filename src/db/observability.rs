@@ -42,6 +42,35 @@ pub struct SystemLogEntry {
     pub context: Option<Value>,
 }
 
+impl SystemLogEntry {
+    /// The `system_logs` row for one event-bus record, correlated with the
+    /// search job that emitted it. `source` is "coordinator" for the
+    /// dashboard's own bus and "worker" for a `work` loop's.
+    pub fn from_event(
+        e: crate::events::EventRecord,
+        source: &str,
+        worker_id: Option<&str>,
+    ) -> Self {
+        let level = match e.kind.as_str() {
+            "error" => "error",
+            "warning" => "warn",
+            _ => "info",
+        };
+        let ts = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(e.timestamp_ms);
+        SystemLogEntry {
+            ts: DateTime::<Utc>::from(ts),
+            level: level.to_string(),
+            source: source.to_string(),
+            component: "event_bus".to_string(),
+            message: e.message,
+            worker_id: worker_id.map(str::to_string),
+            search_job_id: e.search_job_id,
+            search_id: None,
+            context: Some(serde_json::json!({"kind": e.kind, "elapsed_secs": e.elapsed_secs})),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, sqlx::FromRow)]
 pub struct SystemLogRow {
     pub id: i64,
@@ -66,6 +95,32 @@ pub struct WorkerRateRow {
     pub found: f64,
 }
 
+/// Per-form discovery efficiency: primes found per million candidates tested.
+#[derive(Clone, Debug, Serialize)]
+pub struct FormEfficiencyRow {
+    pub form: String,
+    pub tested: i64,
+    pub found: i64,
+    pub primes_per_million: f64,
+    pub avg_digits: Option<f64>,
+}
+
+#[derive(Clone, Debug, sqlx::FromRow)]
+struct FormEfficiencyRaw {
+    form: String,
+    tested: i64,
+    found: i64,
+    avg_digits: Option<f64>,
+}
+
+fn primes_per_million(found: i64, tested: i64) -> f64 {
+    if tested <= 0 {
+        0.0
+    } else {
+        found as f64 / tested as f64 * 1_000_000.0
+    }
+}
+
 fn rate_from_samples(latest: f64, previous: f64, dt: f64) -> f64 {
     if dt <= 0.0 {
         0.0
@@ -74,6 +129,24 @@ fn rate_from_samples(latest: f64, previous: f64, dt: f64) -> f64 {
     }
 }
 
+/// Convert consecutive samples of a monotonic counter into per-second rates.
+///
+/// Each rate is stamped with the later sample's timestamp. A negative delta
+/// means the counter reset (worker restart), so it is clamped to zero rather
+/// than reported as negative throughput.
+fn counter_rates(points: &[MetricPoint]) -> Vec<MetricPoint> {
+    points
+        .windows(2)
+        .map(|pair| {
+            let dt = (pair[1].ts - pair[0].ts).num_milliseconds() as f64 / 1000.0;
+            MetricPoint {
+                ts: pair[1].ts,
+                value: rate_from_samples(pair[1].value, pair[0].value, dt).max(0.0),
+            }
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, sqlx::FromRow)]
 struct WorkerRateRaw {
     worker_id: String,
@@ -258,10 +331,11 @@ impl Database {
         source: Option<&str>,
         component: Option<&str>,
         worker_id: Option<&str>,
+        search_job_id: Option<i64>,
         limit: i64,
     ) -> Result<Vec<SystemLogRow>> {
         let rows = sqlx::query_as::<_, SystemLogRow>(
-            "SELECT id, ts, level, source, component, message, worker_id, search_job_id, search_id, context\n             FROM system_logs\n             WHERE ts BETWEEN $1 AND $2\n               AND ($3::text IS NULL OR level = $3)\n               AND ($4::text IS NULL OR source = $4)\n               AND ($5::text IS NULL OR component = $5)\n               AND ($6::text IS NULL OR worker_id = $6)\n             ORDER BY ts DESC\n             LIMIT $7",
+            "SELECT id, ts, level, source, component, message, worker_id, search_job_id, search_id, context\n             FROM system_logs\n             WHERE ts BETWEEN $1 AND $2\n               AND ($3::text IS NULL OR level = $3)\n               AND ($4::text IS NULL OR source = $4)\n               AND ($5::text IS NULL OR component = $5)\n               AND ($6::text IS NULL OR worker_id = $6)\n               AND ($7::bigint IS NULL OR search_job_id = $7)\n             ORDER BY ts DESC\n             LIMIT $8",
         )
         .bind(from)
         .bind(to)
@@ -269,6 +343,7 @@ impl Database {
         .bind(source)
         .bind(component)
         .bind(worker_id)
+        .bind(search_job_id)
         .bind(limit)
         .fetch_all(&self.read_pool)
         .await?;
@@ -340,6 +415,30 @@ impl Database {
             .collect())
     }
 
+    /// Candidates/sec over time for one search job, derived from its
+    /// `search_job.total_tested` samples.
+    pub async fn get_search_throughput(
+        &self,
+        job_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<MetricPoint>> {
+        let job_id = job_id.to_string();
+        let points = self
+            .get_metric_points(
+                from,
+                to,
+                "search_job.total_tested",
+                Some("search_job"),
+                None,
+                Some("job_id"),
+                Some(&job_id),
+                "raw",
+            )
+            .await?;
+        Ok(counter_rates(&points))
+    }
+
     pub async fn max_metric_in_range(
         &self,
         from: DateTime<Utc>,
@@ -424,6 +523,62 @@ impl Database {
         Ok(rows)
     }
 
+    /// Discovery efficiency per form.
+    ///
+    /// `tested` and `found` sum the latest `search_job.total_tested` /
+    /// `search_job.total_found` sample of every job, grouped by the job's
+    /// `search_type` label. `avg_digits` comes from the `primes` table, so it
+    /// is `None` for forms without any stored prime.
+    pub async fn get_form_efficiency(&self) -> Result<Vec<FormEfficiencyRow>> {
+        let rows = sqlx::query_as::<_, FormEfficiencyRaw>(
+            "WITH latest AS (
+                SELECT DISTINCT ON (labels->>'job_id', metric)
+                    labels->>'search_type' AS form,
+                    metric,
+                    value
+                FROM metric_samples
+                WHERE scope = 'search_job'
+                  AND metric IN ('search_job.total_tested', 'search_job.total_found')
+                  AND labels ? 'job_id'
+                  AND labels ? 'search_type'
+                ORDER BY labels->>'job_id', metric, ts DESC
+            ),
+            per_form AS (
+                SELECT
+                    form,
+                    COALESCE(SUM(value) FILTER (WHERE metric = 'search_job.total_tested'), 0) AS tested,
+                    COALESCE(SUM(value) FILTER (WHERE metric = 'search_job.total_found'), 0) AS found
+                FROM latest
+                GROUP BY form
+            ),
+            digits AS (
+                SELECT form, AVG(digits)::float8 AS avg_digits
+                FROM primes
+                GROUP BY form
+            )
+            SELECT
+                p.form,
+                p.tested::BIGINT AS tested,
+                p.found::BIGINT AS found,
+                d.avg_digits
+            FROM per_form p
+            LEFT JOIN digits d ON d.form = p.form
+            ORDER BY p.form",
+        )
+        .fetch_all(&self.read_pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| FormEfficiencyRow {
+                primes_per_million: primes_per_million(row.found, row.tested),
+                form: row.form,
+                tested: row.tested,
+                found: row.found,
+                avg_digits: row.avg_digits,
+            })
+            .collect())
+    }
+
     /// Compute top workers by tested/sec over a recent window.
     pub async fn get_top_workers_by_rate(
         &self,
@@ -521,11 +676,36 @@ impl Database {
             .await?;
         Ok(())
     }
+
+    /// Refresh planner statistics on the high-churn tables.
+    ///
+    /// Autovacuum's analyze threshold lags behind the insert/delete rate of
+    /// `metric_samples` and `work_blocks`, so the planner drifts toward
+    /// sequential scans. Called from the hourly housekeeping loop unless
+    /// `OBS_ANALYZE_ENABLED=false`.
+    pub async fn analyze_hot_tables(&self) -> Result<()> {
+        sqlx::query("ANALYZE primes, metric_samples, work_blocks, search_jobs")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::rate_from_samples;
+    use super::{counter_rates, primes_per_million, rate_from_samples, MetricPoint};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn primes_per_million_scales_found_over_tested() {
+        assert!((primes_per_million(3, 1_500_000) - 2.0).abs() < 1e-9);
+        assert_eq!(primes_per_million(0, 1000), 0.0);
+    }
+
+    #[test]
+    fn primes_per_million_handles_zero_tested() {
+        assert_eq!(primes_per_million(5, 0), 0.0);
+    }
 
     #[test]
     fn rate_from_samples_handles_zero_dt() {
@@ -537,4 +717,38 @@ mod tests {
         let rate = rate_from_samples(150.0, 50.0, 10.0);
         assert!((rate - 10.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn counter_rates_divides_deltas_by_interval() {
+        let t0 = Utc::now();
+        let points: Vec<MetricPoint> = [(0, 0.0), (10, 100.0), (30, 500.0)]
+            .iter()
+            .map(|&(secs, value)| MetricPoint {
+                ts: t0 + Duration::seconds(secs),
+                value,
+            })
+            .collect();
+        let rates = counter_rates(&points);
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].ts, points[1].ts);
+        assert!((rates[0].value - 10.0).abs() < 1e-6);
+        assert!((rates[1].value - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn counter_rates_clamps_resets_to_zero() {
+        let t0 = Utc::now();
+        let points = vec![
+            MetricPoint {
+                ts: t0,
+                value: 1000.0,
+            },
+            MetricPoint {
+                ts: t0 + Duration::seconds(60),
+                value: 50.0,
+            },
+        ];
+        assert_eq!(counter_rates(&points)[0].value, 0.0);
+        assert!(counter_rates(&points[..1]).is_empty());
+    }
 }
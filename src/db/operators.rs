@@ -4,7 +4,7 @@
 //! trust level progression (adaptive replication), and credit tracking for
 //! the public operator computing platform.
 
-use super::Database;
+use super::{BlockReclaim, Database};
 use anyhow::Result;
 use serde::Serialize;
 
@@ -30,6 +30,9 @@ pub struct OperatorTrustRow {
     pub total_valid: i32,
     pub total_invalid: i32,
     pub trust_level: i16,
+    /// Time-decayed valid-block ratio, refreshed by the coordinator.
+    /// `None` until the first refresh after the operator has block history.
+    pub reliability_score: Option<f64>,
 }
 
 /// Leaderboard entry from the `operator_leaderboard` view.
@@ -46,6 +49,34 @@ pub struct LeaderboardRow {
     pub worker_count: Option<i64>,
 }
 
+/// Time span a leaderboard ranks over, from `?window=7d|30d|all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardWindow {
+    Days7,
+    Days30,
+    All,
+}
+
+impl LeaderboardWindow {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "7d" => Some(LeaderboardWindow::Days7),
+            "30d" => Some(LeaderboardWindow::Days30),
+            "all" => Some(LeaderboardWindow::All),
+            _ => None,
+        }
+    }
+
+    /// Window length in days; `None` for all time.
+    pub fn days(self) -> Option<i32> {
+        match self {
+            LeaderboardWindow::Days7 => Some(7),
+            LeaderboardWindow::Days30 => Some(30),
+            LeaderboardWindow::All => None,
+        }
+    }
+}
+
 impl Database {
     // ── Registration ──────────────────────────────────────────────
 
@@ -109,14 +140,15 @@ impl Database {
         gpu_vram_gb: Option<i32>,
         worker_version: Option<&str>,
         update_channel: Option<&str>,
+        benchmark_score: Option<f64>,
     ) -> Result<()> {
         sqlx::query(
             "INSERT INTO operator_nodes (
                volunteer_id, worker_id, hostname, cores, cpu_model,
                os, arch, ram_gb, has_gpu, gpu_model, gpu_vram_gb,
-               worker_version, update_channel
+               worker_version, update_channel, benchmark_score
              )
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
              ON CONFLICT (worker_id) DO UPDATE SET
                hostname = EXCLUDED.hostname,
                cores = EXCLUDED.cores,
@@ -129,6 +161,7 @@ impl Database {
                gpu_vram_gb = EXCLUDED.gpu_vram_gb,
                worker_version = EXCLUDED.worker_version,
                update_channel = EXCLUDED.update_channel,
+               benchmark_score = COALESCE(EXCLUDED.benchmark_score, operator_nodes.benchmark_score),
                last_heartbeat = NOW()",
         )
         .bind(volunteer_id)
@@ -144,6 +177,7 @@ impl Database {
         .bind(gpu_vram_gb)
         .bind(worker_version)
         .bind(update_channel)
+        .bind(benchmark_score)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -166,7 +200,7 @@ impl Database {
         let rows = sqlx::query_as::<_, OperatorNodeRow>(
             "SELECT worker_id, hostname, cores, cpu_model, os, arch,
                     ram_gb, has_gpu, gpu_model, worker_version,
-                    registered_at, last_heartbeat
+                    benchmark_score, registered_at, last_heartbeat
              FROM operator_nodes
              WHERE volunteer_id = $1
              ORDER BY last_heartbeat DESC NULLS LAST",
@@ -209,59 +243,29 @@ impl Database {
         volunteer_id: uuid::Uuid,
         caps: &WorkerCapabilities,
     ) -> Result<Option<OperatorWorkBlock>> {
-        let row = sqlx::query_as::<_, OperatorWorkBlock>(
+        let sql = format!(
             "UPDATE work_blocks SET
                status = 'claimed',
-               claimed_by = $1::text,
-               volunteer_id = $2,
+               claimed_by = $6::text,
+               volunteer_id = $7,
                claimed_at = NOW()
              WHERE id = (
                SELECT wb.id
                FROM work_blocks wb
                JOIN search_jobs sj ON sj.id = wb.search_job_id
                WHERE wb.status = 'available'
-                 AND (
-                   NOT (sj.params ? 'min_cores')
-                   OR (
-                     jsonb_typeof(sj.params->'min_cores') = 'number'
-                     AND (sj.params->>'min_cores')::int <= $3
-                   )
-                 )
-                 AND (
-                   NOT (sj.params ? 'min_ram_gb')
-                   OR (
-                     jsonb_typeof(sj.params->'min_ram_gb') = 'number'
-                     AND (sj.params->>'min_ram_gb')::int <= $4
-                   )
-                 )
-                 AND (
-                   NOT (sj.params ? 'requires_gpu')
-                   OR lower(sj.params->>'requires_gpu') <> 'true'
-                   OR $5 = TRUE
-                 )
-                 AND (
-                   NOT (sj.params ? 'required_os')
-                   OR ($6 IS NOT NULL AND lower(sj.params->>'required_os') = lower($6))
-                 )
-                 AND (
-                   NOT (sj.params ? 'required_arch')
-                   OR ($7 IS NOT NULL AND lower(sj.params->>'required_arch') = lower($7))
-                 )
+                 AND {OPERATOR_CAPABILITY_FILTER}
                ORDER BY wb.id
                FOR UPDATE SKIP LOCKED
                LIMIT 1
              )
-             RETURNING id AS block_id, search_job_id, block_start, block_end",
-        )
-        .bind(volunteer_id.to_string())
-        .bind(volunteer_id)
-        .bind(caps.cores)
-        .bind(caps.ram_gb)
-        .bind(caps.has_gpu)
-        .bind(caps.os.as_deref())
-        .bind(caps.arch.as_deref())
-        .fetch_optional(&self.pool)
-        .await?;
+             RETURNING id AS block_id, search_job_id, block_start, block_end"
+        );
+        let row = bind_capabilities(sqlx::query_as::<_, OperatorWorkBlock>(&sql), caps)
+            .bind(volunteer_id.to_string())
+            .bind(volunteer_id)
+            .fetch_optional(&self.pool)
+            .await?;
 
         if let Some(ref block) = row {
             // Look up the search job to get search_type and params
@@ -287,6 +291,111 @@ impl Database {
         Ok(row)
     }
 
+    /// Search job of the first available block the given capabilities
+    /// qualify for. Takes no lock: the follow-up
+    /// [`claim_operator_block_sized`](Self::claim_operator_block_sized)
+    /// returns None if another node claims the block in between.
+    pub async fn next_operator_job(&self, caps: &WorkerCapabilities) -> Result<Option<i64>> {
+        let sql = format!(
+            "SELECT wb.search_job_id
+             FROM work_blocks wb
+             JOIN search_jobs sj ON sj.id = wb.search_job_id
+             WHERE wb.status = 'available'
+               AND {OPERATOR_CAPABILITY_FILTER}
+             ORDER BY wb.id
+             LIMIT 1"
+        );
+        let row = bind_capabilities(sqlx::query_as::<_, (i64,)>(&sql), caps)
+            .fetch_optional(&self.read_pool)
+            .await?;
+        Ok(row.map(|(job_id,)| job_id))
+    }
+
+    /// Claim at most `target_size` candidates of `job_id` for the operator
+    /// node `node_id` (its `worker_id`).
+    ///
+    /// Takes the job's first available block and, when it spans more than
+    /// `target_size`, splits it with [`split_block`](super::jobs::split_block):
+    /// the node claims the head and the tail goes back into the pool. A block
+    /// reclaimed with a checkpoint is claimed whole and handed out from just
+    /// past its `last_tested`, so the node does not redo finished work.
+    /// Returns None if the node is unknown or the job has no available block.
+    pub async fn claim_operator_block_sized(
+        &self,
+        job_id: i64,
+        node_id: &str,
+        target_size: i64,
+    ) -> Result<Option<OperatorWorkBlock>> {
+        let mut tx = self.pool.begin().await?;
+        let volunteer_id: Option<uuid::Uuid> =
+            sqlx::query_scalar("SELECT volunteer_id FROM operator_nodes WHERE worker_id = $1")
+                .bind(node_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+        let Some(volunteer_id) = volunteer_id else {
+            return Ok(None);
+        };
+
+        let block: Option<(i64, i64, i64, Option<serde_json::Value>)> = sqlx::query_as(
+            "SELECT id, block_start, block_end, block_checkpoint
+             FROM work_blocks
+             WHERE search_job_id = $1 AND status = 'available'
+             ORDER BY id
+             FOR UPDATE SKIP LOCKED
+             LIMIT 1",
+        )
+        .bind(job_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some((block_id, block_start, block_end, checkpoint)) = block else {
+            return Ok(None);
+        };
+        let claim_end = super::jobs::split_block(
+            &mut tx,
+            block_id,
+            block_start,
+            block_end,
+            checkpoint.as_ref(),
+            target_size,
+        )
+        .await?;
+
+        let row = sqlx::query_as::<_, OperatorWorkBlock>(
+            "UPDATE work_blocks SET
+               block_end = $2,
+               status = 'claimed',
+               claimed_by = $3::text,
+               volunteer_id = $4,
+               claimed_at = NOW()
+             WHERE id = $1
+             RETURNING id AS block_id, search_job_id, block_start, block_end",
+        )
+        .bind(block_id)
+        .bind(claim_end)
+        .bind(volunteer_id.to_string())
+        .bind(volunteer_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let job = sqlx::query_as::<_, OperatorJobInfo>(
+            "SELECT search_type, params FROM search_jobs WHERE id = $1",
+        )
+        .bind(job_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let resume_start = checkpoint
+            .as_ref()
+            .and_then(|cp| cp.get("last_tested").and_then(|v| v.as_i64()))
+            .map_or(row.block_start, |last| (last + 1).clamp(row.block_start, row.block_end));
+        Ok(Some(OperatorWorkBlock {
+            block_start: resume_start,
+            search_type: Some(job.search_type),
+            params: Some(job.params),
+            ..row
+        }))
+    }
+
     // ── Result Submission ─────────────────────────────────────────
 
     /// Record a completed block result from a volunteer.
@@ -317,6 +426,85 @@ impl Database {
         Ok(row)
     }
 
+    // ── Result Idempotency ────────────────────────────────────────
+
+    /// Reserve an idempotency key for a result submission.
+    ///
+    /// Returns `true` if this request owns the key and should apply the
+    /// result, `false` if the key was already used by this operator.
+    pub async fn reserve_operator_result_key(
+        &self,
+        volunteer_id: uuid::Uuid,
+        key: uuid::Uuid,
+        block_id: i32,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT INTO operator_result_keys (volunteer_id, idempotency_key, block_id)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (volunteer_id, idempotency_key) DO NOTHING",
+        )
+        .bind(volunteer_id)
+        .bind(key)
+        .bind(block_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Stored response for a used key, or `None` while the first request
+    /// holding it is still in flight.
+    pub async fn get_operator_result_response(
+        &self,
+        volunteer_id: uuid::Uuid,
+        key: uuid::Uuid,
+    ) -> Result<Option<serde_json::Value>> {
+        let response: Option<Option<serde_json::Value>> = sqlx::query_scalar(
+            "SELECT response FROM operator_result_keys
+             WHERE volunteer_id = $1 AND idempotency_key = $2",
+        )
+        .bind(volunteer_id)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(response.flatten())
+    }
+
+    /// Record the response for a key once its result has been applied.
+    pub async fn complete_operator_result_key(
+        &self,
+        volunteer_id: uuid::Uuid,
+        key: uuid::Uuid,
+        response: &serde_json::Value,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE operator_result_keys SET response = $3
+             WHERE volunteer_id = $1 AND idempotency_key = $2",
+        )
+        .bind(volunteer_id)
+        .bind(key)
+        .bind(response)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Release a reserved key after a failed submission so a retry can apply.
+    pub async fn release_operator_result_key(
+        &self,
+        volunteer_id: uuid::Uuid,
+        key: uuid::Uuid,
+    ) -> Result<()> {
+        sqlx::query(
+            "DELETE FROM operator_result_keys
+             WHERE volunteer_id = $1 AND idempotency_key = $2 AND response IS NULL",
+        )
+        .bind(volunteer_id)
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     // ── Trust & Credits ───────────────────────────────────────────
 
     /// Get the trust record for an operator.
@@ -441,6 +629,43 @@ impl Database {
         Ok(rows)
     }
 
+    /// Leaderboard over `window`: top N by credit granted within it.
+    ///
+    /// `All` is [`get_operator_leaderboard`](Self::get_operator_leaderboard).
+    /// Shorter windows sum `operator_credits` rows granted since the cutoff,
+    /// count the `prime_discovered` grants as `primes_found`, and leave out
+    /// operators with no credit in the window.
+    pub async fn get_leaderboard_windowed(
+        &self,
+        window: LeaderboardWindow,
+        limit: i64,
+    ) -> Result<Vec<LeaderboardRow>> {
+        let Some(days) = window.days() else {
+            return self.get_operator_leaderboard(limit).await;
+        };
+        let rows = sqlx::query_as::<_, LeaderboardRow>(
+            "SELECT o.id, o.username, o.team,
+                    SUM(c.credit)::BIGINT AS credit,
+                    (COUNT(*) FILTER (WHERE c.reason = 'prime_discovered'))::INT AS primes_found,
+                    o.joined_at, o.last_seen,
+                    COALESCE(ot.trust_level, 1) AS trust_level,
+                    (SELECT COUNT(*) FROM operator_nodes n
+                     WHERE n.volunteer_id = o.id) AS worker_count
+             FROM operator_credits c
+             JOIN operators o ON o.id = c.volunteer_id
+             LEFT JOIN operator_trust ot ON ot.volunteer_id = o.id
+             WHERE c.granted_at >= NOW() - make_interval(days => $1)
+             GROUP BY o.id, ot.trust_level
+             ORDER BY credit DESC, o.username
+             LIMIT $2",
+        )
+        .bind(days)
+        .bind(limit)
+        .fetch_all(&self.read_pool)
+        .await?;
+        Ok(rows)
+    }
+
     // ── Quorum & Verification ─────────────────────────────────────
 
     /// Set the minimum quorum for a work block based on volunteer trust.
@@ -485,21 +710,40 @@ impl Database {
     }
 
     /// Reclaim stale volunteer blocks (24-hour timeout, vs 2-min for internal).
-    pub async fn reclaim_stale_operator_blocks(&self, timeout_secs: i64) -> Result<i64> {
-        let result = sqlx::query(
-            "UPDATE work_blocks SET
-               status = 'available',
-               claimed_by = NULL,
-               volunteer_id = NULL,
-               claimed_at = NULL
-             WHERE status = 'claimed'
-               AND volunteer_id IS NOT NULL
-               AND claimed_at < NOW() - ($1 || ' seconds')::interval",
+    ///
+    /// Counts reclaims like [`Database::reclaim_stale_blocks`]: a block already
+    /// reclaimed `max_reclaims` times is quarantined as 'failed'.
+    pub async fn reclaim_stale_operator_blocks(
+        &self,
+        timeout_secs: i64,
+        max_reclaims: i32,
+    ) -> Result<BlockReclaim> {
+        let row = sqlx::query_as::<_, BlockReclaim>(
+            "WITH reclaimed AS (
+               UPDATE work_blocks SET
+                 status = CASE WHEN reclaim_count >= $2 THEN 'failed' ELSE 'available' END,
+                 quarantine_reason = CASE WHEN reclaim_count >= $2
+                   THEN format('stalled %s times; last claimed by %s',
+                               reclaim_count + 1, COALESCE(claimed_by, 'unknown'))
+                   END,
+                 reclaim_count = reclaim_count + 1,
+                 claimed_by = NULL,
+                 volunteer_id = NULL,
+                 claimed_at = NULL
+               WHERE status = 'claimed'
+                 AND volunteer_id IS NOT NULL
+                 AND claimed_at < NOW() - ($1 || ' seconds')::interval
+               RETURNING status
+             )
+             SELECT COUNT(*) FILTER (WHERE status = 'available') AS requeued,
+                    COUNT(*) FILTER (WHERE status = 'failed') AS quarantined
+             FROM reclaimed",
         )
         .bind(timeout_secs.to_string())
-        .execute(&self.pool)
+        .bind(max_reclaims)
+        .fetch_one(&self.pool)
         .await?;
-        Ok(result.rows_affected() as i64)
+        Ok(row)
     }
 }
 
@@ -512,6 +756,65 @@ pub struct WorkerCapabilities {
     pub arch: Option<String>,
 }
 
+/// Job-level hardware requirements (`min_cores`, `min_ram_gb`,
+/// `requires_gpu`, `required_os`, `required_arch` in `search_jobs.params`)
+/// checked against `sj`. Binds $1..$5 via [`bind_capabilities`].
+const OPERATOR_CAPABILITY_FILTER: &str = "(
+                   NOT (sj.params ? 'min_cores')
+                   OR (
+                     jsonb_typeof(sj.params->'min_cores') = 'number'
+                     AND (sj.params->>'min_cores')::int <= $1
+                   )
+                 )
+                 AND (
+                   NOT (sj.params ? 'min_ram_gb')
+                   OR (
+                     jsonb_typeof(sj.params->'min_ram_gb') = 'number'
+                     AND (sj.params->>'min_ram_gb')::int <= $2
+                   )
+                 )
+                 AND (
+                   NOT (sj.params ? 'requires_gpu')
+                   OR lower(sj.params->>'requires_gpu') <> 'true'
+                   OR $3 = TRUE
+                 )
+                 AND (
+                   NOT (sj.params ? 'required_os')
+                   OR ($4 IS NOT NULL AND lower(sj.params->>'required_os') = lower($4))
+                 )
+                 AND (
+                   NOT (sj.params ? 'required_arch')
+                   OR ($5 IS NOT NULL AND lower(sj.params->>'required_arch') = lower($5))
+                 )";
+
+/// Bind `caps` as $1..$5 of a query built on [`OPERATOR_CAPABILITY_FILTER`].
+fn bind_capabilities<'q, O>(
+    query: sqlx::query::QueryAs<'q, sqlx::Postgres, O, sqlx::postgres::PgArguments>,
+    caps: &'q WorkerCapabilities,
+) -> sqlx::query::QueryAs<'q, sqlx::Postgres, O, sqlx::postgres::PgArguments> {
+    query
+        .bind(caps.cores)
+        .bind(caps.ram_gb)
+        .bind(caps.has_gpu)
+        .bind(caps.os.as_deref())
+        .bind(caps.arch.as_deref())
+}
+
+/// Core count that earns a full-size work block; smaller nodes get a
+/// proportional slice.
+pub const FULL_BLOCK_CORES: i32 = 32;
+
+/// Block size for a node with `cores` cores, out of a job's `full_size`.
+///
+/// Scales linearly so every node finishes its block in about the same wall
+/// time — well inside the operator reclaim timeout — instead of a 1-core
+/// node sitting on a block sized for a 32-core server. Never below 1.
+pub fn operator_block_size(full_size: i64, cores: i32) -> i64 {
+    let cores = i64::from(cores.clamp(1, FULL_BLOCK_CORES));
+    let full = i64::from(FULL_BLOCK_CORES);
+    ((full_size * cores + full - 1) / full).max(1)
+}
+
 /// Work block assigned to a volunteer (subset of work_blocks columns).
 #[derive(Serialize, sqlx::FromRow)]
 pub struct OperatorWorkBlock {
@@ -555,6 +858,7 @@ pub struct OperatorNodeRow {
     pub has_gpu: Option<bool>,
     pub gpu_model: Option<String>,
     pub worker_version: Option<String>,
+    pub benchmark_score: Option<f64>,
     pub registered_at: chrono::DateTime<chrono::Utc>,
     pub last_heartbeat: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -4,15 +4,25 @@
 //! newly discovered primes (both async and sync-from-rayon), filtered listing with
 //! dynamic WHERE clauses, verification status updates, and best-per-form lookups.
 
-use super::{Database, PrimeDetail, PrimeFilter, PrimeRecord};
+use super::{Database, NewPrime, PrimeDetail, PrimeFilter, PrimeRecord, VerificationRunRow};
+use crate::certificate;
 use anyhow::Result;
 
+/// Certificate JSON as bound to `primes.certificate`; compressed when
+/// `CERT_COMPRESS` is set (see [`certificate::encode_for_storage`]).
+fn stored_certificate(json: Option<&str>) -> Result<Option<String>> {
+    json.map(certificate::encode_for_storage).transpose()
+}
+
 impl Database {
     /// Insert a new prime record with the current timestamp.
     ///
     /// Called from engine modules after a candidate passes primality testing.
     /// The `certificate` parameter is an optional JSON string containing the
     /// primality certificate (Proth witness, LLR residue, Pocklington chain, etc.).
+    /// Read it back with [`get_prime_certificate`].
+    ///
+    /// [`get_prime_certificate`]: Database::get_prime_certificate
     pub async fn insert_prime(
         &self,
         form: &str,
@@ -22,18 +32,97 @@ impl Database {
         proof_method: &str,
         certificate: Option<&str>,
     ) -> Result<()> {
-        sqlx::query(
+        self.insert_prime_row(
+            form,
+            expression,
+            digits,
+            search_params,
+            proof_method,
+            certificate,
+            false,
+        )
+        .await
+    }
+
+    /// Shared body of [`insert_prime`] and [`insert_prime_sync`]; with
+    /// `ignore_duplicates` a row already present for (form, expression) is
+    /// left alone and not counted as a discovery.
+    ///
+    /// [`insert_prime`]: Database::insert_prime
+    /// [`insert_prime_sync`]: Database::insert_prime_sync
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_prime_row(
+        &self,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+        certificate: Option<&str>,
+        ignore_duplicates: bool,
+    ) -> Result<()> {
+        let certificate = stored_certificate(certificate)?;
+        let sql = if ignore_duplicates {
+            "INSERT INTO primes (form, expression, digits, found_at, search_params, proof_method, certificate)
+             VALUES ($1, $2, $3, NOW(), $4, $5, $6::jsonb)
+             ON CONFLICT (form, expression) DO NOTHING"
+        } else {
             "INSERT INTO primes (form, expression, digits, found_at, search_params, proof_method, certificate)
-             VALUES ($1, $2, $3, NOW(), $4, $5, $6::jsonb)",
+             VALUES ($1, $2, $3, NOW(), $4, $5, $6::jsonb)"
+        };
+        let inserted = sqlx::query(sql)
+            .bind(form)
+            .bind(expression)
+            .bind(digits as i64)
+            .bind(search_params)
+            .bind(proof_method)
+            .bind(certificate)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        crate::prom_metrics::record_prime_found(form, inserted);
+        Ok(())
+    }
+
+    /// Insert several primes in one statement, all stamped with the same
+    /// `found_at`.
+    ///
+    /// Used by `PrimeBuffer` so a burst of discoveries costs one round trip
+    /// instead of one per prime. A prime already stored for (form,
+    /// expression), e.g. one re-found after a block was reclaimed, is skipped
+    /// rather than failing the batch, and is not counted as a discovery.
+    pub async fn insert_primes_batch(&self, primes: &[NewPrime]) -> Result<()> {
+        if primes.is_empty() {
+            return Ok(());
+        }
+        let forms: Vec<&str> = primes.iter().map(|p| p.form.as_str()).collect();
+        let expressions: Vec<&str> = primes.iter().map(|p| p.expression.as_str()).collect();
+        let digits: Vec<i64> = primes.iter().map(|p| p.digits as i64).collect();
+        let params: Vec<&str> = primes.iter().map(|p| p.search_params.as_str()).collect();
+        let methods: Vec<&str> = primes.iter().map(|p| p.proof_method.as_str()).collect();
+        let certificates = primes
+            .iter()
+            .map(|p| stored_certificate(p.certificate.as_deref()))
+            .collect::<Result<Vec<_>>>()?;
+        let inserted: Vec<String> = sqlx::query_scalar(
+            "INSERT INTO primes (form, expression, digits, found_at, search_params, proof_method, certificate)
+             SELECT f, e, d, NOW(), s, m, c::jsonb
+             FROM UNNEST($1::text[], $2::text[], $3::bigint[], $4::text[], $5::text[], $6::text[])
+               AS t(f, e, d, s, m, c)
+             ON CONFLICT (form, expression) DO NOTHING
+             RETURNING form",
         )
-        .bind(form)
-        .bind(expression)
-        .bind(digits as i64)
-        .bind(search_params)
-        .bind(proof_method)
-        .bind(certificate)
-        .execute(&self.pool)
+        .bind(&forms)
+        .bind(&expressions)
+        .bind(&digits)
+        .bind(&params)
+        .bind(&methods)
+        .bind(&certificates)
+        .fetch_all(&self.pool)
         .await?;
+        for form in &inserted {
+            crate::prom_metrics::record_prime_found(form, 1);
+        }
         Ok(())
     }
 
@@ -49,7 +138,7 @@ impl Database {
         search_params: &str,
         proof_method: &str,
     ) -> Result<()> {
-        sqlx::query(
+        let inserted = sqlx::query(
             "INSERT INTO primes (form, expression, digits, found_at, search_params, proof_method)
              VALUES ($1, $2, $3, NOW(), $4, $5)
              ON CONFLICT (form, expression) DO NOTHING",
@@ -60,7 +149,9 @@ impl Database {
         .bind(search_params)
         .bind(proof_method)
         .execute(&self.pool)
-        .await?;
+        .await?
+        .rows_affected();
+        crate::prom_metrics::record_prime_found(form, inserted);
         Ok(())
     }
 
@@ -70,6 +161,12 @@ impl Database {
     /// `.await`. This bridges async sqlx operations into sync context via
     /// `tokio::runtime::Handle::block_on`. Safe because rayon threads are not
     /// tokio tasks — they won't deadlock the executor.
+    ///
+    /// Unlike [`insert_prime`], a prime already stored for (form, expression)
+    /// is skipped rather than failing, so a re-run over an overlapping range
+    /// does not abort the search.
+    ///
+    /// [`insert_prime`]: Database::insert_prime
     pub fn insert_prime_sync(
         &self,
         rt: &tokio::runtime::Handle,
@@ -80,16 +177,26 @@ impl Database {
         proof_method: &str,
         certificate: Option<&str>,
     ) -> Result<()> {
-        rt.block_on(self.insert_prime(
+        rt.block_on(self.insert_prime_row(
             form,
             expression,
             digits,
             search_params,
             proof_method,
             certificate,
+            true,
         ))
     }
 
+    /// Synchronous batch insert for rayon threads.
+    pub fn insert_primes_batch_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        primes: &[NewPrime],
+    ) -> Result<()> {
+        rt.block_on(self.insert_primes_batch(primes))
+    }
+
     /// Synchronous duplicate-ignoring insert for rayon threads.
     pub fn insert_prime_ignore_sync(
         &self,
@@ -103,6 +210,232 @@ impl Database {
         rt.block_on(self.insert_prime_ignore(form, expression, digits, search_params, proof_method))
     }
 
+    /// Park a probabilistic result in `prp_candidates` instead of `primes`.
+    ///
+    /// Used by `--only-proven` searches: the PRP is not a discovery yet, but is
+    /// kept for a later proof pass. Duplicates on (form, expression) are ignored.
+    pub async fn insert_prp_candidate(
+        &self,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO prp_candidates (form, expression, digits, search_params, proof_method)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (form, expression) DO NOTHING",
+        )
+        .bind(form)
+        .bind(expression)
+        .bind(digits as i64)
+        .bind(search_params)
+        .bind(proof_method)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Synchronous wrapper for `insert_prp_candidate` for rayon threads.
+    pub fn insert_prp_candidate_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+    ) -> Result<()> {
+        rt.block_on(self.insert_prp_candidate(
+            form,
+            expression,
+            digits,
+            search_params,
+            proof_method,
+        ))
+    }
+
+    /// Get primes of a form still carrying a probabilistic proof method.
+    ///
+    /// Matches the labels `crate::is_probabilistic` treats as unproven. Used by
+    /// the project prove phase to find PRPs awaiting a deterministic proof;
+    /// only rows with `id > after_id` are returned, so a pass can resume past
+    /// PRPs it already attempted.
+    pub async fn get_unproven_primes(
+        &self,
+        form: &str,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<PrimeDetail>> {
+        let rows = sqlx::query_as::<_, PrimeDetail>(
+            "SELECT id, form, expression, digits, found_at, search_params, proof_method
+             FROM primes
+             WHERE form = $1 AND (proof_method LIKE 'probabilistic%' OR proof_method = 'PRP')
+               AND id > $2
+             ORDER BY id LIMIT $3",
+        )
+        .bind(form)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.read_pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Record a deterministic proof for a prime previously stored as a PRP.
+    ///
+    /// The existing certificate is kept when the new proof does not produce one.
+    pub async fn update_prime_proof(
+        &self,
+        id: i64,
+        proof_method: &str,
+        certificate: Option<&str>,
+    ) -> Result<()> {
+        let certificate = stored_certificate(certificate)?;
+        sqlx::query(
+            "UPDATE primes SET proof_method = $1, certificate = COALESCE($2::jsonb, certificate)
+             WHERE id = $3",
+        )
+        .bind(proof_method)
+        .bind(certificate)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Collapse duplicate (form, expression) rows into one, returning how
+    /// many rows were removed.
+    ///
+    /// `primes_form_expression_unique` has guarded the table since `001`, so
+    /// this is a repair for databases restored or imported without it. The
+    /// earliest-found row of each group is kept and takes the group's best
+    /// proof (anything [`crate::is_probabilistic`] rejects wins, together with
+    /// that row's certificate or lack of one) and its strongest verification
+    /// (lowest tier). Project and record
+    /// references to the removed rows are repointed at the kept one. Runs in
+    /// one transaction.
+    pub async fn dedupe_primes(&self) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "CREATE TEMP TABLE prime_dupes ON COMMIT DROP AS
+             SELECT id, keeper_id FROM (
+                 SELECT id,
+                        FIRST_VALUE(id) OVER (PARTITION BY form, expression ORDER BY found_at, id) AS keeper_id,
+                        COUNT(*) OVER (PARTITION BY form, expression) AS copies
+                 FROM primes
+             ) grouped
+             WHERE copies > 1",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE primes k
+             SET proof_method = best.proof_method,
+                 certificate = best.certificate
+             FROM (
+                 SELECT DISTINCT ON (d.keeper_id) d.keeper_id, p.proof_method, p.certificate
+                 FROM prime_dupes d JOIN primes p ON p.id = d.id
+                 ORDER BY d.keeper_id,
+                          NOT (p.proof_method LIKE 'probabilistic%' OR p.proof_method = 'PRP') DESC,
+                          (p.certificate IS NOT NULL) DESC, p.found_at, p.id
+             ) best
+             WHERE k.id = best.keeper_id",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE primes k
+             SET verified = true, verified_at = best.verified_at,
+                 verification_method = best.verification_method,
+                 verification_tier = best.verification_tier,
+                 verification_failure_code = NULL
+             FROM (
+                 SELECT DISTINCT ON (d.keeper_id) d.keeper_id, p.verified_at,
+                        p.verification_method, p.verification_tier
+                 FROM prime_dupes d JOIN primes p ON p.id = d.id
+                 WHERE p.verified
+                 ORDER BY d.keeper_id, p.verification_tier NULLS LAST, p.verified_at
+             ) best
+             WHERE k.id = best.keeper_id",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE projects SET best_prime_id = d.keeper_id
+             FROM prime_dupes d WHERE best_prime_id = d.id AND d.id <> d.keeper_id",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE records SET our_best_id = d.keeper_id
+             FROM prime_dupes d WHERE our_best_id = d.id AND d.id <> d.keeper_id",
+        )
+        .execute(&mut *tx)
+        .await?;
+        let removed = sqlx::query(
+            "DELETE FROM primes USING prime_dupes d
+             WHERE primes.id = d.id AND d.id <> d.keeper_id",
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        tx.commit().await?;
+        Ok(removed)
+    }
+
+    /// Get PRPs parked by `--only-proven` that have not been proven yet, with
+    /// `id > after_id`.
+    pub async fn get_unproven_prp_candidates(
+        &self,
+        form: &str,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<PrimeDetail>> {
+        let rows = sqlx::query_as::<_, PrimeDetail>(
+            "SELECT id, form, expression, digits, found_at, search_params, proof_method
+             FROM prp_candidates
+             WHERE form = $1 AND proven_at IS NULL AND id > $2
+             ORDER BY id LIMIT $3",
+        )
+        .bind(form)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.read_pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Promote a proven PRP candidate into `primes` and stamp `proven_at`.
+    ///
+    /// Returns true if a new `primes` row was inserted (false if the candidate
+    /// was already proven or the prime already exists).
+    pub async fn promote_prp_candidate(
+        &self,
+        id: i64,
+        proof_method: &str,
+        certificate: Option<&str>,
+    ) -> Result<bool> {
+        let certificate = stored_certificate(certificate)?;
+        let result = sqlx::query(
+            "WITH proven AS (
+                 UPDATE prp_candidates SET proven_at = NOW()
+                 WHERE id = $1 AND proven_at IS NULL
+                 RETURNING form, expression, digits, found_at, search_params
+             )
+             INSERT INTO primes (form, expression, digits, found_at, search_params, proof_method, certificate)
+             SELECT form, expression, digits, found_at, search_params, $2, $3::jsonb FROM proven
+             ON CONFLICT (form, expression) DO NOTHING",
+        )
+        .bind(id)
+        .bind(proof_method)
+        .bind(certificate)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Query primes with dynamic filtering, sorting, and pagination.
     ///
     /// Builds a parameterized SQL query at runtime based on which filter fields
@@ -218,6 +551,49 @@ impl Database {
         Ok(count)
     }
 
+    /// Page of primes whose expression contains `query` as a literal substring.
+    ///
+    /// Backs `GET /api/primes/search?q=3*2^`. `%`, `_`, and `\` in the query
+    /// are escaped so it always matches literally; the `pg_trgm` GIN index on
+    /// `expression` serves the infix `LIKE`. Newest primes come first.
+    pub async fn search_primes(
+        &self,
+        query: &str,
+        form: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PrimeDetail>> {
+        let rows = sqlx::query_as::<_, PrimeDetail>(
+            "SELECT id, form, expression, digits, found_at, search_params, proof_method
+             FROM primes
+             WHERE expression LIKE $1 ESCAPE '\\'
+               AND ($2::text IS NULL OR form = $2)
+             ORDER BY id DESC
+             LIMIT $3 OFFSET $4",
+        )
+        .bind(like_substring(query))
+        .bind(form)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.read_pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Total matches for [`search_primes`](Self::search_primes), for pagination.
+    pub async fn count_search_primes(&self, query: &str, form: Option<&str>) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM primes
+             WHERE expression LIKE $1 ESCAPE '\\'
+               AND ($2::text IS NULL OR form = $2)",
+        )
+        .bind(like_substring(query))
+        .bind(form)
+        .fetch_one(&self.read_pool)
+        .await?;
+        Ok(count)
+    }
+
     /// Get unverified primes for the verification pipeline.
     pub async fn get_unverified_primes(&self, limit: i64) -> Result<Vec<PrimeDetail>> {
         let rows = sqlx::query_as::<_, PrimeDetail>(
@@ -233,18 +609,21 @@ impl Database {
     /// Get unverified primes with optional form filter and force-reverify flag.
     ///
     /// When `force` is true, returns already-verified primes too (for re-verification).
+    /// Only ids above `after_id` are returned, in id order, so a resumable run
+    /// can page through the table; pass 0 to start from the beginning.
     pub async fn get_unverified_primes_filtered(
         &self,
         limit: i64,
         form: Option<&str>,
         force: bool,
+        after_id: i64,
     ) -> Result<Vec<PrimeDetail>> {
         let verified_clause = if force { "" } else { "NOT verified AND" };
         let (sql, has_form) = if form.is_some() {
             (
                 format!(
                     "SELECT id, form, expression, digits, found_at, search_params, proof_method
-                     FROM primes WHERE {} form = $1 ORDER BY id LIMIT $2",
+                     FROM primes WHERE {} form = $1 AND id > $3 ORDER BY id LIMIT $2",
                     verified_clause
                 ),
                 true,
@@ -253,7 +632,7 @@ impl Database {
             (
                 format!(
                     "SELECT id, form, expression, digits, found_at, search_params, proof_method
-                     FROM primes WHERE {} TRUE ORDER BY id LIMIT $1",
+                     FROM primes WHERE {} id > $2 ORDER BY id LIMIT $1",
                     verified_clause
                 ),
                 false,
@@ -266,6 +645,7 @@ impl Database {
         } else {
             query = query.bind(limit);
         }
+        query = query.bind(after_id);
         let rows = query.fetch_all(&self.read_pool).await?;
         Ok(rows)
     }
@@ -282,12 +662,24 @@ impl Database {
         Ok(row)
     }
 
+    /// Certificate of a prime, decompressed if it was stored with
+    /// `CERT_COMPRESS`. `None` when the prime doesn't exist or has no
+    /// certificate.
+    pub async fn get_prime_certificate(&self, id: i64) -> Result<Option<serde_json::Value>> {
+        let stored: Option<Option<serde_json::Value>> =
+            sqlx::query_scalar("SELECT certificate FROM primes WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&self.read_pool)
+                .await?;
+        stored.flatten().map(certificate::decode_stored).transpose()
+    }
+
     /// Mark a prime as verified with the verification method and tier.
     ///
     /// Tier levels: 1 = deterministic proof, 2 = BPSW+MR10, 3 = PFGW cross-verify.
     pub async fn mark_verified(&self, id: i64, method: &str, tier: i16) -> Result<()> {
         sqlx::query(
-            "UPDATE primes SET verified = true, verified_at = NOW(), verification_method = $1, verification_tier = $2 WHERE id = $3",
+            "UPDATE primes SET verified = true, verified_at = NOW(), verification_method = $1, verification_tier = $2, verification_failure_code = NULL WHERE id = $3",
         )
         .bind(method)
         .bind(tier)
@@ -297,18 +689,98 @@ impl Database {
         Ok(())
     }
 
-    /// Mark a prime's verification as failed with a reason string.
-    pub async fn mark_verification_failed(&self, id: i64, reason: &str) -> Result<()> {
+    /// Mark a prime's verification as failed with a reason string and a
+    /// failure code (`verify::VerifyFailureCode::as_str`).
+    pub async fn mark_verification_failed(&self, id: i64, reason: &str, code: &str) -> Result<()> {
         sqlx::query(
-            "UPDATE primes SET verification_method = $1, verification_tier = 0 WHERE id = $2",
+            "UPDATE primes SET verification_method = $1, verification_tier = 0, verification_failure_code = $2 WHERE id = $3",
         )
         .bind(reason)
+        .bind(code)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the failure code of a verification that errored before reaching
+    /// a verdict (`verify::VerifyResult::Errored`). The prime is left
+    /// unverified, with its stored method untouched, so the next run retries it.
+    pub async fn record_verification_error(&self, id: i64, code: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE primes SET verification_failure_code = $1 WHERE id = $2 AND NOT verified",
+        )
+        .bind(code)
         .bind(id)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
+    /// Resume the running re-verification run for this `form`/`force`/`tool`,
+    /// or start one at the beginning of the table if none is live.
+    ///
+    /// The partial unique index on live runs makes concurrent calls converge on
+    /// one row.
+    pub async fn create_or_resume_verification_run(
+        &self,
+        form: Option<&str>,
+        force: bool,
+        tool: &str,
+    ) -> Result<VerificationRunRow> {
+        let row = sqlx::query_as::<_, VerificationRunRow>(
+            "INSERT INTO verification_runs (form, force, tool) VALUES ($1, $2, $3)
+             ON CONFLICT ((COALESCE(form, '')), force, tool) WHERE status = 'running'
+             DO UPDATE SET updated_at = NOW()
+             RETURNING id, form, force, tool, status, last_processed_id,
+                       verified, failed, skipped, created_at, updated_at, completed_at",
+        )
+        .bind(form)
+        .bind(force)
+        .bind(tool)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
+    /// Record that a run has processed every prime up to `last_processed_id`,
+    /// with its cumulative outcome counts.
+    pub async fn record_verification_run_progress(
+        &self,
+        run_id: i64,
+        last_processed_id: i64,
+        verified: i64,
+        failed: i64,
+        skipped: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE verification_runs
+             SET last_processed_id = $2, verified = $3, failed = $4, skipped = $5, updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(run_id)
+        .bind(last_processed_id)
+        .bind(verified)
+        .bind(failed)
+        .bind(skipped)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark a run finished, so the next `--resumable` invocation starts anew.
+    pub async fn complete_verification_run(&self, run_id: i64) -> Result<()> {
+        sqlx::query(
+            "UPDATE verification_runs
+             SET status = 'completed', completed_at = NOW(), updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(run_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Get our largest prime for a given form (used for records comparison).
     pub async fn get_best_prime_for_form(&self, form: &str) -> Result<Option<PrimeRecord>> {
         let row = sqlx::query_as::<_, PrimeRecord>(
@@ -352,4 +824,116 @@ impl Database {
         .await?;
         Ok(rows)
     }
+
+    /// Count failed verifications per failure code among primes found within
+    /// a time range.
+    pub async fn count_verification_failures_by_code(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<(String, i64)>> {
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            "SELECT verification_failure_code, COUNT(*)::BIGINT FROM primes WHERE verification_failure_code IS NOT NULL AND found_at BETWEEN $1 AND $2 GROUP BY verification_failure_code ORDER BY verification_failure_code",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.read_pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Gaps between consecutive indices (the n of k·b^n ± 1) of one family's
+    /// primes in `form`, as `(n_prev, n_next, gap)` in ascending n.
+    ///
+    /// Expressions are split with [`crate::candidate::parse_family`]; a prime
+    /// is in the family when every key of `params_filter` matches its
+    /// parameters, so `{"k": 3, "base": 2}` takes both signs of 3·2^n ± 1 and
+    /// `{}` takes the whole form. Unparsable expressions are skipped.
+    pub async fn prime_n_gaps(
+        &self,
+        form: &str,
+        params_filter: &serde_json::Value,
+    ) -> Result<Vec<(u64, u64, u64)>> {
+        let expressions: Vec<String> =
+            sqlx::query_scalar("SELECT expression FROM primes WHERE form = $1")
+                .bind(form)
+                .fetch_all(&self.read_pool)
+                .await?;
+        Ok(family_gaps(form, &expressions, params_filter))
+    }
+}
+
+/// Consecutive gaps between the distinct indices of `expressions` in the
+/// family `params_filter` selects; see [`Database::prime_n_gaps`].
+fn family_gaps(
+    form: &str,
+    expressions: &[String],
+    params_filter: &serde_json::Value,
+) -> Vec<(u64, u64, u64)> {
+    let wanted = params_filter.as_object();
+    let mut indices: Vec<u64> = expressions
+        .iter()
+        .filter_map(|expr| crate::candidate::parse_family(form, expr))
+        .filter(|(params, _)| {
+            wanted.is_none_or(|w| w.iter().all(|(key, value)| params.get(key) == Some(value)))
+        })
+        .map(|(_, index)| index)
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+        .windows(2)
+        .map(|w| (w[0], w[1], w[1] - w[0]))
+        .collect()
+}
+
+/// `LIKE` pattern matching `query` anywhere, with metacharacters escaped.
+fn like_substring(query: &str) -> String {
+    let mut pattern = String::with_capacity(query.len() + 2);
+    pattern.push('%');
+    for c in query.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn like_substring_wraps_and_escapes() {
+        assert_eq!(like_substring("3*2^"), "%3*2^%");
+        assert_eq!(like_substring("50%_off\\"), "%50\\%\\_off\\\\%");
+        assert_eq!(like_substring(""), "%%");
+    }
+
+    #[test]
+    fn family_gaps_selects_one_family() {
+        let expressions: Vec<String> = [
+            "3*2^11 + 1",
+            "3*2^2 + 1",
+            "5*2^4 + 1",
+            "3*2^5 + 1",
+            "3*2^3 - 1",
+            "not a kbn",
+        ]
+        .map(String::from)
+        .to_vec();
+        let plus = serde_json::json!({"k": 3, "base": 2, "c": 1});
+        assert_eq!(
+            family_gaps("kbn", &expressions, &plus),
+            vec![(2, 5, 3), (5, 11, 6)]
+        );
+        let both = serde_json::json!({"k": 3, "base": 2});
+        assert_eq!(
+            family_gaps("kbn", &expressions, &both),
+            vec![(2, 3, 1), (3, 5, 2), (5, 11, 6)]
+        );
+        assert!(family_gaps("kbn", &expressions, &serde_json::json!({"k": 7})).is_empty());
+    }
 }
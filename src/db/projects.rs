@@ -143,6 +143,26 @@ impl Database {
         Ok(row)
     }
 
+    /// Get a single project by ID.
+    pub async fn get_project_by_id(
+        &self,
+        project_id: i64,
+    ) -> Result<Option<crate::project::ProjectRow>> {
+        let row = sqlx::query_as::<_, crate::project::ProjectRow>(
+            "SELECT id, slug, name, description, objective, form, status, toml_source,
+                    target, competitive, strategy, infrastructure, budget,
+                    total_tested, total_found, best_prime_id, best_digits,
+                    total_core_hours::FLOAT8 AS total_core_hours,
+                    total_cost_usd::FLOAT8 AS total_cost_usd,
+                    created_at, started_at, completed_at, updated_at
+             FROM projects WHERE id = $1",
+        )
+        .bind(project_id)
+        .fetch_optional(&self.read_pool)
+        .await?;
+        Ok(row)
+    }
+
     /// Get all phases for a project, ordered by phase_order.
     pub async fn get_project_phases(
         &self,
@@ -208,6 +228,20 @@ impl Database {
         Ok(())
     }
 
+    /// Replace a phase's `search_params`, e.g. to persist a proof pass cursor.
+    pub async fn update_phase_params(
+        &self,
+        phase_id: i64,
+        search_params: &serde_json::Value,
+    ) -> Result<()> {
+        sqlx::query("UPDATE project_phases SET search_params = $1 WHERE id = $2")
+            .bind(search_params)
+            .bind(phase_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     /// Update a phase's status.
     pub async fn update_phase_status(&self, phase_id: i64, status: &str) -> Result<()> {
         let completed = if matches!(status, "completed" | "skipped" | "failed") {
@@ -257,6 +291,17 @@ impl Database {
         Ok(())
     }
 
+    /// Activate a phase that runs without a search job (a `"prove"` pass).
+    pub async fn start_phase(&self, phase_id: i64) -> Result<()> {
+        sqlx::query(
+            "UPDATE project_phases SET status = 'active', started_at = NOW() WHERE id = $1",
+        )
+        .bind(phase_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Insert a new phase into an existing project at runtime.
     ///
     /// Used by adaptive phase generation to add follow-up phases after
@@ -377,4 +422,26 @@ impl Database {
         .await?;
         Ok(())
     }
+
+    /// Largest `best_digits` among projects searching `form`.
+    ///
+    /// Returns `None` when no project tracks the form. Used to seed the event
+    /// bus's per-form record so milestone events fire only for new bests.
+    pub async fn get_best_digits(&self, form: &str) -> Result<Option<u64>> {
+        let best: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(best_digits) FROM projects WHERE form = $1")
+                .bind(form)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(best.map(|d| d.max(0) as u64))
+    }
+
+    /// Synchronous wrapper for engine modules running on rayon threads.
+    pub fn get_best_digits_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        form: &str,
+    ) -> Result<Option<u64>> {
+        rt.block_on(self.get_best_digits(form))
+    }
 }
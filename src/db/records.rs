@@ -7,8 +7,11 @@
 //!
 //! Records are upserted on (form, category) so repeated scraping updates
 //! existing entries rather than creating duplicates.
+//!
+//! `primes_within_digits_of_record` goes past `our_best_digits` to list every
+//! prime of ours close to a form's record, for record-proximity tracking.
 
-use super::Database;
+use super::{Database, PrimeDetail};
 use anyhow::Result;
 
 impl Database {
@@ -77,4 +80,29 @@ impl Database {
         .await?;
         Ok(rows)
     }
+
+    /// Our primes of `form` within `margin` digits of its world record (the
+    /// largest record across the form's categories), largest first. Primes
+    /// at or past the record are included; a form with no record returns
+    /// nothing.
+    pub async fn primes_within_digits_of_record(
+        &self,
+        form: &str,
+        margin: i64,
+    ) -> Result<Vec<PrimeDetail>> {
+        let rows = sqlx::query_as::<_, PrimeDetail>(
+            "SELECT p.id, p.form, p.expression, p.digits, p.found_at, p.search_params,
+                    p.proof_method
+             FROM primes p
+             JOIN (SELECT MAX(digits) AS digits FROM records WHERE form = $1) r
+               ON p.digits >= r.digits - $2
+             WHERE p.form = $1
+             ORDER BY p.digits DESC, p.id",
+        )
+        .bind(form)
+        .bind(margin)
+        .fetch_all(&self.read_pool)
+        .await?;
+        Ok(rows)
+    }
 }
@@ -37,6 +37,31 @@ pub struct WorkerReleaseAdoptionRow {
     pub workers: i64,
 }
 
+/// Seconds without a heartbeat after which a node counts as disconnected;
+/// matches the coordinator's stale-worker timeout.
+pub const RELEASE_HEARTBEAT_TIMEOUT_SECS: i64 = 120;
+
+/// Crash/disconnect rate of the nodes running a channel's current version.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ReleaseHealthRow {
+    pub channel: String,
+    pub version: String,
+    pub rollout_percent: i32,
+    /// Nodes on this channel and version seen within the window.
+    pub workers: i64,
+    /// Of those, nodes silent for longer than the heartbeat timeout.
+    pub disconnected: i64,
+    pub crash_rate: f64,
+}
+
+impl ReleaseHealthRow {
+    /// True when at least `min_workers` nodes reported and the crash rate
+    /// exceeds `max_crash_rate`.
+    pub fn is_unhealthy(&self, max_crash_rate: f64, min_workers: i64) -> bool {
+        self.workers >= min_workers && self.crash_rate > max_crash_rate
+    }
+}
+
 impl Database {
     pub async fn upsert_worker_release(
         &self,
@@ -330,6 +355,44 @@ impl Database {
         .await?;
         Ok(rows)
     }
+
+    /// Health of `channel`'s current version over the last `window_minutes`.
+    ///
+    /// Counts nodes reporting that channel and version with a heartbeat in
+    /// the window, and how many of them have since gone quiet for more than
+    /// [`RELEASE_HEARTBEAT_TIMEOUT_SECS`]. A crash-looping binary shows up as
+    /// nodes that upgraded and then stopped heartbeating. `None` if the
+    /// channel does not exist.
+    pub async fn get_release_health(
+        &self,
+        channel: &str,
+        window_minutes: i64,
+    ) -> Result<Option<ReleaseHealthRow>> {
+        let row = sqlx::query_as::<_, ReleaseHealthRow>(
+            "SELECT channel, version, rollout_percent, workers, disconnected,
+                    COALESCE(disconnected::float8 / NULLIF(workers, 0), 0) AS crash_rate
+             FROM (
+               SELECT c.channel, c.version, c.rollout_percent,
+                      COUNT(n.worker_id)::bigint AS workers,
+                      (COUNT(n.worker_id) FILTER (
+                         WHERE n.last_heartbeat < NOW() - ($3 || ' seconds')::interval
+                       ))::bigint AS disconnected
+               FROM worker_release_channels c
+               LEFT JOIN operator_nodes n
+                 ON n.worker_version = c.version
+                AND n.update_channel = c.channel
+                AND n.last_heartbeat >= NOW() - ($2 || ' minutes')::interval
+               WHERE c.channel = $1
+               GROUP BY c.channel, c.version, c.rollout_percent
+             ) h",
+        )
+        .bind(channel)
+        .bind(window_minutes.to_string())
+        .bind(RELEASE_HEARTBEAT_TIMEOUT_SECS.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
 }
 
 fn rollout_bucket(worker_id: &str) -> u8 {
@@ -341,7 +404,7 @@ fn rollout_bucket(worker_id: &str) -> u8 {
 
 #[cfg(test)]
 mod tests {
-    use super::rollout_bucket;
+    use super::{rollout_bucket, ReleaseHealthRow};
 
     #[test]
     fn rollout_bucket_stable_and_bounded() {
@@ -352,4 +415,24 @@ mod tests {
         assert!(a < 100);
         assert!(c < 100);
     }
+
+    #[test]
+    fn release_health_needs_enough_workers_to_trip() {
+        let health = |workers, disconnected| ReleaseHealthRow {
+            channel: "canary".into(),
+            version: "1.2.3".into(),
+            rollout_percent: 10,
+            workers,
+            disconnected,
+            crash_rate: if workers > 0 {
+                disconnected as f64 / workers as f64
+            } else {
+                0.0
+            },
+        };
+        assert!(health(4, 3).is_unhealthy(0.25, 3));
+        assert!(!health(4, 1).is_unhealthy(0.25, 3));
+        assert!(!health(2, 2).is_unhealthy(0.25, 3));
+        assert!(!health(0, 0).is_unhealthy(0.25, 0));
+    }
 }
@@ -10,13 +10,16 @@
 //! 2. Coordinator checks trust level + required quorum.
 //! 3. If quorum ≥ 2: block is queued for independent verification.
 //! 4. A different node claims the verification block and re-runs the search.
-//! 5. Results are compared: matched → mark verified; conflict → escalate.
+//! 5. Results are compared: matched → mark verified; conflict → the block is
+//!    `disputed` and both operators lose trust.
 //!
 //! ## Reliability Scoring
 //!
 //! Each completed block is recorded in `node_block_results`. The 30-day
 //! rolling reliability score (valid / total) feeds into the effective
-//! trust level calculation.
+//! trust level calculation. Per operator, a time-decayed score (30-day
+//! half-life) is materialized into `operator_trust.reliability_score` by the
+//! coordinator and used to lower quorum for highly reliable nodes.
 
 use super::Database;
 use anyhow::Result;
@@ -36,6 +39,7 @@ pub struct WorkBlockWithCheckpoint {
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct VerificationBlock {
     pub verification_id: i64,
+    pub original_block_id: i64,
     pub search_job_id: i64,
     pub block_start: i64,
     pub block_end: i64,
@@ -48,13 +52,25 @@ pub struct VerificationBlock {
 pub enum VerificationOutcome {
     /// Original and verifier agree on tested/found counts.
     Matched,
-    /// Results disagree — needs investigation or third check.
+    /// Results disagree — the block is now `disputed`.
     Conflict {
         original_found: i64,
         verifier_found: i64,
     },
 }
 
+/// Two results for the same block that disagree, as passed to
+/// [`Database::record_result_disagreement`].
+#[derive(Debug, Clone)]
+pub struct ResultDisagreement {
+    pub block_id: i64,
+    pub original_worker: String,
+    pub original_volunteer_id: Option<uuid::Uuid>,
+    pub original_found: i64,
+    pub verifier_worker: String,
+    pub verifier_found: i64,
+}
+
 /// Rolling 30-day reliability data for a node.
 #[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct NodeReliability {
@@ -96,6 +112,31 @@ impl Database {
         Ok(score)
     }
 
+    /// Time-decayed reliability of an operator across all of its nodes: the
+    /// share of verified blocks that were valid, each weighted by a 30-day
+    /// half-life. Returns 1.0 for operators with fewer than 20 results.
+    pub async fn compute_node_reliability(&self, operator_id: uuid::Uuid) -> Result<f64> {
+        let score: f64 =
+            sqlx::query_scalar("SELECT COALESCE(operator_reliability_decayed($1), 1.0)")
+                .bind(operator_id)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(score)
+    }
+
+    /// Materialize `operator_trust.reliability_score` for every operator.
+    /// Operators with fewer than 20 results get NULL. Returns the rows updated.
+    pub async fn refresh_operator_reliability(&self) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE operator_trust
+             SET reliability_score = operator_reliability_decayed(volunteer_id),
+                 reliability_refreshed_at = NOW()",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     /// Compute effective trust level combining operator trust + node reliability.
     ///
     /// If node reliability is below 0.90, cap the effective trust at level 2.
@@ -189,7 +230,7 @@ impl Database {
         match claimed_id {
             Some(id) => {
                 let block = sqlx::query_as::<_, VerificationBlock>(
-                    "SELECT vq.id AS verification_id, vq.search_job_id,
+                    "SELECT vq.id AS verification_id, vq.original_block_id, vq.search_job_id,
                             vq.block_start, vq.block_end,
                             sj.search_type, sj.params
                      FROM verification_queue vq
@@ -205,7 +246,22 @@ impl Database {
         }
     }
 
+    /// The node a verification block is claimed by, if it is still claimed.
+    pub async fn claimed_verification_worker(&self, verification_id: i64) -> Result<Option<String>> {
+        let worker: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT verification_worker FROM verification_queue
+             WHERE id = $1 AND status = 'claimed'",
+        )
+        .bind(verification_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(worker.flatten())
+    }
+
     /// Submit verification results and compare with original.
+    ///
+    /// Reliability results are recorded against the original work block in
+    /// both outcomes.
     pub async fn submit_verification_result(
         &self,
         verification_id: i64,
@@ -214,13 +270,13 @@ impl Database {
         verifier_worker_id: &str,
     ) -> Result<VerificationOutcome> {
         // Fetch original results
-        let (_original_tested, original_found, original_worker, original_volunteer_id): (
+        let (block_id, original_found, original_worker, original_volunteer_id): (
             i64,
             i64,
             String,
             Option<uuid::Uuid>,
         ) = sqlx::query_as(
-            "SELECT original_tested, original_found, original_worker, original_volunteer_id
+            "SELECT original_block_id, original_found, original_worker, original_volunteer_id
              FROM verification_queue WHERE id = $1",
         )
         .bind(verification_id)
@@ -250,9 +306,9 @@ impl Database {
 
         if matches {
             // Record valid result for both workers
-            self.record_block_result(&original_worker, verification_id, true)
+            self.record_block_result(&original_worker, block_id, true)
                 .await?;
-            self.record_block_result(verifier_worker_id, verification_id, true)
+            self.record_block_result(verifier_worker_id, block_id, true)
                 .await?;
 
             // Advance trust for original operator
@@ -261,24 +317,19 @@ impl Database {
             }
 
             // Mark the original block as verified
-            let block_id: i64 = sqlx::query_scalar(
-                "SELECT original_block_id FROM verification_queue WHERE id = $1",
-            )
-            .bind(verification_id)
-            .fetch_one(&self.pool)
-            .await?;
             self.mark_block_verified(block_id as i32).await?;
 
             Ok(VerificationOutcome::Matched)
         } else {
-            // Record invalid for original, valid for verifier (tentatively)
-            self.record_block_result(&original_worker, verification_id, false)
-                .await?;
-
-            // Penalize trust for original operator
-            if let Some(vol_id) = original_volunteer_id {
-                self.record_invalid_result(vol_id).await?;
-            }
+            self.record_result_disagreement(&ResultDisagreement {
+                block_id,
+                original_worker,
+                original_volunteer_id,
+                original_found,
+                verifier_worker: verifier_worker_id.to_string(),
+                verifier_found: found,
+            })
+            .await?;
 
             Ok(VerificationOutcome::Conflict {
                 original_found,
@@ -287,6 +338,49 @@ impl Database {
         }
     }
 
+    /// Quarantine a block whose quorum results disagree.
+    ///
+    /// The block becomes `disputed`, both results are recorded as invalid for
+    /// node reliability, and both operators' trust is reset. Neither side can
+    /// be believed until someone resolves the block, so neither keeps its
+    /// standing.
+    pub async fn record_result_disagreement(&self, d: &ResultDisagreement) -> Result<()> {
+        sqlx::query(
+            "UPDATE work_blocks SET
+               status = 'disputed',
+               quarantine_reason = format('quorum disagreement: %s found %s, %s found %s',
+                                          $2::TEXT, $3::BIGINT, $4::TEXT, $5::BIGINT)
+             WHERE id = $1",
+        )
+        .bind(d.block_id)
+        .bind(&d.original_worker)
+        .bind(d.original_found)
+        .bind(&d.verifier_worker)
+        .bind(d.verifier_found)
+        .execute(&self.pool)
+        .await?;
+
+        for worker in [&d.original_worker, &d.verifier_worker] {
+            self.record_block_result(worker, d.block_id, false).await?;
+        }
+
+        // The verifier is a node; its operator comes from operator_nodes.
+        let mut operators: Vec<uuid::Uuid> = sqlx::query_scalar(
+            "SELECT volunteer_id FROM operator_nodes
+             WHERE worker_id = $1 AND volunteer_id IS NOT NULL",
+        )
+        .bind(&d.verifier_worker)
+        .fetch_all(&self.pool)
+        .await?;
+        operators.extend(d.original_volunteer_id);
+        operators.sort();
+        operators.dedup();
+        for operator in operators {
+            self.record_invalid_result(operator).await?;
+        }
+        Ok(())
+    }
+
     // ── Block Progress ────────────────────────────────────────────
 
     /// Update live block progress (called from heartbeat).
@@ -308,22 +402,24 @@ impl Database {
     }
 
     /// Batch claim up to `count` work blocks atomically.
+    ///
+    /// Each block is sized like [`Database::claim_work_block`]'s.
     pub async fn claim_work_blocks(
         &self,
         job_id: i64,
         worker_id: &str,
         count: i32,
     ) -> Result<Vec<WorkBlockWithCheckpoint>> {
-        let rows = sqlx::query_as::<_, WorkBlockWithCheckpoint>(
-            "SELECT block_id, block_start, block_end, block_checkpoint
-             FROM claim_work_blocks($1, $2, $3)",
-        )
-        .bind(job_id)
-        .bind(worker_id)
-        .bind(count)
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(rows)
+        let mut tx = self.pool.begin().await?;
+        let mut blocks = Vec::new();
+        for _ in 0..count {
+            match super::jobs::claim_sized_block(&mut tx, job_id, worker_id).await? {
+                Some(block) => blocks.push(block),
+                None => break,
+            }
+        }
+        tx.commit().await?;
+        Ok(blocks)
     }
 
     /// Check if a verification entry already exists for a block.
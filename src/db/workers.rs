@@ -9,6 +9,25 @@ use super::{Database, WorkerRow};
 use anyhow::Result;
 use redis::AsyncCommands;
 use serde_json::Value;
+use sqlx::PgPool;
+
+/// Record a heartbeat's Rayon utilization sample. Shared by
+/// [`Database::set_worker_thread_utilization`] and the PG worker heartbeat,
+/// which holds a bare pool rather than a [`Database`].
+pub async fn set_worker_thread_utilization(
+    pool: &PgPool,
+    worker_id: &str,
+    rayon_threads: i32,
+    threads_busy: i32,
+) -> Result<()> {
+    sqlx::query("UPDATE workers SET rayon_threads = $2, threads_busy = $3 WHERE worker_id = $1")
+        .bind(worker_id)
+        .bind(rayon_threads)
+        .bind(threads_busy)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
 
 impl Database {
     /// Upsert a worker registration. Creates the row on first heartbeat,
@@ -74,6 +93,16 @@ impl Database {
         Ok(command)
     }
 
+    /// Record the Rayon pool size and busy thread count sampled at a heartbeat.
+    pub async fn set_worker_thread_utilization(
+        &self,
+        worker_id: &str,
+        rayon_threads: i32,
+        threads_busy: i32,
+    ) -> Result<()> {
+        set_worker_thread_utilization(&self.pool, worker_id, rayon_threads, threads_busy).await
+    }
+
     /// Remove a worker from the registry (explicit disconnect).
     pub async fn delete_worker(&self, worker_id: &str) -> Result<()> {
         sqlx::query("DELETE FROM workers WHERE worker_id = $1")
@@ -99,6 +128,8 @@ impl Database {
         let rows = sqlx::query_as::<_, WorkerRow>(
             "SELECT worker_id, hostname, cores, search_type, search_params,
                     tested, found, current, checkpoint, metrics,
+                    benchmark_score, rayon_threads, threads_busy,
+                    current_candidate, current_candidate_started_at,
                     registered_at, last_heartbeat
              FROM workers ORDER BY worker_id",
         )
@@ -282,6 +313,12 @@ impl Database {
                     .filter(|s| !s.is_empty())
                     .cloned(),
                 metrics: metrics_val,
+                // Benchmark scores are recorded at PG registration, not in Redis
+                benchmark_score: None,
+                rayon_threads: None,
+                threads_busy: None,
+                current_candidate: None,
+                current_candidate_started_at: None,
                 registered_at: last_hb,
                 last_heartbeat: last_hb,
             });
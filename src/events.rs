@@ -8,9 +8,10 @@
 //! | Variant | Emitted When |
 //! |---------|-------------|
 //! | `PrimeFound` | A new prime is discovered and logged to the database |
+//! | `PrpFound` | A probable prime is deferred to `prp_candidates` (`--only-proven`) |
 //! | `SearchStarted` | A search subprocess begins execution |
 //! | `SearchCompleted` | A search finishes (with summary statistics) |
-//! | `Milestone` | Notable progress (e.g., digit record, sieve phase complete) |
+//! | `Milestone` | A found prime sets a new best for its form or crosses 10k/100k/1M digits |
 //! | `Warning` | Non-fatal issues (e.g., heartbeat timeout, checkpoint failure) |
 //! | `Error` | Fatal errors that terminate a search |
 //!
@@ -19,15 +20,22 @@
 //! Events are stored in a `VecDeque` (bounded to prevent unbounded growth)
 //! and converted to `Notification` structs for WebSocket delivery to the
 //! Next.js frontend. Each notification gets a monotonic `id` for deduplication.
+//!
+//! The event ring holds [`DEFAULT_RECENT_EVENTS_CAP`] records unless sized
+//! with [`EventBus::with_capacity`] or the `EVENT_BUS_CAPACITY` env var. A
+//! poller that falls a full ring behind is told how many events it missed
+//! ([`EventsSince::dropped`]).
 
 use serde::Serialize;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::time::Instant;
 use tracing::{error, info, warn};
 
-/// Events emitted by search modules.
+/// Events emitted by search modules. Search-scoped variants carry the
+/// `search_jobs` id when the search runs for one (see
+/// [`crate::CoordinationClient::search_job_id`]).
 #[derive(Clone, Debug)]
 pub enum Event {
     PrimeFound {
@@ -35,11 +43,23 @@ pub enum Event {
         expression: String,
         digits: u64,
         proof_method: String,
+        search_job_id: Option<i64>,
+        timestamp: Instant,
+    },
+    /// A probabilistic result held back from `primes` under `--only-proven`.
+    /// Recorded in `prp_candidates` for a later proof pass.
+    PrpFound {
+        form: String,
+        expression: String,
+        digits: u64,
+        proof_method: String,
+        search_job_id: Option<i64>,
         timestamp: Instant,
     },
     SearchStarted {
         search_type: String,
         params: String,
+        search_job_id: Option<i64>,
         timestamp: Instant,
     },
     SearchCompleted {
@@ -47,10 +67,17 @@ pub enum Event {
         tested: u64,
         found: u64,
         elapsed_secs: f64,
+        search_job_id: Option<i64>,
         timestamp: Instant,
     },
+    /// A found prime crossed a notable digit threshold. `kind` is
+    /// `"form_record"` (beats the projects' `best_digits` for the form),
+    /// `"10k_digits"`, `"100k_digits"`, or `"megaprime"`.
     Milestone {
-        message: String,
+        form: String,
+        kind: String,
+        digits: u64,
+        expression: String,
         timestamp: Instant,
     },
     Warning {
@@ -65,6 +92,33 @@ pub enum Event {
     },
 }
 
+impl Event {
+    /// Kind name shared by the event log, notifications, and WebSocket
+    /// subscriptions (`{"subscribe": ["prime", "milestone"]}`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::PrimeFound { .. } => "prime",
+            Event::PrpFound { .. } => "prp",
+            Event::SearchStarted { .. } => "search_start",
+            Event::SearchCompleted { .. } => "search_done",
+            Event::Milestone { .. } => "milestone",
+            Event::Warning { .. } => "warning",
+            Event::Error { .. } => "error",
+        }
+    }
+
+    /// The search job this event belongs to, if any.
+    pub fn search_job_id(&self) -> Option<i64> {
+        match self {
+            Event::PrimeFound { search_job_id, .. }
+            | Event::PrpFound { search_job_id, .. }
+            | Event::SearchStarted { search_job_id, .. }
+            | Event::SearchCompleted { search_job_id, .. } => *search_job_id,
+            _ => None,
+        }
+    }
+}
+
 /// A squashed notification ready for delivery to the frontend.
 #[derive(Clone, Debug, Serialize)]
 pub struct Notification {
@@ -80,15 +134,57 @@ pub struct Notification {
 /// buffering, squashing, and broadcasting notifications via WebSocket.
 pub struct EventBus {
     recent: Mutex<VecDeque<EventRecord>>,
+    recent_cap: usize,
     pending_primes: Mutex<Vec<PendingPrime>>,
     last_flush: Mutex<Instant>,
     notifications: Mutex<VecDeque<Notification>>,
     next_id: AtomicU64,
     next_event_id: AtomicU64,
     ws_sender: Mutex<Option<tokio::sync::broadcast::Sender<String>>>,
+    best_digits: Mutex<HashMap<String, FormBest>>,
     start: Instant,
 }
 
+/// Per-form high-water mark used to decide when a prime is a milestone.
+#[derive(Clone, Copy, Debug)]
+struct FormBest {
+    digits: u64,
+    /// False when no project tracks the form; only round thresholds count.
+    record_tracked: bool,
+}
+
+/// Round digit thresholds, largest first, with their milestone kinds.
+const DIGIT_THRESHOLDS: [(u64, &str); 3] = [
+    (1_000_000, "megaprime"),
+    (100_000, "100k_digits"),
+    (10_000, "10k_digits"),
+];
+
+/// Classify a prime of `digits` against the previous best for its form.
+///
+/// A crossed round threshold outranks a plain record, so each prime yields at
+/// most one milestone.
+fn milestone_kind(best: FormBest, digits: u64) -> Option<&'static str> {
+    if digits <= best.digits {
+        return None;
+    }
+    for (threshold, kind) in DIGIT_THRESHOLDS {
+        if best.digits < threshold && digits >= threshold {
+            return Some(kind);
+        }
+    }
+    best.record_tracked.then_some("form_record")
+}
+
+fn milestone_title(form: &str, kind: &str, digits: u64) -> String {
+    match kind {
+        "megaprime" => format!("First {} megaprime ({} digits)", form, digits),
+        "100k_digits" => format!("First 100k-digit {} prime ({} digits)", form, digits),
+        "10k_digits" => format!("First 10k-digit {} prime ({} digits)", form, digits),
+        _ => format!("New {} record: {} digits", form, digits),
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct EventRecord {
     pub id: u64,
@@ -96,6 +192,16 @@ pub struct EventRecord {
     pub message: String,
     pub elapsed_secs: f64,
     pub timestamp_ms: u64,
+    pub search_job_id: Option<i64>,
+}
+
+/// Result of [`EventBus::recent_events_since`].
+#[derive(Clone, Debug, Default)]
+pub struct EventsSince {
+    pub events: Vec<EventRecord>,
+    /// Events after `last_id` that were evicted from the ring before this
+    /// call: nonzero when the poller was lapped.
+    pub dropped: u64,
 }
 
 #[derive(Clone, Debug)]
@@ -121,7 +227,9 @@ fn now_ms() -> u64 {
         .as_millis() as u64
 }
 
-const RECENT_EVENTS_CAP: usize = 200;
+/// Event ring size when neither [`EventBus::with_capacity`] nor
+/// `EVENT_BUS_CAPACITY` sets one.
+pub const DEFAULT_RECENT_EVENTS_CAP: usize = 2000;
 const NOTIFICATIONS_CAP: usize = 50;
 const FLUSH_INTERVAL_SECS: u64 = 10;
 
@@ -132,15 +240,29 @@ impl Default for EventBus {
 }
 
 impl EventBus {
+    /// Bus whose event ring is sized by `EVENT_BUS_CAPACITY`, or
+    /// [`DEFAULT_RECENT_EVENTS_CAP`] when that is unset or unparsable.
     pub fn new() -> Self {
+        let capacity = std::env::var("EVENT_BUS_CAPACITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_RECENT_EVENTS_CAP);
+        Self::with_capacity(capacity)
+    }
+
+    /// Bus keeping the last `capacity` events (at least one).
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
         EventBus {
-            recent: Mutex::new(VecDeque::with_capacity(RECENT_EVENTS_CAP)),
+            recent: Mutex::new(VecDeque::with_capacity(capacity)),
+            recent_cap: capacity,
             pending_primes: Mutex::new(Vec::new()),
             last_flush: Mutex::new(Instant::now()),
             notifications: Mutex::new(VecDeque::with_capacity(NOTIFICATIONS_CAP)),
             next_id: AtomicU64::new(1),
             next_event_id: AtomicU64::new(1),
             ws_sender: Mutex::new(None),
+            best_digits: Mutex::new(HashMap::new()),
             start: Instant::now(),
         }
     }
@@ -176,11 +298,7 @@ impl EventBus {
                 // Truncate long expressions to prevent log bloat. Large primes
                 // (e.g., 100K+ digit palindromics) can produce multi-KB expressions
                 // that flood structured log aggregators (Loki, CloudWatch).
-                let log_expr = if expression.len() > 1000 {
-                    format!("{}...(truncated)", &expression[..1000])
-                } else {
-                    expression.clone()
-                };
+                let log_expr = crate::truncate_display(expression, 1000);
                 info!(
                     form = %form,
                     expression = %log_expr,
@@ -190,7 +308,7 @@ impl EventBus {
                     "prime found"
                 );
                 self.push_record(
-                    "prime",
+                    &event,
                     &format!(
                         "{} {} ({} digits, {})",
                         form, expression, digits, proof_method
@@ -207,20 +325,40 @@ impl EventBus {
                     proof_method: proof_method.clone(),
                 });
             }
+            Event::PrpFound {
+                form,
+                expression,
+                digits,
+                proof_method,
+                ..
+            } => {
+                info!(
+                    form = %form,
+                    expression = %expression,
+                    digits,
+                    proof_method = %proof_method,
+                    elapsed = %tag,
+                    "probable prime deferred for proof"
+                );
+                self.push_record(
+                    &event,
+                    &format!(
+                        "{} {} ({} digits, {})",
+                        form, expression, digits, proof_method
+                    ),
+                    elapsed,
+                );
+            }
             Event::SearchStarted {
                 search_type,
                 params,
                 ..
             } => {
                 info!(search_type = %search_type, params = %params, elapsed = %tag, "search started");
-                self.push_record(
-                    "search_start",
-                    &format!("{} {}", search_type, params),
-                    elapsed,
-                );
+                self.push_record(&event, &format!("{} {}", search_type, params), elapsed);
                 self.broadcast_notification(Notification {
                     id: self.next_id.fetch_add(1, Ordering::Relaxed),
-                    kind: "search_start".into(),
+                    kind: event.kind().into(),
                     title: format!("Search started: {}", search_type),
                     details: vec![params.clone()],
                     count: 1,
@@ -243,13 +381,13 @@ impl EventBus {
                     "search completed"
                 );
                 self.push_record(
-                    "search_done",
+                    &event,
                     &format!("{} tested={} found={}", search_type, tested, found),
                     elapsed,
                 );
                 self.broadcast_notification(Notification {
                     id: self.next_id.fetch_add(1, Ordering::Relaxed),
-                    kind: "search_done".into(),
+                    kind: event.kind().into(),
                     title: format!("Search complete: {}", search_type),
                     details: vec![format!(
                         "Tested {} candidates, found {} primes in {:.1}s",
@@ -259,14 +397,28 @@ impl EventBus {
                     timestamp_ms: now_ms(),
                 });
             }
-            Event::Milestone { message, .. } => {
-                info!(elapsed = %tag, "{}", message);
-                self.push_record("milestone", message, elapsed);
+            Event::Milestone {
+                form,
+                kind,
+                digits,
+                expression,
+                ..
+            } => {
+                let title = milestone_title(form, kind, *digits);
+                info!(
+                    form = %form,
+                    kind = %kind,
+                    digits,
+                    elapsed = %tag,
+                    "{}",
+                    title
+                );
+                self.push_record(&event, &title, elapsed);
                 self.broadcast_notification(Notification {
                     id: self.next_id.fetch_add(1, Ordering::Relaxed),
-                    kind: "milestone".into(),
-                    title: message.clone(),
-                    details: vec![],
+                    kind: event.kind().into(),
+                    title,
+                    details: vec![expression.clone()],
                     count: 1,
                     timestamp_ms: now_ms(),
                 });
@@ -275,16 +427,16 @@ impl EventBus {
                 context, message, ..
             } => {
                 warn!(context = %context, elapsed = %tag, "{}", message);
-                self.push_record("warning", &format!("[{}] {}", context, message), elapsed);
+                self.push_record(&event, &format!("[{}] {}", context, message), elapsed);
             }
             Event::Error {
                 context, message, ..
             } => {
                 error!(context = %context, elapsed = %tag, "{}", message);
-                self.push_record("error", &format!("[{}] {}", context, message), elapsed);
+                self.push_record(&event, &format!("[{}] {}", context, message), elapsed);
                 self.broadcast_notification(Notification {
                     id: self.next_id.fetch_add(1, Ordering::Relaxed),
-                    kind: "error".into(),
+                    kind: event.kind().into(),
                     title: format!("Error: {}", context),
                     details: vec![message.clone()],
                     count: 1,
@@ -303,6 +455,53 @@ impl EventBus {
         }
     }
 
+    /// Emit a `Milestone` if a found prime beats the best for its form or
+    /// crosses a round digit threshold. Returns true if one was emitted.
+    ///
+    /// The per-form best is seeded once from `stored_best` (the projects'
+    /// `best_digits`, `None` if no project tracks the form) and then kept in
+    /// memory, so each record is announced once rather than on every prime
+    /// until the orchestrator syncs `best_digits`.
+    pub fn check_milestone(
+        &self,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        stored_best: impl FnOnce() -> Option<u64>,
+    ) -> bool {
+        let known = self.best_digits.lock().unwrap().get(form).copied();
+        // Seed outside the lock: the lookup may block on the database
+        let seeded = known.unwrap_or_else(|| {
+            let stored = stored_best();
+            FormBest {
+                digits: stored.unwrap_or(0),
+                record_tracked: stored.is_some(),
+            }
+        });
+
+        let kind = {
+            let mut best = self.best_digits.lock().unwrap();
+            let entry = best.entry(form.to_string()).or_insert(seeded);
+            let kind = milestone_kind(*entry, digits);
+            entry.digits = entry.digits.max(digits);
+            kind
+        };
+
+        match kind {
+            Some(kind) => {
+                self.emit(Event::Milestone {
+                    form: form.to_string(),
+                    kind: kind.to_string(),
+                    digits,
+                    expression: expression.to_string(),
+                    timestamp: Instant::now(),
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Flush pending primes: squash by form and broadcast as notifications.
     pub fn flush(&self) {
         let primes: Vec<PendingPrime> = {
@@ -363,32 +562,44 @@ impl EventBus {
         events.iter().rev().take(limit).cloned().collect()
     }
 
-    /// Get events with id greater than `last_id`, in ascending order.
-    pub fn recent_events_since(&self, last_id: u64, limit: usize) -> Vec<EventRecord> {
+    /// Number of events the ring retains.
+    pub fn capacity(&self) -> usize {
+        self.recent_cap
+    }
+
+    /// Get events with id greater than `last_id`, in ascending order: the
+    /// newest `limit` of them, and how many were already evicted.
+    pub fn recent_events_since(&self, last_id: u64, limit: usize) -> EventsSince {
         let events = self.recent.lock().unwrap();
+        let dropped = events.front().map_or(0, |oldest| {
+            oldest.id.saturating_sub(last_id.saturating_add(1))
+        });
         let mut filtered: Vec<EventRecord> =
             events.iter().filter(|e| e.id > last_id).cloned().collect();
         filtered.sort_by_key(|e| e.id);
         if filtered.len() > limit {
-            filtered.split_off(filtered.len() - limit)
-        } else {
-            filtered
+            filtered = filtered.split_off(filtered.len() - limit);
+        }
+        EventsSince {
+            events: filtered,
+            dropped,
         }
     }
 
-    fn push_record(&self, kind: &str, message: &str, elapsed: f64) {
+    fn push_record(&self, event: &Event, message: &str, elapsed: f64) {
         let mut recent = self.recent.lock().unwrap();
-        if recent.len() >= RECENT_EVENTS_CAP {
+        if recent.len() >= self.recent_cap {
             recent.pop_front();
         }
         let id = self.next_event_id.fetch_add(1, Ordering::Relaxed);
         let timestamp_ms = now_ms();
         recent.push_back(EventRecord {
             id,
-            kind: kind.into(),
+            kind: event.kind().into(),
             message: message.into(),
             elapsed_secs: elapsed,
             timestamp_ms,
+            search_job_id: event.search_job_id(),
         });
     }
 
@@ -472,6 +683,17 @@ mod tests {
             expression: expr.into(),
             digits: 10,
             proof_method: "deterministic".into(),
+            search_job_id: None,
+            timestamp: Instant::now(),
+        }
+    }
+
+    fn milestone_event(expression: impl Into<String>) -> Event {
+        Event::Milestone {
+            form: "factorial".into(),
+            kind: "form_record".into(),
+            digits: 1234,
+            expression: expression.into(),
             timestamp: Instant::now(),
         }
     }
@@ -514,6 +736,26 @@ mod tests {
         assert!(bus.recent_notifications(100).is_empty());
     }
 
+    /// PrpFound events are recorded with kind "prp" but never reach the prime
+    /// notification batch — they are not discoveries until proven.
+    #[test]
+    fn emit_prp_found_recorded_without_notification() {
+        let bus = make_bus();
+        bus.emit(Event::PrpFound {
+            form: "factorial".into(),
+            expression: "3!+1".into(),
+            digits: 1,
+            proof_method: "probabilistic".into(),
+            search_job_id: None,
+            timestamp: Instant::now(),
+        });
+        bus.flush();
+        let events = bus.recent_events(100);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, "prp");
+        assert!(bus.recent_notifications(100).is_empty());
+    }
+
     /// SearchStarted events create an immediate notification (not batched).
     /// The notification kind is "search_start" and the title includes the
     /// search type. Params are included in the details array.
@@ -523,11 +765,13 @@ mod tests {
         bus.emit(Event::SearchStarted {
             search_type: "kbn".into(),
             params: "k=3 b=2".into(),
+            search_job_id: Some(7),
             timestamp: Instant::now(),
         });
         let events = bus.recent_events(100);
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].kind, "search_start");
+        assert_eq!(events[0].search_job_id, Some(7));
 
         let notifs = bus.recent_notifications(100);
         assert_eq!(notifs.len(), 1);
@@ -546,6 +790,7 @@ mod tests {
             tested: 1000,
             found: 5,
             elapsed_secs: 3.14,
+            search_job_id: None,
             timestamp: Instant::now(),
         });
         let notifs = bus.recent_notifications(100);
@@ -555,19 +800,17 @@ mod tests {
         assert!(notifs[0].details[0].contains("5"));
     }
 
-    /// Milestone events create immediate notifications. Used for notable
-    /// progress like "Reached 10000 digits" or "Sieve phase complete".
+    /// Milestone events create immediate notifications. The title is built
+    /// from form, kind, and digits; the expression goes in the details.
     #[test]
     fn emit_milestone_creates_notification() {
         let bus = make_bus();
-        bus.emit(Event::Milestone {
-            message: "Reached 10000 digits".into(),
-            timestamp: Instant::now(),
-        });
+        bus.emit(milestone_event("3610!-1"));
         let notifs = bus.recent_notifications(100);
         assert_eq!(notifs.len(), 1);
         assert_eq!(notifs[0].kind, "milestone");
-        assert_eq!(notifs[0].title, "Reached 10000 digits");
+        assert_eq!(notifs[0].title, "New factorial record: 1234 digits");
+        assert_eq!(notifs[0].details, vec!["3610!-1".to_string()]);
     }
 
     /// Warning events are logged to the event buffer but do NOT create
@@ -605,6 +848,90 @@ mod tests {
         assert!(notifs[0].title.contains("db"));
     }
 
+    // ── Digit Milestones ───────────────────────────────────────────
+
+    /// A prime larger than the stored best for its form emits exactly one
+    /// `form_record` milestone.
+    #[test]
+    fn check_milestone_record_emits_once() {
+        let bus = make_bus();
+        assert!(bus.check_milestone("kbn", "3*2^19000+1", 6000, || Some(5000)));
+        let notifs = bus.recent_notifications(100);
+        assert_eq!(notifs.len(), 1);
+        assert_eq!(notifs[0].kind, "milestone");
+        assert_eq!(notifs[0].title, "New kbn record: 6000 digits");
+        let milestones = bus
+            .recent_events(100)
+            .into_iter()
+            .filter(|e| e.kind == "milestone")
+            .count();
+        assert_eq!(milestones, 1);
+    }
+
+    /// Primes at or below the best, including a repeat of the record itself,
+    /// emit nothing.
+    #[test]
+    fn check_milestone_ignores_smaller_primes() {
+        let bus = make_bus();
+        assert!(!bus.check_milestone("kbn", "a", 4000, || Some(5000)));
+        assert!(bus.check_milestone("kbn", "b", 6000, || Some(5000)));
+        assert!(!bus.check_milestone("kbn", "c", 6000, || Some(5000)));
+        assert!(!bus.check_milestone("kbn", "d", 5500, || Some(5000)));
+        assert_eq!(bus.recent_notifications(100).len(), 1);
+    }
+
+    /// The stored best is only consulted the first time a form is seen.
+    #[test]
+    fn check_milestone_seeds_stored_best_once() {
+        let bus = make_bus();
+        let calls = std::cell::Cell::new(0);
+        let lookup = || {
+            calls.set(calls.get() + 1);
+            Some(100)
+        };
+        bus.check_milestone("twin", "a", 50, lookup);
+        bus.check_milestone("twin", "b", 200, || unreachable!());
+        assert_eq!(calls.get(), 1);
+    }
+
+    /// Crossing a round threshold outranks a plain record, and the
+    /// threshold is reported even for forms no project tracks.
+    #[test]
+    fn check_milestone_thresholds() {
+        let bus = make_bus();
+        bus.check_milestone("kbn", "a", 12_000, || Some(9_000));
+        bus.check_milestone("wagstaff", "b", 150_000, || None);
+        bus.check_milestone("wagstaff", "c", 160_000, || None);
+        let notifs = bus.recent_notifications(100);
+        assert_eq!(notifs.len(), 2);
+        assert!(notifs[0]
+            .title
+            .starts_with("First 100k-digit wagstaff prime"));
+        assert!(notifs[1].title.starts_with("First 10k-digit kbn prime"));
+    }
+
+    /// milestone_kind picks the largest threshold crossed, else a record if
+    /// the form is tracked.
+    #[test]
+    fn milestone_kind_classification() {
+        let tracked = |digits| FormBest {
+            digits,
+            record_tracked: true,
+        };
+        assert_eq!(milestone_kind(tracked(5), 1_200_000), Some("megaprime"));
+        assert_eq!(
+            milestone_kind(tracked(99_999), 100_000),
+            Some("100k_digits")
+        );
+        assert_eq!(milestone_kind(tracked(10_000), 10_001), Some("form_record"));
+        assert_eq!(milestone_kind(tracked(10_000), 10_000), None);
+        let untracked = FormBest {
+            digits: 10_000,
+            record_tracked: false,
+        };
+        assert_eq!(milestone_kind(untracked, 20_000), None);
+    }
+
     // ── Prime Batching and Flush ────────────────────────────────────
 
     /// Flush squashes pending primes by form: 2 factorial + 1 kbn primes
@@ -676,12 +1003,12 @@ mod tests {
 
     // ── Bounded Buffer Caps ────────────────────────────────────────
 
-    /// The event buffer is capped at RECENT_EVENTS_CAP=200 entries. Older
-    /// events are evicted (pop_front) to prevent unbounded memory growth
-    /// on long-running coordinators.
+    /// The event buffer is capped at its capacity (200 here). Older events
+    /// are evicted (pop_front) to prevent unbounded memory growth on
+    /// long-running coordinators.
     #[test]
     fn recent_events_capped_at_200() {
-        let bus = make_bus();
+        let bus = EventBus::with_capacity(200);
         // Emit 250 warnings (warnings add to events but not notifications)
         for i in 0..250 {
             bus.emit(Event::Warning {
@@ -691,7 +1018,23 @@ mod tests {
             });
         }
         let events = bus.recent_events(300);
-        assert_eq!(events.len(), RECENT_EVENTS_CAP); // capped at 200
+        assert_eq!(events.len(), 200);
+    }
+
+    /// The default ring holds DEFAULT_RECENT_EVENTS_CAP events, and a bus
+    /// sized up with with_capacity retains more of a burst.
+    #[test]
+    fn with_capacity_retains_more_events() {
+        assert_eq!(make_bus().capacity(), DEFAULT_RECENT_EVENTS_CAP);
+        let small = EventBus::with_capacity(10);
+        let large = EventBus::with_capacity(100);
+        for bus in [&small, &large] {
+            for i in 0..100 {
+                bus.emit(milestone_event(format!("m{}", i)));
+            }
+        }
+        assert_eq!(small.recent_events_since(0, usize::MAX).events.len(), 10);
+        assert_eq!(large.recent_events_since(0, usize::MAX).events.len(), 100);
     }
 
     /// The notification buffer is capped at NOTIFICATIONS_CAP=50 entries.
@@ -702,10 +1045,7 @@ mod tests {
         let bus = make_bus();
         // Emit 60 milestones (each creates a notification)
         for i in 0..60 {
-            bus.emit(Event::Milestone {
-                message: format!("milestone {}", i),
-                timestamp: Instant::now(),
-            });
+            bus.emit(milestone_event(format!("milestone {}", i)));
         }
         let notifs = bus.recent_notifications(100);
         assert_eq!(notifs.len(), NOTIFICATIONS_CAP); // capped at 50
@@ -741,14 +1081,8 @@ mod tests {
     #[test]
     fn notification_ids_are_unique_and_increasing() {
         let bus = make_bus();
-        bus.emit(Event::Milestone {
-            message: "a".into(),
-            timestamp: Instant::now(),
-        });
-        bus.emit(Event::Milestone {
-            message: "b".into(),
-            timestamp: Instant::now(),
-        });
+        bus.emit(milestone_event("a"));
+        bus.emit(milestone_event("b"));
         let notifs = bus.recent_notifications(10);
         // Most recent first, so notifs[0].id > notifs[1].id
         assert!(notifs[0].id > notifs[1].id);
@@ -767,10 +1101,7 @@ mod tests {
         bus.set_ws_sender(tx);
         let mut receiver = bus.subscribe_ws();
 
-        bus.emit(Event::Milestone {
-            message: "ws-test".into(),
-            timestamp: Instant::now(),
-        });
+        bus.emit(milestone_event("ws-test"));
 
         let msg = receiver.try_recv();
         assert!(msg.is_ok(), "Should receive a broadcast message");
@@ -792,10 +1123,7 @@ mod tests {
         let mut rx2 = bus.subscribe_ws();
         let mut rx3 = bus.subscribe_ws();
 
-        bus.emit(Event::Milestone {
-            message: "multi-sub".into(),
-            timestamp: Instant::now(),
-        });
+        bus.emit(milestone_event("multi-sub"));
 
         assert!(rx1.try_recv().is_ok());
         assert!(rx2.try_recv().is_ok());
@@ -809,10 +1137,7 @@ mod tests {
     fn ws_no_sender_does_not_panic() {
         // Without set_ws_sender, emitting events should not panic
         let bus = make_bus();
-        bus.emit(Event::Milestone {
-            message: "no-ws".into(),
-            timestamp: Instant::now(),
-        });
+        bus.emit(milestone_event("no-ws"));
         // Just verifying no panic occurs
         let events = bus.recent_events(10);
         assert_eq!(events.len(), 1);
@@ -865,6 +1190,7 @@ mod tests {
         bus.emit(Event::SearchStarted {
             search_type: "kbn".into(),
             params: "k=1 b=2".into(),
+            search_job_id: None,
             timestamp: Instant::now(),
         });
         bus.emit(Event::Warning {
@@ -935,7 +1261,7 @@ mod tests {
         // all is most-recent-first: [third, second, first]
         let first_id = all.last().unwrap().id;
 
-        let since = bus.recent_events_since(first_id, 100);
+        let since = bus.recent_events_since(first_id, 100).events;
         assert_eq!(since.len(), 2); // second, third
         // Results should be in ascending order
         assert!(since[0].id < since[1].id);
@@ -949,16 +1275,10 @@ mod tests {
     #[test]
     fn recent_events_since_zero_returns_all() {
         let bus = make_bus();
-        bus.emit(Event::Milestone {
-            message: "one".into(),
-            timestamp: Instant::now(),
-        });
-        bus.emit(Event::Milestone {
-            message: "two".into(),
-            timestamp: Instant::now(),
-        });
+        bus.emit(milestone_event("one"));
+        bus.emit(milestone_event("two"));
 
-        let since = bus.recent_events_since(0, 100);
+        let since = bus.recent_events_since(0, 100).events;
         assert_eq!(since.len(), 2);
     }
 
@@ -976,7 +1296,7 @@ mod tests {
             });
         }
 
-        let since = bus.recent_events_since(0, 3);
+        let since = bus.recent_events_since(0, 3).events;
         assert_eq!(since.len(), 3);
         // Should return the 3 most recent in ascending order
         assert!(since[0].id < since[1].id);
@@ -995,7 +1315,30 @@ mod tests {
             timestamp: Instant::now(),
         });
         let since = bus.recent_events_since(999999, 100);
-        assert!(since.is_empty());
+        assert!(since.events.is_empty());
+        assert_eq!(since.dropped, 0);
+    }
+
+    /// A poller that falls more than a full ring behind is told how many
+    /// events it missed; one that keeps up is not.
+    #[test]
+    fn recent_events_since_reports_lap() {
+        let bus = EventBus::with_capacity(5);
+        for i in 0..3 {
+            bus.emit(milestone_event(format!("m{}", i)));
+        }
+        let caught_up = bus.recent_events_since(0, 100);
+        assert_eq!(caught_up.dropped, 0);
+        let last_id = caught_up.events.last().unwrap().id;
+
+        for i in 3..11 {
+            bus.emit(milestone_event(format!("m{}", i)));
+        }
+        let since = bus.recent_events_since(last_id, 100);
+        assert_eq!(since.events.len(), 5);
+        assert_eq!(since.dropped, 3);
+        assert_eq!(since.events[0].id, last_id + 4);
+        assert_eq!(bus.recent_events_since(since.events[4].id, 100).dropped, 0);
     }
 
     // ── Event Record IDs ──────────────────────────────────────────
@@ -1031,7 +1374,7 @@ mod tests {
         use std::sync::Arc;
         use std::thread;
 
-        let bus = Arc::new(make_bus());
+        let bus = Arc::new(EventBus::with_capacity(200));
         let handles: Vec<_> = (0..8)
             .map(|i| {
                 let bus = Arc::clone(&bus);
@@ -1053,7 +1396,7 @@ mod tests {
 
         let events = bus.recent_events(300);
         // Capped at 200, but should have at least that many
-        assert_eq!(events.len(), RECENT_EVENTS_CAP);
+        assert_eq!(events.len(), 200);
     }
 
     // ── Default Trait ─────────────────────────────────────────────
@@ -1095,6 +1438,7 @@ mod tests {
             tested: 50000,
             found: 12,
             elapsed_secs: 123.456,
+            search_job_id: None,
             timestamp: Instant::now(),
         });
 
@@ -1134,6 +1478,7 @@ mod tests {
             expression: long_expr.clone(),
             digits: 2000,
             proof_method: "probabilistic".into(),
+            search_job_id: None,
             timestamp: Instant::now(),
         });
         let events = bus.recent_events(10);
@@ -51,10 +51,11 @@ use tracing::info;
 #[cfg(feature = "flint")]
 use tracing::debug;
 
+use crate::candidate::{CandidateForm, Factorial};
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::proof;
 use crate::CoordinationClient;
@@ -130,7 +131,7 @@ pub fn search(
     start: u64,
     end: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -139,6 +140,9 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params = serde_json::json!({"start": start, "end": end});
+    crate::search_params::validate("factorial", &params)?;
+
     // Resolve sieve_limit: auto-tune if 0
     // Stirling's approximation: log2(n!) ≈ n*log2(n/e) + 0.5*log2(2*pi*n)
     let candidate_bits = if end > 2 {
@@ -199,6 +203,7 @@ pub fn search(
     info!(sieve_min_n, "sieve active threshold");
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut sieved_out: u64 = 0;
     let mut wilson_eliminated: u64 = 0;
 
@@ -231,19 +236,22 @@ pub fn search(
             sieved_out += 1;
             continue;
         }
+        progress.record_survivor(n);
 
         // Only construct the huge n!±1 Integers for candidates that survived the sieve.
         // Try PFGW first for large candidates (50-100x faster), fall back to GMP MR.
         let (r_plus, r_minus) = rayon::join(
             || {
+                let _busy = crate::progress::busy();
                 if !test_plus {
                     return (IsPrime::No, None);
                 }
                 let plus = factorial.clone() + 1u32;
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&plus) {
+                if crate::p1::adaptive_p1_filter_for_form(&plus, "factorial") {
                     return (IsPrime::No, None);
                 }
+                let _testing = progress.testing(&Factorial { plus: true }.expression(n), &plus);
                 // Try PFGW acceleration for large candidates
                 if let Some(pfgw_result) =
                     pfgw::try_test(&format!("{}!+1", n), &plus, pfgw::PfgwMode::NMinus1Proof)
@@ -261,20 +269,22 @@ pub fn search(
                             return (IsPrime::Probably, Some(cert));
                         }
                         pfgw::PfgwResult::Composite => return (IsPrime::No, None),
-                        pfgw::PfgwResult::Unavailable { .. } => {} // fall through to GMP
+                        pfgw::PfgwResult::Unavailable { .. } | pfgw::PfgwResult::Failed { .. } => {} // fall through to GMP
                     }
                 }
                 (mr_screened_test(&plus, mr_rounds), None)
             },
             || {
+                let _busy = crate::progress::busy();
                 if !test_minus {
                     return (IsPrime::No, None);
                 }
                 let minus = factorial.clone() - 1u32;
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&minus) {
+                if crate::p1::adaptive_p1_filter_for_form(&minus, "factorial") {
                     return (IsPrime::No, None);
                 }
+                let _testing = progress.testing(&Factorial { plus: false }.expression(n), &minus);
                 // Try PFGW acceleration for large candidates
                 if let Some(pfgw_result) =
                     pfgw::try_test(&format!("{}!-1", n), &minus, pfgw::PfgwMode::NPlus1Proof)
@@ -292,7 +302,7 @@ pub fn search(
                             return (IsPrime::Probably, Some(cert));
                         }
                         pfgw::PfgwResult::Composite => return (IsPrime::No, None),
-                        pfgw::PfgwResult::Unavailable { .. } => {} // fall through to GMP
+                        pfgw::PfgwResult::Unavailable { .. } | pfgw::PfgwResult::Failed { .. } => {} // fall through to GMP
                     }
                 }
                 (mr_screened_test(&minus, mr_rounds), None)
@@ -308,19 +318,18 @@ pub fn search(
                 let certainty: &str = if let Some(ref cert) = pfgw_cert {
                     cert.as_str()
                 } else {
-                    let mut cert = match result {
-                        IsPrime::Yes => "deterministic",
-                        IsPrime::Probably => "probabilistic",
-                        IsPrime::No => unreachable!(),
+                    let candidate = if sign == "+" {
+                        Integer::from(&factorial + 1u32)
+                    } else {
+                        Integer::from(&factorial - 1u32)
                     };
+                    let mut cert = crate::certainty_label(&candidate, result);
 
                     // Attempt deterministic proof for probable primes (GMP path)
                     if result == IsPrime::Probably {
                         let proven = if sign == "+" {
-                            let candidate = Integer::from(&factorial + 1u32);
                             proof::pocklington_factorial_proof(n, &candidate, &sieve_primes)
                         } else {
-                            let candidate = Integer::from(&factorial - 1u32);
                             proof::morrison_factorial_proof(n, &candidate, &sieve_primes)
                         };
                         if proven {
@@ -335,7 +344,28 @@ pub fn search(
                     &certainty_owned
                 };
 
-                let expr = format!("{}! {} 1", n, sign);
+                let expr = Factorial { plus: sign == "+" }.expression(n);
+                let (proven, cert_json) = crate::prove_on_find(
+                    "factorial",
+                    &expr,
+                    digit_count,
+                    certainty.to_string(),
+                    None,
+                );
+                let certainty = proven.as_str();
+                if crate::defer_unproven(
+                    db,
+                    rt,
+                    event_bus,
+                    worker_client.and_then(|wc| wc.search_job_id()),
+                    "factorial",
+                    &expr,
+                    digit_count,
+                    search_params,
+                    certainty,
+                )? {
+                    continue;
+                }
                 progress.found.fetch_add(1, Ordering::Relaxed);
                 if let Some(eb) = event_bus {
                     eb.emit(events::Event::PrimeFound {
@@ -343,8 +373,12 @@ pub fn search(
                         expression: expr.clone(),
                         digits: digit_count,
                         proof_method: certainty.to_string(),
+                        search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                         timestamp: Instant::now(),
                     });
+                    eb.check_milestone("factorial", &expr, digit_count, || {
+                        db.get_best_digits_sync(rt, "factorial").ok().flatten()
+                    });
                 } else {
                     info!(
                         expression = %expr,
@@ -353,22 +387,21 @@ pub fn search(
                         "prime found"
                     );
                 }
-                db.insert_prime_sync(
-                    rt,
+                buffer.push(
                     "factorial",
                     &expr,
                     digit_count,
                     search_params,
                     certainty,
-                    None,
+                    cert_json.as_deref(),
                 )?;
-                if let Some(wc) = worker_client {
-                    wc.report_prime("factorial", &expr, digit_count, search_params, certainty);
-                }
             }
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Factorial {
@@ -382,6 +415,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Factorial {
@@ -395,6 +429,7 @@ pub fn search(
         }
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!(sieved_out, "factorial sieve elimination complete");
     if wilson_eliminated > 0 {
@@ -892,4 +927,40 @@ mod tests {
         );
         assert_eq!(Integer::from(Integer::factorial(2u32)) - 1u32, 1);
     }
+
+    // ── Proof Labels ────────────────────────────────────────────────────
+
+    /// A full `search` over n=11 records 11!+1 = 39916801 (26 bits) with the
+    /// sub-2^64 BPSW label rather than "probabilistic" or a Pocklington proof.
+    #[test]
+    fn small_factorial_prime_recorded_deterministic() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("primes.jsonl");
+        let sink = crate::prime_sink::FilePrimeSink::open(&out).unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let progress = Progress::new();
+        search(
+            11,
+            11,
+            &progress,
+            &sink,
+            rt.handle(),
+            &dir.path().join("factorial.checkpoint"),
+            "{}",
+            25,
+            0,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let contents = std::fs::read_to_string(&out).unwrap();
+        let records: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["expression"], "11! + 1");
+        assert_eq!(records[0]["proof_method"], crate::SMALL_PRIME_PROOF);
+    }
 }
@@ -0,0 +1,345 @@
+//! # FFT — Pure-Rust NTT Modular Squaring
+//!
+//! Portable repeated squaring mod N, the LLR/Proth inner operation, for hosts
+//! where GWNUM ([`crate::gwnum`], x86-64 only) is unavailable, as on Apple
+//! Silicon and Graviton. Enabled by the `native-fft` feature.
+//!
+//! Operands are split into 16-bit digits and multiplied by a number-theoretic
+//! transform over the Goldilocks prime p = 2^64 − 2^32 + 1, which has roots
+//! of unity of every order up to 2^32. A digit product sums at most L terms
+//! below 2^32, so the convolution is exact for transforms up to 2^30 digits
+//! (about 16 Gbit operands).
+//!
+//! Reduction is Barrett's: [`NttModulus`] transforms N and μ = ⌊4^k / N⌋ once,
+//! so each [`NttModulus::square_mod`] costs three transform multiplies (x²,
+//! the quotient estimate, and q·N) plus linear-time shifts and subtractions.
+//!
+//! Nothing routes LLR/Proth through this yet: `benches/fft_bench.rs` measures
+//! one x² − 2 mod 3·2^n − 1 step against GMP, and on x86-64 GMP is faster at
+//! every size it covers (n = 10^4 to 10^6).
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use darkreach::fft::NttModulus;
+//! use rug::Integer;
+//!
+//! let n = (Integer::from(3) << 50_000u32) + 1u32;
+//! let ctx = NttModulus::new(&n);
+//! let mut x = Integer::from(5);
+//! for _ in 0..100 {
+//!     x = ctx.square_mod(&x);
+//! }
+//! ```
+
+use rug::integer::Order;
+use rug::Integer;
+
+/// The Goldilocks prime 2^64 − 2^32 + 1.
+const P: u64 = 0xFFFF_FFFF_0000_0001;
+/// 2^64 mod p = 2^32 − 1.
+const EPSILON: u64 = 0xFFFF_FFFF;
+/// A generator of the multiplicative group mod p.
+const GENERATOR: u64 = 7;
+/// Largest transform the exact-convolution bound allows.
+const MAX_TRANSFORM: usize = 1 << 30;
+
+fn reduce128(x: u128) -> u64 {
+    let lo = x as u64;
+    let hi = (x >> 64) as u64;
+    let (hi_hi, hi_lo) = (hi >> 32, hi & EPSILON);
+    // 2^96 ≡ −1 and 2^64 ≡ 2^32 − 1 (mod p).
+    let (mut t0, borrow) = lo.overflowing_sub(hi_hi);
+    if borrow {
+        t0 = t0.wrapping_sub(EPSILON);
+    }
+    let (t, carry) = t0.overflowing_add(hi_lo * EPSILON);
+    let t = t.wrapping_add(EPSILON * carry as u64);
+    if t >= P {
+        t - P
+    } else {
+        t
+    }
+}
+
+fn mul(a: u64, b: u64) -> u64 {
+    reduce128(a as u128 * b as u128)
+}
+
+fn add(a: u64, b: u64) -> u64 {
+    let (s, carry) = a.overflowing_add(b);
+    let s = s.wrapping_add(EPSILON * carry as u64);
+    if s >= P {
+        s - P
+    } else {
+        s
+    }
+}
+
+fn sub(a: u64, b: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + (P - b)
+    }
+}
+
+fn pow(mut base: u64, mut exp: u64) -> u64 {
+    let mut acc = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul(acc, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    acc
+}
+
+/// Powers of the primitive `len`-th root of unity and of its inverse, for
+/// transforms of one power-of-two length.
+struct Twiddles {
+    forward: Vec<u64>,
+    inverse: Vec<u64>,
+    len_inv: u64,
+}
+
+impl Twiddles {
+    fn new(len: usize) -> Self {
+        assert!(len.is_power_of_two() && len <= MAX_TRANSFORM);
+        let root = pow(GENERATOR, (P - 1) >> len.trailing_zeros());
+        let powers = |w: u64| -> Vec<u64> {
+            std::iter::successors(Some(1), |&x| Some(mul(x, w)))
+                .take(len / 2)
+                .collect()
+        };
+        Twiddles {
+            forward: powers(root),
+            inverse: powers(pow(root, P - 2)),
+            len_inv: pow(len as u64, P - 2),
+        }
+    }
+
+    /// In-place iterative NTT of `a` (of the table's length); `inverse`
+    /// also scales by 1/len.
+    fn ntt(&self, a: &mut [u64], inverse: bool) {
+        let n = a.len();
+        debug_assert_eq!(n, self.forward.len() * 2);
+        let log_n = n.trailing_zeros();
+        for i in 0..n {
+            let j = i.reverse_bits() >> (usize::BITS - log_n);
+            if i < j {
+                a.swap(i, j);
+            }
+        }
+        let table = if inverse {
+            &self.inverse
+        } else {
+            &self.forward
+        };
+        let mut len = 2;
+        while len <= n {
+            let half = len / 2;
+            let stride = n / len;
+            for chunk in a.chunks_exact_mut(len) {
+                let (lo, hi) = chunk.split_at_mut(half);
+                for (j, (u, v)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                    let t = mul(*v, table[j * stride]);
+                    *v = sub(*u, t);
+                    *u = add(*u, t);
+                }
+            }
+            len <<= 1;
+        }
+        if inverse {
+            for x in a.iter_mut() {
+                *x = mul(*x, self.len_inv);
+            }
+        }
+    }
+
+    /// Transform of `x`'s 16-bit digits.
+    fn forward_digits(&self, x: &Integer) -> Vec<u64> {
+        let mut digits = to_digits(x, self.forward.len() * 2);
+        self.ntt(&mut digits, false);
+        digits
+    }
+
+    /// Inverse transform of the pointwise product, carried into an integer.
+    fn product(&self, mut fa: Vec<u64>, fb: &[u64]) -> Integer {
+        for (x, &y) in fa.iter_mut().zip(fb) {
+            *x = mul(*x, y);
+        }
+        self.ntt(&mut fa, true);
+        from_coefficients(&fa)
+    }
+}
+
+/// `x` as 16-bit digits, least significant first, zero-padded to `len`.
+fn to_digits(x: &Integer, len: usize) -> Vec<u64> {
+    let mut digits: Vec<u64> = x
+        .to_digits::<u16>(Order::Lsf)
+        .into_iter()
+        .map(u64::from)
+        .collect();
+    digits.resize(len, 0);
+    digits
+}
+
+/// Carry a convolution of 16-bit digits back into an integer.
+fn from_coefficients(coeffs: &[u64]) -> Integer {
+    let mut digits = Vec::with_capacity(coeffs.len() + 4);
+    let mut carry: u128 = 0;
+    for &c in coeffs {
+        carry += c as u128;
+        digits.push(carry as u16);
+        carry >>= 16;
+    }
+    while carry > 0 {
+        digits.push(carry as u16);
+        carry >>= 16;
+    }
+    Integer::from_digits(&digits, Order::Lsf)
+}
+
+/// Transform length for products of operands up to `bits` bits each.
+fn transform_len(bits: u32) -> usize {
+    let digits = (bits as usize).div_ceil(16).max(1);
+    (2 * digits).next_power_of_two()
+}
+
+/// Product of two non-negative integers through one NTT convolution.
+pub fn multiply(a: &Integer, b: &Integer) -> Integer {
+    let bits = a.significant_bits().max(b.significant_bits());
+    let tw = Twiddles::new(transform_len(bits));
+    let fa = tw.forward_digits(a);
+    let fb = tw.forward_digits(b);
+    tw.product(fa, &fb)
+}
+
+/// A modulus N with its Barrett constant and both transforms precomputed,
+/// for repeated [`square_mod`](Self::square_mod) and
+/// [`mul_mod`](Self::mul_mod).
+pub struct NttModulus {
+    n: Integer,
+    /// Bit length k of N.
+    k: u32,
+    twiddles: Twiddles,
+    n_hat: Vec<u64>,
+    /// Transform of μ = ⌊4^k / N⌋.
+    mu_hat: Vec<u64>,
+}
+
+impl NttModulus {
+    /// Precompute for N > 1.
+    pub fn new(n: &Integer) -> Self {
+        assert!(*n > 1, "modulus must exceed 1");
+        let k = n.significant_bits();
+        let mu = (Integer::from(1) << (2 * k)) / n;
+        // x² < 4^k, q̂ = ⌊x² / 2^(k−1)⌋ < 2^(k+1) and μ ≤ 2^(k+1).
+        let twiddles = Twiddles::new(transform_len(k + 2));
+        NttModulus {
+            n: n.clone(),
+            k,
+            n_hat: twiddles.forward_digits(n),
+            mu_hat: twiddles.forward_digits(&mu),
+            twiddles,
+        }
+    }
+
+    /// The modulus N.
+    pub fn modulus(&self) -> &Integer {
+        &self.n
+    }
+
+    /// x² mod N for 0 ≤ x < N.
+    pub fn square_mod(&self, x: &Integer) -> Integer {
+        let fx = self.twiddles.forward_digits(x);
+        self.reduce(self.twiddles.product(fx.clone(), &fx))
+    }
+
+    /// a·b mod N for 0 ≤ a, b < N.
+    pub fn mul_mod(&self, a: &Integer, b: &Integer) -> Integer {
+        let fa = self.twiddles.forward_digits(a);
+        let fb = self.twiddles.forward_digits(b);
+        self.reduce(self.twiddles.product(fa, &fb))
+    }
+
+    /// Barrett reduction of 0 ≤ x < N².
+    fn reduce(&self, x: Integer) -> Integer {
+        let tw = &self.twiddles;
+        let q1 = Integer::from(&x >> (self.k - 1));
+        let q2 = tw.product(tw.forward_digits(&q1), &self.mu_hat);
+        let q3 = q2 >> (self.k + 1);
+        let mut r = x - tw.product(tw.forward_digits(&q3), &self.n_hat);
+        // The estimate undershoots the true quotient by at most 2.
+        while r >= self.n {
+            r -= &self.n;
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rug::rand::RandState;
+
+    /// The Goldilocks reduction agrees with u128 remainder, including
+    /// values near the 2^128 boundary.
+    #[test]
+    fn reduce128_matches_remainder() {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut samples = vec![0u128, P as u128, u128::MAX, (P as u128) * (P as u128 - 1)];
+        for _ in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            samples.push(((state as u128) << 64) | state.rotate_left(29) as u128);
+        }
+        for x in samples {
+            assert_eq!(reduce128(x), (x % P as u128) as u64, "x={}", x);
+        }
+    }
+
+    /// A forward then inverse transform is the identity.
+    #[test]
+    fn ntt_round_trips() {
+        let original: Vec<u64> = (0..64).map(|i| i * 977 % 65_536).collect();
+        let tw = Twiddles::new(64);
+        let mut a = original.clone();
+        tw.ntt(&mut a, false);
+        assert_ne!(a, original);
+        tw.ntt(&mut a, true);
+        assert_eq!(a, original);
+    }
+
+    /// NTT multiplication and squaring mod N agree with rug on random
+    /// large inputs, including a Proth modulus and repeated squarings.
+    #[test]
+    fn square_mod_matches_rug() {
+        let mut rand = RandState::new();
+        for bits in [17u32, 64, 1000, 4099, 20_000] {
+            let n = Integer::from(Integer::random_bits(bits, &mut rand)) | 1u32;
+            let n = n | (Integer::from(1) << (bits - 1));
+            let ctx = NttModulus::new(&n);
+            for _ in 0..4 {
+                let a = Integer::from(n.random_below_ref(&mut rand));
+                let b = Integer::from(n.random_below_ref(&mut rand));
+                assert_eq!(ctx.square_mod(&a), Integer::from(&a * &a) % &n);
+                assert_eq!(ctx.mul_mod(&a, &b), Integer::from(&a * &b) % &n);
+                assert_eq!(multiply(&a, &b), Integer::from(&a * &b));
+            }
+        }
+
+        let proth = (Integer::from(3) << 5000u32) + 1u32;
+        let ctx = NttModulus::new(&proth);
+        let (mut x, mut expected) = (Integer::from(5), Integer::from(5));
+        for _ in 0..50 {
+            x = ctx.square_mod(&x);
+            expected = expected.square() % &proth;
+        }
+        assert_eq!(x, expected);
+        assert_eq!(ctx.square_mod(&Integer::from(&proth - 1u32)), 1);
+    }
+}
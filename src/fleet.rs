@@ -22,6 +22,16 @@ pub struct WorkerState {
     pub current: String,
     pub checkpoint: Option<String>,
     pub metrics: Option<crate::metrics::HardwareMetrics>,
+    /// LLR tests per second from the startup benchmark, if reported.
+    pub benchmark_score: Option<f64>,
+    /// Rayon pool size at the last heartbeat, if reported.
+    pub rayon_threads: Option<usize>,
+    /// Rayon threads testing a candidate at the last heartbeat, if reported.
+    pub threads_busy: Option<usize>,
+    /// Candidate under test at the last heartbeat, if one was in flight.
+    pub current_candidate: Option<String>,
+    /// Seconds `current_candidate` has been under test.
+    pub current_candidate_secs: Option<u64>,
     pub uptime_secs: u64,
     pub last_heartbeat_secs_ago: u64,
     #[serde(skip)]
@@ -63,6 +73,11 @@ mod tests {
             current: String::new(),
             checkpoint: None,
             metrics: None,
+            benchmark_score: None,
+            rayon_threads: None,
+            threads_busy: None,
+            current_candidate: None,
+            current_candidate_secs: None,
             uptime_secs: 0,
             last_heartbeat_secs_ago: 0,
             last_heartbeat: Instant::now(),
@@ -2,7 +2,9 @@
 //!
 //! FLINT's `fft_small` module uses SIMD-vectorized NTTs (AVX2 on x86, NEON on ARM)
 //! for 3-10x faster multiplication than GMP at large sizes. This module provides
-//! optional wrappers for factorial and primorial computation.
+//! optional wrappers for factorial and primorial computation, and [`primality`],
+//! FLINT's own primality tests, which `verify::verify_flint` uses as a second
+//! implementation next to rug/GMP.
 //!
 //! Enable with `--features flint`. Requires `libflint` installed on the system:
 //! - macOS: `brew install flint`
@@ -57,6 +59,53 @@ mod inner {
         }
     }
 
+    /// Bits up to which [`primality`] asks `fmpz_is_prime` for a proof. Past
+    /// this its APR-CL fallback gets too slow, and only BPSW is run.
+    pub const PROOF_MAX_BITS: u32 = 1024;
+
+    /// FLINT's verdict on a candidate.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Primality {
+        /// Proven prime (`n_is_prime` for one word, `fmpz_is_prime` above).
+        Prime,
+        /// Passed `fmpz_is_probabprime` (BPSW) but was not proven.
+        ProbablePrime,
+        Composite,
+    }
+
+    /// Test `n` with FLINT alone: `n_is_prime` when it fits in a word,
+    /// `fmpz_is_prime` up to [`PROOF_MAX_BITS`], `fmpz_is_probabprime` above
+    /// that or when no proof is found.
+    pub fn primality(n: &Integer) -> Primality {
+        if *n < 2 {
+            return Primality::Composite;
+        }
+        if let Some(small) = n.to_u64() {
+            let prime = unsafe { n_is_prime(small as ulong) } == 1;
+            return if prime {
+                Primality::Prime
+            } else {
+                Primality::Composite
+            };
+        }
+        let mut f = Fmpz::new();
+        unsafe {
+            fmpz_set_mpz(f.as_mut_ptr(), n.as_raw());
+        }
+        if n.significant_bits() <= PROOF_MAX_BITS {
+            match unsafe { fmpz_is_prime(f.as_ptr()) } {
+                1 => return Primality::Prime,
+                0 => return Primality::Composite,
+                _ => {}
+            }
+        }
+        if unsafe { fmpz_is_probabprime(f.as_ptr()) } == 1 {
+            Primality::ProbablePrime
+        } else {
+            Primality::Composite
+        }
+    }
+
     /// Compute n! using FLINT's binary-splitting factorial.
     ///
     /// FLINT's fmpz_fac_ui uses a prime-swing algorithm with SIMD-accelerated
@@ -121,9 +170,34 @@ mod inner {
                 assert_eq!(flint_result, gmp_result, "FLINT and GMP disagree on {}#", p);
             }
         }
+
+        /// FLINT agrees with rug on every n below 5000, on word-sized values
+        /// around 2^64, and on multi-word primes and composites on both sides
+        /// of [`PROOF_MAX_BITS`].
+        #[test]
+        fn primality_matches_rug() {
+            let mut cases: Vec<Integer> = (0..5000u32).map(Integer::from).collect();
+            let two_64 = Integer::from(1u32) << 64;
+            cases.extend((1..200u32).map(|d| Integer::from(&two_64 - d)));
+            cases.extend((1..200u32).map(|d| Integer::from(&two_64 + d)));
+            let mersenne_127 = (Integer::from(1u32) << 127) - 1u32;
+            let mersenne_1279 = (Integer::from(1u32) << 1279) - 1u32;
+            for m in [mersenne_127, mersenne_1279] {
+                cases.push(Integer::from(&m + 2u32));
+                cases.push(m);
+            }
+            for n in cases {
+                let rug_prime = n.is_probably_prime(25) != rug::integer::IsPrime::No;
+                let verdict = primality(&n);
+                assert_eq!(verdict != Primality::Composite, rug_prime, "{}", n);
+                if n.significant_bits() <= PROOF_MAX_BITS && rug_prime {
+                    assert_eq!(verdict, Primality::Prime, "{}", n);
+                }
+            }
+        }
     }
 }
 
 // Re-export inner module functions when feature is enabled
 #[cfg(feature = "flint")]
-pub use inner::{factorial, primorial};
+pub use inner::{factorial, primality, primorial, Primality, PROOF_MAX_BITS};
@@ -0,0 +1,445 @@
+//! # Gaussian — Gaussian Prime Search (a + bi)
+//!
+//! Searches for Gaussian primes a + bi along a line of fixed imaginary part
+//! b, with the real part a running over a range. The b = 1 line is the
+//! classic hunt for primes of the form a² + 1.
+//!
+//! ## Primality Criterion
+//!
+//! A Gaussian integer a + bi is prime in ℤ[i] exactly when:
+//!
+//! - **On an axis** (exactly one of a, b is zero): the nonzero part's absolute
+//!   value is a rational prime p ≡ 3 (mod 4). Primes p ≡ 1 (mod 4) and 2
+//!   split, e.g. 5 = (2 + i)(2 − i) and 2 = −i(1 + i)².
+//! - **Off the axes**: the norm N = a² + b² is a rational prime.
+//!
+//! The rational test is `mr_screened_test` on the nonzero part or the norm,
+//! so the certainty label follows the norm's size.
+//!
+//! ## Algorithm
+//!
+//! 1. **Norm sieve**: For each sieve prime q, strikes the residue classes
+//!    a ≡ ±b·√−1 (mod q), where q | a² + b², when the norm exceeds q. Primes
+//!    q ≡ 3 (mod 4) not dividing b have no such class. On the b = 0 axis the
+//!    class is a ≡ 0, i.e. multiples of q.
+//! 2. **Test**: Survivors are tested in parallel with [`gaussian_test`].
+//!
+//! Only the first quadrant (a, b ≥ 0) is searched: the associates
+//! ±(a + bi), ±i(a + bi) and the conjugate a − bi are prime together.
+//! Reported digit counts are those of the norm (of p on an axis).
+//!
+//! The search runs from the `gaussian` subcommand only; it is not a
+//! distributed form, so there are no projects, jobs or work blocks for it.
+//! Verification re-tests the norm (see [`rational_candidate`]).
+//!
+//! ## References
+//!
+//! - OEIS: [A002496](https://oeis.org/A002496) — Primes of the form n² + 1.
+//! - OEIS: [A103431](https://oeis.org/A103431) — Real parts of first-quadrant
+//!   Gaussian primes.
+
+use anyhow::Result;
+use rayon::prelude::*;
+use rug::integer::IsPrime;
+use rug::Integer;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+use tracing::info;
+
+use crate::checkpoint::{self, Checkpoint};
+use crate::events::{self, EventBus};
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
+use crate::progress::Progress;
+use crate::CoordinationClient;
+use crate::{exact_digits, mr_screened_test, sieve};
+
+/// Render a + bi as `a+bi` (or `a-bi` for negative b).
+pub fn expression(a: &Integer, b: &Integer) -> String {
+    if *b < 0 {
+        format!("{}-{}i", a, Integer::from(b.abs_ref()))
+    } else {
+        format!("{}+{}i", a, b)
+    }
+}
+
+/// The norm a² + b².
+fn norm(a: &Integer, b: &Integer) -> Integer {
+    Integer::from(a.square_ref()) + Integer::from(b.square_ref())
+}
+
+/// The rational number whose primality decides a + bi, or `None` when a + bi
+/// lies on an axis with a nonzero part that is not ≡ 3 (mod 4) (including 0).
+fn rational_part(a: &Integer, b: &Integer) -> Option<Integer> {
+    if *a != 0 && *b != 0 {
+        return Some(norm(a, b));
+    }
+    let p = Integer::from(a.abs_ref()) + Integer::from(b.abs_ref());
+    (p.mod_u(4) == 3).then_some(p)
+}
+
+/// Test a + bi for primality in ℤ[i], with `mr_rounds` Miller–Rabin rounds on
+/// the rational part.
+pub fn gaussian_test(a: &Integer, b: &Integer, mr_rounds: u32) -> IsPrime {
+    match rational_part(a, b) {
+        Some(n) => mr_screened_test(&n, mr_rounds),
+        None => IsPrime::No,
+    }
+}
+
+/// Whether a + bi is a Gaussian prime.
+pub fn is_gaussian_prime(a: i64, b: i64) -> bool {
+    gaussian_test(&Integer::from(a), &Integer::from(b), 25) != IsPrime::No
+}
+
+/// Parse an expression rendered by [`expression`] back into (a, b).
+pub fn parse_expression(expr: &str) -> Option<(Integer, Integer)> {
+    let body = expr.trim().strip_suffix('i')?;
+    // The sign between the parts is the last one not at the start.
+    let split = body.rfind(['+', '-']).filter(|&i| i > 0)?;
+    let a = Integer::parse(&body[..split]).ok()?;
+    let b = Integer::parse(&body[split..]).ok()?;
+    Some((Integer::from(a), Integer::from(b)))
+}
+
+/// The rational number whose primality decides a stored `a+bi` expression:
+/// the norm, or the nonzero part on an axis. This is what
+/// [`crate::verify::reconstruct_candidate`] re-tests for the `gaussian` form.
+pub fn rational_candidate(expr: &str) -> Option<Integer> {
+    let (a, b) = parse_expression(expr)?;
+    rational_part(&a, &b)
+}
+
+/// A square root of −1 mod the prime `q`, or `None` when q ≡ 3 (mod 4).
+fn sqrt_minus_one(q: u64) -> Option<u64> {
+    if q == 2 {
+        return Some(1);
+    }
+    if q % 4 != 1 {
+        return None;
+    }
+    // c^((q−1)/4) for a non-residue c squares to c^((q−1)/2) = −1.
+    (2..q)
+        .find(|&c| sieve::pow_mod(c, (q - 1) / 2, q) == q - 1)
+        .map(|c| sieve::pow_mod(c, (q - 1) / 4, q))
+}
+
+/// Sieve the real parts a in [min_a, max_a] of a + bi.
+///
+/// Returns one flag per a; `false` means a sieve prime properly divides the
+/// rational part, so a + bi is composite.
+///
+/// q | a² + b² exactly when a ≡ ±b·√−1 (mod q), so each sieve prime strikes
+/// at most two residue classes and primes q ≡ 3 (mod 4) not dividing b strike
+/// none: the cost is about `range · Σ 2/q` rather than `range · π(limit)`.
+fn sieve_line(min_a: u64, max_a: u64, b: u64, sieve_primes: &[u64]) -> Vec<bool> {
+    let len = (max_a - min_a + 1) as usize;
+    let mut survives = vec![true; len];
+    let b_sq = u128::from(b).pow(2);
+
+    for &q in sieve_primes {
+        let b_mod = b % q;
+        let roots = if b_mod == 0 {
+            [0, 0]
+        } else {
+            match sqrt_minus_one(q) {
+                Some(i) => {
+                    let r = (u128::from(b_mod) * u128::from(i) % u128::from(q)) as u64;
+                    [r, q - r]
+                }
+                None => continue,
+            }
+        };
+        let span = if roots[0] == roots[1] { 1 } else { 2 };
+        for &root in &roots[..span] {
+            let first = min_a + (root + q - min_a % q) % q;
+            for a in (first..=max_a).step_by(q as usize) {
+                // On an axis the rational part is the nonzero coordinate.
+                let rational = if a == 0 || b == 0 {
+                    u128::from(a.max(b))
+                } else {
+                    u128::from(a).pow(2) + b_sq
+                };
+                if rational > u128::from(q) {
+                    survives[(a - min_a) as usize] = false;
+                }
+            }
+        }
+    }
+
+    survives
+}
+
+/// Search for Gaussian primes a + bi with b = `imag` and a in [min_a, max_a].
+#[allow(clippy::too_many_arguments)]
+pub fn search(
+    imag: u64,
+    min_a: u64,
+    max_a: u64,
+    progress: &Arc<Progress>,
+    db: &dyn PrimeSink,
+    rt: &tokio::runtime::Handle,
+    checkpoint_path: &Path,
+    search_params: &str,
+    mr_rounds: u32,
+    sieve_limit: u64,
+    worker_client: Option<&dyn CoordinationClient>,
+    event_bus: Option<&EventBus>,
+) -> Result<()> {
+    let params = serde_json::json!({"imag": imag, "min_real": min_a, "max_real": max_a});
+    crate::search_params::validate("gaussian", &params)?;
+
+    // a² + b² has about twice the bits of max(a, b)
+    let candidate_bits = 2 * (64 - max_a.max(imag).leading_zeros()) as u64;
+    let sieve_limit = sieve::resolve_sieve_limit(sieve_limit, candidate_bits, max_a - min_a + 1);
+    let sieve_primes = sieve::generate_primes(sieve_limit);
+    info!(
+        imag,
+        min_a,
+        max_a,
+        prime_count = sieve_primes.len(),
+        sieve_limit,
+        "Gaussian prime search started"
+    );
+
+    let resume_from = match checkpoint::load(checkpoint_path) {
+        Some(Checkpoint::Gaussian { last_a, .. }) if last_a >= min_a && last_a < max_a => {
+            info!(resume_a = last_a + 1, "resuming Gaussian prime search");
+            last_a + 1
+        }
+        _ => min_a,
+    };
+
+    let survives = sieve_line(resume_from, max_a, imag, &sieve_primes);
+    let survivors: Vec<u64> = (resume_from..=max_a)
+        .zip(&survives)
+        .filter_map(|(a, &s)| s.then_some(a))
+        .collect();
+    let total_range = max_a - resume_from + 1;
+    info!(
+        survivors = survivors.len(),
+        total_range,
+        survivor_pct = survivors.len() as f64 / total_range as f64 * 100.0,
+        "sieve complete"
+    );
+
+    let b = Integer::from(imag);
+    let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
+
+    for chunk in survivors.chunks(10_000) {
+        let block_min = chunk[0];
+        let block_max = chunk[chunk.len() - 1];
+
+        *progress.current.lock().unwrap() = format!("[{}..{}]+{}i", block_min, block_max, imag);
+
+        let found: Vec<_> = chunk
+            .par_iter()
+            .filter_map(|&a| {
+                let _busy = crate::progress::busy();
+                let a = Integer::from(a);
+                let n = rational_part(&a, &b)?;
+                let _testing = progress.testing(&expression(&a, &b), &n);
+                let r = mr_screened_test(&n, mr_rounds);
+                if r == IsPrime::No {
+                    return None;
+                }
+                let certainty = crate::certainty_label(&n, r);
+                Some((expression(&a, &b), exact_digits(&n), certainty.to_string()))
+            })
+            .collect();
+
+        progress
+            .tested
+            .fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        progress.record_survivors(chunk.iter().copied());
+
+        for (expr, digits, certainty) in found {
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "gaussian",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
+            progress.found.fetch_add(1, Ordering::Relaxed);
+            if let Some(eb) = event_bus {
+                eb.emit(events::Event::PrimeFound {
+                    form: "gaussian".into(),
+                    expression: expr.clone(),
+                    digits,
+                    proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
+                    timestamp: Instant::now(),
+                });
+                eb.check_milestone("gaussian", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "gaussian").ok().flatten()
+                });
+            } else {
+                info!(
+                    expression = %expr,
+                    digits,
+                    certainty = %certainty,
+                    "Gaussian prime found"
+                );
+            }
+            buffer.push("gaussian", &expr, digits, search_params, &certainty, None)?;
+        }
+
+        buffer.flush_if_due()?;
+
+        let stop = worker_client.is_some_and(|wc| wc.is_stop_requested());
+        if stop || last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
+            checkpoint::save(
+                checkpoint_path,
+                &Checkpoint::Gaussian {
+                    last_a: block_max,
+                    imag: Some(imag),
+                    min_a: Some(min_a),
+                    max_a: Some(max_a),
+                },
+            )?;
+            if stop {
+                info!(a = block_max, "stop requested, checkpoint saved");
+                return Ok(());
+            }
+            info!(a = block_max, "checkpoint saved");
+            last_checkpoint = Instant::now();
+        }
+    }
+
+    buffer.flush()?;
+    checkpoint::clear(checkpoint_path);
+    info!("Gaussian prime search complete");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// First-quadrant Gaussian primes with 0 < a, b ≤ 6, by norm: 2, 5, 13,
+    /// 17, 29, 37, 41, 61.
+    #[test]
+    fn small_gaussian_primes() {
+        let primes = [
+            (1, 1),
+            (1, 2),
+            (2, 1),
+            (2, 3),
+            (3, 2),
+            (1, 4),
+            (4, 1),
+            (2, 5),
+            (5, 2),
+            (1, 6),
+            (6, 1),
+            (4, 5),
+            (5, 4),
+            (5, 6),
+            (6, 5),
+        ];
+        for a in 1..=6i64 {
+            for b in 1..=6i64 {
+                let expected = primes.contains(&(a, b));
+                assert_eq!(is_gaussian_prime(a, b), expected, "{}+{}i", a, b);
+            }
+        }
+        // Associates and conjugates are prime together.
+        for (a, b) in primes {
+            assert!(is_gaussian_prime(-a, b) && is_gaussian_prime(a, -b));
+            assert!(is_gaussian_prime(-b, a) && is_gaussian_prime(-a, -b));
+        }
+    }
+
+    /// On an axis only rational primes ≡ 3 (mod 4) stay prime; 2 and primes
+    /// ≡ 1 (mod 4) split, and 0 and units are not prime.
+    #[test]
+    fn axis_primes_are_3_mod_4() {
+        for p in [3i64, 7, 11, 19, 23, 31, 43, 1_000_003] {
+            for (a, b) in [(p, 0), (0, p), (-p, 0), (0, -p)] {
+                assert!(is_gaussian_prime(a, b), "{}+{}i", a, b);
+            }
+        }
+        for n in [0i64, 1, 2, 5, 9, 13, 15, 17, 21, 29, 1_000_033] {
+            assert!(!is_gaussian_prime(n, 0), "{}", n);
+            assert!(!is_gaussian_prime(0, n), "{}i", n);
+        }
+    }
+
+    #[test]
+    fn expression_renders_a_plus_bi() {
+        let expr = |a: i64, b: i64| expression(&Integer::from(a), &Integer::from(b));
+        assert_eq!(expr(3, 2), "3+2i");
+        assert_eq!(expr(3, -2), "3-2i");
+        assert_eq!(expr(0, 7), "0+7i");
+        assert_eq!(expr(-4, 1), "-4+1i");
+    }
+
+    /// The sieve never strikes a prime: on the b = 1 line the survivors are a
+    /// superset of the a with a² + 1 prime (OEIS A005574), and on the real
+    /// axis of the odd rational primes.
+    #[test]
+    fn sieve_keeps_every_prime() {
+        let primes = sieve::generate_primes(100);
+        for b in [0u64, 1, 2, 5] {
+            let survives = sieve_line(0, 200, b, &primes);
+            for a in 0..=200u64 {
+                if is_gaussian_prime(a as i64, b as i64) {
+                    assert!(survives[a as usize], "{}+{}i struck", a, b);
+                }
+            }
+        }
+        let survives = sieve_line(1, 30, 1, &primes);
+        let line: Vec<u64> = (1..=30).filter(|&a| survives[a as usize - 1]).collect();
+        assert_eq!(line, [1, 2, 4, 6, 10, 14, 16, 20, 24, 26]);
+    }
+
+    /// Stored expressions parse back to their parts, and verification's
+    /// rational candidate is the norm (or the axis value).
+    #[test]
+    fn rational_candidate_rebuilds_norm() {
+        let parse = |e: &str| parse_expression(e).map(|(a, b)| (a.to_i64(), b.to_i64()));
+        assert_eq!(parse("3+2i"), Some((Some(3), Some(2))));
+        assert_eq!(parse("3-2i"), Some((Some(3), Some(-2))));
+        assert_eq!(parse("-4+1i"), Some((Some(-4), Some(1))));
+        assert_eq!(parse("3+2"), None);
+        assert_eq!(parse("+2i"), None);
+        assert_eq!(rational_candidate("4+5i"), Some(Integer::from(41)));
+        assert_eq!(rational_candidate("0+7i"), Some(Integer::from(7)));
+        assert_eq!(rational_candidate("5+0i"), None);
+    }
+
+    /// The residue-class sieve strikes exactly what testing every sieve
+    /// prime against every a would.
+    #[test]
+    fn residue_sieve_matches_direct_division() {
+        let primes = sieve::generate_primes(200);
+        for b in [0u64, 1, 2, 3, 10, 13, 97] {
+            let (min_a, max_a) = (0, 500);
+            let survives = sieve_line(min_a, max_a, b, &primes);
+            for a in min_a..=max_a {
+                let rational = if a == 0 || b == 0 {
+                    u128::from(a.max(b))
+                } else {
+                    u128::from(a * a + b * b)
+                };
+                let struck = primes.iter().any(|&q| {
+                    let q = u128::from(q);
+                    rational > q && rational % q == 0
+                });
+                assert_eq!(survives[a as usize], !struck, "{}+{}i", a, b);
+            }
+        }
+        assert_eq!(sieve_line(7, 9, 0, &primes), [true, false, false]);
+    }
+}
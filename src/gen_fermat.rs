@@ -10,6 +10,14 @@
 //! x + 1 | x^m + 1 has non-trivial algebraic factors). The base b must
 //! be even (if b is odd, b^(2^n) + 1 is even and > 2).
 //!
+//! ## Perfect-Power Bases
+//!
+//! If b = c^m, then b^(2^n) + 1 = c^(m·2^n) + 1. When m has an odd factor
+//! d > 1, c^(m·2^n/d) + 1 divides it, so the candidate is composite and the
+//! base is skipped. When m = 2^j the number is c^(2^(n+j)) + 1, a generalized
+//! Fermat number for the primitive base c; it is still tested, but recorded
+//! under that canonical expression so it deduplicates against base-c searches.
+//!
 //! ## Sieve Strategy
 //!
 //! For sieve prime q, b^(2^n) + 1 ≡ 0 (mod q) iff b^(2^n) ≡ −1 (mod q).
@@ -55,13 +63,14 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Instant;
 
-use tracing::info;
+use tracing::{debug, info};
 
+use crate::candidate::{CandidateForm, GenFermat};
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::kbn;
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::CoordinationClient;
 use crate::{exact_digits, sieve};
@@ -79,6 +88,38 @@ fn is_pepin_provable(b: u64) -> bool {
     (1u64 << t) > m
 }
 
+/// How an even base relates to its primitive root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BaseForm {
+    /// b is not a perfect power.
+    Primitive,
+    /// b = c^(2^j): b^(2^n) + 1 is the same number as c^(2^(n+j)) + 1.
+    Reduced { base: u64, fermat_n: u32 },
+    /// b = c^m where m has an odd factor: b^(2^n) + 1 has algebraic factors.
+    Reducible { root: u64, power: u32 },
+}
+
+/// Classify base `b` for exponent `fermat_n` using its perfect-power form.
+fn classify_base(b: u64, fermat_n: u32) -> BaseForm {
+    match sieve::is_perfect_power(b) {
+        None => BaseForm::Primitive,
+        Some((root, power)) if power.is_power_of_two() => BaseForm::Reduced {
+            base: root,
+            fermat_n: fermat_n + power.trailing_zeros(),
+        },
+        Some((root, power)) => BaseForm::Reducible { root, power },
+    }
+}
+
+/// Canonical (base, exponent) for b^(2^n) + 1: the primitive base with the
+/// exponent adjusted for any power-of-two reduction.
+fn canonical_form(b: u64, fermat_n: u32) -> (u64, u32) {
+    match classify_base(b, fermat_n) {
+        BaseForm::Reduced { base, fermat_n } => (base, fermat_n),
+        _ => (b, fermat_n),
+    }
+}
+
 /// Test a generalized Fermat candidate for primality.
 ///
 /// Uses Proth/Pépin test first (deterministic for qualifying bases),
@@ -160,7 +201,7 @@ pub fn search(
     min_base: u64,
     max_base: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -169,6 +210,10 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params =
+        serde_json::json!({"fermat_exp": fermat_n, "min_base": min_base, "max_base": max_base});
+    crate::search_params::validate("gen_fermat", &params)?;
+
     // Ensure bases are even
     let min_b = if min_base.is_multiple_of(2) {
         min_base.max(2)
@@ -231,6 +276,33 @@ pub fn search(
         .map(|i| resume_from + 2 * i as u64)
         .collect();
 
+    // Perfect-power bases with an odd exponent factor are always composite
+    let sieved = survivors.len();
+    let survivors: Vec<u64> = survivors
+        .into_iter()
+        .filter(|&b| match classify_base(b, fermat_n) {
+            BaseForm::Reducible { root, power } => {
+                debug!(
+                    b,
+                    root,
+                    power,
+                    "skipping base: {}^({}·2^{}) + 1 has algebraic factors",
+                    root,
+                    power,
+                    fermat_n
+                );
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    if survivors.len() < sieved {
+        info!(
+            skipped = sieved - survivors.len(),
+            "skipped perfect-power bases with algebraic factors"
+        );
+    }
+
     let total_range = ((max_b - resume_from) / 2 + 1) as usize;
     let eliminated = total_range - survivors.len();
     info!(
@@ -244,6 +316,7 @@ pub fn search(
     // Process in blocks for checkpointing
     let block_size = 100;
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
 
     for chunk in survivors.chunks(block_size) {
         let block_min = chunk[0];
@@ -255,11 +328,13 @@ pub fn search(
         let found: Vec<_> = chunk
             .par_iter()
             .filter_map(|&b| {
+                let _busy = crate::progress::busy();
                 // Compute b^(2^n) + 1
                 let exponent = crate::checked_u32(1u64 << fermat_n);
                 let b_pow = Integer::from(b).pow(exponent);
                 let candidate = Integer::from(&b_pow + 1u32);
                 let expr = format!("{}^{}+1", b, exponent);
+                let _testing = progress.testing(&expr, &candidate);
 
                 // Try PFGW acceleration (50-100x faster for large candidates)
                 if let Some(pfgw_result) = pfgw::try_test(&expr, &candidate, pfgw::PfgwMode::Prp) {
@@ -277,12 +352,12 @@ pub fn search(
                             return Some((b, digits, certainty));
                         }
                         pfgw::PfgwResult::Composite => return None,
-                        pfgw::PfgwResult::Unavailable { .. } => {} // fall through
+                        pfgw::PfgwResult::Unavailable { .. } | pfgw::PfgwResult::Failed { .. } => {} // fall through
                     }
                 }
 
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&candidate) {
+                if crate::p1::adaptive_p1_filter_for_form(&candidate, "gen_fermat") {
                     return None;
                 }
 
@@ -299,9 +374,24 @@ pub fn search(
         progress
             .tested
             .fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        progress.record_survivors(chunk.iter().copied());
 
         for (b, digits, certainty) in found {
-            let expr = format!("{}^(2^{}) + 1", b, fermat_n);
+            let (base, n) = canonical_form(b, fermat_n);
+            let expr = GenFermat { fermat_n: n }.expression(base);
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "gen_fermat",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -309,8 +399,12 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                eb.check_milestone("gen_fermat", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "gen_fermat").ok().flatten()
+                });
             } else {
                 info!(
                     expression = %expr,
@@ -319,21 +413,13 @@ pub fn search(
                     "generalized Fermat prime found"
                 );
             }
-            db.insert_prime_sync(
-                rt,
-                "gen_fermat",
-                &expr,
-                digits,
-                search_params,
-                &certainty,
-                None,
-            )?;
-            if let Some(wc) = worker_client {
-                wc.report_prime("gen_fermat", &expr, digits, search_params, &certainty);
-            }
+            buffer.push("gen_fermat", &expr, digits, search_params, &certainty, None)?;
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::GenFermat {
@@ -348,6 +434,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::GenFermat {
@@ -362,6 +449,7 @@ pub fn search(
         }
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!("generalized Fermat search complete");
     Ok(())
@@ -581,4 +669,47 @@ mod tests {
             "2^6+1 = 65 should be composite"
         );
     }
+
+    #[test]
+    fn base16_reduces_to_base2() {
+        // 16 = 2^4, so 16^(2^n) + 1 = 2^(2^(n+2)) + 1 = F_(n+2)
+        for n in 0..4 {
+            assert_eq!(
+                classify_base(16, n),
+                BaseForm::Reduced {
+                    base: 2,
+                    fermat_n: n + 2
+                }
+            );
+            assert_eq!(canonical_form(16, n), (2, n + 2));
+            assert_eq!(gf(16, n), gf(2, n + 2));
+        }
+    }
+
+    #[test]
+    fn perfect_power_bases_with_odd_exponent_are_reducible() {
+        // 8 = 2^3: 8^2 + 1 = 65 = (2^2 + 1)(2^4 - 2^2 + 1)
+        assert_eq!(
+            classify_base(8, 1),
+            BaseForm::Reducible { root: 2, power: 3 }
+        );
+        assert!(gf(8, 1).is_divisible(&gf(2, 1)));
+        // 64 = 2^6: odd factor 3 makes 64^(2^n) + 1 composite for every n
+        assert_eq!(
+            classify_base(64, 2),
+            BaseForm::Reducible { root: 2, power: 6 }
+        );
+        for n in 0..3 {
+            assert_eq!(gf(64, n).is_probably_prime(25), IsPrime::No);
+        }
+    }
+
+    #[test]
+    fn classify_primitive_and_square_bases() {
+        assert_eq!(classify_base(6, 3), BaseForm::Primitive);
+        assert_eq!(canonical_form(6, 3), (6, 3));
+        // 36 = 6^2: 36^(2^n) + 1 = 6^(2^(n+1)) + 1
+        assert_eq!(canonical_form(36, 1), (6, 2));
+        assert_eq!(gf(36, 1), gf(6, 2));
+    }
 }
@@ -13,6 +13,13 @@
 //!
 //! x86-64 only (SSE2/AVX/FMA3/AVX-512 assembly). Not available on ARM/Apple Silicon.
 //!
+//! GWNUM picks its FFT code path from CPUID at `gwsetup` time. Each context
+//! remembers the [`GwFeatures`] it was set up with, and the long-running tests
+//! re-read CPUID at every Gerbicz checkpoint: if a feature the plan needs is
+//! gone (VM live-migration), the context is rebuilt and the computation
+//! replays from the last verified checkpoint. [`detect_features`] cannot be
+//! used for this, since std caches its answer for the life of the process.
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -28,7 +35,9 @@
 
 use rug::ops::{Pow, RemRounding};
 use rug::Integer;
+use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
+use tracing::{debug, warn};
 
 /// Errors from GWNUM operations.
 #[derive(Debug, Clone)]
@@ -64,6 +73,142 @@ impl std::fmt::Display for GwError {
 
 impl std::error::Error for GwError {}
 
+/// Why `gwsetup` could not produce a usable handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GwSetupError {
+    /// `gwsetup` itself returned this nonzero code.
+    ReturnCode(i32),
+    /// `gwsetup` returned 0 but `gw_test_for_error` then reported this error,
+    /// e.g. for an exponent beyond GWNUM's supported FFT range.
+    ErrorFlagged(i32),
+    /// GWNUM library not available (gwnum.a not linked).
+    Unavailable,
+}
+
+impl std::fmt::Display for GwSetupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GwSetupError::ReturnCode(code) => write!(f, "gwsetup failed with code {}", code),
+            GwSetupError::ErrorFlagged(code) => {
+                write!(f, "gwsetup left GWNUM error {} flagged", code)
+            }
+            GwSetupError::Unavailable => write!(f, "GWNUM library not available"),
+        }
+    }
+}
+
+impl std::error::Error for GwSetupError {}
+
+impl From<GwSetupError> for GwError {
+    fn from(err: GwSetupError) -> Self {
+        match err {
+            GwSetupError::ReturnCode(code) | GwSetupError::ErrorFlagged(code) => {
+                GwError::SetupFailed { code }
+            }
+            GwSetupError::Unavailable => GwError::Unavailable,
+        }
+    }
+}
+
+/// SIMD instruction sets that determine which GWNUM FFT code path is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GwFeatures {
+    pub sse2: bool,
+    pub avx: bool,
+    pub fma3: bool,
+    pub avx2: bool,
+    pub avx512f: bool,
+}
+
+impl GwFeatures {
+    /// Widest FFT code path GWNUM can select with these features.
+    pub fn fft_path(&self) -> &'static str {
+        if self.avx512f {
+            "avx512"
+        } else if self.fma3 {
+            "fma3"
+        } else if self.avx {
+            "avx"
+        } else if self.sse2 {
+            "sse2"
+        } else {
+            "none"
+        }
+    }
+
+    /// True if every feature in `required` is also present in `self`.
+    ///
+    /// Gaining features is harmless for an existing FFT plan; losing one is not.
+    pub fn supports(&self, required: &GwFeatures) -> bool {
+        (self.sse2 || !required.sse2)
+            && (self.avx || !required.avx)
+            && (self.fma3 || !required.fma3)
+            && (self.avx2 || !required.avx2)
+            && (self.avx512f || !required.avx512f)
+    }
+}
+
+/// Detect the host's SIMD features via `is_x86_feature_detected!`.
+///
+/// Returns all-false on non-x86-64 targets, where GWNUM is unavailable.
+pub fn detect_features() -> GwFeatures {
+    #[cfg(target_arch = "x86_64")]
+    {
+        GwFeatures {
+            sse2: is_x86_feature_detected!("sse2"),
+            avx: is_x86_feature_detected!("avx"),
+            fma3: is_x86_feature_detected!("fma"),
+            avx2: is_x86_feature_detected!("avx2"),
+            avx512f: is_x86_feature_detected!("avx512f"),
+        }
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        GwFeatures::default()
+    }
+}
+
+/// Read the SIMD feature bits straight from CPUID and XCR0, bypassing the
+/// cache.
+///
+/// `is_x86_feature_detected!` memoizes its result for the life of the process,
+/// so it cannot notice a live-migration; CPUID and XGETBV reflect the current
+/// host. Like the std check, the AVX family also needs the OS to save its
+/// registers.
+#[cfg(target_arch = "x86_64")]
+fn probe_cpuid_features() -> GwFeatures {
+    use std::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv};
+
+    let leaf1 = __cpuid(1);
+    let max_leaf = __cpuid(0).eax;
+    let leaf7_ebx = if max_leaf >= 7 {
+        __cpuid_count(7, 0).ebx
+    } else {
+        0
+    };
+    let xcr0 = if leaf1.ecx & (1 << 27) != 0 {
+        // SAFETY: OSXSAVE is set, so XGETBV is supported and enabled.
+        unsafe { _xgetbv(0) }
+    } else {
+        0
+    };
+    // XMM|YMM state for AVX; opmask|ZMM_Hi256|Hi16_ZMM on top for AVX-512.
+    let ymm = xcr0 & 0b110 == 0b110;
+    let zmm = ymm && xcr0 & 0b1110_0000 == 0b1110_0000;
+    GwFeatures {
+        sse2: leaf1.edx & (1 << 26) != 0,
+        avx: ymm && leaf1.ecx & (1 << 28) != 0,
+        fma3: ymm && leaf1.ecx & (1 << 12) != 0,
+        avx2: ymm && leaf7_ebx & (1 << 5) != 0,
+        avx512f: zmm && leaf7_ebx & (1 << 16) != 0,
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn probe_cpuid_features() -> GwFeatures {
+    GwFeatures::default()
+}
+
 /// RAII wrapper for a GWNUM context (gwhandle).
 ///
 /// Thread-local only: `!Send`, `!Sync`. Each thread must create its own context.
@@ -73,10 +218,19 @@ pub struct GwContext {
     handle: Box<gwnum_sys::gwhandle>,
     #[cfg(feature = "gwnum")]
     _setup_done: bool,
+    /// CPU features present when `gwsetup` chose the FFT code path.
+    #[cfg(feature = "gwnum")]
+    features: GwFeatures,
+    /// Modulus parameters (k, b, n, c), kept so the context can be rebuilt.
+    #[cfg(feature = "gwnum")]
+    params: (u64, u32, u64, i64),
     /// Prevent Send and Sync — gwhandle is thread-local only
     _not_send: PhantomData<*mut ()>,
 }
 
+/// A set-up GWNUM handle, as returned by [`Gwnum::setup`].
+pub type Gwnum = GwContext;
+
 /// RAII wrapper for a gwnum value. Freed when dropped.
 pub struct GwNum {
     #[cfg(feature = "gwnum")]
@@ -106,12 +260,24 @@ impl GwContext {
     /// # Errors
     ///
     /// Returns `GwError::Unavailable` if GWNUM is not compiled in.
-    /// Returns `GwError::SetupFailed` if the parameters are invalid.
-    #[cfg(feature = "gwnum")]
+    /// Returns `GwError::SetupFailed` if [`Gwnum::setup`] fails.
     pub fn new(k: u64, b: u32, n: u64, c: i64) -> Result<Self, GwError> {
+        Ok(Self::setup(k, b, n, c)?)
+    }
+
+    /// Run `gwsetup` for k*b^n+c and check the handle it leaves behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GwSetupError::ReturnCode` if `gwsetup` returns nonzero, and
+    /// `GwSetupError::ErrorFlagged` if it returns 0 but `gw_test_for_error`
+    /// reports an error; the handle is torn down in both cases.
+    #[cfg(feature = "gwnum")]
+    pub fn setup(k: u64, b: u32, n: u64, c: i64) -> Result<Gwnum, GwSetupError> {
         use std::ffi::CString;
         use std::mem::MaybeUninit;
 
+        let features = probe_cpuid_features();
         let mut handle =
             Box::new(unsafe { MaybeUninit::<gwnum_sys::gwhandle>::zeroed().assume_init() });
 
@@ -129,19 +295,61 @@ impl GwContext {
 
         if ret != 0 {
             unsafe { gwnum_sys::gwdone(&mut *handle) };
-            return Err(GwError::SetupFailed { code: ret });
+            return Err(GwSetupError::ReturnCode(ret));
+        }
+
+        // gwsetup can return 0 yet leave GWERROR set when the exponent is past
+        // the largest FFT it supports; never hand out a handle in that state.
+        let err = unsafe { gwnum_sys::gw_check_error(&mut *handle) };
+        if err != gwnum_sys::GWERROR_NONE {
+            unsafe { gwnum_sys::gwdone(&mut *handle) };
+            return Err(GwSetupError::ErrorFlagged(err));
         }
 
         Ok(GwContext {
             handle,
             _setup_done: true,
+            features,
+            params: (k, b, n, c),
             _not_send: PhantomData,
         })
     }
 
     #[cfg(not(feature = "gwnum"))]
-    pub fn new(_k: u64, _b: u32, _n: u64, _c: i64) -> Result<Self, GwError> {
-        Err(GwError::Unavailable)
+    pub fn setup(_k: u64, _b: u32, _n: u64, _c: i64) -> Result<Gwnum, GwSetupError> {
+        Err(GwSetupError::Unavailable)
+    }
+
+    /// CPU features present when this context ran `gwsetup`.
+    #[cfg(feature = "gwnum")]
+    pub fn setup_features(&self) -> GwFeatures {
+        self.features
+    }
+
+    #[cfg(not(feature = "gwnum"))]
+    pub fn setup_features(&self) -> GwFeatures {
+        probe_cpuid_features()
+    }
+
+    /// True if the current CPU lacks a feature the FFT plan was built for.
+    pub fn features_changed(&self) -> bool {
+        !probe_cpuid_features().supports(&self.setup_features())
+    }
+
+    /// Tear down the handle and run `gwsetup` again for the current CPU.
+    ///
+    /// Every `GwNum` allocated from this context must be dropped first; callers
+    /// reload their state from the last GMP checkpoint afterwards.
+    #[cfg(feature = "gwnum")]
+    pub fn rebuild(self) -> Result<Self, GwError> {
+        let (k, b, n, c) = self.params;
+        drop(self);
+        Self::new(k, b, n, c)
+    }
+
+    #[cfg(not(feature = "gwnum"))]
+    pub fn rebuild(self) -> Result<Self, GwError> {
+        Ok(self)
     }
 
     /// Allocate a gwnum in this context.
@@ -390,12 +598,7 @@ pub fn vrba_reix_test(p: u64) -> Result<bool, GwError> {
 
     for i in 0..iters {
         if p > 50_000 && i % 10_000 == 0 && i > 0 {
-            eprintln!(
-                "  Vrba-Reix: {}/{} squarings ({:.1}%)",
-                i,
-                iters,
-                i as f64 / iters as f64 * 100.0
-            );
+            debug!(iteration = i, total = iters, "Vrba-Reix progress");
         }
 
         ctx.square(&s, &mut temp)?;
@@ -403,6 +606,24 @@ pub fn vrba_reix_test(p: u64) -> Result<bool, GwError> {
 
         // Gerbicz checkpoint every check_interval iterations
         if check_interval < iters && (i + 1) % check_interval == 0 {
+            // A migration off the FFT plan's instruction set invalidates every
+            // GWNUM value: rebuild the context and force the rollback below.
+            let migrated = ctx.features_changed();
+            if migrated {
+                warn!(
+                    iteration = i + 1,
+                    "Vrba-Reix: CPU features changed, rebuilding GWNUM context"
+                );
+                drop(s);
+                drop(temp);
+                drop(two);
+                ctx = ctx.rebuild()?;
+                s = ctx.alloc();
+                temp = ctx.alloc();
+                two = ctx.alloc();
+                ctx.set_small(&mut two, 2.0);
+            }
+
             // Extract current GWNUM value as GMP Integer
             let current_gmp = ctx.checkpoint_value(&s);
 
@@ -415,12 +636,12 @@ pub fn vrba_reix_test(p: u64) -> Result<bool, GwError> {
                 verify = verify.rem_euc(&n_val);
             }
 
-            if verify != current_gmp {
+            if migrated || verify != current_gmp {
                 // Hardware/FFT error detected — rollback to last verified checkpoint
-                eprintln!(
-                    "  Vrba-Reix ERROR at iteration {} — rolling back to {}",
-                    i + 1,
-                    verified_checkpoint_iter
+                warn!(
+                    iteration = i + 1,
+                    rollback_to = verified_checkpoint_iter,
+                    "Vrba-Reix error detected, rolling back"
                 );
 
                 // Reload verified checkpoint into GWNUM and replay
@@ -445,7 +666,7 @@ pub fn vrba_reix_test(p: u64) -> Result<bool, GwError> {
                             verified_checkpoint = last_checkpoint.clone();
                             verified_checkpoint_iter = j + 1;
                         } else {
-                            eprintln!("  Vrba-Reix: persistent error — aborting");
+                            warn!("Vrba-Reix persistent error, aborting");
                             return Err(GwError::GerbiczMismatch { iteration: j + 1 });
                         }
                     }
@@ -473,7 +694,7 @@ pub fn vrba_reix_test(p: u64) -> Result<bool, GwError> {
             verify = verify.rem_euc(&n_val);
         }
         if verify != 0u32 {
-            eprintln!("  Vrba-Reix: final verification FAILED — returning error");
+            warn!("Vrba-Reix prime verification failed, returning error");
             return Err(GwError::GerbiczMismatch { iteration: iters });
         }
     }
@@ -578,12 +799,7 @@ pub fn gwnum_llr(k: u64, n: u64) -> Result<Option<bool>, GwError> {
 
     for i in 0..iters {
         if n > 50_000 && i % 10_000 == 0 && i > 0 {
-            eprintln!(
-                "  GWNUM LLR: {}/{} squarings ({:.1}%)",
-                i,
-                iters,
-                i as f64 / iters as f64 * 100.0
-            );
+            debug!(iteration = i, total = iters, "GWNUM LLR progress");
         }
 
         ctx.square(&u, &mut temp)?;
@@ -591,6 +807,24 @@ pub fn gwnum_llr(k: u64, n: u64) -> Result<Option<bool>, GwError> {
 
         // Gerbicz checkpoint every check_interval iterations
         if check_interval < iters && (i + 1) % check_interval == 0 {
+            // A migration off the FFT plan's instruction set invalidates every
+            // GWNUM value: rebuild the context and force the rollback below.
+            let migrated = ctx.features_changed();
+            if migrated {
+                warn!(
+                    iteration = i + 1,
+                    "GWNUM LLR: CPU features changed, rebuilding GWNUM context"
+                );
+                drop(u);
+                drop(temp);
+                drop(two);
+                ctx = ctx.rebuild()?;
+                u = ctx.alloc();
+                temp = ctx.alloc();
+                two = ctx.alloc();
+                ctx.set_small(&mut two, 2.0);
+            }
+
             let current_gmp = ctx.checkpoint_value(&u);
 
             // Recompute this block from last_checkpoint using GMP (correctness oracle)
@@ -602,12 +836,12 @@ pub fn gwnum_llr(k: u64, n: u64) -> Result<Option<bool>, GwError> {
                 verify = verify.rem_euc(&candidate);
             }
 
-            if verify != current_gmp {
+            if migrated || verify != current_gmp {
                 // Hardware/FFT error detected — rollback to last verified checkpoint
-                eprintln!(
-                    "  GWNUM LLR ERROR at iteration {} — rolling back to {}",
-                    i + 1,
-                    verified_checkpoint_iter
+                warn!(
+                    iteration = i + 1,
+                    rollback_to = verified_checkpoint_iter,
+                    "GWNUM LLR error detected, rolling back"
                 );
 
                 // Reload verified checkpoint into GWNUM and replay
@@ -631,7 +865,7 @@ pub fn gwnum_llr(k: u64, n: u64) -> Result<Option<bool>, GwError> {
                             verified_checkpoint = last_checkpoint.clone();
                             verified_checkpoint_iter = j + 1;
                         } else {
-                            eprintln!("  GWNUM LLR: persistent error — aborting");
+                            warn!("GWNUM LLR persistent error, aborting");
                             return Err(GwError::GerbiczMismatch { iteration: j + 1 });
                         }
                     }
@@ -658,7 +892,7 @@ pub fn gwnum_llr(k: u64, n: u64) -> Result<Option<bool>, GwError> {
             verify = verify.rem_euc(&candidate);
         }
         if verify != 0u32 {
-            eprintln!("  GWNUM LLR: final verification FAILED — returning error");
+            warn!("GWNUM LLR prime verification failed, returning error");
             return Err(GwError::GerbiczMismatch { iteration: iters });
         }
     }
@@ -721,9 +955,63 @@ mod tests {
     //! - Vrba-Reix test against known Wagstaff primes (p=3,5,7,11,13) and composites
     //! - GWNUM Proth test for 3*2^50000+1 (known prime)
     //! - Cross-verification of GWNUM Proth against GMP Miller-Rabin
+    //!
+    //! ## CPU Feature Detection
+    //!
+    //! Feature detection runs without gwnum.a, so it is tested on every host:
+    //! the detected set must be internally consistent and covered by a fresh
+    //! CPUID probe, otherwise contexts would rebuild on every checkpoint.
 
     use super::*;
 
+    // ── CPU Feature Detection ──────────────────────────────────────
+
+    /// On the build host, detection must produce a consistent feature set:
+    /// SSE2 is part of the x86-64 baseline ABI, and each wider extension
+    /// implies the narrower ones GWNUM's code paths are layered on.
+    #[test]
+    fn detect_features_plausible_on_build_host() {
+        let f = detect_features();
+        #[cfg(target_arch = "x86_64")]
+        {
+            assert!(f.sse2, "SSE2 is mandatory on x86-64");
+            assert_ne!(f.fft_path(), "none");
+        }
+        if f.avx512f {
+            assert!(f.avx2, "AVX-512F host without AVX2");
+        }
+        if f.avx2 || f.fma3 {
+            assert!(f.avx, "AVX2/FMA3 reported without AVX");
+        }
+    }
+
+    /// The uncached probe agrees with std's detection on a host that has
+    /// not moved, so a context never rebuilds without a migration.
+    #[test]
+    fn cpuid_probe_covers_detected_features() {
+        assert!(probe_cpuid_features().supports(&detect_features()));
+    }
+
+    /// Losing AVX-512 invalidates a plan built for it; gaining it does not.
+    #[test]
+    fn supports_flags_only_lost_features() {
+        let avx2_host = GwFeatures {
+            sse2: true,
+            avx: true,
+            fma3: true,
+            avx2: true,
+            avx512f: false,
+        };
+        let avx512_host = GwFeatures {
+            avx512f: true,
+            ..avx2_host
+        };
+        assert!(!avx2_host.supports(&avx512_host));
+        assert!(avx512_host.supports(&avx2_host));
+        assert_eq!(avx512_host.fft_path(), "avx512");
+        assert_eq!(avx2_host.fft_path(), "fma3");
+    }
+
     // ── Feature-Gate Fallbacks ───────────────────────────────────────
 
     /// Without the gwnum feature, GwContext::new must return Unavailable.
@@ -736,9 +1024,30 @@ mod tests {
         {
             let result = GwContext::new(3, 2, 50000, 1);
             assert!(matches!(result, Err(GwError::Unavailable)));
+            assert!(matches!(
+                Gwnum::setup(3, 2, 50000, 1),
+                Err(GwSetupError::Unavailable)
+            ));
         }
     }
 
+    /// An exponent far beyond any FFT length GWNUM supports must come back as
+    /// a `GwSetupError` instead of a half-initialised handle, and as
+    /// `SetupFailed` through the tests the kbn engine falls back from.
+    #[test]
+    #[cfg(feature = "gwnum")]
+    fn gw_context_absurd_exponent_is_setup_error() {
+        let result = Gwnum::setup(3, 2, u32::MAX as u64, -1);
+        assert!(matches!(
+            result,
+            Err(GwSetupError::ReturnCode(_) | GwSetupError::ErrorFlagged(_))
+        ));
+        assert!(matches!(
+            gwnum_llr(3, u32::MAX as u64),
+            Err(GwError::SetupFailed { .. })
+        ));
+    }
+
     // ── Parameter Validation ───────────────────────────────────────
 
     /// The Vrba-Reix test requires p >= 3 (the algorithm performs p-2
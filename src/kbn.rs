@@ -14,7 +14,9 @@
 //! 2. **Proth test** (`proth_test`): For k·b^n + 1 with k < b^n and base 2,
 //!    applies Proth's theorem (1878) — a single modular exponentiation yields a
 //!    *deterministic* proof of primality. For non-base-2, generalizes via
-//!    Pocklington's theorem.
+//!    Pocklington's theorem. The witness is a quadratic nonresidue picked by
+//!    Jacobi symbol; `search` keeps a `ProthWitnessCache` of recent
+//!    witnesses, tried first since consecutive n often share one.
 //!
 //! 3. **LLR test** (`llr_test`): For k·2^n − 1 with k odd and k < 2^n, applies
 //!    the Lucas–Lehmer–Riesel test — n−2 modular squarings yield a deterministic
@@ -24,17 +26,47 @@
 //!    catches 1–5% of composites that survive the algebraic sieve.
 //!
 //! 5. **GWNUM/PRST acceleration**: For very large candidates (>10K digits),
-//!    delegates to external GWNUM or PRST tools for 50–100× speedup.
+//!    delegates to external GWNUM or PRST tools for 50–100× speedup. If
+//!    `gwsetup` rejects the modulus (e.g. an exponent past GWNUM's FFT range),
+//!    a single warning is logged and testing continues with the rug paths.
 //!
 //! 6. **Miller–Rabin fallback**: 25-round MR with 2-round pre-screen for
 //!    candidates where no deterministic test applies.
 //!
+//! ## Pipelined Sieve
+//!
+//! With `KBN_PIPELINE_SEGMENTS=N` (N ≥ 2), `search` splits the n range into
+//! N segments and sieves them on a separate thread, handing each segment's
+//! survivors to the rayon testers through a bounded channel. Testing starts
+//! after the first segment rather than the whole range, at the price of
+//! repeating the discrete logs per segment.
+//!
+//! ## Fixed-n Mode
+//!
+//! `search_k_range` holds n fixed and sweeps k instead (Sierpiński/Riesel
+//! style). Its sieve (`k_sieve`) needs no discrete log: k·b^n ± 1 ≡ 0 (mod p)
+//! pins k to a single residue ∓b^{−n} mod p, then stages 2–6 run unchanged.
+//!
+//! ## Arbitrary Offsets
+//!
+//! `search_c` covers k·b^n + c for any fixed nonzero c. Its sieve
+//! (`bsgs_sieve_c`) solves b^n ≡ −c·k^{−1} (mod p); for c ≠ ±1 the Proth and
+//! LLR proofs no longer apply, so survivors get a BPSW+MR verdict instead.
+//!
 //! ## Key Functions (pub(crate))
 //!
 //! - `proth_test`, `llr_test`, `bsgs_sieve`, `test_prime` — reused by `twin`,
 //!   `sophie_germain`, `cullen_woodall`, `carol_kynea`, and `gen_fermat`.
 //! - `find_rodseth_v1`, `lucas_v_k` — LLR starting value computation.
 //!
+//! `normalize_k` (public) factors base powers out of k before `search`, so
+//! 6·2^n ± 1 is searched, and recorded, as 3·2^(n+1) ± 1.
+//!
+//! `diagnose_sieve` (public, behind `darkreach diagnose sieve`) re-tests every
+//! candidate the sieve eliminates and reports any that are not composite.
+//! `verify_covering_set` checks a covering set proving a Sierpiński or Riesel
+//! k can never yield a prime, so the k can be pruned from fixed-n sweeps.
+//!
 //! ## Complexity
 //!
 //! - Sieve: O(π(L) · √p̄) where L is sieve limit and p̄ is mean sieve prime.
@@ -57,20 +89,82 @@ use rug::integer::IsPrime;
 use rug::ops::{Pow, RemRounding};
 use rug::Integer;
 use std::path::Path;
-use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use tracing::{debug, info, warn};
 
+use crate::candidate::{CandidateForm, Kbn};
 use crate::certificate::PrimalityCertificate;
-use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
+use crate::checkpoint::{self, Checkpoint, SieveState};
 use crate::events::{self, EventBus};
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::CoordinationClient;
 use crate::{exact_digits, sieve};
 
+/// Bases [`proth_test`] tries, after any cached ones.
+const PROTH_BASES: [u32; 6] = [2, 3, 5, 7, 11, 13];
+
+/// [`ProthWitnessCache`] size when `PROTH_WITNESS_CACHE` is unset.
+pub(crate) const DEFAULT_PROTH_WITNESS_CACHE: usize = 4;
+
+/// Recently successful Proth witnesses for one (k, base) sweep, most recent
+/// first.
+///
+/// Whether a base is a nonresidue of k·2^n + 1 depends on n only through a
+/// few small residues, so within a block the last witness usually works
+/// again. Trying cached bases first saves Jacobi evaluations; the result is
+/// the same either way.
+pub(crate) struct ProthWitnessCache {
+    capacity: usize,
+    recent: Mutex<Vec<u32>>,
+    jacobi_evals: AtomicU64,
+    hits: AtomicU64,
+}
+
+impl ProthWitnessCache {
+    /// Cache holding up to `capacity` witnesses; 0 disables it.
+    pub(crate) fn new(capacity: usize) -> Self {
+        ProthWitnessCache {
+            capacity,
+            recent: Mutex::new(Vec::with_capacity(capacity)),
+            jacobi_evals: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Cache sized by `PROTH_WITNESS_CACHE`.
+    pub(crate) fn from_env() -> Self {
+        let capacity = std::env::var("PROTH_WITNESS_CACHE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_PROTH_WITNESS_CACHE);
+        ProthWitnessCache::new(capacity)
+    }
+
+    fn record(&self, a: u32) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut recent = self.recent.lock().unwrap();
+        recent.retain(|&b| b != a);
+        recent.insert(0, a);
+        recent.truncate(self.capacity);
+    }
+
+    /// Jacobi symbols evaluated by tests using this cache.
+    pub(crate) fn jacobi_evals(&self) -> u64 {
+        self.jacobi_evals.load(Ordering::Relaxed)
+    }
+
+    /// Tests decided by a cached base.
+    pub(crate) fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+}
+
 /// Proth primality test for p = k*2^n + 1 where k < 2^n.
 ///
 /// By Proth's theorem, if there exists an integer a such that
@@ -80,25 +174,48 @@ use crate::{exact_digits, sieve};
 /// Returns `Some((true, Some(a)))` for deterministic prime (with witness base `a`),
 /// `Some((false, None))` for composite, `None` if inconclusive.
 pub(crate) fn proth_test(p: &Integer) -> Option<(bool, Option<u32>)> {
+    proth_test_cached(p, None)
+}
+
+/// [`proth_test`] trying the bases in `witnesses` first.
+///
+/// Only a base with Jacobi(a, p) = −1 is exponentiated: for prime p its
+/// power is then −1 by Euler's criterion, so any other value proves p
+/// composite and one exponentiation decides the test.
+pub(crate) fn proth_test_cached(
+    p: &Integer,
+    witnesses: Option<&ProthWitnessCache>,
+) -> Option<(bool, Option<u32>)> {
+    if p.is_even() || *p < 3u32 {
+        return None;
+    }
     let p_minus_1 = Integer::from(p - 1u32);
     let exp = Integer::from(&p_minus_1 >> 1u32); // (p-1)/2
 
-    for &a in &[2u32, 3, 5, 7, 11, 13] {
+    let cached = witnesses.map_or_else(Vec::new, |w| w.recent.lock().unwrap().clone());
+    let fallback = PROTH_BASES.into_iter().filter(|a| !cached.contains(a));
+    for (i, a) in cached.iter().copied().chain(fallback).enumerate() {
         if p.is_divisible_u(a) {
             continue; // Skip bases divisible by p (only relevant for tiny p)
         }
-        match Integer::from(a).pow_mod(&exp, p) {
-            Ok(result) => {
-                if result == p_minus_1 {
-                    return Some((true, Some(a))); // Deterministically prime
-                }
-                if result != 1u32 {
-                    return Some((false, None)); // Failed Fermat test → composite
+        if let Some(w) = witnesses {
+            w.jacobi_evals.fetch_add(1, Ordering::Relaxed);
+        }
+        if Integer::from(a).jacobi(p) != -1 {
+            continue; // quadratic residue (or p composite), try next base
+        }
+        if let Some(w) = witnesses.filter(|_| i < cached.len()) {
+            w.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        return match Integer::from(a).pow_mod(&exp, p) {
+            Ok(result) if result == p_minus_1 => {
+                if let Some(w) = witnesses {
+                    w.record(a);
                 }
-                // result == 1: quadratic residue, try next base
+                Some((true, Some(a))) // Deterministically prime
             }
-            Err(_) => return Some((false, None)), // gcd(a, p) > 1 → composite
-        }
+            _ => Some((false, None)), // Euler's criterion fails → composite
+        };
     }
     None // All bases inconclusive
 }
@@ -167,6 +284,21 @@ fn small_prime_factors(mut n: u32) -> Vec<u32> {
     factors
 }
 
+/// Distinct prime factors of k·b^n for any n ≥ 1, i.e. those of k and b.
+///
+/// This is the complete factorization of N−1 for k·b^n + 1 and of N+1 for
+/// k·b^n − 1, as needed by Pocklington and Morrison proofs.
+pub(crate) fn kb_prime_factors(k: u64, base: u32) -> Vec<Integer> {
+    let mut primes: Vec<u64> = sieve::factor_u64(k)
+        .into_iter()
+        .chain(sieve::factor_u64(base as u64))
+        .map(|(q, _)| q)
+        .collect();
+    primes.sort_unstable();
+    primes.dedup();
+    primes.into_iter().map(Integer::from).collect()
+}
+
 /// Find a suitable starting value P for LLR when k is divisible by 3.
 ///
 /// When k % 3 == 0, the default P=4 fails because jacobi(P-2, N) and
@@ -373,6 +505,25 @@ pub(crate) fn llr_test(candidate: &Integer, k: u64, n: u64) -> Option<(bool, Opt
     }
 }
 
+/// Set once the first GWNUM failure has been logged, so a search over many
+/// oversized candidates reports the fallback once instead of per candidate.
+#[cfg(feature = "gwnum")]
+static GWNUM_FALLBACK_LOGGED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Log (once per process) that GWNUM could not be used and the rug-based
+/// tests are taking over. A `SetupFailed` here usually means the exponent is
+/// beyond GWNUM's supported FFT range.
+#[cfg(feature = "gwnum")]
+fn note_gwnum_fallback(err: &crate::gwnum::GwError) {
+    if !GWNUM_FALLBACK_LOGGED.swap(true, Ordering::Relaxed) {
+        warn!(
+            error = %err,
+            "GWNUM unavailable for this candidate, falling back to rug-based testing"
+        );
+    }
+}
+
 /// Test primality using the best available method.
 /// Uses Proth/Pocklington for k*b^n+1 when applicable, falls back to Miller-Rabin.
 /// Returns `(IsPrime, proof_method_label, Option<PrimalityCertificate>)`.
@@ -383,6 +534,19 @@ pub(crate) fn test_prime(
     n: u64,
     is_plus: bool,
     mr_rounds: u32,
+) -> (IsPrime, &'static str, Option<PrimalityCertificate>) {
+    test_prime_cached(candidate, k, base, n, is_plus, mr_rounds, None)
+}
+
+/// [`test_prime`] with a Proth witness cache for a sweep over n.
+pub(crate) fn test_prime_cached(
+    candidate: &Integer,
+    k: u64,
+    base: u32,
+    n: u64,
+    is_plus: bool,
+    mr_rounds: u32,
+    witnesses: Option<&ProthWitnessCache>,
 ) -> (IsPrime, &'static str, Option<PrimalityCertificate>) {
     // Proth/Pocklington only applies to +1 form where k < b^n
     let can_use_n1_test = is_plus && {
@@ -395,7 +559,7 @@ pub(crate) fn test_prime(
 
     if can_use_n1_test {
         let result = if base == 2 {
-            proth_test(candidate)
+            proth_test_cached(candidate, witnesses)
         } else {
             pocklington_test(candidate, base)
         };
@@ -439,7 +603,7 @@ pub(crate) fn test_prime(
 
     // Adaptive P-1 composite pre-filter — auto-tunes B1/B2 by candidate size,
     // uses Stage 1 + Stage 2 to catch composites with one partially-smooth factor.
-    if crate::p1::adaptive_p1_filter(candidate) {
+    if crate::p1::adaptive_p1_filter_for_form(candidate, "kbn") {
         return (IsPrime::No, "", None);
     }
 
@@ -452,13 +616,15 @@ pub(crate) fn test_prime(
                 match crate::gwnum::gwnum_proth(k, base, n) {
                     Ok(Some(true)) => return (IsPrime::Yes, "deterministic", None),
                     Ok(Some(false)) => return (IsPrime::No, "", None),
-                    Ok(None) | Err(_) => {} // fall through
+                    Ok(None) => {} // fall through
+                    Err(e) => note_gwnum_fallback(&e),
                 }
             } else if !is_plus && base == 2 && k % 2 == 1 {
                 match crate::gwnum::gwnum_llr(k, n) {
                     Ok(Some(true)) => return (IsPrime::Yes, "deterministic", None),
                     Ok(Some(false)) => return (IsPrime::No, "", None),
-                    Ok(None) | Err(_) => {} // fall through
+                    Ok(None) => {} // fall through
+                    Err(e) => note_gwnum_fallback(&e),
                 }
             }
         }
@@ -502,9 +668,28 @@ pub(crate) fn test_prime(
     (r, cert_label, cert)
 }
 
+/// Sieve primes processed between checks of the coordinator stop flag.
+const STOP_CHECK_PRIMES: usize = 4096;
+
+#[cfg(test)]
+thread_local! {
+    /// BSGS sieve passes started on this thread, so tests can count them.
+    static SIEVE_PASSES: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+}
+
 /// BSGS-based sieve: for each sieve prime, compute the discrete log to find
 /// all n-values where k*b^n ≡ ∓1 (mod p), then mark them as composite.
-/// Returns (plus_survives, minus_survives) bitmaps indexed by (n - min_n).
+/// Returns (plus_survives, minus_survives, interrupted) with bitmaps indexed
+/// by (n - min_n).
+///
+/// The base need not be prime or a prime power: the order of b is computed
+/// separately modulo each sieve prime, and primes dividing b (2 and 3 for
+/// b = 6) are skipped because k*b^n ± 1 ≡ ±1 modulo them.
+///
+/// With a `stop` client, the stop flag is polled every [`STOP_CHECK_PRIMES`]
+/// primes; on a stop request the sieve returns early with `interrupted` set.
+/// The partial bitmaps are still sound (survivors are a superset of the full
+/// sieve's), but callers exit rather than test from them.
 pub(crate) fn bsgs_sieve(
     min_n: u64,
     max_n: u64,
@@ -512,7 +697,10 @@ pub(crate) fn bsgs_sieve(
     base: u32,
     sieve_primes: &[u64],
     sieve_min_n: u64,
-) -> (sieve::BitSieve, sieve::BitSieve) {
+    stop: Option<&dyn CoordinationClient>,
+) -> (sieve::BitSieve, sieve::BitSieve, bool) {
+    #[cfg(test)]
+    SIEVE_PASSES.with(|p| p.set(p.get() + 1));
     let range = (max_n - min_n + 1) as usize;
     let mut plus_survives = sieve::BitSieve::new_all_set(range);
     let mut minus_survives = sieve::BitSieve::new_all_set(range);
@@ -522,6 +710,14 @@ pub(crate) fn bsgs_sieve(
     let log_interval = (total_primes / 20).max(1); // every 5%
 
     for (pi, &p) in sieve_primes.iter().enumerate() {
+        if pi % STOP_CHECK_PRIMES == 0 && stop.is_some_and(|wc| wc.is_stop_requested()) {
+            info!(
+                processed = pi,
+                total = total_primes,
+                "Stop requested during BSGS sieve"
+            );
+            return (plus_survives, minus_survives, true);
+        }
         if pi % log_interval == 0 && pi > 0 {
             debug!(
                 current = pi,
@@ -564,38 +760,1362 @@ pub(crate) fn bsgs_sieve(
                 n += order;
             }
         }
-
-        // -1 form: k*b^n - 1 ≡ 0 (mod p) → b^n ≡ k^{-1} (mod p)
-        if let Some(n0) = sieve::discrete_log_bsgs(base_u64, k_inv, p, order) {
-            let first = if n0 >= min_n {
-                n0
-            } else if order == 0 {
-                continue;
-            } else {
-                let gap = min_n - n0;
-                let steps = gap.div_ceil(order);
-                n0 + steps * order
-            };
-            let mut n = first;
-            while n <= max_n {
-                if n >= sieve_min_n {
-                    minus_survives.clear((n - min_n) as usize);
-                }
-                n += order;
+
+        // -1 form: k*b^n - 1 ≡ 0 (mod p) → b^n ≡ k^{-1} (mod p)
+        if let Some(n0) = sieve::discrete_log_bsgs(base_u64, k_inv, p, order) {
+            let first = if n0 >= min_n {
+                n0
+            } else if order == 0 {
+                continue;
+            } else {
+                let gap = min_n - n0;
+                let steps = gap.div_ceil(order);
+                n0 + steps * order
+            };
+            let mut n = first;
+            while n <= max_n {
+                if n >= sieve_min_n {
+                    minus_survives.clear((n - min_n) as usize);
+                }
+                n += order;
+            }
+        }
+    }
+
+    (plus_survives, minus_survives, false)
+}
+
+/// Survivor bitmaps from [`bsgs_sieve_resumable`]; bit i is n = `base_n` + i.
+pub(crate) struct KbnSieve {
+    pub(crate) plus: sieve::BitSieve,
+    pub(crate) minus: sieve::BitSieve,
+    pub(crate) base_n: u64,
+    /// Largest prime bound applied (the saved limit if it was higher).
+    pub(crate) sieve_limit: u64,
+    /// Primes this call actually sieved with.
+    pub(crate) primes_sieved: usize,
+}
+
+impl KbnSieve {
+    /// Checkpoint form of the bitmaps.
+    pub(crate) fn to_state(&self) -> SieveState {
+        SieveState {
+            sieve_limit: self.sieve_limit,
+            min_n: self.base_n,
+            len: self.plus.len(),
+            plus: self.plus.to_base64(),
+            minus: self.minus.to_base64(),
+        }
+    }
+}
+
+/// [`bsgs_sieve`] over [min_n, max_n], reusing checkpointed bitmaps.
+///
+/// A `saved` state that covers the range (starting at or before `min_n` and
+/// ending at `max_n`) already accounts for every prime up to its limit, so
+/// only the primes above it are sieved — into a fresh bitmap ANDed onto the
+/// loaded one. An equal or lower `sieve_limit` sieves nothing. Without a
+/// usable state the whole range is sieved from scratch. The bool is true if
+/// a stop was requested mid-sieve.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn bsgs_sieve_resumable(
+    saved: Option<&SieveState>,
+    min_n: u64,
+    max_n: u64,
+    k: u64,
+    base: u32,
+    sieve_primes: &[u64],
+    sieve_limit: u64,
+    sieve_min_n: u64,
+    stop: Option<&dyn CoordinationClient>,
+) -> (KbnSieve, bool) {
+    let loaded = saved
+        .filter(|st| st.min_n <= min_n && st.min_n + st.len as u64 == max_n + 1)
+        .and_then(|st| {
+            let plus = sieve::BitSieve::from_base64(&st.plus, st.len)?;
+            let minus = sieve::BitSieve::from_base64(&st.minus, st.len)?;
+            Some((st, plus, minus))
+        });
+    let Some((st, mut plus, mut minus)) = loaded else {
+        let (plus, minus, interrupted) =
+            bsgs_sieve(min_n, max_n, k, base, sieve_primes, sieve_min_n, stop);
+        let sieve = KbnSieve {
+            plus,
+            minus,
+            base_n: min_n,
+            sieve_limit,
+            primes_sieved: sieve_primes.len(),
+        };
+        return (sieve, interrupted);
+    };
+
+    let new_primes = &sieve_primes[sieve_primes.partition_point(|&p| p <= st.sieve_limit)..];
+    let mut interrupted = false;
+    if !new_primes.is_empty() {
+        let (extra_plus, extra_minus, stopped) =
+            bsgs_sieve(st.min_n, max_n, k, base, new_primes, sieve_min_n, stop);
+        plus.intersect(&extra_plus);
+        minus.intersect(&extra_minus);
+        interrupted = stopped;
+    }
+    let sieve = KbnSieve {
+        plus,
+        minus,
+        base_n: st.min_n,
+        sieve_limit: sieve_limit.max(st.sieve_limit),
+        primes_sieved: new_primes.len(),
+    };
+    (sieve, interrupted)
+}
+
+/// Minimum n where k*b^n > sieve_limit, so a sieve hit is a proper factor.
+fn sieve_min_n(k: u64, base: u32, sieve_limit: u64) -> u64 {
+    if base < 2 {
+        return u64::MAX;
+    }
+    let log_b = (base as f64).log10();
+    let log_limit = (sieve_limit as f64).log10();
+    ((log_limit - (k as f64).log10().max(0.0)) / log_b).ceil() as u64 + 1
+}
+
+/// A candidate k*b^n ± 1 that the sieve cleared but that is not composite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FalseElimination {
+    pub n: u64,
+    /// True for k*b^n − 1, false for k*b^n + 1.
+    pub minus: bool,
+}
+
+/// Outcome of [`diagnose_sieve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SieveDiagnosis {
+    /// Eliminated candidates re-tested (both signs).
+    pub checked: usize,
+    pub false_eliminations: Vec<FalseElimination>,
+}
+
+/// Sieve self-test: run [`bsgs_sieve`] over [min_n, max_n] with primes up to
+/// `sieve_limit` and confirm with [`crate::mr_screened_test`] that every
+/// eliminated candidate is composite. Meant for small ranges; the cost is
+/// one MR test per eliminated n.
+pub fn diagnose_sieve(
+    k: u64,
+    base: u32,
+    min_n: u64,
+    max_n: u64,
+    sieve_limit: u64,
+) -> SieveDiagnosis {
+    let primes = sieve::generate_primes(sieve_limit);
+    let sieve_min_n = sieve_min_n(k, base, sieve_limit);
+    let (plus, minus, _) = bsgs_sieve(min_n, max_n, k, base, &primes, sieve_min_n, None);
+    check_eliminations(k, base, min_n, &plus, &minus)
+}
+
+/// Re-test every cleared bit of bitmaps indexed by (n − min_n).
+pub(crate) fn check_eliminations(
+    k: u64,
+    base: u32,
+    min_n: u64,
+    plus: &sieve::BitSieve,
+    minus: &sieve::BitSieve,
+) -> SieveDiagnosis {
+    let mut checked = 0;
+    let mut false_eliminations = Vec::new();
+    for idx in 0..plus.len() {
+        let n = min_n + idx as u64;
+        let kb = Integer::from(k) * Integer::from(base).pow(crate::checked_u32(n));
+        for (is_minus, bits) in [(false, plus), (true, minus)] {
+            if bits.get(idx) {
+                continue;
+            }
+            checked += 1;
+            let candidate = if is_minus {
+                Integer::from(&kb - 1u32)
+            } else {
+                Integer::from(&kb + 1u32)
+            };
+            if crate::mr_screened_test(&candidate, 25) != IsPrime::No {
+                let minus = is_minus;
+                false_eliminations.push(FalseElimination { n, minus });
+            }
+        }
+    }
+    SieveDiagnosis {
+        checked,
+        false_eliminations,
+    }
+}
+
+/// True if p divides k·2^offset + `sign`.
+///
+/// Works mod p throughout: k·2^offset is reduced before `sign` is added, since
+/// the unreduced product of two residues exceeds i128 once p > 2^63.
+fn covering_member_divides(k: u64, sign: i64, p: u64, offset: u64) -> bool {
+    let p128 = p as u128;
+    let term = (k as u128 % p128) * sieve::pow_mod(2, offset, p) as u128 % p128;
+    let sign = (sign as i128).rem_euclid(p as i128) as u128;
+    (term + sign).is_multiple_of(p128)
+}
+
+/// Largest lcm of periods [`verify_covering_set`] will enumerate.
+pub const COVERING_LCM_LIMIT: u64 = 1 << 24;
+
+/// Check a covering set proving that k·2^n + `sign` (`sign` = +1 for
+/// Sierpiński, −1 for Riesel) is composite for every n ≥ 0.
+///
+/// Each `(p, period, offset)` claims p | k·2^n + sign whenever
+/// n ≡ offset (mod period). A claim holds when it holds at n = offset and
+/// 2^period ≡ 1 (mod p); the set covers when every n mod lcm(periods) falls
+/// in some claimed class. Returns false for an invalid claim, an uncovered
+/// class, a zero period, or an lcm above [`COVERING_LCM_LIMIT`]. For tiny k a
+/// member can equal a term k·2^n + sign, which is then prime, so check the
+/// first few n directly before pruning such a k.
+pub fn verify_covering_set(k: u64, sign: i64, covering: &[(u64, u64, u64)]) -> bool {
+    let mut lcm = 1u64;
+    for &(p, period, offset) in covering {
+        if p < 2 || period == 0 {
+            return false;
+        }
+        if !covering_member_divides(k, sign, p, offset) {
+            return false;
+        }
+        if sieve::pow_mod(2, period, p) != 1 % p {
+            return false;
+        }
+        let g = {
+            let (mut a, mut b) = (lcm, period);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        };
+        match (lcm / g).checked_mul(period) {
+            Some(l) if l <= COVERING_LCM_LIMIT => lcm = l,
+            _ => return false,
+        }
+    }
+
+    let mut covered = vec![false; lcm as usize];
+    for &(_, period, offset) in covering {
+        let mut n = offset % period;
+        while n < lcm {
+            covered[n as usize] = true;
+            n += period;
+        }
+    }
+    covered.iter().all(|&c| c)
+}
+
+/// Sieved segments the pipelined [`search`] buffers ahead of the testers.
+const PIPELINE_DEPTH: usize = 2;
+
+/// Segment count for the pipelined [`search`], from `KBN_PIPELINE_SEGMENTS`.
+/// Unset, unparsable or below 2 sieves the whole range before testing.
+fn pipeline_segments() -> u64 {
+    std::env::var("KBN_PIPELINE_SEGMENTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1)
+}
+
+/// One test block's sieve survivors: (n, test k·b^n + 1, test k·b^n − 1).
+type BlockSurvivors = Vec<(u64, bool, bool)>;
+
+/// A prime found in a block: expression, digits, proof label and
+/// certificate JSON.
+type FoundPrime = (String, u64, String, Option<String>);
+
+/// Survivors in [block_start, block_end] of bitmaps whose bit 0 is `base_n`.
+fn block_survivors(
+    plus: &sieve::BitSieve,
+    minus: &sieve::BitSieve,
+    base_n: u64,
+    block_start: u64,
+    block_end: u64,
+) -> BlockSurvivors {
+    (block_start..=block_end)
+        .filter_map(|n| {
+            let idx = (n - base_n) as usize;
+            let tp = plus.get(idx);
+            let tm = minus.get(idx);
+            if tp || tm {
+                Some((n, tp, tm))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The block loop of [`search`], shared by the sequential and pipelined
+/// paths: tests each block's survivors on rayon, records the primes, and
+/// checkpoints every minute and on a coordinator stop.
+struct BlockTester<'a> {
+    k: u64,
+    base: u32,
+    min_n: u64,
+    max_n: u64,
+    progress: &'a Arc<Progress>,
+    db: &'a dyn PrimeSink,
+    rt: &'a tokio::runtime::Handle,
+    checkpoint_path: &'a Path,
+    search_params: &'a str,
+    mr_rounds: u32,
+    worker_client: Option<&'a dyn CoordinationClient>,
+    event_bus: Option<&'a EventBus>,
+    buffer: PrimeBuffer<'a>,
+    witnesses: ProthWitnessCache,
+    last_checkpoint: Instant,
+    total_sieved: u64,
+}
+
+impl BlockTester<'_> {
+    /// Test one block. `sieve` is the full-range state saved with each
+    /// checkpoint, if there is one. Returns true when the coordinator asked
+    /// to stop; the checkpoint then points past this block.
+    fn run_block(
+        &mut self,
+        block_start: u64,
+        block_end: u64,
+        survivors: BlockSurvivors,
+        sieve: Option<&SieveState>,
+    ) -> Result<bool> {
+        let (k, base) = (self.k, self.base);
+        let block_len = block_end - block_start + 1;
+        *self.progress.current.lock().unwrap() =
+            format!("{}*{}^[{}..{}]+-1", k, base, block_start, block_end);
+
+        self.total_sieved += block_len - survivors.len() as u64;
+        self.progress
+            .record_survivors(survivors.iter().map(|s| s.0));
+        let block_tests: u64 = survivors
+            .iter()
+            .map(|&(_, tp, tm)| u64::from(tp) + u64::from(tm))
+            .sum();
+
+        let found_primes = test_block(
+            k,
+            base,
+            block_start,
+            survivors,
+            self.mr_rounds,
+            &self.witnesses,
+            self.progress,
+        );
+
+        self.progress
+            .tested
+            .fetch_add(block_len * 2, Ordering::Relaxed);
+        self.progress.advance_eta(block_end + 1, block_tests);
+
+        for (expr, digits, certainty, cert_json) in found_primes {
+            self.record(expr, digits, certainty, cert_json)?;
+        }
+
+        self.buffer.flush_if_due()?;
+
+        if self.last_checkpoint.elapsed().as_secs() >= 60 {
+            self.save_checkpoint(block_end, sieve)?;
+            info!(
+                n = block_end,
+                sieved_out = self.total_sieved,
+                "Checkpoint saved"
+            );
+            self.last_checkpoint = Instant::now();
+        }
+
+        if self.worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            self.save_checkpoint(block_end, sieve)?;
+            info!(
+                n = block_end,
+                "Stop requested by coordinator, checkpoint saved"
+            );
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn record(
+        &mut self,
+        expr: String,
+        digits: u64,
+        certainty: String,
+        cert_json: Option<String>,
+    ) -> Result<()> {
+        let (certainty, cert_json) =
+            crate::prove_on_find("kbn", &expr, digits, certainty, cert_json);
+        let (db, rt) = (self.db, self.rt);
+        if crate::defer_unproven(
+            db,
+            rt,
+            self.event_bus,
+            self.worker_client.and_then(|wc| wc.search_job_id()),
+            "kbn",
+            &expr,
+            digits,
+            self.search_params,
+            &certainty,
+        )? {
+            return Ok(());
+        }
+        self.progress.found.fetch_add(1, Ordering::Relaxed);
+        if let Some(eb) = self.event_bus {
+            eb.emit(events::Event::PrimeFound {
+                form: "kbn".into(),
+                expression: expr.clone(),
+                digits,
+                proof_method: certainty.clone(),
+                search_job_id: self.worker_client.and_then(|wc| wc.search_job_id()),
+                timestamp: Instant::now(),
+            });
+            eb.check_milestone("kbn", &expr, digits, || {
+                db.get_best_digits_sync(rt, "kbn").ok().flatten()
+            });
+        } else {
+            info!(
+                expression = %expr,
+                digits,
+                certainty = %certainty,
+                "Prime found"
+            );
+        }
+        self.buffer.push(
+            "kbn",
+            &expr,
+            digits,
+            self.search_params,
+            &certainty,
+            cert_json.as_deref(),
+        )
+    }
+
+    fn save_checkpoint(&mut self, last_n: u64, sieve: Option<&SieveState>) -> Result<()> {
+        self.buffer.flush()?;
+        checkpoint::save(
+            self.checkpoint_path,
+            &Checkpoint::Kbn {
+                last_n,
+                min_n: Some(self.min_n),
+                max_n: Some(self.max_n),
+                sieve: sieve.cloned(),
+            },
+        )
+    }
+
+    /// Flush the last primes and clear the checkpoint after the final block.
+    fn finish(mut self) -> Result<()> {
+        self.buffer.flush()?;
+        checkpoint::clear(self.checkpoint_path);
+        info!(eliminated = self.total_sieved, "KBN sieve complete");
+        debug!(
+            hits = self.witnesses.hits(),
+            jacobi_evals = self.witnesses.jacobi_evals(),
+            "Proth witness cache"
+        );
+        Ok(())
+    }
+}
+
+/// Test one block's survivors in parallel, returning the primes in n order.
+fn test_block(
+    k: u64,
+    base: u32,
+    block_start: u64,
+    survivors: BlockSurvivors,
+    mr_rounds: u32,
+    witnesses: &ProthWitnessCache,
+    progress: &Progress,
+) -> Vec<FoundPrime> {
+    // Pre-compute b^block_start once; each survivor computes b^offset (much smaller)
+    let base_pow_start = Integer::from(base).pow(crate::checked_u32(block_start));
+    let k_int = Integer::from(k);
+
+    survivors
+        .into_par_iter()
+        .flat_map_iter(|(n, test_plus, test_minus)| {
+            let _busy = crate::progress::busy();
+            let offset = n - block_start;
+            let base_pow = if offset == 0 {
+                base_pow_start.clone()
+            } else {
+                &base_pow_start * Integer::from(base).pow(crate::checked_u32(offset))
+            };
+            let kb = Integer::from(&k_int * &base_pow);
+
+            let plus_result = if test_plus {
+                let plus = Integer::from(&kb + 1u32);
+                let expr = Kbn { k, base, c: 1 }.expression(n);
+                let _testing = progress.testing(&expr, &plus);
+                let (r, cert_label, certificate) =
+                    test_prime_cached(&plus, k, base, n, true, mr_rounds, Some(witnesses));
+                if r != IsPrime::No {
+                    let digits = exact_digits(&plus);
+                    let cert_json = certificate
+                        .as_ref()
+                        .and_then(|c| serde_json::to_string(c).ok());
+                    Some((expr, digits, cert_label.to_string(), cert_json))
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            let minus_result = if test_minus {
+                let minus = Integer::from(&kb - 1u32);
+                let expr = Kbn { k, base, c: -1 }.expression(n);
+                let _testing = progress.testing(&expr, &minus);
+                let (r, cert_label, certificate) = test_prime(&minus, k, base, n, false, mr_rounds);
+                if r != IsPrime::No {
+                    let digits = exact_digits(&minus);
+                    let cert_json = certificate
+                        .as_ref()
+                        .and_then(|c| serde_json::to_string(c).ok());
+                    Some((expr, digits, cert_label.to_string(), cert_json))
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            plus_result.into_iter().chain(minus_result)
+        })
+        .collect()
+}
+
+/// Search k·b^n ± 1 for n in [min_n, max_n].
+///
+/// Both signs share one sieve: `bsgs_sieve` yields the +1 and −1 survivor
+/// sets in a single pass, each n tests whichever signs survived, and every
+/// prime is recorded on its own. For one sign alone, use [`search_c`] with
+/// c = ±1.
+///
+/// By default the BSGS sieve covers the whole range before the first test,
+/// and its bitmaps ride along in each checkpoint. With
+/// `KBN_PIPELINE_SEGMENTS` set to 2 or more, the range is split into that
+/// many segments instead and sieving overlaps testing; see
+/// [`search_segmented`].
+///
+/// A `k` divisible by `base` is first reduced to its primitive form with
+/// [`normalize_k`] (6·2^n becomes 3·2^(n+1)) and the n range shifted to
+/// match, so the sieve, the proofs and the recorded expressions all use the
+/// primitive k.
+#[allow(clippy::too_many_arguments)]
+pub fn search(
+    k: u64,
+    base: u32,
+    min_n: u64,
+    max_n: u64,
+    progress: &Arc<Progress>,
+    db: &dyn PrimeSink,
+    rt: &tokio::runtime::Handle,
+    checkpoint_path: &Path,
+    search_params: &str,
+    mr_rounds: u32,
+    sieve_limit: u64,
+    worker_client: Option<&dyn CoordinationClient>,
+    event_bus: Option<&EventBus>,
+) -> Result<()> {
+    let (primitive_k, shift) = normalize_k(k, base);
+    if shift > 0 {
+        info!(
+            k,
+            base,
+            primitive_k,
+            shift,
+            "k is divisible by the base; searching {}*{}^n with n in [{}, {}]",
+            primitive_k,
+            base,
+            min_n.saturating_add(shift),
+            max_n.saturating_add(shift)
+        );
+    }
+    search_segmented(
+        primitive_k,
+        base,
+        min_n.saturating_add(shift),
+        max_n.saturating_add(shift),
+        progress,
+        db,
+        rt,
+        checkpoint_path,
+        search_params,
+        mr_rounds,
+        sieve_limit,
+        worker_client,
+        event_bus,
+        pipeline_segments(),
+    )
+}
+
+/// Factor whole powers of `base` out of `k`: returns `(k', shift)` with
+/// k·b^n = k'·b^(n+shift) and b ∤ k'. `shift` is 0 when b already doesn't
+/// divide k. A k sharing only part of the base (k = 6, b = 4) has no
+/// primitive k'·b^n form and is returned unchanged.
+pub fn normalize_k(mut k: u64, base: u32) -> (u64, u64) {
+    let mut shift = 0;
+    while k > 0 && base > 1 && k.is_multiple_of(base as u64) {
+        k /= base as u64;
+        shift += 1;
+    }
+    (k, shift)
+}
+
+/// [`search`] with an explicit segment count.
+///
+/// With `segments` ≥ 2 and no checkpointed sieve to reuse, a sieving thread
+/// runs [`bsgs_sieve`] one segment at a time and sends each segment's bitmaps
+/// through a bounded channel ([`PIPELINE_DEPTH`]) to the block loop, which
+/// tests them on rayon while the next segment sieves. The first prime comes
+/// after one segment's sieve rather than the whole range's, and only a few
+/// segments' bitmaps are alive at once. Each segment repeats the discrete
+/// logs, so the sieve costs up to `segments` times more: worth it when
+/// testing dominates, as it does at large n. Survivors, and so the primes
+/// found and the progress checksum, are the same either way. Pipelined
+/// checkpoints carry no sieve state.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn search_segmented(
+    k: u64,
+    base: u32,
+    min_n: u64,
+    max_n: u64,
+    progress: &Arc<Progress>,
+    db: &dyn PrimeSink,
+    rt: &tokio::runtime::Handle,
+    checkpoint_path: &Path,
+    search_params: &str,
+    mr_rounds: u32,
+    sieve_limit: u64,
+    worker_client: Option<&dyn CoordinationClient>,
+    event_bus: Option<&EventBus>,
+    segments: u64,
+) -> Result<()> {
+    let params = serde_json::json!({"k": k, "base": base, "min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("kbn", &params)?;
+
+    // Resolve sieve_limit: auto-tune if 0
+    let candidate_bits = (max_n as f64 * (base as f64).log2() + (k as f64).log2().max(0.0)) as u64;
+    let n_range = max_n.saturating_sub(min_n) + 1;
+    let sieve_limit = sieve::resolve_sieve_limit(sieve_limit, candidate_bits, n_range);
+
+    let sieve_primes = sieve::generate_primes(sieve_limit);
+    info!(
+        prime_count = sieve_primes.len(),
+        sieve_limit,
+        "Sieve initialized"
+    );
+
+    let (resume_from, saved_sieve) = match checkpoint::load(checkpoint_path) {
+        Some(Checkpoint::Kbn { last_n, sieve, .. }) if last_n >= min_n && last_n < max_n => {
+            info!(resume_n = last_n + 1, "Resuming kbn search");
+            (last_n + 1, sieve)
+        }
+        _ => (min_n, None),
+    };
+
+    let sieve_min_n = sieve_min_n(k, base, sieve_limit);
+    info!(sieve_min_n, "Sieve active");
+
+    let tester = BlockTester {
+        k,
+        base,
+        min_n,
+        max_n,
+        progress,
+        db,
+        rt,
+        checkpoint_path,
+        search_params,
+        mr_rounds,
+        worker_client,
+        event_bus,
+        buffer: PrimeBuffer::new(db, rt, worker_client),
+        witnesses: ProthWitnessCache::from_env(),
+        last_checkpoint: Instant::now(),
+        total_sieved: 0,
+    };
+
+    if segments >= 2 && saved_sieve.is_none() {
+        let segment_len = (max_n - resume_from + 1).div_ceil(segments);
+        info!(segments, segment_len, "Running pipelined BSGS sieve");
+        return run_pipelined(tester, resume_from, segment_len, &sieve_primes, sieve_min_n);
+    }
+
+    // Run BSGS sieve once over the entire range
+    info!(
+        min_n = resume_from,
+        max_n,
+        candidates = max_n - resume_from + 1,
+        "Running BSGS sieve"
+    );
+    let (kbn_sieve, interrupted) = bsgs_sieve_resumable(
+        saved_sieve.as_ref(),
+        resume_from,
+        max_n,
+        k,
+        base,
+        &sieve_primes,
+        sieve_limit,
+        sieve_min_n,
+        worker_client,
+    );
+    if interrupted {
+        // Nothing was tested, so any checkpoint on disk is still the resume point.
+        info!("Stop requested by coordinator during sieve");
+        return Ok(());
+    }
+    if kbn_sieve.primes_sieved < sieve_primes.len() {
+        info!(
+            saved_limit = saved_sieve.as_ref().map(|st| st.sieve_limit),
+            sieve_limit,
+            primes_sieved = kbn_sieve.primes_sieved,
+            "Reused checkpointed BSGS sieve"
+        );
+    }
+    let sieve_state = kbn_sieve.to_state();
+    let KbnSieve {
+        plus: plus_survives,
+        minus: minus_survives,
+        base_n: sieve_base_n,
+        ..
+    } = kbn_sieve;
+    let bsgs_plus_survivors = plus_survives.count_ones() as u64;
+    let bsgs_minus_survivors = minus_survives.count_ones() as u64;
+    let total_range = max_n - sieve_base_n + 1;
+    info!(
+        plus_survivors = bsgs_plus_survivors,
+        minus_survivors = bsgs_minus_survivors,
+        total_range,
+        plus_pct = format_args!("{:.1}", bsgs_plus_survivors as f64 / total_range as f64 * 100.0),
+        minus_pct = format_args!("{:.1}", bsgs_minus_survivors as f64 / total_range as f64 * 100.0),
+        "BSGS sieve complete"
+    );
+
+    let remaining: u64 = (resume_from..=max_n)
+        .map(|n| {
+            let idx = (n - sieve_base_n) as usize;
+            u64::from(plus_survives.get(idx)) + u64::from(minus_survives.get(idx))
+        })
+        .sum();
+    progress.plan_eta("kbn", resume_from, max_n, remaining);
+
+    let mut tester = tester;
+    let mut block_start = resume_from;
+    while block_start <= max_n {
+        let bsize = crate::block_size_for_n(block_start);
+        let block_end = (block_start + bsize - 1).min(max_n);
+        let survivors = block_survivors(
+            &plus_survives,
+            &minus_survives,
+            sieve_base_n,
+            block_start,
+            block_end,
+        );
+        if tester.run_block(block_start, block_end, survivors, Some(&sieve_state))? {
+            return Ok(());
+        }
+        block_start = block_end + 1;
+    }
+    tester.finish()
+}
+
+/// The n of one range whose k·b^n + 1 (`plus`) or k·b^n − 1 (`minus`)
+/// survive the BSGS sieve, ascending. The coordinator computes these for kbn
+/// work blocks with [`sieve_survivors`] so operator nodes can go straight to
+/// the primality tests with [`search_survivors`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SieveSurvivors {
+    pub plus: Vec<u64>,
+    pub minus: Vec<u64>,
+}
+
+/// Stop client that asks a sieve to stop once the deadline has passed.
+struct Deadline(Instant);
+
+impl CoordinationClient for Deadline {
+    fn is_stop_requested(&self) -> bool {
+        Instant::now() >= self.0
+    }
+    fn report_prime(&self, _: &str, _: &str, _: u64, _: &str, _: &str) {}
+}
+
+/// Deepest sieve [`sieve_survivors`] attempts under a deadline. Generating
+/// the primes is not interruptible, and past this depth it alone can take
+/// seconds and hundreds of MB.
+pub const DEADLINE_SIEVE_LIMIT: u64 = 100_000_000;
+
+/// Sieve [min_n, max_n] as [`search`] would and list the survivors.
+///
+/// `sieve_limit` 0 auto-tunes from the range. A `k` divisible by `base` is
+/// normalized internally, but the returned n are in the caller's terms.
+/// With a `deadline`, returns `Ok(None)` when the sieve has not finished by
+/// then, and without sieving when the limit is deeper than
+/// [`DEADLINE_SIEVE_LIMIT`].
+pub fn sieve_survivors(
+    k: u64,
+    base: u32,
+    min_n: u64,
+    max_n: u64,
+    sieve_limit: u64,
+    deadline: Option<Instant>,
+) -> Result<Option<SieveSurvivors>> {
+    let params = serde_json::json!({"k": k, "base": base, "min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("kbn", &params)?;
+
+    let (pk, shift) = normalize_k(k, base);
+    let (lo, hi) = (min_n + shift, max_n + shift);
+    let candidate_bits = (hi as f64 * (base as f64).log2() + (pk as f64).log2().max(0.0)) as u64;
+    let sieve_limit = sieve::resolve_sieve_limit(sieve_limit, candidate_bits, hi - lo + 1);
+    let deadline = deadline.map(Deadline);
+    if deadline
+        .as_ref()
+        .is_some_and(|d| sieve_limit > DEADLINE_SIEVE_LIMIT || d.is_stop_requested())
+    {
+        return Ok(None);
+    }
+    let primes = sieve::generate_primes(sieve_limit);
+    let min_sieve_n = sieve_min_n(pk, base, sieve_limit);
+    let stop = deadline.as_ref().map(|d| d as &dyn CoordinationClient);
+    let (plus, minus, interrupted) = bsgs_sieve(lo, hi, pk, base, &primes, min_sieve_n, stop);
+    if interrupted {
+        return Ok(None);
+    }
+
+    let mut survivors = SieveSurvivors::default();
+    for (n, tp, tm) in block_survivors(&plus, &minus, lo, lo, hi) {
+        if tp {
+            survivors.plus.push(n - shift);
+        }
+        if tm {
+            survivors.minus.push(n - shift);
+        }
+    }
+    Ok(Some(survivors))
+}
+
+/// Test pre-sieved candidates in [min_n, max_n] without sieving: the
+/// operator-node half of [`sieve_survivors`]. Blocks, checkpoints, progress
+/// and prime recording are those of [`search`], so a block searched this way
+/// finds the same primes. Survivors outside the range are an error.
+#[allow(clippy::too_many_arguments)]
+pub fn search_survivors(
+    k: u64,
+    base: u32,
+    min_n: u64,
+    max_n: u64,
+    survivors: &SieveSurvivors,
+    progress: &Arc<Progress>,
+    db: &dyn PrimeSink,
+    rt: &tokio::runtime::Handle,
+    checkpoint_path: &Path,
+    search_params: &str,
+    mr_rounds: u32,
+    worker_client: Option<&dyn CoordinationClient>,
+    event_bus: Option<&EventBus>,
+) -> Result<()> {
+    let params = serde_json::json!({"k": k, "base": base, "min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("kbn", &params)?;
+    if let Some(&n) = survivors
+        .plus
+        .iter()
+        .chain(&survivors.minus)
+        .find(|&&n| n < min_n || n > max_n)
+    {
+        anyhow::bail!("survivor n={} is outside [{}, {}]", n, min_n, max_n);
+    }
+
+    let (k, shift) = normalize_k(k, base);
+    let (min_n, max_n) = (min_n + shift, max_n + shift);
+    let mut signs = std::collections::BTreeMap::<u64, (bool, bool)>::new();
+    for &n in &survivors.plus {
+        signs.entry(n + shift).or_default().0 = true;
+    }
+    for &n in &survivors.minus {
+        signs.entry(n + shift).or_default().1 = true;
+    }
+
+    let resume_from = match checkpoint::load(checkpoint_path) {
+        Some(Checkpoint::Kbn { last_n, .. }) if last_n >= min_n && last_n < max_n => {
+            info!(resume_n = last_n + 1, "Resuming kbn search");
+            last_n + 1
+        }
+        _ => min_n,
+    };
+    let remaining: u64 = signs
+        .range(resume_from..)
+        .map(|(_, &(tp, tm))| u64::from(tp) + u64::from(tm))
+        .sum();
+    progress.plan_eta("kbn", resume_from, max_n, remaining);
+    info!(
+        plus_survivors = survivors.plus.len(),
+        minus_survivors = survivors.minus.len(),
+        "Testing pre-sieved survivors"
+    );
+
+    let mut tester = BlockTester {
+        k,
+        base,
+        min_n,
+        max_n,
+        progress,
+        db,
+        rt,
+        checkpoint_path,
+        search_params,
+        mr_rounds,
+        worker_client,
+        event_bus,
+        buffer: PrimeBuffer::new(db, rt, worker_client),
+        witnesses: ProthWitnessCache::from_env(),
+        last_checkpoint: Instant::now(),
+        total_sieved: 0,
+    };
+    let mut block_start = resume_from;
+    while block_start <= max_n {
+        let bsize = crate::block_size_for_n(block_start);
+        let block_end = (block_start + bsize - 1).min(max_n);
+        let block = signs
+            .range(block_start..=block_end)
+            .map(|(&n, &(tp, tm))| (n, tp, tm))
+            .collect();
+        if tester.run_block(block_start, block_end, block, None)? {
+            return Ok(());
+        }
+        block_start = block_end + 1;
+    }
+    tester.finish()
+}
+
+/// The pipelined half of [`search_segmented`]: sieve segments of
+/// `segment_len` on a scoped thread, test them here as they arrive.
+fn run_pipelined(
+    mut tester: BlockTester<'_>,
+    resume_from: u64,
+    segment_len: u64,
+    sieve_primes: &[u64],
+    sieve_min_n: u64,
+) -> Result<()> {
+    let (k, base, max_n) = (tester.k, tester.base, tester.max_n);
+    let stop = tester.worker_client;
+    std::thread::scope(|scope| {
+        let (tx, rx) = std::sync::mpsc::sync_channel(PIPELINE_DEPTH);
+        scope.spawn(move || {
+            let mut seg_start = resume_from;
+            while seg_start <= max_n {
+                let seg_end = (seg_start + segment_len - 1).min(max_n);
+                let (plus, minus, interrupted) =
+                    bsgs_sieve(seg_start, seg_end, k, base, sieve_primes, sieve_min_n, stop);
+                // A closed channel means the tester stopped or failed.
+                if interrupted || tx.send((seg_start, seg_end, plus, minus)).is_err() {
+                    return;
+                }
+                seg_start = seg_end + 1;
+            }
+        });
+
+        let mut next_n = resume_from;
+        for (seg_start, seg_end, plus, minus) in rx {
+            debug!(seg_start, seg_end, "BSGS segment sieved");
+            let mut block_start = seg_start;
+            while block_start <= seg_end {
+                let bsize = crate::block_size_for_n(block_start);
+                let block_end = (block_start + bsize - 1).min(seg_end);
+                let survivors = block_survivors(&plus, &minus, seg_start, block_start, block_end);
+                if tester.run_block(block_start, block_end, survivors, None)? {
+                    return Ok(());
+                }
+                block_start = block_end + 1;
+            }
+            next_n = seg_end + 1;
+        }
+
+        if next_n <= max_n {
+            // The sieve thread saw a stop before the block loop did.
+            if next_n > resume_from {
+                tester.save_checkpoint(next_n - 1, None)?;
+            }
+            info!(n = next_n, "Stop requested by coordinator during sieve");
+            return Ok(());
+        }
+        tester.finish()
+    })
+}
+
+/// BSGS sieve for k·b^n + c with an arbitrary nonzero offset c: for each
+/// sieve prime p ∤ k·b·c, k·b^n + c ≡ 0 (mod p) exactly when
+/// b^n ≡ −c·k^{−1} (mod p). A prime dividing k or b leaves the candidate
+/// ≡ c (mod p), so it eliminates every n when it also divides c.
+///
+/// Returns the survivor bitmap indexed by (n − min_n) and the `interrupted`
+/// flag, with the same stop-polling contract as [`bsgs_sieve`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn bsgs_sieve_c(
+    min_n: u64,
+    max_n: u64,
+    k: u64,
+    base: u32,
+    c: i64,
+    sieve_primes: &[u64],
+    sieve_min_n: u64,
+    stop: Option<&dyn CoordinationClient>,
+) -> (sieve::BitSieve, bool) {
+    #[cfg(test)]
+    SIEVE_PASSES.with(|p| p.set(p.get() + 1));
+    let range = (max_n - min_n + 1) as usize;
+    let mut survives = sieve::BitSieve::new_all_set(range);
+    let first_sievable = min_n.max(sieve_min_n);
+    let base_u64 = base as u64;
+
+    for (pi, &p) in sieve_primes.iter().enumerate() {
+        if pi % STOP_CHECK_PRIMES == 0 && stop.is_some_and(|wc| wc.is_stop_requested()) {
+            info!(
+                processed = pi,
+                total = sieve_primes.len(),
+                "Stop requested during BSGS sieve"
+            );
+            return (survives, true);
+        }
+
+        let c_mod = c.rem_euclid(p as i64) as u64;
+        if base_u64.is_multiple_of(p) || k.is_multiple_of(p) {
+            if c_mod == 0 {
+                for n in first_sievable..=max_n {
+                    survives.clear((n - min_n) as usize);
+                }
+            }
+            continue;
+        }
+        if c_mod == 0 {
+            continue; // b^n ≡ 0 (mod p) has no solution
+        }
+
+        let k_inv = match sieve::mod_inverse(k, p) {
+            Some(v) => v,
+            None => continue,
+        };
+        let target = ((p - c_mod) as u128 * k_inv as u128 % p as u128) as u64;
+        let order = sieve::multiplicative_order(base_u64, p);
+        if order == 0 {
+            continue;
+        }
+        if let Some(n0) = sieve::discrete_log_bsgs(base_u64, target, p, order) {
+            let mut n = if n0 >= first_sievable {
+                n0
+            } else {
+                n0 + (first_sievable - n0).div_ceil(order) * order
+            };
+            while n <= max_n {
+                survives.clear((n - min_n) as usize);
+                n += order;
+            }
+        }
+    }
+
+    (survives, false)
+}
+
+/// Test k·b^n + c. For c = ±1 this is [`test_prime`] with its Proth/LLR
+/// proofs; any other offset breaks the N±1 factorizations those proofs rely
+/// on, so the result is a BPSW+MR verdict labelled via [`crate::certainty_label`]
+/// (deterministic only below 2^64).
+pub(crate) fn test_prime_c(
+    candidate: &Integer,
+    k: u64,
+    base: u32,
+    n: u64,
+    c: i64,
+    mr_rounds: u32,
+) -> (IsPrime, &'static str, Option<PrimalityCertificate>) {
+    if c == 1 || c == -1 {
+        return test_prime(candidate, k, base, n, c == 1, mr_rounds);
+    }
+    let r = crate::mr_screened_test(candidate, mr_rounds);
+    if r == IsPrime::No {
+        return (IsPrime::No, "", None);
+    }
+    (r, crate::certainty_label(candidate, r), None)
+}
+
+/// Search k·b^n + c for n in [min_n, max_n] with a fixed nonzero offset c.
+///
+/// Generalizes [`search`] (which covers c = ±1 together) to the offsets used
+/// in covering-set and Sierpiński-number work. Survivors of [`bsgs_sieve_c`]
+/// go through [`test_prime_c`]; results are stored under form `"kbn"` so
+/// `verify` can rebuild them. Checkpoints record the last finished n and c.
+#[allow(clippy::too_many_arguments)]
+pub fn search_c(
+    k: u64,
+    base: u32,
+    c: i64,
+    min_n: u64,
+    max_n: u64,
+    progress: &Arc<Progress>,
+    db: &dyn PrimeSink,
+    rt: &tokio::runtime::Handle,
+    checkpoint_path: &Path,
+    search_params: &str,
+    mr_rounds: u32,
+    sieve_limit: u64,
+    worker_client: Option<&dyn CoordinationClient>,
+    event_bus: Option<&EventBus>,
+) -> Result<()> {
+    let params = serde_json::json!({"k": k, "base": base, "min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("kbn", &params)?;
+
+    anyhow::ensure!(c != 0, "kbn offset c must be nonzero");
+    let candidate_bits = (max_n as f64 * (base as f64).log2() + (k as f64).log2().max(0.0)) as u64;
+    let n_range = max_n.saturating_sub(min_n) + 1;
+    let sieve_limit = sieve::resolve_sieve_limit(sieve_limit, candidate_bits, n_range);
+
+    let sieve_primes = sieve::generate_primes(sieve_limit);
+    info!(
+        prime_count = sieve_primes.len(),
+        sieve_limit, c, "Sieve initialized"
+    );
+
+    let resume_from = match checkpoint::load(checkpoint_path) {
+        Some(Checkpoint::KbnC {
+            last_n,
+            c: Some(cp_c),
+            ..
+        }) if cp_c == c && last_n >= min_n && last_n < max_n => {
+            info!(resume_n = last_n + 1, "Resuming kbn offset search");
+            last_n + 1
+        }
+        _ => min_n,
+    };
+
+    // Minimum n where k*b^n + c > sieve_limit, so a sieve hit is a proper factor.
+    let sieve_min_n = if base >= 2 {
+        let log_b = (base as f64).log10();
+        let log_limit = ((sieve_limit as f64) + c.unsigned_abs() as f64).log10();
+        ((log_limit - (k as f64).log10().max(0.0)) / log_b).ceil() as u64 + 1
+    } else {
+        u64::MAX
+    };
+
+    let (survives, interrupted) = bsgs_sieve_c(
+        resume_from,
+        max_n,
+        k,
+        base,
+        c,
+        &sieve_primes,
+        sieve_min_n,
+        worker_client,
+    );
+    if interrupted {
+        info!("Stop requested by coordinator during sieve");
+        return Ok(());
+    }
+    let total_range = max_n - resume_from + 1;
+    info!(
+        survivors = survives.count_ones(),
+        total_range, sieve_min_n, "BSGS sieve complete"
+    );
+
+    let save = |last_n: u64| {
+        checkpoint::save(
+            checkpoint_path,
+            &Checkpoint::KbnC {
+                last_n,
+                c: Some(c),
+                min_n: Some(min_n),
+                max_n: Some(max_n),
+            },
+        )
+    };
+
+    let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
+    let mut block_start = resume_from;
+    let mut total_sieved: u64 = 0;
+
+    while block_start <= max_n {
+        let bsize = crate::block_size_for_n(block_start);
+        let block_end = (block_start + bsize - 1).min(max_n);
+        let block_len = block_end - block_start + 1;
+
+        *progress.current.lock().unwrap() =
+            format!("{}*{}^[{}..{}]{:+}", k, base, block_start, block_end, c);
+
+        let survivors: Vec<u64> = (block_start..=block_end)
+            .filter(|&n| survives.get((n - resume_from) as usize))
+            .collect();
+        total_sieved += block_len - survivors.len() as u64;
+        progress.record_survivors(survivors.iter().copied());
+
+        let found_primes: Vec<_> = survivors
+            .into_par_iter()
+            .filter_map(|n| {
+                let _busy = crate::progress::busy();
+                let kb = Integer::from(k) * Integer::from(base).pow(crate::checked_u32(n));
+                let candidate = kb + c;
+                if candidate <= 1 {
+                    return None;
+                }
+                let expr = Kbn { k, base, c }.expression(n);
+                let _testing = progress.testing(&expr, &candidate);
+                let (r, cert_label, certificate) =
+                    test_prime_c(&candidate, k, base, n, c, mr_rounds);
+                if r == IsPrime::No {
+                    return None;
+                }
+                let cert_json = certificate
+                    .as_ref()
+                    .and_then(|cert| serde_json::to_string(cert).ok());
+                Some((
+                    expr,
+                    exact_digits(&candidate),
+                    cert_label.to_string(),
+                    cert_json,
+                ))
+            })
+            .collect();
+
+        progress.tested.fetch_add(block_len, Ordering::Relaxed);
+
+        for (expr, digits, certainty, cert_json) in found_primes {
+            let (certainty, cert_json) =
+                crate::prove_on_find("kbn", &expr, digits, certainty, cert_json);
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "kbn",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
+            progress.found.fetch_add(1, Ordering::Relaxed);
+            if let Some(eb) = event_bus {
+                eb.emit(events::Event::PrimeFound {
+                    form: "kbn".into(),
+                    expression: expr.clone(),
+                    digits,
+                    proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
+                    timestamp: Instant::now(),
+                });
+                eb.check_milestone("kbn", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "kbn").ok().flatten()
+                });
+            } else {
+                info!(
+                    expression = %expr,
+                    digits,
+                    certainty = %certainty,
+                    "Prime found"
+                );
+            }
+            buffer.push(
+                "kbn",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+                cert_json.as_deref(),
+            )?;
+        }
+
+        buffer.flush_if_due()?;
+
+        if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
+            save(block_end)?;
+            info!(n = block_end, sieved_out = total_sieved, "Checkpoint saved");
+            last_checkpoint = Instant::now();
+        }
+
+        if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
+            save(block_end)?;
+            info!(
+                n = block_end,
+                "Stop requested by coordinator, checkpoint saved"
+            );
+            return Ok(());
+        }
+
+        block_start = block_end + 1;
+    }
+
+    buffer.flush()?;
+    checkpoint::clear(checkpoint_path);
+    info!(eliminated = total_sieved, c, "KBN offset search complete");
+    Ok(())
+}
+
+/// Sieve over k at fixed n: for each sieve prime p ∤ b, k·b^n ± 1 ≡ 0 (mod p)
+/// exactly when k ≡ ∓b^{−n} (mod p), so one modular inverse per prime marks
+/// every composite k in the range with a stride of p.
+///
+/// Returns a survivor bitmap indexed by (k − min_k). When b^n is small, a
+/// candidate equal to the sieve prime itself is kept.
+pub(crate) fn k_sieve(
+    min_k: u64,
+    max_k: u64,
+    base: u32,
+    n: u64,
+    is_plus: bool,
+    sieve_primes: &[u64],
+) -> sieve::BitSieve {
+    let range = (max_k - min_k + 1) as usize;
+    let mut survives = sieve::BitSieve::new_all_set(range);
+
+    // b^n as an exact value when k*b^n ± 1 could still equal a sieve prime.
+    let limit = sieve_primes.last().copied().unwrap_or(0) as u128;
+    let small_bn = (0..n).try_fold(1u128, |acc, _| {
+        Some(acc * base as u128).filter(|&v| v <= limit + 1)
+    });
+
+    for &p in sieve_primes {
+        let b = base as u64 % p;
+        if b == 0 {
+            continue;
+        }
+        let bn_inv = match sieve::mod_inverse(sieve::pow_mod(b, n, p), p) {
+            Some(v) => v,
+            None => continue,
+        };
+        // +1 form: k ≡ −b^{−n};  −1 form: k ≡ b^{−n}  (mod p)
+        let target = if is_plus { (p - bn_inv) % p } else { bn_inv };
+        let mut k = min_k + (target + p - min_k % p) % p;
+        while k <= max_k {
+            let is_p_itself = small_bn.is_some_and(|bn| {
+                let kb = k as u128 * bn;
+                (if is_plus { kb + 1 } else { kb - 1 }) == p as u128
+            });
+            if !is_p_itself {
+                survives.clear((k - min_k) as usize);
             }
+            k += p;
         }
     }
 
-    (plus_survives, minus_survives)
+    survives
 }
 
-pub fn search(
-    k: u64,
+/// Fixed-n search: test k·b^n + 1 (or − 1) for every k in [min_k, max_k].
+///
+/// The complement of [`search`], used for Sierpiński/Riesel-style questions
+/// such as the smallest k making k·2^n ± 1 prime. Survivors of [`k_sieve`]
+/// go through the same [`test_prime`] pipeline, so k < 2^n still gets a
+/// Proth or LLR proof. Checkpoints record the last finished k.
+#[allow(clippy::too_many_arguments)]
+pub fn search_k_range(
     base: u32,
-    min_n: u64,
-    max_n: u64,
+    n: u64,
+    min_k: u64,
+    max_k: u64,
+    is_plus: bool,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -604,150 +2124,122 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
-    // Resolve sieve_limit: auto-tune if 0
-    let candidate_bits = (max_n as f64 * (base as f64).log2() + (k as f64).log2().max(0.0)) as u64;
-    let n_range = max_n.saturating_sub(min_n) + 1;
-    let sieve_limit = sieve::resolve_sieve_limit(sieve_limit, candidate_bits, n_range);
+    let params = serde_json::json!({"k": min_k, "base": base, "min_n": n, "max_n": n});
+    crate::search_params::validate("kbn", &params)?;
+    anyhow::ensure!(
+        min_k <= max_k,
+        "kbn: min_k ({}) is greater than max_k ({})",
+        min_k,
+        max_k
+    );
+
+    let sign = if is_plus { '+' } else { '-' };
+    let c = if is_plus { 1 } else { -1 };
+    let candidate_bits = (n as f64 * (base as f64).log2() + (max_k as f64).log2()) as u64;
+    let k_range = max_k.saturating_sub(min_k) + 1;
+    let sieve_limit = sieve::resolve_sieve_limit(sieve_limit, candidate_bits, k_range);
 
     let sieve_primes = sieve::generate_primes(sieve_limit);
     info!(
         prime_count = sieve_primes.len(),
-        sieve_limit,
-        "Sieve initialized"
+        sieve_limit, "Sieve initialized"
     );
 
     let resume_from = match checkpoint::load(checkpoint_path) {
-        Some(Checkpoint::Kbn { last_n, .. }) if last_n >= min_n && last_n < max_n => {
-            info!(resume_n = last_n + 1, "Resuming kbn search");
-            last_n + 1
+        Some(Checkpoint::KbnK {
+            last_k,
+            n: Some(cp_n),
+            ..
+        }) if cp_n == n && last_k >= min_k && last_k < max_k => {
+            info!(resume_k = last_k + 1, "Resuming fixed-n kbn search");
+            last_k + 1
         }
-        _ => min_n,
+        _ => min_k,
     };
 
-    // Minimum n where k*b^n > sieve_limit, making the sieve safe.
-    let sieve_min_n = if base >= 2 {
-        let log_b = (base as f64).log10();
-        let log_limit = (sieve_limit as f64).log10();
-        ((log_limit - (k as f64).log10().max(0.0)) / log_b).ceil() as u64 + 1
-    } else {
-        u64::MAX
-    };
-    info!(sieve_min_n, "Sieve active");
-
-    // Run BSGS sieve once over the entire range
-    info!(
-        min_n = resume_from,
-        max_n,
-        candidates = max_n - resume_from + 1,
-        "Running BSGS sieve"
-    );
-    let (plus_survives, minus_survives) =
-        bsgs_sieve(resume_from, max_n, k, base, &sieve_primes, sieve_min_n);
-    let bsgs_plus_survivors = plus_survives.count_ones() as u64;
-    let bsgs_minus_survivors = minus_survives.count_ones() as u64;
-    let total_range = max_n - resume_from + 1;
+    let survives = k_sieve(resume_from, max_k, base, n, is_plus, &sieve_primes);
+    let total_range = max_k - resume_from + 1;
     info!(
-        plus_survivors = bsgs_plus_survivors,
-        minus_survivors = bsgs_minus_survivors,
-        total_range,
-        plus_pct = format_args!("{:.1}", bsgs_plus_survivors as f64 / total_range as f64 * 100.0),
-        minus_pct = format_args!("{:.1}", bsgs_minus_survivors as f64 / total_range as f64 * 100.0),
-        "BSGS sieve complete"
+        survivors = survives.count_ones(),
+        total_range, "k sieve complete"
     );
 
+    let base_pow = Integer::from(base).pow(crate::checked_u32(n));
+    let save = |last_k: u64| {
+        checkpoint::save(
+            checkpoint_path,
+            &Checkpoint::KbnK {
+                last_k,
+                n: Some(n),
+                base: Some(base),
+                min_k: Some(min_k),
+                max_k: Some(max_k),
+            },
+        )
+    };
+
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut block_start = resume_from;
     let mut total_sieved: u64 = 0;
 
-    while block_start <= max_n {
-        let bsize = crate::block_size_for_n(block_start);
-        let block_end = (block_start + bsize - 1).min(max_n);
+    while block_start <= max_k {
+        let bsize = crate::block_size_for_n(n);
+        let block_end = (block_start + bsize - 1).min(max_k);
         let block_len = block_end - block_start + 1;
 
         *progress.current.lock().unwrap() =
-            format!("{}*{}^[{}..{}]+-1", k, base, block_start, block_end);
+            format!("[{}..{}]*{}^{}{}1", block_start, block_end, base, n, sign);
 
-        let survivors: Vec<(u64, bool, bool)> = (block_start..=block_end)
-            .filter_map(|n| {
-                let idx = (n - resume_from) as usize;
-                let tp = plus_survives.get(idx);
-                let tm = minus_survives.get(idx);
-                if tp || tm {
-                    Some((n, tp, tm))
-                } else {
-                    None
-                }
-            })
+        let survivors: Vec<u64> = (block_start..=block_end)
+            .filter(|&k| survives.get((k - resume_from) as usize))
             .collect();
-
         total_sieved += block_len - survivors.len() as u64;
-
-        // Pre-compute b^block_start once; each survivor computes b^offset (much smaller)
-        let base_pow_start = Integer::from(base).pow(crate::checked_u32(block_start));
-        let k_int = Integer::from(k);
+        progress.record_survivors(survivors.iter().copied());
 
         let found_primes: Vec<_> = survivors
             .into_par_iter()
-            .flat_map_iter(|(n, test_plus, test_minus)| {
-                let offset = n - block_start;
-                let base_pow = if offset == 0 {
-                    base_pow_start.clone()
-                } else {
-                    &base_pow_start * Integer::from(base).pow(crate::checked_u32(offset))
-                };
-                let kb = Integer::from(&k_int * &base_pow);
-
-                let plus_result = if test_plus {
-                    let plus = Integer::from(&kb + 1u32);
-                    let (r, cert_label, certificate) =
-                        test_prime(&plus, k, base, n, true, mr_rounds);
-                    if r != IsPrime::No {
-                        let digits = exact_digits(&plus);
-                        let cert_json = certificate
-                            .as_ref()
-                            .and_then(|c| serde_json::to_string(c).ok());
-                        Some((
-                            format!("{}*{}^{} + 1", k, base, n),
-                            digits,
-                            cert_label.to_string(),
-                            cert_json,
-                        ))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
-                let minus_result = if test_minus {
-                    let minus = Integer::from(&kb - 1u32);
-                    let (r, cert_label, certificate) =
-                        test_prime(&minus, k, base, n, false, mr_rounds);
-                    if r != IsPrime::No {
-                        let digits = exact_digits(&minus);
-                        let cert_json = certificate
-                            .as_ref()
-                            .and_then(|c| serde_json::to_string(c).ok());
-                        Some((
-                            format!("{}*{}^{} - 1", k, base, n),
-                            digits,
-                            cert_label.to_string(),
-                            cert_json,
-                        ))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
-                plus_result.into_iter().chain(minus_result)
+            .filter_map(|k| {
+                let _busy = crate::progress::busy();
+                let kb = Integer::from(&base_pow * k);
+                let candidate = if is_plus { kb + 1u32 } else { kb - 1u32 };
+                let expr = Kbn { k, base, c }.expression(n);
+                let _testing = progress.testing(&expr, &candidate);
+                let (r, cert_label, certificate) =
+                    test_prime(&candidate, k, base, n, is_plus, mr_rounds);
+                if r == IsPrime::No {
+                    return None;
+                }
+                let cert_json = certificate
+                    .as_ref()
+                    .and_then(|c| serde_json::to_string(c).ok());
+                Some((
+                    expr,
+                    exact_digits(&candidate),
+                    cert_label.to_string(),
+                    cert_json,
+                ))
             })
             .collect();
 
-        progress.tested.fetch_add(block_len * 2, Ordering::Relaxed);
+        progress.tested.fetch_add(block_len, Ordering::Relaxed);
 
         for (expr, digits, certainty, cert_json) in found_primes {
+            let (certainty, cert_json) =
+                crate::prove_on_find("kbn", &expr, digits, certainty, cert_json);
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "kbn",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -755,8 +2247,12 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                eb.check_milestone("kbn", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "kbn").ok().flatten()
+                });
             } else {
                 info!(
                     expression = %expr,
@@ -765,8 +2261,7 @@ pub fn search(
                     "Prime found"
                 );
             }
-            db.insert_prime_sync(
-                rt,
+            buffer.push(
                 "kbn",
                 &expr,
                 digits,
@@ -774,39 +2269,22 @@ pub fn search(
                 &certainty,
                 cert_json.as_deref(),
             )?;
-            if let Some(wc) = worker_client {
-                wc.report_prime("kbn", &expr, digits, search_params, &certainty);
-            }
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
-            checkpoint::save(
-                checkpoint_path,
-                &Checkpoint::Kbn {
-                    last_n: block_end,
-                    min_n: Some(min_n),
-                    max_n: Some(max_n),
-                },
-            )?;
-            info!(
-                n = block_end,
-                sieved_out = total_sieved,
-                "Checkpoint saved"
-            );
+            buffer.flush()?;
+            save(block_end)?;
+            info!(k = block_end, sieved_out = total_sieved, "Checkpoint saved");
             last_checkpoint = Instant::now();
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
-            checkpoint::save(
-                checkpoint_path,
-                &Checkpoint::Kbn {
-                    last_n: block_end,
-                    min_n: Some(min_n),
-                    max_n: Some(max_n),
-                },
-            )?;
+            buffer.flush()?;
+            save(block_end)?;
             info!(
-                n = block_end,
+                k = block_end,
                 "Stop requested by coordinator, checkpoint saved"
             );
             return Ok(());
@@ -815,8 +2293,9 @@ pub fn search(
         block_start = block_end + 1;
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
-    info!(eliminated = total_sieved, "KBN sieve complete");
+    info!(eliminated = total_sieved, "Fixed-n KBN search complete");
     Ok(())
 }
 
@@ -1175,8 +2654,8 @@ mod tests {
                 ((log_limit - (k as f64).log10().max(0.0)) / log_b).ceil() as u64 + 1
             };
 
-            let (bsgs_plus, bsgs_minus) =
-                bsgs_sieve(min_n, max_n, k, base, &sieve_primes, sieve_min_n);
+            let (bsgs_plus, bsgs_minus, _) =
+                bsgs_sieve(min_n, max_n, k, base, &sieve_primes, sieve_min_n, None);
             let old_survivors = sieve_block(min_n, max_n, k, base, &sieve_primes, sieve_min_n);
 
             // Build equivalent maps from the old sieve_block output
@@ -1215,6 +2694,86 @@ mod tests {
         }
     }
 
+    /// The self-test finds nothing wrong with the real sieve, and reports a
+    /// bit cleared for a known prime: 3·2^143 − 1 (OEIS A002235).
+    #[test]
+    fn diagnose_sieve_detects_false_eliminations() {
+        let clean = diagnose_sieve(3, 2, 1, 300, 10_000);
+        assert!(clean.checked > 300, "checked {}", clean.checked);
+        assert_eq!(clean.false_eliminations, []);
+        assert_eq!(diagnose_sieve(7, 10, 1, 150, 10_000).false_eliminations, []);
+
+        let primes = sieve::generate_primes(10_000);
+        let min_n = sieve_min_n(3, 2, 10_000);
+        let (plus, mut minus, _) = bsgs_sieve(1, 300, 3, 2, &primes, min_n, None);
+        assert!(minus.get(142), "3*2^143-1 survives the sieve");
+        minus.clear(142);
+        let bad = check_eliminations(3, 2, 1, &plus, &minus);
+        let minus = true;
+        let n143 = FalseElimination { n: 143, minus };
+        assert_eq!(bad.false_eliminations, [n143]);
+        assert_eq!(bad.checked, clean.checked + 1);
+    }
+
+    /// 78557·2^n + 1 is covered by {3, 5, 7, 13, 19, 37, 73} (Selfridge,
+    /// 1962); dropping a member or shifting an offset breaks the proof.
+    #[test]
+    fn covering_set_proves_78557_sierpinski() {
+        let covering = [
+            (3, 2, 0),
+            (5, 4, 1),
+            (7, 3, 1),
+            (13, 12, 11),
+            (19, 18, 15),
+            (37, 36, 27),
+            (73, 9, 3),
+        ];
+        assert!(verify_covering_set(78557, 1, &covering));
+        assert!(!verify_covering_set(78557, 1, &covering[1..]));
+        let mut shifted = covering;
+        shifted[6].2 = 4;
+        assert!(!verify_covering_set(78557, 1, &shifted));
+        assert!(!verify_covering_set(78557, -1, &covering));
+        assert!(!verify_covering_set(78559, 1, &covering));
+
+        // Every member divides the terms it claims.
+        for n in 0..72u32 {
+            let term = Integer::from(78557u32) * (Integer::from(1u32) << n) + 1u32;
+            let p = covering.iter().find(|c| n as u64 % c.1 == c.2).unwrap().0;
+            assert!(term.is_divisible_u(p as u32), "{} ∤ 78557*2^{}+1", p, n);
+        }
+    }
+
+    /// 509203·2^n − 1 is covered by {3, 5, 7, 13, 17, 241} (Riesel, 1956).
+    #[test]
+    fn covering_set_proves_509203_riesel() {
+        let covering = [
+            (3, 2, 0),
+            (5, 4, 1),
+            (7, 3, 2),
+            (13, 12, 7),
+            (17, 8, 7),
+            (241, 24, 3),
+        ];
+        assert!(verify_covering_set(509203, -1, &covering));
+        assert!(!verify_covering_set(509203, -1, &covering[..5]));
+        assert!(!verify_covering_set(509203, -1, &[(3, 0, 0)]));
+    }
+
+    /// With p = 2^64 − 59 both residues of k·2^124 sit above 2^63, so their
+    /// product passes 2^127; the claim must still check out mod p.
+    #[test]
+    fn covering_member_divides_above_2_pow_63() {
+        let p = u64::MAX - 58;
+        let k = 14_149_039_550_934_933_254u64;
+        assert!(sieve::pow_mod(2, 124, p) > 1 << 63);
+        let term = Integer::from(k) * (Integer::from(1u32) << 124u32) + 1u32;
+        assert!(term.is_divisible(&Integer::from(p)));
+        assert!(covering_member_divides(k, 1, p, 124));
+        assert!(!covering_member_divides(k, -1, p, 124));
+        assert!(!covering_member_divides(k, 1, p, 123));
+    }
+
     // ── MR Pre-Screen Integration ──────────────────────────────────────
 
     /// Verifies that the MR pre-screen rejects the composite 2^{11} - 1 = 2047
@@ -1276,6 +2835,34 @@ mod tests {
         );
     }
 
+    /// 97 = 3·2^5 + 1 and 193 = 3·2^6 + 1 are both ≡ 1 mod 8 and mod 3, so 2
+    /// and 3 are residues and 5 is the witness for each. The first test needs
+    /// three Jacobi symbols to find it; the second tries the cached 5 first.
+    #[test]
+    fn proth_witness_cache_tries_recent_witness_first() {
+        let cache = ProthWitnessCache::new(DEFAULT_PROTH_WITNESS_CACHE);
+        let first = proth_test_cached(&Integer::from(97u32), Some(&cache));
+        assert_eq!(first, Some((true, Some(5))));
+        assert_eq!((cache.jacobi_evals(), cache.hits()), (3, 0));
+        let second = proth_test_cached(&Integer::from(193u32), Some(&cache));
+        assert_eq!(second, Some((true, Some(5))));
+        assert_eq!((cache.jacobi_evals(), cache.hits()), (4, 1));
+
+        // Over a sweep the cache changes the cost, never the verdict.
+        let uncached = ProthWitnessCache::new(0);
+        for n in 2..400u32 {
+            let p = (Integer::from(3u32) << n) + 1u32;
+            let verdict = proth_test_cached(&p, Some(&cache)).map(|r| r.0);
+            let expected = proth_test_cached(&p, Some(&uncached)).map(|r| r.0);
+            assert_eq!(verdict, expected, "n={}", n);
+            if let Some(is_prime) = verdict {
+                assert_eq!(is_prime, p.is_probably_prime(25) != IsPrime::No, "n={}", n);
+            }
+        }
+        assert!(cache.hits() > 0);
+        assert!(cache.jacobi_evals() < uncached.jacobi_evals());
+    }
+
     /// Verifies that `llr_test` returns a non-empty seed string for the Mersenne
     /// prime M_{13} = 8191. The seed is s_0 = V_k(P, 1) mod N, the initial
     /// value of the LLR iteration sequence. For k=1, P=4 (the standard Lucas-Lehmer
@@ -1335,4 +2922,660 @@ mod tests {
             other => panic!("Expected LLR certificate, got {:?}", other),
         }
     }
+
+    // ── Interruptible Sieve ───────────────────────────────────────────
+
+    /// Coordinator stub whose stop flag turns on after `after` polls.
+    struct StopAfter {
+        polls: std::sync::atomic::AtomicUsize,
+        after: usize,
+    }
+
+    impl StopAfter {
+        fn new(after: usize) -> Self {
+            StopAfter {
+                polls: std::sync::atomic::AtomicUsize::new(0),
+                after,
+            }
+        }
+    }
+
+    impl CoordinationClient for StopAfter {
+        fn is_stop_requested(&self) -> bool {
+            self.polls.fetch_add(1, Ordering::Relaxed) >= self.after
+        }
+        fn report_prime(&self, _: &str, _: &str, _: u64, _: &str, _: &str) {}
+    }
+
+    /// (expression, proof_method) of every prime `run` records through a
+    /// fresh file sink, sorted. `run` also gets a checkpoint path in the same
+    /// temporary directory.
+    fn found_primes(
+        run: impl FnOnce(&crate::prime_sink::FilePrimeSink, &Path) -> Result<()>,
+    ) -> Vec<(String, String)> {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("primes.jsonl");
+        let sink = crate::prime_sink::FilePrimeSink::open(&out).unwrap();
+        run(&sink, &dir.path().join("kbn.checkpoint")).unwrap();
+        let mut found: Vec<(String, String)> = std::fs::read_to_string(&out)
+            .unwrap_or_default()
+            .lines()
+            .map(|line| {
+                let v: serde_json::Value = serde_json::from_str(line).unwrap();
+                let expr = v["expression"].as_str().unwrap().to_string();
+                (expr, v["proof_method"].as_str().unwrap().to_string())
+            })
+            .collect();
+        found.sort();
+        found
+    }
+
+    /// The expressions [`found_primes`] reports for `run`.
+    fn found_expressions(
+        run: impl FnOnce(&crate::prime_sink::FilePrimeSink, &Path) -> Result<()>,
+    ) -> Vec<String> {
+        found_primes(run).into_iter().map(|(expr, _)| expr).collect()
+    }
+
+    /// A stop raised mid-sieve returns early with `interrupted` set and a
+    /// partial sieve whose survivors include every full-sieve survivor.
+    #[test]
+    fn bsgs_sieve_stops_when_requested() {
+        // 5133 primes: one poll at 0, a second at STOP_CHECK_PRIMES.
+        let sieve_primes = sieve::generate_primes(50_000);
+        let (full_plus, full_minus, interrupted) =
+            bsgs_sieve(1, 2000, 3, 2, &sieve_primes, 20, None);
+        assert!(!interrupted);
+
+        let stop = StopAfter::new(1);
+        let (plus, minus, interrupted) = bsgs_sieve(1, 2000, 3, 2, &sieve_primes, 20, Some(&stop));
+        assert!(interrupted);
+        assert_eq!(stop.polls.load(Ordering::Relaxed), 2);
+        assert_eq!(plus.len(), full_plus.len());
+        assert!(plus.count_ones() > full_plus.count_ones());
+        for i in 0..plus.len() {
+            assert!(!full_plus.get(i) || plus.get(i));
+            assert!(!full_minus.get(i) || minus.get(i));
+        }
+    }
+
+    /// `search` exits cleanly on a stop during sieving: no results, no panic,
+    /// and an existing checkpoint is left as the resume point.
+    #[test]
+    fn search_exits_cleanly_on_stop_during_sieve() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let stop = StopAfter::new(0);
+        let found = found_expressions(|sink, cp| {
+            let saved = Checkpoint::Kbn {
+                last_n: 100,
+                min_n: Some(1),
+                max_n: Some(5000),
+                sieve: None,
+            };
+            checkpoint::save(cp, &saved)?;
+            search(
+                3,
+                2,
+                1,
+                5000,
+                &Progress::new(),
+                sink,
+                rt.handle(),
+                cp,
+                "{}",
+                25,
+                100_000,
+                Some(&stop),
+                None,
+            )?;
+            match checkpoint::load(cp) {
+                Some(Checkpoint::Kbn { last_n, .. }) => assert_eq!(last_n, 100),
+                _ => panic!("checkpoint should survive an interrupted sieve"),
+            }
+            Ok(())
+        });
+        assert!(found.is_empty(), "{:?}", found);
+    }
+
+    /// Sieving in four pipelined segments finds the same primes, and feeds
+    /// the testers the same survivors, as sieving the range in one pass.
+    #[test]
+    fn pipelined_search_matches_sequential() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let run = |segments: u64| {
+            let progress = Progress::new();
+            let found = found_primes(|sink, cp| {
+                search_segmented(
+                    3,
+                    2,
+                    1,
+                    600,
+                    &progress,
+                    sink,
+                    rt.handle(),
+                    cp,
+                    "{}",
+                    25,
+                    10_000,
+                    None,
+                    None,
+                    segments,
+                )?;
+                assert!(checkpoint::load(cp).is_none());
+                Ok(())
+            });
+            (found, progress.checksum.load(Ordering::Relaxed))
+        };
+        let (sequential, seq_checksum) = run(1);
+        let (pipelined, pipe_checksum) = run(4);
+        assert!(sequential.iter().any(|(e, _)| e == "3*2^1 + 1"));
+        assert!(sequential.len() > 10, "{:?}", sequential);
+        assert_eq!(pipelined, sequential);
+        assert_eq!(pipe_checksum, seq_checksum);
+    }
+
+    // ── Resumed Sieve (bsgs_sieve_resumable) ──────────────────────────
+
+    /// Resuming with a raised sieve limit sieves only the primes above the
+    /// saved limit, eliminates strictly more candidates than the saved sieve,
+    /// and matches a fresh full sieve at the new limit.
+    #[test]
+    fn resumed_sieve_applies_only_new_primes() {
+        let (k, base, min_n, max_n) = (3, 2, 1000, 3000);
+        let low = sieve::generate_primes(1_000);
+        let high = sieve::generate_primes(100_000);
+        let (first, _) = bsgs_sieve_resumable(None, min_n, max_n, k, base, &low, 1_000, 20, None);
+        assert_eq!(first.primes_sieved, low.len());
+        let state = first.to_state();
+
+        let (resumed, interrupted) =
+            bsgs_sieve_resumable(Some(&state), 1500, max_n, k, base, &high, 100_000, 20, None);
+        assert!(!interrupted);
+        assert_eq!(resumed.primes_sieved, high.len() - low.len());
+        assert_eq!((resumed.base_n, resumed.sieve_limit), (min_n, 100_000));
+        let survivors = |s: &KbnSieve| s.plus.count_ones() + s.minus.count_ones();
+        assert!(survivors(&resumed) < survivors(&first));
+
+        let (fresh_plus, fresh_minus, _) = bsgs_sieve(min_n, max_n, k, base, &high, 20, None);
+        for i in 0..fresh_plus.len() {
+            assert_eq!(resumed.plus.get(i), fresh_plus.get(i));
+            assert_eq!(resumed.minus.get(i), fresh_minus.get(i));
+        }
+
+        // Same limit again: nothing left to sieve
+        let (again, _) = bsgs_sieve_resumable(
+            Some(&resumed.to_state()),
+            2000,
+            max_n,
+            k,
+            base,
+            &high,
+            100_000,
+            20,
+            None,
+        );
+        assert_eq!(again.primes_sieved, 0);
+        assert_eq!(survivors(&again), survivors(&resumed));
+    }
+
+    /// A saved state for a different range is ignored and the range is
+    /// sieved from scratch.
+    #[test]
+    fn resumed_sieve_ignores_mismatched_state() {
+        let primes = sieve::generate_primes(1_000);
+        let (first, _) = bsgs_sieve_resumable(None, 100, 500, 3, 2, &primes, 1_000, 20, None);
+        let state = first.to_state();
+        let (other, _) =
+            bsgs_sieve_resumable(Some(&state), 100, 600, 3, 2, &primes, 1_000, 20, None);
+        assert_eq!(other.primes_sieved, primes.len());
+        assert_eq!(other.plus.len(), 501);
+    }
+
+    // ── Fixed-n Search (search_k_range) ───────────────────────────────
+
+    /// Every k the sieve clears has a sieve-prime factor, and every survivor
+    /// has none, for both signs and bases 2 and 3.
+    #[test]
+    fn k_sieve_matches_trial_division() {
+        let primes = sieve::generate_primes(1000);
+        for (base, n) in [(2u32, 64u64), (3, 40)] {
+            let bn = Integer::from(base).pow(n as u32);
+            for is_plus in [true, false] {
+                let survives = k_sieve(1, 2000, base, n, is_plus, &primes);
+                for k in 1..=2000u64 {
+                    let kb = Integer::from(&bn * k);
+                    let c = if is_plus { kb + 1u32 } else { kb - 1u32 };
+                    let divisible = primes.iter().any(|&p| c.is_divisible_u(p as u32));
+                    assert_eq!(
+                        survives.get((k - 1) as usize),
+                        !divisible,
+                        "k={} base={} n={} plus={}",
+                        k,
+                        base,
+                        n,
+                        is_plus
+                    );
+                }
+            }
+        }
+    }
+
+    /// A candidate equal to a sieve prime (k·2^3 + 1 = 41 for k = 5) is kept.
+    #[test]
+    fn k_sieve_keeps_sieve_prime_itself() {
+        let primes = sieve::generate_primes(100);
+        let survives = k_sieve(1, 10, 2, 3, true, &primes);
+        assert!(survives.get(4), "5*2^3+1 = 41 must survive");
+        assert!(!survives.get(0), "1*2^3+1 = 9 must be sieved");
+    }
+
+    fn run_k_range(n: u64, max_k: u64, is_plus: bool) -> Vec<u64> {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let found = found_expressions(|sink, cp| {
+            search_k_range(
+                2,
+                n,
+                1,
+                max_k,
+                is_plus,
+                &Progress::new(),
+                sink,
+                rt.handle(),
+                cp,
+                "{}",
+                25,
+                1000,
+                None,
+                None,
+            )
+        });
+        let mut ks: Vec<u64> = found
+            .iter()
+            .map(|expr| {
+                // Stored in the kbn format, so `verify` can rebuild it.
+                crate::verify::reconstruct_candidate("kbn", expr).unwrap();
+                expr.split('*').next().unwrap().parse().unwrap()
+            })
+            .collect();
+        ks.sort();
+        ks
+    }
+
+    /// At n = 64 the smallest Proth k is 12 and the smallest Riesel k is 3
+    /// (k·2^64 + 1 is prime for k = 12, 18, 25, 27, 72; k·2^64 − 1 for
+    /// k = 3, 77, 83, 89 below 100).
+    #[test]
+    fn search_k_range_finds_known_k_values() {
+        assert_eq!(run_k_range(64, 100, true), [12, 18, 25, 27, 72]);
+        assert_eq!(run_k_range(64, 100, false), [3, 77, 83, 89]);
+    }
+
+    /// Composite bases: past sieve_min_n, every n the sieve clears for
+    /// k*b^n ± 1 has a sieve-prime factor and is composite, and every survivor
+    /// has none. Covers k sharing a factor with b (3·6^n) and b = 10, where
+    /// ord_p(10) differs from the order of either prime factor.
+    #[test]
+    fn bsgs_sieve_handles_composite_bases() {
+        let primes = sieve::generate_primes(1000);
+        for (k, base) in [(1u64, 6u32), (5, 6), (3, 6), (1, 10), (7, 10), (3, 10)] {
+            let sieve_min_n = 5;
+            let (plus, minus, _) = bsgs_sieve(1, 150, k, base, &primes, sieve_min_n, None);
+            for n in sieve_min_n..=150u64 {
+                let kb = Integer::from(k) * Integer::from(base).pow(n as u32);
+                let idx = (n - 1) as usize;
+                let sides = [(plus.get(idx), "+"), (minus.get(idx), "-")];
+                for (survives, sign) in sides {
+                    let candidate = if sign == "+" {
+                        Integer::from(&kb + 1u32)
+                    } else {
+                        Integer::from(&kb - 1u32)
+                    };
+                    let divisible = primes.iter().any(|&p| candidate.is_divisible_u(p as u32));
+                    assert_eq!(survives, !divisible, "{}*{}^{} {} 1", k, base, n, sign);
+                    if !survives {
+                        assert_eq!(candidate.is_probably_prime(15), IsPrime::No);
+                    }
+                }
+            }
+        }
+    }
+
+    // ── Arbitrary Offset (search_c) ───────────────────────────────────
+
+    /// Past sieve_min_n, every n the offset sieve clears has a sieve-prime
+    /// factor and every survivor has none — including primes dividing k, b
+    /// and c together (6·2^n + 9 and 3·3^n + 6 are always divisible by 3).
+    #[test]
+    fn bsgs_sieve_c_matches_trial_division() {
+        let primes = sieve::generate_primes(1000);
+        for (k, base, c) in [
+            (1u64, 2u32, 3i64),
+            (5, 2, -3),
+            (3, 3, 6),
+            (6, 2, 9),
+            (7, 10, 13),
+        ] {
+            let (survives, interrupted) = bsgs_sieve_c(20, 200, k, base, c, &primes, 20, None);
+            assert!(!interrupted);
+            for n in 20..=200u64 {
+                let candidate = Integer::from(k) * Integer::from(base).pow(n as u32) + c;
+                let divisible = primes.iter().any(|&p| candidate.is_divisible_u(p as u32));
+                assert_eq!(
+                    survives.get((n - 20) as usize),
+                    !divisible,
+                    "k={} base={} c={} n={}",
+                    k,
+                    base,
+                    c,
+                    n
+                );
+            }
+        }
+    }
+
+    fn run_search_c(k: u64, c: i64, min_n: u64, max_n: u64) -> Vec<(u64, String)> {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let found = found_primes(|sink, cp| {
+            search_c(
+                k,
+                2,
+                c,
+                min_n,
+                max_n,
+                &Progress::new(),
+                sink,
+                rt.handle(),
+                cp,
+                "{}",
+                25,
+                1000,
+                None,
+                None,
+            )
+        });
+        let mut found: Vec<(u64, String)> = found
+            .into_iter()
+            .map(|(expr, proof)| {
+                let (_, tail) = expr.split_once('^').unwrap();
+                let n: u64 = tail.split(' ').next().unwrap().parse().unwrap();
+                let rebuilt = crate::verify::reconstruct_candidate("kbn", &expr).unwrap();
+                let expected = Integer::from(k) * Integer::from(2u32).pow(n as u32) + c;
+                assert_eq!(rebuilt, expected);
+                (n, proof)
+            })
+            .collect();
+        found.sort();
+        found
+    }
+
+    /// 2^n + 3 is prime for n = 12, 15, 16, 18, 28, 30, 55, 67, 84 in
+    /// [10, 100] (OEIS A057732), and 5·2^n − 3 for n = 12, 17, 20, 27, 29,
+    /// 30, 36, 62, 72, 83. Without an N±1 proof, the results above 2^64 are
+    /// recorded as probabilistic.
+    #[test]
+    fn search_c_finds_known_offset_primes() {
+        let plus3 = run_search_c(1, 3, 10, 100);
+        let ns: Vec<u64> = plus3.iter().map(|(n, _)| *n).collect();
+        assert_eq!(ns, [12, 15, 16, 18, 28, 30, 55, 67, 84]);
+        for (n, proof) in &plus3 {
+            let expected = if *n < 64 {
+                "deterministic (n<2^64)"
+            } else {
+                "probabilistic"
+            };
+            assert_eq!(proof, expected, "n={}", n);
+        }
+
+        let minus3: Vec<u64> = run_search_c(5, -3, 10, 100)
+            .iter()
+            .map(|(n, _)| *n)
+            .collect();
+        assert_eq!(minus3, [12, 17, 20, 27, 29, 30, 36, 62, 72, 83]);
+    }
+
+    /// At c = ±1 the offset search defers to test_prime and keeps its proofs.
+    #[test]
+    fn test_prime_c_keeps_proth_for_unit_offset() {
+        let candidate = Integer::from(3u32) * Integer::from(2u32).pow(100) + 1u32;
+        assert_eq!(
+            test_prime_c(&candidate, 3, 2, 100, 1, 25),
+            test_prime(&candidate, 3, 2, 100, true, 25)
+        );
+        let expr = |k, c, n| Kbn { k, base: 2, c }.expression(n);
+        assert_eq!(expr(5, -3, 7), "5*2^7 - 3");
+        assert_eq!(expr(1, 3, 12), "1*2^12 + 3");
+    }
+
+    /// `normalize_k` strips every factor of the base from k, and leaves a k
+    /// the base doesn't divide alone.
+    #[test]
+    fn normalize_k_factors_out_base_powers() {
+        assert_eq!(normalize_k(6, 2), (3, 1));
+        assert_eq!(normalize_k(24, 2), (3, 3));
+        assert_eq!(normalize_k(45, 3), (5, 2));
+        assert_eq!(normalize_k(3, 2), (3, 0));
+        assert_eq!(normalize_k(6, 4), (6, 0));
+    }
+
+    /// Searching k=6 over n in [1, 60] records exactly the primes of the
+    /// primitive k=3 search over [2, 61], under the k=3 expressions.
+    #[test]
+    fn search_normalizes_k_divisible_by_base() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let run = |k: u64, min_n: u64, max_n: u64| {
+            found_expressions(|sink, cp| {
+                search(
+                    k,
+                    2,
+                    min_n,
+                    max_n,
+                    &Progress::new(),
+                    sink,
+                    rt.handle(),
+                    cp,
+                    "{}",
+                    25,
+                    10_000,
+                    None,
+                    None,
+                )
+            })
+        };
+        let normalized = run(6, 1, 60);
+        assert!(
+            normalized.iter().any(|e| e == "3*2^2 + 1"),
+            "{:?}",
+            normalized
+        );
+        assert!(normalized.iter().all(|e| e.starts_with("3*2^")));
+        assert_eq!(normalized, run(3, 2, 61));
+    }
+
+    /// Testing the coordinator's pre-sieved survivors finds exactly the
+    /// primes a full search does, and survivors outside the block are
+    /// rejected.
+    #[test]
+    fn search_survivors_matches_search() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let run = |survivors: Option<&SieveSurvivors>| {
+            found_expressions(|sink, cp| match survivors {
+                Some(s) => search_survivors(
+                    3,
+                    2,
+                    1,
+                    400,
+                    s,
+                    &Progress::new(),
+                    sink,
+                    rt.handle(),
+                    cp,
+                    "{}",
+                    25,
+                    None,
+                    None,
+                ),
+                None => search(
+                    3,
+                    2,
+                    1,
+                    400,
+                    &Progress::new(),
+                    sink,
+                    rt.handle(),
+                    cp,
+                    "{}",
+                    25,
+                    10_000,
+                    None,
+                    None,
+                ),
+            })
+        };
+        let survivors = sieve_survivors(3, 2, 1, 400, 10_000, None).unwrap().unwrap();
+        assert!(survivors.plus.len() < 400 && survivors.minus.len() < 400);
+        // A deadline that has already passed gives up instead of sieving
+        assert_eq!(
+            sieve_survivors(3, 2, 1, 400, 10_000, Some(Instant::now())).unwrap(),
+            None
+        );
+        // So does one too deep to reach within any deadline
+        let later = Instant::now() + std::time::Duration::from_secs(3600);
+        let deep = DEADLINE_SIEVE_LIMIT + 1;
+        assert_eq!(
+            sieve_survivors(3, 2, 1, 400, deep, Some(later)).unwrap(),
+            None
+        );
+        let presieved = run(Some(&survivors));
+        assert!(presieved.contains(&"3*2^2 + 1".to_string()));
+        assert_eq!(presieved, run(None));
+
+        let stray = SieveSurvivors {
+            plus: vec![401],
+            minus: vec![],
+        };
+        let found = found_expressions(|sink, cp| {
+            let err = search_survivors(
+                3,
+                2,
+                1,
+                400,
+                &stray,
+                &Progress::new(),
+                sink,
+                rt.handle(),
+                cp,
+                "{}",
+                25,
+                None,
+                None,
+            )
+            .unwrap_err();
+            assert!(err.to_string().contains("outside"), "{}", err);
+            Ok(())
+        });
+        assert!(found.is_empty());
+    }
+
+    /// One `search` over both signs runs a single sieve pass and finds
+    /// exactly the union of separate `search_c` runs at c = +1 and c = −1,
+    /// each of which sieves on its own.
+    #[test]
+    fn search_finds_union_of_single_sign_searches() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        // (sorted expressions, sieve passes the search ran)
+        let run = |c: Option<i64>| {
+            let passes_before = SIEVE_PASSES.with(|p| p.get());
+            let found = found_expressions(|sink, cp| match c {
+                Some(c) => search_c(
+                    5,
+                    2,
+                    c,
+                    1,
+                    300,
+                    &Progress::new(),
+                    sink,
+                    rt.handle(),
+                    cp,
+                    "{}",
+                    25,
+                    10_000,
+                    None,
+                    None,
+                ),
+                // One segment, whatever KBN_PIPELINE_SEGMENTS says.
+                None => search_segmented(
+                    5,
+                    2,
+                    1,
+                    300,
+                    &Progress::new(),
+                    sink,
+                    rt.handle(),
+                    cp,
+                    "{}",
+                    25,
+                    10_000,
+                    None,
+                    None,
+                    1,
+                ),
+            });
+            (found, SIEVE_PASSES.with(|p| p.get()) - passes_before)
+        };
+        let (plus, plus_passes) = run(Some(1));
+        let (minus, minus_passes) = run(Some(-1));
+        assert!(!plus.is_empty() && !minus.is_empty());
+        assert_eq!((plus_passes, minus_passes), (1, 1));
+        let mut union: Vec<String> = plus.into_iter().chain(minus).collect();
+        union.sort();
+        union.dedup();
+        let (both, both_passes) = run(None);
+        assert_eq!(both, union);
+        assert_eq!(both_passes, 1);
+    }
+
+    /// While a search runs, `current_candidate` names the specific k*b^n±1
+    /// under test rather than the block.
+    #[test]
+    fn search_reports_current_candidate() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let progress = Progress::new();
+        let done = std::sync::atomic::AtomicBool::new(false);
+        let mut seen = Vec::new();
+        found_expressions(|sink, cp| {
+            std::thread::scope(|s| {
+                s.spawn(|| {
+                    search(
+                        3,
+                        2,
+                        4000,
+                        4100,
+                        &progress,
+                        sink,
+                        rt.handle(),
+                        cp,
+                        "{}",
+                        25,
+                        100_000,
+                        None,
+                        None,
+                    )
+                    .unwrap();
+                    done.store(true, Ordering::Relaxed);
+                });
+                while !done.load(Ordering::Relaxed) {
+                    if let Some((label, _)) = progress.current_candidate() {
+                        seen.push(label);
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(1));
+                }
+            });
+            Ok(())
+        });
+        assert!(!seen.is_empty(), "no candidate observed during the search");
+        assert!(seen
+            .iter()
+            .all(|l| l.starts_with("3*2^") && l.contains("digits")));
+        assert!(progress.current_candidate().is_none());
+    }
 }
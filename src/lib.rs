@@ -19,18 +19,27 @@
 //! - [`wagstaff`] — Wagstaff primes (2^p + 1)/3 (OEIS [A000978](https://oeis.org/A000978))
 //! - [`repunit`] — Repunit primes (b^n − 1)/(b − 1)
 //! - [`gen_fermat`] — Generalized Fermat primes b^(2^n) + 1
+//! - [`gaussian`] — Gaussian primes a + bi along a line of fixed b
 //!
 //! **Infrastructure modules** (server, coordination, proofs):
+//! - [`candidate`] — Per-form candidate construction, expression rendering and parsing
 //! - [`sieve`] — Prime generation, Montgomery multiplication, BSGS discrete log
 //! - [`proof`] — Pocklington N−1, Morrison N+1, BLS proofs
 //! - [`p1`] — Pollard P−1 composite pre-filter
 //! - [`prst`], [`pfgw`] — External tool integration (GWNUM-accelerated testing)
+//! - [`prime_sink`] — Result destination: PostgreSQL or an offline JSONL file
 //! - [`dashboard`], [`db`], [`checkpoint`], [`progress`], etc.
 //!
 //! ## Shared Utilities
 //!
 //! - `has_small_factor`: Trial division by first 64 primes (up to 311).
 //! - `mr_screened_test`: Two-round Miller–Rabin pre-screen before full test.
+//!   Returns `IsPrime::Yes` for candidates of at most 64 bits, where BPSW is
+//!   deterministic; `certainty_label` turns the result into a proof string.
+//! - `mr_screened_test_witnessed` / `frobenius_test_witnessed`: The same tests,
+//!   returning a re-checkable [`CompositenessWitness`] on rejection.
+//! - `mr_screened_test_seeded`: MR bases drawn from a seed (`--deterministic-mr`
+//!   seeds from the candidate), so a rejection replays exactly.
 //! - `estimate_digits` / `exact_digits`: Decimal digit count from bit length.
 //!
 //! ## Design Philosophy
@@ -41,6 +50,7 @@
 
 pub mod agent;
 pub mod ai_engine;
+pub mod candidate;
 pub mod carol_kynea;
 pub mod certificate;
 pub mod checkpoint;
@@ -50,18 +60,23 @@ pub mod db;
 pub mod deploy;
 pub mod events;
 pub mod factorial;
+#[cfg(feature = "native-fft")]
+pub mod fft;
 pub mod fleet;
 #[cfg(feature = "flint")]
 pub mod flint;
+pub mod gaussian;
 pub mod gen_fermat;
 pub mod gwnum;
 pub mod kbn;
+pub mod logging;
 pub mod metrics;
 pub mod near_repdigit;
 pub mod p1;
 pub mod palindromic;
 pub mod pfgw;
 pub mod pg_worker;
+pub mod prime_sink;
 pub mod primorial;
 pub mod progress;
 pub mod project;
@@ -82,9 +97,13 @@ pub mod volunteer {
     pub use crate::operator::*;
 }
 pub mod wagstaff;
+pub mod witness;
 pub mod worker_client;
 
+pub use witness::CompositenessWitness;
+
 use rug::Integer;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
 
 /// Trait for coordination clients. All nodes now use `PgWorkerClient` (PostgreSQL-backed).
 /// Search functions accept `Option<&dyn CoordinationClient>` to check for stop commands.
@@ -98,6 +117,11 @@ pub trait CoordinationClient: Send + Sync {
         search_params: &str,
         proof_method: &str,
     );
+    /// The `search_jobs` row this client works for, attached to the events a
+    /// search emits so persisted logs can be filtered by job.
+    fn search_job_id(&self) -> Option<i64> {
+        None
+    }
 }
 
 /// Small primes for trial division pre-filter.
@@ -122,6 +146,115 @@ pub fn checked_u32(n: u64) -> u32 {
     })
 }
 
+/// Process-wide `--only-proven` switch, set once from `main` before any search starts.
+static ONLY_PROVEN: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable `--only-proven` mode for all engines in this process.
+pub fn set_only_proven(enabled: bool) {
+    ONLY_PROVEN.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether `--only-proven` mode is active.
+pub fn only_proven() -> bool {
+    ONLY_PROVEN.load(Ordering::Relaxed)
+}
+
+/// True if an engine certainty string denotes an unproven (PRP) result.
+///
+/// Engines report "probabilistic", "probabilistic (Vrba-Reix)", or PFGW's "PRP"
+/// for results without a deterministic proof; everything else is proven.
+pub fn is_probabilistic(certainty: &str) -> bool {
+    certainty.starts_with("probabilistic") || certainty == "PRP"
+}
+
+fn defers_result(only_proven: bool, certainty: &str) -> bool {
+    only_proven && is_probabilistic(certainty)
+}
+
+/// Divert a probabilistic result under `--only-proven`.
+///
+/// Returns `Ok(true)` when the result was parked in `prp_candidates` and a
+/// `PrpFound` event emitted; the caller must then skip the `found` counter,
+/// `PrimeFound` event, and `PrimeBuffer::push`. Returns `Ok(false)` (no side
+/// effects) for proven results or when the mode is off.
+#[allow(clippy::too_many_arguments)]
+pub fn defer_unproven(
+    db: &dyn prime_sink::PrimeSink,
+    rt: &tokio::runtime::Handle,
+    event_bus: Option<&events::EventBus>,
+    search_job_id: Option<i64>,
+    form: &str,
+    expression: &str,
+    digits: u64,
+    search_params: &str,
+    certainty: &str,
+) -> anyhow::Result<bool> {
+    if !defers_result(only_proven(), certainty) {
+        return Ok(false);
+    }
+    db.insert_prp_candidate_sync(rt, form, expression, digits, search_params, certainty)?;
+    if let Some(eb) = event_bus {
+        eb.emit(events::Event::PrpFound {
+            form: form.to_string(),
+            expression: expression.to_string(),
+            digits,
+            proof_method: certainty.to_string(),
+            search_job_id,
+            timestamp: std::time::Instant::now(),
+        });
+    }
+    Ok(true)
+}
+
+/// Process-wide `--verify-on-find` digit cap, set once from `main`; 0 is off.
+static VERIFY_ON_FIND_MAX_DIGITS: AtomicU64 = AtomicU64::new(0);
+
+/// Enable `--verify-on-find` for results of up to `max_digits` digits, or
+/// disable it with `None`.
+pub fn set_verify_on_find(max_digits: Option<u64>) {
+    VERIFY_ON_FIND_MAX_DIGITS.store(max_digits.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Prove a probabilistic result at discovery under `--verify-on-find`.
+///
+/// Runs the form's deterministic proof ([`verify::prove_probable_prime`])
+/// and returns its proof method and certificate in place of `certainty` and
+/// `certificate`, so what is stored is final. Proven results, results above
+/// the digit cap, and proofs that fail or don't exist for the form pass
+/// through unchanged and are left to the background proof pass. Engines
+/// whose form has a proof call this before [`defer_unproven`].
+pub fn prove_on_find(
+    form: &str,
+    expression: &str,
+    digits: u64,
+    certainty: String,
+    certificate: Option<String>,
+) -> (String, Option<String>) {
+    let max_digits = VERIFY_ON_FIND_MAX_DIGITS.load(Ordering::Relaxed);
+    prove_within(max_digits, form, expression, digits, certainty, certificate)
+}
+
+fn prove_within(
+    max_digits: u64,
+    form: &str,
+    expression: &str,
+    digits: u64,
+    certainty: String,
+    certificate: Option<String>,
+) -> (String, Option<String>) {
+    if max_digits == 0 || digits > max_digits || !is_probabilistic(&certainty) {
+        return (certainty, certificate);
+    }
+    match verify::prove_probable_prime(form, expression) {
+        Ok(Some(proven)) => proven,
+        Ok(None) => (certainty, certificate),
+        Err(e) => {
+            tracing::warn!(form, expression, error = %e, "Inline proof failed");
+            (certainty, certificate)
+        }
+    }
+}
+
 /// Quick check if n is divisible by any small prime.
 /// Returns true if n is definitely composite (has a small factor).
 /// Returns false if n might be prime (passed trial division).
@@ -137,23 +270,294 @@ pub fn has_small_factor(n: &Integer) -> bool {
     false
 }
 
+/// Process-wide `--frobenius-min-bits` threshold, set once from `main`.
+static FROBENIUS_MIN_BITS: AtomicU64 = AtomicU64::new(10_000);
+
+/// Set the bit size above which `mr_screened_test` runs the Frobenius filter.
+///
+/// 0 runs it on every candidate; `u64::MAX` disables it.
+pub fn set_frobenius_min_bits(bits: u64) {
+    FROBENIUS_MIN_BITS.store(bits, Ordering::Relaxed);
+}
+
+/// Current Frobenius activation threshold in bits.
+pub fn frobenius_min_bits() -> u64 {
+    FROBENIUS_MIN_BITS.load(Ordering::Relaxed)
+}
+
+/// Primality-test preset selected with `--test-profile`.
+///
+/// - `survey`: 1 MR round, with Frobenius above the configured
+///   `--frobenius-min-bits` as in `standard` — for broad surveys where proof
+///   is not the goal.
+/// - `standard`: the plain `--mr-rounds` / `--frobenius-min-bits` screen.
+/// - `rigorous`: at least 25 MR rounds plus Frobenius on every candidate, and
+///   unproven results are deferred as under `--only-proven`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TestProfile {
+    Survey,
+    #[default]
+    Standard,
+    Rigorous,
+}
+
+impl TestProfile {
+    /// MR rounds the profile runs when the caller asks for `requested`.
+    pub fn mr_rounds(self, requested: u32) -> u32 {
+        match self {
+            TestProfile::Survey => 1,
+            TestProfile::Standard => requested,
+            TestProfile::Rigorous => requested.max(25),
+        }
+    }
+
+    /// Frobenius threshold in bits, given the configured `--frobenius-min-bits`.
+    pub fn frobenius_min_bits(self, configured: u64) -> u64 {
+        match self {
+            TestProfile::Survey | TestProfile::Standard => configured,
+            TestProfile::Rigorous => 0,
+        }
+    }
+
+    /// Worst-case screen cost for a `bits`-bit candidate that passes, in
+    /// single MR round equivalents (a Frobenius test counts as 3).
+    pub fn screen_cost(self, requested: u32, configured: u64, bits: u64) -> u32 {
+        let rounds = self.mr_rounds(requested);
+        let prescreen = if rounds > 2 { 2 } else { 0 };
+        let frobenius = if bits > self.frobenius_min_bits(configured) {
+            3
+        } else {
+            0
+        };
+        prescreen + rounds + frobenius
+    }
+
+    /// Whether only deterministically proven primes may be recorded.
+    pub fn requires_proof(self) -> bool {
+        self == TestProfile::Rigorous
+    }
+}
+
+impl std::str::FromStr for TestProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "survey" => Ok(TestProfile::Survey),
+            "standard" => Ok(TestProfile::Standard),
+            "rigorous" => Ok(TestProfile::Rigorous),
+            _ => anyhow::bail!("unknown test profile {s:?} (survey, standard, rigorous)"),
+        }
+    }
+}
+
+/// Process-wide `--test-profile`, set once from `main`.
+static TEST_PROFILE: AtomicU8 = AtomicU8::new(TestProfile::Standard as u8);
+
+/// Select the preset `mr_screened_test` applies in every engine.
+pub fn set_test_profile(profile: TestProfile) {
+    TEST_PROFILE.store(profile as u8, Ordering::Relaxed);
+}
+
+/// Current `--test-profile`.
+pub fn test_profile() -> TestProfile {
+    match TEST_PROFILE.load(Ordering::Relaxed) {
+        0 => TestProfile::Survey,
+        2 => TestProfile::Rigorous,
+        _ => TestProfile::Standard,
+    }
+}
+
+/// Bit size up to which a BPSW probable prime is a proven prime.
+///
+/// GMP's `is_probably_prime` always runs Baillie-PSW, and the Feitsma-Galway
+/// enumeration of base-2 strong pseudoprimes below 2^64 shows none of them
+/// passes the strong Lucas test.
+pub const BPSW_DETERMINISTIC_BITS: u32 = 64;
+
+/// Proof label for primes proven by the sub-2^64 BPSW bound.
+pub const SMALL_PRIME_PROOF: &str = "deterministic (n<2^64)";
+
+/// True if `candidate` is small enough for BPSW to be deterministic.
+pub fn is_below_bpsw_bound(candidate: &Integer) -> bool {
+    candidate.significant_bits() <= BPSW_DETERMINISTIC_BITS
+}
+
+/// Certainty string for a `mr_screened_test` result.
+///
+/// `Yes` on a candidate below the BPSW bound maps to [`SMALL_PRIME_PROOF`],
+/// any other `Yes` to "deterministic", `Probably` to "probabilistic", and
+/// `No` to an empty string.
+pub fn certainty_label(candidate: &Integer, result: rug::integer::IsPrime) -> &'static str {
+    use rug::integer::IsPrime;
+    match result {
+        IsPrime::Yes if is_below_bpsw_bound(candidate) => SMALL_PRIME_PROOF,
+        IsPrime::Yes => "deterministic",
+        IsPrime::Probably => "probabilistic",
+        IsPrime::No => "",
+    }
+}
+
 /// Two-round Miller-Rabin pre-screening: run 2 fast rounds first, full rounds only for survivors.
 /// Composites are rejected ~7x faster since most fail within 2 rounds.
 ///
-/// For large candidates (>10K bits), also runs a Frobenius quadratic test that
-/// catches composites MR occasionally misses (false positive < 1/7710 per round
-/// vs MR's 1/4, at ~3× the cost of a single MR round).
+/// For large candidates (above `--frobenius-min-bits`, default 10K bits), also
+/// runs a Frobenius quadratic test that catches composites MR occasionally
+/// misses (false positive < 1/7710 per round vs MR's 1/4, at ~3× the cost of a
+/// single MR round).
+///
+/// Candidates of at most [`BPSW_DETERMINISTIC_BITS`] bits that pass are
+/// reported as `IsPrime::Yes`: BPSW has no pseudoprimes in that range.
+///
+/// The active [`TestProfile`] adjusts both the round count and the Frobenius
+/// threshold.
 #[inline]
 pub fn mr_screened_test(candidate: &Integer, mr_rounds: u32) -> rug::integer::IsPrime {
+    profile_screened_test(candidate, mr_rounds, test_profile())
+}
+
+/// [`mr_screened_test`] that explains a rejection.
+///
+/// `Ok(())` means the candidate passed (probably or provably prime). On
+/// rejection the returned [`CompositenessWitness`] is found deterministically,
+/// so it is the same every run and [`CompositenessWitness::verify`]
+/// re-confirms it without trusting GMP. The witness is logged at debug level.
+pub fn mr_screened_test_witnessed(
+    candidate: &Integer,
+    mr_rounds: u32,
+) -> Result<(), CompositenessWitness> {
+    if mr_screened_test(candidate, mr_rounds) != rug::integer::IsPrime::No {
+        return Ok(());
+    }
+    let witness = witness::find_witness(candidate);
+    tracing::debug!(
+        bits = candidate.significant_bits(),
+        %witness,
+        "candidate rejected as composite"
+    );
+    Err(witness)
+}
+
+fn profile_screened_test(
+    candidate: &Integer,
+    mr_rounds: u32,
+    profile: TestProfile,
+) -> rug::integer::IsPrime {
+    screened_test_with_threshold(
+        candidate,
+        profile.mr_rounds(mr_rounds),
+        profile.frobenius_min_bits(frobenius_min_bits()),
+    )
+}
+
+fn frobenius_applies(candidate: &Integer, min_bits: u64) -> bool {
+    u64::from(candidate.significant_bits()) > min_bits
+}
+
+fn screened_test_with_threshold(
+    candidate: &Integer,
+    mr_rounds: u32,
+    frobenius_min_bits: u64,
+) -> rug::integer::IsPrime {
+    let seed = deterministic_mr().then(|| candidate_seed(candidate));
+    seeded_screened_test(candidate, mr_rounds, frobenius_min_bits, seed)
+}
+
+fn seeded_screened_test(
+    candidate: &Integer,
+    mr_rounds: u32,
+    frobenius_min_bits: u64,
+    seed: Option<u64>,
+) -> rug::integer::IsPrime {
     use rug::integer::IsPrime;
     if mr_rounds > 2 && candidate.is_probably_prime(2) == IsPrime::No {
         return IsPrime::No;
     }
-    // Frobenius filter for candidates > 10K bits (where each MR round is expensive)
-    if candidate.significant_bits() > 10_000 && !frobenius_test(candidate) {
+    // Frobenius filter for large candidates (where each MR round is expensive)
+    if frobenius_applies(candidate, frobenius_min_bits) && !frobenius_test(candidate) {
         return IsPrime::No;
     }
-    candidate.is_probably_prime(mr_rounds)
+    let result = match seed {
+        None => candidate.is_probably_prime(mr_rounds),
+        // Trial division and BPSW only; the MR rounds use the seeded bases.
+        Some(seed) => match candidate.is_probably_prime(1) {
+            IsPrime::Probably if seeded_witness_round(candidate, mr_rounds, seed).is_some() => {
+                IsPrime::No
+            }
+            r => r,
+        },
+    };
+    match result {
+        IsPrime::Probably if is_below_bpsw_bound(candidate) => IsPrime::Yes,
+        r => r,
+    }
+}
+
+// ---- Seeded Miller-Rabin ----
+
+/// Process-wide `--deterministic-mr` switch, set once from `main`.
+static DETERMINISTIC_MR: AtomicBool = AtomicBool::new(false);
+
+/// Seed `mr_screened_test`'s Miller-Rabin bases from each candidate.
+pub fn set_deterministic_mr(enabled: bool) {
+    DETERMINISTIC_MR.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether `--deterministic-mr` is active.
+pub fn deterministic_mr() -> bool {
+    DETERMINISTIC_MR.load(Ordering::Relaxed)
+}
+
+/// Seed derived from the candidate under `--deterministic-mr`: FNV-1a over
+/// its little-endian bytes, so it is stable across runs and platforms.
+pub fn candidate_seed(candidate: &Integer) -> u64 {
+    candidate
+        .to_digits::<u8>(rug::integer::Order::Lsf)
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// [`mr_screened_test`] with Miller-Rabin bases drawn from a random state
+/// seeded with `seed`.
+///
+/// GMP picks its own bases for the rounds beyond BPSW, so a composite that
+/// slips through is hard to reproduce. Here the same `(candidate, seed)`
+/// always tests the same bases in the same order. `--deterministic-mr` runs
+/// every screen this way with the seed from [`candidate_seed`].
+pub fn mr_screened_test_seeded(
+    candidate: &Integer,
+    mr_rounds: u32,
+    seed: u64,
+) -> rug::integer::IsPrime {
+    let profile = test_profile();
+    seeded_screened_test(
+        candidate,
+        profile.mr_rounds(mr_rounds),
+        profile.frobenius_min_bits(frobenius_min_bits()),
+        Some(seed),
+    )
+}
+
+/// The `rounds` Miller-Rabin bases in `[2, n − 2]` for `seed`.
+fn seeded_bases(candidate: &Integer, rounds: u32, seed: u64) -> Vec<Integer> {
+    if *candidate < 5u32 {
+        return Vec::new();
+    }
+    let mut rand = rug::rand::RandState::new();
+    rand.seed(&Integer::from(seed));
+    let span = Integer::from(candidate - 3u32);
+    (0..rounds)
+        .map(|_| Integer::from(span.random_below_ref(&mut rand)) + 2u32)
+        .collect()
+}
+
+/// 0-based round whose seeded base proves `candidate` composite, if any.
+fn seeded_witness_round(candidate: &Integer, rounds: u32, seed: u64) -> Option<usize> {
+    seeded_bases(candidate, rounds, seed)
+        .iter()
+        .position(|base| witness::is_strong_witness(candidate, base))
 }
 
 // ---- Frobenius quadratic compositeness test ----
@@ -247,6 +651,7 @@ fn poly_pow_mod(exp: &Integer, coeff_b: &Integer, coeff_c: &Integer, n: &Integer
 /// **Cost**: ~3× one MR round (polynomial ring exponentiation + Euler check).
 ///
 /// Returns `true` if `n` passes (probably prime), `false` if definitely composite.
+/// [`frobenius_test_witnessed`] returns the data behind a `false`.
 ///
 /// # References
 ///
@@ -254,12 +659,25 @@ fn poly_pow_mod(exp: &Integer, coeff_b: &Integer, coeff_c: &Integer, n: &Integer
 ///   70(234):873–891, 2001.
 /// - Crandall & Pomerance, "Prime Numbers: A Computational Perspective", §3.5.
 pub fn frobenius_test(n: &Integer) -> bool {
+    frobenius_test_witnessed(n).is_ok()
+}
+
+/// [`frobenius_test`] that returns what disqualified `n`: a factor found along
+/// the way, or the (b, c) and residue that violated the Euler or automorphism
+/// check. The witness can be re-checked with [`CompositenessWitness::verify`].
+pub fn frobenius_test_witnessed(n: &Integer) -> Result<(), CompositenessWitness> {
+    let factor = |f: Integer| Err(CompositenessWitness::Factor { factor: f });
+
     // Trivial cases
     if *n <= 2u32 {
-        return *n == 2u32;
+        return if *n == 2u32 {
+            Ok(())
+        } else {
+            Err(CompositenessWitness::LessThanTwo)
+        };
     }
     if n.is_even() {
-        return false;
+        return factor(Integer::from(2u32));
     }
 
     // Check for perfect squares — sqrt(n)² == n means n is composite.
@@ -267,15 +685,13 @@ pub fn frobenius_test(n: &Integer) -> bool {
     {
         let s = n.clone().sqrt();
         if &(Integer::from(&s * &s)) == n {
-            return false;
+            return factor(s);
         }
     }
 
     // Find (b, c) with Jacobi(b² − 4c, n) = −1.
     // Prefer c ≥ 2 so the Euler criterion c^((n-1)/2) ≡ Jacobi(c,n) is non-trivial.
-    let mut coeff_b = Integer::new();
-    let mut coeff_c = Integer::new();
-    let mut found = false;
+    let mut found = None;
 
     'outer: for c in 2u32..=20 {
         for b in 1u32..=50 {
@@ -284,53 +700,54 @@ pub fn frobenius_test(n: &Integer) -> bool {
             if j == -1 {
                 let g = disc.clone().abs().gcd(n);
                 if g == 1u32 || &g == n {
-                    coeff_b = Integer::from(b);
-                    coeff_c = Integer::from(c);
-                    found = true;
+                    found = Some((b, c));
                     break 'outer;
                 }
-                return false; // non-trivial gcd → composite
+                return factor(g); // non-trivial gcd → composite
             }
             if j == 0 {
                 let g = disc.clone().abs().gcd(n);
                 if g > 1u32 && &g < n {
-                    return false;
+                    return factor(g);
                 }
             }
         }
     }
 
     // Fallback: try c=1 if nothing found with c ≥ 2
-    if !found {
+    if found.is_none() {
         'fallback: for b in 1u32..=100 {
             let disc = Integer::from(b * b) - Integer::from(4u32);
             let j = disc.jacobi(n);
             if j == -1 {
                 let g = disc.clone().abs().gcd(n);
                 if g == 1u32 || &g == n {
-                    coeff_b = Integer::from(b);
-                    coeff_c = Integer::from(1u32);
-                    found = true;
+                    found = Some((b, 1));
                     break 'fallback;
                 }
-                return false;
+                return factor(g);
             }
             if j == 0 {
                 let g = disc.clone().abs().gcd(n);
                 if g > 1u32 && &g < n {
-                    return false;
+                    return factor(g);
                 }
             }
         }
     }
 
-    if !found {
-        return true; // extremely rare — can't run the test
-    }
+    let Some((b, c)) = found else {
+        return Ok(()); // extremely rare — can't run the test
+    };
+    let coeff_b = Integer::from(b);
+    let coeff_c = Integer::from(c);
 
     if n.is_divisible(&coeff_c) {
         // c | n means n has a small factor (c ≤ 20) → composite (unless n = c)
-        return coeff_c == *n;
+        if coeff_c == *n {
+            return Ok(());
+        }
+        return factor(coeff_c);
     }
 
     // === Check 1: Euler criterion on c ===
@@ -338,7 +755,7 @@ pub fn frobenius_test(n: &Integer) -> bool {
     let jacobi_c = coeff_c.clone().jacobi(n);
     if jacobi_c == 0 {
         // c and n share a factor → composite (since c ≤ 20)
-        return false;
+        return factor(coeff_c.gcd(n));
     }
     let half_nm1 = Integer::from(n - 1u32) >> 1u32;
     if let Ok(euler_val) = coeff_c.clone().pow_mod(&half_nm1, n) {
@@ -348,7 +765,9 @@ pub fn frobenius_test(n: &Integer) -> bool {
             Integer::from(n - 1u32) // -1 mod n
         };
         if euler_val != expected_euler {
-            return false; // Euler criterion fails → composite
+            // Euler criterion fails → composite
+            let residue = euler_val;
+            return Err(CompositenessWitness::Euler { c, residue });
         }
     }
 
@@ -360,7 +779,12 @@ pub fn frobenius_test(n: &Integer) -> bool {
     let expected_r0 = Integer::from(&coeff_b % n);
     let expected_r1 = Integer::from(n - 1u32); // -1 mod n
 
-    result[0] == expected_r0 && result[1] == expected_r1
+    if result[0] == expected_r0 && result[1] == expected_r1 {
+        Ok(())
+    } else {
+        let residue = result;
+        Err(CompositenessWitness::Frobenius { b, c, residue })
+    }
 }
 
 /// Estimate decimal digit count from bit length, avoiding expensive to_string conversion.
@@ -379,6 +803,23 @@ pub fn exact_digits(n: &Integer) -> u64 {
     n.to_string_radix(10).len() as u64
 }
 
+/// `s` if it has at most `max` chars, otherwise its first `max - 3` chars
+/// followed by `...`, so the result never exceeds `max` chars.
+///
+/// Counts and cuts by `char`, so a multi-byte character is never split. For
+/// ASCII this matches the `len() > max` check and `&s[..max - 3]` slice it
+/// replaces.
+pub fn truncate_display(s: &str, max: usize) -> String {
+    if s.chars().nth(max).is_none() {
+        return s.to_string();
+    }
+    let cut = s
+        .char_indices()
+        .nth(max.saturating_sub(3))
+        .map_or(s.len(), |(i, _)| i);
+    format!("{}...", &s[..cut])
+}
+
 /// Redact a database URL for safe logging. Replaces the password with `***`
 /// while preserving the scheme, username, host, port, and database name.
 ///
@@ -594,6 +1035,181 @@ mod tests {
         }
     }
 
+    /// BPSW is deterministic up to 64 bits: the largest 64-bit prime,
+    /// 2^64 - 59, comes back `Yes` and is labelled with the small-prime proof.
+    #[test]
+    fn mr_screened_test_deterministic_below_2_pow_64() {
+        let p = (Integer::from(1u32) << 64u32) - 59u32;
+        assert_eq!(p.significant_bits(), 64);
+        assert_eq!(mr_screened_test(&p, 25), IsPrime::Yes);
+        assert_eq!(certainty_label(&p, IsPrime::Yes), SMALL_PRIME_PROOF);
+        // 39916801 = 11! + 1 (26 bits)
+        let f = Integer::from(39_916_801u32);
+        assert_eq!(
+            certainty_label(&f, mr_screened_test(&f, 25)),
+            SMALL_PRIME_PROOF
+        );
+    }
+
+    /// Just above the bound the result stays probabilistic: 2^64 + 13 is the
+    /// smallest 65-bit prime.
+    #[test]
+    fn mr_screened_test_probabilistic_above_2_pow_64() {
+        let p = (Integer::from(1u32) << 64u32) + 13u32;
+        assert_eq!(p.significant_bits(), 65);
+        assert!(!is_below_bpsw_bound(&p));
+        let r = mr_screened_test(&p, 25);
+        assert_eq!(r, IsPrime::Probably);
+        assert_eq!(certainty_label(&p, r), "probabilistic");
+        // Composites are still rejected on both sides of the bound
+        let c = (Integer::from(1u32) << 64u32) + 1u32; // 274177 * 67280421310721
+        assert_eq!(mr_screened_test(&c, 25), IsPrime::No);
+        assert_eq!(certainty_label(&c, IsPrime::No), "");
+    }
+
+    /// With the threshold at 0 the Frobenius filter runs even on small
+    /// candidates and rejects composites; the result agrees with plain MR.
+    #[test]
+    fn frobenius_threshold_zero_filters_small_composites() {
+        for &c in &[9u32, 15, 21, 1001, 561, 1105] {
+            let n = Integer::from(c);
+            assert!(frobenius_applies(&n, 0));
+            assert!(!frobenius_test(&n), "Frobenius accepted composite {}", c);
+            assert_eq!(screened_test_with_threshold(&n, 2, 0), IsPrime::No);
+        }
+        for &p in &[3u32, 101, 10007] {
+            let n = Integer::from(p);
+            assert_ne!(screened_test_with_threshold(&n, 25, 0), IsPrime::No);
+        }
+    }
+
+    /// A threshold of u64::MAX skips the Frobenius filter for any size,
+    /// leaving the decision to Miller-Rabin alone.
+    #[test]
+    fn frobenius_threshold_max_skips_filter() {
+        let big = Integer::from(Integer::u_pow_u(2, 20_000)) + 1u32;
+        assert!(frobenius_applies(&big, 10_000));
+        assert!(!frobenius_applies(&big, u64::MAX));
+        assert_eq!(
+            screened_test_with_threshold(&Integer::from(1001u32), 25, u64::MAX),
+            IsPrime::No
+        );
+    }
+
+    /// The default threshold matches the historical 10K-bit cutoff.
+    #[test]
+    fn frobenius_min_bits_defaults_to_10k() {
+        assert_eq!(frobenius_min_bits(), 10_000);
+    }
+
+    // ── Seeded Miller-Rabin ────────────────────────────────────────────
+
+    /// 12403 = 79 · 157 has strong liars for about a quarter of all bases, so
+    /// the rejecting round varies with the seed but never between two runs
+    /// with the same seed.
+    #[test]
+    fn seeded_mr_rejects_at_the_same_round() {
+        let n = Integer::from(12_403u32);
+        let rounds: Vec<usize> = (0..50u64)
+            .map(|seed| {
+                let round = seeded_witness_round(&n, 20, seed).expect("no witness");
+                assert_eq!(seeded_witness_round(&n, 20, seed), Some(round));
+                round
+            })
+            .collect();
+        assert!(rounds.iter().any(|&r| r > 0), "{:?}", rounds);
+        for seed in [0, 7, candidate_seed(&n)] {
+            assert_eq!(mr_screened_test_seeded(&n, 20, seed), IsPrime::No);
+        }
+        let p = Integer::from(1_000_003u32);
+        assert_eq!(mr_screened_test_seeded(&p, 20, 7), IsPrime::Yes);
+    }
+
+    /// With seeds derived from the candidate, different candidates test
+    /// different bases, and the same candidate always tests the same ones.
+    #[test]
+    fn candidate_seeds_give_distinct_sequences() {
+        let a = Integer::from(12_403u32);
+        let b = Integer::from(1_891u32);
+        assert_eq!(candidate_seed(&a), candidate_seed(&a.clone()));
+        assert_ne!(candidate_seed(&a), candidate_seed(&b));
+        let bases = |n: &Integer| seeded_bases(n, 10, candidate_seed(n));
+        assert_eq!(bases(&a), bases(&a));
+        assert_ne!(bases(&a), bases(&b));
+        for base in bases(&a) {
+            assert!((2u32..=12_401u32).contains(&base), "{}", base);
+        }
+    }
+
+    // ── Test Profiles ──────────────────────────────────────────────────
+
+    /// Every profile accepts a prime and rejects a composite, including a
+    /// Carmichael number (561) and a large semiprime above the Frobenius
+    /// threshold of `rigorous`.
+    #[test]
+    fn test_profiles_classify_prime_and_composite() {
+        let prime = Integer::from(Integer::u_pow_u(2, 127)) - 1u32;
+        let semiprime = Integer::from(&prime * 1_000_003u32);
+        let small_prime = Integer::from(10_007u32);
+        let carmichael = Integer::from(561u32);
+        for profile in [
+            TestProfile::Survey,
+            TestProfile::Standard,
+            TestProfile::Rigorous,
+        ] {
+            let test = |n: &Integer| profile_screened_test(n, 15, profile);
+            assert_ne!(test(&prime), IsPrime::No);
+            assert_eq!(test(&small_prime), IsPrime::Yes);
+            assert_eq!(test(&carmichael), IsPrime::No);
+            assert_eq!(test(&semiprime), IsPrime::No);
+        }
+    }
+
+    /// `survey` runs fewer MR rounds than `standard` at the same Frobenius
+    /// threshold; `rigorous` runs at least 25 and always runs Frobenius.
+    #[test]
+    fn test_profile_round_counts() {
+        let survey = TestProfile::Survey;
+        let rigorous = TestProfile::Rigorous;
+        assert!(survey.mr_rounds(15) < TestProfile::Standard.mr_rounds(15));
+        assert_eq!(rigorous.mr_rounds(15), 25);
+        assert_eq!(rigorous.mr_rounds(40), 40);
+        assert_eq!(survey.frobenius_min_bits(10_000), 10_000);
+        assert_eq!(TestProfile::Standard.frobenius_min_bits(10_000), 10_000);
+        assert_eq!(rigorous.frobenius_min_bits(10_000), 0);
+        assert!(rigorous.requires_proof() && !survey.requires_proof());
+    }
+
+    /// `survey` never costs more per candidate than `standard`, below and
+    /// above the Frobenius threshold, including with `--mr-rounds 1`.
+    #[test]
+    fn test_profile_survey_costs_no_more_than_standard() {
+        for requested in [1, 2, 15, 40] {
+            for bits in [64, 9_999, 10_000, 10_001, 1_000_000] {
+                let survey = TestProfile::Survey.screen_cost(requested, 10_000, bits);
+                let standard = TestProfile::Standard.screen_cost(requested, 10_000, bits);
+                assert!(
+                    survey <= standard,
+                    "rounds {requested}, {bits} bits: survey {survey} > standard {standard}"
+                );
+            }
+        }
+        assert!(
+            TestProfile::Survey.screen_cost(15, 10_000, 20_000)
+                < TestProfile::Standard.screen_cost(15, 10_000, 20_000)
+        );
+    }
+
+    /// Profile names parse; anything else is rejected.
+    #[test]
+    fn test_profile_parses_names() {
+        let parse = |s: &str| s.parse::<TestProfile>().ok();
+        assert_eq!(parse("survey"), Some(TestProfile::Survey));
+        assert_eq!(parse("rigorous"), Some(TestProfile::Rigorous));
+        assert_eq!(parse("fast"), None);
+        assert_eq!(test_profile(), TestProfile::Standard);
+    }
+
     // ── Digit Estimation (estimate_digits / exact_digits) ──────────────
 
     /// Verifies that `estimate_digits` (using bit_length * log10(2)) agrees
@@ -675,6 +1291,56 @@ mod tests {
         checked_u32(u32::MAX as u64 + 1);
     }
 
+    // ── Only-Proven Gate (defer_unproven) ──────────────────────────────
+
+    /// Factorial's GMP path reports a bare "probabilistic" when neither
+    /// Pocklington nor Morrison succeeds; under `--only-proven` such a result
+    /// must be deferred to `prp_candidates` rather than inserted.
+    #[test]
+    fn only_proven_defers_probabilistic_factorial_result() {
+        assert!(defers_result(true, "probabilistic"));
+        assert!(defers_result(true, "probabilistic (Vrba-Reix)"));
+        assert!(defers_result(true, "PRP"));
+        assert!(!defers_result(false, "probabilistic"));
+    }
+
+    /// A kbn Proth result is deterministic and is inserted as usual with or
+    /// without `--only-proven`.
+    #[test]
+    fn only_proven_keeps_deterministic_proth_result() {
+        assert!(!defers_result(true, "deterministic (Proth)"));
+        assert!(!defers_result(true, "deterministic"));
+        assert!(!defers_result(true, "deterministic (Pocklington N-1)"));
+        assert!(!is_probabilistic("deterministic (LLR)"));
+    }
+
+    // ── Inline Proofs (prove_on_find) ──────────────────────────────────
+
+    /// A small Proth prime reported as a PRP (as by the PFGW path) is
+    /// proven inline and stored as deterministic, with the cap off it is
+    /// untouched, and an already proven result is not re-proven.
+    #[test]
+    fn verify_on_find_proves_small_proth_prime() {
+        let prp = || "probabilistic".to_string();
+        let (method, _) = prove_within(10_000, "kbn", "3*2^5 + 1", 2, prp(), None);
+        assert_eq!(method, "deterministic");
+        let (method, _) = prove_within(0, "kbn", "3*2^5 + 1", 2, prp(), None);
+        assert_eq!(method, "probabilistic");
+        let proven = "deterministic (Proth)".to_string();
+        let (method, _) = prove_within(10_000, "kbn", "3*2^5 + 1", 2, proven, None);
+        assert_eq!(method, "deterministic (Proth)");
+    }
+
+    /// A factorial prime above the digit cap skips the inline proof and is
+    /// stored as probabilistic; the same form under the cap is proven.
+    #[test]
+    fn verify_on_find_skips_factorial_prime_above_cap() {
+        let (method, cert) = prove_within(100, "factorial", "116! + 1", 191, "PRP".into(), None);
+        assert_eq!((method.as_str(), cert), ("PRP", None));
+        let (method, _) = prove_within(100, "factorial", "11! + 1", 8, "PRP".into(), None);
+        assert!(method.starts_with("deterministic"), "{}", method);
+    }
+
     // ── Block Sizing (block_size_for_n / block_size_for_n_heavy) ────────
 
     /// Verifies the block size at each match-arm boundary for the standard
@@ -1511,4 +2177,23 @@ mod tests {
         assert!(redacted.contains("***"), "password should be replaced with ***");
         assert!(redacted.contains("supabase.com"), "host should be preserved");
     }
+
+    /// Cuts land on char boundaries even when the limit falls inside a
+    /// multi-byte character; strings up to `max` chars are left whole, as
+    /// the `len() > 38` checks this replaced left 36–38 char expressions.
+    #[test]
+    fn truncate_display_respects_char_boundaries() {
+        // '⁵' and '−' are 3 bytes each: byte 4 falls inside the '⁵'.
+        assert_eq!(truncate_display("3*2⁵⁰ − 1", 7), "3*2⁵...");
+        assert_eq!(truncate_display("3*2⁵⁰ − 1", 9), "3*2⁵⁰ − 1");
+        assert_eq!(truncate_display("", 0), "");
+
+        for len in 36..=38 {
+            let s = "7".repeat(len);
+            assert_eq!(truncate_display(&s, 38), s);
+        }
+        let ascii = "104723*2^1234567 + 1 (found by worker-0042)";
+        assert_eq!(truncate_display(ascii, 38), format!("{}...", &ascii[..35]));
+        assert_eq!(truncate_display("7! + 1", 38), "7! + 1");
+    }
 }
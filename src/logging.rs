@@ -0,0 +1,186 @@
+//! # Logging — Log Output Format Selection
+//!
+//! Builds the process-wide `tracing` subscriber. Two formats are supported:
+//!
+//! - **human** (default): compact single-line text on stderr, without targets.
+//! - **json**: one JSON object per line on stdout, for log aggregators such as
+//!   Loki or CloudWatch. Event fields (`form`, `expression`, `digits`, …) are
+//!   emitted as keys under `"fields"` rather than interpolated into the message.
+//!
+//! The format comes from `--log-format` or the `LOG_FORMAT` environment
+//! variable; an unknown value logs a warning and falls back to human.
+//! Filtering follows `RUST_LOG`, defaulting to [`DEFAULT_FILTER`].
+
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Filter used when `RUST_LOG` is unset or invalid.
+pub const DEFAULT_FILTER: &str = "darkreach=info,tower_http=info";
+
+/// Log line format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "human" | "text" | "" => Ok(LogFormat::Human),
+            "json" => Ok(LogFormat::Json),
+            other => anyhow::bail!("unknown log format '{}' (expected human or json)", other),
+        }
+    }
+}
+
+/// `RUST_LOG` if set and valid, otherwise [`DEFAULT_FILTER`].
+pub fn env_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER))
+}
+
+/// Build a subscriber writing `format` lines to `writer`.
+pub fn subscriber<W>(
+    format: LogFormat,
+    filter: EnvFilter,
+    writer: W,
+) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer);
+    match format {
+        LogFormat::Json => Box::new(builder.json().with_target(true).finish()),
+        LogFormat::Human => Box::new(builder.with_target(false).finish()),
+    }
+}
+
+/// Install the global subscriber: JSON on stdout or human text on stderr.
+pub fn init(format: LogFormat) {
+    let filter = env_filter();
+    let subscriber = match format {
+        LogFormat::Json => subscriber(format, filter, std::io::stdout),
+        LogFormat::Human => subscriber(format, filter, std::io::stderr),
+    };
+    // `try_init` also routes `log` records from dependencies into tracing.
+    if subscriber.try_init().is_err() {
+        eprintln!("Warning: a global tracing subscriber was already installed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{Event, EventBus};
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    /// In-memory writer shared between the subscriber and the test.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'w> MakeWriter<'w> for Capture {
+        type Writer = Capture;
+
+        fn make_writer(&'w self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    /// Emit one found-prime event through the event bus and return the
+    /// captured log output.
+    fn log_found_prime(format: LogFormat) -> String {
+        let capture = Capture::default();
+        let filter = EnvFilter::new(DEFAULT_FILTER);
+        let subscriber = subscriber(format, filter, capture.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            EventBus::new().emit(Event::PrimeFound {
+                form: "kbn".to_string(),
+                expression: "3*2^1000-1".to_string(),
+                digits: 302,
+                proof_method: "deterministic".to_string(),
+                search_job_id: None,
+                timestamp: Instant::now(),
+            });
+        });
+        let bytes = capture.0.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn parses_log_formats() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("human".parse::<LogFormat>().unwrap(), LogFormat::Human);
+        assert_eq!("".parse::<LogFormat>().unwrap(), LogFormat::Human);
+        assert!("logfmt".parse::<LogFormat>().is_err());
+    }
+
+    /// In JSON mode the found-prime line is a JSON object whose fields are
+    /// separate keys, not part of the message text.
+    #[test]
+    fn json_found_prime_line_has_structured_fields() {
+        let output = log_found_prime(LogFormat::Json);
+        let line = output
+            .lines()
+            .find(|l| l.contains("prime found"))
+            .expect("no found-prime line");
+        let value: serde_json::Value = serde_json::from_str(line).unwrap();
+        let fields = &value["fields"];
+        assert_eq!(fields["message"], "prime found");
+        assert_eq!(fields["form"], "kbn");
+        assert_eq!(fields["expression"], "3*2^1000-1");
+        assert_eq!(fields["digits"], 302);
+        assert_eq!(fields["proof_method"], "deterministic");
+        assert_eq!(value["level"], "INFO");
+        assert_eq!(value["target"], "darkreach::events");
+    }
+
+    /// Drop ANSI SGR sequences (`ESC [ … m`) from colored text output.
+    fn strip_ansi(text: &str) -> String {
+        let mut out = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    /// The default format is a human-readable text line, not JSON.
+    #[test]
+    fn human_found_prime_line_is_text() {
+        let output = strip_ansi(&log_found_prime(LogFormat::default()));
+        let line = output
+            .lines()
+            .find(|l| l.contains("prime found"))
+            .expect("no found-prime line");
+        assert!(serde_json::from_str::<serde_json::Value>(line).is_err());
+        assert!(line.contains("INFO"), "{}", line);
+        assert!(line.contains("form=kbn"), "{}", line);
+        assert!(line.contains("digits=302"), "{}", line);
+        assert!(!line.contains("darkreach::events"), "{}", line);
+    }
+}
@@ -8,17 +8,29 @@
 //!
 //! Each engine form has a corresponding subcommand (factorial, kbn, palindromic,
 //! primorial, cullen_woodall, wagstaff, carol_kynea, twin, sophie_germain,
-//! repunit, gen_fermat, near_repdigit). The `dashboard` subcommand starts the
-//! web server. The `work` subcommand connects to a search job via PostgreSQL.
+//! repunit, gen_fermat, near_repdigit, gaussian). The `dashboard` subcommand
+//! starts the web server. `migrate` applies the embedded schema migrations to
+//! a plain PostgreSQL database. The `work` subcommand connects to a search job
+//! via PostgreSQL.
+//! The `test` subcommand runs one explicit candidate through the pipeline
+//! offline, e.g. `test --form kbn --k 3 --base 2 --n 100`. `run --config
+//! searches.toml` runs a batch of `[[search]]` entries sequentially.
 //!
 //! ## Global Options
 //!
 //! - `--database-url` / `DATABASE_URL`: PostgreSQL connection for prime storage.
+//! - `--output-file`: Offline mode — append primes to a JSONL file when no database URL is set.
 //! - `--checkpoint`: JSON file for resumable search state.
-//! - `--mr-rounds`: Miller–Rabin iterations (default 15).
+//! - `--checkpoint-dir`: Directory of per-search-type checkpoints (`<dir>/<type>.checkpoint`); overrides `--checkpoint`.
+//! - `--mr-rounds`: Miller–Rabin iterations (default 15), raised to `search_params::MR_ROUND_FLOORS` for some forms.
 //! - `--sieve-limit`: Sieve depth (0 = auto-tune per GIMPS heuristic).
 //! - `--qos`: macOS QoS P-core scheduling via `pthread_set_qos_class_self_np`.
 //! - `--threads`: Rayon thread pool size (0 = all cores).
+//! - `--only-proven`: Defer probabilistic results to `prp_candidates` instead of `primes`.
+//! - `--frobenius-min-bits`: Bit size above which the Frobenius filter runs (default 10000).
+//! - `--test-profile`: `survey` (1 MR, Frobenius above `--frobenius-min-bits`), `standard` (default), or `rigorous` (MR(25) + Frobenius, proven only).
+//! - `--deterministic-mr`: Draw MR bases from a per-candidate seed, so a rejection reproduces exactly.
+//! - `--prime-buffer-size` / `--prime-flush-secs`: Batch found-prime inserts (default 32 primes / 10s).
 
 mod cli;
 
@@ -38,11 +50,22 @@ struct Cli {
     #[arg(long, env = "DATABASE_URL")]
     database_url: Option<String>,
 
+    /// Append found primes to this JSONL file instead of PostgreSQL (offline
+    /// mode; used only when no database URL is set)
+    #[arg(long)]
+    output_file: Option<PathBuf>,
+
     /// Path to checkpoint file for resuming searches
     #[arg(long, default_value = "darkreach.checkpoint")]
     checkpoint: PathBuf,
 
-    /// Miller-Rabin rounds for primality testing (default: 15, higher = more certain but slower)
+    /// Directory for per-search-type checkpoint files
+    /// (`<dir>/<search_type>.checkpoint`); overrides --checkpoint for searches
+    #[arg(long)]
+    checkpoint_dir: Option<PathBuf>,
+
+    /// Miller-Rabin rounds for primality testing (default: 15, higher = more certain but slower).
+    /// Some forms raise this to a floor (palindromic, near-repdigit: 25)
     #[arg(long, default_value_t = 15)]
     mr_rounds: u32,
 
@@ -79,6 +102,62 @@ struct Cli {
     #[arg(long)]
     threads: Option<usize>,
 
+    /// Only record deterministically proven primes; probable primes are parked
+    /// in `prp_candidates` for a later proof pass
+    #[arg(long)]
+    only_proven: bool,
+
+    /// Run the form's deterministic proof as soon as a probable prime is
+    /// found, so the stored proof method is final (kbn, Cullen/Woodall,
+    /// factorial and primorial)
+    #[arg(long)]
+    verify_on_find: bool,
+
+    /// Largest prime, in digits, --verify-on-find proves inline; larger ones
+    /// are recorded as PRPs for the background proof pass
+    #[arg(long, default_value_t = 10_000)]
+    verify_on_find_max_digits: u64,
+
+    /// Minimum candidate size in bits for the Frobenius pre-filter in MR
+    /// screening (0 = always, 18446744073709551615 = never)
+    #[arg(long, default_value_t = 10_000)]
+    frobenius_min_bits: u64,
+
+    /// Primality-test preset: "survey" (1 MR round), "standard"
+    /// (--mr-rounds screen), or "rigorous" (MR(25) + Frobenius, proven only)
+    #[arg(long, default_value = "standard")]
+    test_profile: darkreach::TestProfile,
+
+    /// Seed Miller-Rabin bases from each candidate so every run of a
+    /// candidate tests the same witnesses (reproducible rejections)
+    #[arg(long)]
+    deterministic_mr: bool,
+
+    /// Found primes held in memory before a batched insert (1 = write each
+    /// prime immediately)
+    #[arg(long, default_value_t = 32)]
+    prime_buffer_size: usize,
+
+    /// Maximum seconds a found prime waits in the buffer before it is written
+    #[arg(long, default_value_t = 10)]
+    prime_flush_secs: u64,
+
+    /// Memory (MiB) the `work` loop keeps free; blocks whose largest
+    /// candidate would not fit above it wait until memory frees up, and are
+    /// released if it does not (0 disables)
+    #[arg(long, default_value_t = 0)]
+    min_free_mem_mb: u64,
+
+    /// Prometheus Pushgateway base URL; workers push their metrics there on
+    /// every heartbeat (for workers that cannot be scraped)
+    #[arg(long)]
+    pushgateway_url: Option<String>,
+
+    /// Log output: "human" (text on stderr) or "json" (one object per line on
+    /// stdout, for log aggregation); anything else falls back to human
+    #[arg(long, env = "LOG_FORMAT", default_value = "human")]
+    log_format: String,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -105,6 +184,11 @@ enum Commands {
         /// Maximum digit count
         #[arg(long)]
         max_digits: u64,
+        /// Candidate order: "ascending" or "minimal-change" (Gray code over
+        /// the half-digits; each candidate is one addition, found primes are
+        /// sorted before reporting)
+        #[arg(long, default_value = "ascending")]
+        order: darkreach::palindromic::PalindromeOrder,
     },
     /// Search for primes of form k*b^n +/- 1
     Kbn {
@@ -220,6 +304,18 @@ enum Commands {
         #[arg(long)]
         max_base: u64,
     },
+    /// Search for Gaussian primes a+bi with fixed imaginary part b
+    Gaussian {
+        /// Imaginary part b (b = 1 searches primes a^2+1)
+        #[arg(long, default_value_t = 1)]
+        imag: u64,
+        /// Minimum real part a
+        #[arg(long)]
+        min_real: u64,
+        /// Maximum real part a
+        #[arg(long)]
+        max_real: u64,
+    },
     /// Launch web dashboard to browse results and monitor searches
     Dashboard {
         /// Port to listen on
@@ -228,6 +324,19 @@ enum Commands {
         /// Directory to serve static files from (e.g. Next.js export)
         #[arg(long)]
         static_dir: Option<PathBuf>,
+        /// PEM certificate chain; serves HTTPS instead of HTTP. Required
+        /// when operators connect over an untrusted network
+        #[arg(long, requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+        /// PEM private key for --tls-cert
+        #[arg(long, requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
+    },
+    /// Apply pending schema migrations to DATABASE_URL (plain PostgreSQL)
+    Migrate {
+        /// Also apply Supabase-only migrations and statements (auth, RLS policies, Realtime publication)
+        #[arg(long)]
+        supabase: bool,
     },
     /// Claim and execute work blocks from a search job
     Work {
@@ -252,9 +361,50 @@ enum Commands {
         /// Re-verify even if already verified
         #[arg(long)]
         force: bool,
-        /// Verification tool to use: "default" (tier1+tier2), "pfgw" (PFGW cross-verification)
+        /// Verification tool to use: "default" (tier1+tier2), "pfgw" (PFGW cross-verification),
+        /// "flint" (FLINT re-test, needs the flint feature)
         #[arg(long, default_value = "default")]
         tool: String,
+        /// Trace the prime given by --id through every search stage with timings
+        #[arg(long, requires = "id")]
+        replay: bool,
+        /// Verify the fetched batch in parallel across rayon threads
+        #[arg(long)]
+        parallel: bool,
+        /// Output: "table" (stderr) or "json" (array of results on stdout);
+        /// exits nonzero if any prime fails either way
+        #[arg(long, default_value = "table")]
+        format: darkreach::verify::VerifyFormat,
+        /// Page through the whole backlog under a tracked verification run,
+        /// resuming an interrupted run with the same --form/--force/--tool
+        #[arg(long, requires = "all")]
+        resumable: bool,
+    },
+    /// Test one explicit candidate through the full pipeline (no database)
+    Test {
+        /// Prime form of the candidate (kbn, factorial, wagstaff, ...)
+        #[arg(long)]
+        form: String,
+        /// Candidate expression in stored format, e.g. "3*2^100 + 1" (overrides --k/--base/--n)
+        #[arg(long)]
+        expr: Option<String>,
+        /// Multiplier k (kbn, twin, sophie_germain)
+        #[arg(long)]
+        k: Option<u64>,
+        /// Base b (kbn, twin, sophie_germain default 2; repunit default 10; gen_fermat)
+        #[arg(long)]
+        base: Option<u32>,
+        /// Exponent or index n
+        #[arg(long)]
+        n: Option<u64>,
+        /// Test the -1 member (Woodall, Carol, n!-1, k*b^n-1) instead of +1
+        #[arg(long)]
+        minus: bool,
+    },
+    /// Self-tests of the search machinery (no database)
+    Diagnose {
+        #[command(subcommand)]
+        check: DiagnoseCheck,
     },
     /// Manage prime-hunting projects (campaigns with phases, budgets, records)
     Project {
@@ -274,9 +424,36 @@ enum Commands {
         #[arg(long)]
         server: String,
     },
-    /// Run as an operator node (claim work, compute, submit results)
+    /// Run as an operator node (claim work, compute, submit results), or
+    /// with --config run a TOML batch of searches back to back
     #[command(alias = "volunteer")]
-    Run,
+    Run {
+        /// Batch file of [[search]] entries to run in order instead of operating
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DiagnoseCheck {
+    /// Confirm every n the k*b^n±1 sieve eliminates is composite
+    Sieve {
+        /// Multiplier k
+        #[arg(long)]
+        k: u64,
+        /// Base b
+        #[arg(long)]
+        base: u32,
+        /// Smallest exponent n
+        #[arg(long, default_value_t = 1)]
+        min_n: u64,
+        /// Largest exponent n (each eliminated n costs an MR test)
+        #[arg(long)]
+        max_n: u64,
+        /// Sieve primes up to this bound
+        #[arg(long, default_value_t = 100_000)]
+        sieve_limit: u64,
+    },
 }
 
 #[derive(Subcommand)]
@@ -326,27 +503,17 @@ enum ProjectAction {
 fn main() -> Result<()> {
     let _ = dotenvy::dotenv();
 
-    // Initialize structured logging with RUST_LOG filtering.
-    // LOG_FORMAT=json for K8s/log aggregation, human-readable otherwise.
-    let log_format = std::env::var("LOG_FORMAT").unwrap_or_default();
-    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("darkreach=info,tower_http=info"));
-    if log_format == "json" {
-        tracing_subscriber::fmt()
-            .json()
-            .with_target(true)
-            .with_env_filter(env_filter)
-            .init();
-    } else {
-        tracing_subscriber::fmt()
-            .with_writer(std::io::stderr)
-            .with_target(false)
-            .with_env_filter(env_filter)
-            .init();
-    }
-
     let cli = Cli::parse();
 
+    // Initialize structured logging with RUST_LOG filtering, before anything
+    // else can log.
+    // A typo in LOG_FORMAT must not keep a worker from starting.
+    let log_format = cli.log_format.parse::<darkreach::logging::LogFormat>();
+    darkreach::logging::init(log_format.as_ref().copied().unwrap_or_default());
+    if let Err(e) = &log_format {
+        tracing::warn!(error = %e, "Invalid log format, using human");
+    }
+
     darkreach::prst::init(
         cli.prst_min_digits,
         cli.prst_path.clone(),
@@ -357,22 +524,48 @@ fn main() -> Result<()> {
         cli.pfgw_path.clone(),
         std::time::Duration::from_secs(3600),
     );
+    darkreach::set_only_proven(cli.only_proven || cli.test_profile.requires_proof());
+    darkreach::set_verify_on_find(cli.verify_on_find.then_some(cli.verify_on_find_max_digits));
+    darkreach::set_frobenius_min_bits(cli.frobenius_min_bits);
+    darkreach::set_test_profile(cli.test_profile);
+    darkreach::set_deterministic_mr(cli.deterministic_mr);
+    darkreach::prime_sink::set_buffer_limits(
+        cli.prime_buffer_size,
+        std::time::Duration::from_secs(cli.prime_flush_secs),
+    );
+    darkreach::prom_metrics::set_pushgateway_url(cli.pushgateway_url.clone());
     cli::configure_rayon(cli.threads, cli.qos);
 
     match &cli.command {
         Commands::Project { action } => cli::run_project(&cli, action),
-        Commands::Dashboard { port, static_dir } => {
+        Commands::Diagnose { check } => cli::run_diagnose(check),
+        Commands::Dashboard {
+            port,
+            static_dir,
+            tls_cert,
+            tls_key,
+        } => {
             let database_url = cli.database_url.as_deref().ok_or_else(|| {
                 anyhow::anyhow!("DATABASE_URL is required (set via --database-url or env)")
             })?;
+            // clap requires both flags together.
+            let tls = match (tls_cert, tls_key) {
+                (Some(cert), Some(key)) => Some(darkreach::dashboard::TlsConfig {
+                    cert: cert.clone(),
+                    key: key.clone(),
+                }),
+                _ => None,
+            };
             let rt = tokio::runtime::Runtime::new()?;
             rt.block_on(darkreach::dashboard::run(
                 *port,
                 database_url,
                 &cli.checkpoint,
                 static_dir.as_deref(),
+                tls.as_ref(),
             ))
         }
+        Commands::Migrate { supabase } => cli::run_migrate(&cli, *supabase),
         Commands::Verify {
             id,
             all,
@@ -380,12 +573,19 @@ fn main() -> Result<()> {
             batch_size,
             force,
             tool,
+            replay,
+            parallel,
+            format,
+            resumable,
         } => {
             let database_url = cli.database_url.as_deref().ok_or_else(|| {
                 anyhow::anyhow!("DATABASE_URL is required (set via --database-url or env)")
             })?;
             let rt = tokio::runtime::Runtime::new()?;
             let database = rt.block_on(db::Database::connect(database_url))?;
+            if *replay {
+                return cli::run_replay(&rt, &database, id.unwrap());
+            }
             cli::run_verify(
                 &rt,
                 &database,
@@ -395,14 +595,26 @@ fn main() -> Result<()> {
                 *batch_size,
                 *force,
                 tool,
+                *parallel,
+                *format,
+                *resumable,
             )
         }
+        Commands::Test {
+            form,
+            expr,
+            k,
+            base,
+            n,
+            minus,
+        } => cli::run_test(form, expr.as_deref(), *k, *base, *n, *minus),
         Commands::Register {
             username,
             email,
             server,
         } => cli::run_register(server, username, email),
-        Commands::Run => cli::run_operator(&cli),
+        Commands::Run { config: Some(path) } => cli::run_batch(&cli, path),
+        Commands::Run { config: None } => cli::run_operator(&cli),
         _ => cli::run_search(&cli),
     }
 }
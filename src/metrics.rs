@@ -18,10 +18,26 @@
 //! Called every 10 seconds by the heartbeat thread in both `WorkerClient`
 //! and `PgWorkerClient`. The `sysinfo::System` instance is reused across
 //! calls (passed by `&mut` reference) to amortize initialization cost.
+//!
+//! ## Startup Benchmark
+//!
+//! [`benchmark_score`] times a fixed LLR test on the Riesel prime 3·2^5134 − 1
+//! and reports LLR tests per second. Workers run it once at registration so
+//! the coordinator can compare node throughput independently of what each
+//! node happens to be searching.
 
+use rug::Integer;
 use serde::{Deserialize, Serialize};
+use std::time::Instant;
 use sysinfo::System;
 
+/// Multiplier of the benchmark candidate k·2^n − 1.
+const BENCHMARK_K: u64 = 3;
+/// Exponent of the benchmark candidate (1,546 digits, a known Riesel prime).
+const BENCHMARK_N: u64 = 5134;
+/// Number of timed repetitions; the fastest one is reported.
+const BENCHMARK_REPS: u32 = 3;
+
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct HardwareMetrics {
     pub cpu_usage_percent: f32,
@@ -81,6 +97,27 @@ pub fn collect(sys: &System) -> HardwareMetrics {
     }
 }
 
+/// Run the standardized startup benchmark and return LLR tests per second.
+///
+/// Keeps the fastest of [`BENCHMARK_REPS`] runs so the score reflects the
+/// machine's single-core throughput rather than scheduler noise. Higher is
+/// faster.
+pub fn benchmark_score() -> f64 {
+    let candidate = (Integer::from(BENCHMARK_K) << BENCHMARK_N as u32) - 1u32;
+    let best = (0..BENCHMARK_REPS)
+        .map(|_| {
+            let start = Instant::now();
+            let _ = crate::kbn::llr_test(&candidate, BENCHMARK_K, BENCHMARK_N);
+            start.elapsed().as_secs_f64()
+        })
+        .fold(f64::INFINITY, f64::min);
+    if best > 0.0 {
+        (1.0 / best * 100.0).round() / 100.0
+    } else {
+        0.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //! Tests for hardware telemetry collection.
@@ -314,4 +351,27 @@ mod tests {
         assert!(m.memory_total_gb > 0.0, "Total memory should be > 0 on real hardware");
         assert!(m.disk_total_gb > 0.0, "Total disk should be > 0 on real hardware");
     }
+
+    // ── Startup Benchmark ────────────────────────────────────────
+
+    /// The benchmark candidate 3·2^5134 − 1 must be a proven Riesel prime,
+    /// so every worker exercises the full LLR loop including the final
+    /// Gerbicz verification pass.
+    #[test]
+    fn benchmark_candidate_is_prime() {
+        let candidate = (Integer::from(BENCHMARK_K) << BENCHMARK_N as u32) - 1u32;
+        let result = crate::kbn::llr_test(&candidate, BENCHMARK_K, BENCHMARK_N);
+        assert!(
+            matches!(result, Some((true, _))),
+            "3*2^5134-1 should be prime"
+        );
+    }
+
+    /// The score is a positive, finite throughput figure.
+    #[test]
+    fn benchmark_score_is_positive() {
+        let score = benchmark_score();
+        assert!(score.is_finite());
+        assert!(score > 0.0, "benchmark score should be > 0, got {}", score);
+    }
 }
@@ -20,9 +20,12 @@
 //!
 //! ## Algorithm
 //!
-//! 1. **Modular sieve** (`candidate_mod_p`): Evaluates N mod p using modular
-//!    exponentiation on the algebraic form — no big integer is constructed.
-//!    Each component 10^x mod p is computed via `pow_mod`.
+//! 1. **Modular sieve** (`PatternSieve`): Evaluates N mod p on the algebraic
+//!    form — no big integer is constructed. Divisibility by 2, 3 and 5 is read
+//!    off the last digit and the digit sum 9(2k+1) − 2d, which rules out
+//!    d = 3 and d = 6 outright. For p below 1000, 10^x mod p comes from a
+//!    table over one period of 10 mod p; larger primes use `candidate_mod_p`,
+//!    one `pow_mod` per component.
 //!
 //! 2. **BLS N+1 proof** (`proof::bls_near_repdigit_proof`): N+1 contains
 //!    10^(k−m) = 2^(k−m) · 5^(k−m) as a factor, providing (k−m)·log₂(10) bits
@@ -53,9 +56,9 @@ use std::time::Instant;
 use tracing::info;
 
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::proof;
 use crate::CoordinationClient;
@@ -97,13 +100,18 @@ pub fn build_candidate(k: u64, d: u32, m: u64) -> Integer {
 /// N = 10^(2k+1) - 1 - d*(10^(k+m) + 10^(k-m))
 /// Each component computed via sieve::pow_mod.
 pub fn candidate_mod_p(k: u64, d: u32, m: u64, p: u64) -> u64 {
-    let a = sieve::pow_mod(10, 2 * k + 1, p);
+    residue_from_powers(k, d, m, p, |x| sieve::pow_mod(10, x, p))
+}
+
+/// N mod p given `pow10(x)` = 10^x mod p.
+fn residue_from_powers(k: u64, d: u32, m: u64, p: u64, pow10: impl Fn(u64) -> u64) -> u64 {
+    let a = pow10(2 * k + 1);
 
     let b = if m == 0 {
-        (2 * d as u64 % p) * sieve::pow_mod(10, k, p) % p
+        (2 * d as u64 % p) * pow10(k) % p
     } else {
-        let high = sieve::pow_mod(10, k + m, p);
-        let low = sieve::pow_mod(10, k - m, p);
+        let high = pow10(k + m);
+        let low = pow10(k - m);
         (d as u64 % p) * ((high + low) % p) % p
     };
 
@@ -112,6 +120,103 @@ pub fn candidate_mod_p(k: u64, d: u32, m: u64, p: u64) -> u64 {
     (step1 + p - b) % p
 }
 
+/// Digit sum of N, read off the pattern: 2k+1 nines less the total
+/// deficiency 2d, whether it sits on two digits or on the center one.
+pub fn digit_sum(k: u64, d: u32) -> u64 {
+    9 * (2 * k + 1) - 2 * u64::from(d)
+}
+
+/// Last decimal digit of N: 9 unless the low position `k - m` is the units
+/// digit.
+pub fn last_digit(k: u64, d: u32, m: u64) -> u32 {
+    match (m == k, m == 0) {
+        (false, _) => 9,
+        (true, false) => 9 - d,
+        (true, true) => 9 - 2 * d,
+    }
+}
+
+/// True if 2, 3 or 5 divides N, decided from the digits alone: the last
+/// digit for 2 and 5, the digit sum for 3. Since the digit sum is ≡ −2d
+/// (mod 3), d = 3 and d = 6 are always divisible by 3. One-digit candidates
+/// (k = 0) are never rejected, as they may be 3 or 5 themselves.
+pub fn divisible_by_pattern(k: u64, d: u32, m: u64) -> bool {
+    if k == 0 {
+        return false;
+    }
+    let last = last_digit(k, d, m);
+    last.is_multiple_of(2) || last == 5 || digit_sum(k, d).is_multiple_of(3)
+}
+
+/// Sieve primes below this get a table of 10^x mod p over one period.
+const PERIOD_TABLE_LIMIT: u64 = 1000;
+
+/// The search's sieve. Candidates first go through
+/// [`divisible_by_pattern`]; sieve primes from 7 to [`PERIOD_TABLE_LIMIT`]
+/// then use a precomputed cycle of 10^x mod p, since powers of 10 repeat
+/// with period ord_p(10), so N mod p costs three table lookups instead of
+/// three `pow_mod` calls. Larger primes fall back to [`candidate_mod_p`].
+/// Rejects exactly what [`sieve_filter`] does.
+pub struct PatternSieve {
+    /// (p, 10^i mod p for i in 0..ord_p(10)), ascending in p.
+    tables: Vec<(u64, Vec<u64>)>,
+    /// Sieve primes at or above [`PERIOD_TABLE_LIMIT`].
+    large: Vec<u64>,
+}
+
+impl PatternSieve {
+    pub fn new(sieve_primes: &[u64]) -> Self {
+        let mut tables = Vec::new();
+        let mut large = Vec::new();
+        for &p in sieve_primes {
+            if p < 7 {
+                continue;
+            }
+            if p >= PERIOD_TABLE_LIMIT {
+                large.push(p);
+                continue;
+            }
+            let mut cycle = vec![1u64];
+            let mut x = 10 % p;
+            while x != 1 {
+                cycle.push(x);
+                x = x * 10 % p;
+            }
+            tables.push((p, cycle));
+        }
+        PatternSieve { tables, large }
+    }
+
+    /// True if N has a factor among the sieve primes (or 2, 3, 5) smaller
+    /// than 10^(2k), the same guard [`sieve_filter`] uses.
+    pub fn is_composite(&self, k: u64, d: u32, m: u64) -> bool {
+        if divisible_by_pattern(k, d, m) {
+            return true;
+        }
+        let digit_count = 2 * k + 1;
+        let max_safe_prime = if digit_count > 18 {
+            u64::MAX
+        } else {
+            10u64.pow((digit_count - 1) as u32)
+        };
+        for (p, cycle) in &self.tables {
+            let p = *p;
+            if p >= max_safe_prime {
+                return false;
+            }
+            let period = cycle.len() as u64;
+            let pow10 = |x: u64| cycle[(x % period) as usize];
+            if residue_from_powers(k, d, m, p, pow10) == 0 {
+                return true;
+            }
+        }
+        self.large
+            .iter()
+            .take_while(|&&p| p < max_safe_prime)
+            .any(|&p| candidate_mod_p(k, d, m, p) == 0)
+    }
+}
+
 /// Check if the candidate is divisible by any sieve prime.
 /// Returns true if composite (has a small factor).
 pub fn sieve_filter(k: u64, d: u32, m: u64, sieve_primes: &[u64]) -> bool {
@@ -155,7 +260,7 @@ pub fn search(
     min_digits: u64,
     max_digits: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -164,12 +269,16 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params = serde_json::json!({"min_digits": min_digits, "max_digits": max_digits});
+    crate::search_params::validate("near_repdigit", &params)?;
+
     // Resolve sieve_limit: auto-tune if 0 (base 10 near-repdigits)
     let candidate_bits = (max_digits as f64 * 10f64.log2()) as u64;
     let n_range = (max_digits.saturating_sub(min_digits)) / 2 + 1;
     let sieve_limit = sieve::resolve_sieve_limit(sieve_limit, candidate_bits, n_range);
 
     let sieve_primes = sieve::generate_primes(sieve_limit);
+    let pattern_sieve = PatternSieve::new(&sieve_primes);
     info!(prime_count = sieve_primes.len(), sieve_limit, "near-repdigit sieve initialized");
 
     // Ensure we start on an odd digit count
@@ -196,6 +305,7 @@ pub fn search(
     };
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut total_sieved: u64 = 0;
 
     let mut digit_count = resume_from;
@@ -212,7 +322,7 @@ pub fn search(
                     continue;
                 }
                 candidates_checked += 1;
-                if sieve_filter(k, d, m, &sieve_primes) {
+                if pattern_sieve.is_composite(k, d, m) {
                     total_sieved += 1;
                 } else {
                     survivors.push((d, m));
@@ -230,14 +340,17 @@ pub fn search(
         }
 
         *progress.current.lock().unwrap() = format!("{}-digit near-repdigit", digit_count);
+        progress.record_survivors(survivors.iter().map(|&(d, m)| m << 4 | u64::from(d)));
 
         // MR test phase: parallel over all survivors for this digit count.
         // Try PFGW first for large candidates (50-100x faster), fall back to GMP MR.
         let found_primes: Vec<_> = survivors
             .into_par_iter()
             .filter_map(|(d, m)| {
+                let _busy = crate::progress::busy();
                 let candidate = build_candidate(k, d, m);
                 let expr = format_expression(k, d, m);
+                let _testing = progress.testing(&expr, &candidate);
 
                 // Try PFGW acceleration (near-repdigit: PRP only — N-1 doesn't have a
                 // trivially factored form; the BLS proof uses N+1 factorization instead)
@@ -256,12 +369,12 @@ pub fn search(
                             return Some((expr, digits, cert));
                         }
                         pfgw::PfgwResult::Composite => return None,
-                        pfgw::PfgwResult::Unavailable { .. } => {} // fall through to GMP
+                        pfgw::PfgwResult::Unavailable { .. } | pfgw::PfgwResult::Failed { .. } => {} // fall through to GMP
                     }
                 }
 
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&candidate) {
+                if crate::p1::adaptive_p1_filter_for_form(&candidate, "near_repdigit") {
                     return None;
                 }
 
@@ -271,10 +384,7 @@ pub fn search(
                     let cert = if bls_ok {
                         "deterministic"
                     } else {
-                        match r {
-                            IsPrime::Yes => "deterministic",
-                            _ => "probabilistic",
-                        }
+                        crate::certainty_label(&candidate, r)
                     };
                     let digits = candidate.to_string_radix(10).len() as u64;
                     Some((expr, digits, cert.to_string()))
@@ -289,6 +399,19 @@ pub fn search(
             .fetch_add(candidates_checked, Ordering::Relaxed);
 
         for (expr, digits, certainty) in found_primes {
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "near_repdigit",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -296,8 +419,12 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                eb.check_milestone("near_repdigit", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "near_repdigit").ok().flatten()
+                });
             } else {
                 info!(
                     expression = %expr,
@@ -306,8 +433,7 @@ pub fn search(
                     "*** PRIME FOUND ***"
                 );
             }
-            db.insert_prime_sync(
-                rt,
+            buffer.push(
                 "near_repdigit",
                 &expr,
                 digits,
@@ -315,12 +441,12 @@ pub fn search(
                 &certainty,
                 None,
             )?;
-            if let Some(wc) = worker_client {
-                wc.report_prime("near_repdigit", &expr, digits, search_params, &certainty);
-            }
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::NearRepdigit {
@@ -336,6 +462,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::NearRepdigit {
@@ -356,6 +483,7 @@ pub fn search(
     if total_sieved > 0 {
         info!(total_sieved, "near-repdigit sieve eliminated candidates");
     }
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     Ok(())
 }
@@ -388,8 +516,8 @@ mod tests {
     //!
     //! 1. **Candidate construction**: Verify `build_candidate` produces correct
     //!    values and palindromic digit patterns.
-    //! 2. **Modular sieve**: Cross-validate `candidate_mod_p` against direct
-    //!    big-integer modular arithmetic.
+    //! 2. **Modular sieve**: Cross-validate `candidate_mod_p`, the digit-sum
+    //!    filter and `PatternSieve` against direct big-integer arithmetic.
     //! 3. **Known primes/composites**: Confirm correctness against OEIS data.
     //! 4. **Edge cases**: Parameter validation, boundary positions (m=0, m=k).
 
@@ -526,6 +654,62 @@ mod tests {
         }
     }
 
+    /// The digit-sum filter is computed from (k, d) alone, yet it matches
+    /// the full candidate exactly: the pattern's digit sum is the real one,
+    /// and it flags divisibility by 3 precisely when the Integer is divisible
+    /// by 3. Likewise for 2 and 5 via the last digit.
+    #[test]
+    fn digit_sum_filter_matches_integer_divisibility() {
+        for k in 1..=12u64 {
+            for d in 1..=8u32 {
+                for m in 0..=k {
+                    if !is_valid_params(k, d, m) {
+                        continue;
+                    }
+                    let n = build_candidate(k, d, m);
+                    let digits = n.to_string_radix(10);
+                    let sum: u64 = digits.bytes().map(|b| u64::from(b - b'0')).sum();
+                    assert_eq!(digit_sum(k, d), sum, "k={}, d={}, m={}", k, d, m);
+                    let last = u32::from(digits.as_bytes()[digits.len() - 1] - b'0');
+                    assert_eq!(last_digit(k, d, m), last, "k={}, d={}, m={}", k, d, m);
+
+                    let by_three = digit_sum(k, d).is_multiple_of(3);
+                    assert_eq!(by_three, n.is_divisible_u(3), "k={}, d={}, m={}", k, d, m);
+                    let direct = [2u32, 3, 5].iter().any(|&p| n.is_divisible_u(p));
+                    assert_eq!(divisible_by_pattern(k, d, m), direct);
+                }
+            }
+        }
+        // 3 and 5 are 1-digit candidates and must not be filtered.
+        assert!(!divisible_by_pattern(0, 3, 0));
+        assert!(!divisible_by_pattern(0, 2, 0));
+    }
+
+    /// `PatternSieve` (pattern checks, period tables, then `pow_mod` above
+    /// the table limit) eliminates exactly what `sieve_filter` does.
+    #[test]
+    fn pattern_sieve_matches_sieve_filter() {
+        let sieve_primes = sieve::generate_primes(5000);
+        let pattern_sieve = PatternSieve::new(&sieve_primes);
+        for k in 0..=25u64 {
+            for d in 1..=8u32 {
+                for m in 0..=k {
+                    if !is_valid_params(k, d, m) {
+                        continue;
+                    }
+                    assert_eq!(
+                        pattern_sieve.is_composite(k, d, m),
+                        sieve_filter(k, d, m, &sieve_primes),
+                        "k={}, d={}, m={}",
+                        k,
+                        d,
+                        m
+                    );
+                }
+            }
+        }
+    }
+
     // ── Edge Cases ──────────────────────────────────────────────────────
 
     /// Verifies parameter validation for boundary conditions.
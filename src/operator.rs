@@ -63,6 +63,14 @@ pub struct WorkAssignment {
     pub params: serde_json::Value,
     pub block_start: i64,
     pub block_end: i64,
+    /// Survivors the coordinator already sieved for this block, if any; the
+    /// node then skips its own sieve. Only kbn blocks carry them.
+    #[serde(default)]
+    pub survivors: Option<crate::kbn::SieveSurvivors>,
+    /// Set when the block is a quorum re-run of another operator's result;
+    /// echo it in the [`ResultSubmission`].
+    #[serde(default)]
+    pub verification_id: Option<i64>,
 }
 
 /// Result submission to `POST /api/v1/result`.
@@ -72,6 +80,13 @@ pub struct ResultSubmission {
     pub tested: i64,
     pub found: i64,
     pub primes: Vec<PrimeReport>,
+    /// Generated once per submission and reused on retry so the coordinator
+    /// applies the result only once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<uuid::Uuid>,
+    /// The assignment's `verification_id`, if it was a quorum re-run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_id: Option<i64>,
 }
 
 /// Individual prime report within a result submission.
@@ -203,6 +218,7 @@ pub fn register_worker(config: &OperatorConfig) -> Result<()> {
         "gpu_vram_gb": gpu_vram_gb(),
         "worker_version": env!("CARGO_PKG_VERSION"),
         "update_channel": std::env::var("DARKREACH_UPDATE_CHANNEL").unwrap_or_else(|_| "stable".to_string()),
+        "benchmark_score": crate::metrics::benchmark_score(),
     });
     ureq::post(&url)
         .header("Authorization", &auth_header(config))
@@ -228,13 +244,14 @@ pub fn heartbeat(config: &OperatorConfig) -> Result<()> {
 /// Claim a work block from the coordinator.
 pub fn claim_work(config: &OperatorConfig, cores: usize) -> Result<Option<WorkAssignment>> {
     let url = format!(
-        "{}/api/v1/work?cores={}&ram_gb={}&has_gpu={}&os={}&arch={}",
+        "{}/api/v1/work?cores={}&ram_gb={}&has_gpu={}&os={}&arch={}&worker_id={}&survivors=true",
         config.server.trim_end_matches('/'),
         cores,
         sys_ram_gb(),
         has_gpu(),
         worker_os(),
         worker_arch(),
+        config.worker_id,
     );
     let mut resp = ureq::get(&url)
         .header("Authorization", &auth_header(config))
@@ -255,6 +272,38 @@ pub fn submit_result(config: &OperatorConfig, submission: &ResultSubmission) ->
     Ok(())
 }
 
+/// Attempts [`submit_result_with_retry`] makes before giving up.
+pub const SUBMIT_ATTEMPTS: u32 = 3;
+
+/// Submit a result, retrying failures up to [`SUBMIT_ATTEMPTS`] times with a
+/// linearly growing `backoff`.
+///
+/// Every attempt sends the same submission, so its `idempotency_key` lets
+/// the coordinator drop a retry whose first attempt was applied but whose
+/// response was lost.
+pub fn submit_result_with_retry(
+    config: &OperatorConfig,
+    submission: &ResultSubmission,
+    backoff: std::time::Duration,
+) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        match submit_result(config, submission) {
+            Err(e) if attempt < SUBMIT_ATTEMPTS => {
+                tracing::warn!(
+                    block_id = submission.block_id,
+                    attempt,
+                    error = %e,
+                    "Result submission failed, retrying"
+                );
+                std::thread::sleep(backoff * attempt);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Get volunteer stats from the coordinator.
 pub fn get_stats(config: &OperatorConfig) -> Result<OperatorStats> {
     let url = format!("{}/api/v1/stats", config.server.trim_end_matches('/'));
@@ -672,6 +721,8 @@ mod tests {
                 proof_method: "proth".to_string(),
                 certificate: None,
             }],
+            idempotency_key: None,
+            verification_id: None,
         };
         let json = serde_json::to_string(&sub).unwrap();
         assert!(json.contains("block_id"));
@@ -782,6 +833,25 @@ mod tests {
         assert_eq!(wa.search_type, "factorial");
         assert_eq!(wa.block_start, 1);
         assert_eq!(wa.block_end, 50);
+        assert!(wa.survivors.is_none());
+    }
+
+    /// A kbn assignment may carry the coordinator's pre-sieved survivors.
+    #[test]
+    fn work_assignment_deserializes_survivors() {
+        let json = r#"{
+            "block_id": 43,
+            "search_job_id": 7,
+            "search_type": "kbn",
+            "params": {"k": 3, "base": 2, "min_n": 1, "max_n": 100},
+            "block_start": 1,
+            "block_end": 50,
+            "survivors": {"plus": [2, 5, 6], "minus": [1, 2, 3]}
+        }"#;
+        let wa: WorkAssignment = serde_json::from_str(json).unwrap();
+        let survivors = wa.survivors.unwrap();
+        assert_eq!(survivors.plus, vec![2, 5, 6]);
+        assert_eq!(survivors.minus, vec![1, 2, 3]);
     }
 
     /// Validates OperatorStats deserialization including the trust_level
@@ -926,6 +996,8 @@ mod tests {
                 proof_method: "pocklington".to_string(),
                 certificate: Some("{\"type\":\"Pocklington\",\"factors\":[]}".to_string()),
             }],
+            idempotency_key: None,
+            verification_id: None,
         };
         let json = serde_json::to_string(&sub).unwrap();
         assert!(json.contains("certificate"));
@@ -963,6 +1035,8 @@ mod tests {
                     certificate: None,
                 },
             ],
+            idempotency_key: None,
+            verification_id: None,
         };
         let json = serde_json::to_string(&sub).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
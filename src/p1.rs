@@ -4,6 +4,10 @@
 //! is B1-smooth (all prime factors of p-1 are ≤ B1). Applied to sieve survivors
 //! before expensive PRP tests — costs ~1 modular exponentiation but eliminates
 //! 1-5% of composites that survive the algebraic sieve.
+//!
+//! Also hosts the restricted-class trial division for 2^p − 1 and (2^p + 1)/3
+//! ([`mersenne_factor_trial`], [`wagstaff_factor_trial`]), which exploits the
+//! same group-order structure: every factor q has 2p | q − 1.
 
 use rug::Integer;
 
@@ -154,6 +158,65 @@ pub fn is_p1_composite(n: &Integer, b1: u64) -> bool {
     p1_stage1(n, b1).is_some()
 }
 
+/// P-1 tiers as `(min_bits, B1, B2)`, ascending: candidates with at least
+/// `min_bits` bits (and below the next tier) use that row's bounds.
+///
+/// Default tiers, used by [`adaptive_p1_filter`] and forms without a table.
+const DEFAULT_TIERS: &[(u64, u64, u64)] = &[
+    (5_000, 100_000, 10_000_000),
+    (20_000, 500_000, 50_000_000),
+    (50_000, 1_000_000, 100_000_000),
+];
+
+/// n!±1 and p#±1: sieving already removes every factor ≤ n, and survivors go
+/// to a generic MR/PRP test with no form-specific speedup, so a deeper P-1
+/// pays for itself earlier.
+const FACTORIAL_TIERS: &[(u64, u64, u64)] = &[
+    (4_000, 200_000, 20_000_000),
+    (16_000, 1_000_000, 100_000_000),
+    (40_000, 2_000_000, 200_000_000),
+];
+
+/// k·b^n±1 and its relatives: Proth/LLR/Pépin tests are a single cheap
+/// squaring chain, so P-1 must stay light to save any time at all.
+const KBN_TIERS: &[(u64, u64, u64)] = &[
+    (8_000, 50_000, 5_000_000),
+    (30_000, 250_000, 25_000_000),
+    (80_000, 500_000, 50_000_000),
+];
+
+/// Bound table for a search form. The form tables are the default tiers
+/// scaled by the reasoning on each table, not measured; benchmark before
+/// tightening them.
+fn tiers_for_form(form: &str) -> &'static [(u64, u64, u64)] {
+    match form {
+        "factorial" | "primorial" => FACTORIAL_TIERS,
+        "kbn" | "twin" | "sophie_germain" | "cullen_woodall" | "carol_kynea" | "gen_fermat" => {
+            KBN_TIERS
+        }
+        _ => DEFAULT_TIERS,
+    }
+}
+
+/// B1/B2 bounds for a `bits`-sized candidate of `form`, or `None` when the
+/// candidate is below the form's smallest tier and P-1 is not worth running.
+pub fn p1_bounds_for_form(bits: u64, form: &str) -> Option<(u64, u64)> {
+    tiers_for_form(form)
+        .iter()
+        .rev()
+        .find(|&&(min_bits, _, _)| bits >= min_bits)
+        .map(|&(_, b1, b2)| (b1, b2))
+}
+
+/// B1/B2 bounds of `form`'s smallest tier, regardless of candidate size.
+///
+/// Used by `verify::replay` to exercise P-1 on candidates that the search
+/// itself would skip.
+pub fn p1_entry_bounds_for_form(form: &str) -> (u64, u64) {
+    let (_, b1, b2) = tiers_for_form(form)[0];
+    (b1, b2)
+}
+
 /// Adaptive P-1 composite pre-filter with auto-tuned B1/B2 bounds.
 ///
 /// Uses [`p1_factor`] (Stage 1 + Stage 2) instead of Stage 1 alone, catching
@@ -170,24 +233,83 @@ pub fn is_p1_composite(n: &Integer, b1: u64) -> bool {
 /// | 20K–50K  | 500K   | 50M   | Moderate depth; worth it for 6K+ digit numbers |
 /// | 50K+     | 1M     | 100M  | Deep search; large candidates amortize the cost |
 ///
+/// These are the default-form bounds; engines call
+/// [`adaptive_p1_filter_for_form`] to use their own table.
+///
 /// Returns `true` if definitely composite (a non-trivial factor was found).
 pub fn adaptive_p1_filter(n: &Integer) -> bool {
-    let bits = n.significant_bits();
+    adaptive_p1_filter_for_form(n, "default")
+}
 
-    // Below 5K bits, P-1 is not cost-effective
-    if bits < 5_000 {
-        return false;
+/// Adaptive P-1 pre-filter with bounds from `form`'s tier table.
+///
+/// The best bounds depend on how expensive the form's primality test is and
+/// on how deep its sieve already went; see [`p1_bounds_for_form`]. Unknown
+/// forms fall back to the default table.
+///
+/// Returns `true` if definitely composite (a non-trivial factor was found).
+pub fn adaptive_p1_filter_for_form(n: &Integer, form: &str) -> bool {
+    match p1_bounds_for_form(n.significant_bits() as u64, form) {
+        Some((b1, b2)) => p1_factor(n, b1, Some(b2)).is_some(),
+        None => false,
     }
+}
 
-    let (b1, b2) = if bits < 20_000 {
-        (100_000u64, 10_000_000u64)
-    } else if bits < 50_000 {
-        (500_000u64, 50_000_000u64)
+/// Trial-divide 2^p − 1 by its admissible factors up to `limit`.
+///
+/// For odd prime p, any prime q dividing 2^p − 1 has ord_q(2) = p, so
+/// q = 2kp + 1; 2 is then a quadratic residue mod q, so q ≡ ±1 (mod 8).
+/// Only about one integer in 4p is tested. Returns the smallest factor
+/// found below the number itself, or `None`.
+pub fn mersenne_factor_trial(p: u64, limit: u64) -> Option<u64> {
+    special_factor_trial(p, limit, false)
+}
+
+/// Trial-divide the Wagstaff number (2^p + 1)/3 by its admissible factors up
+/// to `limit`.
+///
+/// For odd prime p and q ∤ 3 dividing 2^p + 1, ord_q(2) = 2p, so again
+/// q = 2kp + 1; −2 is a quadratic residue mod q, so q ≡ 1 or 3 (mod 8).
+pub fn wagstaff_factor_trial(p: u64, limit: u64) -> Option<u64> {
+    special_factor_trial(p, limit, true)
+}
+
+/// Walk q = 2kp + 1 ≤ `limit` in the admissible mod-8 classes and return the
+/// first q dividing 2^p − 1 (`plus = false`) or 2^p + 1 (`plus = true`).
+fn special_factor_trial(p: u64, limit: u64, plus: bool) -> Option<u64> {
+    if p < 3 || p.is_multiple_of(2) {
+        return None;
+    }
+    // A factor must be at most sqrt(N); below 2^127 that bound is tighter
+    // than most limits and keeps N itself from being reported.
+    let limit = if p < 127 {
+        let n: u128 = if plus {
+            ((1u128 << p) + 1) / 3
+        } else {
+            (1u128 << p) - 1
+        };
+        limit.min(n.isqrt().min(u64::MAX as u128) as u64)
     } else {
-        (1_000_000u64, 100_000_000u64)
+        limit
     };
-
-    p1_factor(n, b1, Some(b2)).is_some()
+    let step = p.checked_mul(2)?;
+    let mut q = step.checked_add(1)?;
+    while q <= limit {
+        let admissible = match q % 8 {
+            1 => true,
+            7 => !plus,
+            3 => plus,
+            _ => false,
+        };
+        if admissible {
+            let r = crate::sieve::pow_mod(2, p, q);
+            if (plus && r == q - 1) || (!plus && r == 1) {
+                return Some(q);
+            }
+        }
+        q = q.checked_add(step)?;
+    }
+    None
 }
 
 #[cfg(test)]
@@ -580,6 +702,61 @@ mod tests {
         );
     }
 
+    // ── Per-Form Bounds ──────────────────────────────────────────────
+    //
+    // Each form has its own tier table; unknown forms use the default one,
+    // which must match the bounds documented on `adaptive_p1_filter`.
+
+    /// Factorial and kbn candidates of the same size get different bounds:
+    /// factorial goes deeper, kbn stays light.
+    #[test]
+    fn per_form_bounds_differ_for_equal_sizes() {
+        let factorial = p1_bounds_for_form(10_000, "factorial").unwrap();
+        let kbn = p1_bounds_for_form(10_000, "kbn").unwrap();
+        assert_ne!(factorial, kbn);
+        assert!(factorial.0 > kbn.0, "factorial B1 should exceed kbn B1");
+        assert!(factorial.1 > kbn.1, "factorial B2 should exceed kbn B2");
+
+        // Thresholds differ too: 6K bits is filtered for factorial only
+        assert!(p1_bounds_for_form(6_000, "factorial").is_some());
+        assert!(p1_bounds_for_form(6_000, "kbn").is_none());
+    }
+
+    /// The default table reproduces the documented tiers; unknown forms use it.
+    #[test]
+    fn default_bounds_match_documented_tiers() {
+        assert_eq!(p1_bounds_for_form(4_999, "default"), None);
+        assert_eq!(
+            p1_bounds_for_form(5_000, "default"),
+            Some((100_000, 10_000_000))
+        );
+        assert_eq!(
+            p1_bounds_for_form(20_000, "default"),
+            Some((500_000, 50_000_000))
+        );
+        assert_eq!(
+            p1_bounds_for_form(50_000, "default"),
+            Some((1_000_000, 100_000_000))
+        );
+        assert_eq!(
+            p1_bounds_for_form(30_000, "no_such_form"),
+            p1_bounds_for_form(30_000, "default")
+        );
+    }
+
+    /// Both the factorial and kbn filters catch a ~8K-bit composite whose
+    /// factor 65537 has perfectly smooth p-1 = 2^16. The cofactor is the
+    /// factorial prime 974! - 1 (OEIS A002982), cheap to build and with no
+    /// smooth p-1.
+    #[test]
+    fn per_form_filters_catch_smooth_factor() {
+        let q = Integer::from(Integer::factorial(974)) - 1u32;
+        let n = Integer::from(65537u32) * q;
+        assert!(n.significant_bits() >= 8_000, "must hit both forms' tiers");
+        assert!(adaptive_p1_filter_for_form(&n, "factorial"));
+        assert!(adaptive_p1_filter_for_form(&n, "kbn"));
+    }
+
     // ── Stage 1 Boundary Conditions ──────────────────────────────────
 
     /// Verify B1 < 2 returns None immediately (no primes to sieve with).
@@ -823,4 +1000,65 @@ mod tests {
             "P-1 with B1=5 should miss 29 (p-1 needs prime 7)"
         );
     }
+
+    // ── Restricted-Class Trial Division (Mersenne / Wagstaff) ─────────
+
+    /// Smallest odd factor of `n` up to `limit` by plain trial division.
+    fn smallest_factor_brute(n: u64, limit: u64) -> Option<u64> {
+        (3..=limit)
+            .step_by(2)
+            .take_while(|q| q * q <= n)
+            .find(|q| n.is_multiple_of(*q))
+    }
+
+    /// 2^11 − 1 = 2047 = 23 · 89; 23 = 2·1·11 + 1 ≡ 7 (mod 8) is in the class.
+    #[test]
+    fn mersenne_trial_finds_m11_factor() {
+        assert_eq!(mersenne_factor_trial(11, 1000), Some(23));
+        assert_eq!(mersenne_factor_trial(23, 1000), Some(47));
+        assert_eq!(mersenne_factor_trial(29, 1000), Some(233));
+    }
+
+    /// Prime Mersenne and Wagstaff numbers yield no factor, and the number
+    /// itself is never reported (127 = 2·9·7 + 1 is in M7's class).
+    #[test]
+    fn special_trial_skips_primes() {
+        for p in [3, 5, 7, 13, 17, 19, 31, 61] {
+            assert_eq!(mersenne_factor_trial(p, u64::MAX), None, "M{}", p);
+        }
+        for p in [3, 5, 7, 11, 13, 17, 19, 23, 31, 43, 61] {
+            assert_eq!(wagstaff_factor_trial(p, 1 << 20), None, "W{}", p);
+        }
+    }
+
+    /// (2^29 + 1)/3 = 59 · 3033169; 59 ≡ 3 (mod 8).
+    #[test]
+    fn wagstaff_trial_finds_w29_factor() {
+        assert_eq!(wagstaff_factor_trial(29, 1000), Some(59));
+    }
+
+    /// The class filter never skips a real factor: for every odd prime
+    /// p < 64 the restricted search agrees with plain trial division.
+    #[test]
+    fn special_trial_matches_brute_force() {
+        let limit = 100_000;
+        for p in crate::sieve::generate_primes(63).into_iter().skip(1) {
+            let mersenne = (1u64 << p) - 1;
+            assert_eq!(
+                mersenne_factor_trial(p, limit),
+                smallest_factor_brute(mersenne, limit),
+                "M{}",
+                p
+            );
+            let wagstaff = ((1u128 << p) + 1) / 3;
+            if wagstaff <= u64::MAX as u128 {
+                assert_eq!(
+                    wagstaff_factor_trial(p, limit),
+                    smallest_factor_brute(wagstaff as u64, limit),
+                    "W{}",
+                    p
+                );
+            }
+        }
+    }
 }
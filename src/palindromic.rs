@@ -8,11 +8,18 @@
 //!
 //! 1. **Half-digit generation**: A d-digit palindrome is fully determined by its
 //!    first ⌈d/2⌉ digits (the "half"). The search iterates over half-values
-//!    and mirrors them to produce full palindromes.
+//!    and mirrors them to produce full palindromes. In
+//!    [`PalindromeOrder::MinimalChange`] the halves after each leading digit
+//!    follow a reflected base-b Gray code, so consecutive palindromes differ
+//!    by one precomputed place weight and each candidate costs a single
+//!    big-integer addition instead of a rebuild. Found primes are sorted
+//!    before reporting because that order isn't ascending.
 //!
-//! 2. **Even-digit skip**: Even-length palindromes in base b are always divisible
-//!    by b+1 (e.g., all 4-digit base-10 palindromes are divisible by 11). The
-//!    only possible even-length palindromic prime is b+1 itself (= "11" in base b).
+//! 2. **Even-digit skip** (`is_length_viable`): Even-length palindromes in base b
+//!    are always divisible by b+1 (e.g., all 4-digit base-10 palindromes are
+//!    divisible by 11). The only possible even-length palindromic prime is b+1
+//!    itself (= "11" in base b), so every other even length is pruned before
+//!    any candidate is generated.
 //!
 //! 3. **Leading digit filter**: The first digit of a palindrome equals its last
 //!    digit. A prime > b must have its last digit coprime to b (e.g., in base 10,
@@ -49,12 +56,12 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Instant;
 
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::CoordinationClient;
 use crate::{mr_screened_test, sieve};
@@ -146,12 +153,199 @@ fn is_filter_composite(digits: &[u32], base: u32, filter_primes: &[u64], digit_c
     false
 }
 
+/// Whether any `digit_count`-digit palindrome in `base` can be prime.
+///
+/// Even lengths are divisible by base+1 and are pruned, except length 2 when
+/// base+1 ("11") is itself prime. Odd lengths are always viable.
+pub fn is_length_viable(base: u32, digit_count: u64) -> bool {
+    match digit_count {
+        0 => false,
+        2 => Integer::from(base + 1).is_probably_prime(25) != IsPrime::No,
+        d => d % 2 == 1,
+    }
+}
+
+/// Order in which the half-digits of a palindrome block are enumerated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PalindromeOrder {
+    /// Increasing half values; survivors of the digit filter are rebuilt
+    /// from their digits.
+    #[default]
+    Ascending,
+    /// Reflected base-b Gray code over the half-digits after the leading
+    /// one: consecutive candidates differ in a single half-digit by ±1, so
+    /// each palindrome is the previous one plus or minus a precomputed place
+    /// weight. Candidates are no longer ascending.
+    MinimalChange,
+}
+
+impl PalindromeOrder {
+    /// The `--order` name, also recorded in checkpoints.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PalindromeOrder::Ascending => "ascending",
+            PalindromeOrder::MinimalChange => "minimal-change",
+        }
+    }
+}
+
+impl std::str::FromStr for PalindromeOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ascending" => Ok(PalindromeOrder::Ascending),
+            "minimal-change" => Ok(PalindromeOrder::MinimalChange),
+            _ => anyhow::bail!("unknown palindrome order {s:?} (ascending, minimal-change)"),
+        }
+    }
+}
+
+/// Walk over the palindromes of one digit count that share a leading digit.
+///
+/// `counter` holds the walk's *position* as half-digits and only counts up;
+/// in ascending order `half` equals it. In minimal-change order a counter
+/// increment that stops carrying at index i moves half-digit i one step in
+/// its direction and reverses the direction of every digit after it, which
+/// leaves those digits where they were. The position equals the half value
+/// in ascending order, so checkpoints and resume work the same in both
+/// orders.
+struct HalfWalk {
+    base: u32,
+    is_odd: bool,
+    order: PalindromeOrder,
+    half: Vec<u32>,
+    counter: Vec<u32>,
+    /// Minimal-change only: whether each half-digit is moving up.
+    up: Vec<bool>,
+    /// Minimal-change only: change in the palindrome for +1 in each
+    /// half-digit (b^(d−1−i) + b^i, or b^i for the middle digit).
+    weights: Vec<Integer>,
+    /// Minimal-change only: the current palindrome.
+    value: Integer,
+}
+
+impl HalfWalk {
+    /// Start at `position`, a half value taken as a position in `order`.
+    fn at(base: u32, digit_count: u64, order: PalindromeOrder, position: &Integer) -> Self {
+        let half_len = digit_count.div_ceil(2) as usize;
+        let is_odd = digit_count % 2 == 1;
+        let counter = integer_to_digits(position, half_len, base);
+        let mut half = counter.clone();
+        let mut up = vec![true; half_len];
+        let mut weights = Vec::new();
+        let mut value = Integer::new();
+        if order == PalindromeOrder::MinimalChange {
+            // Digit i moves up while the position of the digits before it
+            // (after the leading one) is even.
+            let mut odd_prefix = false;
+            for i in 1..half_len {
+                up[i] = !odd_prefix;
+                if !up[i] {
+                    half[i] = base - 1 - counter[i];
+                }
+                odd_prefix = (odd_prefix && base % 2 == 1) != (counter[i] % 2 == 1);
+            }
+            let place = |e: u64| Integer::from(base).pow(e as u32);
+            weights = (0..half_len as u64)
+                .map(|i| match digit_count - 1 - i {
+                    m if m == i => place(i),
+                    m => place(m) + place(i),
+                })
+                .collect();
+            value = digits_to_integer(&mirror_to_palindrome(&half, is_odd), base);
+        }
+        HalfWalk {
+            base,
+            is_odd,
+            order,
+            half,
+            counter,
+            up,
+            weights,
+            value,
+        }
+    }
+
+    fn digits(&self) -> Vec<u32> {
+        mirror_to_palindrome(&self.half, self.is_odd)
+    }
+
+    /// The current palindrome: kept up to date in minimal-change order,
+    /// rebuilt from `digits` in ascending order.
+    fn value(&self, digits: &[u32]) -> Integer {
+        match self.order {
+            PalindromeOrder::Ascending => digits_to_integer(digits, self.base),
+            PalindromeOrder::MinimalChange => self.value.clone(),
+        }
+    }
+
+    /// Step to the next palindrome; false once the leading digit's block is
+    /// exhausted.
+    fn advance(&mut self) -> bool {
+        if increment_digits(&mut self.counter[1..], self.base) {
+            // Position the checkpoint at the next leading digit's block.
+            self.counter[0] += 1;
+            return false;
+        }
+        // The carry stopped at the last nonzero digit; the ones after wrapped.
+        let i = self.counter.iter().rposition(|&d| d != 0).unwrap();
+        for up in &mut self.up[i + 1..] {
+            *up = !*up;
+        }
+        match self.order {
+            PalindromeOrder::Ascending => self.half[i..].copy_from_slice(&self.counter[i..]),
+            PalindromeOrder::MinimalChange if self.up[i] => {
+                self.half[i] += 1;
+                self.value += &self.weights[i];
+            }
+            PalindromeOrder::MinimalChange => {
+                self.half[i] -= 1;
+                self.value -= &self.weights[i];
+            }
+        }
+        true
+    }
+
+    /// Position of the next palindrome to test, for the checkpoint.
+    fn position(&self) -> Integer {
+        digits_to_integer(&self.counter, self.base)
+    }
+}
+
+/// Every `digit_count`-digit palindrome in `base` whose leading digit is in
+/// `lead_digits`, block by block in the order given and within each block in
+/// `order`. Both orders yield the same set.
+pub fn generate_palindromes(
+    base: u32,
+    digit_count: u64,
+    lead_digits: &[u32],
+    order: PalindromeOrder,
+) -> impl Iterator<Item = Integer> + '_ {
+    let half_len = digit_count.div_ceil(2) as u32;
+    let base_pow_half = Integer::from(base).pow(half_len - 1);
+    lead_digits.iter().flat_map(move |&lead| {
+        let start = Integer::from(lead) * &base_pow_half;
+        let mut walk = Some(HalfWalk::at(base, digit_count, order, &start));
+        std::iter::from_fn(move || {
+            let current = walk.as_mut()?;
+            let value = current.value(&current.digits());
+            if !current.advance() {
+                walk = None;
+            }
+            Some(value)
+        })
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn search(
     base: u32,
     min_digits: u64,
     max_digits: u64,
+    order: PalindromeOrder,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -160,19 +354,41 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
-    let (resume_digits, resume_half) = match checkpoint::load(checkpoint_path) {
+    let params =
+        serde_json::json!({"base": base, "min_digits": min_digits, "max_digits": max_digits});
+    crate::search_params::validate("palindromic", &params)?;
+
+    // The interrupted leading-digit block is finished in the order it was
+    // walked in, since `half_value` is a position in that order; later blocks
+    // use `order`.
+    let (resume_digits, resume_half, resume_order) = match checkpoint::load(checkpoint_path) {
         Some(Checkpoint::Palindromic {
             digit_count,
             half_value,
+            order: saved_order,
             ..
         }) if digit_count >= min_digits && digit_count <= max_digits => {
-            let half: Integer = half_value
-                .parse()
-                .unwrap_or_else(|_| Integer::from(base).pow((digit_count.div_ceil(2) - 1) as u32));
-            info!(digit_count, half_value, "resuming palindromic search");
-            (digit_count, Some(half))
+            let saved_order = saved_order.as_deref().unwrap_or("ascending");
+            match saved_order.parse::<PalindromeOrder>() {
+                Ok(saved_order) => {
+                    let half: Integer = half_value.parse().unwrap_or_else(|_| {
+                        Integer::from(base).pow((digit_count.div_ceil(2) - 1) as u32)
+                    });
+                    info!(
+                        digit_count,
+                        half_value,
+                        order = saved_order.as_str(),
+                        "resuming palindromic search"
+                    );
+                    (digit_count, Some(half), saved_order)
+                }
+                Err(_) => {
+                    warn!(saved_order, "checkpoint order unknown, starting fresh");
+                    (min_digits, None, order)
+                }
+            }
         }
-        _ => (min_digits, None),
+        _ => (min_digits, None, order),
     };
 
     // Leading digit filter: for a palindrome, the first digit equals the last digit.
@@ -203,54 +419,62 @@ pub fn search(
     );
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut total_filtered: u64 = 0;
 
     for digit_count in resume_digits..=max_digits {
-        // Even-digit palindromes are always divisible by (base+1).
-        // Only (base+1) itself can be prime — it's "11" in that base with 2 digits.
+        if !is_length_viable(base, digit_count) {
+            info!(
+                digit_count,
+                base, "skipping length: every palindrome is divisible by base+1"
+            );
+            continue;
+        }
+
+        // The only viable even length is 2, whose sole candidate is base+1 ("11").
         if digit_count % 2 == 0 {
-            if digit_count == 2 {
-                let candidate = Integer::from(base + 1);
-                let r = candidate.is_probably_prime(mr_rounds);
-                progress.tested.fetch_add(1, Ordering::Relaxed);
-                if r != IsPrime::No {
-                    let cert = match r {
-                        IsPrime::Yes => "deterministic",
-                        _ => "probabilistic",
-                    };
-                    let expr = candidate.to_string_radix(10);
-                    let digits = expr.len() as u64;
-                    progress.found.fetch_add(1, Ordering::Relaxed);
-                    if let Some(eb) = event_bus {
-                        eb.emit(events::Event::PrimeFound {
-                            form: "palindromic".into(),
-                            expression: expr.clone(),
-                            digits,
-                            proof_method: cert.to_string(),
-                            timestamp: Instant::now(),
-                        });
-                    } else {
-                        info!(expression = %expr, digits, cert, "prime found");
-                    }
-                    db.insert_prime_sync(
-                        rt,
-                        "palindromic",
-                        &expr,
+            let candidate = Integer::from(base + 1);
+            let r = mr_screened_test(&candidate, mr_rounds);
+            progress.tested.fetch_add(1, Ordering::Relaxed);
+            if r != IsPrime::No {
+                let cert = crate::certainty_label(&candidate, r);
+                let expr = candidate.to_string_radix(10);
+                let digits = expr.len() as u64;
+                if crate::defer_unproven(
+                    db,
+                    rt,
+                    event_bus,
+                    worker_client.and_then(|wc| wc.search_job_id()),
+                    "palindromic",
+                    &expr,
+                    digits,
+                    search_params,
+                    cert,
+                )? {
+                    continue;
+                }
+                progress.found.fetch_add(1, Ordering::Relaxed);
+                if let Some(eb) = event_bus {
+                    eb.emit(events::Event::PrimeFound {
+                        form: "palindromic".into(),
+                        expression: expr.clone(),
                         digits,
-                        search_params,
-                        cert,
-                        None,
-                    )?;
-                    if let Some(wc) = worker_client {
-                        wc.report_prime("palindromic", &expr, digits, search_params, cert);
-                    }
+                        proof_method: cert.to_string(),
+                        search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
+                        timestamp: Instant::now(),
+                    });
+                    eb.check_milestone("palindromic", &expr, digits, || {
+                        db.get_best_digits_sync(rt, "palindromic").ok().flatten()
+                    });
+                } else {
+                    info!(expression = %expr, digits, cert, "prime found");
                 }
+                buffer.push("palindromic", &expr, digits, search_params, cert, None)?;
             }
             continue;
         }
 
         let half_len = digit_count.div_ceil(2) as usize;
-        let is_odd = digit_count % 2 == 1;
         let base_pow_half = Integer::from(base).pow((half_len - 1) as u32);
 
         // For single-digit palindromes, check all digits (2, 3, 5, 7 are all prime).
@@ -267,6 +491,7 @@ pub fn search(
 
             // Handle resume: skip sub-ranges we've already completed
             let mut start_half = sub_start.clone();
+            let mut walk_order = order;
             if digit_count == resume_digits {
                 if let Some(ref rh) = resume_half {
                     if *rh > sub_end {
@@ -274,6 +499,7 @@ pub fn search(
                     }
                     if *rh > start_half {
                         start_half = rh.clone();
+                        walk_order = resume_order;
                     }
                 }
             }
@@ -290,38 +516,31 @@ pub fn search(
             }
 
             // Use digit arrays for the inner loop to avoid per-candidate Integer arithmetic
-            let mut half_digits = integer_to_digits(&start_half, half_len, base);
-            let end_digits = integer_to_digits(&sub_end, half_len, base);
+            let mut walk = HalfWalk::at(base, digit_count, walk_order, &start_half);
 
             let mut exhausted = false;
-            while !exhausted && half_digits <= end_digits {
+            while !exhausted {
                 let mut batch: Vec<Integer> = Vec::new();
                 let mut batch_total: u64 = 0;
 
                 for _ in 0..BATCH_SIZE {
-                    if half_digits > end_digits {
-                        break;
-                    }
                     batch_total += 1;
 
-                    let full_digits = mirror_to_palindrome(&half_digits, is_odd);
+                    let full_digits = walk.digits();
 
                     // Digit-based pre-filter: check divisibility without building Integer
                     if is_filter_composite(&full_digits, base, &filter_primes, digit_count) {
                         total_filtered += 1;
                     } else {
-                        batch.push(digits_to_integer(&full_digits, base));
+                        batch.push(walk.value(&full_digits));
                     }
 
-                    if increment_digits(&mut half_digits, base) {
+                    if !walk.advance() {
                         exhausted = true;
                         break;
                     }
                 }
-
-                if batch_total == 0 {
-                    break;
-                }
+                progress.record_survivors(batch.iter().map(|num| num.to_u64_wrapping()));
 
                 *progress.current.lock().unwrap() = format!(
                     "{}-digit palindrome (base {}, d={})",
@@ -330,14 +549,16 @@ pub fn search(
 
                 // Only candidates surviving the digit filter need primality testing.
                 // Try PFGW first for large candidates (50-100x faster), fall back to GMP MR.
-                let found_primes: Vec<_> = batch
+                let mut found_primes: Vec<_> = batch
                     .into_par_iter()
                     .filter_map(|num| {
+                        let _busy = crate::progress::busy();
                         // Try PFGW acceleration (palindromic: PRP via decimal string).
                         // pfgw::is_available() is a cheap check; only compute the
                         // expensive to_string_radix(10) when PFGW will actually run.
                         if pfgw::is_available(digit_count) {
                             let decimal = num.to_string_radix(10);
+                            let _testing = progress.testing(&decimal, &num);
                             match pfgw::try_test(&decimal, &num, pfgw::PfgwMode::Prp) {
                                 Some(pfgw::PfgwResult::Prime {
                                     is_deterministic, ..
@@ -355,17 +576,14 @@ pub fn search(
                         }
 
                         // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                        if crate::p1::adaptive_p1_filter(&num) {
+                        if crate::p1::adaptive_p1_filter_for_form(&num, "palindromic") {
                             return None;
                         }
 
                         // GMP Miller-Rabin fallback — defer to_string_radix until prime is found
                         let r = mr_screened_test(&num, mr_rounds);
                         if r != IsPrime::No {
-                            let cert = match r {
-                                IsPrime::Yes => "deterministic",
-                                _ => "probabilistic",
-                            };
+                            let cert = crate::certainty_label(&num, r);
                             let decimal = num.to_string_radix(10);
                             Some((decimal, digit_count, cert.to_string()))
                         } else {
@@ -376,7 +594,22 @@ pub fn search(
 
                 progress.tested.fetch_add(batch_total, Ordering::Relaxed);
 
+                // Minimal-change batches aren't ascending; report in numeric order.
+                found_primes.sort_by(|a, b| (a.0.len(), &a.0).cmp(&(b.0.len(), &b.0)));
                 for (expr, digits, certainty) in found_primes {
+                    if crate::defer_unproven(
+                        db,
+                        rt,
+                        event_bus,
+                        worker_client.and_then(|wc| wc.search_job_id()),
+                        "palindromic",
+                        &expr,
+                        digits,
+                        search_params,
+                        &certainty,
+                    )? {
+                        continue;
+                    }
                     progress.found.fetch_add(1, Ordering::Relaxed);
                     if let Some(eb) = event_bus {
                         eb.emit(events::Event::PrimeFound {
@@ -384,13 +617,16 @@ pub fn search(
                             expression: expr.clone(),
                             digits,
                             proof_method: certainty.clone(),
+                            search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                             timestamp: Instant::now(),
                         });
+                        eb.check_milestone("palindromic", &expr, digits, || {
+                            db.get_best_digits_sync(rt, "palindromic").ok().flatten()
+                        });
                     } else {
                         info!(expression = %expr, digits, certainty, "prime found");
                     }
-                    db.insert_prime_sync(
-                        rt,
+                    buffer.push(
                         "palindromic",
                         &expr,
                         digits,
@@ -398,19 +634,19 @@ pub fn search(
                         &certainty,
                         None,
                     )?;
-                    if let Some(wc) = worker_client {
-                        wc.report_prime("palindromic", &expr, digits, search_params, &certainty);
-                    }
                 }
 
+                buffer.flush_if_due()?;
+
                 if last_checkpoint.elapsed().as_secs() >= 60 {
-                    let half_int = digits_to_integer(&half_digits, base);
-                    let half_val = half_int.to_string_radix(10);
+                    buffer.flush()?;
+                    let half_val = walk.position().to_string_radix(10);
                     checkpoint::save(
                         checkpoint_path,
                         &Checkpoint::Palindromic {
                             digit_count,
                             half_value: half_val.clone(),
+                            order: Some(walk_order.as_str().into()),
                             min_digits: Some(min_digits),
                             max_digits: Some(max_digits),
                         },
@@ -420,13 +656,14 @@ pub fn search(
                 }
 
                 if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
-                    let half_int = digits_to_integer(&half_digits, base);
-                    let half_val = half_int.to_string_radix(10);
+                    buffer.flush()?;
+                    let half_val = walk.position().to_string_radix(10);
                     checkpoint::save(
                         checkpoint_path,
                         &Checkpoint::Palindromic {
                             digit_count,
                             half_value: half_val.clone(),
+                            order: Some(walk_order.as_str().into()),
                             min_digits: Some(min_digits),
                             max_digits: Some(max_digits),
                         },
@@ -441,6 +678,7 @@ pub fn search(
     if total_filtered > 0 {
         info!(total_filtered, "digit pre-filter elimination complete");
     }
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     Ok(())
 }
@@ -545,6 +783,150 @@ mod tests {
         }
     }
 
+    /// Verifies the minimal-change enumeration yields exactly the ascending
+    /// set of palindromes, including odd bases (3, 7), where the reflected
+    /// Gray code's directions depend on the parity of every earlier digit.
+    ///
+    /// Within a leading-digit block consecutive minimal-change candidates
+    /// differ by one step of a single half-digit, i.e. in one base-b digit
+    /// (the middle one) or in a mirrored pair.
+    #[test]
+    fn minimal_change_order_matches_ascending_set() {
+        for (base, digit_count) in [(10u32, 1u64), (10, 5), (10, 7), (2, 11), (3, 7), (7, 5)] {
+            let leads: Vec<u32> = (1..base).collect();
+            let asc: Vec<Integer> =
+                generate_palindromes(base, digit_count, &leads, PalindromeOrder::Ascending)
+                    .collect();
+            assert!(asc.windows(2).all(|w| w[0] < w[1]), "base {}", base);
+            let half = digit_count.div_ceil(2) as u32;
+            let per_lead = (base as usize).pow(half - 1);
+            assert_eq!(asc.len(), (base as usize - 1) * per_lead);
+
+            let order = PalindromeOrder::MinimalChange;
+            let mut gray: Vec<Integer> =
+                generate_palindromes(base, digit_count, &leads, order).collect();
+            let radix = base as i32;
+            for w in gray.windows(2) {
+                let (a, b) = (w[0].to_string_radix(radix), w[1].to_string_radix(radix));
+                assert_eq!(a.len(), digit_count as usize);
+                if a.as_bytes()[0] != b.as_bytes()[0] {
+                    continue;
+                }
+                let changed: Vec<u8> = a
+                    .bytes()
+                    .zip(b.bytes())
+                    .filter(|(x, y)| x != y)
+                    .map(|(x, y)| x.abs_diff(y))
+                    .collect();
+                assert!(matches!(changed[..], [1] | [1, 1]), "{} -> {}", a, b);
+            }
+            gray.sort();
+            assert_eq!(gray, asc, "base {} digits {}", base, digit_count);
+        }
+    }
+
+    /// A walk restarted from a checkpointed position continues the same
+    /// minimal-change sequence, and `position` counts up like a half value.
+    /// Base 3, 9 digits: the block for leading digit 1 is halves 81..=161.
+    #[test]
+    fn minimal_change_walk_resumes_from_position() {
+        let order = PalindromeOrder::MinimalChange;
+        let mut walk = HalfWalk::at(3, 9, order, &Integer::from(81u32));
+        for step in 0..40 {
+            assert_eq!(walk.position(), 81 + step);
+            assert!(walk.advance());
+        }
+        let mut walk = HalfWalk::at(3, 9, order, &walk.position());
+        let mut rest = Vec::new();
+        loop {
+            rest.push(walk.value(&walk.digits()));
+            if !walk.advance() {
+                break;
+            }
+        }
+        assert_eq!(walk.position(), 162, "next block after exhaustion");
+        let expected: Vec<Integer> = generate_palindromes(3, 9, &[1], order).skip(40).collect();
+        assert_eq!(rest, expected);
+    }
+
+    /// Base-10 palindromic primes a search over `min..=max` digits reports,
+    /// after resuming from `resume` if given.
+    fn search_primes(
+        order: PalindromeOrder,
+        min_digits: u64,
+        max_digits: u64,
+        resume: Option<Checkpoint>,
+    ) -> Vec<u64> {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("primes.jsonl");
+        let sink = crate::prime_sink::FilePrimeSink::open(&out).unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let checkpoint = dir.path().join("palindromic.checkpoint");
+        if let Some(cp) = resume {
+            checkpoint::save(&checkpoint, &cp).unwrap();
+        }
+        let progress = Progress::new();
+        search(
+            10,
+            min_digits,
+            max_digits,
+            order,
+            &progress,
+            &sink,
+            rt.handle(),
+            &checkpoint,
+            "{}",
+            25,
+            1000,
+            None,
+            None,
+        )
+        .unwrap();
+        let text = std::fs::read_to_string(&out).unwrap();
+        text.lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+            .map(|v| v["expression"].as_str().unwrap().parse::<u64>().unwrap())
+            .collect()
+    }
+
+    /// Both orders find the 113 base-10 palindromic primes below 10^5
+    /// (OEIS A002385) and report them in the same ascending order.
+    #[test]
+    fn search_reports_same_primes_in_both_orders() {
+        let ascending = search_primes(PalindromeOrder::Ascending, 1, 5, None);
+        assert_eq!(ascending.len(), 113);
+        assert_eq!(ascending[..6], [2, 3, 5, 7, 11, 101]);
+        assert!(ascending.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(search_primes(PalindromeOrder::MinimalChange, 1, 5, None), ascending);
+    }
+
+    /// A checkpoint taken mid-block in minimal-change order, resumed with
+    /// `--order ascending`, finishes that block in minimal-change order and
+    /// runs the later blocks ascending: nothing is skipped or repeated.
+    #[test]
+    fn resume_with_other_order_finishes_block_in_saved_order() {
+        let checkpoint = Checkpoint::Palindromic {
+            digit_count: 5,
+            half_value: "135".into(),
+            order: Some("minimal-change".into()),
+            min_digits: Some(5),
+            max_digits: Some(5),
+        };
+        let mut resumed = search_primes(PalindromeOrder::Ascending, 5, 5, Some(checkpoint));
+        resumed.sort_unstable();
+
+        let is_prime = |p: &Integer| p.is_probably_prime(25) != IsPrime::No;
+        let mut expected: Vec<u64> =
+            generate_palindromes(10, 5, &[1], PalindromeOrder::MinimalChange)
+                .skip(35)
+                .chain(generate_palindromes(10, 5, &[3, 7, 9], PalindromeOrder::Ascending))
+                .filter(is_prime)
+                .map(|p| p.to_u64().unwrap())
+                .collect();
+        expected.sort_unstable();
+        assert_eq!(resumed, expected);
+    }
+
     // ── Digit Array Operations ────────────────────────────────────────
 
     /// Verifies basic increment: [1,2,3] + 1 = [1,2,4] with no carry.
@@ -718,6 +1100,27 @@ mod tests {
         }
     }
 
+    /// Even base-10 lengths are pruned except 2 (the prime 11); odd lengths stay.
+    #[test]
+    fn is_length_viable_prunes_even_base10_lengths() {
+        assert!(is_length_viable(10, 2), "11 is prime");
+        for d in (4..=40).step_by(2) {
+            assert!(!is_length_viable(10, d), "{} digits", d);
+        }
+        for d in (1..=41).step_by(2) {
+            assert!(is_length_viable(10, d), "{} digits", d);
+        }
+        assert!(!is_length_viable(10, 0));
+    }
+
+    /// Length 2 is viable only when base+1 is prime: base 8 gives 9 = 3², base 6 gives 7.
+    #[test]
+    fn is_length_viable_two_digits_depends_on_base_plus_one() {
+        assert!(!is_length_viable(8, 2));
+        assert!(is_length_viable(6, 2));
+        assert!(is_length_viable(2, 2), "11 in base 2 is 3");
+    }
+
     // ── Batch Enumeration ─────────────────────────────────────────────
 
     /// Verifies the correct count of palindromes per leading digit sub-range.
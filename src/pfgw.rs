@@ -35,6 +35,8 @@ pub enum PfgwResult {
     Composite,
     /// PFGW was not available or not applicable.
     Unavailable { reason: String },
+    /// PFGW ran but crashed, could not be started, or hit the timeout.
+    Failed { reason: String, timed_out: bool },
 }
 
 /// Test mode for PFGW invocation.
@@ -153,8 +155,9 @@ pub fn try_test(expression: &str, candidate: &rug::Integer, mode: PfgwMode) -> O
     let input_path = dir.join(format!("pfgw_{}.txt", id));
 
     if let Err(e) = std::fs::write(&input_path, format!("{}\n", expression)) {
-        return Some(PfgwResult::Unavailable {
+        return Some(PfgwResult::Failed {
             reason: format!("failed to write input file: {}", e),
+            timed_out: false,
         });
     }
 
@@ -166,8 +169,9 @@ pub fn try_test(expression: &str, candidate: &rug::Integer, mode: PfgwMode) -> O
 
     match result {
         Ok(r) => Some(r),
-        Err(e) => Some(PfgwResult::Unavailable {
+        Err(e) => Some(PfgwResult::Failed {
             reason: format!("PFGW execution failed: {}", e),
+            timed_out: false,
         }),
     }
 }
@@ -212,22 +216,30 @@ fn run_subprocess(
     let start = Instant::now();
     loop {
         match child.try_wait()? {
-            Some(_status) => {
+            Some(status) => {
                 let mut stdout = String::new();
                 if let Some(mut out) = child.stdout.take() {
                     out.read_to_string(&mut stdout)?;
                 }
                 let stderr = stderr_thread.join().unwrap_or_default();
                 let combined = format!("{}\n{}", stdout, stderr);
-                return Ok(parse_output(&combined));
+                return Ok(match parse_output(&combined) {
+                    // No verdict and a bad exit: the tool crashed.
+                    PfgwResult::Unavailable { reason } if !status.success() => PfgwResult::Failed {
+                        reason: format!("exited with {}: {}", status, reason),
+                        timed_out: false,
+                    },
+                    result => result,
+                });
             }
             None => {
                 if start.elapsed() > timeout {
                     let _ = child.kill();
                     let _ = child.wait();
                     let _ = stderr_thread.join();
-                    return Ok(PfgwResult::Unavailable {
+                    return Ok(PfgwResult::Failed {
                         reason: format!("timed out after {}s", timeout.as_secs()),
+                        timed_out: true,
                     });
                 }
                 std::thread::sleep(Duration::from_millis(100));
@@ -508,6 +520,50 @@ mod tests {
         assert!(crate::estimate_digits(&large) > 10_000);
     }
 
+    // ── Subprocess Failures ──────────────────────────────────────
+
+    /// Write an executable shell script standing in for the PFGW binary.
+    #[cfg(unix)]
+    fn fake_pfgw(dir: &Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join("pfgw64");
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    /// A binary that dies without printing a verdict is a tool failure, not
+    /// an "unavailable" skip.
+    #[cfg(unix)]
+    #[test]
+    fn crashed_binary_is_failed() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_pfgw(dir.path(), "echo 'Segmentation fault' >&2; exit 139");
+        let input = dir.path().join("input.txt");
+        std::fs::write(&input, "7!+1\n").unwrap();
+        match run_subprocess(&binary, &input, PfgwMode::Prp, Duration::from_secs(10)).unwrap() {
+            PfgwResult::Failed { reason, timed_out } => {
+                assert!(!timed_out);
+                assert!(reason.contains("exited with"), "reason: {}", reason);
+            }
+            other => panic!("expected Failed, got {:?}", other),
+        }
+    }
+
+    /// A binary that outlives the timeout is killed and reported as timed out.
+    #[cfg(unix)]
+    #[test]
+    fn hung_binary_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_pfgw(dir.path(), "exec sleep 5");
+        let input = dir.path().join("input.txt");
+        std::fs::write(&input, "7!+1\n").unwrap();
+        match run_subprocess(&binary, &input, PfgwMode::Prp, Duration::from_millis(200)).unwrap() {
+            PfgwResult::Failed { timed_out, .. } => assert!(timed_out),
+            other => panic!("expected timed-out Failed, got {:?}", other),
+        }
+    }
+
     // ── Integration Tests (require PFGW binary) ─────────────────
 
     /// Tests PFGW execution with a known factorial prime: 11!+1 = 39916801.
@@ -525,6 +581,7 @@ mod tests {
                     eprintln!("PFGW not available, skipping integration test");
                 }
                 PfgwResult::Composite => panic!("11!+1 should be prime!"),
+                PfgwResult::Failed { reason, .. } => panic!("PFGW failed: {}", reason),
             }
         }
     }
@@ -545,6 +602,7 @@ mod tests {
                     eprintln!("PFGW not available, skipping integration test");
                 }
                 PfgwResult::Composite => panic!("(2^5+1)/3 should be prime!"),
+                PfgwResult::Failed { reason, .. } => panic!("PFGW failed: {}", reason),
             }
         }
     }
@@ -602,6 +660,7 @@ mod tests {
                     eprintln!("PFGW not available, skipping integration test");
                 }
                 PfgwResult::Composite => panic!("1*2^1+1 = 3 should be prime!"),
+                PfgwResult::Failed { reason, .. } => panic!("PFGW failed: {}", reason),
             }
         }
     }
@@ -621,6 +680,7 @@ mod tests {
                 PfgwResult::Unavailable { .. } => {
                     eprintln!("PFGW not available, skipping integration test");
                 }
+                PfgwResult::Failed { reason, .. } => panic!("PFGW failed: {}", reason),
             }
         }
     }
@@ -639,6 +699,7 @@ mod tests {
                     eprintln!("PFGW not available, skipping integration test");
                 }
                 PfgwResult::Composite => panic!("2^4+1 = 17 should be prime!"),
+                PfgwResult::Failed { reason, .. } => panic!("PFGW failed: {}", reason),
             }
         }
     }
@@ -657,6 +718,7 @@ mod tests {
                     eprintln!("PFGW not available, skipping integration test");
                 }
                 PfgwResult::Composite => panic!("(2^7-1)^2-2 = 16127 should be prime!"),
+                PfgwResult::Failed { reason, .. } => panic!("PFGW failed: {}", reason),
             }
         }
     }
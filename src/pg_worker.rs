@@ -25,12 +25,60 @@
 //! On success, the interval resets to the base 10s. This prevents thundering herd
 //! on temporary PG outages.
 //!
+//! ## Startup Benchmark
+//!
+//! Before registering, `new()` runs [`crate::metrics::benchmark_score`] and
+//! stores the result in `workers.benchmark_score`, where `/api/fleet/topology`
+//! reports it per host.
+//!
 //! ## Block Progress Reporting
 //!
 //! The `current_block_id` field tracks which block the worker is processing.
 //! On each successful heartbeat, live progress (tested/found) is reported to
 //! `update_block_progress`, enabling real-time block tracking and smarter reclaim.
 //!
+//! ## Thread Utilization
+//!
+//! Each successful heartbeat also stores [`crate::progress::thread_utilization`]
+//! in `workers.rayon_threads` / `threads_busy`, which `/api/fleet` reports per
+//! worker as `rayon_threads` and `threads_busy`.
+//!
+//! ## Current Candidate
+//!
+//! `current_candidate` holds the candidate the engine is testing and when it
+//! started (see [`crate::progress::Progress::testing`]). The heartbeat stores
+//! it in `workers.current_candidate` / `current_candidate_started_at`, which
+//! `/api/fleet` reports as `current_candidate` and `current_candidate_secs`.
+//!
+//! ## Pushgateway
+//!
+//! With `--pushgateway-url` set, the heartbeat thread also folds tested/found,
+//! CPU/memory and the heartbeat RTT into a worker-local
+//! [`PushGateway`](crate::prom_metrics::PushGateway) registry and hands it
+//! to a push thread after every heartbeat attempt, so the PUT never delays
+//! the next heartbeat. A failed push is only logged.
+//!
+//! ## Shutdown
+//!
+//! `darkreach work` calls [`stop_on_signal`] so SIGINT/SIGTERM raise the same
+//! stop flag as a dashboard stop instead of killing the process. A DB write
+//! in flight when the signal lands runs to completion, the search saves its
+//! checkpoint and returns, and [`BlockOutcome::of`] then releases the block's
+//! claim rather than reporting a partial block as complete. Before the
+//! release, [`release_checkpoint`] turns that checkpoint into the block's
+//! `block_checkpoint`, so whoever claims it next resumes past the last index
+//! tested.
+//!
+//! ## Memory Guard
+//!
+//! With `--min-free-mem-mb`, the work loop checks each block's largest
+//! candidate against [`MemoryGuard`] before running it. A block whose
+//! estimated working set would not fit goes to the back of the local queue
+//! and the next one is tried ([`next_fitting`]). When nothing queued fits,
+//! the loop waits [`MEMORY_RETRY_INTERVAL`] for memory to free up; after
+//! [`MEMORY_RETRY_PASSES`] such waits it releases the queue for a bigger
+//! node and stops.
+//!
 //! ## Auto-Selection
 //!
 //! `main.rs` chooses `PgWorkerClient` when no `--coordinator` URL is given,
@@ -49,6 +97,152 @@ const HEARTBEAT_BASE_SECS: u64 = 10;
 /// Maximum heartbeat interval in seconds (after exponential backoff).
 const HEARTBEAT_MAX_SECS: u64 = 300;
 
+/// How long a stopping work loop waits for its final DB writes.
+pub const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+/// What the work loop does with a block once its search returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockOutcome {
+    /// Record the block as done.
+    Complete,
+    /// Hand the claim back so the block is resumed elsewhere.
+    Release,
+    /// Mark the block failed.
+    Fail,
+}
+
+impl BlockOutcome {
+    /// Searches return `Ok` early after a stop, so `Ok` under a stop means
+    /// the block may be unfinished.
+    pub fn of(result: &anyhow::Result<()>, stop_requested: bool) -> Self {
+        match result {
+            Err(_) => BlockOutcome::Fail,
+            Ok(()) if stop_requested => BlockOutcome::Release,
+            Ok(()) => BlockOutcome::Complete,
+        }
+    }
+}
+
+/// The `block_checkpoint` for a block over `[start, end]` whose search
+/// stopped early: `{"last_tested": n}` with the index its checkpoint at
+/// `checkpoint_path` reached, when that index falls inside the block short
+/// of `end` (a claim resuming past `end` would have nothing to search).
+pub fn release_checkpoint(
+    checkpoint_path: &std::path::Path,
+    start: i64,
+    end: i64,
+) -> Option<serde_json::Value> {
+    let last = crate::checkpoint::load(checkpoint_path)?.last_index()?;
+    let last = i64::try_from(last).ok()?;
+    (start..end)
+        .contains(&last)
+        .then(|| serde_json::json!({ "last_tested": last }))
+}
+
+/// Share of the memory above the floor one candidate's working set may take.
+pub const MEMORY_SAFETY_FRACTION: f64 = 0.5;
+
+/// Wait between passes over the queue when no block fits in memory.
+pub const MEMORY_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Passes with no block fitting before the work loop gives its queue back.
+pub const MEMORY_RETRY_PASSES: u32 = 10;
+
+/// A candidate's working set as a multiple of its own size: the operands,
+/// a double-width product and multiplication scratch.
+pub const CANDIDATE_WORKING_SET_FACTOR: u64 = 8;
+
+/// Estimated bytes needed to test a candidate of `digits` decimal digits.
+pub fn candidate_working_set(digits: u64) -> u64 {
+    let bytes = (digits as f64 * std::f64::consts::LOG2_10 / 8.0).ceil() as u64;
+    bytes.saturating_mul(CANDIDATE_WORKING_SET_FACTOR)
+}
+
+/// `--min-free-mem-mb`: keep this much memory free, and defer candidates
+/// whose [`candidate_working_set`] exceeds [`MEMORY_SAFETY_FRACTION`] of what
+/// is available above the floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryGuard {
+    min_free_bytes: u64,
+}
+
+impl MemoryGuard {
+    /// A guard for a floor of `min_free_mb` MiB; `None` (no guard) for 0.
+    pub fn new(min_free_mb: u64) -> Option<Self> {
+        (min_free_mb > 0).then(|| MemoryGuard {
+            min_free_bytes: min_free_mb.saturating_mul(1 << 20),
+        })
+    }
+
+    /// Why a candidate of `digits` digits should not run with `available`
+    /// bytes free, or `None` if it fits.
+    pub fn refusal(&self, digits: u64, available: u64) -> Option<String> {
+        let needed = candidate_working_set(digits);
+        let usable = available.saturating_sub(self.min_free_bytes);
+        let budget = (usable as f64 * MEMORY_SAFETY_FRACTION) as u64;
+        (needed > budget).then(|| {
+            format!(
+                "{}-digit candidate needs ~{} MiB, {} MiB available above the {} MiB floor",
+                digits,
+                needed >> 20,
+                usable >> 20,
+                self.min_free_bytes >> 20
+            )
+        })
+    }
+
+    /// System memory currently available, from `sysinfo`.
+    pub fn available_memory() -> u64 {
+        let mut sys = sysinfo::System::new();
+        sys.refresh_memory();
+        sys.available_memory()
+    }
+}
+
+/// Take the first block in `queue` that `fits`, moving the ones it skips to
+/// the back in order. `None` if nothing fits; the queue is then unchanged.
+pub fn next_fitting<T>(
+    queue: &mut std::collections::VecDeque<T>,
+    mut fits: impl FnMut(&T) -> bool,
+) -> Option<T> {
+    for _ in 0..queue.len() {
+        let block = queue.pop_front()?;
+        if fits(&block) {
+            return Some(block);
+        }
+        queue.push_back(block);
+    }
+    None
+}
+
+/// Set `stop` on SIGINT or SIGTERM instead of letting the signal end the
+/// process. The SIGTERM handler is installed before this returns.
+pub fn stop_on_signal(
+    rt_handle: &tokio::runtime::Handle,
+    stop: Arc<AtomicBool>,
+) -> std::io::Result<()> {
+    let _guard = rt_handle.enter();
+    #[cfg(unix)]
+    let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+    rt_handle.spawn(async move {
+        #[cfg(unix)]
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => info!("received SIGINT, stopping work loop"),
+            _ = sigterm.recv() => info!("received SIGTERM, stopping work loop"),
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = tokio::signal::ctrl_c().await;
+            info!("received SIGINT, stopping work loop");
+        }
+        stop.store(true, Ordering::Relaxed);
+    });
+    Ok(())
+}
+
+/// Candidate label and the time its test started; `None` between candidates.
+pub type CurrentCandidate = Option<(String, chrono::DateTime<chrono::Utc>)>;
+
 /// PostgreSQL-based worker client — heartbeats directly to the `workers` table.
 /// Drop-in alternative to `WorkerClient` with the same shared-state pattern.
 pub struct PgWorkerClient {
@@ -63,12 +257,17 @@ pub struct PgWorkerClient {
     pub tested: Arc<AtomicU64>,
     pub found: Arc<AtomicU64>,
     pub current: Arc<Mutex<String>>,
+    /// Candidate under test and when it started, synced from the engine's
+    /// [`Progress`](crate::progress::Progress).
+    pub current_candidate: Arc<Mutex<CurrentCandidate>>,
     pub checkpoint: Arc<Mutex<Option<String>>>,
     pub stop_requested: Arc<AtomicBool>,
     /// Currently processing block ID. Set by the work loop before processing
     /// each block, cleared after completion. Used by the heartbeat thread to
     /// report live block progress via `update_block_progress`.
     pub current_block_id: Arc<Mutex<Option<i64>>>,
+    /// Job worked by the `work` loop; tags the events its searches emit.
+    search_job_id: Option<i64>,
 }
 
 impl PgWorkerClient {
@@ -81,6 +280,7 @@ impl PgWorkerClient {
     ) -> Self {
         let hostname = gethostname().unwrap_or_else(|| worker_id.to_string());
         let cores = rayon::current_num_threads() as i32;
+        let benchmark_score = crate::metrics::benchmark_score();
 
         // Register immediately
         let wid = worker_id.to_string();
@@ -91,11 +291,12 @@ impl PgWorkerClient {
         rt_handle
             .block_on(async {
                 sqlx::query(
-                    "INSERT INTO workers (worker_id, hostname, cores, search_type, search_params, last_heartbeat)
-                 VALUES ($1, $2, $3, $4, $5, NOW())
+                    "INSERT INTO workers (worker_id, hostname, cores, search_type, search_params, benchmark_score, last_heartbeat)
+                 VALUES ($1, $2, $3, $4, $5, $6, NOW())
                  ON CONFLICT (worker_id) DO UPDATE SET
                    hostname = EXCLUDED.hostname, cores = EXCLUDED.cores,
                    search_type = EXCLUDED.search_type, search_params = EXCLUDED.search_params,
+                   benchmark_score = EXCLUDED.benchmark_score,
                    last_heartbeat = NOW(), pending_command = NULL",
                 )
                 .bind(&wid)
@@ -103,11 +304,18 @@ impl PgWorkerClient {
                 .bind(cores)
                 .bind(&st)
                 .bind(&sp)
+                .bind(benchmark_score)
                 .execute(&p)
                 .await
             })
             .expect("Failed to register worker in database");
-        info!(worker_id = %worker_id, hostname = %hostname, cores, "registered with PostgreSQL");
+        info!(
+            worker_id = %worker_id,
+            hostname = %hostname,
+            cores,
+            benchmark_score,
+            "registered with PostgreSQL"
+        );
 
         PgWorkerClient {
             pool,
@@ -121,12 +329,21 @@ impl PgWorkerClient {
             tested: Arc::new(AtomicU64::new(0)),
             found: Arc::new(AtomicU64::new(0)),
             current: Arc::new(Mutex::new(String::new())),
+            current_candidate: Arc::new(Mutex::new(None)),
             checkpoint: Arc::new(Mutex::new(None)),
             stop_requested: Arc::new(AtomicBool::new(false)),
             current_block_id: Arc::new(Mutex::new(None)),
+            search_job_id: None,
         }
     }
 
+    /// Attach the search job this client works for; see
+    /// [`crate::CoordinationClient::search_job_id`].
+    pub fn with_search_job(mut self, search_job_id: i64) -> Self {
+        self.search_job_id = Some(search_job_id);
+        self
+    }
+
     pub fn start_heartbeat(&self) -> thread::JoinHandle<()> {
         let pool = self.pool.clone();
         let rt_handle = self.rt_handle.clone();
@@ -139,12 +356,14 @@ impl PgWorkerClient {
         let tested = Arc::clone(&self.tested);
         let found = Arc::clone(&self.found);
         let current = Arc::clone(&self.current);
+        let current_candidate = Arc::clone(&self.current_candidate);
         let checkpoint = Arc::clone(&self.checkpoint);
         let stop_requested = Arc::clone(&self.stop_requested);
         let current_block_id = Arc::clone(&self.current_block_id);
 
         thread::spawn(move || {
             let mut sys = sysinfo::System::new();
+            let mut pusher = crate::prom_metrics::PushGateway::from_settings(&worker_id);
             let mut consecutive_failures: u32 = 0;
             loop {
                 let interval_secs = if consecutive_failures == 0 {
@@ -184,7 +403,15 @@ impl PgWorkerClient {
                         .fetch_one(&pool)
                         .await
                 });
-                let hb_rtt_ms = hb_start.elapsed().as_millis();
+                let hb_rtt = hb_start.elapsed();
+                let hb_rtt_ms = hb_rtt.as_millis();
+
+                if let Some(pusher) = pusher.as_mut() {
+                    pusher.record_progress(&search_type, t as u64, f as u64);
+                    pusher.record_hardware(&hw);
+                    pusher.metrics().heartbeat_rtt.observe(hb_rtt.as_secs_f64());
+                    pusher.push_in_background();
+                }
 
                 match heartbeat_result {
                     Ok(command) => {
@@ -203,6 +430,33 @@ impl PgWorkerClient {
                         }
                         consecutive_failures = 0;
 
+                        let threads = crate::progress::thread_utilization();
+                        if let Err(e) = rt_handle.block_on(crate::db::set_worker_thread_utilization(
+                            &pool,
+                            &worker_id,
+                            i32::try_from(threads.threads).unwrap_or(i32::MAX),
+                            i32::try_from(threads.busy).unwrap_or(i32::MAX),
+                        )) {
+                            warn!(
+                                worker_id = %worker_id,
+                                error = %e,
+                                "failed to report thread utilization"
+                            );
+                        }
+
+                        let (candidate, started_at) =
+                            current_candidate.lock().unwrap().clone().unzip();
+                        let _ = rt_handle.block_on(async {
+                            sqlx::query(
+                                "UPDATE workers SET current_candidate = $2, current_candidate_started_at = $3 WHERE worker_id = $1",
+                            )
+                            .bind(&worker_id)
+                            .bind(candidate)
+                            .bind(started_at)
+                            .execute(&pool)
+                            .await
+                        });
+
                         if command.as_deref() == Some("stop") {
                             info!(worker_id = %worker_id, "received stop command from PostgreSQL");
                             stop_requested.store(true, Ordering::Relaxed);
@@ -257,6 +511,10 @@ impl crate::CoordinationClient for PgWorkerClient {
         self.stop_requested.load(Ordering::Relaxed)
     }
 
+    fn search_job_id(&self) -> Option<i64> {
+        self.search_job_id
+    }
+
     fn report_prime(
         &self,
         form: &str,
@@ -270,7 +528,7 @@ impl crate::CoordinationClient for PgWorkerClient {
         let sp = search_params.to_string();
         let pm = proof_method.to_string();
         let pool = self.pool.clone();
-        let _ = self.rt_handle.block_on(async {
+        let inserted = self.rt_handle.block_on(async {
             sqlx::query(
                 "INSERT INTO primes (form, expression, digits, found_at, search_params, proof_method)
                  VALUES ($1, $2, $3, NOW(), $4, $5)
@@ -284,6 +542,9 @@ impl crate::CoordinationClient for PgWorkerClient {
             .execute(&pool)
             .await
         });
+        if let Ok(result) = inserted {
+            crate::prom_metrics::record_prime_found(form, result.rows_affected());
+        }
     }
 }
 
@@ -295,3 +556,167 @@ fn gethostname() -> Option<String> {
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::Progress;
+    use crate::CoordinationClient;
+    use std::time::Instant;
+
+    /// Coordinator stub that reports the shared stop flag.
+    struct Flag(Arc<AtomicBool>);
+
+    impl CoordinationClient for Flag {
+        fn is_stop_requested(&self) -> bool {
+            self.0.load(Ordering::Relaxed)
+        }
+        fn report_prime(&self, _: &str, _: &str, _: u64, _: &str, _: &str) {}
+    }
+
+    #[test]
+    fn block_outcome_releases_stopped_blocks() {
+        let failed = Err(anyhow::anyhow!("boom"));
+        assert_eq!(BlockOutcome::of(&Ok(()), false), BlockOutcome::Complete);
+        assert_eq!(BlockOutcome::of(&Ok(()), true), BlockOutcome::Release);
+        assert_eq!(BlockOutcome::of(&failed, true), BlockOutcome::Fail);
+    }
+
+    /// With a tight floor an oversized candidate is refused while smaller
+    /// ones still run; a zero floor disables the guard.
+    #[test]
+    fn memory_guard_defers_oversized_candidates() {
+        let guard = MemoryGuard::new(1024).unwrap();
+        let available = 1280 << 20;
+        assert!(guard.refusal(1_000, available).is_none());
+        assert!(guard.refusal(100_000_000, available).is_some());
+        assert!(guard.refusal(1_000, 512 << 20).is_some());
+        assert_eq!(MemoryGuard::new(0), None);
+    }
+
+    /// The work loop's queue under memory pressure: a block too big for the
+    /// memory left is passed over, not the end of the run, and runs once
+    /// memory frees up.
+    #[test]
+    fn oversized_block_is_requeued_not_fatal() {
+        let guard = MemoryGuard::new(1024).unwrap();
+        // Digits of each queued block's largest candidate.
+        let mut queue: std::collections::VecDeque<u64> = [1_000, 100_000_000, 2_000].into();
+        let mut available: u64 = 1280 << 20;
+        let mut ran = Vec::new();
+        loop {
+            match next_fitting(&mut queue, |&d| guard.refusal(d, available).is_none()) {
+                Some(digits) => ran.push(digits),
+                None if queue.is_empty() => break,
+                None => {
+                    assert_eq!(ran, [1_000, 2_000]);
+                    assert_eq!(queue, [100_000_000]);
+                    available = 1 << 40;
+                }
+            }
+        }
+        assert_eq!(ran, [1_000, 2_000, 100_000_000]);
+    }
+
+    /// SIGTERM sent to the process while a block's search is testing
+    /// candidates sets the stop flag rather than killing it; the search
+    /// returns at its next stop check and the block is released instead of
+    /// completed.
+    #[cfg(unix)]
+    #[test]
+    fn sigterm_mid_block_releases_the_claim() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let stop = Arc::new(AtomicBool::new(false));
+        stop_on_signal(rt.handle(), Arc::clone(&stop)).unwrap();
+
+        // One kbn test block; the signal lands while it is testing.
+        let (min_n, max_n) = (1, 3_000);
+        let progress = Progress::new();
+        let signaller = {
+            let progress = Arc::clone(&progress);
+            thread::spawn(move || {
+                let deadline = Instant::now() + Duration::from_secs(60);
+                while progress.current.lock().unwrap().is_empty() && Instant::now() < deadline {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                let testing = progress.tested.load(Ordering::Relaxed) == 0
+                    && !progress.current.lock().unwrap().is_empty();
+                let pid = std::process::id().to_string();
+                let kill = std::process::Command::new("kill")
+                    .args(["-TERM", &pid])
+                    .status();
+                assert!(kill.unwrap().success());
+                testing
+            })
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let cp = dir.path().join("kbn.checkpoint");
+        let out = dir.path().join("primes.jsonl");
+        let sink = crate::prime_sink::FilePrimeSink::open(&out).unwrap();
+        let client = Flag(Arc::clone(&stop));
+        let result = crate::kbn::search(
+            3,
+            2,
+            min_n,
+            max_n,
+            &progress,
+            &sink,
+            rt.handle(),
+            &cp,
+            "{}",
+            25,
+            100_000,
+            Some(&client),
+            None,
+        );
+        assert!(signaller.join().unwrap(), "signal did not land mid-block");
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !client.is_stop_requested() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(client.is_stop_requested(), "no stop after SIGTERM");
+        let outcome = BlockOutcome::of(&result, client.is_stop_requested());
+        assert_eq!(outcome, BlockOutcome::Release);
+    }
+
+    /// A stopped block's checkpoint is stored only when its index falls
+    /// inside the block and short of its end; anything else resumes the
+    /// block from its start.
+    #[test]
+    fn release_checkpoint_keeps_only_in_block_progress() {
+        use crate::checkpoint::{self, Checkpoint};
+
+        let dir = tempfile::tempdir().unwrap();
+        let cp = dir.path().join("kbn.checkpoint");
+        assert_eq!(release_checkpoint(&cp, 100, 199), None);
+
+        let kbn = |last_n| Checkpoint::Kbn {
+            last_n,
+            min_n: Some(100),
+            max_n: Some(199),
+            sieve: None,
+        };
+        checkpoint::save(&cp, &kbn(150)).unwrap();
+        assert_eq!(
+            release_checkpoint(&cp, 100, 199),
+            Some(serde_json::json!({"last_tested": 150}))
+        );
+        assert_eq!(release_checkpoint(&cp, 151, 199), None);
+        assert_eq!(release_checkpoint(&cp, 200, 299), None);
+
+        checkpoint::save(&cp, &kbn(199)).unwrap();
+        assert_eq!(release_checkpoint(&cp, 100, 199), None);
+
+        let palindromic = Checkpoint::Palindromic {
+            digit_count: 150,
+            half_value: "1".into(),
+            order: None,
+            min_digits: Some(100),
+            max_digits: Some(199),
+        };
+        checkpoint::save(&cp, &palindromic).unwrap();
+        assert_eq!(release_checkpoint(&cp, 100, 199), None);
+    }
+}
@@ -0,0 +1,635 @@
+//! # PrimeSink — Where Engines Record Results
+//!
+//! Engines report found primes, deferred probable primes, and look up the best
+//! stored digit count through the [`PrimeSink`] trait instead of a concrete
+//! database handle. Two implementations exist:
+//!
+//! - [`Database`]: the normal path — rows in `primes` / `prp_candidates`.
+//! - [`FilePrimeSink`]: offline mode (`--output-file` without `--database-url`)
+//!   for volunteers without PostgreSQL. Each result is appended as one JSON line.
+//!
+//! ## JSONL Record Format
+//!
+//! ```text
+//! {"certificate":null,"deferred":false,"digits":8,"expression":"11! + 1",
+//!  "form":"factorial","found_at":"2026-...","proof_method":"deterministic",
+//!  "search_params":"{...}"}
+//! ```
+//!
+//! `deferred` is true for probable primes parked by `--only-proven`; the
+//! certificate, when present, is the serialized `PrimalityCertificate`.
+//!
+//! ## Buffered Writes
+//!
+//! Dense searches (short palindromic ranges, small-n kbn) can find hundreds of
+//! primes per second, and one `INSERT` per prime against the small connection
+//! pool stalls the search. Engines therefore record primes through a
+//! [`PrimeBuffer`], which holds them until `--prime-buffer-size` primes are
+//! pending or `--prime-flush-secs` (default 10s) have passed, then writes them
+//! with one [`PrimeSink::insert_primes_batch_sync`] call and reports each to the
+//! coordinator. Engines flush explicitly before every checkpoint save, on stop,
+//! and when the block completes, so a checkpoint never runs ahead of the
+//! primes it covers.
+
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::db::{Database, NewPrime};
+use crate::CoordinationClient;
+
+/// Destination for search results. Method signatures mirror the synchronous
+/// `Database` helpers so engine call sites are unchanged.
+pub trait PrimeSink: Send + Sync {
+    /// Record a found prime.
+    #[allow(clippy::too_many_arguments)]
+    fn insert_prime_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+        certificate: Option<&str>,
+    ) -> Result<()>;
+
+    /// Record a probable prime deferred for a later proof pass.
+    #[allow(clippy::too_many_arguments)]
+    fn insert_prp_candidate_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+    ) -> Result<()>;
+
+    /// Record several found primes. The default inserts them one at a time;
+    /// sinks with a cheaper bulk path override it.
+    fn insert_primes_batch_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        primes: &[NewPrime],
+    ) -> Result<()> {
+        for p in primes {
+            self.insert_prime_sync(
+                rt,
+                &p.form,
+                &p.expression,
+                p.digits,
+                &p.search_params,
+                &p.proof_method,
+                p.certificate.as_deref(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Best stored digit count for a form, used to seed milestone tracking.
+    fn get_best_digits_sync(&self, rt: &tokio::runtime::Handle, form: &str) -> Result<Option<u64>>;
+}
+
+impl PrimeSink for Database {
+    fn insert_prime_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+        certificate: Option<&str>,
+    ) -> Result<()> {
+        Database::insert_prime_sync(
+            self,
+            rt,
+            form,
+            expression,
+            digits,
+            search_params,
+            proof_method,
+            certificate,
+        )
+    }
+
+    fn insert_prp_candidate_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+    ) -> Result<()> {
+        Database::insert_prp_candidate_sync(
+            self,
+            rt,
+            form,
+            expression,
+            digits,
+            search_params,
+            proof_method,
+        )
+    }
+
+    fn insert_primes_batch_sync(
+        &self,
+        rt: &tokio::runtime::Handle,
+        primes: &[NewPrime],
+    ) -> Result<()> {
+        Database::insert_primes_batch_sync(self, rt, primes)
+    }
+
+    fn get_best_digits_sync(&self, rt: &tokio::runtime::Handle, form: &str) -> Result<Option<u64>> {
+        Database::get_best_digits_sync(self, rt, form)
+    }
+}
+
+/// Append-only JSONL sink for offline searches.
+pub struct FilePrimeSink {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FilePrimeSink {
+    /// Open (or create) `path` for appending. Existing records are kept.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open output file {}", path.display()))?;
+        Ok(FilePrimeSink {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write one record as a single line. The lock keeps lines from
+    /// interleaving; each write is flushed so a killed search loses nothing.
+    fn append(&self, record: &serde_json::Value) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap();
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Build a JSONL record. `certificate` is embedded as JSON when it parses,
+/// otherwise as a string.
+fn record(
+    form: &str,
+    expression: &str,
+    digits: u64,
+    search_params: &str,
+    proof_method: &str,
+    certificate: Option<&str>,
+    deferred: bool,
+) -> serde_json::Value {
+    let certificate = certificate.map(|c| {
+        serde_json::from_str::<serde_json::Value>(c)
+            .unwrap_or_else(|_| serde_json::Value::String(c.to_string()))
+    });
+    serde_json::json!({
+        "form": form,
+        "expression": expression,
+        "digits": digits,
+        "proof_method": proof_method,
+        "certificate": certificate,
+        "search_params": search_params,
+        "deferred": deferred,
+        "found_at": chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+impl PrimeSink for FilePrimeSink {
+    fn insert_prime_sync(
+        &self,
+        _rt: &tokio::runtime::Handle,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+        certificate: Option<&str>,
+    ) -> Result<()> {
+        self.append(&record(
+            form,
+            expression,
+            digits,
+            search_params,
+            proof_method,
+            certificate,
+            false,
+        ))
+    }
+
+    fn insert_prp_candidate_sync(
+        &self,
+        _rt: &tokio::runtime::Handle,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+    ) -> Result<()> {
+        self.append(&record(
+            form,
+            expression,
+            digits,
+            search_params,
+            proof_method,
+            None,
+            true,
+        ))
+    }
+
+    /// Offline runs keep no history; the event bus tracks bests in memory.
+    fn get_best_digits_sync(
+        &self,
+        _rt: &tokio::runtime::Handle,
+        _form: &str,
+    ) -> Result<Option<u64>> {
+        Ok(None)
+    }
+}
+
+/// Process-wide `--prime-buffer-size`, set once from `main`.
+static BUFFER_MAX_PRIMES: AtomicUsize = AtomicUsize::new(32);
+
+/// Process-wide `--prime-flush-secs` in milliseconds, set once from `main`.
+static BUFFER_FLUSH_MS: AtomicU64 = AtomicU64::new(10_000);
+
+/// Set the size and age thresholds used by [`PrimeBuffer::new`].
+///
+/// A size of 0 or 1 flushes on every prime, restoring unbuffered writes.
+pub fn set_buffer_limits(max_primes: usize, flush_interval: Duration) {
+    BUFFER_MAX_PRIMES.store(max_primes, Ordering::Relaxed);
+    BUFFER_FLUSH_MS.store(flush_interval.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Accumulates found primes and writes them to a [`PrimeSink`] in batches.
+///
+/// Only the write and the coordinator report are delayed: engines still
+/// count, log, and emit `PrimeFound` the moment a prime is found. Pending
+/// primes are flushed on drop as a last resort, but engines are expected to
+/// call [`flush`](Self::flush) themselves so a write error surfaces as `Err`.
+pub struct PrimeBuffer<'a> {
+    db: &'a dyn PrimeSink,
+    rt: &'a tokio::runtime::Handle,
+    worker_client: Option<&'a dyn CoordinationClient>,
+    pending: Vec<NewPrime>,
+    max_primes: usize,
+    flush_interval: Duration,
+    last_flush: Instant,
+}
+
+impl<'a> PrimeBuffer<'a> {
+    /// Buffer using the process-wide limits from [`set_buffer_limits`].
+    pub fn new(
+        db: &'a dyn PrimeSink,
+        rt: &'a tokio::runtime::Handle,
+        worker_client: Option<&'a dyn CoordinationClient>,
+    ) -> Self {
+        Self::with_limits(
+            db,
+            rt,
+            worker_client,
+            BUFFER_MAX_PRIMES.load(Ordering::Relaxed),
+            Duration::from_millis(BUFFER_FLUSH_MS.load(Ordering::Relaxed)),
+        )
+    }
+
+    pub fn with_limits(
+        db: &'a dyn PrimeSink,
+        rt: &'a tokio::runtime::Handle,
+        worker_client: Option<&'a dyn CoordinationClient>,
+        max_primes: usize,
+        flush_interval: Duration,
+    ) -> Self {
+        PrimeBuffer {
+            db,
+            rt,
+            worker_client,
+            pending: Vec::new(),
+            max_primes: max_primes.max(1),
+            flush_interval,
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Queue a found prime, flushing if either threshold has been reached.
+    pub fn push(
+        &mut self,
+        form: &str,
+        expression: &str,
+        digits: u64,
+        search_params: &str,
+        proof_method: &str,
+        certificate: Option<&str>,
+    ) -> Result<()> {
+        self.pending.push(NewPrime {
+            form: form.to_string(),
+            expression: expression.to_string(),
+            digits,
+            search_params: search_params.to_string(),
+            proof_method: proof_method.to_string(),
+            certificate: certificate.map(str::to_string),
+        });
+        if self.pending.len() >= self.max_primes {
+            return self.flush();
+        }
+        self.flush_if_due()
+    }
+
+    /// Flush if the oldest pending prime has waited past the flush interval.
+    /// Engines call this once per block so a lone prime is not held until the
+    /// next discovery.
+    pub fn flush_if_due(&mut self) -> Result<()> {
+        if !self.pending.is_empty() && self.last_flush.elapsed() >= self.flush_interval {
+            return self.flush();
+        }
+        Ok(())
+    }
+
+    /// Write all pending primes in one batch, then report each to the
+    /// coordinator. Pending primes are kept if the write fails.
+    pub fn flush(&mut self) -> Result<()> {
+        self.last_flush = Instant::now();
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        self.db.insert_primes_batch_sync(self.rt, &self.pending)?;
+        for p in self.pending.drain(..) {
+            if let Some(wc) = self.worker_client {
+                wc.report_prime(
+                    &p.form,
+                    &p.expression,
+                    p.digits,
+                    &p.search_params,
+                    &p.proof_method,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl Drop for PrimeBuffer<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            warn!(
+                error = %e,
+                pending = self.pending.len(),
+                "failed to flush buffered primes"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_lines(path: &Path) -> Vec<serde_json::Value> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    /// Each insert appends exactly one parseable line with the result fields.
+    #[test]
+    fn file_sink_appends_one_line_per_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("primes.jsonl");
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sink = FilePrimeSink::open(&path).unwrap();
+
+        sink.insert_prime_sync(
+            rt.handle(),
+            "factorial",
+            "11!+1",
+            8,
+            "{}",
+            "deterministic",
+            Some(r#"{"type":"Proth","base":3}"#),
+        )
+        .unwrap();
+        sink.insert_prp_candidate_sync(rt.handle(), "kbn", "3*2^5-1", 2, "{}", "probabilistic")
+            .unwrap();
+
+        let lines = read_lines(&path);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["form"], "factorial");
+        assert_eq!(lines[0]["expression"], "11!+1");
+        assert_eq!(lines[0]["digits"], 8);
+        assert_eq!(lines[0]["proof_method"], "deterministic");
+        assert_eq!(lines[0]["certificate"]["type"], "Proth");
+        assert_eq!(lines[0]["deferred"], false);
+        assert_eq!(lines[1]["deferred"], true);
+        assert!(lines[1]["certificate"].is_null());
+    }
+
+    /// Reopening an existing file appends rather than truncating.
+    #[test]
+    fn file_sink_reopen_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("primes.jsonl");
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        for expr in ["3!-1", "4!-1"] {
+            let sink = FilePrimeSink::open(&path).unwrap();
+            sink.insert_prime_sync(
+                rt.handle(),
+                "factorial",
+                expr,
+                1,
+                "{}",
+                "deterministic",
+                None,
+            )
+            .unwrap();
+        }
+        let lines = read_lines(&path);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["expression"], "4!-1");
+        let sink = FilePrimeSink::open(&path).unwrap();
+        assert_eq!(
+            sink.get_best_digits_sync(rt.handle(), "factorial").unwrap(),
+            None
+        );
+    }
+
+    // ── PrimeBuffer ──
+
+    /// In-memory sink counting write calls, and a coordinator recording reports.
+    #[derive(Default)]
+    struct CountingSink {
+        calls: AtomicUsize,
+        rows: Mutex<Vec<String>>,
+    }
+
+    impl PrimeSink for CountingSink {
+        fn insert_prime_sync(
+            &self,
+            _rt: &tokio::runtime::Handle,
+            _form: &str,
+            expression: &str,
+            _digits: u64,
+            _search_params: &str,
+            _proof_method: &str,
+            _certificate: Option<&str>,
+        ) -> Result<()> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.rows.lock().unwrap().push(expression.to_string());
+            Ok(())
+        }
+
+        fn insert_prp_candidate_sync(
+            &self,
+            _rt: &tokio::runtime::Handle,
+            _form: &str,
+            _expression: &str,
+            _digits: u64,
+            _search_params: &str,
+            _proof_method: &str,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn insert_primes_batch_sync(
+            &self,
+            _rt: &tokio::runtime::Handle,
+            primes: &[NewPrime],
+        ) -> Result<()> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            let mut rows = self.rows.lock().unwrap();
+            rows.extend(primes.iter().map(|p| p.expression.clone()));
+            Ok(())
+        }
+
+        fn get_best_digits_sync(
+            &self,
+            _rt: &tokio::runtime::Handle,
+            _form: &str,
+        ) -> Result<Option<u64>> {
+            Ok(None)
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingClient {
+        reported: Mutex<Vec<String>>,
+    }
+
+    impl CoordinationClient for RecordingClient {
+        fn is_stop_requested(&self) -> bool {
+            false
+        }
+
+        fn report_prime(&self, _: &str, expression: &str, _: u64, _: &str, _: &str) {
+            self.reported.lock().unwrap().push(expression.to_string());
+        }
+    }
+
+    /// A burst of N primes costs far fewer than N writes, and every prime is
+    /// persisted and reported once the buffer is flushed.
+    #[test]
+    fn burst_of_primes_batches_writes() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sink = CountingSink::default();
+        let client = RecordingClient::default();
+        let expected: Vec<String> = (0..100).map(|i| format!("{}*2^5+1", 2 * i + 1)).collect();
+
+        let mut buffer = PrimeBuffer::with_limits(
+            &sink,
+            rt.handle(),
+            Some(&client),
+            32,
+            Duration::from_secs(3600),
+        );
+        for expr in &expected {
+            buffer
+                .push("kbn", expr, 3, "{}", "deterministic", None)
+                .unwrap();
+        }
+        // Three full batches went out; the remaining four wait for a flush.
+        assert_eq!(sink.calls.load(Ordering::Relaxed), 3);
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(client.reported.lock().unwrap().len(), 96);
+
+        buffer.flush().unwrap();
+        assert!(buffer.is_empty());
+        assert_eq!(sink.calls.load(Ordering::Relaxed), 4);
+        assert_eq!(*sink.rows.lock().unwrap(), expected);
+        assert_eq!(*client.reported.lock().unwrap(), expected);
+    }
+
+    /// A zero flush interval writes on every push; a long one holds the prime
+    /// until an explicit flush or drop.
+    #[test]
+    fn flush_interval_controls_age_threshold() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sink = CountingSink::default();
+        let mut eager = PrimeBuffer::with_limits(&sink, rt.handle(), None, 32, Duration::ZERO);
+        eager
+            .push("kbn", "3*2^1+1", 1, "{}", "deterministic", None)
+            .unwrap();
+        assert!(eager.is_empty());
+        assert_eq!(sink.calls.load(Ordering::Relaxed), 1);
+
+        let sink = CountingSink::default();
+        {
+            let mut lazy =
+                PrimeBuffer::with_limits(&sink, rt.handle(), None, 32, Duration::from_secs(3600));
+            lazy.push("kbn", "3*2^1+1", 1, "{}", "deterministic", None)
+                .unwrap();
+            lazy.flush_if_due().unwrap();
+            assert_eq!(sink.calls.load(Ordering::Relaxed), 0);
+        }
+        assert_eq!(*sink.rows.lock().unwrap(), vec!["3*2^1+1".to_string()]);
+    }
+
+    /// The default batch path falls back to one line per prime in the file sink.
+    #[test]
+    fn file_sink_batch_appends_each_prime() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("primes.jsonl");
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let sink = FilePrimeSink::open(&path).unwrap();
+        let mut buffer =
+            PrimeBuffer::with_limits(&sink, rt.handle(), None, 8, Duration::from_secs(3600));
+        for expr in ["3!-1", "4!-1", "6!-1"] {
+            buffer
+                .push("factorial", expr, 3, "{}", "deterministic", None)
+                .unwrap();
+        }
+        buffer.flush().unwrap();
+        let lines = read_lines(&path);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2]["expression"], "6!-1");
+        assert_eq!(lines[2]["deferred"], false);
+    }
+}
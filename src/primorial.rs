@@ -48,10 +48,11 @@ use std::sync::Arc;
 use std::time::Instant;
 use tracing::info;
 
+use crate::candidate::{CandidateForm, Primorial};
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::proof;
 use crate::CoordinationClient;
@@ -132,7 +133,7 @@ pub fn search(
     start: u64,
     end: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -141,6 +142,9 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params = serde_json::json!({"start": start, "end": end});
+    crate::search_params::validate("primorial", &params)?;
+
     // Generate all primes up to end — these are the p values we iterate over
     let all_primes = sieve::generate_primes(end);
     let search_count = all_primes.iter().filter(|&&p| p >= start).count();
@@ -220,6 +224,7 @@ pub fn search(
     info!(sieve_min_prime, "sieve active");
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut sieved_out: u64 = 0;
 
     for &p in &all_primes[resume_idx..] {
@@ -248,19 +253,22 @@ pub fn search(
             sieved_out += 1;
             continue;
         }
+        progress.record_survivor(p);
 
         // Only construct the huge p#±1 Integers for candidates that survived the sieve.
         // Try PFGW first for large candidates (50-100x faster), fall back to GMP MR.
         let (r_plus, r_minus) = rayon::join(
             || {
+                let _busy = crate::progress::busy();
                 if !test_plus {
                     return (IsPrime::No, None);
                 }
                 let plus = Integer::from(&primorial + 1u32);
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&plus) {
+                if crate::p1::adaptive_p1_filter_for_form(&plus, "primorial") {
                     return (IsPrime::No, None);
                 }
+                let _testing = progress.testing(&Primorial { plus: true }.expression(p), &plus);
                 if let Some(pfgw_result) =
                     pfgw::try_test(&format!("{}#+1", p), &plus, pfgw::PfgwMode::NMinus1Proof)
                 {
@@ -277,20 +285,22 @@ pub fn search(
                             return (IsPrime::Probably, Some(cert));
                         }
                         pfgw::PfgwResult::Composite => return (IsPrime::No, None),
-                        pfgw::PfgwResult::Unavailable { .. } => {}
+                        pfgw::PfgwResult::Unavailable { .. } | pfgw::PfgwResult::Failed { .. } => {}
                     }
                 }
                 (mr_screened_test(&plus, mr_rounds), None)
             },
             || {
+                let _busy = crate::progress::busy();
                 if !test_minus {
                     return (IsPrime::No, None);
                 }
                 let minus = Integer::from(&primorial - 1u32);
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&minus) {
+                if crate::p1::adaptive_p1_filter_for_form(&minus, "primorial") {
                     return (IsPrime::No, None);
                 }
+                let _testing = progress.testing(&Primorial { plus: false }.expression(p), &minus);
                 if let Some(pfgw_result) =
                     pfgw::try_test(&format!("{}#-1", p), &minus, pfgw::PfgwMode::NPlus1Proof)
                 {
@@ -307,7 +317,7 @@ pub fn search(
                             return (IsPrime::Probably, Some(cert));
                         }
                         pfgw::PfgwResult::Composite => return (IsPrime::No, None),
-                        pfgw::PfgwResult::Unavailable { .. } => {}
+                        pfgw::PfgwResult::Unavailable { .. } | pfgw::PfgwResult::Failed { .. } => {}
                     }
                 }
                 (mr_screened_test(&minus, mr_rounds), None)
@@ -322,21 +332,20 @@ pub fn search(
                 let certainty: &str = if let Some(ref cert) = pfgw_cert {
                     cert.as_str()
                 } else {
-                    let mut cert = match result {
-                        IsPrime::Yes => "deterministic",
-                        IsPrime::Probably => "probabilistic",
-                        IsPrime::No => unreachable!(),
+                    let candidate = if sign == "+" {
+                        Integer::from(&primorial + 1u32)
+                    } else {
+                        Integer::from(&primorial - 1u32)
                     };
+                    let mut cert = crate::certainty_label(&candidate, result);
 
                     // Attempt deterministic proof for probable primes.
                     // p# has the same distinct prime factors as p! (all primes ≤ p),
                     // so we can reuse the factorial proof functions.
                     if result == IsPrime::Probably {
                         let proven = if sign == "+" {
-                            let candidate = Integer::from(&primorial + 1u32);
                             proof::pocklington_factorial_proof(p, &candidate, &sieve_primes)
                         } else {
-                            let candidate = Integer::from(&primorial - 1u32);
                             proof::morrison_factorial_proof(p, &candidate, &sieve_primes)
                         };
                         if proven {
@@ -351,7 +360,28 @@ pub fn search(
                     &certainty_owned
                 };
 
-                let expr = format!("{}# {} 1", p, sign);
+                let expr = Primorial { plus: sign == "+" }.expression(p);
+                let (proven, cert_json) = crate::prove_on_find(
+                    "primorial",
+                    &expr,
+                    digit_count,
+                    certainty.to_string(),
+                    None,
+                );
+                let certainty = proven.as_str();
+                if crate::defer_unproven(
+                    db,
+                    rt,
+                    event_bus,
+                    worker_client.and_then(|wc| wc.search_job_id()),
+                    "primorial",
+                    &expr,
+                    digit_count,
+                    search_params,
+                    certainty,
+                )? {
+                    continue;
+                }
                 progress.found.fetch_add(1, Ordering::Relaxed);
                 if let Some(eb) = event_bus {
                     eb.emit(events::Event::PrimeFound {
@@ -359,8 +389,12 @@ pub fn search(
                         expression: expr.clone(),
                         digits: digit_count,
                         proof_method: certainty.to_string(),
+                        search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                         timestamp: Instant::now(),
                     });
+                    eb.check_milestone("primorial", &expr, digit_count, || {
+                        db.get_best_digits_sync(rt, "primorial").ok().flatten()
+                    });
                 } else {
                     info!(
                         expression = %expr,
@@ -369,22 +403,21 @@ pub fn search(
                         "*** PRIME FOUND ***"
                     );
                 }
-                db.insert_prime_sync(
-                    rt,
+                buffer.push(
                     "primorial",
                     &expr,
                     digit_count,
                     search_params,
                     certainty,
-                    None,
+                    cert_json.as_deref(),
                 )?;
-                if let Some(wc) = worker_client {
-                    wc.report_prime("primorial", &expr, digit_count, search_params, certainty);
-                }
             }
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Primorial {
@@ -398,6 +431,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Primorial {
@@ -411,6 +445,7 @@ pub fn search(
         }
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!(sieved_out, "primorial sieve eliminated candidates");
     Ok(())
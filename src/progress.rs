@@ -10,8 +10,48 @@
 //! A dedicated thread prints progress to stderr every 30 seconds:
 //! tested count, found count, rate (candidates/sec), and current candidate.
 //! Shuts down cleanly via the `shutdown` atomic flag.
+//!
+//! ## Thread Utilization
+//!
+//! [`THREADS_BUSY`] counts Rayon threads currently inside an engine's
+//! per-candidate closure: each closure holds a [`busy`] guard while it tests.
+//! The worker heartbeat reports [`thread_utilization`] so `/api/fleet` can
+//! tell a saturated pool from one stalled on the database or GWNUM.
+//!
+//! ## Current Candidate
+//!
+//! Each per-candidate closure also holds a [`Progress::testing`] guard naming
+//! the candidate it tests. [`Progress::current_candidate`] reports the oldest
+//! one still under test and for how long, so a single candidate that hangs
+//! shows up in `/api/fleet` instead of hiding behind the block range in
+//! [`Progress::current`].
+//!
+//! ## Bit-Length Histogram
+//!
+//! [`Progress::testing`] also counts each candidate into a bucket by bit
+//! length, one bucket per power of two ([`Progress::bit_histogram`]). The
+//! status line prints it, showing whether a search's time goes to a few huge
+//! candidates or to many small ones.
+//!
+//! ## ETA
+//!
+//! An engine that knows its survivors up front calls [`Progress::plan_eta`]
+//! after sieving and [`Progress::advance_eta`] after each block (kbn does).
+//! [`EtaModel`] weighs each candidate by the per-form power law in
+//! [`crate::project::secs_per_candidate`], so the expensive candidates still
+//! ahead at large n are not priced at the rate of the cheap ones already done.
+//!
+//! ## Block Checksum
+//!
+//! Engines report each sieve survivor they send to primality testing through
+//! [`Progress::record_survivor`]. The survivors are folded into
+//! [`Progress::checksum`] by XOR of a 64-bit mix, so the result does not
+//! depend on Rayon's scheduling: a block run twice must yield the same value,
+//! and a worker that skipped or altered candidates almost certainly won't.
 
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use rug::Integer;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -20,17 +60,37 @@ use tracing::info;
 pub struct Progress {
     pub tested: AtomicU64,
     pub found: AtomicU64,
+    /// [`survivor_checksum`] of the survivors recorded so far.
+    pub checksum: AtomicU64,
     pub current: Mutex<String>,
+    /// Candidates under test by start order, as (description, started).
+    candidates: Mutex<BTreeMap<u64, (String, Instant)>>,
+    next_candidate: AtomicU64,
+    /// Candidates tested per bit-length bucket; bucket i holds lengths in
+    /// [2^i, 2^(i+1)).
+    bit_buckets: [AtomicU64; BIT_BUCKETS],
+    eta: Mutex<Option<EtaModel>>,
     start: Instant,
     shutdown: AtomicBool,
 }
 
+/// Longest candidate description kept; huge palindromes are all digits.
+const CANDIDATE_DISPLAY_CHARS: usize = 120;
+
+/// One bucket per power of two up to 2^31 bits, the most a `u32` length holds.
+const BIT_BUCKETS: usize = 32;
+
 impl Progress {
     pub fn new() -> Arc<Self> {
         Arc::new(Progress {
             tested: AtomicU64::new(0),
             found: AtomicU64::new(0),
+            checksum: AtomicU64::new(0),
             current: Mutex::new(String::new()),
+            candidates: Mutex::new(BTreeMap::new()),
+            next_candidate: AtomicU64::new(0),
+            bit_buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            eta: Mutex::new(None),
             start: Instant::now(),
             shutdown: AtomicBool::new(false),
         })
@@ -57,24 +117,260 @@ impl Progress {
         } else {
             0.0
         };
-        let h = elapsed.as_secs() / 3600;
-        let m = (elapsed.as_secs() % 3600) / 60;
-        let s = elapsed.as_secs() % 60;
+        let eta = self.eta().map_or_else(|| "unknown".to_string(), hms);
+        let bits = format_histogram(&self.bit_histogram());
         info!(
             current = %current,
             tested,
             rate = format_args!("{:.2}", rate),
             found,
-            elapsed = format_args!("{:02}:{:02}:{:02}", h, m, s),
+            elapsed = %hms(elapsed),
+            eta = %eta,
+            bits = %bits,
             "search progress"
         );
     }
 
+    /// Start estimating time to completion for a `form` search over indices
+    /// `[start, end]` with `survivors` candidates left to test.
+    pub fn plan_eta(&self, form: &str, start: u64, end: u64, survivors: u64) {
+        *self.eta.lock().unwrap() = Some(EtaModel {
+            form: form.to_string(),
+            start,
+            end,
+            position: start,
+            done: 0,
+            remaining: survivors,
+            started: Instant::now(),
+        });
+    }
+
+    /// Record that everything below `position` is done, `tested` survivors
+    /// of it since the last call.
+    pub fn advance_eta(&self, position: u64, tested: u64) {
+        if let Some(model) = self.eta.lock().unwrap().as_mut() {
+            model.position = position;
+            model.done += tested;
+            model.remaining = model.remaining.saturating_sub(tested);
+        }
+    }
+
+    /// Estimated time to completion, once [`plan_eta`](Self::plan_eta) was
+    /// called and at least one survivor has been tested.
+    pub fn eta(&self) -> Option<Duration> {
+        let eta = self.eta.lock().unwrap();
+        eta.as_ref()
+            .and_then(|model| model.eta_after(model.started.elapsed()))
+    }
+
+    /// Mark `expression` as under test until the guard drops, labelled with
+    /// the candidate's digit count.
+    pub fn testing(&self, expression: &str, candidate: &Integer) -> CandidateGuard<'_> {
+        let label = format!(
+            "{} ({} digits)",
+            crate::truncate_display(expression, CANDIDATE_DISPLAY_CHARS),
+            crate::estimate_digits(candidate)
+        );
+        let bucket = candidate.significant_bits().max(1).ilog2() as usize;
+        self.bit_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        let id = self.next_candidate.fetch_add(1, Ordering::Relaxed);
+        let entry = (label, Instant::now());
+        self.candidates.lock().unwrap().insert(id, entry);
+        CandidateGuard { progress: self, id }
+    }
+
+    /// Candidates started through [`testing`](Self::testing) per bit-length
+    /// bucket, as (smallest bit length in the bucket, count), for non-empty
+    /// buckets in ascending order. Bucket `2^i` covers [2^i, 2^(i+1)) bits.
+    pub fn bit_histogram(&self) -> Vec<(u64, u64)> {
+        self.bit_buckets
+            .iter()
+            .enumerate()
+            .map(|(i, count)| (1u64 << i, count.load(Ordering::Relaxed)))
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+
+    /// The longest-running candidate under test and how long it has run.
+    pub fn current_candidate(&self) -> Option<(String, Duration)> {
+        let candidates = self.candidates.lock().unwrap();
+        let (label, started) = candidates.values().next()?;
+        Some((label.clone(), started.elapsed()))
+    }
+
+    /// Fold one survivor (its form-specific index, e.g. `n` or `k`) into
+    /// [`checksum`](Self::checksum).
+    pub fn record_survivor(&self, index: u64) {
+        self.checksum.fetch_xor(mix(index), Ordering::Relaxed);
+    }
+
+    /// [`record_survivor`](Self::record_survivor) for a whole block.
+    pub fn record_survivors(&self, indices: impl IntoIterator<Item = u64>) {
+        self.checksum
+            .fetch_xor(survivor_checksum(indices), Ordering::Relaxed);
+    }
+
     pub fn stop(&self) {
         self.shutdown.store(true, Ordering::Relaxed);
     }
 }
 
+/// Keeps one candidate in [`Progress::current_candidate`] for its lifetime.
+pub struct CandidateGuard<'a> {
+    progress: &'a Progress,
+    id: u64,
+}
+
+impl Drop for CandidateGuard<'_> {
+    fn drop(&mut self) {
+        self.progress.candidates.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Remaining work of a search whose per-candidate cost grows with its index.
+#[derive(Debug, Clone)]
+pub struct EtaModel {
+    pub form: String,
+    /// First index of the run.
+    pub start: u64,
+    /// Last index of the run.
+    pub end: u64,
+    /// Next index to test; everything below it is done.
+    pub position: u64,
+    /// Survivors tested so far.
+    pub done: u64,
+    /// Survivors left to test.
+    pub remaining: u64,
+    started: Instant,
+}
+
+/// Midpoint-rule samples for [`EtaModel::mean_cost`].
+const COST_SAMPLES: u64 = 64;
+
+impl EtaModel {
+    /// Time to completion after `elapsed` of testing.
+    ///
+    /// The observed throughput calibrates the power law: the remaining time is
+    /// `elapsed` scaled by the modeled cost of the remaining survivors over
+    /// that of the survivors already tested. `None` before anything is done.
+    pub fn eta_after(&self, elapsed: Duration) -> Option<Duration> {
+        if self.done == 0 {
+            return None;
+        }
+        let done_cost = self.done as f64 * self.mean_cost(self.start, self.position);
+        let remaining_cost = self.remaining as f64 * self.mean_cost(self.position, self.end + 1);
+        if done_cost <= 0.0 {
+            return None;
+        }
+        let secs = elapsed.as_secs_f64() * remaining_cost / done_cost;
+        Duration::try_from_secs_f64(secs).ok()
+    }
+
+    /// Mean modeled seconds per candidate for indices in `[from, to)`.
+    fn mean_cost(&self, from: u64, to: u64) -> f64 {
+        let cost = |n: f64| {
+            let digits = crate::project::estimate_digits_for_form(&self.form, n as u64);
+            crate::project::secs_per_candidate(&self.form, digits.max(1), false)
+        };
+        if to <= from + 1 {
+            return cost(from as f64);
+        }
+        let width = (to - from) as f64 / COST_SAMPLES as f64;
+        let total: f64 = (0..COST_SAMPLES)
+            .map(|i| cost(from as f64 + (i as f64 + 0.5) * width))
+            .sum();
+        total / COST_SAMPLES as f64
+    }
+}
+
+/// "64-127:12 128-255:3" for [`Progress::bit_histogram`] output.
+fn format_histogram(buckets: &[(u64, u64)]) -> String {
+    if buckets.is_empty() {
+        return "none".to_string();
+    }
+    buckets
+        .iter()
+        .map(|&(low, count)| format!("{}-{}:{}", low, 2 * low - 1, count))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `hh:mm:ss`; hours do not wrap at a day.
+fn hms(d: Duration) -> String {
+    let h = d.as_secs() / 3600;
+    let m = (d.as_secs() % 3600) / 60;
+    let s = d.as_secs() % 60;
+    format!("{:02}:{:02}:{:02}", h, m, s)
+}
+
+/// Order-independent checksum of a survivor set: XOR of [`mix`]ed indices.
+pub fn survivor_checksum(indices: impl IntoIterator<Item = u64>) -> u64 {
+    indices.into_iter().fold(0, |acc, i| acc ^ mix(i))
+}
+
+/// SplitMix64 finalizer, so nearby indices differ in many bits.
+fn mix(index: u64) -> u64 {
+    let mut z = index.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Number of threads inside a tracked section, maintained by [`BusyGuard`].
+#[derive(Default)]
+pub struct BusyCounter(AtomicUsize);
+
+impl BusyCounter {
+    pub const fn new() -> Self {
+        BusyCounter(AtomicUsize::new(0))
+    }
+
+    /// Count the calling thread as busy until the returned guard drops.
+    pub fn enter(&self) -> BusyGuard<'_> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        BusyGuard(self)
+    }
+
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Marks one thread busy on a [`BusyCounter`] for its lifetime.
+pub struct BusyGuard<'a>(&'a BusyCounter);
+
+impl Drop for BusyGuard<'_> {
+    fn drop(&mut self) {
+        self.0 .0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Rayon threads currently testing a candidate, across all engines.
+pub static THREADS_BUSY: BusyCounter = BusyCounter::new();
+
+/// Count the calling thread against [`THREADS_BUSY`] until the guard drops.
+pub fn busy() -> BusyGuard<'static> {
+    THREADS_BUSY.enter()
+}
+
+/// Rayon pool size and how many of its threads are testing candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThreadUtilization {
+    pub threads: usize,
+    pub busy: usize,
+}
+
+/// Sample the global Rayon pool. Work stealing inside a nested parallel
+/// proof can run a second candidate on a waiting thread, so the busy count
+/// is capped at the pool size.
+pub fn thread_utilization() -> ThreadUtilization {
+    let threads = rayon::current_num_threads();
+    ThreadUtilization {
+        threads,
+        busy: THREADS_BUSY.get().min(threads),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //! Tests for atomic search progress counters and background reporter.
@@ -329,4 +625,122 @@ mod tests {
         p.stop();
         assert!(p.shutdown.load(Ordering::Relaxed));
     }
+
+    // ── Thread Utilization ──────────────────────────────────────────
+
+    /// The sampled busy count never exceeds the pool size.
+    #[test]
+    fn thread_utilization_is_bounded_by_pool_size() {
+        let u = thread_utilization();
+        assert!(u.threads >= 1);
+        assert!(u.busy <= u.threads);
+    }
+
+    // ── Current Candidate ───────────────────────────────────────────
+
+    /// The oldest candidate still under test is reported, and a finished
+    /// candidate drops out.
+    #[test]
+    fn current_candidate_tracks_oldest_in_flight() {
+        let p = Progress::new();
+        assert_eq!(p.current_candidate(), None);
+        let first = p.testing("3*2^100+1", &Integer::from(1u32 << 20));
+        let second = p.testing("3*2^101+1", &Integer::from(7u32));
+        let (label, _) = p.current_candidate().unwrap();
+        assert_eq!(label, "3*2^100+1 (7 digits)");
+        drop(first);
+        assert_eq!(p.current_candidate().unwrap().0, "3*2^101+1 (1 digits)");
+        drop(second);
+        assert_eq!(p.current_candidate(), None);
+    }
+
+    // ── ETA ─────────────────────────────────────────────────────────
+
+    /// kbn cost grows as digits^2, and digits as n. Half the survivors of
+    /// n ∈ [1000, 3000] took 100 s, so a constant rate would predict 100 s
+    /// more; the mean n² over [2000, 3000] is 19/7 times that over
+    /// [1000, 2000], so the estimate is about 271 s.
+    #[test]
+    fn eta_accounts_for_costlier_remaining_candidates() {
+        let p = Progress::new();
+        assert_eq!(p.eta(), None);
+        p.plan_eta("kbn", 1000, 2999, 1000);
+        assert_eq!(p.eta(), None, "no estimate before any survivor is tested");
+        p.advance_eta(2000, 500);
+
+        let model = p.eta.lock().unwrap().clone().unwrap();
+        assert_eq!((model.done, model.remaining), (500, 500));
+        let eta = model.eta_after(Duration::from_secs(100)).unwrap();
+        let expected = 100.0 * 19.0 / 7.0;
+        let secs = eta.as_secs_f64();
+        assert!((secs - expected).abs() < 2.7, "eta {:.1}s", secs);
+
+        // Nothing remains once every survivor is tested.
+        p.advance_eta(3000, 500);
+        let model = p.eta.lock().unwrap().clone().unwrap();
+        let eta = model.eta_after(Duration::from_secs(300));
+        assert_eq!(eta, Some(Duration::ZERO));
+    }
+
+    // ── Block Checksum ──────────────────────────────────────────────
+
+    /// The k·2^n − 1 sieve survivors of one block, as a worker collects them.
+    fn kbn_block_survivors() -> Vec<u64> {
+        let sieve_primes = crate::sieve::generate_primes(10_000);
+        let (_, minus, _) = crate::kbn::bsgs_sieve(100, 600, 3, 2, &sieve_primes, 14, None);
+        (100..=600)
+            .filter(|&n| minus.get((n - 100) as usize))
+            .collect()
+    }
+
+    /// Two independent runs of the same block agree on the checksum even
+    /// though one records survivors from Rayon threads in arbitrary order;
+    /// dropping or substituting one survivor changes it.
+    #[test]
+    fn block_checksum_is_reproducible_and_detects_tampering() {
+        use rayon::prelude::*;
+
+        let first = Progress::new();
+        first.record_survivors(kbn_block_survivors());
+
+        let second = Progress::new();
+        let survivors = kbn_block_survivors();
+        survivors
+            .par_iter()
+            .for_each(|&n| second.record_survivor(n));
+
+        let checksum = first.checksum.load(Ordering::Relaxed);
+        assert_ne!(checksum, 0);
+        assert_eq!(second.checksum.load(Ordering::Relaxed), checksum);
+        assert_eq!(survivor_checksum(survivors.iter().copied()), checksum);
+
+        let skipped = survivor_checksum(survivors[1..].iter().copied());
+        assert_ne!(skipped, checksum);
+        let mut altered = survivors.clone();
+        altered[0] += 1;
+        assert_ne!(survivor_checksum(altered), checksum);
+    }
+
+    /// Candidates land in power-of-two bit-length buckets: two 3-bit values
+    /// in [2, 4), two 100-bit values in [64, 128), one 1000-bit value in
+    /// [512, 1024).
+    #[test]
+    fn bit_histogram_counts_candidates_by_bucket() {
+        let p = Progress::new();
+        assert!(p.bit_histogram().is_empty());
+        assert_eq!(format_histogram(&p.bit_histogram()), "none");
+        let sizes = [3u32, 3, 100, 100, 1000];
+        for bits in sizes {
+            let candidate = (Integer::from(1) << (bits - 1)) + 1u32;
+            let _guard = p.testing("x", &candidate);
+        }
+        assert_eq!(p.bit_histogram(), vec![(2, 2), (64, 2), (512, 1)]);
+        assert_eq!(
+            format_histogram(&p.bit_histogram()),
+            "2-3:2 64-127:2 512-1023:1"
+        );
+        // Zero and one both count as one-bit candidates.
+        let _zero = p.testing("0", &Integer::new());
+        assert_eq!(p.bit_histogram()[0], (1, 1));
+    }
 }
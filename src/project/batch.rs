@@ -0,0 +1,220 @@
+//! Batch search configs: a TOML list of searches run back to back.
+//!
+//! `darkreach run --config searches.toml` executes each `[[search]]` entry in
+//! order through the same per-block dispatch the `work` loop uses, each with
+//! its own checkpoint. Unlike a project, nothing is imported into PostgreSQL:
+//! there are no phases, jobs or work blocks.
+//!
+//! ```toml
+//! [[search]]
+//! form = "kbn"
+//! start = 1000   # range passed to the engine as the block start/end
+//! end = 5000
+//! k = 3          # any other keys become the search params
+//! base = 2
+//! ```
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::config::{slugify, VALID_FORMS};
+
+/// A batch file: the `[[search]]` entries, run in file order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchConfig {
+    #[serde(rename = "search")]
+    pub searches: Vec<BatchSearch>,
+}
+
+/// One `[[search]]` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSearch {
+    /// Label for logs and the checkpoint file; defaults to `<form>-<position>`.
+    pub name: Option<String>,
+    pub form: String,
+    /// Range handed to the engine as the block start/end: n, exponent, base
+    /// or digit count, depending on the form.
+    pub start: u64,
+    pub end: u64,
+    /// Remaining keys, e.g. `k` and `base` for kbn.
+    #[serde(flatten)]
+    pub params: Map<String, Value>,
+}
+
+impl BatchSearch {
+    /// File-safe label for the search at `index` (0-based) in its batch.
+    pub fn label(&self, index: usize) -> String {
+        match &self.name {
+            Some(name) => slugify(name),
+            None => format!("{}-{}", self.form, index + 1),
+        }
+    }
+
+    /// Search params JSON in the shape `run_search_block` reads.
+    pub fn params_json(&self) -> Value {
+        let mut params = self.params.clone();
+        params.insert("form".to_string(), Value::from(self.form.as_str()));
+        Value::Object(params)
+    }
+}
+
+impl BatchConfig {
+    /// Run every search in order, calling `run_one(label, search, params)`
+    /// for each, and stop at the first failure.
+    pub fn run<F>(&self, mut run_one: F) -> Result<()>
+    where
+        F: FnMut(&str, &BatchSearch, &Value) -> Result<()>,
+    {
+        for (i, search) in self.searches.iter().enumerate() {
+            let label = search.label(i);
+            run_one(&label, search, &search.params_json())
+                .map_err(|e| e.context(format!("batch search '{}' failed", label)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a batch configuration from a TOML string.
+pub fn parse_batch_toml(content: &str) -> Result<BatchConfig> {
+    let config: BatchConfig = toml::from_str(content)?;
+    validate_batch(&config)?;
+    Ok(config)
+}
+
+/// Parse a batch configuration from a TOML file path.
+pub fn parse_batch_toml_file(path: &std::path::Path) -> Result<BatchConfig> {
+    let content = std::fs::read_to_string(path)?;
+    parse_batch_toml(&content)
+}
+
+/// Every search needs a known form and a non-empty range, and labels must be
+/// distinct so no two searches share a checkpoint.
+fn validate_batch(config: &BatchConfig) -> Result<()> {
+    if config.searches.is_empty() {
+        anyhow::bail!("batch config has no [[search]] entries");
+    }
+    let mut labels = std::collections::HashSet::new();
+    for (i, search) in config.searches.iter().enumerate() {
+        let label = search.label(i);
+        if !VALID_FORMS.contains(&search.form.as_str()) {
+            anyhow::bail!(
+                "Unknown form '{}' in search '{}'. Valid forms: {}",
+                search.form,
+                label,
+                VALID_FORMS.join(", ")
+            );
+        }
+        if search.start > search.end {
+            anyhow::bail!(
+                "search '{}' has start {} > end {}",
+                label,
+                search.start,
+                search.end
+            );
+        }
+        if !labels.insert(label.clone()) {
+            anyhow::bail!("duplicate search name '{}'", label);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const THREE_KBN: &str = r#"
+[[search]]
+form = "kbn"
+start = 1000
+end = 2000
+k = 3
+base = 2
+
+[[search]]
+form = "kbn"
+start = 1000
+end = 2000
+k = 5
+base = 2
+
+[[search]]
+name = "Riesel k=7"
+form = "kbn"
+start = 500
+end = 900
+k = 7
+base = 2
+"#;
+
+    /// Each spec becomes a `{"form", ...}` params object, handed to the
+    /// runner in file order with its own label and range. The real engine
+    /// dispatch is covered by `batch_config_runs_each_search` in cli_tests.
+    #[test]
+    fn three_kbn_specs_build_params_in_order() {
+        let batch = parse_batch_toml(THREE_KBN).unwrap();
+        let mut dispatched = Vec::new();
+        batch
+            .run(|label, search, params| {
+                dispatched.push((label.to_string(), search.start, search.end, params.clone()));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(
+            dispatched,
+            vec![
+                (
+                    "kbn-1".to_string(),
+                    1000,
+                    2000,
+                    json!({"form": "kbn", "k": 3, "base": 2})
+                ),
+                (
+                    "kbn-2".to_string(),
+                    1000,
+                    2000,
+                    json!({"form": "kbn", "k": 5, "base": 2})
+                ),
+                (
+                    "riesel-k-7".to_string(),
+                    500,
+                    900,
+                    json!({"form": "kbn", "k": 7, "base": 2})
+                ),
+            ]
+        );
+    }
+
+    /// A failing search stops the batch and names the search in the error.
+    #[test]
+    fn failure_stops_the_batch() {
+        let batch = parse_batch_toml(THREE_KBN).unwrap();
+        let mut calls = 0;
+        let err = batch
+            .run(|label, _, _| {
+                calls += 1;
+                if label == "kbn-2" {
+                    anyhow::bail!("engine error");
+                }
+                Ok(())
+            })
+            .unwrap_err();
+        assert_eq!(calls, 2);
+        assert!(err.to_string().contains("kbn-2"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_invalid_batches() {
+        assert!(parse_batch_toml("").is_err());
+        let unknown = "[[search]]\nform = \"mersenne\"\nstart = 1\nend = 2\n";
+        assert!(parse_batch_toml(unknown).is_err());
+        let reversed = "[[search]]\nform = \"kbn\"\nstart = 9\nend = 2\n";
+        assert!(parse_batch_toml(reversed).is_err());
+        let duplicate = "[[search]]\nname = \"a\"\nform = \"kbn\"\nstart = 1\nend = 2\n\
+                         [[search]]\nname = \"a\"\nform = \"twin\"\nstart = 1\nend = 2\n";
+        assert!(parse_batch_toml(duplicate).is_err());
+    }
+}
@@ -90,6 +90,9 @@ pub struct CompetitiveConfig {
 pub struct StrategyConfig {
     #[serde(default)]
     pub auto_strategy: bool,
+    /// Append a final `"prove"` phase to auto-generated strategies.
+    #[serde(default)]
+    pub prove_phase: bool,
     #[serde(default)]
     pub phases: Vec<PhaseConfig>,
 }
@@ -161,6 +164,22 @@ pub fn parse_toml_file(path: &std::path::Path) -> Result<ProjectConfig> {
     parse_toml(&content)
 }
 
+/// Search forms a project or batch config may name.
+pub const VALID_FORMS: &[&str] = &[
+    "factorial",
+    "primorial",
+    "kbn",
+    "palindromic",
+    "near_repdigit",
+    "cullen_woodall",
+    "wagstaff",
+    "carol_kynea",
+    "twin",
+    "sophie_germain",
+    "repunit",
+    "gen_fermat",
+];
+
 /// Validate a project configuration for logical consistency.
 fn validate_config(config: &ProjectConfig) -> Result<()> {
     if config.project.name.is_empty() {
@@ -170,26 +189,11 @@ fn validate_config(config: &ProjectConfig) -> Result<()> {
         anyhow::bail!("project.form is required");
     }
 
-    // Validate form name
-    let valid_forms = [
-        "factorial",
-        "primorial",
-        "kbn",
-        "palindromic",
-        "near_repdigit",
-        "cullen_woodall",
-        "wagstaff",
-        "carol_kynea",
-        "twin",
-        "sophie_germain",
-        "repunit",
-        "gen_fermat",
-    ];
-    if !valid_forms.contains(&config.project.form.as_str()) {
+    if !VALID_FORMS.contains(&config.project.form.as_str()) {
         anyhow::bail!(
             "Unknown form '{}'. Valid forms: {}",
             config.project.form,
-            valid_forms.join(", ")
+            VALID_FORMS.join(", ")
         );
     }
 
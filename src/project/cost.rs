@@ -500,6 +500,7 @@ mod tests {
             competitive: None,
             strategy: super::super::config::StrategyConfig {
                 auto_strategy: false,
+                prove_phase: false,
                 phases: vec![],
             },
             infrastructure: None,
@@ -528,6 +529,7 @@ mod tests {
             competitive: None,
             strategy: super::super::config::StrategyConfig {
                 auto_strategy: false,
+                prove_phase: false,
                 phases: vec![super::super::config::PhaseConfig {
                     name: "sweep".into(),
                     description: String::new(),
@@ -23,17 +23,20 @@
 //! ## Module Structure
 //!
 //! - [`config`] — TOML configuration structs, parsing, validation, slugification
+//! - [`batch`] — Lightweight TOML search lists run back to back (`darkreach run --config`)
 //! - [`types`] — Database row types for projects, phases, records, events
 //! - [`cost`] — Empirical cost estimation model (power-law timing per form)
 //! - [`orchestration`] — Phase state machine, auto-strategy generation, tick loop
 //! - [`records`] — World record tracking via t5k.org scraping
 
+mod batch;
 mod config;
 mod cost;
 mod orchestration;
 mod records;
 mod types;
 
+pub use batch::*;
 pub use config::*;
 pub use cost::*;
 pub use orchestration::*;
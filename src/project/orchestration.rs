@@ -1,13 +1,16 @@
 //! Phase state machine, auto-strategy generation, and orchestration tick loop.
 //!
 //! The orchestration engine runs every 30 seconds from the dashboard and:
-//! 1. Checks active phases for completion (all blocks done, first prime found, etc.)
-//! 2. Activates next eligible phases (dependencies met, conditions satisfied)
+//! 1. Checks active phases for completion (all blocks done, first prime found, etc.);
+//!    active `"prove"` phases run their next proof pass
+//! 2. Activates next eligible phases (dependencies met, conditions satisfied);
+//!    `"prove"` phases run a deterministic proof pass instead of a search job
 //! 3. Aggregates progress and cost to the project level
 //! 4. Marks projects completed when all phases are done
 //! 5. Checks budget alerts
 
 use anyhow::Result;
+use serde::Serialize;
 use tracing::{info, warn};
 
 use super::config::{Objective, PhaseConfig, ProjectConfig};
@@ -19,11 +22,14 @@ use crate::db::Database;
 
 /// Generate form-specific default phases when `auto_strategy = true` and
 /// no manual phases are defined.
+///
+/// With `strategy.prove_phase = true`, a final `"prove"` phase is appended that
+/// depends on every search phase.
 pub fn generate_auto_strategy(config: &ProjectConfig) -> Vec<PhaseConfig> {
     let form = &config.project.form;
     let objective = &config.project.objective;
 
-    match (form.as_str(), objective) {
+    let mut phases = match (form.as_str(), objective) {
         ("factorial", Objective::Record) => {
             let start = config.target.range_start.unwrap_or(1000);
             let end = config.target.range_end.unwrap_or(start + 10_000);
@@ -145,7 +151,36 @@ pub fn generate_auto_strategy(config: &ProjectConfig) -> Vec<PhaseConfig> {
                 completion: "all_blocks_done".to_string(),
             }]
         }
+    };
+
+    if config.strategy.prove_phase {
+        let depends_on = phases.iter().map(|p| p.name.clone()).collect();
+        phases.push(PhaseConfig {
+            name: "prove".to_string(),
+            description: format!("Deterministic proof pass over {} PRPs", form),
+            search_params: serde_json::json!({
+                "action": "prove",
+                "search_type": form,
+            }),
+            block_size: None,
+            depends_on: Some(depends_on),
+            activation_condition: None,
+            completion: "all_blocks_done".to_string(),
+        });
     }
+
+    phases
+}
+
+/// A phase's action, stored as `search_params.action`.
+///
+/// `"search"` (the default when absent) creates a search job; `"prove"` runs a
+/// deterministic proof pass over the project form's accumulated PRPs.
+pub(crate) fn phase_action(search_params: &serde_json::Value) -> &str {
+    search_params
+        .get("action")
+        .and_then(|v| v.as_str())
+        .unwrap_or("search")
 }
 
 // ── Orchestration Engine ────────────────────────────────────────
@@ -163,28 +198,119 @@ pub async fn orchestrate_tick(db: &Database) -> Result<()> {
     let projects = db.get_projects(Some("active")).await?;
 
     for project in &projects {
-        if let Err(e) = orchestrate_project(db, project).await {
-            warn!(slug = %project.slug, error = %e, "orchestration error");
-            db.insert_project_event(
-                project.id,
-                "error",
-                &format!("Orchestration error: {}", e),
-                None,
-            )
-            .await
-            .ok();
+        if let Err(e) = advance_project(db, project).await {
+            record_orchestration_error(db, project, &e).await;
         }
     }
 
     Ok(())
 }
 
-/// Orchestrate a single project: advance phases, aggregate progress.
-async fn orchestrate_project(db: &Database, project: &ProjectRow) -> Result<()> {
+/// A phase whose status changed during [`orchestrate_project`]. `from` is
+/// `None` for a phase generated by this run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PhaseTransition {
+    pub phase: String,
+    pub from: Option<String>,
+    pub to: String,
+}
+
+/// What one [`orchestrate_project`] run changed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectTickOutcome {
+    /// Project status after the run (e.g. `"completed"` or `"paused"`).
+    pub status: String,
+    pub transitions: Vec<PhaseTransition>,
+    /// Search jobs created by phase activations, in phase order.
+    pub created_jobs: Vec<i64>,
+}
+
+/// Run orchestration for one active project now, outside the 30-second tick.
+///
+/// Performs the same steps as [`orchestrate_tick`] does per project and
+/// reports the phase transitions and search jobs it caused. Errors are
+/// recorded as a project event, as the tick does, and returned.
+pub async fn orchestrate_project(db: &Database, project_id: i64) -> Result<ProjectTickOutcome> {
+    let project = db
+        .get_project_by_id(project_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("project {} not found", project_id))?;
+    if project.status != "active" {
+        anyhow::bail!(
+            "project '{}' is {}, not active",
+            project.slug,
+            project.status
+        );
+    }
+
+    let before = db.get_project_phases(project.id).await?;
+    if let Err(e) = advance_project(db, &project).await {
+        record_orchestration_error(db, &project, &e).await;
+        return Err(e);
+    }
+    let after = db.get_project_phases(project.id).await?;
+    let status = db
+        .get_project_by_id(project.id)
+        .await?
+        .map_or(project.status, |p| p.status);
+
+    let (transitions, created_jobs) = diff_phases(&before, &after);
+    Ok(ProjectTickOutcome {
+        status,
+        transitions,
+        created_jobs,
+    })
+}
+
+/// Phase status changes and newly assigned search jobs between two
+/// snapshots of a project's phases.
+fn diff_phases(
+    before: &[ProjectPhaseRow],
+    after: &[ProjectPhaseRow],
+) -> (Vec<PhaseTransition>, Vec<i64>) {
+    let mut transitions = Vec::new();
+    let mut created_jobs = Vec::new();
+    for phase in after {
+        let prior = before.iter().find(|p| p.id == phase.id);
+        if prior.map(|p| p.status.as_str()) != Some(phase.status.as_str()) {
+            transitions.push(PhaseTransition {
+                phase: phase.name.clone(),
+                from: prior.map(|p| p.status.clone()),
+                to: phase.status.clone(),
+            });
+        }
+        if let Some(job_id) = phase.search_job_id {
+            if prior.and_then(|p| p.search_job_id) != Some(job_id) {
+                created_jobs.push(job_id);
+            }
+        }
+    }
+    (transitions, created_jobs)
+}
+
+async fn record_orchestration_error(db: &Database, project: &ProjectRow, e: &anyhow::Error) {
+    warn!(slug = %project.slug, error = %e, "orchestration error");
+    db.insert_project_event(
+        project.id,
+        "error",
+        &format!("Orchestration error: {}", e),
+        None,
+    )
+    .await
+    .ok();
+}
+
+/// Advance a single project: advance phases, aggregate progress.
+async fn advance_project(db: &Database, project: &ProjectRow) -> Result<()> {
     let phases = db.get_project_phases(project.id).await?;
 
     // 1. Check active phases for completion
     for phase in phases.iter().filter(|p| p.status == "active") {
+        // Proof passes have no search job; run the next pass instead
+        if phase_action(&phase.search_params) == "prove" {
+            prove_pass(db, project, phase).await?;
+            continue;
+        }
         if let Some(job_id) = phase.search_job_id {
             let summary = db.get_job_block_summary(job_id).await?;
 
@@ -272,6 +398,12 @@ async fn orchestrate_project(db: &Database, project: &ProjectRow) -> Result<()>
 
     for phase in phases.iter().filter(|p| p.status == "pending") {
         if should_activate(phase, &phases) {
+            // Proof passes run on the coordinator, not the fleet
+            if phase_action(&phase.search_params) == "prove" {
+                run_prove_phase(db, project, phase).await?;
+                continue;
+            }
+
             // Enforce infrastructure and worker requirements before activation
             if let Some(reason) = check_fleet_requirements(project, &fleet) {
                 warn!(
@@ -502,6 +634,11 @@ pub(crate) fn generate_followup_phase(
         return None;
     }
 
+    // Proof passes have no range to extend
+    if phase_action(&completed_phase.search_params) != "search" {
+        return None;
+    }
+
     // Extract the range from the completed phase's search params
     let (range_start, range_end) =
         super::cost::extract_range_from_params(&completed_phase.search_params);
@@ -630,7 +767,9 @@ pub(crate) fn check_fleet_requirements(
     None
 }
 
-/// Activate a phase: create a search job via the existing infrastructure.
+/// Activate a phase: create a search job via the existing infrastructure,
+/// or take over an identical live one (e.g. left by an activation that
+/// failed after creating its job) instead of searching the range twice.
 async fn activate_phase(
     db: &Database,
     project: &ProjectRow,
@@ -652,9 +791,8 @@ async fn activate_phase(
         );
     }
 
-    // Create search job using existing infrastructure
-    let job_id = db
-        .create_search_job(
+    let (job_id, reused) = db
+        .find_or_create_search_job(
             search_type,
             &phase.search_params,
             range_start as i64,
@@ -673,8 +811,9 @@ async fn activate_phase(
         project.id,
         "phase_activated",
         &format!(
-            "Phase '{}' activated: search job {} (range {}..{}, {} blocks)",
+            "Phase '{}' activated: {} search job {} (range {}..{}, {} blocks)",
             phase.name,
+            if reused { "existing" } else { "new" },
             job_id,
             range_start,
             range_end,
@@ -683,6 +822,7 @@ async fn activate_phase(
         Some(&serde_json::json!({
             "phase_id": phase.id,
             "search_job_id": job_id,
+            "reused": reused,
         })),
     )
     .await?;
@@ -691,12 +831,211 @@ async fn activate_phase(
         slug = %project.slug,
         phase = %phase.name,
         job_id,
+        reused,
         "activated phase"
     );
 
     Ok(())
 }
 
+/// Default cap on PRPs claimed per source table by one proof pass.
+const DEFAULT_PROVE_BATCH: i64 = 1000;
+
+/// Default wall-clock budget for one proof pass, kept under the 30 s tick.
+const DEFAULT_PROVE_BUDGET_SECS: u64 = 20;
+
+/// Start a `"prove"` phase and run its first proof pass.
+///
+/// Claims unproven PRPs of the phase's form from `primes` and from the
+/// `--only-proven` holding table `prp_candidates`, attempts deterministic
+/// proofs on a blocking thread, then records upgraded proof methods and
+/// certificates. `total_tested` counts proof attempts; `total_found` counts
+/// candidates promoted into `primes` (upgrading an existing row is not a new
+/// discovery).
+///
+/// No new proof starts once `time_budget_secs` (default 20) has elapsed. The
+/// phase stays active while PRPs remain, and each later tick runs another
+/// pass from where the last one stopped (see [`prove_pass`]). If the first
+/// pass fails, the phase is put back to its previous status.
+async fn run_prove_phase(
+    db: &Database,
+    project: &ProjectRow,
+    phase: &ProjectPhaseRow,
+) -> Result<()> {
+    db.start_phase(phase.id).await?;
+    let started = async {
+        db.insert_project_event(
+            project.id,
+            "phase_activated",
+            &format!(
+                "Phase '{}' activated: proof pass over {} PRPs",
+                phase.name,
+                prove_form(project, phase)
+            ),
+            Some(&serde_json::json!({ "phase_id": phase.id })),
+        )
+        .await?;
+        prove_pass(db, project, phase).await
+    };
+    if let Err(e) = started.await {
+        db.update_phase_status(phase.id, &phase.status).await.ok();
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Form a prove phase works on: its `search_type`, else the project's form.
+fn prove_form(project: &ProjectRow, phase: &ProjectPhaseRow) -> String {
+    phase
+        .search_params
+        .get("search_type")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&project.form)
+        .to_string()
+}
+
+/// One proof pass of an active `"prove"` phase.
+///
+/// Attempts up to `batch_size` PRPs per source table past the phase's
+/// `prove_cursor` (the last id attempted in each table, kept in
+/// `search_params`), within the time budget. PRPs not reached are deferred to
+/// the next tick. The phase completes only once neither table has an unproven
+/// PRP past the cursor; PRPs that were attempted but resisted proof stay
+/// probabilistic and are not retried by this phase.
+async fn prove_pass(db: &Database, project: &ProjectRow, phase: &ProjectPhaseRow) -> Result<()> {
+    let form = prove_form(project, phase);
+    let batch = phase
+        .search_params
+        .get("batch_size")
+        .and_then(serde_json::Value::as_i64)
+        .unwrap_or(DEFAULT_PROVE_BATCH);
+    let budget = std::time::Duration::from_secs(
+        phase
+            .search_params
+            .get("time_budget_secs")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_PROVE_BUDGET_SECS),
+    );
+    let cursor = |table: &str| {
+        phase.search_params["prove_cursor"][table]
+            .as_i64()
+            .unwrap_or(0)
+    };
+    let (after_prime, after_candidate) = (cursor("primes"), cursor("prp_candidates"));
+
+    let primes = db.get_unproven_primes(&form, after_prime, batch).await?;
+    let candidates = db
+        .get_unproven_prp_candidates(&form, after_candidate, batch)
+        .await?;
+    let targets: Vec<(String, String)> = primes
+        .iter()
+        .chain(&candidates)
+        .map(|p| (p.form.clone(), p.expression.clone()))
+        .collect();
+    let claimed = targets.len() as i64;
+
+    let proofs = tokio::task::spawn_blocking(move || {
+        let deadline = std::time::Instant::now() + budget;
+        targets
+            .iter()
+            .map_while(|(form, expression)| {
+                (std::time::Instant::now() < deadline).then(|| {
+                    crate::verify::prove_probable_prime(form, expression).unwrap_or_else(|e| {
+                        warn!(expression = %expression, error = %e, "cannot reconstruct PRP");
+                        None
+                    })
+                })
+            })
+            .collect::<Vec<_>>()
+    })
+    .await?;
+    let (prime_proofs, candidate_proofs) = proofs.split_at(primes.len().min(proofs.len()));
+
+    let mut proven = 0i64;
+    let mut promoted = 0i64;
+    for (prime, proof) in primes.iter().zip(prime_proofs) {
+        if let Some((method, certificate)) = proof {
+            db.update_prime_proof(prime.id, method, certificate.as_deref())
+                .await?;
+            proven += 1;
+        }
+    }
+    for (candidate, proof) in candidates.iter().zip(candidate_proofs) {
+        if let Some((method, certificate)) = proof {
+            proven += 1;
+            if db
+                .promote_prp_candidate(candidate.id, method, certificate.as_deref())
+                .await?
+            {
+                promoted += 1;
+            }
+        }
+    }
+
+    // Advance the cursor past every PRP attempted in this pass
+    let last_id = |rows: &[crate::db::PrimeDetail], attempted: usize, after: i64| {
+        attempted.checked_sub(1).map_or(after, |i| rows[i].id)
+    };
+    let after_prime = last_id(&primes, prime_proofs.len(), after_prime);
+    let after_candidate = last_id(&candidates, candidate_proofs.len(), after_candidate);
+    let mut params = phase.search_params.clone();
+    params["prove_cursor"] = serde_json::json!({
+        "primes": after_prime,
+        "prp_candidates": after_candidate,
+    });
+    db.update_phase_params(phase.id, &params).await?;
+
+    let attempted = proofs.len() as i64;
+    let deferred = claimed - attempted;
+    let total_tested = phase.total_tested + attempted;
+    let total_found = phase.total_found + promoted;
+    db.update_phase_progress(phase.id, total_tested, total_found)
+        .await?;
+
+    let remaining = deferred > 0
+        || !db
+            .get_unproven_primes(&form, after_prime, 1)
+            .await?
+            .is_empty()
+        || !db
+            .get_unproven_prp_candidates(&form, after_candidate, 1)
+            .await?
+            .is_empty();
+    if remaining {
+        info!(
+            slug = %project.slug,
+            phase = %phase.name,
+            attempted,
+            proven,
+            promoted,
+            deferred,
+            "proof pass done, PRPs remain for the next tick"
+        );
+        return Ok(());
+    }
+
+    db.update_phase_status(phase.id, "completed").await?;
+    db.insert_project_event(
+        project.id,
+        "phase_completed",
+        &format!(
+            "Phase '{}' completed: {} PRPs attempted, {} promoted",
+            phase.name, total_tested, total_found
+        ),
+        None,
+    )
+    .await?;
+    info!(
+        slug = %project.slug,
+        phase = %phase.name,
+        total_tested,
+        total_found,
+        "proof pass completed"
+    );
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -778,6 +1117,7 @@ mod tests {
             claimed: 0,
             completed: 50,
             failed: 0,
+            quarantined: 0,
             total_tested: 50000,
             total_found: 3,
         };
@@ -791,6 +1131,7 @@ mod tests {
             claimed: 2,
             completed: 8,
             failed: 0,
+            quarantined: 0,
             total_tested: 8000,
             total_found: 0,
         };
@@ -804,6 +1145,7 @@ mod tests {
             claimed: 0,
             completed: 5,
             failed: 0,
+            quarantined: 0,
             total_tested: 5000,
             total_found: 0,
         };
@@ -817,6 +1159,7 @@ mod tests {
             claimed: 2,
             completed: 3,
             failed: 0,
+            quarantined: 0,
             total_tested: 3000,
             total_found: 1,
         };
@@ -830,6 +1173,7 @@ mod tests {
             claimed: 3,
             completed: 2,
             failed: 0,
+            quarantined: 0,
             total_tested: 2000,
             total_found: 0,
         };
@@ -843,6 +1187,7 @@ mod tests {
             claimed: 0,
             completed: 10,
             failed: 0,
+            quarantined: 0,
             total_tested: 10000,
             total_found: 0,
         };
@@ -856,6 +1201,7 @@ mod tests {
             claimed: 0,
             completed: 9,
             failed: 0,
+            quarantined: 0,
             total_tested: 9000,
             total_found: 5,
         };
@@ -1243,6 +1589,41 @@ mod tests {
         assert_eq!(s, 1000);
     }
 
+    #[test]
+    fn auto_strategy_appends_prove_phase_when_requested() {
+        let mut config = make_config("wagstaff", Objective::Record, Some(1000), Some(3000));
+        config.strategy.prove_phase = true;
+        let phases = generate_auto_strategy(&config);
+        assert_eq!(phases.len(), 3);
+        let prove = &phases[2];
+        assert_eq!(prove.name, "prove");
+        assert_eq!(phase_action(&prove.search_params), "prove");
+        assert_eq!(prove.search_params["search_type"], "wagstaff");
+        assert_eq!(
+            prove.depends_on,
+            Some(vec!["sweep".to_string(), "extend".to_string()])
+        );
+    }
+
+    #[test]
+    fn auto_strategy_omits_prove_phase_by_default() {
+        let config = make_config("factorial", Objective::Survey, Some(1), Some(100));
+        let phases = generate_auto_strategy(&config);
+        assert!(phases
+            .iter()
+            .all(|p| phase_action(&p.search_params) == "search"));
+    }
+
+    // ── Prove phases ────────────────────────────────────────────
+
+    #[test]
+    fn prove_phase_never_generates_followup() {
+        let project = make_project_row(serde_json::json!(null));
+        let mut phase = make_phase("prove", "completed", vec![], None, 0);
+        phase.search_params = serde_json::json!({"action": "prove", "search_type": "factorial", "start": 1, "end": 100});
+        assert!(generate_followup_phase(&project, &phase, &[phase.clone()]).is_none());
+    }
+
     // ── check_fleet_requirements ────────────────────────────────
 
     #[test]
@@ -1323,4 +1704,37 @@ mod tests {
         let fleet = make_fleet(1, 8, 16, vec![]);
         assert!(check_fleet_requirements(&project, &fleet).is_none());
     }
+
+    #[test]
+    fn diff_phases_reports_transitions_and_new_jobs() {
+        let sweep = make_phase("sweep", "pending", vec![], None, 0);
+        let mut verify = make_phase("verify", "pending", vec![], None, 0);
+        verify.id = 2;
+        let before = vec![sweep.clone(), verify.clone()];
+
+        let mut sweep_after = sweep;
+        sweep_after.status = "active".into();
+        sweep_after.search_job_id = Some(42);
+        let mut followup = make_phase("sweep-ext", "pending", vec![], None, 0);
+        followup.id = 3;
+        let after = vec![sweep_after, verify, followup];
+
+        let (transitions, created_jobs) = diff_phases(&before, &after);
+        assert_eq!(
+            transitions,
+            vec![
+                PhaseTransition {
+                    phase: "sweep".into(),
+                    from: Some("pending".into()),
+                    to: "active".into(),
+                },
+                PhaseTransition {
+                    phase: "sweep-ext".into(),
+                    from: None,
+                    to: "pending".into(),
+                },
+            ]
+        );
+        assert_eq!(created_jobs, vec![42]);
+    }
 }
@@ -317,6 +317,7 @@ fn phase_complete_all_blocks_done() {
         claimed: 0,
         completed: 10,
         failed: 0,
+        quarantined: 0,
         total_tested: 10000,
         total_found: 2,
     };
@@ -330,6 +331,7 @@ fn phase_complete_first_prime_found() {
         claimed: 2,
         completed: 3,
         failed: 0,
+        quarantined: 0,
         total_tested: 3000,
         total_found: 1,
     };
@@ -30,6 +30,18 @@
 //! Metrics are updated from the dashboard's 30-second background loop.
 //! The `/metrics` endpoint renders the current registry state on each scrape.
 //!
+//! `darkreach_primes_found_total` is the exception: the DB insert path calls
+//! [`record_prime_found`] for every prime it persists, on a process-global
+//! family that the dashboard's registry ([`Metrics::process`]) shares, so a
+//! search running in the coordinator process shows up without going through
+//! `AppState`.
+//!
+//! Workers cannot usually be scraped (short-lived, behind NAT). With
+//! `--pushgateway-url` set, each worker keeps its own registry and a
+//! [`PushGateway`] PUTs it to a Prometheus Pushgateway on every heartbeat,
+//! rendered by [`Metrics::gather_text`]. Heartbeat pushes go out on a
+//! separate thread so a slow gateway cannot delay the heartbeat.
+//!
 //! ## References
 //!
 //! - [OpenMetrics specification](https://openmetrics.io/)
@@ -42,6 +54,7 @@ use prometheus_client::metrics::gauge::Gauge;
 use prometheus_client::metrics::histogram::Histogram;
 use prometheus_client::registry::Registry;
 use std::sync::atomic::AtomicU64;
+use std::time::Duration;
 
 /// A single entry in the metric catalog returned by `GET /api/observability/catalog`.
 #[derive(Clone, Debug, serde::Serialize)]
@@ -118,12 +131,38 @@ pub struct Metrics {
     pub ai_engine_cost_model_version: Gauge,
 }
 
+/// Per-form count of primes persisted by this process; see
+/// [`record_prime_found`].
+pub fn primes_found_total() -> &'static Family<FormLabel, Counter> {
+    static PRIMES_FOUND: std::sync::OnceLock<Family<FormLabel, Counter>> =
+        std::sync::OnceLock::new();
+    PRIMES_FOUND.get_or_init(Family::default)
+}
+
+/// Count `n` newly persisted primes of `form` in [`primes_found_total`].
+pub fn record_prime_found(form: &str, n: u64) {
+    primes_found_total()
+        .get_or_create(&FormLabel {
+            form: form.to_string(),
+        })
+        .inc_by(n);
+}
+
 impl Metrics {
     /// Create a new metrics registry with all darkreach metrics registered.
     pub fn new() -> Self {
+        Self::with_primes_found(Family::default())
+    }
+
+    /// A registry whose `primes_found` is the process-global
+    /// [`primes_found_total`], for the coordinator's `/metrics`.
+    pub fn process() -> Self {
+        Self::with_primes_found(primes_found_total().clone())
+    }
+
+    fn with_primes_found(primes_found: Family<FormLabel, Counter>) -> Self {
         let mut registry = Registry::default();
 
-        let primes_found = Family::<FormLabel, Counter>::default();
         registry.register(
             "darkreach_primes_found",
             "Total primes discovered by form",
@@ -373,6 +412,49 @@ impl Metrics {
         encode(&mut buf, &self.registry).expect("encoding metrics should not fail");
         buf
     }
+
+    /// Render all metrics in the classic Prometheus text format (version
+    /// 0.0.4), which is what a Pushgateway accepts.
+    ///
+    /// Same samples as [`encode`](Self::encode), minus the OpenMetrics-only
+    /// parts: counter families are named with their `_total` suffix, and the
+    /// `# UNIT` and `# EOF` lines are dropped.
+    pub fn gather_text(&self) -> String {
+        let openmetrics = self.encode();
+        let counters: std::collections::HashSet<&str> = openmetrics
+            .lines()
+            .filter_map(|line| line.strip_prefix("# TYPE "))
+            .filter_map(|rest| rest.strip_suffix(" counter"))
+            .collect();
+
+        let mut out = String::with_capacity(openmetrics.len());
+        for line in openmetrics.lines() {
+            if line == "# EOF" || line.starts_with("# UNIT ") {
+                continue;
+            }
+            let descriptor = ["# HELP ", "# TYPE "]
+                .into_iter()
+                .find_map(|prefix| Some((prefix, line.strip_prefix(prefix)?)));
+            match descriptor {
+                Some((prefix, rest)) => {
+                    let (name, tail) = rest.split_once(' ').unwrap_or((rest, ""));
+                    out.push_str(prefix);
+                    out.push_str(name);
+                    if counters.contains(name) {
+                        out.push_str("_total");
+                    }
+                    out.push(' ');
+                    out.push_str(tail);
+                    out.push('\n');
+                }
+                None => {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
 }
 
 impl Default for Metrics {
@@ -381,6 +463,181 @@ impl Default for Metrics {
     }
 }
 
+// ── Pushgateway ─────────────────────────────────────────────────
+
+/// Process-wide `--pushgateway-url`, set once from `main`.
+static PUSHGATEWAY_URL: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Set the Pushgateway that workers push their metrics to.
+pub fn set_pushgateway_url(url: Option<String>) {
+    if let Some(url) = url {
+        let _ = PUSHGATEWAY_URL.set(url);
+    }
+}
+
+/// The configured Pushgateway URL, if any.
+pub fn pushgateway_url() -> Option<&'static str> {
+    PUSHGATEWAY_URL.get().map(String::as_str)
+}
+
+/// Pushgateway job label for worker metrics.
+const PUSH_JOB: &str = "darkreach_worker";
+
+/// A worker-local [`Metrics`] registry pushed to a Prometheus Pushgateway.
+///
+/// Ephemeral workers (spot instances, volunteer desktops behind NAT) cannot be
+/// scraped, so they push instead. Each push replaces the worker's group, keyed
+/// by `job="darkreach_worker"` and `instance=<worker id>`.
+pub struct PushGateway {
+    agent: ureq::Agent,
+    url: String,
+    metrics: Metrics,
+    tested: u64,
+    found: u64,
+    /// Snapshots for the background push thread, started on first use.
+    background: Option<std::sync::mpsc::SyncSender<String>>,
+}
+
+impl PushGateway {
+    /// A pusher for the `--pushgateway-url` setting, or `None` if it is unset
+    /// or not a valid URL.
+    pub fn from_settings(worker_id: &str) -> Option<Self> {
+        let gateway_url = pushgateway_url()?;
+        match Self::new(gateway_url, worker_id) {
+            Ok(pusher) => Some(pusher),
+            Err(e) => {
+                tracing::warn!(error = %e, "invalid --pushgateway-url, not pushing metrics");
+                None
+            }
+        }
+    }
+
+    pub fn new(gateway_url: &str, worker_id: &str) -> anyhow::Result<Self> {
+        let mut url = url::Url::parse(gateway_url)?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("'{}' cannot be a base URL", gateway_url))?
+            .pop_if_empty()
+            .extend(["metrics", "job", PUSH_JOB, "instance", worker_id]);
+        let agent = ureq::Agent::new_with_config(
+            ureq::config::Config::builder()
+                .timeout_global(Some(Duration::from_secs(10)))
+                .build(),
+        );
+        Ok(PushGateway {
+            agent,
+            url: url.to_string(),
+            metrics: Metrics::new(),
+            tested: 0,
+            found: 0,
+            background: None,
+        })
+    }
+
+    /// The grouping URL pushes are sent to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The registry being pushed, for recording block durations and the like.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Fold the worker's running tested/found totals into the per-form
+    /// counters. Totals lower than the last ones are treated as a restart
+    /// from zero.
+    pub fn record_progress(&mut self, form: &str, tested: u64, found: u64) {
+        let (last_tested, last_found) = if tested < self.tested || found < self.found {
+            (0, 0)
+        } else {
+            (self.tested, self.found)
+        };
+        let form = FormLabel {
+            form: form.to_string(),
+        };
+        self.metrics
+            .candidates_tested
+            .get_or_create(&form)
+            .inc_by((tested - last_tested) as f64);
+        self.metrics
+            .primes_found
+            .get_or_create(&form)
+            .inc_by(found - last_found);
+        self.tested = tested;
+        self.found = found;
+    }
+
+    /// Record one finished work block: its counts and processing time.
+    pub fn record_block(&self, form: &str, tested: u64, found: u64, elapsed: Duration) {
+        let form = FormLabel {
+            form: form.to_string(),
+        };
+        let m = &self.metrics;
+        m.primes_found.get_or_create(&form).inc_by(found);
+        m.candidates_tested
+            .get_or_create(&form)
+            .inc_by(tested as f64);
+        m.work_block_duration
+            .get_or_create(&form)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Record the host's CPU and memory usage.
+    pub fn record_hardware(&self, hw: &crate::metrics::HardwareMetrics) {
+        let m = &self.metrics;
+        m.cpu_usage_percent.set(hw.cpu_usage_percent as f64);
+        m.memory_usage_percent.set(hw.memory_usage_percent as f64);
+    }
+
+    /// Replace this worker's group on the Pushgateway with the current registry.
+    pub fn push(&self) -> anyhow::Result<()> {
+        put_exposition(&self.agent, &self.url, self.metrics.gather_text())
+    }
+
+    /// Hand the current registry to a background thread that pushes it, so
+    /// a slow or unreachable Pushgateway never holds up the caller. At most
+    /// one snapshot waits behind the push in flight; a newer one is dropped
+    /// until then, and the next call sends fresher data anyway. Failures are
+    /// logged by the push thread.
+    pub fn push_in_background(&mut self) {
+        let sender = self.background.get_or_insert_with(|| {
+            let (tx, rx) = std::sync::mpsc::sync_channel::<String>(1);
+            let agent = self.agent.clone();
+            let url = self.url.clone();
+            std::thread::spawn(move || {
+                for body in rx {
+                    if let Err(e) = put_exposition(&agent, &url, body) {
+                        tracing::warn!(url = %url, error = %e, "metrics push failed");
+                    }
+                }
+            });
+            tx
+        });
+        if let Err(std::sync::mpsc::TrySendError::Full(_)) =
+            sender.try_send(self.metrics.gather_text())
+        {
+            tracing::debug!(url = %self.url, "metrics push still in flight, skipping");
+        }
+    }
+
+    /// [`push`](Self::push), logging a failure as a warning: a missing
+    /// Pushgateway must never stop the search.
+    pub fn push_or_warn(&self) {
+        if let Err(e) = self.push() {
+            tracing::warn!(url = %self.url, error = %e, "metrics push failed");
+        }
+    }
+}
+
+/// PUT an exposition-format `body` to a Pushgateway grouping `url`.
+fn put_exposition(agent: &ureq::Agent, url: &str, body: String) -> anyhow::Result<()> {
+    agent
+        .put(url)
+        .header("Content-Type", "text/plain; version=0.0.4")
+        .send(body)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     //! Tests for Prometheus metrics exposition.
@@ -705,4 +962,184 @@ mod tests {
         assert_eq!(q1, q2);
         assert_ne!(q1, q3);
     }
+
+    // ── Pushgateway ───────────────────────────────────────────────
+
+    /// `gather_text()` on a populated registry is valid classic exposition
+    /// text: every sample belongs to the family declared by the preceding
+    /// `# TYPE` line, values parse as numbers, and there are no OpenMetrics
+    /// `# EOF` or `# UNIT` lines (the Pushgateway rejects the former).
+    #[test]
+    fn gather_text_is_valid_exposition() {
+        let m = Metrics::new();
+        let kbn = FormLabel {
+            form: "kbn".to_string(),
+        };
+        m.primes_found.get_or_create(&kbn).inc_by(3);
+        m.candidates_tested.get_or_create(&kbn).inc_by(1500.0);
+        m.work_block_duration.get_or_create(&kbn).observe(42.0);
+        m.heartbeat_rtt.observe(0.02);
+        m.cpu_usage_percent.set(87.5);
+
+        let text = m.gather_text();
+        assert!(text.ends_with('\n'));
+        let mut family: Option<(String, String)> = None;
+        let mut helped = std::collections::HashSet::new();
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# HELP ") {
+                helped.insert(rest.split(' ').next().unwrap().to_string());
+            } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = rest.split_once(' ').unwrap();
+                assert!(helped.contains(name), "TYPE without HELP: {}", line);
+                let known = ["counter", "gauge", "histogram"].contains(&kind);
+                assert!(known, "{}", line);
+                family = Some((name.to_string(), kind.to_string()));
+            } else {
+                assert!(!line.starts_with('#'), "unexpected comment: {}", line);
+                let (name, kind) = family.as_ref().expect("sample before TYPE");
+                let sample = line.split(['{', ' ']).next().unwrap();
+                let allowed = match kind.as_str() {
+                    "histogram" => ["_bucket", "_sum", "_count"]
+                        .iter()
+                        .any(|s| sample == format!("{}{}", name, s)),
+                    _ => sample == name,
+                };
+                assert!(allowed, "sample {} outside family {}", sample, name);
+                let value = line.rsplit(' ').next().unwrap();
+                assert!(value.parse::<f64>().is_ok(), "bad value in {}", line);
+            }
+        }
+
+        assert!(text.contains("# TYPE darkreach_primes_found_total counter"));
+        assert!(text.contains("darkreach_primes_found_total{form=\"kbn\"} 3"));
+        assert!(text.contains("darkreach_cpu_usage_percent 87.5"));
+        assert!(text.contains("darkreach_work_block_duration_seconds_count{form=\"kbn\"} 1"));
+    }
+
+    /// Pushes go to the worker's grouping key, with the worker ID escaped.
+    #[test]
+    fn push_gateway_url_groups_by_worker() {
+        let pusher = PushGateway::new("http://gw:9091/", "spot 7/a").unwrap();
+        assert_eq!(
+            pusher.url(),
+            "http://gw:9091/metrics/job/darkreach_worker/instance/spot%207%2Fa"
+        );
+        assert!(PushGateway::new("not a url", "w").is_err());
+    }
+
+    /// `record_prime_found` lands in the process-global family that
+    /// `Metrics::process` exposes; a plain `Metrics::new` registry is
+    /// unaffected. Compared as deltas since the family is shared process-wide.
+    #[test]
+    fn process_registry_reports_recorded_primes() {
+        let count = |form: &str| {
+            primes_found_total()
+                .get_or_create(&FormLabel {
+                    form: form.to_string(),
+                })
+                .get()
+        };
+        let (kbn, factorial) = (count("kbn"), count("factorial"));
+        record_prime_found("kbn", 1);
+        record_prime_found("kbn", 1);
+        record_prime_found("factorial", 1);
+
+        let text = Metrics::process().encode();
+        assert!(text.contains(&format!(
+            "darkreach_primes_found_total{{form=\"kbn\"}} {}",
+            kbn + 2
+        )));
+        assert!(text.contains(&format!(
+            "darkreach_primes_found_total{{form=\"factorial\"}} {}",
+            factorial + 1
+        )));
+        assert!(!Metrics::new().encode().contains("form=\"kbn\""));
+    }
+
+    /// Progress totals become counter increments, and a lower total (the
+    /// worker restarted its count) is added whole.
+    #[test]
+    fn push_gateway_records_progress_deltas() {
+        let mut pusher = PushGateway::new("http://gw:9091", "w1").unwrap();
+        pusher.record_progress("kbn", 100, 1);
+        pusher.record_progress("kbn", 250, 1);
+        pusher.record_progress("kbn", 40, 2);
+        let text = pusher.metrics().gather_text();
+        assert!(text.contains("darkreach_candidates_tested_total{form=\"kbn\"} 290"));
+        assert!(text.contains("darkreach_primes_found_total{form=\"kbn\"} 3"));
+    }
+
+    /// A PUT reaches the gateway with the exposition body; an unreachable
+    /// gateway is an error for the caller to log, not a panic.
+    #[test]
+    fn push_sends_exposition_and_reports_failures() {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !String::from_utf8_lossy(&request).contains("darkreach_heartbeat_rtt") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let gateway = format!("http://{}", addr);
+        let pusher = PushGateway::new(&gateway, "w1").unwrap();
+        pusher.push().unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("PUT /metrics/job/darkreach_worker/instance/w1 "));
+        assert!(request.contains("text/plain; version=0.0.4"));
+
+        // Nothing listens on the port once the server thread has exited.
+        assert!(pusher.push().is_err());
+    }
+
+    /// A background push returns before the gateway answers; the PUT still
+    /// arrives, from the push thread.
+    #[test]
+    fn push_in_background_does_not_wait_for_the_gateway() {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (received_tx, received) = std::sync::mpsc::channel();
+        let (respond, respond_rx) = std::sync::mpsc::channel::<()>();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !String::from_utf8_lossy(&request).contains("darkreach_heartbeat_rtt") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            received_tx.send(String::from_utf8(request).unwrap()).unwrap();
+            // Hold the response until the test has moved on.
+            respond_rx.recv().unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+        });
+
+        let mut pusher = PushGateway::new(&format!("http://{}", addr), "w1").unwrap();
+        pusher.push_in_background();
+        let request = received
+            .recv_timeout(Duration::from_secs(10))
+            .expect("push thread never sent the PUT");
+        assert!(request.starts_with("PUT /metrics/job/darkreach_worker/instance/w1 "));
+        // The gateway has not answered yet, and the caller is already here.
+        respond.send(()).unwrap();
+        server.join().unwrap();
+    }
 }
@@ -18,6 +18,19 @@
 //! Pocklington) proves primality using Lucas V-sequences: V_{N+1}(P,1) ≡ 2 (mod N)
 //! and gcd(V_{(N+1)/q}(P,1) − 2, N) = 1 for each prime factor q.
 //!
+//! `morrison_auto` generalizes this to any Lucas pair (P, Q): given only the
+//! primes known to divide N+1 (e.g. the 2^(n+1) in Carol/Kynea numbers), it
+//! searches for parameters with Jacobi(P²−4Q, N) = −1 and emits a certificate.
+//!
+//! ### Generic Pocklington N−1 Proof (`pocklington_auto`)
+//!
+//! For a single large candidate whose N−1 has no algebraic structure,
+//! `pocklington_auto` trial-divides N−1 and then splits the cofactor with
+//! independent factoring attempts (P−1 at several bounds, ECM curves) run
+//! across Rayon. Attempts are ordered and resolved with `find_map_first`, so
+//! the parallel path returns the same factor — and certificate — as the serial
+//! one while still stopping once an earlier attempt succeeds.
+//!
 //! ### BLS N+1 Proof (for near-repdigit palindromes)
 //!
 //! Brillhart–Lehmer–Selfridge theorem: if ≥ 1/3 of N+1's bits come from known
@@ -25,6 +38,21 @@
 //! palindromes, N+1 contains a large power of 10 = 2·5, providing factored
 //! bits for free. Trial division of the cofactor adds more when needed.
 //!
+//! ### ECM Cofactor Reduction (`ecm_factor`)
+//!
+//! When trial division leaves the N±1 cofactor short of the BLS threshold,
+//! `ecm_factor` runs Lenstra ECM (stage 1 to B1, standard-continuation
+//! stage 2 to 100·B1) under a curve count and a wall-clock budget. Medium
+//! factors it pulls out count toward the factored bits once each is proven
+//! prime itself, so the proof succeeds without a full factorization.
+//!
+//! ### Cofactor Testing (`is_prime_cofactor`)
+//!
+//! Some candidates are a cofactor of a number with known algebraic factors,
+//! e.g. the Wagstaff (2^p+1)/3. `is_prime_cofactor` divides the known
+//! factors out of the full number and tests what remains, which also covers
+//! probable-prime cofactor records of composites with known factors.
+//!
 //! ## Lucas V-Sequence
 //!
 //! Both Morrison and BLS proofs use the Lucas V binary chain:
@@ -46,9 +74,13 @@
 //! - OEIS: [A002981](https://oeis.org/A002981) — n! + 1 primes.
 //! - OEIS: [A002982](https://oeis.org/A002982) — n! − 1 primes.
 
+use crate::certificate::{MorrisonWitness, PocklingtonWitness, PrimalityCertificate};
+use crate::sieve;
 use rayon::prelude::*;
+use rug::integer::IsPrime;
 use rug::ops::{Pow, RemRounding};
 use rug::Integer;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 /// Compute V_k(P, 1) mod N using the Lucas V binary chain, with arbitrary-precision index k.
@@ -229,6 +261,549 @@ pub fn morrison_factorial_proof(n: u64, candidate: &Integer, sieve_primes: &[u64
     false
 }
 
+/// Compute (U_k(P, Q), V_k(P, Q)) mod N for arbitrary Lucas parameters.
+///
+/// Unlike `lucas_v_big` (fixed Q = 1), this tracks Q^k alongside the sequences
+/// so any (P, Q) with gcd(N, 2Q) = 1 can be used. Left-to-right binary ladder:
+///   doubling: U_2m = U_m·V_m,  V_2m = V_m² − 2Q^m
+///   increment: U_{m+1} = (P·U_m + V_m)/2,  V_{m+1} = (D·U_m + P·V_m)/2
+/// where D = P² − 4Q and division by 2 is modular (N must be odd).
+///
+/// O(log k) multiplications mod N.
+pub(crate) fn lucas_uv_big(k: &Integer, p_val: u32, q_val: i32, n: &Integer) -> (Integer, Integer) {
+    if *k == 0u32 {
+        return (Integer::new(), Integer::from(2).rem_euc(n));
+    }
+
+    let p_int = Integer::from(p_val);
+    let q_int = Integer::from(q_val).rem_euc(n);
+    let d_int = (Integer::from(p_val) * p_val - Integer::from(q_val) * 4u32).rem_euc(n);
+
+    // Modular halving: x/2 mod n for odd n.
+    let half = |mut x: Integer| -> Integer {
+        if x.is_odd() {
+            x += n;
+        }
+        x >>= 1u32;
+        x.rem_euc(n)
+    };
+
+    let mut u = Integer::from(1u32); // U(1)
+    let mut v = p_int.clone().rem_euc(n); // V(1) = P
+    let mut qk = q_int.clone(); // Q^1
+
+    let bits = k.significant_bits();
+    for i in (0..bits - 1).rev() {
+        // Doubling step: m → 2m
+        u = Integer::from(&u * &v).rem_euc(n);
+        v = (Integer::from(&v * &v) - Integer::from(&qk * 2u32)).rem_euc(n);
+        qk.square_mut();
+        qk = qk.rem_euc(n);
+
+        if k.get_bit(i) {
+            // Increment step: 2m → 2m+1
+            let new_u = half(Integer::from(&p_int * &u) + &v);
+            let new_v = half(Integer::from(&d_int * &u) + Integer::from(&p_int * &v));
+            u = new_u;
+            v = new_v;
+            qk = Integer::from(&qk * &q_int).rem_euc(n);
+        }
+    }
+    (u, v)
+}
+
+/// Morrison N+1 proof with automatic Lucas (P, Q) parameter selection.
+///
+/// `factors_np1` lists distinct primes known to divide N+1. Each contributes
+/// its full power q^v_q(N+1) to the factored part F. Morrison's theorem:
+/// if (F − 1)² > N and for every prime q | F there is a Lucas sequence with
+/// D = P² − 4Q, Jacobi(D, N) = −1, gcd(N, 2QD) = 1 satisfying both
+/// U_{N+1}(P, Q) ≡ 0 (mod N) and gcd(U_{(N+1)/q}(P, Q), N) = 1, then N is prime.
+///
+/// Parameter search mirrors `frobenius_test`: Q ranges over small values of
+/// either sign (outer loop), P over small positive values (inner loop),
+/// skipping pairs whose discriminant is not a non-residue. A wide Q range
+/// matters for the factor 2: when Jacobi(Q, N) = 1, U_{(N+1)/2} ≡ 0 for every
+/// P, so q = 2 is only satisfied by a non-residue Q. A non-trivial gcd with 2QD, or a
+/// failed U_{N+1} check, proves N composite. The theorem needs one D for every
+/// q | F, so witnesses are collected per discriminant: factors left unsatisfied
+/// by one pair are retried only with later pairs of the same D, and a
+/// certificate is returned once a single D covers every factor.
+///
+/// Returns a `Morrison` certificate with per-factor (P, Q) witnesses, or `None`
+/// if N is composite, the factorization is insufficient, or no parameters work.
+pub fn morrison_auto(n: &Integer, factors_np1: &[Integer]) -> Option<PrimalityCertificate> {
+    if *n < 5u32 || n.is_even() {
+        return None;
+    }
+
+    let n_plus_1 = Integer::from(n + 1u32);
+
+    // Build the factored part F from the supplied primes, ignoring non-divisors
+    let mut factors: Vec<Integer> = Vec::new();
+    let mut factored = Integer::from(1u32);
+    for q in factors_np1 {
+        if *q < 2u32 || factors.contains(q) || !n_plus_1.is_divisible(q) {
+            continue;
+        }
+        let mut rest = n_plus_1.clone();
+        while rest.is_divisible(q) {
+            rest /= q;
+            factored *= q;
+        }
+        factors.push(q.clone());
+    }
+    if factors.is_empty() {
+        return None;
+    }
+
+    // Morrison threshold: F > sqrt(N) + 1  ⇔  (F − 1)² > N
+    let f_minus_1 = Integer::from(&factored - 1u32);
+    if Integer::from(&f_minus_1 * &f_minus_1) <= *n {
+        debug!(
+            factored_bits = factored.significant_bits(),
+            total_bits = n.significant_bits(),
+            "Morrison auto: N+1 insufficiently factored"
+        );
+        return None;
+    }
+
+    // Per discriminant D: the first P seen and each factor's witness so far
+    let mut by_disc: Vec<(Integer, u32, Vec<Option<MorrisonWitness>>)> = Vec::new();
+
+    for q_abs in 1i32..=20 {
+        for q_val in [q_abs, -q_abs] {
+            for p_val in 1u32..=50 {
+                let disc = Integer::from(p_val * p_val) - Integer::from(q_val) * 4u32;
+                if disc == 0u32 {
+                    continue;
+                }
+                let j = disc.jacobi(n);
+                if j == 0 {
+                    let g = disc.clone().abs().gcd(n);
+                    if g > 1u32 && g < *n {
+                        return None; // non-trivial factor of N
+                    }
+                    continue;
+                }
+                if j != -1 {
+                    continue;
+                }
+                let g = Integer::from(q_val).abs().gcd(n);
+                if g != 1u32 {
+                    if g < *n {
+                        return None;
+                    }
+                    continue;
+                }
+
+                // Global check: U_{N+1} ≡ 0 (mod N)
+                let (u_full, _) = lucas_uv_big(&n_plus_1, p_val, q_val, n);
+                if u_full != 0u32 {
+                    return None; // N is composite
+                }
+
+                let idx = match by_disc.iter().position(|(d, _, _)| *d == disc) {
+                    Some(idx) => idx,
+                    None => {
+                        by_disc.push((disc, p_val, vec![None; factors.len()]));
+                        by_disc.len() - 1
+                    }
+                };
+                let (_, first_p, witnesses) = &mut by_disc[idx];
+                for (i, q) in factors.iter().enumerate() {
+                    if witnesses[i].is_some() {
+                        continue;
+                    }
+                    let exp_q = Integer::from(&n_plus_1 / q);
+                    let (u, _) = lucas_uv_big(&exp_q, p_val, q_val, n);
+                    if u.gcd(n) == 1u32 {
+                        witnesses[i] = Some(MorrisonWitness {
+                            factor: q.to_string(),
+                            p_value: p_val,
+                            q_value: q_val,
+                        });
+                    }
+                }
+
+                if witnesses.iter().all(|w| w.is_some()) {
+                    return Some(PrimalityCertificate::Morrison {
+                        p_value: *first_p,
+                        factors: witnesses.drain(..).flatten().collect(),
+                    });
+                }
+            }
+        }
+    }
+
+    let remaining = by_disc
+        .iter()
+        .map(|(_, _, w)| w.iter().filter(|w| w.is_none()).count())
+        .min()
+        .unwrap_or(factors.len());
+    warn!(
+        remaining,
+        "Morrison auto: no single discriminant satisfied every factor"
+    );
+    None
+}
+
+/// Trial-division bound for the small-prime part of N−1 in `pocklington_auto`.
+const N_MINUS_1_TRIAL_LIMIT: u64 = 100_000;
+
+/// One independent attempt to split an unfactored part of N−1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FactorAttempt {
+    /// Pollard P−1 with stage 1 bound `b1` and stage 2 bound `b2`.
+    PMinus1 { b1: u64, b2: u64 },
+    /// Lenstra ECM stage 1 on the curve selected by `curve`.
+    Ecm { curve: u32, b1: u64 },
+}
+
+/// Default attempt schedule: cheap P−1 first, then deeper P−1 and ECM curves
+/// with increasing B1.
+pub fn default_factor_attempts() -> Vec<FactorAttempt> {
+    let mut attempts: Vec<FactorAttempt> = [10_000u64, 100_000, 1_000_000]
+        .iter()
+        .map(|&b1| FactorAttempt::PMinus1 { b1, b2: b1 * 100 })
+        .collect();
+    for (i, b1) in [2_000u64, 11_000, 50_000].into_iter().enumerate() {
+        let first = i as u32 * 8 + 1;
+        attempts.extend((first..first + 8).map(|curve| FactorAttempt::Ecm { curve, b1 }));
+    }
+    attempts
+}
+
+fn run_attempt(n: &Integer, attempt: &FactorAttempt) -> Option<Integer> {
+    match *attempt {
+        FactorAttempt::PMinus1 { b1, b2 } => crate::p1::p1_factor(n, b1, Some(b2)),
+        FactorAttempt::Ecm { curve, b1 } => ecm_stage1(n, curve, b1),
+    }
+}
+
+/// Affine point on y² = x³ + ax + b (mod n); `None` is the point at infinity.
+type EcmPoint = Option<(Integer, Integer)>;
+
+/// Affine point addition. `Err(g)` carries the gcd that made a denominator
+/// non-invertible — a factor of n, or n itself.
+fn ecm_add(p: &EcmPoint, q: &EcmPoint, a: &Integer, n: &Integer) -> Result<EcmPoint, Integer> {
+    let (Some((x1, y1)), Some((x2, y2))) = (p, q) else {
+        return Ok(if p.is_none() { q.clone() } else { p.clone() });
+    };
+    let (num, den) = if x1 == x2 {
+        if Integer::from(y1 + y2).rem_euc(n) == 0u32 {
+            return Ok(None);
+        }
+        (Integer::from(x1 * x1) * 3u32 + a, Integer::from(y1 * 2u32))
+    } else {
+        (Integer::from(y2 - y1), Integer::from(x2 - x1))
+    };
+    let inv = match den.rem_euc(n).invert(n) {
+        Ok(inv) => inv,
+        Err(den) => return Err(den.gcd(n)),
+    };
+    let lambda = (num * inv).rem_euc(n);
+    let x3 = (Integer::from(&lambda * &lambda) - x1 - x2).rem_euc(n);
+    let y3 = (lambda * Integer::from(x1 - &x3) - y1).rem_euc(n);
+    Ok(Some((x3, y3)))
+}
+
+fn ecm_mul(mut k: u64, p: &EcmPoint, a: &Integer, n: &Integer) -> Result<EcmPoint, Integer> {
+    let mut result: EcmPoint = None;
+    let mut addend = p.clone();
+    while k > 0 {
+        if k & 1 == 1 {
+            result = ecm_add(&result, &addend, a, n)?;
+        }
+        k >>= 1;
+        if k > 0 {
+            addend = ecm_add(&addend, &addend, a, n)?;
+        }
+    }
+    Ok(result)
+}
+
+/// `a` coefficient of the curve selected by `curve`, independent of n.
+fn ecm_curve_a(n: &Integer, curve: u32) -> Integer {
+    (Integer::from(curve) * 7919u32 + 3u32).rem_euc(n)
+}
+
+/// Stage 1 scalar multiplication: the curve's base point times every prime
+/// power ≤ B1. `Err(g)` is the gcd from a failed inversion.
+fn ecm_stage1_point(n: &Integer, curve: u32, b1: u64) -> Result<EcmPoint, Integer> {
+    let a = ecm_curve_a(n, curve);
+    let mut point: EcmPoint = Some((Integer::from(curve + 2).rem_euc(n), Integer::from(1u32)));
+    for q in sieve::generate_primes(b1) {
+        let mut pk = q;
+        while pk <= b1 / q {
+            pk *= q;
+        }
+        point = ecm_mul(pk, &point, &a, n)?;
+        if point.is_none() {
+            break;
+        }
+    }
+    Ok(point)
+}
+
+/// Lenstra ECM stage 1 on a curve chosen deterministically from `curve`.
+///
+/// The curve passes through (curve + 2, 1), so only `a` needs choosing; `b`
+/// is implied. Multiplies the point by every prime power ≤ B1 and returns the
+/// factor exposed when an inversion fails.
+fn ecm_stage1(n: &Integer, curve: u32, b1: u64) -> Option<Integer> {
+    match ecm_stage1_point(n, curve, b1) {
+        Ok(_) => None,
+        Err(g) => (g > 1u32 && g < *n).then_some(g),
+    }
+}
+
+/// Baby-step width of the ECM stage 2 continuation (2·3·5·7·11).
+const ECM_STAGE2_D: u64 = 2310;
+
+/// Stage 2 bound as a multiple of B1.
+const ECM_B2_FACTOR: u64 = 100;
+
+/// Standard ECM stage 2 continuation: catches a curve whose order mod p is
+/// B1-smooth apart from one prime q in (B1, B2].
+///
+/// Writes q = v·D ± u with gcd(u, D) = 1, u < D/2. Then qQ = O (mod p) iff
+/// x(vD·Q) ≡ x(u·Q), so the product of all x(vD·Q) − x(u·Q) shares p with n.
+/// Costs one point addition per giant step plus φ(D)/2 multiplications.
+fn ecm_stage2(n: &Integer, q: &EcmPoint, a: &Integer, b1: u64, b2: u64) -> Option<Integer> {
+    let nontrivial = |g: Integer| (g > 1u32 && g < *n).then_some(g);
+    let d = ECM_STAGE2_D;
+    let q2 = ecm_add(q, q, a, n).map_err(nontrivial).ok()?;
+    let mut baby: Vec<Integer> = Vec::new();
+    let mut u_point = q.clone();
+    for u in (1..d / 2).step_by(2) {
+        if u > 1 {
+            u_point = ecm_add(&u_point, &q2, a, n).map_err(nontrivial).ok()?;
+        }
+        if sieve::gcd(u as u32, d as u32) == 1 {
+            baby.push(u_point.as_ref()?.0.clone());
+        }
+    }
+
+    let dq = match ecm_mul(d, q, a, n) {
+        Ok(p) => p,
+        Err(g) => return nontrivial(g),
+    };
+    let mut v = (b1 / d).max(1);
+    let mut giant = match ecm_mul(v, &dq, a, n) {
+        Ok(p) => p,
+        Err(g) => return nontrivial(g),
+    };
+    let mut acc = Integer::from(1u32);
+    while v * d <= b2 + d {
+        if let Some((xg, _)) = &giant {
+            for xu in &baby {
+                acc *= Integer::from(xg - xu);
+                acc = acc.rem_euc(n);
+            }
+        }
+        giant = match ecm_add(&giant, &dq, a, n) {
+            Ok(p) => p,
+            Err(g) => return nontrivial(g),
+        };
+        v += 1;
+    }
+    nontrivial(acc.gcd(n))
+}
+
+/// One full ECM curve (stage 1 to B1, stage 2 to `ECM_B2_FACTOR`·B1).
+fn ecm_curve(n: &Integer, curve: u32, b1: u64) -> Option<Integer> {
+    match ecm_stage1_point(n, curve, b1) {
+        Err(g) => (g > 1u32 && g < *n).then_some(g),
+        Ok(None) => None,
+        Ok(point) => ecm_stage2(n, &point, &ecm_curve_a(n, curve), b1, b1 * ECM_B2_FACTOR),
+    }
+}
+
+/// Wall-clock cap on a single [`ecm_factor`] call, on top of its curve count.
+pub const ECM_TIME_BUDGET: Duration = Duration::from_secs(60);
+
+/// Factor `n` with up to `curves` ECM curves (stage 1 to `b1`, stage 2 to
+/// 100·b1), stopping early once [`ECM_TIME_BUDGET`] is spent.
+///
+/// Curves 1, 2, … are tried in order; each split is pushed back and later
+/// curves keep working on any composite part, so several medium factors can
+/// come out of one call. Returns the parts of `n` in ascending order — their
+/// product is `n`; each is a probable prime unless the budget ran out first —
+/// or an empty vector if `n` is a probable prime or no curve split it.
+pub fn ecm_factor(n: &Integer, curves: u32, b1: u64) -> Vec<Integer> {
+    if *n < 4u32 || n.is_probably_prime(25) != IsPrime::No {
+        return Vec::new();
+    }
+    let start = Instant::now();
+    let mut done: Vec<Integer> = Vec::new();
+    let mut pending = vec![n.clone()];
+    let mut curve = 1u32;
+    while let Some(part) = pending.pop() {
+        if part.is_probably_prime(25) != IsPrime::No {
+            done.push(part);
+            continue;
+        }
+        let mut split = None;
+        while split.is_none() && curve <= curves && start.elapsed() < ECM_TIME_BUDGET {
+            split = ecm_curve(&part, curve, b1);
+            curve += 1;
+        }
+        match split {
+            Some(d) => {
+                debug!(
+                    curve = curve - 1,
+                    bits = d.significant_bits(),
+                    "ECM: found factor"
+                );
+                pending.push(Integer::from(&part / &d));
+                pending.push(d);
+            }
+            None => done.push(part),
+        }
+    }
+    if done.len() < 2 {
+        return Vec::new();
+    }
+    done.sort();
+    done
+}
+
+/// Whether a prime factor of N−1 is known to be prime rather than merely PRP.
+///
+/// GMP's test includes BPSW, which has no counterexamples below 2^64; larger
+/// factors are proven recursively.
+fn factor_is_proven(q: &Integer, attempts: &[FactorAttempt], parallel: bool) -> bool {
+    q.significant_bits() <= 64 || pocklington_auto_with(q, &[], attempts, parallel).is_some()
+}
+
+/// Move the full power of prime `q` from `rest` into `factored`, once.
+fn strip_prime(q: &Integer, rest: &mut Integer, factored: &mut Integer, primes: &mut Vec<Integer>) {
+    if !rest.is_divisible(q) || primes.contains(q) {
+        return;
+    }
+    while rest.is_divisible(q) {
+        *rest /= q;
+        *factored *= q;
+    }
+    primes.push(q.clone());
+}
+
+/// Generic Pocklington N−1 proof with parallel factor discovery.
+///
+/// Equivalent to `pocklington_auto_with` using [`default_factor_attempts`]
+/// across Rayon. Twin pairs prove the upper member with it, and the BLS proof
+/// uses it for the ECM factors of its cofactor.
+pub fn pocklington_auto(n: &Integer, known_factors: &[Integer]) -> Option<PrimalityCertificate> {
+    pocklington_auto_with(n, known_factors, &default_factor_attempts(), true)
+}
+
+/// Pocklington N−1 proof for an arbitrary candidate.
+///
+/// Strips `known_factors` (primes dividing N−1) and primes up to `N_MINUS_1_TRIAL_LIMIT` from N−1,
+/// then splits the remaining parts with `attempts` until the proven factored
+/// part F satisfies F² > N. With `parallel`, attempts on each part run across
+/// Rayon and the first success in schedule order wins, matching the serial
+/// result. Each prime q | F then needs a base a with a^(N−1) ≡ 1 and
+/// gcd(a^((N−1)/q) − 1, N) = 1.
+///
+/// Returns a `Pocklington` certificate with factors in ascending order, or
+/// `None` if N is composite or not enough of N−1 could be factored.
+fn pocklington_auto_with(
+    n: &Integer,
+    known_factors: &[Integer],
+    attempts: &[FactorAttempt],
+    parallel: bool,
+) -> Option<PrimalityCertificate> {
+    if *n < 5u32 || n.is_even() {
+        return None;
+    }
+    let n_minus_1 = Integer::from(n - 1u32);
+    let mut rest = n_minus_1.clone();
+    let mut primes: Vec<Integer> = Vec::new();
+    let mut factored = Integer::from(1u32);
+
+    for q in known_factors.iter().filter(|q| **q >= 2u32) {
+        strip_prime(q, &mut rest, &mut factored, &mut primes);
+    }
+    for p in sieve::generate_primes(N_MINUS_1_TRIAL_LIMIT) {
+        if rest.is_divisible_u(p as u32) {
+            strip_prime(&Integer::from(p), &mut rest, &mut factored, &mut primes);
+        }
+    }
+
+    let sufficient = |f: &Integer| Integer::from(f * f) > *n;
+    let mut pending: Vec<Integer> = if rest > 1u32 { vec![rest] } else { vec![] };
+    while !sufficient(&factored) {
+        let Some(part) = pending.pop() else {
+            break;
+        };
+        if part.is_probably_prime(30) != IsPrime::No {
+            if factor_is_proven(&part, attempts, parallel) {
+                let mut rest = Integer::from(&n_minus_1 / &factored);
+                strip_prime(&part, &mut rest, &mut factored, &mut primes);
+            }
+            continue;
+        }
+        let split = if parallel {
+            attempts
+                .par_iter()
+                .find_map_first(|a| run_attempt(&part, a))
+        } else {
+            attempts.iter().find_map(|a| run_attempt(&part, a))
+        };
+        if let Some(d) = split {
+            debug!(
+                bits = d.significant_bits(),
+                "Pocklington auto: split N-1 cofactor"
+            );
+            let cofactor = Integer::from(&part / &d);
+            pending.push(cofactor);
+            pending.push(d);
+        }
+    }
+    if !sufficient(&factored) {
+        debug!(
+            factored_bits = factored.significant_bits(),
+            total_bits = n.significant_bits(),
+            "Pocklington auto: N-1 insufficiently factored"
+        );
+        return None;
+    }
+
+    primes.sort();
+    // None if N is proven composite or no base in range witnesses q
+    let witness = |q: &Integer| -> Option<PocklingtonWitness> {
+        let exp_q = Integer::from(&n_minus_1 / q);
+        for base in 2u32..=200 {
+            let a = Integer::from(base);
+            if a.clone().pow_mod(&n_minus_1, n).ok()? != 1u32 {
+                return None; // N is composite
+            }
+            let r = a.pow_mod(&exp_q, n).ok()?;
+            if (r - 1u32).gcd(n) == 1u32 {
+                return Some(PocklingtonWitness {
+                    factor: q.to_string(),
+                    base,
+                });
+            }
+        }
+        None
+    };
+    let factors: Option<Vec<PocklingtonWitness>> = if parallel {
+        primes.par_iter().map(witness).collect()
+    } else {
+        primes.iter().map(witness).collect()
+    };
+    factors.map(|factors| PrimalityCertificate::Pocklington { factors })
+}
+
+/// ECM curve budget spent on a BLS cofactor that trial division left short.
+const BLS_ECM_CURVES: u32 = 8;
+
+/// ECM stage 1 bound for the BLS cofactor pass (finds ~15–20 digit factors).
+const BLS_ECM_B1: u64 = 2_000;
+
 /// BLS N+1 proof for near-repdigit palindromes.
 ///
 /// For N = 10^(2k+1) - 1 - d*(10^(k+m) + 10^(k-m)):
@@ -289,20 +864,40 @@ pub fn bls_near_repdigit_proof(
         }
     }
 
+    // Trial division fell short: spend a small ECM budget on what is left
+    // beyond the sieve and keep only the parts that are proven prime. An
+    // empty sieve means the caller opted out of cofactor factoring.
+    let mut ecm_factors: Vec<Integer> = Vec::new();
+    if factored_bits < total_bits / 3.0 && !sieve_primes.is_empty() && remaining > 1u32 {
+        for q in ecm_factor(&remaining, BLS_ECM_CURVES, BLS_ECM_B1) {
+            if q.is_probably_prime(25) == IsPrime::No
+                || (q.significant_bits() > 64 && pocklington_auto(&q, &[]).is_none())
+            {
+                continue;
+            }
+            while remaining.is_divisible(&q) {
+                remaining /= &q;
+                factored_bits += q.to_f64().log2();
+            }
+            ecm_factors.push(q);
+        }
+    }
+
     // BLS threshold: factored portion must exceed N^(1/3)
     if factored_bits < total_bits / 3.0 {
         return false;
     }
 
     // Collect all distinct prime factors for BLS verification
-    let mut all_factors: Vec<u64> = Vec::new();
+    let mut all_factors: Vec<Integer> = Vec::new();
     if power_of_10_exp > 0 {
-        all_factors.push(2);
-        all_factors.push(5);
+        all_factors.push(Integer::from(2u32));
+        all_factors.push(Integer::from(5u32));
     }
-    for &(p, _) in &cofactor_factors {
-        if !all_factors.contains(&p) {
-            all_factors.push(p);
+    let trial_factors = cofactor_factors.iter().map(|&(p, _)| Integer::from(p));
+    for q in trial_factors.chain(ecm_factors) {
+        if !all_factors.contains(&q) {
+            all_factors.push(q);
         }
     }
 
@@ -338,7 +933,7 @@ pub fn bls_near_repdigit_proof(
         }
 
         // Check unsatisfied factors with this P
-        for (i, &q) in all_factors.iter().enumerate() {
+        for (i, q) in all_factors.iter().enumerate() {
             if factor_satisfied[i] {
                 continue;
             }
@@ -363,6 +958,44 @@ pub fn bls_near_repdigit_proof(
     false
 }
 
+/// The cofactor of `full` after dividing out each of `known_factors` once.
+///
+/// List a factor twice to remove its square. Factors below 2 and factors
+/// that do not divide the running cofactor are skipped with a warning.
+pub fn cofactor(full: &Integer, known_factors: &[Integer]) -> Integer {
+    let mut rest = full.clone();
+    for f in known_factors.iter().filter(|f| **f > 1u32) {
+        if rest.is_divisible(f) {
+            rest.div_exact_mut(f);
+        } else {
+            warn!(factor = %f, "known factor does not divide candidate, skipped");
+        }
+    }
+    rest
+}
+
+/// Test the cofactor of `full` left after dividing out `known_factors`;
+/// see [`cofactor`].
+///
+/// A cofactor below 2 (the known factors cover `full`) is `IsPrime::No`.
+/// The rest goes through [`crate::mr_screened_test`] with 25 rounds.
+pub fn is_prime_cofactor(full: &Integer, known_factors: &[Integer]) -> IsPrime {
+    is_prime_cofactor_with(full, known_factors, 25)
+}
+
+/// [`is_prime_cofactor`] with an explicit Miller–Rabin round count.
+pub fn is_prime_cofactor_with(
+    full: &Integer,
+    known_factors: &[Integer],
+    mr_rounds: u32,
+) -> IsPrime {
+    let rest = cofactor(full, known_factors);
+    if rest < 2u32 {
+        return IsPrime::No;
+    }
+    crate::mr_screened_test(&rest, mr_rounds)
+}
+
 #[cfg(test)]
 mod tests {
     //! # Tests for Deterministic Primality Proofs
@@ -960,6 +1593,236 @@ mod tests {
         );
     }
 
+    // ── Morrison with Automatic (P, Q) Selection ─────────────────────
+    //
+    // `morrison_auto` searches Lucas parameters itself and only needs the
+    // primes dividing N+1. Carol numbers (2^n−1)²−2 have N+1 = 2^(n+1)·(2^(n−1)−1)
+    // and Kynea numbers (2^n+1)²−2 have N+1 = 2^(n+1)·(2^(n−1)+1), so the
+    // single factor 2 already exceeds sqrt(N).
+
+    /// Reference Lucas U computation via the recurrence U_k = P·U_{k−1} − Q·U_{k−2}.
+    fn lucas_u_ref(k: u64, p: i64, q: i64, n: &Integer) -> Integer {
+        let (mut prev, mut cur) = (Integer::new(), Integer::from(1u32));
+        if k == 0 {
+            return prev;
+        }
+        for _ in 1..k {
+            let next = (Integer::from(p) * &cur - Integer::from(q) * &prev).rem_euc(n);
+            prev = cur;
+            cur = next;
+        }
+        cur.rem_euc(n)
+    }
+
+    /// Cross-validate the U output of `lucas_uv_big` against the naive recurrence
+    /// for several (P, Q) pairs, including negative Q.
+    #[test]
+    fn lucas_uv_big_matches_recurrence() {
+        let modulus = Integer::from(1000003u32);
+        for &(p, q) in &[(1u32, -1i32), (3, 1), (5, 2), (4, -3)] {
+            for k in 0..30u64 {
+                let (u, _) = lucas_uv_big(&Integer::from(k), p, q, &modulus);
+                assert_eq!(
+                    u,
+                    lucas_u_ref(k, p as i64, q as i64, &modulus),
+                    "U_{}({}, {}) mismatch",
+                    k,
+                    p,
+                    q
+                );
+            }
+        }
+    }
+
+    /// Carol primes (OEIS [A091515](https://oeis.org/A091515)) and Kynea primes
+    /// (OEIS [A091514](https://oeis.org/A091514)) are proven from the factor 2 alone.
+    #[test]
+    fn morrison_auto_proves_carol_and_kynea_primes() {
+        let two = [Integer::from(2u32)];
+        for &n in &[7u32, 10, 12, 15, 18, 19, 21, 25, 27] {
+            let carol = (Integer::from(1u32) << n) - 1u32;
+            let carol = Integer::from(&carol * &carol) - 2u32;
+            let cert = morrison_auto(&carol, &two)
+                .unwrap_or_else(|| panic!("Carol n={} should be proven", n));
+            match cert {
+                PrimalityCertificate::Morrison { factors, .. } => {
+                    assert_eq!(factors.len(), 1);
+                    assert_eq!(factors[0].factor, "2");
+                }
+                other => panic!("expected Morrison certificate, got {:?}", other),
+            }
+        }
+        for &n in &[5u32, 8, 9, 12, 15, 17, 18, 21, 23, 27] {
+            let kynea = (Integer::from(1u32) << n) + 1u32;
+            let kynea = Integer::from(&kynea * &kynea) - 2u32;
+            assert!(
+                morrison_auto(&kynea, &two).is_some(),
+                "Kynea n={} should be proven",
+                n
+            );
+        }
+    }
+
+    /// Composite Carol numbers: n=13 gives 3761·17839, n=16 gives 41·104752103.
+    #[test]
+    fn morrison_auto_rejects_composite_carol() {
+        let two = [Integer::from(2u32)];
+        for &n in &[13u32, 16] {
+            let carol = (Integer::from(1u32) << n) - 1u32;
+            let carol = Integer::from(&carol * &carol) - 2u32;
+            assert!(
+                morrison_auto(&carol, &two).is_none(),
+                "Carol n={} is composite",
+                n
+            );
+        }
+    }
+
+    /// 1009 is prime with N+1 = 1010 = 2·5·101. The factor 2 alone is far below
+    /// sqrt(N); the full factorization proves it. Non-divisors are ignored.
+    #[test]
+    fn morrison_auto_requires_sufficient_factorization() {
+        let n = Integer::from(1009u32);
+        assert!(morrison_auto(&n, &[Integer::from(2u32)]).is_none());
+        let full = [2u32, 5, 101, 7].map(Integer::from);
+        assert!(morrison_auto(&n, &full).is_some());
+        assert!(morrison_auto(&n, &[]).is_none());
+        assert!(morrison_auto(&Integer::from(1010u32), &full).is_none());
+    }
+
+    /// Every witness of a multi-factor certificate shares one discriminant
+    /// D = P² − 4Q. Primes N = 2^a·3^b·5^c − 1 put three distinct primes in
+    /// N+1, so a search that mixed D across factors would show up here.
+    #[test]
+    fn morrison_auto_uses_one_discriminant_for_all_factors() {
+        let factors = [2u32, 3, 5].map(Integer::from);
+        let mut multi_factor = 0;
+        for a in 1..=12u32 {
+            for b in 1..=6u32 {
+                for c in 1..=4u32 {
+                    let n = Integer::from(1u32 << a)
+                        * Integer::from(3u32).pow(b)
+                        * Integer::from(5u32).pow(c)
+                        - 1u32;
+                    if n.is_probably_prime(25) == IsPrime::No {
+                        continue;
+                    }
+                    let Some(PrimalityCertificate::Morrison { factors: w, .. }) =
+                        morrison_auto(&n, &factors)
+                    else {
+                        panic!("no Morrison certificate for prime {}", n);
+                    };
+                    assert_eq!(w.len(), 3, "{}", n);
+                    let disc = |w: &MorrisonWitness| {
+                        i64::from(w.p_value).pow(2) - 4 * i64::from(w.q_value)
+                    };
+                    assert!(
+                        w.iter().all(|x| disc(x) == disc(&w[0])),
+                        "mixed discriminants for {}: {:?}",
+                        n,
+                        w
+                    );
+                    multi_factor += 1;
+                }
+            }
+        }
+        assert!(multi_factor > 10, "only {} primes checked", multi_factor);
+    }
+
+    // ── Pocklington with Parallel Factor Discovery ───────────────────
+
+    /// 80000392000457 = 8 · 1000003 · 10000019 + 1 is prime. Both large
+    /// factors of N−1 exceed the trial-division limit, so the proof must split
+    /// the cofactor with ECM or P−1 (10000018 = 2·7²·67·1523 is smooth).
+    fn moderately_factorable_prime() -> Integer {
+        Integer::from(80_000_392_000_457u64)
+    }
+
+    /// A small schedule mixing ECM curves and a P−1 attempt that is certain
+    /// to split 1000003 · 10000019.
+    fn test_attempts() -> Vec<FactorAttempt> {
+        let mut attempts: Vec<FactorAttempt> = (1..=6)
+            .map(|curve| FactorAttempt::Ecm { curve, b1: 2_000 })
+            .collect();
+        attempts.push(FactorAttempt::PMinus1 {
+            b1: 2_000,
+            b2: 200_000,
+        });
+        attempts
+    }
+
+    /// The parallel proof of a prime with a moderately-factorable N−1 must
+    /// produce exactly the certificate the serial path does.
+    #[test]
+    fn pocklington_auto_parallel_matches_serial() {
+        let n = moderately_factorable_prime();
+        let attempts = test_attempts();
+        let serial = pocklington_auto_with(&n, &[], &attempts, false).expect("serial proof");
+        let parallel = pocklington_auto_with(&n, &[], &attempts, true).expect("parallel proof");
+        assert_eq!(serial, parallel);
+
+        let PrimalityCertificate::Pocklington { factors } = serial else {
+            panic!("expected a Pocklington certificate");
+        };
+        let primes: Vec<&str> = factors.iter().map(|w| w.factor.as_str()).collect();
+        assert_eq!(primes, vec!["2", "1000003", "10000019"]);
+    }
+
+    /// The default schedule also proves the candidate.
+    #[test]
+    fn pocklington_auto_default_schedule() {
+        assert!(pocklington_auto(&moderately_factorable_prime(), &[]).is_some());
+    }
+
+    /// A composite with fully factored N−1 fails the Fermat condition.
+    #[test]
+    fn pocklington_auto_rejects_composite() {
+        // Carmichael number 561 = 3 · 11 · 17 passes Fermat for base 2, but
+        // no base satisfies every gcd condition; N−1 = 2^4 · 5 · 7
+        let n = Integer::from(561u32);
+        assert!(pocklington_auto_with(&n, &[], &test_attempts(), false).is_none());
+        assert!(pocklington_auto_with(&Integer::from(1001u32), &[], &[], true).is_none());
+    }
+
+    /// Without any attempt able to split the cofactor, the proof gives up.
+    #[test]
+    fn pocklington_auto_needs_enough_factorization() {
+        assert!(pocklington_auto_with(&moderately_factorable_prime(), &[], &[], true).is_none());
+    }
+
+    /// ECM stage 1 finds a six- or eight-digit factor on at least one of a
+    /// handful of curves; any factor returned divides N non-trivially.
+    #[test]
+    fn ecm_stage1_splits_semiprime() {
+        let n = Integer::from(1_000_003u64 * 10_000_019);
+        let found: Vec<Integer> = (1..=20).filter_map(|c| ecm_stage1(&n, c, 2_000)).collect();
+        assert!(!found.is_empty(), "no curve split the semiprime");
+        for d in found {
+            assert!(d == 1_000_003u32 || d == 10_000_019u32, "bad factor {}", d);
+        }
+    }
+
+    /// ecm_factor pulls a 30-digit prime out of a 60-digit semiprime within an
+    /// 8-curve budget at B1 = 50000, and returns both parts. p was picked as
+    /// the first prime above 3·10^29 whose group order on curve 1 is smooth
+    /// enough for stage 2, so the test is deterministic rather than lucky.
+    #[test]
+    fn ecm_factor_splits_off_30_digit_factor() {
+        let p: Integer = "300000000000000000000000529723".parse().unwrap();
+        let q: Integer = "700000000000000000000000012373".parse().unwrap();
+        let n = Integer::from(&p * &q);
+        assert_eq!(ecm_factor(&n, 8, 50_000), vec![p, q]);
+    }
+
+    /// Primes and exhausted budgets yield no parts.
+    #[test]
+    fn ecm_factor_returns_empty_without_split() {
+        let q: Integer = "700000000000000000000000012373".parse().unwrap();
+        assert!(ecm_factor(&q, 8, 2_000).is_empty());
+        let n = Integer::from(1_000_003u64 * 10_000_019);
+        assert!(ecm_factor(&n, 0, 2_000).is_empty());
+    }
+
     // ── Additional BLS Tests ─────────────────────────────────────────
 
     /// Verify BLS fails when factored bits are insufficient with no sieve help.
@@ -1059,4 +1922,47 @@ mod tests {
         let r2 = lucas_v_big(&k, 7, &modulus);
         assert_eq!(r1, r2, "lucas_v_big should be deterministic");
     }
+
+    // ── Cofactor Testing ─────────────────────────────────────────────
+
+    /// Dividing 3 out of 2^p + 1 and testing the cofactor agrees with a
+    /// direct test of (2^p + 1)/3, for Wagstaff exponents (5, 7, 11, 13,
+    /// 17, 19, 23, 31, 43, 61, 79, 101, 127) and the composite cases between.
+    #[test]
+    fn is_prime_cofactor_matches_direct_wagstaff_test() {
+        let three = Integer::from(3u32);
+        let odd_primes = sieve::generate_primes(127);
+        for &p in odd_primes.iter().filter(|&&p| p >= 5) {
+            let full = (Integer::from(1u32) << p as u32) + 1u32;
+            let direct = crate::mr_screened_test(&Integer::from(&full / 3u32), 25);
+            let via_cofactor = is_prime_cofactor(&full, std::slice::from_ref(&three));
+            assert_eq!(via_cofactor, direct, "p={}", p);
+        }
+        let wagstaff_127 = (Integer::from(1u32) << 127u32) + 1u32;
+        assert_ne!(
+            is_prime_cofactor(&wagstaff_127, std::slice::from_ref(&three)),
+            IsPrime::No
+        );
+        let composite_29 = (Integer::from(1u32) << 29u32) + 1u32;
+        assert_eq!(is_prime_cofactor(&composite_29, &[three]), IsPrime::No);
+    }
+
+    /// Each factor is divided out once: 2^3 + 1 = 9 over 3 leaves the prime
+    /// 3, over 3·3 leaves 1. Non-divisors are skipped.
+    #[test]
+    fn cofactor_divides_each_factor_once() {
+        let nine = Integer::from(9u32);
+        let three = Integer::from(3u32);
+        assert_eq!(cofactor(&nine, std::slice::from_ref(&three)), 3);
+        assert_ne!(
+            is_prime_cofactor(&nine, std::slice::from_ref(&three)),
+            IsPrime::No
+        );
+        assert_eq!(
+            is_prime_cofactor(&nine, &[three.clone(), three]),
+            IsPrime::No
+        );
+        let factors = [Integer::from(3u32), Integer::from(5u32)];
+        assert_eq!(cofactor(&Integer::from(35u32), &factors), 7);
+    }
 }
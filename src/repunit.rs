@@ -19,6 +19,12 @@
 //! This "one elimination per prime" property makes the sieve less effective
 //! than for other forms, requiring deeper sieving or more PRP tests.
 //!
+//! ## Aurifeuillian Factors
+//!
+//! Before sieving, exponents where R(b, n) has an Aurifeuillian factor are
+//! dropped (see [`aurifeuillian_factor`]). With b = p·m², p ≡ 1 (mod 4) prime,
+//! this removes the single prime exponent n = p, e.g. R(5, 5) = 11 · 71.
+//!
 //! ## Complexity
 //!
 //! - Sieve construction: O(π(L)²) due to multiplicative order computation.
@@ -44,10 +50,11 @@ use std::time::Instant;
 
 use tracing::info;
 
+use crate::candidate::{CandidateForm, Repunit};
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::CoordinationClient;
 use crate::{exact_digits, mr_screened_test, sieve};
@@ -99,13 +106,131 @@ fn sieve_repunit(
     survives
 }
 
+/// Largest squarefree part p for which [`aurifeuillian_factor`] builds the
+/// degree-(p−1)/2 factor polynomial (quadratic in p).
+const AURIFEUILLIAN_MAX_P: u64 = 2_000;
+
+/// Write `base` as `s · m²` with `s` squarefree.
+fn squarefree_decompose(base: u64) -> (u64, u64) {
+    let (mut s, mut m, mut rest) = (1u64, 1u64, base);
+    let mut q = 2u64;
+    while q * q <= rest {
+        let mut e = 0;
+        while rest.is_multiple_of(q) {
+            rest /= q;
+            e += 1;
+        }
+        m *= q.pow(e / 2);
+        if e % 2 == 1 {
+            s *= q;
+        }
+        q += 1;
+    }
+    (s * rest, m)
+}
+
+/// An element `(u + v·√p) / 2` of Z[(1 + √p)/2].
+type HalfQuadratic = (Integer, Integer);
+
+fn half_mul(a: &HalfQuadratic, b: &HalfQuadratic, p: u64) -> HalfQuadratic {
+    let u = (Integer::from(&a.0 * &b.0) + Integer::from(&a.1 * &b.1) * p) / 2u32;
+    let v = (Integer::from(&a.0 * &b.1) + Integer::from(&a.1 * &b.0)) / 2u32;
+    (u, v)
+}
+
+/// Coefficients of L(y) = ∏ (y − ζ^a) over the quadratic residues a mod p,
+/// highest degree first, for a prime p ≡ 1 (mod 4).
+///
+/// The power sums of the roots are Gauss periods, (−1 ± √p)/2 or (p − 1)/2,
+/// so Newton's identities give the coefficients in Z[(1 + √p)/2].
+fn residue_polynomial(p: u64) -> Vec<HalfQuadratic> {
+    let h = ((p - 1) / 2) as usize;
+    let pi = Integer::from(p);
+    let power_sum = |k: usize| -> HalfQuadratic {
+        match Integer::from(k).jacobi(&pi) {
+            0 => (Integer::from(p - 1), Integer::new()),
+            1 => (Integer::from(-1), Integer::from(1)),
+            _ => (Integer::from(-1), Integer::from(-1)),
+        }
+    };
+    let sums: Vec<HalfQuadratic> = (1..=h).map(power_sum).collect();
+
+    // k·e_k = Σ_{i=1..k} (−1)^(i−1) e_(k−i) S_i
+    let mut e: Vec<HalfQuadratic> = vec![(Integer::from(2), Integer::new())];
+    for k in 1..=h {
+        let (mut u, mut v) = (Integer::new(), Integer::new());
+        for i in 1..=k {
+            let (tu, tv) = half_mul(&e[k - i], &sums[i - 1], p);
+            if i % 2 == 1 {
+                u += tu;
+                v += tv;
+            } else {
+                u -= tu;
+                v -= tv;
+            }
+        }
+        e.push((u / k as u64, v / k as u64));
+    }
+    // L(y) = Σ (−1)^j e_j y^(h−j)
+    for (j, c) in e.iter_mut().enumerate() {
+        if j % 2 == 1 {
+            c.0 = -std::mem::take(&mut c.0);
+            c.1 = -std::mem::take(&mut c.1);
+        }
+    }
+    e
+}
+
+/// An Aurifeuillian factor of R(b, n), if the form admits one.
+///
+/// Write b = p·m² with p squarefree. When p is a prime ≡ 1 (mod 4) and n is
+/// an odd multiple of p, Φ_p(b^(n/p)) divides R(b, n) and splits as
+/// Φ_p(p·x²) = Φ_p(x√p)·Φ_p(−x√p) into two integer factors: the norms of
+/// L(±x√p), where L is the residue half of Φ_p. For example
+/// R(5, 5) = 781 = 11 · 71. Returns the smaller factor when it is > 1.
+///
+/// For prime n this only ever fires at n = p. Bases whose squarefree part is
+/// ≡ 2, 3 (mod 4), base 2 among them, have Aurifeuillian factors of b^n + 1
+/// rather than b^n − 1, so their repunits never match.
+pub fn aurifeuillian_factor(base: u32, n: u64) -> Option<Integer> {
+    let (p, m) = squarefree_decompose(base as u64);
+    if p % 4 != 1 || p > AURIFEUILLIAN_MAX_P || !n.is_multiple_of(p) {
+        return None;
+    }
+    let k = n / p;
+    if k.is_multiple_of(2) || Integer::from(p).is_probably_prime(25) == IsPrime::No {
+        return None;
+    }
+    // b^k = p·x² with x = p^((k−1)/2)·m^k.
+    let k32 = crate::checked_u32(k);
+    let x = Integer::from(p).pow((k32 - 1) / 2) * Integer::from(m).pow(k32);
+
+    // Evaluate L(x√p) by Horner's rule.
+    let point: HalfQuadratic = (Integer::new(), Integer::from(&x * 2u32));
+    let mut value: HalfQuadratic = (Integer::new(), Integer::new());
+    for c in residue_polynomial(p) {
+        value = half_mul(&value, &point, p);
+        value.0 += c.0;
+        value.1 += c.1;
+    }
+    // N((u + v√p)/2) = (u² − p·v²)/4
+    let (u, v) = value;
+    let factor = ((u.square() - v.square() * p) / 4u32).abs();
+
+    let y = Integer::from(base).pow(k32);
+    let phi = (Integer::from((&y).pow(p as u32)) - 1u32) / (y - 1u32);
+    let cofactor = Integer::from(&phi / &factor);
+    let smaller = factor.min(cofactor);
+    (smaller > 1u32).then_some(smaller)
+}
+
 /// Search for repunit primes: R(b,n) = (b^n - 1)/(b - 1) for prime n.
 pub fn search(
     base: u32,
     min_n: u64,
     max_n: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -114,7 +239,8 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
-    assert!(base >= 2, "Base must be >= 2");
+    let params = serde_json::json!({"base": base, "min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("repunit", &params)?;
 
     // Resolve sieve_limit: auto-tune if 0
     // R(b,n) ≈ b^(n-1) has ~max_n * log2(base) bits
@@ -145,6 +271,26 @@ pub fn search(
         .filter(|&p| p >= resume_from)
         .collect();
 
+    // Aurifeuillian pre-filter: R(b, n) splits algebraically when b = p·m²
+    // with p ≡ 1 (mod 4) prime and n an odd multiple of p; for prime n that
+    // is n = p alone, so only multiples of p need the check.
+    let (squarefree, _) = squarefree_decompose(base as u64);
+    let prime_exponents: Vec<u64> = prime_exponents
+        .into_iter()
+        .filter(|&n| {
+            if squarefree == 1 || !n.is_multiple_of(squarefree) {
+                return true;
+            }
+            match aurifeuillian_factor(base, n) {
+                Some(factor) => {
+                    info!(n, %factor, "Aurifeuillian factor, skipping exponent");
+                    false
+                }
+                None => true,
+            }
+        })
+        .collect();
+
     info!(count = prime_exponents.len(), "prime exponents in range");
 
     if prime_exponents.is_empty() {
@@ -184,6 +330,7 @@ pub fn search(
     // Process in blocks for checkpointing
     let block_size = 100;
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let b_minus_1 = base - 1;
 
     for chunk in survivors.chunks(block_size) {
@@ -195,8 +342,10 @@ pub fn search(
         let found: Vec<_> = chunk
             .par_iter()
             .filter_map(|&n| {
+                let _busy = crate::progress::busy();
                 let val = (Integer::from(base).pow(crate::checked_u32(n)) - 1u32) / b_minus_1;
                 let pfgw_expr = format!("({}^{}-1)/{}", base, n, base - 1);
+                let _testing = progress.testing(&pfgw_expr, &val);
 
                 // Try PFGW acceleration (50-100x faster for large candidates)
                 if let Some(pfgw_result) = pfgw::try_test(&pfgw_expr, &val, pfgw::PfgwMode::Prp) {
@@ -214,12 +363,12 @@ pub fn search(
                             return Some((n, digits, certainty));
                         }
                         pfgw::PfgwResult::Composite => return None,
-                        pfgw::PfgwResult::Unavailable { .. } => {} // fall through
+                        pfgw::PfgwResult::Unavailable { .. } | pfgw::PfgwResult::Failed { .. } => {} // fall through
                     }
                 }
 
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&val) {
+                if crate::p1::adaptive_p1_filter_for_form(&val, "repunit") {
                     return None;
                 }
 
@@ -240,9 +389,23 @@ pub fn search(
         progress
             .tested
             .fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        progress.record_survivors(chunk.iter().copied());
 
         for (n, digits, certainty) in found {
-            let expr = format!("R({}, {})", base, n);
+            let expr = Repunit { base }.expression(n);
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "repunit",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -250,8 +413,12 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                eb.check_milestone("repunit", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "repunit").ok().flatten()
+                });
             } else {
                 info!(
                     expression = %expr,
@@ -260,21 +427,13 @@ pub fn search(
                     "repunit prime found"
                 );
             }
-            db.insert_prime_sync(
-                rt,
-                "repunit",
-                &expr,
-                digits,
-                search_params,
-                &certainty,
-                None,
-            )?;
-            if let Some(wc) = worker_client {
-                wc.report_prime("repunit", &expr, digits, search_params, &certainty);
-            }
+            buffer.push("repunit", &expr, digits, search_params, &certainty, None)?;
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Repunit {
@@ -289,6 +448,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Repunit {
@@ -303,6 +463,7 @@ pub fn search(
         }
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!("repunit search complete");
     Ok(())
@@ -650,6 +811,62 @@ mod tests {
         );
     }
 
+    // ── Aurifeuillian Factors ─────────────────────────────────────────
+
+    /// R(5, 5) = 781 = 11 · 71 is the textbook Aurifeuillian split:
+    /// Φ_5(5x²) = (25x⁴ + 25x³ + 15x² + 5x + 1)(25x⁴ − 25x³ + 15x² − 5x + 1)
+    /// at x = 1.
+    #[test]
+    fn aurifeuillian_factor_of_r5_5() {
+        assert_eq!(aurifeuillian_factor(5, 5), Some(Integer::from(11u32)));
+    }
+
+    /// Bases b = p·m² with p ≡ 1 (mod 4) prime split R(b, p) into two proper
+    /// factors. R(13, 13) = 53 · 264031 · 1803647 splits as 1803647 · 13993643,
+    /// and R(17, 17) = 10949 · 1749233 · 2699538733 as 2699538733 · 19152352117.
+    #[test]
+    fn aurifeuillian_factor_divides_repunit() {
+        let expected = [(13u32, 1_803_647u64), (17, 2_699_538_733)];
+        for (base, factor) in expected {
+            let f = aurifeuillian_factor(base, base as u64).unwrap();
+            assert_eq!(f, factor, "R({}, {})", base, base);
+        }
+        // p = 5, 13, 17, 29, 37, 53 with m = 1, plus 20 = 5·2², 45 = 5·3²,
+        // 52 = 13·2² and 148 = 37·2².
+        let cases = [(5, 5), (13, 13), (17, 17), (29, 29), (37, 37), (53, 53)];
+        let scaled = [(20, 5), (45, 5), (52, 13), (148, 37)];
+        for (base, n) in cases.into_iter().chain(scaled) {
+            let r = repunit(base, n);
+            let f = aurifeuillian_factor(base, n).unwrap();
+            assert!(f > 1u32 && f < r, "R({}, {}): {} not proper", base, n, f);
+            assert!(r.is_divisible(&f), "R({}, {}): {} not a factor", base, n, f);
+        }
+    }
+
+    /// n an odd multiple of p also works: Φ_5(5³) divides R(5, 15).
+    #[test]
+    fn aurifeuillian_factor_odd_multiple_of_p() {
+        let f = aurifeuillian_factor(5, 15).unwrap();
+        assert!(repunit(5, 15).is_divisible(&f));
+        assert!(f > 1u32);
+    }
+
+    /// Forms without an Aurifeuillian split of b^n − 1: base 2 (Mersenne
+    /// numbers; its split is of 2^n + 1), squarefree parts ≡ 2, 3 (mod 4),
+    /// perfect squares, composite squarefree parts, and n not an odd
+    /// multiple of p.
+    #[test]
+    fn aurifeuillian_factor_absent() {
+        for n in [3u64, 5, 7, 11, 13, 61, 127] {
+            assert_eq!(aurifeuillian_factor(2, n), None, "base 2, n = {}", n);
+        }
+        let cases = [(10, 5), (10, 10), (3, 3), (7, 7), (4, 5), (21, 21)];
+        let wrong_n = [(5, 7), (5, 10), (13, 26)];
+        for (base, n) in cases.into_iter().chain(wrong_n) {
+            assert_eq!(aurifeuillian_factor(base, n), None, "R({}, {})", base, n);
+        }
+    }
+
     /// Soundness check: known repunit prime exponents must survive the sieve.
     ///
     /// Tests that R(10, 2), R(10, 19), and R(10, 23) — the three smallest
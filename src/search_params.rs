@@ -9,9 +9,366 @@
 //! - `routes_searches.rs` — creating search jobs in PG
 //! - `routes_jobs.rs` — search job parameter parsing
 //! - `deploy.rs` — SSH deployment command building (feature-gated)
+//!
+//! [`FORMS`] describes every form's CLI subcommand and parameters (types,
+//! bounds, defaults, OEIS references) for `GET /api/docs/forms`, so the
+//! search-creation UI doesn't hardcode them. The CLI tests check it against
+//! each subcommand's `--help`. [`validate`] checks a form's parameters against
+//! the same table before an engine starts, so a misconfigured job fails with a
+//! specific error instead of running an empty or meaningless search.
 
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
 
+/// JSON-schema-style type of a form parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    U32,
+    U64,
+}
+
+/// One parameter of a search form.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ParamSpec {
+    /// Field name in `SearchParams` JSON; the CLI flag is `--` plus the name
+    /// with `_` replaced by `-`.
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub param_type: ParamType,
+    pub required: bool,
+    /// Smallest accepted value.
+    pub min: u64,
+    /// Value used when the parameter is omitted.
+    pub default: Option<u64>,
+    pub description: &'static str,
+}
+
+impl ParamSpec {
+    const fn required(
+        name: &'static str,
+        param_type: ParamType,
+        min: u64,
+        description: &'static str,
+    ) -> Self {
+        ParamSpec {
+            name,
+            param_type,
+            required: true,
+            min,
+            default: None,
+            description,
+        }
+    }
+
+    const fn optional(
+        name: &'static str,
+        param_type: ParamType,
+        min: u64,
+        default: u64,
+        description: &'static str,
+    ) -> Self {
+        ParamSpec {
+            name,
+            param_type,
+            required: false,
+            min,
+            default: Some(default),
+            description,
+        }
+    }
+
+    /// The CLI flag for this parameter, e.g. `--min-n`.
+    pub fn flag(&self) -> String {
+        format!("--{}", self.name.replace('_', "-"))
+    }
+}
+
+/// Two parameters bounding the search range: `lower` must be below `upper`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RangeSpec {
+    pub lower: &'static str,
+    pub upper: &'static str,
+}
+
+/// Machine-readable description of one search form.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FormSpec {
+    /// `search_type` tag, as in `search_jobs.search_type`.
+    pub search_type: &'static str,
+    /// CLI subcommand name.
+    pub subcommand: &'static str,
+    pub description: &'static str,
+    pub params: &'static [ParamSpec],
+    pub range: RangeSpec,
+    /// OEIS sequence IDs, e.g. `A002981` (https://oeis.org/A002981).
+    pub oeis: &'static [&'static str],
+}
+
+const fn range(lower: &'static str, upper: &'static str) -> RangeSpec {
+    RangeSpec { lower, upper }
+}
+
+use ParamType::{U32, U64};
+
+const KBN_PARAMS: &[ParamSpec] = &[
+    ParamSpec::required("k", U64, 1, "Multiplier k"),
+    ParamSpec::required("base", U32, 2, "Base b"),
+    ParamSpec::required("min_n", U64, 1, "Minimum exponent n"),
+    ParamSpec::required("max_n", U64, 1, "Maximum exponent n"),
+];
+
+/// Every search form, in CLI order.
+pub const FORMS: &[FormSpec] = &[
+    FormSpec {
+        search_type: "factorial",
+        subcommand: "factorial",
+        description: "Factorial primes n! ± 1",
+        params: &[
+            ParamSpec::required("start", U64, 1, "Start of search range (n)"),
+            ParamSpec::required("end", U64, 1, "End of search range (n)"),
+        ],
+        range: range("start", "end"),
+        oeis: &["A002981", "A002982"],
+    },
+    FormSpec {
+        search_type: "palindromic",
+        subcommand: "palindromic",
+        description: "Palindromic primes in a given base",
+        params: &[
+            ParamSpec::optional("base", U32, 2, 10, "Number base"),
+            ParamSpec::required("min_digits", U64, 1, "Minimum digit count"),
+            ParamSpec::required("max_digits", U64, 1, "Maximum digit count"),
+        ],
+        range: range("min_digits", "max_digits"),
+        oeis: &["A002385"],
+    },
+    FormSpec {
+        search_type: "kbn",
+        subcommand: "kbn",
+        description: "Primes of the form k·b^n ± 1 (Proth, Riesel)",
+        params: KBN_PARAMS,
+        range: range("min_n", "max_n"),
+        oeis: &["A080076"],
+    },
+    FormSpec {
+        search_type: "near_repdigit",
+        subcommand: "near-repdigit",
+        description: "Near-repdigit palindromic primes (all 9s with symmetric modifications)",
+        params: &[
+            ParamSpec::required("min_digits", U64, 3, "Minimum digit count (odd only)"),
+            ParamSpec::required("max_digits", U64, 3, "Maximum digit count (odd only)"),
+        ],
+        range: range("min_digits", "max_digits"),
+        oeis: &[],
+    },
+    FormSpec {
+        search_type: "primorial",
+        subcommand: "primorial",
+        description: "Primorial primes p# ± 1",
+        params: &[
+            ParamSpec::required("start", U64, 2, "Smallest prime p to test"),
+            ParamSpec::required("end", U64, 2, "Largest prime p to test"),
+        ],
+        range: range("start", "end"),
+        oeis: &["A014545", "A057704"],
+    },
+    FormSpec {
+        search_type: "cullen_woodall",
+        subcommand: "cullen-woodall",
+        description: "Cullen primes n·2^n + 1 and Woodall primes n·2^n − 1",
+        params: &[
+            ParamSpec::required("min_n", U64, 1, "Minimum n value"),
+            ParamSpec::required("max_n", U64, 1, "Maximum n value"),
+        ],
+        range: range("min_n", "max_n"),
+        oeis: &["A005849", "A002234"],
+    },
+    FormSpec {
+        search_type: "wagstaff",
+        subcommand: "wagstaff",
+        description: "Wagstaff primes (2^p + 1)/3 for prime p",
+        params: &[
+            ParamSpec::required("min_exp", U64, 3, "Minimum prime exponent"),
+            ParamSpec::required("max_exp", U64, 3, "Maximum prime exponent"),
+        ],
+        range: range("min_exp", "max_exp"),
+        oeis: &["A000978"],
+    },
+    FormSpec {
+        search_type: "carol_kynea",
+        subcommand: "carol-kynea",
+        description: "Carol primes (2^n − 1)² − 2 and Kynea primes (2^n + 1)² − 2",
+        params: &[
+            ParamSpec::required("min_n", U64, 1, "Minimum n value"),
+            ParamSpec::required("max_n", U64, 1, "Maximum n value"),
+        ],
+        range: range("min_n", "max_n"),
+        oeis: &["A091515", "A091513"],
+    },
+    FormSpec {
+        search_type: "twin",
+        subcommand: "twin",
+        description: "Twin primes k·b^n ± 1 (both prime)",
+        params: KBN_PARAMS,
+        range: range("min_n", "max_n"),
+        oeis: &["A001097"],
+    },
+    FormSpec {
+        search_type: "sophie_germain",
+        subcommand: "sophie-germain",
+        description: "Sophie Germain primes p = k·b^n − 1 with 2p + 1 also prime",
+        params: KBN_PARAMS,
+        range: range("min_n", "max_n"),
+        oeis: &["A005384", "A005385"],
+    },
+    FormSpec {
+        search_type: "repunit",
+        subcommand: "repunit",
+        description: "Repunit primes (b^n − 1)/(b − 1) for prime n",
+        params: &[
+            ParamSpec::optional("base", U32, 2, 10, "Number base"),
+            ParamSpec::required("min_n", U64, 2, "Minimum exponent n (must be prime)"),
+            ParamSpec::required("max_n", U64, 2, "Maximum exponent n"),
+        ],
+        range: range("min_n", "max_n"),
+        oeis: &["A004023", "A085104"],
+    },
+    FormSpec {
+        search_type: "gen_fermat",
+        subcommand: "gen-fermat",
+        description: "Generalized Fermat primes b^(2^n) + 1 for even b",
+        params: &[
+            ParamSpec::required("fermat_exp", U32, 1, "Fermat exponent n"),
+            ParamSpec::required("min_base", U64, 2, "Minimum base b (must be even)"),
+            ParamSpec::required("max_base", U64, 2, "Maximum base b"),
+        ],
+        range: range("min_base", "max_base"),
+        oeis: &["A019434", "A056993"],
+    },
+    FormSpec {
+        search_type: "gaussian",
+        subcommand: "gaussian",
+        description: "Gaussian primes a + bi with fixed imaginary part b",
+        params: &[
+            ParamSpec::optional("imag", U64, 0, 1, "Imaginary part b"),
+            ParamSpec::required("min_real", U64, 0, "Minimum real part a"),
+            ParamSpec::required("max_real", U64, 0, "Maximum real part a"),
+        ],
+        range: range("min_real", "max_real"),
+        oeis: &["A002496"],
+    },
+];
+
+/// The [`FormSpec`] for a `search_type` tag.
+pub fn form_spec(search_type: &str) -> Option<&'static FormSpec> {
+    FORMS.iter().find(|f| f.search_type == search_type)
+}
+
+/// Reject parameters no search of `form` can make sense of.
+///
+/// Checks every parameter in the form's [`FormSpec`]: required ones must be
+/// present, values must be non-negative integers within the type and at least
+/// `min` (so base ≥ 2 and k ≥ 1), and the range must not be reversed. Repunits
+/// in a square base a² are rejected too: R(a², n) = R(a, n) · (a^n + 1)/(a + 1)
+/// is composite for every n > 2.
+///
+/// A range that merely contains no candidate, such as even digit counts for
+/// near-repdigits or odd bases for generalized Fermat, is accepted: a work
+/// block can land there, and the engine finishes it without testing anything.
+/// Keys the form doesn't use (`form`, `search_type`) are ignored.
+pub fn validate(form: &str, params: &serde_json::Value) -> Result<()> {
+    let Some(spec) = form_spec(form) else {
+        bail!("Unknown search type: {}", form);
+    };
+    let value = |name: &str| -> Result<Option<u64>> {
+        match &params[name] {
+            serde_json::Value::Null => Ok(None),
+            v => match v.as_u64() {
+                Some(n) => Ok(Some(n)),
+                None => bail!(
+                    "{}: {} must be a non-negative integer, got {}",
+                    form,
+                    name,
+                    v
+                ),
+            },
+        }
+    };
+    for param in spec.params {
+        let v = match (value(param.name)?, param.default) {
+            (Some(v), _) | (None, Some(v)) => v,
+            (None, None) => bail!("{}: missing required parameter {}", form, param.name),
+        };
+        if param.param_type == ParamType::U32 && v > u32::MAX as u64 {
+            bail!("{}: {} must fit in 32 bits, got {}", form, param.name, v);
+        }
+        if v < param.min {
+            bail!(
+                "{}: {} must be at least {}, got {}",
+                form,
+                param.name,
+                param.min,
+                v
+            );
+        }
+    }
+    let (lower, upper) = (spec.range.lower, spec.range.upper);
+    if let (Some(lo), Some(hi)) = (value(lower)?, value(upper)?) {
+        if lo > hi {
+            bail!(
+                "{}: {} ({}) is greater than {} ({})",
+                form,
+                lower,
+                lo,
+                upper,
+                hi
+            );
+        }
+    }
+    if form == "repunit" {
+        let base = value("base")?.unwrap_or(10);
+        let root = base.isqrt();
+        if root * root == base {
+            bail!(
+                "repunit: base {} = {}² makes every repunit with n > 2 composite",
+                base,
+                root
+            );
+        }
+    }
+    Ok(())
+}
+
+/// [`validate`] for one work block: `params` with the form's range bounds
+/// replaced by `start..=end`, the range the engine is actually given.
+pub fn validate_block(form: &str, params: &serde_json::Value, start: i64, end: i64) -> Result<()> {
+    let mut params = params.clone();
+    if let (Some(spec), Some(fields)) = (form_spec(form), params.as_object_mut()) {
+        fields.insert(spec.range.lower.to_string(), start.into());
+        fields.insert(spec.range.upper.to_string(), end.into());
+    }
+    validate(form, &params)
+}
+
+/// Minimum Miller–Rabin rounds per `search_type`, for forms whose dense
+/// base-10 structure has historically produced stubborn pseudoprimes. Forms
+/// not listed use the caller's round count as given.
+pub const MR_ROUND_FLOORS: &[(&str, u32)] = &[("palindromic", 25), ("near_repdigit", 25)];
+
+/// The [`MR_ROUND_FLOORS`] entry for a `search_type`, if any.
+pub fn mr_round_floor(search_type: &str) -> Option<u32> {
+    MR_ROUND_FLOORS
+        .iter()
+        .find(|(form, _)| *form == search_type)
+        .map(|&(_, floor)| floor)
+}
+
+/// Rounds an engine for `search_type` runs: `max(mr_rounds, floor)`.
+pub fn effective_mr_rounds(search_type: &str, mr_rounds: u32) -> u32 {
+    mr_round_floor(search_type).map_or(mr_rounds, |floor| mr_rounds.max(floor))
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(tag = "search_type")]
 pub enum SearchParams {
@@ -541,6 +898,155 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ── Form Descriptors ────────────────────────────────────────
+
+    /// Every variant has a descriptor whose parameters are exactly the flags
+    /// `to_args()` emits, and whose range bounds are among its parameters.
+    #[test]
+    fn form_specs_match_to_args() {
+        for p in all_variants() {
+            let spec = form_spec(p.search_type_name()).expect("missing FormSpec");
+            let args = p.to_args();
+            assert_eq!(args[0], spec.subcommand);
+            let flags: Vec<&str> = args[1..].iter().step_by(2).map(String::as_str).collect();
+            let spec_flags: Vec<String> = spec.params.iter().map(ParamSpec::flag).collect();
+            assert_eq!(flags, spec_flags, "{}", spec.search_type);
+        }
+        for spec in FORMS {
+            let names: Vec<&str> = spec.params.iter().map(|p| p.name).collect();
+            assert!(names.contains(&spec.range.lower), "{}", spec.search_type);
+            assert!(names.contains(&spec.range.upper), "{}", spec.search_type);
+            for param in spec.params {
+                assert_eq!(param.required, param.default.is_none(), "{}", param.name);
+            }
+        }
+    }
+
+    // ── Validation ─────────────────────────────────────────────
+
+    /// The first error `validate` reports for `params`.
+    fn rejection(form: &str, params: serde_json::Value) -> String {
+        validate(form, &params).unwrap_err().to_string()
+    }
+
+    /// Every variant's representative params are accepted, with the
+    /// `search_type` tag present as it is in `search_jobs.params`.
+    #[test]
+    fn validate_accepts_all_variants() {
+        for p in all_variants() {
+            let params = serde_json::to_value(&p).unwrap();
+            validate(p.search_type_name(), &params).unwrap();
+        }
+        let gaussian = serde_json::json!({"min_real": 0, "max_real": 100});
+        validate("gaussian", &gaussian).unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_base_below_two() {
+        let params = serde_json::json!({"k": 3, "base": 1, "min_n": 1, "max_n": 100});
+        assert_eq!(
+            rejection("kbn", params),
+            "kbn: base must be at least 2, got 1"
+        );
+        let params = serde_json::json!({"base": 0, "min_n": 2, "max_n": 100});
+        assert_eq!(
+            rejection("repunit", params),
+            "repunit: base must be at least 2, got 0"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_zero_k() {
+        let params = serde_json::json!({"k": 0, "base": 2, "min_n": 1, "max_n": 100});
+        let err = rejection("sophie_germain", params);
+        assert_eq!(err, "sophie_germain: k must be at least 1, got 0");
+    }
+
+    #[test]
+    fn validate_rejects_reversed_range() {
+        let params = serde_json::json!({"min_n": 50, "max_n": 10});
+        let err = rejection("carol_kynea", params);
+        assert_eq!(err, "carol_kynea: min_n (50) is greater than max_n (10)");
+    }
+
+    #[test]
+    fn validate_rejects_missing_required_parameter() {
+        let params = serde_json::json!({"base": 2, "min_n": 1, "max_n": 100});
+        assert_eq!(
+            rejection("twin", params),
+            "twin: missing required parameter k"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_non_integer_and_oversized_values() {
+        let params = serde_json::json!({"start": -5, "end": 10});
+        let err = rejection("factorial", params);
+        assert_eq!(
+            err,
+            "factorial: start must be a non-negative integer, got -5"
+        );
+        let params = serde_json::json!({"k": 3, "base": 1u64 << 32, "min_n": 1, "max_n": 9});
+        let err = rejection("kbn", params);
+        assert_eq!(err, "kbn: base must fit in 32 bits, got 4294967296");
+    }
+
+    /// Below-minimum range bounds: near-repdigits need at least 3 digits and
+    /// Wagstaff exponents start at 3.
+    #[test]
+    fn validate_rejects_range_below_form_minimum() {
+        let params = serde_json::json!({"min_digits": 1, "max_digits": 9});
+        let err = rejection("near_repdigit", params);
+        assert_eq!(err, "near_repdigit: min_digits must be at least 3, got 1");
+        let params = serde_json::json!({"min_exp": 2, "max_exp": 100});
+        let err = rejection("wagstaff", params);
+        assert_eq!(err, "wagstaff: min_exp must be at least 3, got 2");
+    }
+
+    /// R(4, n) = (2^n − 1)(2^n + 1)/3, so square bases are refused; the
+    /// default base 10 is not a square and base 2 (Mersenne) is fine.
+    #[test]
+    fn validate_rejects_square_repunit_base() {
+        let params = serde_json::json!({"base": 4, "min_n": 3, "max_n": 100});
+        let err = rejection("repunit", params);
+        assert_eq!(
+            err,
+            "repunit: base 4 = 2² makes every repunit with n > 2 composite"
+        );
+        validate("repunit", &serde_json::json!({"min_n": 3, "max_n": 100})).unwrap();
+        let mersenne = serde_json::json!({"base": 2, "min_n": 3, "max_n": 100});
+        validate("repunit", &mersenne).unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_unknown_form() {
+        let err = rejection("fibonacci", serde_json::json!({}));
+        assert_eq!(err, "Unknown search type: fibonacci");
+    }
+
+    /// Ranges with no candidate are valid: a block of odd bases for
+    /// generalized Fermat is completed by the engine, not failed.
+    #[test]
+    fn validate_accepts_ranges_without_candidates() {
+        let params = serde_json::json!({"fermat_exp": 2, "min_base": 3, "max_base": 3});
+        validate("gen_fermat", &params).unwrap();
+        let params = serde_json::json!({"min_digits": 4, "max_digits": 4});
+        validate("near_repdigit", &params).unwrap();
+    }
+
+    /// `validate_block` checks the block's range rather than the job's: batch
+    /// params carry no range fields, and a block can't run backwards.
+    #[test]
+    fn validate_block_substitutes_the_block_range() {
+        let params = serde_json::json!({"form": "kbn", "k": 3, "base": 2});
+        validate_block("kbn", &params, 1000, 2000).unwrap();
+        let err = validate_block("kbn", &params, 2000, 1000).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "kbn: min_n (2000) is greater than max_n (1000)"
+        );
+    }
+
     /// Validates specific block sizes: factorial=100 (1 minute per block),
     /// palindromic=2 (2 digit counts per block), kbn=10000 (exponent range).
     #[test]
@@ -580,4 +1086,17 @@ mod tests {
             );
         }
     }
+
+    /// A floored form runs at least its floor; others keep the user's count.
+    #[test]
+    fn mr_round_floor_applies_per_form() {
+        assert_eq!(mr_round_floor("palindromic"), Some(25));
+        assert_eq!(effective_mr_rounds("palindromic", 5), 25);
+        assert_eq!(effective_mr_rounds("palindromic", 40), 40);
+        assert_eq!(mr_round_floor("kbn"), None);
+        assert_eq!(effective_mr_rounds("kbn", 5), 5);
+        for (form, _) in MR_ROUND_FLOORS {
+            assert!(form_spec(form).is_some(), "unknown form {}", form);
+        }
+    }
 }
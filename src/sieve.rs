@@ -363,6 +363,27 @@ pub fn factor_u64(mut n: u64) -> Vec<(u64, u32)> {
     factors
 }
 
+/// Detect whether `n` is a perfect power c^m with m >= 2.
+///
+/// Returns `(c, m)` with the largest such m, so `c` is itself not a perfect
+/// power. Returns `None` for n < 4 and for non-powers.
+pub fn is_perfect_power(n: u64) -> Option<(u64, u32)> {
+    if n < 4 {
+        return None;
+    }
+    // Largest exponent first: the first hit has a primitive root
+    for m in (2..=(63 - n.leading_zeros())).rev() {
+        let approx = (n as f64).powf(1.0 / m as f64).round() as u64;
+        // Float roots can be off by one near u64 precision limits
+        for c in approx.saturating_sub(1).max(2)..=approx + 1 {
+            if c.checked_pow(m) == Some(n) {
+                return Some((c, m));
+            }
+        }
+    }
+    None
+}
+
 /// Multiplicative order of `base` modulo `p`: smallest d > 0 with base^d ≡ 1 (mod p).
 /// Uses Montgomery multiplication internally for odd primes.
 /// Requires p prime and base not divisible by p.
@@ -538,6 +559,47 @@ impl BitSieve {
         self.words.iter().map(|w| w.count_ones() as usize).sum()
     }
 
+    /// Clear every bit that is clear in `other` (bitwise AND), layering an
+    /// extra sieve pass onto an existing survivor set.
+    ///
+    /// # Panics
+    /// Panics if the lengths differ.
+    pub fn intersect(&mut self, other: &BitSieve) {
+        assert_eq!(self.len, other.len, "BitSieve length mismatch");
+        for (w, o) in self.words.iter_mut().zip(&other.words) {
+            *w &= o;
+        }
+    }
+
+    /// Base64 of the little-endian word bytes, for checkpointing.
+    pub fn to_base64(&self) -> String {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine as _;
+        let bytes: Vec<u8> = self.words.iter().flat_map(|w| w.to_le_bytes()).collect();
+        STANDARD.encode(bytes)
+    }
+
+    /// Inverse of [`to_base64`](Self::to_base64). Returns None if `encoded`
+    /// is not valid base64 of exactly `len` bits.
+    pub fn from_base64(encoded: &str, len: usize) -> Option<Self> {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine as _;
+        let bytes = STANDARD.decode(encoded).ok()?;
+        let num_words = len.div_ceil(64);
+        if bytes.len() != num_words * 8 {
+            return None;
+        }
+        let words: Vec<u64> = bytes
+            .chunks_exact(8)
+            .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        let extra = num_words * 64 - len;
+        if extra > 0 && words[num_words - 1] >> (64 - extra) != 0 {
+            return None;
+        }
+        Some(BitSieve { words, len })
+    }
+
     /// Iterate over the indices of all set bits in ascending order.
     pub fn iter_set_bits(&self) -> impl Iterator<Item = usize> + '_ {
         self.words.iter().enumerate().flat_map(|(wi, &word)| {
@@ -656,6 +718,27 @@ mod tests {
         assert_eq!(factor_u64(97), vec![(97, 1)]); // prime
     }
 
+    // ── Perfect Power Detection (is_perfect_power) ─────────────────────
+
+    /// Perfect powers report the primitive root and the largest exponent:
+    /// 16 = 2^4 (not 4^2), 64 = 2^6, 36 = 6^2. Non-powers and values below
+    /// 4 return None.
+    #[test]
+    fn test_is_perfect_power() {
+        assert_eq!(is_perfect_power(16), Some((2, 4)));
+        assert_eq!(is_perfect_power(64), Some((2, 6)));
+        assert_eq!(is_perfect_power(36), Some((6, 2)));
+        assert_eq!(is_perfect_power(1000), Some((10, 3)));
+        assert_eq!(is_perfect_power(1 << 63), Some((2, 63)));
+        assert_eq!(
+            is_perfect_power(u32::MAX as u64 * u32::MAX as u64),
+            Some((u32::MAX as u64, 2))
+        );
+        for n in [0, 1, 2, 3, 6, 12, 18, 100_003, u64::MAX] {
+            assert_eq!(is_perfect_power(n), None, "{} is not a perfect power", n);
+        }
+    }
+
     // ── Multiplicative Order ───────────────────────────────────────────
 
     /// Verifies the multiplicative order ord_m(a) = min{k > 0 : a^k = 1 (mod m)}.
@@ -1201,4 +1284,36 @@ mod tests {
             "count_ones and iter_set_bits should agree"
         );
     }
+
+    /// `to_base64`/`from_base64` round-trip a non-word-aligned bitmap, and
+    /// decoding rejects a length mismatch or stray padding bits.
+    #[test]
+    fn bitsieve_base64_roundtrip() {
+        let mut bs = BitSieve::new_all_set(100);
+        for i in (0..100).step_by(3) {
+            bs.clear(i);
+        }
+        let encoded = bs.to_base64();
+        let decoded = BitSieve::from_base64(&encoded, 100).unwrap();
+        assert_eq!(decoded.words, bs.words);
+        assert_eq!(decoded.len(), 100);
+        assert!(BitSieve::from_base64(&encoded, 200).is_none());
+        assert!(BitSieve::from_base64(&encoded, 70).is_none());
+        assert!(BitSieve::from_base64("not base64!", 100).is_none());
+    }
+
+    /// `intersect` keeps exactly the bits set in both sieves.
+    #[test]
+    fn bitsieve_intersect_is_and() {
+        let mut a = BitSieve::new_all_set(130);
+        let mut b = BitSieve::new_all_set(130);
+        a.clear(0);
+        a.clear(64);
+        b.clear(64);
+        b.clear(129);
+        a.intersect(&b);
+        let cleared: Vec<usize> = (0..130).filter(|&i| !a.get(i)).collect();
+        assert_eq!(cleared, vec![0, 64, 129]);
+        assert_eq!(a.count_ones(), 127);
+    }
 }
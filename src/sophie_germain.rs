@@ -2,7 +2,8 @@
 //!
 //! Searches for Sophie Germain primes: primes p such that 2p + 1 (the "safe prime")
 //! is also prime. In the k·b^n form: if p = k·b^n − 1, then 2p + 1 = 2k·b^n − 1.
-//! Both are of the Riesel form and LLR-testable when base = 2.
+//! Both are of the Riesel form; p is LLR-testable when base = 2 and k is odd,
+//! while 2p+1 (even multiplier 2k) is proven through its N+1 factorization.
 //!
 //! ## Algorithm
 //!
@@ -15,8 +16,11 @@
 //!    for 2k. An n-value is tested only if both survive. This eliminates >99%
 //!    of candidates.
 //!
-//! 3. **LLR deterministic proofs**: Both p and 2p+1 are k·2^n − 1 forms, so
-//!    LLR provides deterministic certificates for both (when base = 2 and k is odd).
+//! 3. **Deterministic proofs**: p = k·2^n − 1 gets an LLR certificate (when
+//!    base = 2 and k is odd). The safe prime 2k·b^n − 1 has an even multiplier,
+//!    so LLR does not apply; instead its N+1 = 2k·b^n is fully factored from
+//!    the prime factors of 2k and b, and `test_safe_prime` proves it with
+//!    Morrison N+1. If Morrison finds no witnesses it stays probabilistic.
 //!
 //! ## Relationship to Twin Primes
 //!
@@ -48,25 +52,64 @@ use std::time::Instant;
 
 use tracing::info;
 
+use crate::candidate::{CandidateForm, SophieGermain};
+use crate::certificate::PrimalityCertificate;
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::kbn;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::CoordinationClient;
-use crate::{exact_digits, sieve};
+use crate::{exact_digits, proof, sieve};
+
+/// Test the safe prime 2p+1 = k2·b^n − 1 (k2 = 2k).
+///
+/// k2 is even, so `kbn::test_prime` would skip LLR and label the result by
+/// Miller-Rabin alone. Survivors of the MR screen get a Morrison N+1 proof
+/// over the factors of k2 and b; without a proof the result is probabilistic.
+fn test_safe_prime(
+    safe: &Integer,
+    k2: u64,
+    base: u32,
+    mr_rounds: u32,
+) -> (IsPrime, &'static str, Option<PrimalityCertificate>) {
+    let r = crate::mr_screened_test(safe, mr_rounds);
+    if r == IsPrime::No {
+        return (IsPrime::No, "", None);
+    }
+    match proof::morrison_auto(safe, &kbn::kb_prime_factors(k2, base)) {
+        Some(cert) => (IsPrime::Yes, "deterministic (Morrison N+1)", Some(cert)),
+        None => (r, crate::certainty_label(safe, r), None),
+    }
+}
+
+/// Certificate stored for a Sophie Germain pair: a `SophieGermain` pair when
+/// both members are proven, otherwise whichever single proof is available
+/// (p first).
+fn pair_certificate(
+    prime: Option<PrimalityCertificate>,
+    safe: Option<PrimalityCertificate>,
+) -> Option<PrimalityCertificate> {
+    match (prime, safe) {
+        (Some(prime), Some(safe)) => Some(PrimalityCertificate::SophieGermain {
+            prime: Box::new(prime),
+            safe: Box::new(safe),
+        }),
+        (prime, safe) => prime.or(safe),
+    }
+}
 
 /// Search for Sophie Germain primes: p = k*b^n - 1 where both p and 2p+1 are prime.
 ///
-/// 2p+1 = 2*k*b^n - 1, which is also a Riesel form with doubled k.
-/// Both forms are LLR-testable when base=2.
+/// 2p+1 = 2*k*b^n - 1, which is also a Riesel form with doubled k. p is
+/// LLR-testable when base=2; 2p+1 (even multiplier) is proven by Morrison N+1.
 pub fn search(
     k: u64,
     base: u32,
     min_n: u64,
     max_n: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -75,6 +118,9 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params = serde_json::json!({"k": k, "base": base, "min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("sophie_germain", &params)?;
+
     let k2 = k.checked_mul(2).expect("2*k overflows u64");
 
     // Resolve sieve_limit: auto-tune if 0
@@ -111,13 +157,36 @@ pub fn search(
 
     // Sieve for p = k*b^n - 1
     info!(k, base, from = resume_from, to = max_n, "running sieve for p=k*b^n-1");
-    let (_plus_surv_k, minus_surv_k) =
-        kbn::bsgs_sieve(resume_from, max_n, k, base, &sieve_primes, sieve_min_n);
+    let (_plus_surv_k, minus_surv_k, interrupted) = kbn::bsgs_sieve(
+        resume_from,
+        max_n,
+        k,
+        base,
+        &sieve_primes,
+        sieve_min_n,
+        worker_client,
+    );
+    if interrupted {
+        // Nothing was tested, so any checkpoint on disk is still the resume point.
+        info!("stop requested by coordinator during sieve");
+        return Ok(());
+    }
 
     // Sieve for 2p+1 = 2k*b^n - 1
     info!(k = k2, base, from = resume_from, to = max_n, "running sieve for 2p+1=2k*b^n-1");
-    let (_plus_surv_k2, minus_surv_k2) =
-        kbn::bsgs_sieve(resume_from, max_n, k2, base, &sieve_primes, sieve_min_n);
+    let (_plus_surv_k2, minus_surv_k2, interrupted) = kbn::bsgs_sieve(
+        resume_from,
+        max_n,
+        k2,
+        base,
+        &sieve_primes,
+        sieve_min_n,
+        worker_client,
+    );
+    if interrupted {
+        info!("stop requested by coordinator during sieve");
+        return Ok(());
+    }
 
     let total_range = max_n - resume_from + 1;
     let sg_survivors: u64 = (0..minus_surv_k.len())
@@ -131,6 +200,7 @@ pub fn search(
     );
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut block_start = resume_from;
     let mut total_sieved: u64 = 0;
 
@@ -151,6 +221,7 @@ pub fn search(
             .collect();
 
         total_sieved += block_len - survivors.len() as u64;
+        progress.record_survivors(survivors.iter().copied());
 
         let base_pow_start = Integer::from(base).pow(crate::checked_u32(block_start));
         let k_int = Integer::from(k);
@@ -159,6 +230,7 @@ pub fn search(
         let found: Vec<_> = survivors
             .into_par_iter()
             .filter_map(|n| {
+                let _busy = crate::progress::busy();
                 let offset = n - block_start;
                 let base_pow = if offset == 0 {
                     base_pow_start.clone()
@@ -171,8 +243,9 @@ pub fn search(
                 if p <= 0u32 {
                     return None;
                 }
+                let _testing = progress.testing(&SophieGermain { k, base }.expression(n), &p);
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&p) {
+                if crate::p1::adaptive_p1_filter_for_form(&p, "sophie_germain") {
                     return None;
                 }
                 let (r_p, cert_p, certificate_p) =
@@ -184,23 +257,25 @@ pub fn search(
                 // p is (probably) prime, now test 2p+1 = 2k*b^n - 1
                 let safe = Integer::from(&k2_int * &base_pow) - 1u32;
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&safe) {
+                if crate::p1::adaptive_p1_filter_for_form(&safe, "sophie_germain") {
                     return None;
                 }
-                let (r_safe, cert_safe, _certificate_safe) =
-                    kbn::test_prime(&safe, k2, base, n, false, mr_rounds);
+                let (r_safe, cert_safe, certificate_safe) =
+                    test_safe_prime(&safe, k2, base, mr_rounds);
                 if r_safe == IsPrime::No {
                     return None;
                 }
 
                 // Sophie Germain pair found!
                 let digits = exact_digits(&p);
-                let certainty = match (cert_p, cert_safe) {
-                    ("deterministic", "deterministic") => "deterministic",
-                    _ => "probabilistic",
+                let certainty = if cert_p.starts_with("deterministic")
+                    && cert_safe.starts_with("deterministic")
+                {
+                    "deterministic"
+                } else {
+                    "probabilistic"
                 };
-                // Use the certificate from p (the Sophie Germain prime itself)
-                let cert_json = certificate_p
+                let cert_json = pair_certificate(certificate_p, certificate_safe)
                     .as_ref()
                     .and_then(|c| serde_json::to_string(c).ok());
                 Some((n, digits, certainty.to_string(), cert_json))
@@ -210,7 +285,20 @@ pub fn search(
         progress.tested.fetch_add(block_len, Ordering::Relaxed);
 
         for (n, digits, certainty, cert_json) in found {
-            let expr = format!("{}*{}^{}-1", k, base, n);
+            let expr = SophieGermain { k, base }.expression(n);
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "sophie_germain",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -218,8 +306,12 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                eb.check_milestone("sophie_germain", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "sophie_germain").ok().flatten()
+                });
             } else {
                 info!(
                     expression = %expr,
@@ -228,8 +320,7 @@ pub fn search(
                     "Sophie Germain prime found"
                 );
             }
-            db.insert_prime_sync(
-                rt,
+            buffer.push(
                 "sophie_germain",
                 &expr,
                 digits,
@@ -237,12 +328,12 @@ pub fn search(
                 &certainty,
                 cert_json.as_deref(),
             )?;
-            if let Some(wc) = worker_client {
-                wc.report_prime("sophie_germain", &expr, digits, search_params, &certainty);
-            }
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::SophieGermain {
@@ -258,6 +349,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::SophieGermain {
@@ -275,6 +367,7 @@ pub fn search(
         block_start = block_end + 1;
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!(total_sieved, "Sophie Germain search complete");
     Ok(())
@@ -451,8 +544,10 @@ mod tests {
         let base = 2u32;
         let sieve_min_n = 14u64;
 
-        let (_plus_k, minus_k) = kbn::bsgs_sieve(1, 200, k, base, &sieve_primes, sieve_min_n);
-        let (_plus_k2, minus_k2) = kbn::bsgs_sieve(1, 200, k2, base, &sieve_primes, sieve_min_n);
+        let (_plus_k, minus_k, _) =
+            kbn::bsgs_sieve(1, 200, k, base, &sieve_primes, sieve_min_n, None);
+        let (_plus_k2, minus_k2, _) =
+            kbn::bsgs_sieve(1, 200, k2, base, &sieve_primes, sieve_min_n, None);
 
         // Verify sieve correctness: if sieved out, must be composite
         for n in sieve_min_n..=200 {
@@ -594,8 +689,8 @@ mod tests {
         let sieve_primes = sieve::generate_primes(10_000);
         let sieve_min_n = 14u64;
 
-        let (_p_k, minus_k) = kbn::bsgs_sieve(1, 100, 3, 2, &sieve_primes, sieve_min_n);
-        let (_p_k2, minus_k2) = kbn::bsgs_sieve(1, 100, 6, 2, &sieve_primes, sieve_min_n);
+        let (_p_k, minus_k, _) = kbn::bsgs_sieve(1, 100, 3, 2, &sieve_primes, sieve_min_n, None);
+        let (_p_k2, minus_k2, _) = kbn::bsgs_sieve(1, 100, 6, 2, &sieve_primes, sieve_min_n, None);
 
         let k_survivors = minus_k.count_ones();
         let k2_survivors = minus_k2.count_ones();
@@ -632,4 +727,71 @@ mod tests {
         assert_eq!(r_safe, IsPrime::Yes, "47 should be prime");
         assert_eq!(cert_safe, "deterministic");
     }
+
+    // ── Safe Prime Proofs ──────────────────────────────────────────────
+
+    /// The safe prime 6*2^63 - 1 (66 bits, above the BPSW bound) has an even
+    /// multiplier, so `kbn::test_prime` can only report it probabilistic. The
+    /// SG engine's `test_safe_prime` proves it via Morrison over N+1 = 3*2^64.
+    #[test]
+    fn safe_prime_proven_by_morrison_not_llr() {
+        let safe = kb_minus(6, 2, 63);
+        assert!(safe.significant_bits() > 64);
+
+        let (r_kbn, cert_kbn, _) = kbn::test_prime(&safe, 6, 2, 63, false, 25);
+        assert_eq!(r_kbn, IsPrime::Probably);
+        assert_eq!(
+            cert_kbn, "probabilistic",
+            "even k must not get an LLR label"
+        );
+
+        let (r, cert, certificate) = test_safe_prime(&safe, 6, 2, 25);
+        assert_eq!(r, IsPrime::Yes);
+        assert_eq!(cert, "deterministic (Morrison N+1)");
+        assert!(matches!(
+            certificate,
+            Some(PrimalityCertificate::Morrison { .. })
+        ));
+    }
+
+    /// Morrison also covers non-binary bases: N+1 = 4*3^n factors over {2, 3}.
+    /// Composite safe candidates are rejected without a label.
+    #[test]
+    fn safe_prime_morrison_base3_and_composites() {
+        assert_eq!(
+            kbn::kb_prime_factors(4, 3),
+            vec![Integer::from(2u32), Integer::from(3u32)]
+        );
+        let (r, cert, _) = test_safe_prime(&kb_minus(4, 3, 1), 4, 3, 25);
+        assert_eq!(r, IsPrime::Yes, "4*3-1 = 11 is prime");
+        assert_eq!(cert, "deterministic (Morrison N+1)");
+
+        let (r, cert, certificate) = test_safe_prime(&kb_minus(6, 2, 4), 6, 2, 25);
+        assert_eq!(r, IsPrime::No, "6*16-1 = 95 is composite");
+        assert_eq!(cert, "");
+        assert!(certificate.is_none());
+    }
+
+    /// Both members of (23, 47) are proven, so the stored certificate nests
+    /// p's LLR proof next to the safe prime's Morrison N+1 proof.
+    #[test]
+    fn pair_certificate_keeps_safe_prime_proof() {
+        let (_, _, certificate_p) = kbn::test_prime(&kb_minus(3, 2, 3), 3, 2, 3, false, 25);
+        let (_, _, certificate_safe) = test_safe_prime(&kb_minus(6, 2, 3), 6, 2, 25);
+        let cert = pair_certificate(certificate_p, certificate_safe).unwrap();
+        let PrimalityCertificate::SophieGermain { prime, safe } = cert else {
+            panic!("expected a SophieGermain certificate, got {:?}", cert);
+        };
+        assert!(matches!(*prime, PrimalityCertificate::Llr { .. }));
+        assert!(matches!(*safe, PrimalityCertificate::Morrison { .. }));
+
+        let safe_only = PrimalityCertificate::Morrison {
+            p_value: 3,
+            factors: vec![],
+        };
+        assert_eq!(
+            pair_certificate(None, Some(safe_only.clone())),
+            Some(safe_only)
+        );
+    }
 }
@@ -15,6 +15,9 @@
 //! 4. **Execute**: Logs decisions and executes them (project creation, job pausing, etc.).
 //! 5. **Monitor**: Checks for stalled jobs, high failure rates, and near-record discoveries.
 //!
+//! Before surveying, a tick also adapts each running job's work-block size to
+//! its recent block failures and completion times ([`adapt_block_sizes`]).
+//!
 //! ## Scoring Model
 //!
 //! | Component      | Weight | Source                                        |
@@ -603,6 +606,7 @@ pub fn build_auto_project_config(
         competitive: None,
         strategy: crate::project::StrategyConfig {
             auto_strategy: true,
+            prove_phase: false,
             phases: vec![],
         },
         infrastructure: None,
@@ -670,6 +674,8 @@ pub async fn strategy_tick(db: &Database) -> Result<TickResult> {
         });
     }
 
+    adapt_block_sizes(db).await;
+
     let survey_data = survey(db).await?;
     let scores = score_forms(&survey_data, &config);
     let monthly_spend = db.get_monthly_strategy_spend().await.unwrap_or(0.0);
@@ -689,6 +695,25 @@ pub async fn strategy_tick(db: &Database) -> Result<TickResult> {
     })
 }
 
+/// Resize running jobs' work blocks from their recent outcomes; see
+/// [`Database::adapt_block_sizes`]. Failures are logged, not returned, so a
+/// tick never stops on them.
+pub async fn adapt_block_sizes(db: &Database) {
+    match db.adapt_block_sizes().await {
+        Ok(changes) => {
+            for c in changes {
+                info!(
+                    job_id = c.job_id,
+                    previous = c.previous,
+                    multiplier = c.multiplier,
+                    "Strategy: adapted block size"
+                );
+            }
+        }
+        Err(e) => warn!(error = %e, "Strategy: failed to adapt block sizes"),
+    }
+}
+
 /// Force an immediate strategy tick (for the manual trigger API endpoint).
 pub async fn force_tick(db: &Database) -> Result<TickResult> {
     strategy_tick(db).await
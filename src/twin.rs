@@ -15,9 +15,12 @@
 //!    k·b^n − 1 (LLR or Miller–Rabin). This avoids the expensive −1 test
 //!    in >50% of cases.
 //!
-//! 3. **Deterministic proofs**: When both forms get deterministic proofs
-//!    (Proth for +1, LLR for −1), the twin pair is certified deterministic.
-//!    Otherwise it is probabilistic.
+//! 3. **Deterministic proofs**: N−1 of the +1 member and N+1 of the −1
+//!    member are both k·b^n, completely factored by the primes of k and b.
+//!    A member without a Proth or LLR certificate from the screening test is
+//!    proven directly: Pocklington N−1 for +1, Morrison N+1 for −1. With both
+//!    sides proven the pair is certified deterministic under a combined
+//!    `Twin` certificate; otherwise it is probabilistic.
 //!
 //! ## Complexity
 //!
@@ -44,13 +47,64 @@ use std::time::Instant;
 
 use tracing::info;
 
+use crate::candidate::{CandidateForm, Twin};
+use crate::certificate::PrimalityCertificate;
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::kbn;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::CoordinationClient;
-use crate::{exact_digits, sieve};
+use crate::{exact_digits, proof, sieve};
+
+/// Pocklington N−1 proof of k·b^n + 1 from the prime factors of k·b^n.
+fn prove_plus(plus: &Integer, factors: &[Integer]) -> Option<PrimalityCertificate> {
+    // N−1 is stripped completely by the known factors, so the factoring
+    // attempts never run.
+    proof::pocklington_auto(plus, factors)
+}
+
+/// Morrison N+1 proof of k·b^n − 1 from the prime factors of k·b^n.
+fn prove_minus(minus: &Integer, factors: &[Integer]) -> Option<PrimalityCertificate> {
+    proof::morrison_auto(minus, factors)
+}
+
+/// A member's deterministic proof, or its screening certificate when unproven.
+type SideCertificate = std::result::Result<PrimalityCertificate, Option<PrimalityCertificate>>;
+
+/// Deterministic certificate for one member of a pair that passed
+/// `kbn::test_prime`: its own certificate when the test was deterministic,
+/// otherwise the result of `prove`. On failure, returns the test's
+/// (probabilistic) certificate as the error.
+fn side_certificate(
+    method: &str,
+    certificate: Option<PrimalityCertificate>,
+    prove: impl FnOnce() -> Option<PrimalityCertificate>,
+) -> SideCertificate {
+    match certificate {
+        Some(cert) if method == "deterministic" => Ok(cert),
+        certificate => prove().ok_or(certificate),
+    }
+}
+
+/// Deterministic certificate for the twin pair k·b^n ± 1 built only from
+/// the structure of k·b^n, without Proth, LLR or external tools.
+///
+/// Returns a `Twin` certificate with a Pocklington proof of the +1 member and
+/// a Morrison proof of the −1 member, or `None` if either member is composite
+/// or has no proof.
+pub fn twin_certificate(k: u64, base: u32, n: u64) -> Option<PrimalityCertificate> {
+    let kb = Integer::from(k) * Integer::from(base).pow(crate::checked_u32(n));
+    let plus = Integer::from(&kb + 1u32);
+    let minus = Integer::from(&kb - 1u32);
+    let factors = kbn::kb_prime_factors(k, base);
+    let plus_cert = prove_plus(&plus, &factors)?;
+    let minus_cert = prove_minus(&minus, &factors)?;
+    Some(PrimalityCertificate::Twin {
+        plus: Box::new(plus_cert),
+        minus: Box::new(minus_cert),
+    })
+}
 
 pub fn search(
     k: u64,
@@ -58,7 +112,7 @@ pub fn search(
     min_n: u64,
     max_n: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -67,6 +121,9 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params = serde_json::json!({"k": k, "base": base, "min_n": min_n, "max_n": max_n});
+    crate::search_params::validate("twin", &params)?;
+
     // Resolve sieve_limit: auto-tune if 0
     let candidate_bits = (max_n as f64 * (base as f64).log2() + (k as f64).log2().max(0.0)) as u64;
     let n_range = max_n.saturating_sub(min_n) + 1;
@@ -104,8 +161,20 @@ pub fn search(
         candidates = max_n - resume_from + 1,
         "running twin sieve"
     );
-    let (plus_survives, minus_survives) =
-        kbn::bsgs_sieve(resume_from, max_n, k, base, &sieve_primes, sieve_min_n);
+    let (plus_survives, minus_survives, interrupted) = kbn::bsgs_sieve(
+        resume_from,
+        max_n,
+        k,
+        base,
+        &sieve_primes,
+        sieve_min_n,
+        worker_client,
+    );
+    if interrupted {
+        // Nothing was tested, so any checkpoint on disk is still the resume point.
+        info!("stop requested by coordinator during sieve");
+        return Ok(());
+    }
 
     let total_range = max_n - resume_from + 1;
     let twin_survivors: u64 = (0..plus_survives.len())
@@ -119,6 +188,7 @@ pub fn search(
     );
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut block_start = resume_from;
     let mut total_sieved: u64 = 0;
 
@@ -139,6 +209,7 @@ pub fn search(
             .collect();
 
         total_sieved += block_len - survivors.len() as u64;
+        progress.record_survivors(survivors.iter().copied());
 
         let base_pow_start = Integer::from(base).pow(crate::checked_u32(block_start));
         let k_int = Integer::from(k);
@@ -146,6 +217,7 @@ pub fn search(
         let found_twins: Vec<_> = survivors
             .into_par_iter()
             .filter_map(|n| {
+                let _busy = crate::progress::busy();
                 let offset = n - block_start;
                 let base_pow = if offset == 0 {
                     base_pow_start.clone()
@@ -156,8 +228,9 @@ pub fn search(
 
                 // Test +1 first (Proth is fast for composites)
                 let plus = Integer::from(&kb + 1u32);
+                let _testing = progress.testing(&Twin { k, base }.expression(n), &plus);
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&plus) {
+                if crate::p1::adaptive_p1_filter_for_form(&plus, "twin") {
                     return None;
                 }
                 let (r_plus, cert_plus, certificate_plus) =
@@ -172,7 +245,7 @@ pub fn search(
                     return None;
                 }
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&minus) {
+                if crate::p1::adaptive_p1_filter_for_form(&minus, "twin") {
                     return None;
                 }
                 let (r_minus, cert_minus, certificate_minus) =
@@ -183,12 +256,26 @@ pub fn search(
 
                 // Both are prime — twin pair found!
                 let digits = exact_digits(&plus);
-                let certainty = match (cert_plus, cert_minus) {
-                    ("deterministic", "deterministic") => "deterministic",
-                    _ => "probabilistic",
+                let factors = kbn::kb_prime_factors(k, base);
+                let proven_plus =
+                    side_certificate(cert_plus, certificate_plus, || prove_plus(&plus, &factors));
+                let proven_minus = side_certificate(cert_minus, certificate_minus, || {
+                    prove_minus(&minus, &factors)
+                });
+                let (certainty, certificate) = match (proven_plus, proven_minus) {
+                    (Ok(plus), Ok(minus)) => {
+                        let twin = PrimalityCertificate::Twin {
+                            plus: Box::new(plus),
+                            minus: Box::new(minus),
+                        };
+                        ("deterministic", Some(twin))
+                    }
+                    // Prefer the +1 certificate, fall back to -1
+                    (plus, minus) => {
+                        let cert = |side: SideCertificate| side.map_or_else(|c| c, Some);
+                        ("probabilistic", cert(plus).or(cert(minus)))
+                    }
                 };
-                // Prefer the +1 certificate (Proth), fall back to -1 (LLR)
-                let certificate = certificate_plus.or(certificate_minus);
                 let cert_json = certificate
                     .as_ref()
                     .and_then(|c| serde_json::to_string(c).ok());
@@ -199,7 +286,20 @@ pub fn search(
         progress.tested.fetch_add(block_len, Ordering::Relaxed);
 
         for (n, digits, certainty, cert_json) in found_twins {
-            let expr = format!("{}*{}^{} +/- 1", k, base, n);
+            let expr = Twin { k, base }.expression(n);
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "twin",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -207,8 +307,12 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                eb.check_milestone("twin", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "twin").ok().flatten()
+                });
             } else {
                 info!(
                     expression = %expr,
@@ -217,8 +321,7 @@ pub fn search(
                     "twin prime pair found"
                 );
             }
-            db.insert_prime_sync(
-                rt,
+            buffer.push(
                 "twin",
                 &expr,
                 digits,
@@ -226,12 +329,12 @@ pub fn search(
                 &certainty,
                 cert_json.as_deref(),
             )?;
-            if let Some(wc) = worker_client {
-                wc.report_prime("twin", &expr, digits, search_params, &certainty);
-            }
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Twin {
@@ -247,6 +350,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Twin {
@@ -264,6 +368,7 @@ pub fn search(
         block_start = block_end + 1;
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!(total_sieved, "twin prime search complete");
     Ok(())
@@ -296,7 +401,8 @@ mod tests {
     //!
     //! 1. **Known twin pairs**: Verify both sides are prime for known k*b^n values.
     //! 2. **Non-twin cases**: Test where exactly one side is composite.
-    //! 3. **Deterministic proofs**: Verify Proth (+1) and LLR (-1) certificates.
+    //! 3. **Deterministic proofs**: Verify Proth (+1) and LLR (-1) certificates,
+    //!    and the structural Pocklington/Morrison `Twin` certificate.
     //! 4. **Sieve intersection**: Verify the dual sieve correctly intersects.
     //! 5. **Edge cases**: Zero minus values, large k, digit count consistency.
 
@@ -431,7 +537,8 @@ mod tests {
         let base = 2u32;
         let sieve_min_n = 14u64;
 
-        let (plus_surv, minus_surv) = kbn::bsgs_sieve(1, 200, k, base, &sieve_primes, sieve_min_n);
+        let (plus_surv, minus_surv, _) =
+            kbn::bsgs_sieve(1, 200, k, base, &sieve_primes, sieve_min_n, None);
 
         // Verify: when BOTH survive, at least check that the sieve was correct
         for n in sieve_min_n..=200 {
@@ -473,6 +580,98 @@ mod tests {
         assert!(twin_count <= minus_count);
     }
 
+    /// Factor strings of the Pocklington (+1) and Morrison (−1) halves of a
+    /// `Twin` certificate.
+    fn twin_factor_lists(cert: &PrimalityCertificate) -> (Vec<String>, Vec<String>) {
+        let PrimalityCertificate::Twin { plus, minus } = cert else {
+            panic!("expected a Twin certificate, got {:?}", cert);
+        };
+        let PrimalityCertificate::Pocklington { factors: plus } = plus.as_ref() else {
+            panic!("expected Pocklington for +1, got {:?}", plus);
+        };
+        let PrimalityCertificate::Morrison { factors: minus, .. } = minus.as_ref() else {
+            panic!("expected Morrison for -1, got {:?}", minus);
+        };
+        (
+            plus.iter().map(|w| w.factor.clone()).collect(),
+            minus.iter().map(|w| w.factor.clone()).collect(),
+        )
+    }
+
+    /// Both members of a twin pair are proven from the factorization of k·b^n
+    /// alone: the factor lists are exactly the primes of k and b.
+    ///
+    ///   - 3·2^18 ± 1 = (786431, 786433): factors {2, 3}
+    ///   - 51·10^26 ± 1 (27 digits): factors {2, 3, 5, 17}
+    ///
+    /// Each Pocklington witness is re-checked: a^(N−1) ≡ 1 and
+    /// gcd(a^((N−1)/q) − 1, N) = 1.
+    #[test]
+    fn twin_certificate_from_structure() {
+        let cases: [(u64, u32, u64, &[&str]); 2] = [
+            (3, 2, 18, &["2", "3"]),
+            (51, 10, 26, &["2", "3", "5", "17"]),
+        ];
+        for (k, base, n, expected) in cases {
+            let cert = twin_certificate(k, base, n).expect("twin pair should be proven");
+            let (plus_factors, minus_factors) = twin_factor_lists(&cert);
+            let kb = format!("{}*{}^{}", k, base, n);
+            assert_eq!(plus_factors, expected, "N-1 factors of {}+1", kb);
+            assert_eq!(minus_factors, expected, "N+1 factors of {}-1", kb);
+
+            let PrimalityCertificate::Twin { plus, .. } = &cert else {
+                unreachable!()
+            };
+            let PrimalityCertificate::Pocklington { factors } = plus.as_ref() else {
+                unreachable!()
+            };
+            let p = kb_plus(k, base, n);
+            let pm1 = Integer::from(&p - 1u32);
+            for w in factors {
+                let a = Integer::from(w.base);
+                let q: Integer = w.factor.parse().unwrap();
+                assert_eq!(a.clone().pow_mod(&pm1, &p).unwrap(), 1u32);
+                let r = a.pow_mod(&Integer::from(&pm1 / &q), &p).unwrap();
+                assert_eq!((r - 1u32).gcd(&p), 1u32, "witness for q={}", q);
+            }
+        }
+    }
+
+    /// Pairs with a composite member get no twin certificate.
+    #[test]
+    fn twin_certificate_rejects_non_twins() {
+        // 3·2^3 + 1 = 25 and 3·2^5 − 1 = 95 are composite.
+        assert!(twin_certificate(3, 2, 3).is_none());
+        assert!(twin_certificate(3, 2, 5).is_none());
+    }
+
+    /// A Proth certificate from screening is kept as is, while a member that
+    /// screening could only call probable (base 10 has no LLR) is upgraded to
+    /// a Morrison proof.
+    #[test]
+    fn side_certificate_upgrades_probabilistic_members() {
+        let factors = kbn::kb_prime_factors(51, 10);
+        let minus = kb_minus(51, 10, 26);
+        let (r, method, screened) = kbn::test_prime(&minus, 51, 10, 26, false, 25);
+        assert_eq!(r, IsPrime::Probably);
+        assert_eq!(method, "probabilistic");
+        let proven = side_certificate(method, screened, || prove_minus(&minus, &factors));
+        assert!(matches!(proven, Ok(PrimalityCertificate::Morrison { .. })));
+
+        let plus = kb_plus(3, 2, 6);
+        let (_, method, screened) = kbn::test_prime(&plus, 3, 2, 6, true, 25);
+        let proven = side_certificate(method, screened, || panic!("Proth suffices"));
+        assert_eq!(proven, Ok(PrimalityCertificate::Proth { base: 5 }));
+
+        // A composite stays unproven and keeps the screening certificate.
+        let mr = Some(PrimalityCertificate::MillerRabin { rounds: 25 });
+        let composite = kb_minus(3, 2, 5);
+        let proven = side_certificate("probabilistic", mr.clone(), || {
+            prove_minus(&composite, &kbn::kb_prime_factors(3, 2))
+        });
+        assert_eq!(proven, Err(mr));
+    }
+
     // ── Additional Twin Prime Tests ────────────────────────────────────
 
     /// Verifies twin pairs in base 3: k=2, base=3, n=1 gives (5, 7).
@@ -573,7 +772,8 @@ mod tests {
         let sieve_primes = sieve::generate_primes(10_000);
         let sieve_min_n = 14u64;
 
-        let (plus_surv, minus_surv) = kbn::bsgs_sieve(1, 200, 3, 2, &sieve_primes, sieve_min_n);
+        let (plus_surv, minus_surv, _) =
+            kbn::bsgs_sieve(1, 200, 3, 2, &sieve_primes, sieve_min_n, None);
 
         // For n < sieve_min_n, all candidates survive (sieve not applied)
         for &n in &[1u64, 2, 6] {
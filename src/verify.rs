@@ -11,328 +11,320 @@
 //! | 1 | Deterministic proof (Proth, LLR, Pocklington, Morrison, BLS) | Proven |
 //! | 2 | GMP `is_probably_prime(25)` (Miller-Rabin + BPSW) | ~1 in 4^25 error |
 //! | 3 | PFGW subprocess verification | Independent PRP |
+//! | 4 | FLINT `n_is_prime`/`fmpz_is_prime` (`--tool flint`) | Second library |
+//!
+//! Tier 4 ([`verify_flint`], `flint` feature) re-tests the candidate with
+//! FLINT instead of rug. FLINT is built on GMP's integer layer, so the two are
+//! not fully independent: a GMP arithmetic bug could fool both. What it does
+//! catch is a bug in either library's primality code or in how darkreach
+//! calls them, which is what a record claim most needs ruled out.
 //!
 //! ## Expression Parsing
 //!
-//! Each prime form has a dedicated parser that reconstructs the `rug::Integer`
-//! from the human-readable expression string (e.g., `"73! + 1"`, `"3*2^50000+1"`,
-//! `"R(317)"` for repunits). Parsers handle all 12 supported forms.
+//! Each prime form's [`crate::candidate::CandidateForm`] implementation
+//! reconstructs the `rug::Integer` from the human-readable expression string
+//! (e.g., `"73! + 1"`, `"3*2^50000 + 1"`, `"R(10, 317)"` for repunits), and
+//! [`reconstruct_candidate`] looks the form up in the candidate registry.
+//! Factorial and primorial products come from a byte-bounded [`PrefixCache`],
+//! so a bulk run over consecutive `n! ± 1` extends one running product.
 //!
 //! ## Usage
 //!
 //! Called by the `darkreach verify` subcommand and the dashboard's
 //! `/api/verify` endpoint to audit primes already in the database.
+//! `darkreach verify --replay --id N` runs [`replay`], which traces one
+//! stored prime through every search-time stage with per-stage timings.
+//! `darkreach test` does the same for an explicit candidate via
+//! [`test_candidate`], adding the deterministic certificate when one exists.
+//! `darkreach verify --all --parallel` runs [`verify_batch`] across Rayon,
+//! capping the pool at [`pfgw_max_parallel`] when PFGW subprocesses may run.
+//! `--format json` prints a [`VerifyRecord`] per prime to stdout, and the
+//! command exits nonzero when any prime fails.
 
 use anyhow::{anyhow, Result};
+use rayon::prelude::*;
 use rug::integer::IsPrime;
 use rug::ops::Pow;
 use rug::Integer;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
+use crate::candidate::{self, CandidateForm};
 use crate::db::PrimeDetail;
 use crate::pfgw;
-use crate::{has_small_factor, kbn, proof, sieve};
+use crate::{has_small_factor, kbn, p1, proof, sieve};
 
 /// Result of a verification attempt.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum VerifyResult {
-    Verified { method: String, tier: u8 },
-    Failed { reason: String },
-    Skipped { reason: String },
+    Verified {
+        method: String,
+        tier: u8,
+    },
+    Failed {
+        reason: String,
+        reason_code: VerifyFailureCode,
+    },
+    Skipped {
+        reason: String,
+    },
+    /// A tool crashed or timed out before reaching a verdict. The prime stays
+    /// unverified and is retried; only `reason_code` is recorded.
+    Errored {
+        reason: String,
+        reason_code: VerifyFailureCode,
+    },
 }
 
-/// Reconstruct the candidate integer from the stored form and expression.
-pub fn reconstruct_candidate(form: &str, expression: &str) -> Result<Integer> {
-    match form {
-        "factorial" => parse_factorial(expression),
-        "primorial" => parse_primorial(expression),
-        "kbn" => parse_kbn(expression),
-        "palindromic" => parse_palindromic(expression),
-        "near_repdigit" => parse_near_repdigit(expression),
-        "cullen" | "woodall" | "cullen_woodall" => parse_cullen_woodall(expression),
-        "wagstaff" => parse_wagstaff(expression),
-        "carol" | "kynea" | "carol_kynea" => parse_carol_kynea(expression),
-        "twin" => parse_twin(expression),
-        "sophie_germain" => parse_sophie_germain(expression),
-        "repunit" => parse_repunit(expression),
-        "gen_fermat" => parse_gen_fermat(expression),
-        _ => Err(anyhow!("Unknown form: {}", form)),
-    }
+/// Why a verification failed, for grouping failures without parsing `reason`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyFailureCode {
+    /// The expression could not be rebuilt, or rebuilds to the wrong size.
+    Reconstruct,
+    /// A probabilistic or independent test found the candidate composite.
+    CompositeProbabilistic,
+    /// The deterministic proof used at discovery time did not hold.
+    ProofMismatch,
+    /// A verification tool crashed or produced unusable output.
+    ToolError,
+    /// A verification tool exceeded its time limit.
+    Timeout,
 }
 
-// --- Expression parsers ---
-
-/// Parse "73! + 1" or "73! - 1"
-fn parse_factorial(expr: &str) -> Result<Integer> {
-    // "N! + 1" or "N! - 1"
-    let expr = expr.trim();
-    let bang = expr
-        .find('!')
-        .ok_or_else(|| anyhow!("No '!' in factorial expression: {}", expr))?;
-    let n: u32 = expr[..bang].trim().parse()?;
-    let rest = expr[bang + 1..].trim();
-    let factorial = Integer::from(Integer::factorial(n));
-    if rest.starts_with('+') {
-        Ok(factorial + 1u32)
-    } else if rest.starts_with('-') {
-        Ok(factorial - 1u32)
-    } else {
-        Err(anyhow!("Expected +/- after '!' in: {}", expr))
+impl VerifyFailureCode {
+    /// Value stored in `primes.verification_failure_code`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerifyFailureCode::Reconstruct => "reconstruct",
+            VerifyFailureCode::CompositeProbabilistic => "composite_probabilistic",
+            VerifyFailureCode::ProofMismatch => "proof_mismatch",
+            VerifyFailureCode::ToolError => "tool_error",
+            VerifyFailureCode::Timeout => "timeout",
+        }
     }
 }
 
-/// Parse "31# + 1" or "31# - 1"
-fn parse_primorial(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    let hash = expr
-        .find('#')
-        .ok_or_else(|| anyhow!("No '#' in primorial expression: {}", expr))?;
-    let n: u32 = expr[..hash].trim().parse()?;
-    let rest = expr[hash + 1..].trim();
-    let primorial = Integer::from(Integer::primorial(n));
-    if rest.starts_with('+') {
-        Ok(primorial + 1u32)
-    } else if rest.starts_with('-') {
-        Ok(primorial - 1u32)
-    } else {
-        Err(anyhow!("Expected +/- after '#' in: {}", expr))
-    }
+/// Output of `darkreach verify`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerifyFormat {
+    /// Human-readable table on stderr.
+    #[default]
+    Table,
+    /// JSON array of [`VerifyRecord`]s on stdout; stderr keeps the table.
+    Json,
 }
 
-/// Parse "3*2^31 + 1" or "3*2^31 - 1"
-fn parse_kbn(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    let star = expr
-        .find('*')
-        .ok_or_else(|| anyhow!("No '*' in kbn expression: {}", expr))?;
-    let k: u64 = expr[..star].trim().parse()?;
-    let rest = &expr[star + 1..];
-    let caret = rest
-        .find('^')
-        .ok_or_else(|| anyhow!("No '^' in kbn expression: {}", expr))?;
-    let base: u32 = rest[..caret].trim().parse()?;
-    // After '^', find the exponent (until + or -)
-    let rest_after_caret = &rest[caret + 1..];
-    let (n_str, sign) = split_at_sign(rest_after_caret)?;
-    let n: u32 = n_str.trim().parse()?;
-    let value = Integer::from(k) * Integer::from(base).pow(n);
-    match sign {
-        '+' => Ok(value + 1u32),
-        '-' => Ok(value - 1u32),
-        _ => Err(anyhow!(
-            "Unexpected sign '{}' in kbn expression: {}",
-            sign,
-            expr
-        )),
+impl std::str::FromStr for VerifyFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "table" => Ok(VerifyFormat::Table),
+            "json" => Ok(VerifyFormat::Json),
+            _ => anyhow::bail!("unknown verify format {s:?} (table, json)"),
+        }
     }
 }
 
-/// Parse raw decimal palindrome "10301"
-fn parse_palindromic(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    Integer::parse(expr)
-        .map(Integer::from)
-        .map_err(|_| anyhow!("Invalid palindromic expression: {}", expr))
+/// One prime's outcome, as printed by `verify --format json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerifyRecord {
+    pub id: i64,
+    pub expression: String,
+    /// `"verified"`, `"failed"`, `"skipped"` or `"errored"`.
+    pub status: &'static str,
+    pub tier: Option<u8>,
+    pub method: Option<String>,
+    pub reason: Option<String>,
 }
 
-/// Parse "10^7 - 1 - 4*10^3" or "10^7 - 1 - 5*(10^5 + 10^1)"
-fn parse_near_repdigit(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    // Format 1: "10^D - 1 - C*10^P"         (m == 0)
-    // Format 2: "10^D - 1 - C*(10^A + 10^B)" (m != 0)
-
-    // Extract D from "10^D"
-    let caret1 = expr
-        .find('^')
-        .ok_or_else(|| anyhow!("No '^' in near_repdigit: {}", expr))?;
-    let after_caret = &expr[caret1 + 1..];
-    let d_end = after_caret.find(' ').unwrap_or(after_caret.len());
-    let digit_count: u32 = after_caret[..d_end].trim().parse()?;
-
-    let repdigit = Integer::from(10u32).pow(digit_count) - 1u32;
-
-    // Find the second subtraction (after "- 1 -")
-    let last_dash_idx = expr
-        .rfind(" - ")
-        .ok_or_else(|| anyhow!("Missing subtraction in: {}", expr))?;
-    let modifier_str = &expr[last_dash_idx + 3..];
-
-    if modifier_str.contains('(') {
-        // Format 2: "C*(10^A + 10^B)"
-        let star = modifier_str
-            .find('*')
-            .ok_or_else(|| anyhow!("No '*' in modifier: {}", modifier_str))?;
-        let c: u32 = modifier_str[..star].trim().parse()?;
-        // Extract A and B from "(10^A + 10^B)"
-        let inner = modifier_str[star + 1..]
-            .trim()
-            .trim_start_matches('(')
-            .trim_end_matches(')');
-        let plus = inner
-            .find('+')
-            .ok_or_else(|| anyhow!("No '+' in inner: {}", inner))?;
-        let part_a = inner[..plus].trim();
-        let part_b = inner[plus + 1..].trim();
-        let a: u32 = part_a
-            .split('^')
-            .nth(1)
-            .ok_or_else(|| anyhow!("No '^' in: {}", part_a))?
-            .trim()
-            .parse()?;
-        let b: u32 = part_b
-            .split('^')
-            .nth(1)
-            .ok_or_else(|| anyhow!("No '^' in: {}", part_b))?
-            .trim()
-            .parse()?;
-        Ok(repdigit
-            - Integer::from(c) * (Integer::from(10u32).pow(a) + Integer::from(10u32).pow(b)))
-    } else {
-        // Format 1: "C*10^P"
-        let star = modifier_str
-            .find('*')
-            .ok_or_else(|| anyhow!("No '*' in modifier: {}", modifier_str))?;
-        let c: u32 = modifier_str[..star].trim().parse()?;
-        let caret = modifier_str
-            .find('^')
-            .ok_or_else(|| anyhow!("No '^' in modifier: {}", modifier_str))?;
-        let p: u32 = modifier_str[caret + 1..].trim().parse()?;
-        Ok(repdigit - Integer::from(c) * Integer::from(10u32).pow(p))
+impl VerifyRecord {
+    pub fn new(prime: &PrimeDetail, result: &VerifyResult) -> Self {
+        let (status, tier, method, reason) = match result {
+            VerifyResult::Verified { method, tier } => {
+                ("verified", Some(*tier), Some(method.clone()), None)
+            }
+            VerifyResult::Failed { reason, .. } => ("failed", None, None, Some(reason.clone())),
+            VerifyResult::Skipped { reason } => ("skipped", None, None, Some(reason.clone())),
+            VerifyResult::Errored { reason, .. } => ("errored", None, None, Some(reason.clone())),
+        };
+        VerifyRecord {
+            id: prime.id,
+            expression: prime.expression.clone(),
+            status,
+            tier,
+            method,
+            reason,
+        }
     }
 }
 
-/// Parse "141*2^141 + 1" (Cullen) or "6*2^6 - 1" (Woodall)
-fn parse_cullen_woodall(expr: &str) -> Result<Integer> {
-    // Same format as kbn
-    parse_kbn(expr)
+/// Reconstruct the candidate integer from the stored form and expression,
+/// dispatching through the [`candidate::REGISTRY`].
+///
+/// Factorial and primorial candidates are built on top of the process-wide
+/// [`PrefixCache`], so consecutive `n! ± 1` rebuilds share their product.
+pub fn reconstruct_candidate(form: &str, expression: &str) -> Result<Integer> {
+    reconstruct_with(&PREFIX_CACHE, form, expression)
 }
 
-/// Parse "(2^127+1)/3"
-fn parse_wagstaff(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    // "(2^P+1)/3"
-    let caret = expr
-        .find('^')
-        .ok_or_else(|| anyhow!("No '^' in wagstaff: {}", expr))?;
-    let plus = expr
-        .find('+')
-        .ok_or_else(|| anyhow!("No '+' in wagstaff: {}", expr))?;
-    let p: u32 = expr[caret + 1..plus].trim().parse()?;
-    let numerator = (Integer::from(1u32) << p) + Integer::from(1u32);
-    Ok(numerator / Integer::from(3u32))
+/// [`reconstruct_candidate`] with an explicit prefix cache.
+fn reconstruct_with(cache: &Mutex<PrefixCache>, form: &str, expression: &str) -> Result<Integer> {
+    let prefix = match form {
+        "factorial" => candidate::Factorial::parse_expression(expression)
+            .map(|(f, n)| (PrefixKind::Factorial, n, f.plus)),
+        "primorial" => candidate::Primorial::parse_expression(expression)
+            .map(|(f, p)| (PrefixKind::Primorial, p, f.plus)),
+        _ => None,
+    };
+    if let Some((kind, index, plus)) = prefix {
+        // The product is computed outside the lock so parallel verification
+        // of unrelated candidates does not serialize on it.
+        let start = cache.lock().unwrap().nearest(kind, index);
+        let (value, factors) = extend_prefix(kind, start, index);
+        cache.lock().unwrap().insert(kind, index, &value, factors);
+        return Ok(if plus { value + 1u32 } else { value - 1u32 });
+    }
+    let rebuild =
+        candidate::reconstructor(form).ok_or_else(|| anyhow!("Unknown form: {}", form))?;
+    rebuild(expression).ok_or_else(|| anyhow!("Cannot parse {} expression: {}", form, expression))
 }
 
-/// Parse "(2^7-1)^2-2" (Carol) or "(2^7+1)^2-2" (Kynea)
-fn parse_carol_kynea(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    // "(2^N-1)^2-2" or "(2^N+1)^2-2"
-    let caret = expr
-        .find('^')
-        .ok_or_else(|| anyhow!("No '^' in carol_kynea: {}", expr))?;
-    // Find the sign before ')^2-2'
-    let paren_close = expr
-        .find(')')
-        .ok_or_else(|| anyhow!("No ')' in carol_kynea: {}", expr))?;
-    let inner = &expr[caret + 1..paren_close];
-    let (n_str, sign) = if let Some(pos) = inner.find('-') {
-        (&inner[..pos], '-')
-    } else if let Some(pos) = inner.find('+') {
-        (&inner[..pos], '+')
-    } else {
-        return Err(anyhow!("No +/- in carol_kynea inner: {}", inner));
-    };
-    let n: u32 = n_str.trim().parse()?;
-    let base = if sign == '-' {
-        Integer::from(1u32 << n) - 1u32 // Carol: 2^n - 1
-    } else {
-        Integer::from(1u32 << n) + 1u32 // Kynea: 2^n + 1
-    };
-    Ok(base.square() - 2u32)
+// ── Prefix Cache ──────────────────────────────────────────────────
+
+/// Byte budget of the process-wide [`PrefixCache`].
+pub const PREFIX_CACHE_BYTES: usize = 256 << 20;
+
+static PREFIX_CACHE: Mutex<PrefixCache> = Mutex::new(PrefixCache::new(PREFIX_CACHE_BYTES));
+
+/// Which running product a [`PrefixCache`] entry holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixKind {
+    /// `n!`
+    Factorial,
+    /// `p#`
+    Primorial,
 }
 
-/// Parse "3*2^100 +/- 1" (twin prime pair)
-fn parse_twin(expr: &str) -> Result<Integer> {
-    // Twin expression is "k*b^n +/- 1". We return k*b^n - 1 (the smaller twin).
-    let expr = expr.trim();
-    let star = expr
-        .find('*')
-        .ok_or_else(|| anyhow!("No '*' in twin: {}", expr))?;
-    let k: u64 = expr[..star].trim().parse()?;
-    let rest = &expr[star + 1..];
-    let caret = rest
-        .find('^')
-        .ok_or_else(|| anyhow!("No '^' in twin: {}", expr))?;
-    let base: u32 = rest[..caret].trim().parse()?;
-    let rest_after_caret = &rest[caret + 1..];
-    // Find where the number ends (before " +/- 1")
-    let space = rest_after_caret.find(' ').unwrap_or(rest_after_caret.len());
-    let n: u32 = rest_after_caret[..space].trim().parse()?;
-    // Return the minus form (smaller twin) — both k*b^n-1 and k*b^n+1 are prime
-    Ok(Integer::from(k) * Integer::from(base).pow(n) - 1u32)
+/// Least-recently-used cache of factorial and primorial prefixes, bounded
+/// by the total size of the cached integers rather than their count.
+///
+/// Rebuilding `n!` starts from the largest cached `m!` with `m ≤ n` and
+/// multiplies in only the factors in `(m, n]`, so verifying `27! + 1`,
+/// `37! + 1` and `41! + 1` in turn computes the shared product once.
+pub struct PrefixCache {
+    capacity_bytes: usize,
+    used_bytes: usize,
+    tick: u64,
+    entries: Vec<PrefixEntry>,
+    /// Factors multiplied into prefixes so far: integers for factorials,
+    /// primes for primorials.
+    pub factors_multiplied: u64,
 }
 
-/// Parse "3*2^100-1" (Sophie Germain prime)
-fn parse_sophie_germain(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    let star = expr
-        .find('*')
-        .ok_or_else(|| anyhow!("No '*' in sophie_germain: {}", expr))?;
-    let k: u64 = expr[..star].trim().parse()?;
-    let rest = &expr[star + 1..];
-    let caret = rest
-        .find('^')
-        .ok_or_else(|| anyhow!("No '^' in sophie_germain: {}", expr))?;
-    let base: u32 = rest[..caret].trim().parse()?;
-    let rest_after_caret = &rest[caret + 1..];
-    // Sophie Germain expression has no spaces: "k*b^n-1"
-    let minus = rest_after_caret
-        .find('-')
-        .ok_or_else(|| anyhow!("No '-' in sophie_germain: {}", expr))?;
-    let n: u32 = rest_after_caret[..minus].trim().parse()?;
-    Ok(Integer::from(k) * Integer::from(base).pow(n) - 1u32)
+struct PrefixEntry {
+    kind: PrefixKind,
+    index: u64,
+    value: Integer,
+    bytes: usize,
+    last_used: u64,
 }
 
-/// Parse "R(10, 19)" (repunit)
-fn parse_repunit(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    // "R(base, n)"
-    let open = expr
-        .find('(')
-        .ok_or_else(|| anyhow!("No '(' in repunit: {}", expr))?;
-    let close = expr
-        .find(')')
-        .ok_or_else(|| anyhow!("No ')' in repunit: {}", expr))?;
-    let inner = &expr[open + 1..close];
-    let comma = inner
-        .find(',')
-        .ok_or_else(|| anyhow!("No ',' in repunit: {}", inner))?;
-    let base: u32 = inner[..comma].trim().parse()?;
-    let n: u32 = inner[comma + 1..].trim().parse()?;
-    // R(b, n) = (b^n - 1) / (b - 1)
-    Ok((Integer::from(base).pow(n) - 1u32) / (base - 1))
+impl PrefixCache {
+    pub const fn new(capacity_bytes: usize) -> Self {
+        PrefixCache {
+            capacity_bytes,
+            used_bytes: 0,
+            tick: 0,
+            entries: Vec::new(),
+            factors_multiplied: 0,
+        }
+    }
+
+    /// Total size of the cached integers, at most the capacity.
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    /// The largest cached prefix of `kind` at or below `index`, as
+    /// `(index, value)`, or the empty product `(0, 1)`.
+    fn nearest(&mut self, kind: PrefixKind, index: u64) -> (u64, Integer) {
+        self.tick += 1;
+        let tick = self.tick;
+        let best = self
+            .entries
+            .iter_mut()
+            .filter(|e| e.kind == kind && e.index <= index)
+            .max_by_key(|e| e.index);
+        match best {
+            Some(entry) => {
+                entry.last_used = tick;
+                (entry.index, entry.value.clone())
+            }
+            None => (0, Integer::from(1u32)),
+        }
+    }
+
+    /// Cache `value` as the `kind` prefix at `index`, evicting the least
+    /// recently used entries to stay within the byte budget. Values larger
+    /// than the whole budget are not cached.
+    fn insert(&mut self, kind: PrefixKind, index: u64, value: &Integer, factors: u64) {
+        self.factors_multiplied += factors;
+        self.tick += 1;
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.kind == kind && e.index == index)
+        {
+            entry.last_used = self.tick;
+            return;
+        }
+        let bytes = value.significant_bits().div_ceil(8) as usize;
+        if bytes > self.capacity_bytes {
+            return;
+        }
+        while self.used_bytes + bytes > self.capacity_bytes {
+            let oldest = (0..self.entries.len())
+                .min_by_key(|&i| self.entries[i].last_used)
+                .expect("used bytes without entries");
+            self.used_bytes -= self.entries.swap_remove(oldest).bytes;
+        }
+        self.used_bytes += bytes;
+        self.entries.push(PrefixEntry {
+            kind,
+            index,
+            value: value.clone(),
+            bytes,
+            last_used: self.tick,
+        });
+    }
 }
 
-/// Parse "6^(2^3) + 1" (generalized Fermat)
-fn parse_gen_fermat(expr: &str) -> Result<Integer> {
-    let expr = expr.trim();
-    // "b^(2^n) + 1"
-    let caret1 = expr
-        .find('^')
-        .ok_or_else(|| anyhow!("No '^' in gen_fermat: {}", expr))?;
-    let b: u64 = expr[..caret1].trim().parse()?;
-    // Find inner "2^n" inside "(2^n)"
-    let open = expr
-        .find('(')
-        .ok_or_else(|| anyhow!("No '(' in gen_fermat: {}", expr))?;
-    let close = expr
-        .find(')')
-        .ok_or_else(|| anyhow!("No ')' in gen_fermat: {}", expr))?;
-    let inner = &expr[open + 1..close];
-    let caret2 = inner
-        .find('^')
-        .ok_or_else(|| anyhow!("No inner '^' in gen_fermat: {}", inner))?;
-    let n: u32 = inner[caret2 + 1..].trim().parse()?;
-    let exponent = 1u32 << n; // 2^n
-    Ok(Integer::from(b).pow(exponent) + 1u32)
+/// Extend the `kind` prefix `(from, value)` to `to`, returning the new
+/// prefix and the number of factors multiplied in.
+fn extend_prefix(kind: PrefixKind, (from, value): (u64, Integer), to: u64) -> (Integer, u64) {
+    let factors: Vec<u64> = match kind {
+        PrefixKind::Factorial => (from + 1..=to).collect(),
+        PrefixKind::Primorial => sieve::generate_primes(to)
+            .into_iter()
+            .filter(|&q| q > from)
+            .collect(),
+    };
+    (value * product_tree(&factors), factors.len() as u64)
+}
+
+/// Product of `factors` by binary splitting, keeping the operands of each
+/// multiplication balanced.
+fn product_tree(factors: &[u64]) -> Integer {
+    match factors {
+        [] => Integer::from(1u32),
+        [f] => Integer::from(*f),
+        _ => {
+            let (low, high) = factors.split_at(factors.len() / 2);
+            product_tree(low) * product_tree(high)
+        }
+    }
 }
 
 /// Split a string at the last '+' or '-' sign (not inside parentheses).
@@ -441,6 +433,13 @@ fn verify_tier1_kbn(expression: &str, candidate: &Integer) -> VerifyResult {
     };
 
     let is_plus = sign == '+';
+    // Proth/LLR only prove k*b^n ± 1; other offsets have no tier-1 test.
+    let kb = Integer::from(k) * Integer::from(base).pow(crate::checked_u32(n));
+    if *candidate != if is_plus { kb + 1u32 } else { kb - 1u32 } {
+        return VerifyResult::Skipped {
+            reason: "kbn offset is not ±1; no tier-1 test".into(),
+        };
+    }
     let (result, method, _certificate) = kbn::test_prime(candidate, k, base, n, is_plus, 15);
     match result {
         IsPrime::Yes if method == "deterministic" => VerifyResult::Verified {
@@ -449,6 +448,7 @@ fn verify_tier1_kbn(expression: &str, candidate: &Integer) -> VerifyResult {
         },
         IsPrime::No => VerifyResult::Failed {
             reason: "Tier-1 kbn test says composite".into(),
+            reason_code: VerifyFailureCode::ProofMismatch,
         },
         _ => {
             // Fell through to MR — treat as "not proven" by tier 1
@@ -494,6 +494,7 @@ fn verify_tier1_factorial(expression: &str, candidate: &Integer) -> VerifyResult
         } else {
             VerifyResult::Failed {
                 reason: "Pocklington proof failed".into(),
+                reason_code: VerifyFailureCode::ProofMismatch,
             }
         }
     } else if proof::morrison_factorial_proof(n, candidate, &sieve_primes) {
@@ -504,6 +505,7 @@ fn verify_tier1_factorial(expression: &str, candidate: &Integer) -> VerifyResult
     } else {
         VerifyResult::Failed {
             reason: "Morrison proof failed".into(),
+            reason_code: VerifyFailureCode::ProofMismatch,
         }
     }
 }
@@ -543,6 +545,7 @@ fn verify_tier1_primorial(expression: &str, candidate: &Integer) -> VerifyResult
         } else {
             VerifyResult::Failed {
                 reason: "Pocklington proof failed for primorial".into(),
+                reason_code: VerifyFailureCode::ProofMismatch,
             }
         }
     } else if proof::morrison_factorial_proof(p, candidate, &sieve_primes) {
@@ -553,6 +556,7 @@ fn verify_tier1_primorial(expression: &str, candidate: &Integer) -> VerifyResult
     } else {
         VerifyResult::Failed {
             reason: "Morrison proof failed for primorial".into(),
+            reason_code: VerifyFailureCode::ProofMismatch,
         }
     }
 }
@@ -564,6 +568,7 @@ pub fn verify_tier2(candidate: &Integer) -> VerifyResult {
     if has_small_factor(candidate) {
         return VerifyResult::Failed {
             reason: "Has small factor (trial division)".into(),
+            reason_code: VerifyFailureCode::CompositeProbabilistic,
         };
     }
 
@@ -572,6 +577,7 @@ pub fn verify_tier2(candidate: &Integer) -> VerifyResult {
     if candidate.is_probably_prime(1) == IsPrime::No {
         return VerifyResult::Failed {
             reason: "Failed BPSW test".into(),
+            reason_code: VerifyFailureCode::CompositeProbabilistic,
         };
     }
 
@@ -584,11 +590,13 @@ pub fn verify_tier2(candidate: &Integer) -> VerifyResult {
             Ok(r) if r != 1u32 => {
                 return VerifyResult::Failed {
                     reason: format!("Failed Fermat test with base {}", base),
+                    reason_code: VerifyFailureCode::CompositeProbabilistic,
                 };
             }
             Err(_) => {
                 return VerifyResult::Failed {
                     reason: format!("GCD != 1 with base {}", base),
+                    reason_code: VerifyFailureCode::CompositeProbabilistic,
                 };
             }
             _ => {} // passed
@@ -645,7 +653,14 @@ pub fn verify_pfgw(form: &str, expression: &str, candidate: &Integer) -> VerifyR
         _ => pfgw::PfgwMode::Prp,
     };
 
-    match pfgw::try_test(&pfgw_expr, candidate, mode) {
+    pfgw_verdict(pfgw::try_test(&pfgw_expr, candidate, mode))
+}
+
+/// Map a PFGW run to a verification result. A tool crash or timeout is
+/// [`VerifyResult::Errored`]: it says nothing about the candidate, so the
+/// prime is not flagged as failed.
+fn pfgw_verdict(result: Option<pfgw::PfgwResult>) -> VerifyResult {
+    match result {
         Some(pfgw::PfgwResult::Prime {
             method,
             is_deterministic,
@@ -662,6 +677,15 @@ pub fn verify_pfgw(form: &str, expression: &str, candidate: &Integer) -> VerifyR
         }
         Some(pfgw::PfgwResult::Composite) => VerifyResult::Failed {
             reason: "PFGW says composite".into(),
+            reason_code: VerifyFailureCode::CompositeProbabilistic,
+        },
+        Some(pfgw::PfgwResult::Failed { reason, timed_out }) => VerifyResult::Errored {
+            reason: format!("PFGW failed: {}", reason),
+            reason_code: if timed_out {
+                VerifyFailureCode::Timeout
+            } else {
+                VerifyFailureCode::ToolError
+            },
         },
         Some(pfgw::PfgwResult::Unavailable { reason }) => VerifyResult::Skipped { reason },
         None => VerifyResult::Skipped {
@@ -670,6 +694,104 @@ pub fn verify_pfgw(form: &str, expression: &str, candidate: &Integer) -> VerifyR
     }
 }
 
+/// Tier 4: re-test with FLINT's primality code instead of rug's.
+///
+/// Word-sized candidates and those up to [`crate::flint::PROOF_MAX_BITS`]
+/// are proven; larger ones get FLINT's BPSW. See the module docs for how
+/// independent this is.
+#[cfg(feature = "flint")]
+pub fn verify_flint(candidate: &Integer) -> VerifyResult {
+    use crate::flint::{primality, Primality};
+    match primality(candidate) {
+        Primality::Prime => VerifyResult::Verified {
+            method: "tier4-flint-proof".into(),
+            tier: 4,
+        },
+        Primality::ProbablePrime => VerifyResult::Verified {
+            method: "tier4-flint-bpsw".into(),
+            tier: 4,
+        },
+        Primality::Composite => VerifyResult::Failed {
+            reason: "FLINT says composite".into(),
+            reason_code: VerifyFailureCode::CompositeProbabilistic,
+        },
+    }
+}
+
+#[cfg(not(feature = "flint"))]
+pub fn verify_flint(_candidate: &Integer) -> VerifyResult {
+    VerifyResult::Skipped {
+        reason: "built without the flint feature".into(),
+    }
+}
+
+/// Primes at least this large also get a tier-3 PFGW cross-check.
+const TIER3_MIN_DIGITS: u64 = 1000;
+
+/// Most PFGW subprocesses a parallel batch may have running at once: one
+/// per core available to this process (cgroup and affinity limits included).
+pub fn pfgw_max_parallel() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Verify one stored prime with the CLI's `--tool` choice: `"pfgw"` goes
+/// straight to PFGW, `"flint"` to [`verify_flint`], anything else runs the
+/// tiered [`verify_prime`] pipeline.
+pub fn verify_with_tool(detail: &PrimeDetail, tool: &str) -> VerifyResult {
+    if tool != "pfgw" && tool != "flint" {
+        return verify_prime(detail);
+    }
+    match reconstruct_candidate(&detail.form, &detail.expression) {
+        Ok(c) if tool == "flint" => verify_flint(&c),
+        Ok(c) => verify_pfgw(&detail.form, &detail.expression, &c),
+        Err(e) => VerifyResult::Failed {
+            reason: format!("Cannot reconstruct: {}", e),
+            reason_code: VerifyFailureCode::Reconstruct,
+        },
+    }
+}
+
+/// Verify a fetched batch, returning `(id, result)` in input order.
+///
+/// With `parallel`, primes are verified across Rayon. Tier 1/2 is pure rug
+/// work and uses the global pool; if any prime may reach PFGW, the batch
+/// runs on a pool of [`pfgw_max_parallel`] threads instead so it never forks
+/// more subprocesses than there are cores, whatever `--threads` asked for. Callers apply DB updates serially afterwards.
+pub fn verify_batch(
+    primes: &[PrimeDetail],
+    tool: &str,
+    parallel: bool,
+) -> Vec<(i64, VerifyResult)> {
+    let verify_all = || -> Vec<(i64, VerifyResult)> {
+        primes
+            .par_iter()
+            .map(|p| (p.id, verify_with_tool(p, tool)))
+            .collect()
+    };
+    if !parallel {
+        return primes
+            .iter()
+            .map(|p| (p.id, verify_with_tool(p, tool)))
+            .collect();
+    }
+
+    let may_spawn_pfgw = primes.iter().any(|p| {
+        let digits = p.digits.max(0) as u64;
+        let tier3 = tool != "flint" && digits >= TIER3_MIN_DIGITS;
+        (tool == "pfgw" || tier3) && pfgw::is_available(digits)
+    });
+    if !may_spawn_pfgw {
+        return verify_all();
+    }
+    match rayon::ThreadPoolBuilder::new()
+        .num_threads(pfgw_max_parallel())
+        .build()
+    {
+        Ok(pool) => pool.install(verify_all),
+        Err(_) => verify_batch(primes, tool, false),
+    }
+}
+
 /// Main entry point: verify a single prime from the database.
 pub fn verify_prime(detail: &PrimeDetail) -> VerifyResult {
     // Step 1: Reconstruct candidate
@@ -678,6 +800,7 @@ pub fn verify_prime(detail: &PrimeDetail) -> VerifyResult {
         Err(e) => {
             return VerifyResult::Failed {
                 reason: format!("Cannot reconstruct: {}", e),
+                reason_code: VerifyFailureCode::Reconstruct,
             }
         }
     };
@@ -690,6 +813,7 @@ pub fn verify_prime(detail: &PrimeDetail) -> VerifyResult {
                 "Digit count mismatch: stored={}, reconstructed={}",
                 detail.digits, actual_digits
             ),
+            reason_code: VerifyFailureCode::Reconstruct,
         };
     }
 
@@ -702,7 +826,7 @@ pub fn verify_prime(detail: &PrimeDetail) -> VerifyResult {
     );
     match &t1 {
         VerifyResult::Verified { .. } => return t1,
-        VerifyResult::Failed { .. } => return t1,
+        VerifyResult::Failed { .. } | VerifyResult::Errored { .. } => return t1,
         VerifyResult::Skipped { .. } => {} // proceed to tier 2
     }
 
@@ -710,18 +834,19 @@ pub fn verify_prime(detail: &PrimeDetail) -> VerifyResult {
     let t2 = verify_tier2(&candidate);
     match &t2 {
         VerifyResult::Failed { .. } => return t2,
-        VerifyResult::Skipped { .. } => return t2,
+        VerifyResult::Skipped { .. } | VerifyResult::Errored { .. } => return t2,
         VerifyResult::Verified { .. } => {}
     }
 
     // Step 5: For primes >= 1000 digits, attempt tier 3 (PFGW cross-verification)
     // using a completely independent code path for maximum confidence.
-    if actual_digits >= 1000 {
+    if actual_digits >= TIER3_MIN_DIGITS {
         let t3 = verify_pfgw(&detail.form, &detail.expression, &candidate);
         match &t3 {
             VerifyResult::Verified { .. } => return t3, // stronger: independent tool
             VerifyResult::Failed { .. } => return t3,   // PFGW disagrees — flag it
             VerifyResult::Skipped { .. } => {}          // PFGW not available
+            VerifyResult::Errored { .. } => {}          // says nothing about N
         }
     }
 
@@ -729,6 +854,73 @@ pub fn verify_prime(detail: &PrimeDetail) -> VerifyResult {
     t2
 }
 
+// ── Deterministic Upgrade (project prove phase) ───────────────────
+
+/// Attempt a deterministic proof for a stored probable prime.
+///
+/// Used by the project `"prove"` phase to upgrade PRPs accumulated by fast
+/// discovery searches. Returns the proof method label (in the same format the
+/// engines store) and the serialized certificate when the proof produces one,
+/// or `None` if the form has no deterministic test or the proof fails.
+pub fn prove_probable_prime(
+    form: &str,
+    expression: &str,
+) -> Result<Option<(String, Option<String>)>> {
+    let candidate = reconstruct_candidate(form, expression)?;
+    match form {
+        "factorial" | "primorial" => Ok(prove_factorial_like(form, expression, &candidate)),
+        "kbn" | "cullen" | "woodall" | "cullen_woodall" => {
+            let (form, n) = candidate::Kbn::parse_expression(expression.trim())
+                .ok_or_else(|| anyhow!("Cannot parse kbn expression: {}", expression))?;
+            // Proth/LLR only prove k*b^n ± 1.
+            if form.c.abs() != 1 {
+                return Ok(None);
+            }
+            let (result, method, certificate) =
+                kbn::test_prime(&candidate, form.k, form.base, n, form.c > 0, 15);
+            if result == IsPrime::Yes && method == "deterministic" {
+                let cert_json = certificate
+                    .as_ref()
+                    .and_then(|c| serde_json::to_string(c).ok());
+                Ok(Some((method.to_string(), cert_json)))
+            } else {
+                Ok(None)
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Pocklington (+1) or Morrison (-1) proof for n!±1 and p#±1.
+///
+/// The label names the tier-1 proof that actually ran. For the -1 side the
+/// fully factored N+1 also yields a Morrison certificate.
+fn prove_factorial_like(
+    form: &str,
+    expression: &str,
+    candidate: &Integer,
+) -> Option<(String, Option<String>)> {
+    let method = match verify_tier1(form, expression, candidate, "deterministic") {
+        VerifyResult::Verified { method, .. } => method,
+        _ => return None,
+    };
+    match method.as_str() {
+        "tier1-pocklington" => Some(("deterministic (Pocklington N-1)".to_string(), None)),
+        "tier1-morrison" => {
+            let mark = if form == "factorial" { '!' } else { '#' };
+            let n: u64 = expression.split(mark).next()?.trim().parse().ok()?;
+            let factors: Vec<Integer> = sieve::generate_primes(n)
+                .into_iter()
+                .map(Integer::from)
+                .collect();
+            let cert_json = proof::morrison_auto(candidate, &factors)
+                .and_then(|c| serde_json::to_string(&c).ok());
+            Some(("deterministic (Morrison N+1)".to_string(), cert_json))
+        }
+        _ => None,
+    }
+}
+
 // ── Volunteer Quorum Logic ────────────────────────────────────────
 
 /// Provable forms that have deterministic proof methods (certificates).
@@ -769,6 +961,27 @@ pub fn required_quorum(trust_level: i16, form: &str) -> i16 {
     }
 }
 
+/// Decayed reliability at or above which a proven operator's PRP-form blocks
+/// are single-checked (see [`required_quorum_with_reliability`]).
+pub const HIGH_RELIABILITY: f64 = 0.98;
+
+/// [`required_quorum`], lowered to 1 for proven (level ≥ 2) operators whose
+/// materialized reliability score is at least [`HIGH_RELIABILITY`]. New and
+/// untrusted operators, and operators with no score yet (fewer than 20
+/// verified blocks), are unaffected.
+pub fn required_quorum_with_reliability(
+    trust_level: i16,
+    reliability: Option<f64>,
+    form: &str,
+) -> i16 {
+    let quorum = required_quorum(trust_level, form);
+    if trust_level >= 2 && reliability.is_some_and(|r| r >= HIGH_RELIABILITY) {
+        quorum.min(1)
+    } else {
+        quorum
+    }
+}
+
 /// Determine quorum for a high-value result (≥100K digits).
 /// Always returns 3 (triple-check) regardless of trust level.
 pub fn required_quorum_high_value(trust_level: i16, form: &str, digits: u64) -> i16 {
@@ -784,6 +997,245 @@ pub fn is_provable_form(form: &str) -> bool {
     PROVABLE_FORMS.contains(&form)
 }
 
+// ── Candidate Replay ──────────────────────────────────────────────
+
+/// Outcome of one replayed pipeline stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageOutcome {
+    Pass,
+    Fail,
+    Skip,
+}
+
+/// One stage of a [`ReplayReport`].
+#[derive(Debug, Clone)]
+pub struct ReplayStage {
+    pub name: &'static str,
+    pub outcome: StageOutcome,
+    pub detail: String,
+    pub elapsed: Duration,
+}
+
+/// Stage-by-stage trace of a candidate through the search-time test pipeline.
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    pub form: String,
+    pub expression: String,
+    /// Digit count of the reconstructed candidate; `None` if it could not be rebuilt.
+    pub digits: Option<u64>,
+    pub stages: Vec<ReplayStage>,
+}
+
+impl ReplayReport {
+    /// True when no stage failed (skipped stages are allowed).
+    pub fn passed(&self) -> bool {
+        self.stages.iter().all(|s| s.outcome != StageOutcome::Fail)
+    }
+}
+
+/// Time `f` and record its outcome as a stage.
+fn run_stage(name: &'static str, f: impl FnOnce() -> (StageOutcome, String)) -> ReplayStage {
+    let start = Instant::now();
+    let (outcome, detail) = f();
+    ReplayStage {
+        name,
+        outcome,
+        detail,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Re-run a stored prime through every stage of the search pipeline.
+///
+/// Stages: reconstruction, trial division, MR screening, Frobenius, P-1 and
+/// the tier-1 deterministic proof. Unlike the search, size thresholds are
+/// ignored so every filter runs: Frobenius is applied at any size and P-1
+/// falls back to the form's entry-tier bounds. Every stage runs even after a
+/// failure, so the report shows exactly which tests disagree.
+pub fn replay(form: &str, expression: &str) -> ReplayReport {
+    let mut stages = Vec::new();
+    let mut candidate = None;
+    stages.push(run_stage("reconstruct", || {
+        match reconstruct_candidate(form, expression) {
+            Ok(c) => {
+                let detail = format!("{} bits", c.significant_bits());
+                candidate = Some(c);
+                (StageOutcome::Pass, detail)
+            }
+            Err(e) => (StageOutcome::Fail, e.to_string()),
+        }
+    }));
+    let Some(candidate) = candidate else {
+        return ReplayReport {
+            form: form.to_string(),
+            expression: expression.to_string(),
+            digits: None,
+            stages,
+        };
+    };
+
+    stages.push(run_stage("trial_division", || {
+        if has_small_factor(&candidate) {
+            (StageOutcome::Fail, "has a small prime factor".into())
+        } else {
+            (StageOutcome::Pass, String::new())
+        }
+    }));
+    stages.push(run_stage("mr_screen", || {
+        let rounds = if candidate.is_probably_prime(2) == IsPrime::No {
+            2
+        } else if candidate.is_probably_prime(25) == IsPrime::No {
+            25
+        } else {
+            0
+        };
+        if rounds > 0 {
+            let witness = crate::witness::find_witness(&candidate);
+            let detail = format!("composite after {} MR rounds: {}", rounds, witness);
+            (StageOutcome::Fail, detail)
+        } else {
+            (StageOutcome::Pass, "25 rounds".into())
+        }
+    }));
+    stages.push(run_stage("frobenius", || {
+        let result = crate::frobenius_test_witnessed(&candidate);
+        match result {
+            Ok(()) => (StageOutcome::Pass, String::new()),
+            Err(witness) => (
+                StageOutcome::Fail,
+                format!("Frobenius test says composite: {}", witness),
+            ),
+        }
+    }));
+    stages.push(run_stage("p1", || {
+        let bits = candidate.significant_bits() as u64;
+        let (b1, b2) = p1::p1_bounds_for_form(bits, form)
+            .unwrap_or_else(|| p1::p1_entry_bounds_for_form(form));
+        match p1::p1_factor(&candidate, b1, Some(b2)) {
+            Some(f) => (
+                StageOutcome::Fail,
+                format!("factor {} (B1={}, B2={})", f, b1, b2),
+            ),
+            None => (StageOutcome::Pass, format!("B1={}, B2={}", b1, b2)),
+        }
+    }));
+    stages.push(run_stage("proof", || {
+        match verify_tier1(form, expression, &candidate, "deterministic") {
+            VerifyResult::Verified { method, .. } => (StageOutcome::Pass, method),
+            VerifyResult::Failed { reason, .. } => (StageOutcome::Fail, reason),
+            VerifyResult::Skipped { reason } | VerifyResult::Errored { reason, .. } => {
+                (StageOutcome::Skip, reason)
+            }
+        }
+    }));
+
+    ReplayReport {
+        form: form.to_string(),
+        expression: expression.to_string(),
+        digits: Some(crate::exact_digits(&candidate)),
+        stages,
+    }
+}
+
+// ── Explicit Candidates ───────────────────────────────────────────
+
+/// Build the stored expression for one explicit candidate, in the format
+/// [`reconstruct_candidate`] parses. `minus` selects the −1 member (Woodall
+/// for cullen_woodall, Carol for carol_kynea). Palindromic and near-repdigit
+/// candidates have no parameter form; pass their expression directly.
+pub fn expression_for(
+    form: &str,
+    k: Option<u64>,
+    base: Option<u32>,
+    n: Option<u64>,
+    minus: bool,
+) -> Result<String> {
+    let n = n.ok_or_else(|| anyhow!("--n is required for form '{}'", form))?;
+    let plus = !minus;
+    let c = if minus { -1 } else { 1 };
+    let k_arg = || k.ok_or_else(|| anyhow!("--k is required for form '{}'", form));
+    let b = base.unwrap_or(2);
+    let expr = match form {
+        "factorial" => candidate::Factorial { plus }.expression(n),
+        "primorial" => candidate::Primorial { plus }.expression(n),
+        "kbn" => {
+            let k = k_arg()?;
+            candidate::Kbn { k, base: b, c }.expression(n)
+        }
+        "cullen" | "woodall" | "cullen_woodall" => candidate::CullenWoodall { plus }.expression(n),
+        "wagstaff" => candidate::Wagstaff.expression(n),
+        "carol" | "kynea" | "carol_kynea" => candidate::CarolKynea { plus }.expression(n),
+        "twin" => {
+            let k = k_arg()?;
+            candidate::Twin { k, base: b }.expression(n)
+        }
+        "sophie_germain" => {
+            let k = k_arg()?;
+            candidate::SophieGermain { k, base: b }.expression(n)
+        }
+        "repunit" => candidate::Repunit {
+            base: base.unwrap_or(10),
+        }
+        .expression(n),
+        "gen_fermat" => {
+            let b = base.ok_or_else(|| anyhow!("--base is required for form 'gen_fermat'"))?;
+            let fermat_n = u32::try_from(n).map_err(|_| anyhow!("--n {} is too large", n))?;
+            candidate::GenFermat { fermat_n }.expression(b as u64)
+        }
+        _ => {
+            return Err(anyhow!(
+                "Form '{}' has no parameter form; pass --expr instead",
+                form
+            ))
+        }
+    };
+    Ok(expr)
+}
+
+/// Outcome of [`test_candidate`]: the stage trace plus the overall verdict.
+#[derive(Debug, Clone)]
+pub struct CandidateVerdict {
+    pub report: ReplayReport,
+    /// No stage rejected the candidate.
+    pub prime: bool,
+    /// Proof method label when a deterministic proof succeeded.
+    pub proof_method: Option<String>,
+    /// Serialized certificate, when the proof produces one.
+    pub certificate: Option<String>,
+}
+
+/// Run one explicit candidate through the full pipeline without a DB or
+/// search loop: the [`replay`] stages, then, for a survivor, the same
+/// deterministic proof and certificate the `prove` phase would record.
+pub fn test_candidate(form: &str, expression: &str) -> CandidateVerdict {
+    let report = replay(form, expression);
+    let prime = report.digits.is_some() && report.passed();
+    let proof = if prime {
+        prove_probable_prime(form, expression)
+            .ok()
+            .flatten()
+            .or_else(|| {
+                // BPSW itself is a proof below 2^64, whatever the form.
+                reconstruct_candidate(form, expression)
+                    .ok()
+                    .filter(crate::is_below_bpsw_bound)
+                    .map(|_| (crate::SMALL_PRIME_PROOF.to_string(), None))
+            })
+    } else {
+        None
+    };
+    let (proof_method, certificate) = match proof {
+        Some((method, cert)) => (Some(method), cert),
+        None => (None, None),
+    };
+    CandidateVerdict {
+        report,
+        prime,
+        proof_method,
+        certificate,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //! # Tests for the 3-Tier Independent Verification Pipeline
@@ -806,6 +1258,9 @@ mod tests {
     //! 4. **Tier 3 (PFGW)**: External tool cross-verification using GWNUM
     //!    internally. Provides independent implementation verification.
     //!
+    //! 5. **Tier 4 (FLINT)**: `--tool flint` re-tests with FLINT's primality
+    //!    code; its cross-check against rug lives in `flint.rs`.
+    //!
     //! Additionally tests the **volunteer quorum logic** which determines how
     //! many independent checks are required based on trust level and prime form
     //! (following the BOINC adaptive replication model).
@@ -869,6 +1324,53 @@ mod tests {
         assert_eq!(c, Integer::from(29u32));
     }
 
+    /// Consecutive factorial primes (OEIS A002981: 27, 37, 41, 73) share one
+    /// running product: 73! costs 73 multiplications in total, not
+    /// 27 + 37 + 41 + 73, and the rebuilt candidates match a fresh build.
+    #[test]
+    fn prefix_cache_reuses_factorial_product() {
+        let cache = Mutex::new(PrefixCache::new(1 << 20));
+        for n in [27u64, 37, 41, 73] {
+            let expr = format!("{}! + 1", n);
+            let c = reconstruct_with(&cache, "factorial", &expr).unwrap();
+            let fresh = candidate::Factorial { plus: true }.construct(n);
+            assert_eq!(c, fresh, "{}", expr);
+            assert_ne!(c.is_probably_prime(25), IsPrime::No, "{}", expr);
+        }
+        assert_eq!(cache.lock().unwrap().factors_multiplied, 73);
+
+        // A smaller n reuses the largest prefix below it.
+        reconstruct_with(&cache, "factorial", "30! - 1").unwrap();
+        assert_eq!(cache.lock().unwrap().factors_multiplied, 76);
+
+        // Primorials are cached separately: 13# after 7# adds 11 and 13.
+        let seven = reconstruct_with(&cache, "primorial", "7# + 1").unwrap();
+        assert_eq!(seven, 211u32);
+        let thirteen = reconstruct_with(&cache, "primorial", "13# + 1").unwrap();
+        assert_eq!(thirteen, 30031u32);
+        assert_eq!(cache.lock().unwrap().factors_multiplied, 82);
+    }
+
+    /// The budget is in bytes: 73! takes 44 bytes, so a 64-byte cache holds it
+    /// alone and evicts the older prefixes, and values over budget are never
+    /// cached.
+    #[test]
+    fn prefix_cache_is_bounded_by_bytes() {
+        let cache = Mutex::new(PrefixCache::new(64));
+        for n in [27u64, 37, 41, 73] {
+            reconstruct_with(&cache, "factorial", &format!("{}! + 1", n)).unwrap();
+            assert!(cache.lock().unwrap().used_bytes() <= 64);
+        }
+        reconstruct_with(&cache, "factorial", "41! + 1").unwrap();
+        assert_eq!(cache.lock().unwrap().factors_multiplied, 73 + 41);
+
+        let tiny = Mutex::new(PrefixCache::new(8));
+        reconstruct_with(&tiny, "factorial", "73! + 1").unwrap();
+        reconstruct_with(&tiny, "factorial", "73! + 1").unwrap();
+        assert_eq!(tiny.lock().unwrap().used_bytes(), 0);
+        assert_eq!(tiny.lock().unwrap().factors_multiplied, 146);
+    }
+
     /// Parse "3*2^5 + 1" -> 97 (Proth prime: k=3, b=2, n=5, 3*32+1=97).
     /// Proth's theorem applies since k=3 < 2^5=32.
     #[test]
@@ -886,6 +1388,21 @@ mod tests {
         assert_eq!(c, Integer::from(95u32));
     }
 
+    /// Offsets other than ±1 ("1*2^12 + 3" = 4099, "5*2^7 - 3" = 637) are
+    /// rebuilt from the trailing constant, and tier 1 skips them rather than
+    /// running Proth/LLR on a number of the wrong shape.
+    #[test]
+    fn reconstruct_kbn_arbitrary_offset() {
+        let c = reconstruct_candidate("kbn", "1*2^12 + 3").unwrap();
+        assert_eq!(c, Integer::from(4099u32));
+        let c = reconstruct_candidate("kbn", "5*2^7 - 3").unwrap();
+        assert_eq!(c, Integer::from(637u32));
+
+        let c = reconstruct_candidate("kbn", "1*2^12 + 3").unwrap();
+        let t1 = verify_tier1("kbn", "1*2^12 + 3", &c, "deterministic (n<2^64)");
+        assert!(matches!(t1, VerifyResult::Skipped { .. }), "{:?}", t1);
+    }
+
     /// Parse a raw decimal palindrome "10301" -> 10301 (prime palindrome).
     /// Palindromic expressions are stored as plain decimal strings.
     #[test]
@@ -894,6 +1411,16 @@ mod tests {
         assert_eq!(c, Integer::from(10301u32));
     }
 
+    /// Gaussian primes "a+bi" are rebuilt as their norm: "4+1i" -> 17.
+    /// On an axis the nonzero part is the candidate: "0+7i" -> 7.
+    #[test]
+    fn reconstruct_gaussian() {
+        let c = reconstruct_candidate("gaussian", "4+1i").unwrap();
+        assert_eq!(c, Integer::from(17u32));
+        let c = reconstruct_candidate("gaussian", "0+7i").unwrap();
+        assert_eq!(c, Integer::from(7u32));
+    }
+
     /// Parse near-repdigit with m=0: "10^3 - 1 - 8*10^1" -> 919.
     /// Format 1 (single power): 999 - 80 = 919 (prime palindrome).
     #[test]
@@ -1132,6 +1659,40 @@ mod tests {
         }
     }
 
+    /// `verify --format json` rows carry every key, with nulls for the
+    /// fields an outcome does not have.
+    #[test]
+    fn verify_record_serializes_outcomes() {
+        let detail = PrimeDetail {
+            id: 7,
+            form: "factorial".into(),
+            expression: "5! + 1".into(),
+            digits: 3,
+            found_at: chrono::Utc::now(),
+            search_params: "{}".into(),
+            proof_method: "deterministic".into(),
+        };
+        let failed = VerifyRecord::new(&detail, &verify_prime(&detail));
+        assert_eq!(failed.status, "failed");
+        let json = serde_json::to_value(&failed).unwrap();
+        assert_eq!(json["id"], 7);
+        assert_eq!(json["expression"], "5! + 1");
+        assert!(json["tier"].is_null() && json["method"].is_null());
+        assert!(json["reason"].is_string());
+
+        let verified = VerifyResult::Verified {
+            method: "tier1-proth".into(),
+            tier: 1,
+        };
+        let json = serde_json::to_value(VerifyRecord::new(&detail, &verified)).unwrap();
+        assert_eq!(json["status"], "verified");
+        assert_eq!(json["tier"], 1);
+        assert_eq!(json["method"], "tier1-proth");
+        assert!(json["reason"].is_null());
+        assert_eq!("json".parse::<VerifyFormat>().unwrap(), VerifyFormat::Json);
+        assert!("xml".parse::<VerifyFormat>().is_err());
+    }
+
     /// Verify full pipeline for a palindromic prime (no tier-1, falls to tier 2).
     ///
     /// 10301 is prime but palindromic form has no deterministic proof.
@@ -1155,6 +1716,55 @@ mod tests {
         }
     }
 
+    // ── Batch Verification ───────────────────────────────────────────
+
+    /// Parallel batch verification returns the same per-prime results, in
+    /// the same order, as the serial path — including failures.
+    #[test]
+    fn verify_batch_parallel_matches_serial() {
+        let cases = [
+            ("kbn", "3*2^5 + 1", 2, "deterministic"),
+            ("palindromic", "10301", 5, "probabilistic"),
+            ("factorial", "11! + 1", 8, "deterministic"),
+            ("kbn", "3*2^4 + 1", 2, "deterministic"),
+            ("bogus_form", "7", 1, "probabilistic"),
+            ("palindromic", "10201", 5, "probabilistic"),
+        ];
+        let primes: Vec<PrimeDetail> = cases
+            .iter()
+            .enumerate()
+            .map(|(i, &(form, expression, digits, proof))| PrimeDetail {
+                id: i as i64 + 1,
+                form: form.into(),
+                expression: expression.into(),
+                digits,
+                found_at: chrono::Utc::now(),
+                search_params: "{}".into(),
+                proof_method: proof.into(),
+            })
+            .collect();
+
+        let serial = verify_batch(&primes, "default", false);
+        let parallel = verify_batch(&primes, "default", true);
+        assert_eq!(serial.len(), primes.len());
+        assert_eq!(serial, parallel);
+        assert!(matches!(
+            serial[0].1,
+            VerifyResult::Verified { tier: 1, .. }
+        ));
+        assert!(matches!(serial[4].1, VerifyResult::Failed { .. }));
+
+        let serial = verify_batch(&primes, "pfgw", false);
+        assert_eq!(serial, verify_batch(&primes, "pfgw", true));
+    }
+
+    /// The PFGW cap follows the cores this process may run on.
+    #[test]
+    fn pfgw_cap_matches_available_cores() {
+        let cores = std::thread::available_parallelism().unwrap().get();
+        assert_eq!(pfgw_max_parallel(), cores);
+    }
+
     // ── Tier 3: PFGW Cross-Verification ──────────────────────────────
     //
     // PFGW uses GWNUM internally (completely independent from GMP),
@@ -1211,11 +1821,79 @@ mod tests {
             proof_method: "deterministic".into(),
         };
         match verify_prime(&detail) {
-            VerifyResult::Failed { reason } => assert!(reason.contains("Digit count mismatch")),
+            VerifyResult::Failed {
+                reason,
+                reason_code,
+            } => {
+                assert!(reason.contains("Digit count mismatch"));
+                assert_eq!(reason_code, VerifyFailureCode::Reconstruct);
+            }
             other => panic!("Expected Failed, got {:?}", other),
         }
     }
 
+    /// `--tool flint` reconstructs the candidate and hands it to FLINT: a
+    /// tier-4 pass with the feature, a skip (never a failure) without it.
+    #[test]
+    fn flint_tool_runs_tier4() {
+        let detail = PrimeDetail {
+            id: 1,
+            form: "kbn".into(),
+            expression: "3*2^5 + 1".into(),
+            digits: 2,
+            found_at: chrono::Utc::now(),
+            search_params: "{}".into(),
+            proof_method: "deterministic".into(),
+        };
+        let result = verify_with_tool(&detail, "flint");
+        #[cfg(feature = "flint")]
+        assert_eq!(
+            result,
+            VerifyResult::Verified {
+                method: "tier4-flint-proof".into(),
+                tier: 4,
+            }
+        );
+        #[cfg(not(feature = "flint"))]
+        assert!(
+            matches!(result, VerifyResult::Skipped { .. }),
+            "{:?}",
+            result
+        );
+    }
+
+    /// A PFGW crash or timeout is an error with a tool code, neither a
+    /// failure of the prime nor a skip like a missing binary.
+    #[test]
+    fn pfgw_tool_failures_have_codes() {
+        let crashed = pfgw_verdict(Some(pfgw::PfgwResult::Failed {
+            reason: "exited with signal 11".into(),
+            timed_out: false,
+        }));
+        match crashed {
+            VerifyResult::Errored { reason_code, .. } => {
+                assert_eq!(reason_code, VerifyFailureCode::ToolError)
+            }
+            other => panic!("Expected Errored, got {:?}", other),
+        }
+        let timed_out = pfgw_verdict(Some(pfgw::PfgwResult::Failed {
+            reason: "timed out after 3600s".into(),
+            timed_out: true,
+        }));
+        match timed_out {
+            VerifyResult::Errored { reason_code, .. } => {
+                assert_eq!(reason_code, VerifyFailureCode::Timeout)
+            }
+            other => panic!("Expected Errored, got {:?}", other),
+        }
+        assert!(matches!(
+            pfgw_verdict(Some(pfgw::PfgwResult::Unavailable {
+                reason: "PFGW binary not found".into()
+            })),
+            VerifyResult::Skipped { .. }
+        ));
+    }
+
     // ── Repunit PFGW Format Conversion ───────────────────────────────
     //
     // PFGW expects algebraic notation "(b^n-1)/(b-1)" rather than
@@ -1253,10 +1931,176 @@ mod tests {
         match result {
             VerifyResult::Verified { .. }
             | VerifyResult::Failed { .. }
-            | VerifyResult::Skipped { .. } => {}
+            | VerifyResult::Skipped { .. }
+            | VerifyResult::Errored { .. } => {}
         }
     }
 
+    // ── Deterministic Upgrade (prove phase) ──────────────────────────
+
+    /// 11!+1 = 39916801 is prime; N-1 = 11! is fully factored, so the prove
+    /// phase upgrades it with a Pocklington N-1 proof.
+    #[test]
+    fn prove_probable_prime_factorial_plus_pocklington() {
+        let (method, cert) = prove_probable_prime("factorial", "11! + 1")
+            .unwrap()
+            .expect("11!+1 should be proven");
+        assert_eq!(method, "deterministic (Pocklington N-1)");
+        assert!(cert.is_none());
+    }
+
+    /// 7!-1 = 5039 is prime; N+1 = 7! is fully factored, so the Morrison proof
+    /// also yields a serialized certificate.
+    #[test]
+    fn prove_probable_prime_factorial_minus_morrison_with_certificate() {
+        let (method, cert) = prove_probable_prime("factorial", "7! - 1")
+            .unwrap()
+            .expect("7!-1 should be proven");
+        assert_eq!(method, "deterministic (Morrison N+1)");
+        assert!(cert.unwrap().contains("Morrison"));
+    }
+
+    /// 3*2^5+1 = 97 is a Proth prime; the kbn path stores the Proth witness.
+    #[test]
+    fn prove_probable_prime_kbn_proth_with_certificate() {
+        let (method, cert) = prove_probable_prime("kbn", "3*2^5 + 1")
+            .unwrap()
+            .expect("3*2^5+1 should be proven");
+        assert_eq!(method, "deterministic");
+        assert!(cert.is_some());
+    }
+
+    /// Forms without a deterministic test are left as PRPs, and composites are
+    /// never upgraded.
+    #[test]
+    fn prove_probable_prime_declines_unprovable() {
+        assert!(prove_probable_prime("wagstaff", "(2^127+1)/3")
+            .unwrap()
+            .is_none());
+        // 5!+1 = 121 = 11^2
+        assert!(prove_probable_prime("factorial", "5! + 1")
+            .unwrap()
+            .is_none());
+        assert!(prove_probable_prime("unknown", "1").is_err());
+    }
+
+    // ── Candidate Replay ─────────────────────────────────────────────
+
+    /// Replaying the Proth prime 3*2^30 + 1 passes every stage, including
+    /// P-1 (forced to entry-tier bounds) and the Proth proof.
+    #[test]
+    fn replay_proth_prime_passes_every_stage() {
+        let report = replay("kbn", "3*2^30 + 1");
+        assert_eq!(report.digits, Some(10));
+        let names: Vec<_> = report.stages.iter().map(|s| s.name).collect();
+        assert_eq!(
+            names,
+            [
+                "reconstruct",
+                "trial_division",
+                "mr_screen",
+                "frobenius",
+                "p1",
+                "proof"
+            ]
+        );
+        for stage in &report.stages {
+            assert_eq!(
+                stage.outcome,
+                StageOutcome::Pass,
+                "{}: {}",
+                stage.name,
+                stage.detail
+            );
+            assert!(
+                stage.elapsed < Duration::from_secs(30),
+                "{} too slow",
+                stage.name
+            );
+        }
+        assert_eq!(report.stages[5].detail, "tier1-kbn-deterministic");
+        assert!(report.passed());
+    }
+
+    /// A composite shows which stages reject it; an unparseable expression
+    /// stops after reconstruction.
+    #[test]
+    fn replay_reports_failing_stages() {
+        // 3*2^4 + 1 = 49 = 7^2
+        let report = replay("kbn", "3*2^4 + 1");
+        assert!(!report.passed());
+        assert_eq!(report.stages[1].outcome, StageOutcome::Fail);
+        assert_eq!(report.stages[2].outcome, StageOutcome::Fail);
+        assert!(report.stages[2].detail.ends_with("divisible by 7"));
+        assert!(report.stages[3].detail.ends_with("divisible by 7"));
+
+        let report = replay("kbn", "garbage");
+        assert_eq!(report.digits, None);
+        assert_eq!(report.stages.len(), 1);
+        assert_eq!(report.stages[0].outcome, StageOutcome::Fail);
+    }
+
+    // ── Explicit Candidates ──────────────────────────────────────────
+
+    /// Parameters render into expressions the matching parser rebuilds.
+    #[test]
+    fn expression_for_round_trips_through_parsers() {
+        let cases = [
+            ("factorial", None, None, 11, false, "11! + 1", 39916801u64),
+            ("primorial", None, None, 7, true, "7# - 1", 209),
+            ("kbn", Some(3), None, 5, false, "3*2^5 + 1", 97),
+            ("cullen_woodall", None, None, 3, true, "3*2^3 - 1", 23),
+            ("wagstaff", None, None, 7, false, "(2^7+1)/3", 43),
+            ("carol_kynea", None, None, 3, true, "(2^3-1)^2-2", 47),
+            ("sophie_germain", Some(3), None, 4, false, "3*2^4-1", 47),
+            ("repunit", None, None, 2, false, "R(10, 2)", 11),
+            ("gen_fermat", None, Some(6), 1, false, "6^(2^1) + 1", 37),
+        ];
+        for (form, k, base, n, minus, expr, value) in cases {
+            assert_eq!(expression_for(form, k, base, Some(n), minus).unwrap(), expr);
+            let c = reconstruct_candidate(form, expr).unwrap();
+            assert_eq!(c, Integer::from(value), "{}", form);
+        }
+        assert!(expression_for("kbn", None, None, Some(5), false).is_err());
+        assert!(expression_for("palindromic", None, None, Some(5), false).is_err());
+    }
+
+    /// 3·2^189 + 1 is a Proth prime: every stage passes and the verdict
+    /// carries a deterministic Proth certificate. 3·2^100 + 1 is composite
+    /// and is rejected with no proof.
+    #[test]
+    fn test_candidate_prime_and_composite() {
+        use crate::certificate::PrimalityCertificate;
+
+        let expr = expression_for("kbn", Some(3), Some(2), Some(189), false).unwrap();
+        let verdict = test_candidate("kbn", &expr);
+        assert!(verdict.prime, "{:?}", verdict.report.stages);
+        assert_eq!(verdict.proof_method.as_deref(), Some("deterministic"));
+        let cert: PrimalityCertificate =
+            serde_json::from_str(verdict.certificate.as_deref().unwrap()).unwrap();
+        assert!(matches!(cert, PrimalityCertificate::Proth { .. }));
+
+        let verdict = test_candidate("kbn", "3*2^100 + 1");
+        assert!(!verdict.prime);
+        assert!(verdict.proof_method.is_none());
+        assert!(verdict
+            .report
+            .stages
+            .iter()
+            .any(|s| s.outcome == StageOutcome::Fail));
+    }
+
+    /// A small prime with no form-specific proof is still recorded as
+    /// deterministic, since BPSW is exact below 2^64.
+    #[test]
+    fn test_candidate_small_prime_is_deterministic() {
+        let verdict = test_candidate("palindromic", "10301");
+        assert!(verdict.prime);
+        let method = verdict.proof_method.as_deref();
+        assert_eq!(method, Some(crate::SMALL_PRIME_PROOF));
+        assert!(verdict.certificate.is_none());
+    }
+
     // ── Volunteer Quorum Logic (BOINC-style Adaptive Replication) ────
     //
     // Determines the minimum number of independent checks required for
@@ -1317,6 +2161,20 @@ mod tests {
         assert_eq!(required_quorum(4, "factorial"), 1);
     }
 
+    /// A highly reliable proven operator (decayed score ≥ 0.98) is
+    /// single-checked even on PRP-only forms; a lower or missing score, or a
+    /// new/untrusted operator, keeps the trust-level quorum.
+    #[test]
+    fn quorum_lowered_for_highly_reliable_nodes() {
+        let quorum = required_quorum_with_reliability;
+        assert_eq!(quorum(2, Some(0.99), "wagstaff"), 1);
+        assert_eq!(quorum(2, Some(0.97), "wagstaff"), 2);
+        assert_eq!(quorum(2, None, "repunit"), 2);
+        assert_eq!(quorum(1, Some(1.0), "kbn"), 2);
+        assert_eq!(quorum(0, Some(1.0), "wagstaff"), 2);
+        assert_eq!(quorum(3, Some(0.5), "wagstaff"), 1);
+    }
+
     /// High-value results (≥100K digits) always get triple-check.
     #[test]
     fn quorum_high_value_always_triple() {
@@ -1485,8 +2343,12 @@ mod tests {
         // 6 should be caught by has_small_factor
         let c = Integer::from(6u32);
         match verify_tier2(&c) {
-            VerifyResult::Failed { reason } => {
+            VerifyResult::Failed {
+                reason,
+                reason_code,
+            } => {
                 assert!(reason.contains("small factor"), "Should fail via trial division: {}", reason);
+                assert_eq!(reason_code, VerifyFailureCode::CompositeProbabilistic);
             }
             other => panic!("Expected Failed for 6, got {:?}", other),
         }
@@ -1549,7 +2411,8 @@ mod tests {
     }
 
     /// Verify full pipeline fails gracefully on an unparseable expression.
-    /// The reconstruction step should fail, returning Failed with "reconstruct" reason.
+    /// The reconstruction step should fail, returning Failed with "reconstruct"
+    /// reason and the `Reconstruct` code.
     #[test]
     fn verify_prime_invalid_expression() {
         let detail = PrimeDetail {
@@ -1562,8 +2425,12 @@ mod tests {
             proof_method: "deterministic".into(),
         };
         match verify_prime(&detail) {
-            VerifyResult::Failed { reason } => {
+            VerifyResult::Failed {
+                reason,
+                reason_code,
+            } => {
                 assert!(reason.contains("reconstruct"), "Should fail on reconstruction: {}", reason);
+                assert_eq!(reason_code, VerifyFailureCode::Reconstruct);
             }
             other => panic!("Expected Failed for invalid expression, got {:?}", other),
         }
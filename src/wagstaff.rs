@@ -18,7 +18,12 @@
 //!    ord ≡ 0 (mod 4) are excluded because their half-order is even and can never
 //!    match an odd prime exponent. Entries are deduplicated and sorted by order.
 //!
-//! 2. **No deterministic proof exists**: Unlike Mersenne or Proth primes, there
+//! 2. **Restricted-class trial division** (`p1::wagstaff_factor_trial`): every
+//!    factor q of (2^p + 1)/3 satisfies q = 2kp + 1 and q ≡ 1, 3 (mod 8), so
+//!    survivors are trial-divided by those q alone, up to k = 2^16 — well past
+//!    the sieve limit at a fraction of one MR round.
+//!
+//! 3. **No deterministic proof exists**: Unlike Mersenne or Proth primes, there
 //!    is no known efficient deterministic test for Wagstaff primes. All results
 //!    are probabilistic (PRP). The Vrba-Reix test (via GWNUM) provides a fast
 //!    PRP test specific to this form.
@@ -46,13 +51,19 @@ use std::sync::Arc;
 use std::time::Instant;
 use tracing::info;
 
+use crate::candidate::{CandidateForm, Wagstaff};
 use crate::checkpoint::{self, Checkpoint};
-use crate::db::Database;
 use crate::events::{self, EventBus};
 use crate::pfgw;
+use crate::prime_sink::{PrimeBuffer, PrimeSink};
 use crate::progress::Progress;
 use crate::CoordinationClient;
-use crate::{exact_digits, mr_screened_test, sieve};
+use crate::{exact_digits, sieve};
+
+/// Largest k tried by the restricted-class trial division (factors q = 2kp + 1).
+/// Reaches past the sieve limit for any p, at a cost of at most 2^16 small
+/// modular exponentiations per candidate.
+const SPECIAL_FACTOR_K_MAX: u64 = 1 << 16;
 
 /// Precomputed sieve data for Wagstaff composites.
 ///
@@ -106,7 +117,7 @@ pub fn search(
     min_exp: u64,
     max_exp: u64,
     progress: &Arc<Progress>,
-    db: &Arc<Database>,
+    db: &dyn PrimeSink,
     rt: &tokio::runtime::Handle,
     checkpoint_path: &Path,
     search_params: &str,
@@ -115,6 +126,9 @@ pub fn search(
     worker_client: Option<&dyn CoordinationClient>,
     event_bus: Option<&EventBus>,
 ) -> Result<()> {
+    let params = serde_json::json!({"min_exp": min_exp, "max_exp": max_exp});
+    crate::search_params::validate("wagstaff", &params)?;
+
     // Generate prime exponents in range (p must be odd prime >= 3)
     let all_primes = sieve::generate_primes(max_exp);
     let candidate_exponents: Vec<u64> = all_primes
@@ -175,6 +189,7 @@ pub fn search(
     }
 
     let mut last_checkpoint = Instant::now();
+    let mut buffer = PrimeBuffer::new(db, rt, worker_client);
     let mut sieved_out: u64 = 0;
     let mut pos = 0;
 
@@ -195,6 +210,7 @@ pub fn search(
             .collect();
 
         sieved_out += (block.len() - survivors.len()) as u64;
+        progress.record_survivors(survivors.iter().copied());
 
         // Test survivors: try PFGW first (50-100x faster), fall back to GMP MR.
         // Note: PRST does not support Wagstaff form — PRST requires k*b^n±1 with integer k,
@@ -203,13 +219,22 @@ pub fn search(
         let found_primes: Vec<_> = survivors
             .into_par_iter()
             .filter_map(|p| {
+                let _busy = crate::progress::busy();
+                // Factors of (2^p+1)/3 are q = 2kp+1 with q ≡ 1, 3 (mod 8); checking
+                // only those classes is far cheaper than building the candidate.
+                let trial_limit = p.saturating_mul(2 * SPECIAL_FACTOR_K_MAX);
+                if crate::p1::wagstaff_factor_trial(p, trial_limit).is_some() {
+                    return None;
+                }
+
                 let two_p_plus_1 = (Integer::from(1u32) << crate::checked_u32(p)) + 1u32;
                 debug_assert!(
                     two_p_plus_1.is_divisible_u(3),
                     "2^{} + 1 must be divisible by 3 for odd prime p",
                     p
                 );
-                let candidate = two_p_plus_1 / 3u32;
+                let candidate = Integer::from(&two_p_plus_1 / 3u32);
+                let _testing = progress.testing(&Wagstaff.expression(p), &candidate);
 
                 // Try GWNUM Vrba-Reix test (when --features gwnum is enabled)
                 #[cfg(feature = "gwnum")]
@@ -229,7 +254,7 @@ pub fn search(
 
                 // Try PFGW acceleration (Wagstaff: PRP only, no deterministic test exists)
                 if let Some(pfgw_result) =
-                    pfgw::try_test(&format!("(2^{}+1)/3", p), &candidate, pfgw::PfgwMode::Prp)
+                    pfgw::try_test(&Wagstaff.expression(p), &candidate, pfgw::PfgwMode::Prp)
                 {
                     match pfgw_result {
                         pfgw::PfgwResult::Prime {
@@ -245,23 +270,23 @@ pub fn search(
                             return Some((p, digits, certainty));
                         }
                         pfgw::PfgwResult::Composite => return None,
-                        pfgw::PfgwResult::Unavailable { .. } => {} // fall through to GMP
+                        pfgw::PfgwResult::Unavailable { .. } | pfgw::PfgwResult::Failed { .. } => {} // fall through to GMP
                     }
                 }
 
                 // Adaptive P-1 pre-filter (Stage 1 + Stage 2, auto-tuned B1/B2)
-                if crate::p1::adaptive_p1_filter(&candidate) {
+                if crate::p1::adaptive_p1_filter_for_form(&candidate, "wagstaff") {
                     return None;
                 }
 
-                let r = mr_screened_test(&candidate, mr_rounds);
+                let r = crate::proof::is_prime_cofactor_with(
+                    &two_p_plus_1,
+                    &[Integer::from(3u32)],
+                    mr_rounds,
+                );
                 if r != IsPrime::No {
                     let digits = exact_digits(&candidate);
-                    let certainty = match r {
-                        IsPrime::Yes => "deterministic",
-                        IsPrime::Probably => "probabilistic",
-                        IsPrime::No => unreachable!(),
-                    };
+                    let certainty = crate::certainty_label(&candidate, r);
                     Some((p, digits, certainty.to_string()))
                 } else {
                     None
@@ -274,7 +299,20 @@ pub fn search(
             .fetch_add(block.len() as u64, Ordering::Relaxed);
 
         for (p, digits, certainty) in found_primes {
-            let expr = format!("(2^{}+1)/3", p);
+            let expr = Wagstaff.expression(p);
+            if crate::defer_unproven(
+                db,
+                rt,
+                event_bus,
+                worker_client.and_then(|wc| wc.search_job_id()),
+                "wagstaff",
+                &expr,
+                digits,
+                search_params,
+                &certainty,
+            )? {
+                continue;
+            }
             progress.found.fetch_add(1, Ordering::Relaxed);
             if let Some(eb) = event_bus {
                 eb.emit(events::Event::PrimeFound {
@@ -282,8 +320,12 @@ pub fn search(
                     expression: expr.clone(),
                     digits,
                     proof_method: certainty.clone(),
+                    search_job_id: worker_client.and_then(|wc| wc.search_job_id()),
                     timestamp: Instant::now(),
                 });
+                eb.check_milestone("wagstaff", &expr, digits, || {
+                    db.get_best_digits_sync(rt, "wagstaff").ok().flatten()
+                });
             } else {
                 info!(
                     expression = %expr,
@@ -292,21 +334,13 @@ pub fn search(
                     "*** PRIME FOUND ***"
                 );
             }
-            db.insert_prime_sync(
-                rt,
-                "wagstaff",
-                &expr,
-                digits,
-                search_params,
-                &certainty,
-                None,
-            )?;
-            if let Some(wc) = worker_client {
-                wc.report_prime("wagstaff", &expr, digits, search_params, &certainty);
-            }
+            buffer.push("wagstaff", &expr, digits, search_params, &certainty, None)?;
         }
 
+        buffer.flush_if_due()?;
+
         if last_checkpoint.elapsed().as_secs() >= 60 {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Wagstaff {
@@ -320,6 +354,7 @@ pub fn search(
         }
 
         if worker_client.is_some_and(|wc| wc.is_stop_requested()) {
+            buffer.flush()?;
             checkpoint::save(
                 checkpoint_path,
                 &Checkpoint::Wagstaff {
@@ -335,6 +370,7 @@ pub fn search(
         pos = block_end;
     }
 
+    buffer.flush()?;
     checkpoint::clear(checkpoint_path);
     info!(sieved_out, total = candidates.len(), "Wagstaff sieve eliminated candidates");
     Ok(())
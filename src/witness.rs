@@ -0,0 +1,262 @@
+//! # Witness — Compositeness Witnesses for Rejected Candidates
+//!
+//! When a candidate fails screening it is simply discarded. To audit a
+//! rejection (for example, a claim that the search missed a prime), the
+//! witnessed test variants in the crate root return a [`CompositenessWitness`]:
+//! a small piece of data that proves `n` composite and that
+//! [`CompositenessWitness::verify`] re-checks from scratch.
+//!
+//! ## Witness Types
+//!
+//! - **Factor**: A non-trivial divisor, from trial division, a perfect-square
+//!   root or a gcd found while choosing Frobenius parameters.
+//! - **MillerRabin**: A base `a` for which the strong probable prime chain
+//!   `a^d, a^(2d), …, a^(2^s·d)` (with `n − 1 = 2^s·d`) never reaches −1 and
+//!   does not start at 1.
+//! - **Euler**: The Frobenius `c` and residue with
+//!   `c^((n−1)/2) ≢ Jacobi(c, n) (mod n)`.
+//! - **Frobenius**: The `(b, c)` with `Jacobi(b² − 4c, n) = −1` and the residue
+//!   `x^n mod (x² − bx + c, n)` that differs from `b − x`.
+//!
+//! MR rejections from GMP use internal bases, so the witness is searched for
+//! again with fixed bases in a fixed order: the same `n` always yields the
+//! same witness.
+
+use rug::Integer;
+use std::fmt;
+
+/// Proof that a number is composite (or below 2), re-checkable with
+/// [`verify`](Self::verify).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompositenessWitness {
+    /// `n < 2`: neither prime nor composite.
+    LessThanTwo,
+    /// A divisor `1 < factor < n`.
+    Factor { factor: Integer },
+    /// A strong-probable-prime witness base.
+    MillerRabin { base: u64 },
+    /// `residue = c^((n−1)/2) mod n` disagrees with `Jacobi(c, n)`.
+    Euler { c: u32, residue: Integer },
+    /// `residue = x^n mod (x² − bx + c, n)` as `[r0, r1]`, not `b − x`.
+    Frobenius {
+        b: u32,
+        c: u32,
+        residue: [Integer; 2],
+    },
+}
+
+impl CompositenessWitness {
+    /// Recompute the witness against `n` without trusting how it was found.
+    ///
+    /// Returns `true` only if the witness proves that `n` is not prime.
+    pub fn verify(&self, n: &Integer) -> bool {
+        match self {
+            CompositenessWitness::LessThanTwo => *n < 2u32,
+            CompositenessWitness::Factor { factor } => {
+                *factor > 1u32 && factor < n && n.is_divisible(factor)
+            }
+            CompositenessWitness::MillerRabin { base } => is_mr_witness(n, *base),
+            CompositenessWitness::Euler { c, residue } => {
+                if *n < 3u32 || n.is_even() {
+                    return false;
+                }
+                let coeff_c = Integer::from(*c);
+                let jacobi_c = coeff_c.clone().jacobi(n);
+                if jacobi_c == 0 {
+                    return false;
+                }
+                let half_nm1 = Integer::from(n - 1u32) >> 1u32;
+                let Ok(actual) = coeff_c.pow_mod(&half_nm1, n) else {
+                    return false;
+                };
+                let expected = if jacobi_c == 1 {
+                    Integer::from(1u32)
+                } else {
+                    Integer::from(n - 1u32)
+                };
+                actual == *residue && actual != expected
+            }
+            CompositenessWitness::Frobenius { b, c, residue } => {
+                if *n < 3u32 || n.is_even() {
+                    return false;
+                }
+                let coeff_b = Integer::from(*b);
+                let coeff_c = Integer::from(*c);
+                // The automorphism only has to hold for primes when x² − bx + c
+                // is irreducible, i.e. the discriminant is a non-residue.
+                let disc = Integer::from(b * b) - Integer::from(4 * c);
+                if disc.jacobi(n) != -1 {
+                    return false;
+                }
+                let actual = crate::poly_pow_mod(n, &coeff_b, &coeff_c, n);
+                let expected = [coeff_b % n, Integer::from(n - 1u32)];
+                actual == *residue && actual != expected
+            }
+        }
+    }
+}
+
+impl fmt::Display for CompositenessWitness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompositenessWitness::LessThanTwo => write!(f, "n < 2"),
+            CompositenessWitness::Factor { factor } => write!(f, "divisible by {}", factor),
+            CompositenessWitness::MillerRabin { base } => {
+                write!(f, "Miller-Rabin witness base {}", base)
+            }
+            CompositenessWitness::Euler { c, .. } => {
+                write!(f, "Euler criterion fails for c={}", c)
+            }
+            CompositenessWitness::Frobenius { b, c, .. } => {
+                write!(f, "x^n != b - x for (b, c) = ({}, {})", b, c)
+            }
+        }
+    }
+}
+
+/// Whether `base` proves odd `n` composite by the strong probable prime test.
+fn is_mr_witness(n: &Integer, base: u64) -> bool {
+    is_strong_witness(n, &Integer::from(base))
+}
+
+/// [`is_mr_witness`] for an arbitrary-precision base in `[2, n − 2]`.
+pub(crate) fn is_strong_witness(n: &Integer, base: &Integer) -> bool {
+    if *n < 5u32 || n.is_even() {
+        return false;
+    }
+    let nm1 = Integer::from(n - 1u32);
+    if *base < 2u32 || nm1 <= *base {
+        return false;
+    }
+    let s = nm1.find_one(0).unwrap_or(0);
+    let d = Integer::from(&nm1 >> s);
+    let Ok(mut x) = base.clone().pow_mod(&d, n) else {
+        return false;
+    };
+    if x == 1u32 || x == nm1 {
+        return false;
+    }
+    for _ in 1..s {
+        x.square_mut();
+        x %= n;
+        if x == nm1 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Find a witness for a number already known to be composite or below 2.
+///
+/// Tries trial division, then the Frobenius test, then MR bases 2, 3, 4, ….
+/// At least three quarters of the bases below `n − 1` are MR witnesses for an
+/// odd composite, so the base search ends almost immediately; it does not end
+/// for a prime, which is why this is only called after a rejection.
+pub(crate) fn find_witness(n: &Integer) -> CompositenessWitness {
+    if *n < 2u32 {
+        return CompositenessWitness::LessThanTwo;
+    }
+    if let Some(&p) = crate::SMALL_PRIMES.iter().find(|&&p| n.is_divisible_u(p)) {
+        if *n != p {
+            let factor = Integer::from(p);
+            return CompositenessWitness::Factor { factor };
+        }
+    }
+    if let Err(witness) = crate::frobenius_test_witnessed(n) {
+        return witness;
+    }
+    let mut base = 2u64;
+    while !is_mr_witness(n, base) {
+        base += 1;
+    }
+    CompositenessWitness::MillerRabin { base }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2^67 − 1 = 193707721 · 761838257287 (Cole, 1903): composite with no
+    /// factor ≤ 311, so it gets past trial division.
+    fn mersenne_67() -> Integer {
+        (Integer::from(1u32) << 67u32) - 1u32
+    }
+
+    /// 2047 = 23 · 89 is the smallest strong pseudoprime to base 2: base 2 is
+    /// a liar and base 3 a witness.
+    #[test]
+    fn mr_witness_skips_strong_liars() {
+        let n = Integer::from(2047u32);
+        assert!(!is_mr_witness(&n, 2));
+        assert!(is_mr_witness(&n, 3));
+        // Primes have no witnesses.
+        let p = Integer::from(1_000_003u32);
+        assert!((2..200).all(|a| !is_mr_witness(&p, a)));
+    }
+
+    /// A rejected composite yields the same witness every time, and the
+    /// witness independently confirms compositeness. M67 passes the Frobenius
+    /// test with (b, c) = (1, 2), so its witness comes from the MR base search.
+    #[test]
+    fn rejected_composite_has_reproducible_witness() {
+        let n = mersenne_67();
+        let first = crate::mr_screened_test_witnessed(&n, 25).unwrap_err();
+        let second = crate::mr_screened_test_witnessed(&n, 25).unwrap_err();
+        assert_eq!(first, second);
+        assert!(matches!(first, CompositenessWitness::MillerRabin { .. }));
+        assert!(first.verify(&n), "{} does not verify", first);
+    }
+
+    #[test]
+    fn primes_pass_witnessed_tests() {
+        for p in [2u64, 3, 5, 1_000_003, 2_305_843_009_213_693_951] {
+            let n = Integer::from(p);
+            assert_eq!(crate::frobenius_test_witnessed(&n), Ok(()), "{}", p);
+            assert_eq!(crate::mr_screened_test_witnessed(&n, 25), Ok(()), "{}", p);
+        }
+    }
+
+    /// Semiprimes of two large primes fail the Euler check on c; the Chernick
+    /// Carmichael number 601 · 1201 · 1801 passes it and fails the automorphism.
+    #[test]
+    fn frobenius_witnesses_verify() {
+        let primes = [1_000_003u64, 1_000_033, 1_000_037, 1_000_039, 1_000_081];
+        for (i, &p) in primes.iter().enumerate() {
+            for &q in &primes[i + 1..] {
+                let n = Integer::from(p) * q;
+                let witness = crate::frobenius_test_witnessed(&n).unwrap_err();
+                assert!(matches!(witness, CompositenessWitness::Euler { .. }));
+                assert!(witness.verify(&n), "{} for {}", witness, n);
+            }
+        }
+
+        let carmichael = Integer::from(1_299_963_601u64);
+        let witness = crate::frobenius_test_witnessed(&carmichael).unwrap_err();
+        let automorphism = matches!(witness, CompositenessWitness::Frobenius { b: 1, c: 2, .. });
+        assert!(automorphism, "{}", witness);
+        assert!(witness.verify(&carmichael));
+    }
+
+    /// Witnesses that don't hold for `n` are rejected.
+    #[test]
+    fn forged_witnesses_fail_verification() {
+        let n = Integer::from(2047u32);
+        assert!(!CompositenessWitness::MillerRabin { base: 2 }.verify(&n));
+        for factor in [Integer::from(7u32), n.clone()] {
+            assert!(!CompositenessWitness::Factor { factor }.verify(&n));
+        }
+        assert!(!CompositenessWitness::LessThanTwo.verify(&n));
+
+        // A genuine witness for one number proves nothing about a prime.
+        let p = Integer::from(1_000_003u32);
+        let semiprime = Integer::from(1_000_003u32) * 1_000_033u32;
+        let carmichael = Integer::from(1_299_963_601u64);
+        for witness in [
+            crate::frobenius_test_witnessed(&semiprime).unwrap_err(),
+            crate::frobenius_test_witnessed(&carmichael).unwrap_err(),
+            find_witness(&mersenne_67()),
+        ] {
+            assert!(!witness.verify(&p), "{} accepted for a prime", witness);
+        }
+    }
+}
@@ -44,6 +44,7 @@ struct RegisterPayload {
     cores: usize,
     search_type: String,
     search_params: String,
+    benchmark_score: f64,
 }
 
 #[derive(Serialize)]
@@ -113,6 +114,7 @@ impl WorkerClient {
             cores,
             search_type: search_type.to_string(),
             search_params: search_params.to_string(),
+            benchmark_score: crate::metrics::benchmark_score(),
         };
 
         match agent.post(&url).send_json(&payload) {
@@ -287,6 +289,7 @@ mod tests {
             cores: 8,
             search_type: "factorial".to_string(),
             search_params: "{\"start\":1,\"end\":100}".to_string(),
+            benchmark_score: 12.5,
         };
         let json = serde_json::to_string(&payload).unwrap();
         assert!(json.contains("worker_id"));
@@ -0,0 +1,21 @@
+-- Probable-prime holding table for `--only-proven` searches.
+--
+-- When a search runs with --only-proven, results whose certainty is
+-- probabilistic are not inserted into `primes`. They are parked here so a
+-- dedicated proof pass can pick them up later and promote proven ones.
+
+CREATE TABLE IF NOT EXISTS prp_candidates (
+    id              BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    form            TEXT NOT NULL,
+    expression      TEXT NOT NULL,
+    digits          BIGINT NOT NULL,
+    search_params   TEXT NOT NULL DEFAULT '',
+    proof_method    TEXT NOT NULL DEFAULT 'probabilistic',
+    found_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    proven_at       TIMESTAMPTZ,            -- set once promoted to `primes`
+    UNIQUE (form, expression)
+);
+
+CREATE INDEX IF NOT EXISTS idx_prp_candidates_unproven
+    ON prp_candidates (form, digits)
+    WHERE proven_at IS NULL;
@@ -0,0 +1,19 @@
+-- Idempotency keys for operator result submission.
+--
+-- An operator that retries `POST /api/v1/nodes/result` after a network flake
+-- (the server committed, the response was lost) would otherwise double-report
+-- its block and inflate credit and trust counts. Clients send a generated
+-- UUID per submission; the first request with a key reserves it here, and
+-- repeats replay the stored response instead of re-applying the result.
+
+CREATE TABLE IF NOT EXISTS operator_result_keys (
+    volunteer_id    UUID NOT NULL REFERENCES operators(id) ON DELETE CASCADE,
+    idempotency_key UUID NOT NULL,
+    block_id        INTEGER NOT NULL,
+    response        JSONB,                  -- NULL while the first request is in flight
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (volunteer_id, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_operator_result_keys_created
+    ON operator_result_keys (created_at);
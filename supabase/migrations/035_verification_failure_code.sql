@@ -0,0 +1,14 @@
+-- Structured cause for failed verifications.
+--
+-- `verification_method` holds a free-text reason when verification fails
+-- (`verification_tier = 0`), which cannot be grouped. The code is one of
+-- reconstruct, composite_probabilistic, proof_mismatch, tool_error, timeout
+-- (see `verify::VerifyFailureCode`) and is cleared when a later run verifies
+-- the prime.
+
+ALTER TABLE primes
+    ADD COLUMN IF NOT EXISTS verification_failure_code TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_primes_verification_failure_code
+    ON primes (verification_failure_code)
+    WHERE verification_failure_code IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Trigram index for substring search over prime expressions.
+--
+-- `GET /api/primes/search?q=3*2^` matches `expression LIKE '%q%'`, which a
+-- B-tree index cannot serve. A pg_trgm GIN index makes infix matches an index
+-- scan instead of a sequential scan of `primes`.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_primes_expression_trgm
+    ON primes USING gin (expression gin_trgm_ops);
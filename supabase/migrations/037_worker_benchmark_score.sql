@@ -0,0 +1,12 @@
+-- Startup benchmark score reported at worker registration.
+--
+-- Workers time a fixed LLR test (3*2^5134-1) before registering and report
+-- LLR tests per second. Stored on both the PG-direct `workers` table and the
+-- operator `operator_nodes` table so block assignment can weight by it.
+--
+-- 028 already created operator_nodes.benchmark_score as REAL, which ADD COLUMN
+-- IF NOT EXISTS leaves alone; the server reads it as f64, so widen it.
+
+ALTER TABLE workers ADD COLUMN IF NOT EXISTS benchmark_score DOUBLE PRECISION;
+ALTER TABLE operator_nodes ADD COLUMN IF NOT EXISTS benchmark_score DOUBLE PRECISION;
+ALTER TABLE operator_nodes ALTER COLUMN benchmark_score TYPE DOUBLE PRECISION;
@@ -0,0 +1,29 @@
+-- Materialized per-operator reliability score.
+--
+-- The score is the share of an operator's verified blocks (across all of its
+-- nodes in `operator_nodes`) that were valid, with each result weighted by
+-- exp(-ln2 * age / 30 days) so old history fades out. Operators with fewer
+-- than 20 verified blocks get no score, so a handful of matches never earns
+-- single-check quorum. The coordinator
+-- refreshes `operator_trust.reliability_score` every 5 minutes; quorum
+-- assignment reads it to single-check highly reliable proven operators.
+
+ALTER TABLE operator_trust ADD COLUMN IF NOT EXISTS reliability_score DOUBLE PRECISION;
+ALTER TABLE operator_trust ADD COLUMN IF NOT EXISTS reliability_refreshed_at TIMESTAMPTZ;
+
+-- Decayed valid/total ratio for one operator. NULL below 20 results.
+CREATE OR REPLACE FUNCTION operator_reliability_decayed(p_volunteer_id UUID)
+RETURNS DOUBLE PRECISION
+LANGUAGE sql STABLE AS $$
+    SELECT CASE WHEN COUNT(*) >= 20
+                THEN SUM(w) FILTER (WHERE valid) / NULLIF(SUM(w), 0)
+           END
+    FROM (
+        SELECT r.valid,
+               EXP(-LN(2) * EXTRACT(EPOCH FROM NOW() - r.completed_at) / (30 * 86400))
+                   AS w
+        FROM node_block_results r
+        JOIN operator_nodes n ON n.worker_id = r.worker_id
+        WHERE n.volunteer_id = p_volunteer_id
+    ) weighted;
+$$;
@@ -0,0 +1,9 @@
+-- Rayon thread-pool utilization sampled on each PG-direct worker heartbeat.
+--
+-- `rayon_threads` is the worker's pool size; `threads_busy` is how many of
+-- those threads were inside an engine's per-candidate closure at the sample.
+-- A pool that is mostly idle while the worker is running points at time spent
+-- blocked on the database or an external tool rather than testing.
+
+ALTER TABLE workers ADD COLUMN IF NOT EXISTS rayon_threads INTEGER;
+ALTER TABLE workers ADD COLUMN IF NOT EXISTS threads_busy INTEGER;
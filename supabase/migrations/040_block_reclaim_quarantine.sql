@@ -0,0 +1,50 @@
+-- Quarantine work blocks that keep stalling.
+--
+-- Every stale reclaim bumps `reclaim_count`. A block that has already been
+-- reclaimed `p_max_reclaims` times is marked 'failed' with a
+-- `quarantine_reason` instead of going back to 'available', so a block that
+-- hangs its workers stops cycling through the fleet and waits for a human.
+--
+-- reclaim_stale_blocks now returns (requeued, quarantined) and only covers
+-- internal blocks; operator blocks have their own 24-hour timeout.
+
+ALTER TABLE work_blocks ADD COLUMN IF NOT EXISTS reclaim_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE work_blocks ADD COLUMN IF NOT EXISTS quarantine_reason TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_work_blocks_quarantined
+    ON work_blocks (search_job_id) WHERE quarantine_reason IS NOT NULL;
+
+DROP FUNCTION IF EXISTS reclaim_stale_blocks(INTEGER);
+CREATE OR REPLACE FUNCTION reclaim_stale_blocks(
+    p_stale_seconds INTEGER DEFAULT 120,
+    p_max_reclaims INTEGER DEFAULT 5
+)
+RETURNS TABLE(requeued INTEGER, quarantined INTEGER)
+LANGUAGE sql
+SET search_path = ''
+AS $$
+    WITH reclaimed AS (
+        UPDATE public.work_blocks wb SET
+            status = CASE WHEN wb.reclaim_count >= p_max_reclaims
+                          THEN 'failed' ELSE 'available' END,
+            quarantine_reason = CASE WHEN wb.reclaim_count >= p_max_reclaims
+                THEN format('stalled %s times; last claimed by %s',
+                            wb.reclaim_count + 1, COALESCE(wb.claimed_by, 'unknown'))
+                END,
+            reclaim_count = wb.reclaim_count + 1,
+            claimed_by = NULL,
+            claimed_at = NULL
+        WHERE wb.status = 'claimed'
+          AND wb.volunteer_id IS NULL
+          AND wb.claimed_at < NOW() - (p_stale_seconds || ' seconds')::interval
+          AND NOT EXISTS (
+              SELECT 1 FROM public.workers
+              WHERE public.workers.worker_id = wb.claimed_by
+                AND public.workers.last_heartbeat > NOW() - INTERVAL '60 seconds'
+          )
+        RETURNING wb.status
+    )
+    SELECT COUNT(*) FILTER (WHERE status = 'available')::INTEGER,
+           COUNT(*) FILTER (WHERE status = 'failed')::INTEGER
+    FROM reclaimed;
+$$;
@@ -0,0 +1,10 @@
+-- Checksum of the sieve survivors a worker tested for a block.
+--
+-- Workers fold every survivor index into an order-independent 64-bit XOR
+-- checksum (see `progress::survivor_checksum`) and store it on completion. A
+-- re-run of the same block must reproduce it, so a mismatch points at a
+-- worker that skipped candidates or computed them wrong. NULL for blocks
+-- completed before this column existed or resumed from a mid-block
+-- checkpoint, whose checksum only covers part of the range.
+
+ALTER TABLE work_blocks ADD COLUMN IF NOT EXISTS result_checksum BIGINT;
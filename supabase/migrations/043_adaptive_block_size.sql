@@ -0,0 +1,17 @@
+-- Adaptive work-block size per search job.
+--
+-- `claim_work_block` hands out `block_size * block_size_multiplier`
+-- candidates: it splits the next available block when that is smaller, and
+-- absorbs the untouched blocks that follow it when that is larger. The
+-- strategy tick halves the multiplier when the job has new failed or
+-- reclaimed blocks and grows it when blocks complete well inside the reclaim
+-- timeout, within the bounds enforced in `db::jobs`.
+--
+-- `block_failures_seen` is the job's failure count (reclaims plus failed
+-- blocks) at the last adjustment, so each failure shrinks the size once;
+-- completions are counted from `block_size_adapted_at`.
+
+ALTER TABLE search_jobs
+    ADD COLUMN IF NOT EXISTS block_size_multiplier DOUBLE PRECISION NOT NULL DEFAULT 1.0,
+    ADD COLUMN IF NOT EXISTS block_failures_seen BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS block_size_adapted_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,14 @@
+-- Quarantine work blocks whose quorum results disagree.
+--
+-- When a verifier's result for an operator block disagrees with the original
+-- (for example one finds a prime and the other doesn't), the block is set to
+-- 'disputed' with a `quarantine_reason` naming both workers. A disputed block
+-- is neither re-queued nor counted as completed, so it waits for a human
+-- instead of letting either result stand.
+
+ALTER TABLE work_blocks DROP CONSTRAINT IF EXISTS work_blocks_status_check;
+ALTER TABLE work_blocks ADD CONSTRAINT work_blocks_status_check
+    CHECK (status IN ('available', 'claimed', 'completed', 'failed', 'disputed'));
+
+CREATE INDEX IF NOT EXISTS idx_work_blocks_disputed
+    ON work_blocks (search_job_id) WHERE status = 'disputed';
@@ -0,0 +1,7 @@
+-- Windowed operator leaderboards.
+--
+-- `GET /api/v1/operators/leaderboard?window=7d|30d` sums the credits granted
+-- since a cutoff, so the ledger needs a range index on `granted_at`.
+
+CREATE INDEX IF NOT EXISTS idx_operator_credits_granted_at
+    ON operator_credits (granted_at, volunteer_id);
@@ -0,0 +1,9 @@
+-- The specific candidate a PG-direct worker is testing, with when it started.
+--
+-- `current` describes the block ("k*2^n-1, n=[1000..2000]"); these columns
+-- name the one candidate under test at the last heartbeat. For multi-hour
+-- tests the start time shows how long it has been running. Both are NULL
+-- between candidates.
+
+ALTER TABLE workers ADD COLUMN IF NOT EXISTS current_candidate TEXT;
+ALTER TABLE workers ADD COLUMN IF NOT EXISTS current_candidate_started_at TIMESTAMPTZ;
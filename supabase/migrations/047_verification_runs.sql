@@ -0,0 +1,25 @@
+-- Resumable bulk re-verification runs.
+--
+-- `verify --all --resumable` records its position here as it goes: the
+-- highest prime id processed and running outcome counts. An interrupted run
+-- stays 'running' and the next invocation with the same form/force/tool picks
+-- it up, fetching only `id > last_processed_id`. At most one run per key is
+-- live at a time.
+
+CREATE TABLE IF NOT EXISTS verification_runs (
+    id BIGSERIAL PRIMARY KEY,
+    form TEXT,
+    force BOOLEAN NOT NULL DEFAULT FALSE,
+    tool TEXT NOT NULL DEFAULT 'default',
+    status TEXT NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'completed')),
+    last_processed_id BIGINT NOT NULL DEFAULT 0,
+    verified BIGINT NOT NULL DEFAULT 0,
+    failed BIGINT NOT NULL DEFAULT 0,
+    skipped BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_verification_runs_live
+    ON verification_runs ((COALESCE(form, '')), force, tool) WHERE status = 'running';
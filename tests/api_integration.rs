@@ -121,6 +121,44 @@ async fn get_status_returns_200() {
     assert!(json.get("active").is_some());
 }
 
+/// Tests that /api/status/checkpoint reports the local search checkpoint.
+///
+/// Exercises: GET /api/status/checkpoint, `checkpoint::load()`.
+///
+/// With no file at the coordinator's checkpoint path the endpoint returns 204
+/// and an empty body; once a kbn checkpoint is saved it returns the parsed
+/// fields and search type.
+#[tokio::test]
+async fn status_checkpoint_returns_parsed_checkpoint() {
+    require_db!();
+    let db = common::setup_test_db().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("darkreach.checkpoint");
+    let state = darkreach::dashboard::AppState::with_db(db, &common::test_db_url(), path.clone());
+    let router = darkreach::dashboard::build_router(state, None);
+
+    let (status, json) = get(router.clone(), "/api/status/checkpoint").await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(json.is_null());
+
+    darkreach::checkpoint::save(
+        &path,
+        &darkreach::checkpoint::Checkpoint::Kbn {
+            last_n: 1234,
+            min_n: Some(1000),
+            max_n: Some(2000),
+            sieve: None,
+        },
+    )
+    .unwrap();
+    let (status, json) = get(router, "/api/status/checkpoint").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["search_type"], "kbn");
+    assert_eq!(json["last_n"], 1234);
+    assert_eq!(json["min_n"], 1000);
+    assert_eq!(json["max_n"], 2000);
+}
+
 /// Verifies the /api/fleet endpoint returns 200 with empty worker list.
 ///
 /// Exercises: GET /api/fleet, fleet status aggregation.
@@ -136,6 +174,73 @@ async fn get_fleet_returns_200() {
     assert_eq!(json["total_workers"], 0);
 }
 
+/// Verifies the /api/fleet/topology endpoint reports coordinator CPU features.
+///
+/// Exercises: GET /api/fleet/topology, `gwnum::detect_features()`.
+///
+/// The coordinator is always listed as the first server, and the detected
+/// SIMD features are exposed with the FFT path GWNUM would select.
+#[tokio::test]
+async fn get_fleet_topology_returns_200() {
+    require_db!();
+    let (status, json) = get(app().await, "/api/fleet/topology").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["cpu_features"].get("avx512f").is_some());
+    assert!(json["fft_path"].is_string());
+    assert_eq!(json["servers"][0]["role"], "service");
+}
+
+/// Verifies a PG worker's startup benchmark is reported by /api/fleet/topology.
+///
+/// Exercises: `PgWorkerClient::new()` (benchmark + `workers` upsert),
+/// `metrics::benchmark_score()`, GET /api/fleet/topology.
+///
+/// The worker registers from a blocking thread (the client drives its own
+/// queries through the runtime handle), then the topology's compute server
+/// for that host must carry a positive `benchmark_score`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fleet_topology_reports_worker_benchmark_score() {
+    require_db!();
+    let db = common::setup_test_db().await;
+    let pool = db.pool().clone();
+    let handle = tokio::runtime::Handle::current();
+    tokio::task::spawn_blocking(move || {
+        darkreach::pg_worker::PgWorkerClient::new(pool, handle, "bench-worker", "kbn", "")
+    })
+    .await
+    .unwrap();
+
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let router = darkreach::dashboard::build_router(state, None);
+    let (status, json) = get(router, "/api/fleet/topology").await;
+    assert_eq!(status, StatusCode::OK);
+    let compute = json["servers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["role"] == "compute")
+        .expect("registered worker should appear as a compute server");
+    assert_eq!(compute["worker_ids"][0], "bench-worker");
+    assert!(compute["benchmark_score"].as_f64().unwrap() > 0.0);
+}
+
+/// Verifies the /api/observability/efficiency endpoint returns a forms array.
+///
+/// Exercises: GET /api/observability/efficiency, `db.get_form_efficiency()`.
+///
+/// With no search_job metric samples recorded, no form has efficiency data.
+#[tokio::test]
+async fn get_observability_efficiency_returns_200() {
+    require_db!();
+    let (status, json) = get(app().await, "/api/observability/efficiency").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["forms"], serde_json::json!([]));
+}
+
 /// Verifies the /api/searches endpoint returns 200 with a searches array.
 ///
 /// Exercises: GET /api/searches, active search listing.
@@ -188,6 +293,40 @@ async fn get_docs_returns_200() {
     assert!(json.get("docs").is_some());
 }
 
+/// Verifies /api/docs/forms describes every search form's parameters.
+///
+/// Exercises: GET /api/docs/forms, `search_params::FORMS` serialization.
+///
+/// Each of the 12+ forms carries its subcommand, typed parameters with
+/// `required` flags, a range constraint over two of them and OEIS references.
+/// Matching against the CLI is covered by `cli_tests::form_schema_matches_cli_arguments`.
+#[tokio::test]
+async fn get_docs_forms_lists_every_form() {
+    require_db!();
+    let (status, json) = get(app().await, "/api/docs/forms").await;
+    assert_eq!(status, StatusCode::OK);
+    let forms = json["forms"].as_array().unwrap();
+    assert!(forms.len() >= 12);
+    let kbn = forms.iter().find(|f| f["search_type"] == "kbn").unwrap();
+    let names: Vec<&str> = kbn["params"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|p| p["required"] == true)
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["k", "base", "min_n", "max_n"]);
+    assert_eq!(kbn["params"][1]["type"], "u32");
+    assert_eq!(kbn["params"][1]["min"], 2);
+    let range = serde_json::json!({"lower": "min_n", "upper": "max_n"});
+    assert_eq!(kbn["range"], range);
+    for form in forms {
+        assert!(form["subcommand"].is_string());
+        assert!(form["oeis"].is_array());
+        assert!(!form["params"].as_array().unwrap().is_empty());
+    }
+}
+
 /// Verifies the volunteer worker latest-release endpoint returns channel metadata.
 ///
 /// Exercises: GET /api/volunteer/worker/latest, release channel lookup.
@@ -355,6 +494,144 @@ async fn releases_upsert_rejects_non_array_artifacts() {
     assert_eq!(json["error"], "artifacts must be a JSON array");
 }
 
+/// Tests that widening a rollout whose nodes are crash-looping pauses it.
+///
+/// Exercises: POST /api/releases/rollout health gate,
+/// `db.get_release_health()`.
+///
+/// 1.1.0-test is on the canary channel at 10%. Three of its four nodes
+/// stopped heartbeating ten minutes ago, so asking for 50% must return 409
+/// and drop the channel to 0% (serving 1.0.0-test again) instead.
+#[tokio::test]
+async fn releases_rollout_pauses_on_high_crash_rate() {
+    require_db!();
+    let db = common::setup_test_db().await;
+    let artifacts = serde_json::json!([]);
+    for version in ["1.0.0-test", "1.1.0-test"] {
+        db.upsert_worker_release(version, &artifacts, None, None)
+            .await
+            .unwrap();
+    }
+    db.set_worker_release_channel("canary", "1.0.0-test", 100, None)
+        .await
+        .unwrap();
+    db.set_worker_release_channel("canary", "1.1.0-test", 10, None)
+        .await
+        .unwrap();
+
+    let op = db
+        .register_operator("canary_op", "canary@example.com")
+        .await
+        .unwrap();
+    for i in 0..4 {
+        db.register_operator_node(
+            op.id,
+            &format!("canary-node-{}", i),
+            "canary.local",
+            8,
+            "Test CPU",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("1.1.0-test"),
+            Some("canary"),
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    sqlx::query(
+        "UPDATE operator_nodes SET last_heartbeat = NOW() - INTERVAL '10 minutes'
+         WHERE worker_id <> 'canary-node-0'",
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let health = db.get_release_health("canary", 30).await.unwrap().unwrap();
+    assert_eq!((health.workers, health.disconnected), (4, 3));
+
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let router = darkreach::dashboard::build_router(state, None);
+    let (status, json) = post_json(
+        router.clone(),
+        "/api/releases/rollout",
+        serde_json::json!({
+            "channel": "canary",
+            "version": "1.1.0-test",
+            "rollout_percent": 50
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["paused"], true);
+    assert_eq!(json["channel"]["rollout_percent"], 0);
+
+    let (_, json) = get(router.clone(), "/api/releases/events?channel=canary").await;
+    assert_eq!(json["events"][0]["changed_by"], "health-gate");
+    let (_, json) = get(
+        router,
+        "/api/volunteer/worker/latest?channel=canary&worker_id=abc",
+    )
+    .await;
+    assert_eq!(json["version"], "1.0.0-test");
+}
+
+/// Verifies /api/primes/{id}/decimal returns the exact decimal expansion.
+///
+/// Exercises: GET /api/primes/{id}/decimal, `verify::reconstruct_candidate`,
+/// the on-disk decimal cache and the 413 digit limit.
+///
+/// 27! + 1 (OEIS A002981) is rebuilt from its expression and served as plain
+/// text. A prime recorded with 2M digits is refused with 413 before anything
+/// is reconstructed, and an unknown id is 404.
+#[tokio::test]
+async fn get_prime_decimal_returns_exact_value() {
+    require_db!();
+    let db = common::setup_test_db().await;
+    db.insert_prime("factorial", "27! + 1", 29, "{}", "deterministic", None)
+        .await
+        .unwrap();
+    let huge = "400000! + 1";
+    db.insert_prime("factorial", huge, 2_000_000, "{}", "probabilistic", None)
+        .await
+        .unwrap();
+    let ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM primes ORDER BY digits")
+        .fetch_all(db.pool())
+        .await
+        .unwrap();
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let router = darkreach::dashboard::build_router(state, None);
+
+    let uri = format!("/api/primes/{}/decimal", ids[0]);
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_type = response.headers()["content-type"].to_str().unwrap();
+    assert!(content_type.starts_with("text/plain"), "{}", content_type);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"10888869450418352160768000001");
+
+    let uri = format!("/api/primes/{}/decimal", ids[1]);
+    let (status, json) = get(router.clone(), &uri).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(json["error"].as_str().unwrap().contains("confirm=1"));
+
+    let (status, _) = get(router, "/api/primes/999999999/decimal").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 // == Worker API ================================================================
 // Tests for the internal worker-to-coordinator API: registration, heartbeat,
 // prime submission, and deregistration. These endpoints are called by darkreach
@@ -504,6 +781,47 @@ async fn post_search_job_creates_blocks() {
     assert_eq!(json["search_jobs"].as_array().unwrap().len(), 1);
 }
 
+/// Tests that an identical live job is reused by default, with its own blocks.
+///
+/// Exercises: POST /api/search_jobs dedup (200 OK on reuse), block count of
+/// the reused job, the `reuse_existing: false` opt-out.
+///
+/// Creates a factorial job over [1, 500] in blocks of 100, then posts the same
+/// search with block_size 50. The first job comes back with `reused: true`
+/// and its 5 blocks, not the 10 the second payload would have produced; with
+/// `reuse_existing: false` a second job is created.
+#[tokio::test]
+async fn post_search_job_reuse_reports_existing_blocks() {
+    require_db!();
+    let router = app().await;
+    let payload = |block_size: i64| {
+        serde_json::json!({
+            "search_type": "factorial",
+            "params": {"start": 1, "end": 500},
+            "range_start": 1,
+            "range_end": 500,
+            "block_size": block_size
+        })
+    };
+
+    let (status, first) = post_json(router.clone(), "/api/search_jobs", payload(100)).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, json) = post_json(router.clone(), "/api/search_jobs", payload(50)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["reused"], true);
+    assert_eq!(json["id"], first["id"]);
+    assert_eq!(json["blocks"], 5);
+
+    let mut duplicate = payload(50);
+    duplicate["reuse_existing"] = false.into();
+    let (status, json) = post_json(router.clone(), "/api/search_jobs", duplicate).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(json["reused"], false);
+    assert_ne!(json["id"], first["id"]);
+    assert_eq!(json["blocks"], 10);
+}
+
 /// Tests that creating a search job with an invalid range returns 400.
 ///
 /// Exercises: POST /api/search_jobs input validation (range_start >= range_end).
@@ -604,6 +922,69 @@ async fn cancel_search_job() {
     assert_eq!(detail["job"]["status"], "cancelled");
 }
 
+/// Tests the per-search throughput time series.
+///
+/// Exercises: GET /api/searches/{id}/throughput, `db.get_search_throughput()`.
+///
+/// Seeds four `search_job.total_tested` samples 10 minutes apart, the last
+/// one lower than its predecessor to simulate a worker restart. The endpoint
+/// must return one rate per interval (1/s, 2/s) and clamp the reset to 0.
+#[tokio::test]
+async fn search_throughput_computes_rates() {
+    require_db!();
+    let router = app().await;
+
+    let (_, create_json) = post_json(
+        router.clone(),
+        "/api/search_jobs",
+        serde_json::json!({
+            "search_type": "kbn",
+            "params": {"k": 3, "base": 2},
+            "range_start": 1,
+            "range_end": 100,
+            "block_size": 50
+        }),
+    )
+    .await;
+    let job_id = create_json["id"].as_i64().unwrap();
+
+    let db = darkreach::db::Database::connect(&common::test_db_url())
+        .await
+        .unwrap();
+    let now = chrono::Utc::now();
+    let samples: Vec<_> = [(40, 0.0), (30, 600.0), (20, 1800.0), (10, 300.0)]
+        .iter()
+        .map(|&(mins_ago, value)| darkreach::db::MetricSample {
+            ts: now - chrono::Duration::minutes(mins_ago),
+            scope: "search_job".to_string(),
+            metric: "search_job.total_tested".to_string(),
+            value,
+            labels: Some(serde_json::json!({ "job_id": job_id.to_string() })),
+        })
+        .collect();
+    db.insert_metric_samples(&samples).await.unwrap();
+
+    let (status, json) = get(
+        router.clone(),
+        &format!("/api/searches/{}/throughput?window=1h", job_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let rates: Vec<f64> = json["points"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["value"].as_f64().unwrap())
+        .collect();
+    assert_eq!(rates.len(), 3);
+    assert!((rates[0] - 1.0).abs() < 1e-6);
+    assert!((rates[1] - 2.0).abs() < 1e-6);
+    assert_eq!(rates[2], 0.0);
+
+    let (status, _) = get(router, "/api/searches/999999/throughput").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 // == Agent API =================================================================
 // Tests for the agent management REST endpoints: task creation, retrieval,
 // event listing, and budget information.
@@ -909,6 +1290,57 @@ async fn operator_work_claim_returns_204_when_empty() {
     );
 }
 
+/// Tests that a kbn work claim can carry the block's pre-sieved survivors.
+///
+/// Exercises: GET /api/v1/work?survivors=true, `kbn::sieve_survivors()`.
+///
+/// The claimed block's survivor lists must lie inside its n range and match
+/// a fresh local sieve of the same block, so the node can skip sieving.
+#[tokio::test]
+async fn operator_work_claim_includes_presieved_survivors() {
+    require_db!();
+    let db = common::setup_test_db().await;
+    let op = db
+        .register_operator("sieve_worker", "sieve@example.com")
+        .await
+        .unwrap();
+    let params = serde_json::json!({"search_type": "kbn", "k": 3, "base": 2});
+    db.create_search_job("kbn", &params, 5000, 5299, 100)
+        .await
+        .unwrap();
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let router = darkreach::dashboard::build_router(state, None);
+
+    let response = router
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/work?cores=4&ram_gb=16&survivors=true")
+                .header("authorization", format!("Bearer {}", op.api_key))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let start = json["block_start"].as_u64().unwrap();
+    let end = json["block_end"].as_u64().unwrap();
+    let survivors: darkreach::kbn::SieveSurvivors =
+        serde_json::from_value(json["survivors"].clone()).unwrap();
+
+    assert!(!survivors.plus.is_empty() && !survivors.minus.is_empty());
+    for &n in survivors.plus.iter().chain(&survivors.minus) {
+        assert!((start..=end).contains(&n), "n={} outside block", n);
+    }
+    let local = darkreach::kbn::sieve_survivors(3, 2, start, end, 0, None).unwrap();
+    assert_eq!(Some(survivors), local);
+}
+
 /// Tests the public leaderboard endpoint with operator registration.
 ///
 /// Exercises: GET /api/v1/leaderboard (no authentication required),
@@ -967,3 +1399,592 @@ async fn operator_leaderboard_endpoint() {
         assert!(entry.get("worker_count").is_some());
     }
 }
+
+/// Tests the leaderboard time window.
+///
+/// Exercises: GET /api/v1/operators/leaderboard?window=7d|all,
+/// `db.get_leaderboard_windowed()`.
+///
+/// `old_timer` earned 100 credit ten days ago and 5 today; `newcomer` earned
+/// 50 today. The 7-day window ranks only the recent grants (newcomer first),
+/// `all` ranks lifetime credit, and an unknown window is a 400.
+#[tokio::test]
+async fn operator_leaderboard_time_window() {
+    require_db!();
+    let db = common::setup_test_db().await;
+    let old = db
+        .register_operator("old_timer", "old@example.com")
+        .await
+        .unwrap();
+    let new = db
+        .register_operator("newcomer", "new@example.com")
+        .await
+        .unwrap();
+    db.grant_credit(old.id, 1, 100, "block_completed")
+        .await
+        .unwrap();
+    sqlx::query(
+        "UPDATE operator_credits SET granted_at = NOW() - INTERVAL '10 days'
+         WHERE volunteer_id = $1",
+    )
+    .bind(old.id)
+    .execute(db.pool())
+    .await
+    .unwrap();
+    db.grant_credit(old.id, 2, 5, "block_completed")
+        .await
+        .unwrap();
+    db.grant_credit(new.id, 3, 50, "block_completed")
+        .await
+        .unwrap();
+
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let router = darkreach::dashboard::build_router(state, None);
+    let ranking = |json: &serde_json::Value| -> Vec<(String, i64)> {
+        let entries = json.as_array().unwrap().iter();
+        entries
+            .map(|e| {
+                (
+                    e["username"].as_str().unwrap().to_string(),
+                    e["credit"].as_i64().unwrap(),
+                )
+            })
+            .collect()
+    };
+
+    let uri = "/api/v1/operators/leaderboard?window=7d";
+    let (status, json) = get(router.clone(), uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let expected = [("newcomer".to_string(), 50), ("old_timer".to_string(), 5)];
+    assert_eq!(ranking(&json), expected);
+
+    let uri = "/api/v1/operators/leaderboard?window=all";
+    let (status, json) = get(router.clone(), uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let expected = [("old_timer".to_string(), 105), ("newcomer".to_string(), 50)];
+    assert_eq!(ranking(&json), expected);
+
+    let uri = "/api/v1/operators/leaderboard?window=1y";
+    let (status, _) = get(router, uri).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// The observability snapshot has the coordinator, fleet, workers, jobs and
+/// events sections, with the seeded worker and job in them.
+#[tokio::test]
+async fn observability_snapshot_reports_seeded_fleet() {
+    require_db!();
+    let db = common::setup_test_db().await;
+    db.upsert_worker("snap-worker", "snap-host", 8, "kbn", "{}")
+        .await
+        .unwrap();
+    let params = serde_json::json!({"search_type": "kbn"});
+    let job_id = db
+        .create_search_job("kbn", &params, 0, 300, 100)
+        .await
+        .unwrap();
+    let blocks = db
+        .claim_work_blocks(job_id, "snap-worker", 1)
+        .await
+        .unwrap();
+    db.complete_work_block(blocks[0].block_id, 100, 1)
+        .await
+        .unwrap();
+
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let router = darkreach::dashboard::build_router(state, None);
+    let (status, json) = get(router, "/api/observability/snapshot").await;
+    assert_eq!(status, StatusCode::OK);
+    for key in ["ts", "coordinator", "fleet", "workers", "jobs", "events"] {
+        assert!(json.get(key).is_some(), "missing {}", key);
+    }
+
+    assert_eq!(json["fleet"]["workers_connected"], 1);
+    assert_eq!(json["fleet"]["total_cores"], 8);
+    let worker = &json["workers"][0];
+    assert_eq!(worker["worker_id"], "snap-worker");
+    assert_eq!(worker["hostname"], "snap-host");
+    assert_eq!(worker["search_type"], "kbn");
+
+    let jobs = json["jobs"].as_array().unwrap();
+    let job = jobs.iter().find(|j| j["job_id"] == job_id).unwrap();
+    assert_eq!(job["search_type"], "kbn");
+    assert_eq!(job["blocks"]["completed"], 1);
+    assert_eq!(job["blocks"]["available"], 2);
+    assert_eq!(job["blocks"]["total_tested"], 100);
+    let pct = job["completion_pct"].as_f64().unwrap();
+    assert!((pct - 100.0 / 3.0).abs() < 1e-9, "{}", pct);
+}
+
+/// Sets up two operators with one node each and a completed factorial block
+/// from operator A's node (one prime found) queued for verification.
+///
+/// Returns the router, both operators' API keys, the block id and the
+/// operator ids.
+async fn quorum_fixture() -> (
+    Router,
+    std::sync::Arc<darkreach::dashboard::AppState>,
+    [String; 2],
+    i64,
+    [uuid::Uuid; 2],
+) {
+    let db = common::setup_test_db().await;
+    let mut operators = Vec::new();
+    for (name, node) in [("quorum_a", "quorum-node-a"), ("quorum_b", "quorum-node-b")] {
+        let email = format!("{}@example.com", name);
+        let op = db.register_operator(name, &email).await.unwrap();
+        db.register_operator_node(
+            op.id,
+            node,
+            "quorum.local",
+            8,
+            "Test CPU",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        operators.push(op);
+    }
+
+    let params = serde_json::json!({"form": "factorial", "start": 1, "end": 100});
+    let job_id = db
+        .create_search_job("factorial", &params, 1, 100, 100)
+        .await
+        .unwrap();
+    let block_id: i64 = sqlx::query_scalar("SELECT id FROM work_blocks WHERE search_job_id = $1")
+        .bind(job_id)
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    sqlx::query("UPDATE work_blocks SET status = 'completed', found = 1 WHERE id = $1")
+        .bind(block_id)
+        .execute(db.pool())
+        .await
+        .unwrap();
+    db.queue_verification(
+        block_id,
+        job_id,
+        1,
+        101,
+        100,
+        1,
+        "quorum-node-a",
+        Some(operators[0].id),
+    )
+    .await
+    .unwrap();
+
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let router = darkreach::dashboard::build_router(state.clone(), None);
+    (
+        router,
+        state,
+        [operators[0].api_key.clone(), operators[1].api_key.clone()],
+        block_id,
+        [operators[0].id, operators[1].id],
+    )
+}
+
+/// Operator B's node claims the queued re-run through the work endpoint and
+/// submits `found` through the result endpoint; returns the result response.
+async fn rerun_through_api(
+    router: &Router,
+    api_key: &str,
+    block_id: i64,
+    found: i64,
+) -> serde_json::Value {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/nodes/work?cores=8&worker_id=quorum-node-b")
+                .header("authorization", format!("Bearer {}", api_key))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let work: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(work["block_id"], block_id);
+    let verification_id = work["verification_id"]
+        .as_i64()
+        .expect("the re-run carries its verification id");
+
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/nodes/result")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {}", api_key))
+                .body(Body::from(
+                    serde_json::json!({
+                        "block_id": block_id,
+                        "tested": 100,
+                        "found": found,
+                        "verification_id": verification_id,
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+/// Tests that disagreeing quorum results dispute the block.
+///
+/// Exercises: GET /api/v1/nodes/work (verification hand-out),
+/// POST /api/v1/nodes/result with `verification_id`,
+/// `AppState::submit_verification_result()`, `db.record_result_disagreement()`.
+///
+/// Operator A's node reports one prime in a block; operator B's node
+/// re-runs it and finds none. The block must become `disputed` with a
+/// quarantine reason, both operators' trust must drop to 0 with one invalid
+/// result each, and an error event must be emitted.
+#[tokio::test]
+async fn conflicting_quorum_results_dispute_block() {
+    require_db!();
+    let (router, state, keys, block_id, operators) = quorum_fixture().await;
+    let db = &state.db;
+
+    let result = rerun_through_api(&router, &keys[1], block_id, 0).await;
+    assert_eq!(result["verification"], "conflict");
+
+    let (status, reason): (String, Option<String>) =
+        sqlx::query_as("SELECT status, quarantine_reason FROM work_blocks WHERE id = $1")
+            .bind(block_id)
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+    assert_eq!(status, "disputed");
+    let reason = reason.expect("disputed blocks carry a reason");
+    assert!(reason.contains("quorum-node-a") && reason.contains("quorum-node-b"));
+
+    for id in operators {
+        let trust = db.get_operator_trust(id).await.unwrap().unwrap();
+        assert_eq!(trust.trust_level, 0, "operator {} keeps its trust", id);
+        assert_eq!(trust.total_invalid, 1);
+    }
+
+    let events = state.event_bus.recent_events(10);
+    let alert = events.iter().find(|e| e.kind == "error").unwrap();
+    assert!(alert.message.contains("disputed"), "{}", alert.message);
+}
+
+/// Tests that agreeing quorum results verify the block.
+///
+/// Exercises: POST /api/v1/nodes/result with `verification_id`,
+/// `db.record_block_result()`.
+///
+/// Both nodes' reliability rows must be keyed by the original work block,
+/// the same key the disputed path uses, and the block must be verified.
+#[tokio::test]
+async fn matching_quorum_results_verify_block() {
+    require_db!();
+    let (router, state, keys, block_id, _) = quorum_fixture().await;
+    let db = &state.db;
+
+    let result = rerun_through_api(&router, &keys[1], block_id, 1).await;
+    assert_eq!(result["verification"], "matched");
+
+    let verified: bool = sqlx::query_scalar("SELECT verified FROM work_blocks WHERE id = $1")
+        .bind(block_id)
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert!(verified);
+    let keyed: Vec<(String, i64, bool)> = sqlx::query_as(
+        "SELECT worker_id, block_id, valid FROM node_block_results ORDER BY worker_id",
+    )
+    .fetch_all(db.pool())
+    .await
+    .unwrap();
+    assert_eq!(
+        keyed,
+        [
+            ("quorum-node-a".to_string(), block_id, true),
+            ("quorum-node-b".to_string(), block_id, true),
+        ]
+    );
+}
+
+// == Project API ===============================================================
+// Tests for project lifecycle endpoints that drive orchestration directly.
+// ==============================================================================
+
+/// Tests that ticking a freshly activated project starts its first phase.
+///
+/// Exercises: POST /api/projects/{slug}/activate,
+/// POST /api/projects/{slug}/tick, `project::orchestrate_project()`.
+///
+/// The tick must activate the pending sweep phase and return its new search
+/// job, without waiting for the 30-second orchestration loop.
+#[tokio::test]
+async fn project_tick_creates_first_phase_job_immediately() {
+    require_db!();
+    let db = common::setup_test_db().await;
+    let config = darkreach::project::ProjectConfig {
+        project: darkreach::project::ProjectMeta {
+            name: "Tick Test".to_string(),
+            description: "".to_string(),
+            objective: darkreach::project::Objective::Custom,
+            form: "factorial".to_string(),
+            author: "test".to_string(),
+            tags: vec![],
+        },
+        target: darkreach::project::TargetConfig::default(),
+        competitive: None,
+        strategy: darkreach::project::StrategyConfig {
+            auto_strategy: false,
+            prove_phase: false,
+            phases: vec![darkreach::project::PhaseConfig {
+                name: "sweep".to_string(),
+                description: "Factorial sweep".to_string(),
+                search_params: serde_json::json!({
+                    "search_type": "factorial",
+                    "start": 100,
+                    "end": 200,
+                }),
+                block_size: Some(50),
+                depends_on: None,
+                activation_condition: None,
+                completion: "all_blocks_done".to_string(),
+            }],
+        },
+        infrastructure: None,
+        budget: None,
+        workers: None,
+    };
+    let project_id = db.create_project(&config, None).await.unwrap();
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let router = darkreach::dashboard::build_router(state.clone(), None);
+
+    let (status, _) = post_json(
+        router.clone(),
+        "/api/projects/tick-test/tick",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::CONFLICT,
+        "draft projects are not ticked"
+    );
+
+    let (status, _) = post_json(
+        router.clone(),
+        "/api/projects/tick-test/activate",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, json) = post_json(
+        router,
+        "/api/projects/tick-test/tick",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["status"], "active");
+    assert_eq!(json["transitions"][0]["phase"], "sweep");
+    assert_eq!(json["transitions"][0]["from"], "pending");
+    assert_eq!(json["transitions"][0]["to"], "active");
+
+    let phases = state.db.get_project_phases(project_id).await.unwrap();
+    let job_id = phases[0]
+        .search_job_id
+        .expect("sweep phase has a search job");
+    assert_eq!(json["created_jobs"], serde_json::json!([job_id]));
+}
+
+// == WebSocket =================================================================
+// Tests for `/ws` with a live TCP listener (WebSocket upgrades cannot go
+// through `oneshot`). The test keeps a handle on `AppState` to emit events
+// into the same bus the socket subscribes to.
+// ==============================================================================
+
+/// Tests that a `{"subscribe": ["prime"]}` message filters frames by kind.
+///
+/// Exercises: GET /ws upgrade, subscription message, stats suppression, and
+/// `prime_found` broadcast delivery.
+///
+/// The client subscribes immediately after connecting, then a PrimeFound event
+/// and a SearchStarted event are emitted. Over a window longer than the 2s
+/// stats interval the client must see the prime frame and neither an `update`
+/// snapshot nor the `search_start` notification.
+#[tokio::test]
+async fn websocket_subscription_filters_event_kinds() {
+    use darkreach::events::Event;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    require_db!();
+    let db = common::setup_test_db().await;
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let (ws_tx, _) = tokio::sync::broadcast::channel::<String>(256);
+    state.event_bus.set_ws_sender(ws_tx);
+    let router = darkreach::dashboard::build_router(state.clone(), None);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr))
+        .await
+        .unwrap();
+    ws.send(Message::Text(r#"{"subscribe": ["prime"]}"#.into()))
+        .await
+        .unwrap();
+    // Let the handler apply the subscription before events are emitted
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    state.event_bus.emit(Event::SearchStarted {
+        search_type: "kbn".to_string(),
+        params: "{}".to_string(),
+        search_job_id: None,
+        timestamp: std::time::Instant::now(),
+    });
+    state.event_bus.emit(Event::PrimeFound {
+        form: "kbn".to_string(),
+        expression: "3*2^5-1".to_string(),
+        digits: 2,
+        proof_method: "deterministic".to_string(),
+        search_job_id: None,
+        timestamp: std::time::Instant::now(),
+    });
+
+    let mut types = Vec::new();
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+    while let Ok(Some(Ok(msg))) = tokio::time::timeout_at(deadline, ws.next()).await {
+        if let Message::Text(text) = msg {
+            let frame: serde_json::Value = serde_json::from_str(&text).unwrap();
+            types.push(frame["type"].as_str().unwrap_or_default().to_string());
+        }
+    }
+
+    assert!(
+        types.iter().any(|t| t == "prime_found"),
+        "prime frame should pass the subscription, got {:?}",
+        types
+    );
+    assert!(
+        !types.iter().any(|t| t == "update"),
+        "stats frames should be suppressed, got {:?}",
+        types
+    );
+    assert!(
+        !types.iter().any(|t| t == "notification"),
+        "search_start notification should be filtered, got {:?}",
+        types
+    );
+
+    server.abort();
+}
+
+/// Tests that a `{"subscribe": ["stats"]}` client still gets the snapshots.
+///
+/// Exercises: GET /ws upgrade, subscription message, `update` snapshot
+/// delivery, and `prime_found` suppression.
+///
+/// The periodic snapshot goes out as an `update` frame; subscribing to
+/// `stats` must let those through while a PrimeFound broadcast is dropped.
+#[tokio::test]
+async fn websocket_stats_subscription_receives_update_frames() {
+    use darkreach::events::Event;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    require_db!();
+    let db = common::setup_test_db().await;
+    let state = darkreach::dashboard::AppState::with_db(
+        db,
+        &common::test_db_url(),
+        std::path::PathBuf::from("/tmp/darkreach-test-checkpoint"),
+    );
+    let (ws_tx, _) = tokio::sync::broadcast::channel::<String>(256);
+    state.event_bus.set_ws_sender(ws_tx);
+    let router = darkreach::dashboard::build_router(state.clone(), None);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr))
+        .await
+        .unwrap();
+    ws.send(Message::Text(r#"{"subscribe": ["stats"]}"#.into()))
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    state.event_bus.emit(Event::PrimeFound {
+        form: "kbn".to_string(),
+        expression: "3*2^5-1".to_string(),
+        digits: 2,
+        proof_method: "deterministic".to_string(),
+        search_job_id: None,
+        timestamp: std::time::Instant::now(),
+    });
+
+    let mut types = Vec::new();
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+    while let Ok(Some(Ok(msg))) = tokio::time::timeout_at(deadline, ws.next()).await {
+        if let Message::Text(text) = msg {
+            let frame: serde_json::Value = serde_json::from_str(&text).unwrap();
+            types.push(frame["type"].as_str().unwrap_or_default().to_string());
+        }
+    }
+
+    assert!(
+        types.iter().any(|t| t == "update"),
+        "stats subscription should receive update frames, got {:?}",
+        types
+    );
+    assert!(
+        !types.iter().any(|t| t == "prime_found"),
+        "prime frame should be filtered, got {:?}",
+        types
+    );
+
+    server.abort();
+}
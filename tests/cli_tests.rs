@@ -62,9 +62,10 @@ fn darkreach() -> Command {
 ///
 /// Exercises: top-level `clap` parser, subcommand registration.
 ///
-/// The darkreach binary supports 12 search forms (factorial, palindromic, kbn,
+/// The darkreach binary supports 13 search forms (factorial, palindromic, kbn,
 /// primorial, wagstaff, carol-kynea, twin, sophie-germain, repunit, gen-fermat,
-/// cullen-woodall, near-repdigit) plus utility commands (dashboard, work, verify).
+/// cullen-woodall, near-repdigit, gaussian) plus utility commands (dashboard, work, verify,
+/// migrate).
 /// All must appear in the help text.
 #[test]
 fn help_shows_all_subcommands() {
@@ -82,11 +83,57 @@ fn help_shows_all_subcommands() {
             .and(predicate::str::contains("gen-fermat"))
             .and(predicate::str::contains("cullen-woodall"))
             .and(predicate::str::contains("near-repdigit"))
+            .and(predicate::str::contains("gaussian"))
             .and(predicate::str::contains("work"))
-            .and(predicate::str::contains("verify")),
+            .and(predicate::str::contains("verify"))
+            .and(predicate::str::contains("migrate")),
     );
 }
 
+/// Verifies every form in the `/api/docs/forms` schema matches its CLI
+/// subcommand's arguments.
+///
+/// Exercises: `darkreach::search_params::FORMS`, each search subcommand's
+/// `clap` parser.
+///
+/// The options listed by `<subcommand> --help` must be exactly the form's
+/// parameters, in order. Required parameters appear in the usage line;
+/// optional ones show the documented default. `palindromic --order` is a
+/// local enumeration choice rather than a search job parameter (work blocks
+/// always run ascending), so the schema leaves it out.
+#[test]
+fn form_schema_matches_cli_arguments() {
+    let cli_only = [("palindromic", "--order")];
+    let forms = darkreach::search_params::FORMS;
+    assert!(forms.len() >= 12, "only {} forms", forms.len());
+    for form in forms {
+        let output = darkreach()
+            .args([form.subcommand, "--help"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{} --help failed", form.subcommand);
+        let help = String::from_utf8(output.stdout).unwrap();
+        let usage = help.lines().find(|l| l.starts_with("Usage:")).unwrap();
+        let options: Vec<&str> = help
+            .lines()
+            .filter_map(|l| l.split_whitespace().next())
+            .filter(|w| w.starts_with("--") && *w != "--help")
+            .filter(|w| !cli_only.contains(&(form.subcommand, w)))
+            .collect();
+        let flags: Vec<String> = form.params.iter().map(|p| p.flag()).collect();
+        assert_eq!(options, flags, "{}", form.subcommand);
+
+        for param in form.params {
+            let flag = param.flag();
+            let line = help.lines().find(|l| l.trim().starts_with(&flag)).unwrap();
+            match param.default {
+                None => assert!(usage.contains(&flag), "{} {}", form.subcommand, flag),
+                Some(d) => assert!(line.contains(&format!("[default: {}]", d)), "{}", line),
+            }
+        }
+    }
+}
+
 /// Verifies `factorial --help` documents the --start and --end arguments.
 ///
 /// Exercises: factorial subcommand `clap` parser.
@@ -128,6 +175,7 @@ fn help_kbn_shows_args() {
 ///
 /// The palindromic search requires a base and digit range [min-digits, max-digits].
 /// Even-digit palindromes are automatically skipped (always divisible by base+1).
+/// `--order` picks ascending or minimal-change candidate enumeration.
 #[test]
 fn help_palindromic_shows_args() {
     darkreach()
@@ -137,7 +185,8 @@ fn help_palindromic_shows_args() {
         .stdout(
             predicate::str::contains("--base")
                 .and(predicate::str::contains("--min-digits"))
-                .and(predicate::str::contains("--max-digits")),
+                .and(predicate::str::contains("--max-digits"))
+                .and(predicate::str::contains("--order")),
         );
 }
 
@@ -215,6 +264,28 @@ fn palindromic_missing_required_args_fails() {
         .stderr(predicate::str::contains("--min-digits").or(predicate::str::contains("required")));
 }
 
+/// Verifies that `palindromic --order` rejects an unknown order.
+///
+/// Exercises: `PalindromeOrder` parsing in the palindromic subcommand.
+#[test]
+fn palindromic_unknown_order_fails() {
+    darkreach()
+        .args([
+            "--database-url",
+            "postgres://fake",
+            "palindromic",
+            "--min-digits",
+            "1",
+            "--max-digits",
+            "5",
+            "--order",
+            "descending",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("minimal-change"));
+}
+
 /// Verifies that an unreachable database URL causes a connection failure.
 ///
 /// Exercises: database connection error handling, non-zero exit code.
@@ -245,6 +316,235 @@ fn invalid_database_url_fails() {
         .failure();
 }
 
+// == Offline Mode ==============================================================
+// `--output-file` without a database URL routes results to a local JSONL file.
+// These tests run without TEST_DATABASE_URL and must never open a connection.
+// ==============================================================================
+
+/// Verifies a small factorial search in offline mode writes one JSONL line per
+/// prime and needs no database.
+///
+/// Exercises: `--output-file` with `DATABASE_URL` unset, `FilePrimeSink`.
+///
+/// Runs in a scratch directory so no `.env` file can supply a database URL.
+/// In [1, 20] there are 10 factorial primes: n!+1 for n = 1, 2, 3, 11 and
+/// n!-1 for n = 3, 4, 6, 7, 12, 14 (OEIS A002981, A002982).
+#[test]
+fn factorial_offline_writes_jsonl() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("primes.jsonl");
+
+    darkreach()
+        .current_dir(dir.path())
+        .env_remove("DATABASE_URL")
+        .args([
+            "--output-file",
+            output.to_str().unwrap(),
+            "--checkpoint",
+            dir.path().join("offline.checkpoint").to_str().unwrap(),
+            "factorial",
+            "--start",
+            "1",
+            "--end",
+            "20",
+        ])
+        .timeout(std::time::Duration::from_secs(90))
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(&output).unwrap();
+    let records: Vec<serde_json::Value> = contents
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(records.len(), 10, "one line per prime, got:\n{}", contents);
+    for record in &records {
+        assert_eq!(record["form"], "factorial");
+        assert!(record["digits"].as_u64().unwrap() >= 1);
+        assert!(record["proof_method"].is_string());
+        assert!(record.get("certificate").is_some());
+    }
+    let expressions: Vec<&str> = records
+        .iter()
+        .map(|r| r["expression"].as_str().unwrap())
+        .collect();
+    assert!(expressions.contains(&"11! + 1"));
+    assert!(expressions.contains(&"14! - 1"));
+}
+
+/// Verifies that an unknown `LOG_FORMAT` logs a warning and falls back to
+/// human-readable logs instead of refusing to start.
+///
+/// Exercises: `LOG_FORMAT` parsing in `main`, offline search.
+#[test]
+fn invalid_log_format_falls_back_to_human() {
+    let dir = tempfile::tempdir().unwrap();
+    darkreach()
+        .current_dir(dir.path())
+        .env_remove("DATABASE_URL")
+        .env("LOG_FORMAT", "logfmt")
+        .args([
+            "--output-file",
+            dir.path().join("primes.jsonl").to_str().unwrap(),
+            "--checkpoint",
+            dir.path().join("offline.checkpoint").to_str().unwrap(),
+            "factorial",
+            "--start",
+            "1",
+            "--end",
+            "5",
+        ])
+        .timeout(std::time::Duration::from_secs(60))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Invalid log format, using human"));
+}
+
+/// Verifies `run --config` dispatches each `[[search]]` entry to its engine in
+/// turn, each with its own params and checkpoint.
+///
+/// Exercises: `run --config`, `run_batch`, `run_search_block` kbn dispatch,
+/// offline `FilePrimeSink`.
+///
+/// Three kbn specs over n in [2, 12]: 3*2^n+/-1, 5*2^n+/-1 and 7*2^n+/-1
+/// (OEIS A002253/A002235, A002256/A001770, A032360/A001772), in file order.
+#[test]
+fn batch_config_runs_each_search() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("primes.jsonl");
+    let config = dir.path().join("searches.toml");
+    std::fs::write(
+        &config,
+        "[[search]]\nform = \"kbn\"\nstart = 2\nend = 12\nk = 3\nbase = 2\n\n\
+         [[search]]\nform = \"kbn\"\nstart = 2\nend = 12\nk = 5\nbase = 2\n\n\
+         [[search]]\nform = \"kbn\"\nstart = 2\nend = 12\nk = 7\nbase = 2\n",
+    )
+    .unwrap();
+
+    darkreach()
+        .current_dir(dir.path())
+        .env_remove("DATABASE_URL")
+        .args([
+            "--output-file",
+            output.to_str().unwrap(),
+            "--checkpoint",
+            dir.path().join("batch.checkpoint").to_str().unwrap(),
+            "--sieve-limit",
+            "1000",
+            "run",
+            "--config",
+            config.to_str().unwrap(),
+        ])
+        .timeout(std::time::Duration::from_secs(60))
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(&output).unwrap();
+    let expressions: Vec<String> = contents
+        .lines()
+        .map(|l| {
+            let record: serde_json::Value = serde_json::from_str(l).unwrap();
+            assert_eq!(record["form"], "kbn");
+            record["expression"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(
+        expressions,
+        [
+            "3*2^2 + 1", "3*2^2 - 1", "3*2^3 - 1", "3*2^4 - 1", "3*2^5 + 1", "3*2^6 + 1",
+            "3*2^6 - 1", "3*2^7 - 1", "3*2^8 + 1", "3*2^11 - 1", "3*2^12 + 1",
+            "5*2^2 - 1", "5*2^3 + 1", "5*2^4 - 1", "5*2^7 + 1", "5*2^8 - 1", "5*2^10 - 1",
+            "5*2^12 - 1",
+            "7*2^2 + 1", "7*2^4 + 1", "7*2^5 - 1", "7*2^6 + 1", "7*2^9 - 1",
+        ],
+        "searches should run in file order, each over its own k"
+    );
+}
+
+/// Verifies `--only-proven` parks an engine's probabilistic results as
+/// deferred PRP candidates while proven results are stored as primes.
+///
+/// Exercises: `--only-proven`, `defer_unproven` in the Wagstaff engine,
+/// `FilePrimeSink::insert_prp_candidate_sync`.
+///
+/// Wagstaff primes (2^p+1)/3 for p in [3, 130] (OEIS A000978): those below
+/// 2^64 are proven by GMP's deterministic range, while p = 79, 101 and 127
+/// have no proof and must come out deferred.
+#[test]
+fn only_proven_defers_probabilistic_results() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("primes.jsonl");
+
+    darkreach()
+        .current_dir(dir.path())
+        .env_remove("DATABASE_URL")
+        .args([
+            "--only-proven",
+            "--output-file",
+            output.to_str().unwrap(),
+            "--checkpoint",
+            dir.path().join("wagstaff.checkpoint").to_str().unwrap(),
+            "--sieve-limit",
+            "1000",
+            "wagstaff",
+            "--min-exp",
+            "3",
+            "--max-exp",
+            "130",
+        ])
+        .timeout(std::time::Duration::from_secs(60))
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(&output).unwrap();
+    let mut deferred = Vec::new();
+    let mut stored = Vec::new();
+    for line in contents.lines() {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        let expression = record["expression"].as_str().unwrap().to_string();
+        let proof_method = record["proof_method"].as_str().unwrap();
+        if record["deferred"].as_bool().unwrap() {
+            assert!(proof_method.starts_with("probabilistic"), "{}", line);
+            deferred.push(expression);
+        } else {
+            assert!(proof_method.starts_with("deterministic"), "{}", line);
+            stored.push(expression);
+        }
+    }
+    deferred.sort();
+    assert_eq!(deferred, ["(2^101+1)/3", "(2^127+1)/3", "(2^79+1)/3"]);
+    assert_eq!(stored.len(), 11, "proven primes are still stored: {:?}", stored);
+}
+
+/// Verifies that without a database URL or `--output-file`, a search fails
+/// with a message pointing at both options.
+#[test]
+fn search_without_db_or_output_file_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    darkreach()
+        .current_dir(dir.path())
+        .env_remove("DATABASE_URL")
+        .args(["factorial", "--start", "1", "--end", "10"])
+        .timeout(std::time::Duration::from_secs(10))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--output-file"));
+}
+
+/// Verifies `diagnose sieve` re-tests the eliminated 3*2^n+/-1 candidates
+/// and reports a clean sieve without needing a database.
+#[test]
+fn diagnose_sieve_reports_no_false_eliminations() {
+    darkreach()
+        .env_remove("DATABASE_URL")
+        .args(["diagnose", "sieve", "--k", "3", "--base", "2"])
+        .args(["--max-n", "200"])
+        .timeout(std::time::Duration::from_secs(30))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("no false eliminations"));
+}
+
 // == Search Integration Tests ==================================================
 // These tests run actual prime searches against small parameter ranges and
 // verify that known primes are found. They require a test database for result
@@ -434,3 +734,109 @@ fn carol_kynea_finds_primes() {
         .success()
         .stderr(predicate::str::contains("PRIME"));
 }
+
+/// Verifies `verify --format json` prints one object per prime on stdout and
+/// exits nonzero when any of them fails.
+///
+/// Exercises: `run_verify` JSON mode over a seeded prime (`7! - 1`) and a
+/// composite stored as prime (`5! + 1` = 121).
+#[test]
+fn verify_json_reports_failures() {
+    let db_url = db_url_or_skip!();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let db = common::setup_test_db().await;
+        for (expr, digits) in [("7! - 1", 4), ("5! + 1", 3)] {
+            db.insert_prime("factorial", expr, digits, "{}", "deterministic", None)
+                .await
+                .unwrap();
+        }
+    });
+
+    let output = darkreach()
+        .args([
+            "--database-url",
+            &db_url,
+            "verify",
+            "--all",
+            "--format",
+            "json",
+        ])
+        .timeout(std::time::Duration::from_secs(60))
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let records: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let records = records.as_array().unwrap();
+    assert_eq!(records.len(), 2);
+    for record in records {
+        for key in ["id", "expression", "status", "tier", "method", "reason"] {
+            assert!(record.get(key).is_some(), "missing {key} in {record}");
+        }
+    }
+    let status =
+        |expr: &str| records.iter().find(|r| r["expression"] == expr).unwrap()["status"].clone();
+    assert_eq!(status("7! - 1"), "verified");
+    assert_eq!(status("5! + 1"), "failed");
+}
+
+/// Verifies `verify --all --resumable` continues an interrupted run from its
+/// saved position instead of starting over.
+///
+/// Exercises: `run_verify_resumable` over four seeded factorial primes, with a
+/// run already advanced past the first two as if the process had been killed.
+#[test]
+fn verify_resumable_continues_interrupted_run() {
+    let db_url = db_url_or_skip!();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let ids = rt.block_on(async {
+        let db = common::setup_test_db().await;
+        for (expr, digits) in [("3! - 1", 1), ("4! - 1", 2), ("6! - 1", 3), ("7! - 1", 4)] {
+            db.insert_prime("factorial", expr, digits, "{}", "deterministic", None)
+                .await
+                .unwrap();
+        }
+        let primes = db
+            .get_unverified_primes_filtered(10, None, false, 0)
+            .await
+            .unwrap();
+        let run = db
+            .create_or_resume_verification_run(None, false, "default")
+            .await
+            .unwrap();
+        db.record_verification_run_progress(run.id, primes[1].id, 2, 0, 0)
+            .await
+            .unwrap();
+        primes.iter().map(|p| p.id).collect::<Vec<_>>()
+    });
+
+    let output = darkreach()
+        .args([
+            "--database-url",
+            &db_url,
+            "verify",
+            "--all",
+            "--resumable",
+            "--batch-size",
+            "1",
+            "--format",
+            "json",
+        ])
+        .timeout(std::time::Duration::from_secs(60))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Resuming verification run"))
+        .get_output()
+        .stdout
+        .clone();
+    let records: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let seen: Vec<i64> = records
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(seen, ids[2..]);
+}
@@ -74,7 +74,6 @@ pub fn has_test_db() -> bool {
 ///
 /// `std::sync::Once` ensures `run_migrations` executes at most once per process,
 /// even when multiple `#[tokio::test]` functions run concurrently or sequentially.
-/// This avoids redundant DDL operations and "table already exists" errors.
 static SCHEMA_INIT: Once = Once::new();
 
 /// Runs all database migrations exactly once per test suite invocation.
@@ -149,12 +148,13 @@ pub async fn build_test_app() -> axum::Router {
 ///   `agent_task_deps`, `agent_memory`, `agent_events`, `agent_tasks`,
 ///   `agent_budgets`, `agent_templates`, `agent_roles`
 /// - **Projects**: `project_events`, `project_phases`, `projects`
-/// - **Operators**: `operator_credits`, `operator_trust`, `operator_nodes`, `operators`
+/// - **Operators**: `operator_result_keys`, `operator_credits`, `operator_trust`,
+///   `operator_nodes`, `operators`
 /// - **Calibration**: `cost_calibration`
 /// - **Observability**: `metric_rollups_daily`, `metric_rollups_hourly`,
 ///   `metric_samples`, `system_logs`
 /// - **Coordination**: `work_blocks`, `search_jobs`, `workers`
-/// - **Core**: `primes`
+/// - **Core**: `prp_candidates`, `primes`
 ///
 /// # Re-seeded reference data
 ///
@@ -180,10 +180,11 @@ pub async fn truncate_all_tables(pool: &sqlx::PgPool) {
         "TRUNCATE TABLE agent_logs, agent_schedules, agent_role_templates, agent_task_deps, agent_memory,
                        agent_events, agent_tasks, agent_budgets, agent_templates,
                        agent_roles, project_events, project_phases, projects,
-                       operator_credits, operator_trust, operator_nodes, operators,
+                       operator_result_keys, operator_credits, operator_trust, operator_nodes, operators,
                        cost_calibration,
                        metric_rollups_daily, metric_rollups_hourly, metric_samples, system_logs,
-                       work_blocks, search_jobs, workers, primes
+                       verification_queue, verification_runs, node_block_results,
+                       work_blocks, search_jobs, workers, prp_candidates, primes
          CASCADE",
     )
     .execute(pool)
@@ -243,115 +244,17 @@ pub async fn truncate_all_tables(pool: &sqlx::PgPool) {
 
 /// Runs all database migrations against the test database in order.
 ///
-/// # Migration execution order
-///
-/// Migrations are applied sequentially in the order listed below. This mirrors
-/// the production migration order maintained in `supabase/migrations/`. Each
-/// migration builds on the schema established by previous ones:
-///
-/// 1. `001_create_primes.sql` -- Core `primes` table (expression, form, digits, proof)
-/// 2. `002_create_functions.sql` -- Database functions (statistics, aggregates)
-/// 3. `004_coordination_tables.sql` -- `workers`, `search_jobs`, `work_blocks`
-/// 4. `005_verification.sql` -- Verification pipeline tables
-/// 5. `006_agents.sql` -- Agent task system (`agent_tasks`, `agent_events`)
-/// 6. `007_agent_cost_control.sql` -- `agent_budgets`, cost tracking columns
-/// 7. `008_agent_permissions.sql` -- Permission levels, role-based access
-/// 8. `009_agent_memory.sql` -- `agent_memory` key-value store
-/// 9. `010_task_decomposition.sql` -- `agent_templates`, `agent_task_deps`, child tasks
-/// 10. `011_projects.sql` -- `projects`, `project_phases`, `project_events`
-/// 11. `012_form_leaderboard.sql` -- Per-form leaderboard views
-/// 12. `0121_project_cost_tracking.sql` -- Project-level cost aggregation
-/// 13. `013_agent_roles.sql` -- `agent_roles`, `agent_role_templates`
-/// 14. `014_agent_schedules.sql` -- `agent_schedules` for periodic tasks
-/// 15. `015_add_certificate.sql` -- Primality certificate column on `primes`
-/// 16. `016_lifecycle_management.sql` -- Task lifecycle (cancel, timeout)
-/// 17. `017_cost_calibration.sql` -- `cost_calibration` for token cost estimates
-/// 18. `018_agent_observability.sql` -- `agent_logs`, extended event fields
-/// 19. `019_volunteers.sql` -- `operators`, `operator_trust`, `operator_nodes`, `operator_credits`
-/// 20. `020_observability.sql` -- `metric_samples`, `metric_rollups_hourly`, `system_logs`
-/// 21. `021_volunteer_worker_capabilities.sql` -- Worker hardware capability columns
-/// 22. `022_worker_release_channels.sql` -- Release channel management tables
-/// 23. `023_volunteer_worker_release_tracking.sql` -- Per-worker release version tracking
-/// 24. `024_metric_rollups_daily.sql` -- Daily metric rollup materialization
-/// 25. `025_operator_rename.sql` -- Rename volunteers -> operators (terminology change)
-///
-/// Note: Migration `003` is intentionally absent (superseded by later migrations).
-///
-/// # Supabase compatibility
-///
-/// Each migration SQL is passed through `clean_migration_sql()` to strip
-/// Supabase-specific directives (RLS policies, ALTER PUBLICATION for Realtime)
-/// that would fail on a plain PostgreSQL instance.
+/// Uses the same embedded, ordered list as `darkreach migrate`
+/// ([`darkreach::db::migrations::MIGRATIONS`]), with Supabase-specific
+/// statements stripped. Applied files are recorded in `schema_migrations`, so
+/// a test database that is already up to date is left as is.
 ///
 /// # Panics
 ///
-/// Panics with a descriptive message if any migration file is missing or fails
-/// to execute, since a broken schema makes all subsequent tests meaningless.
+/// Panics with a descriptive message if any migration fails to execute, since
+/// a broken schema makes all subsequent tests meaningless.
 async fn run_migrations(pool: &sqlx::PgPool) {
-    let migration_files = [
-        "supabase/migrations/001_create_primes.sql",
-        "supabase/migrations/002_create_functions.sql",
-        "supabase/migrations/004_coordination_tables.sql",
-        "supabase/migrations/005_verification.sql",
-        "supabase/migrations/006_agents.sql",
-        "supabase/migrations/007_agent_cost_control.sql",
-        "supabase/migrations/008_agent_permissions.sql",
-        "supabase/migrations/009_agent_memory.sql",
-        "supabase/migrations/010_task_decomposition.sql",
-        "supabase/migrations/011_projects.sql",
-        "supabase/migrations/012_form_leaderboard.sql",
-        "supabase/migrations/0121_project_cost_tracking.sql",
-        "supabase/migrations/013_agent_roles.sql",
-        "supabase/migrations/014_agent_schedules.sql",
-        "supabase/migrations/015_add_certificate.sql",
-        "supabase/migrations/016_lifecycle_management.sql",
-        "supabase/migrations/017_cost_calibration.sql",
-        "supabase/migrations/018_agent_observability.sql",
-        "supabase/migrations/019_volunteers.sql",
-        "supabase/migrations/020_observability.sql",
-        "supabase/migrations/021_volunteer_worker_capabilities.sql",
-        "supabase/migrations/022_worker_release_channels.sql",
-        "supabase/migrations/023_volunteer_worker_release_tracking.sql",
-        "supabase/migrations/024_metric_rollups_daily.sql",
-        "supabase/migrations/025_operator_rename.sql",
-    ];
-
-    for file in &migration_files {
-        let path = std::path::Path::new(file);
-        if !path.exists() {
-            panic!("Migration file not found: {}", file);
-        }
-        let sql = std::fs::read_to_string(path).unwrap();
-        let cleaned = clean_migration_sql(&sql);
-        if !cleaned.trim().is_empty() {
-            sqlx::raw_sql(&cleaned)
-                .execute(pool)
-                .await
-                .unwrap_or_else(|e| {
-                    panic!("Migration {} failed: {}", file, e);
-                });
-        }
-    }
-}
-
-/// Strips Supabase-specific SQL directives that fail on plain PostgreSQL.
-///
-/// Supabase migrations often include:
-/// - `ALTER PUBLICATION supabase_realtime ...` -- Realtime replication setup
-/// - `ENABLE ROW LEVEL SECURITY` -- RLS requires Supabase auth context
-/// - `CREATE POLICY ...` -- RLS policies reference `auth.uid()` which does not exist
-///
-/// These are production-only concerns. The test database uses direct connections
-/// without Supabase's auth layer, so these statements would cause errors.
-/// Stripping them lets us reuse the same migration files for both environments.
-fn clean_migration_sql(sql: &str) -> String {
-    sql.lines()
-        .filter(|line| {
-            let t = line.trim();
-            !t.starts_with("ALTER PUBLICATION")
-                && !t.contains("ENABLE ROW LEVEL SECURITY")
-                && !t.starts_with("CREATE POLICY")
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
+    darkreach::db::migrations::run(pool, false)
+        .await
+        .unwrap_or_else(|e| panic!("Migrations failed: {:#}", e));
 }
@@ -113,6 +113,69 @@ async fn insert_prime_and_retrieve() {
     assert_eq!(primes[0].proof_method, "deterministic");
 }
 
+/// Tests prime gaps within one kbn family.
+///
+/// Exercises: `db.prime_n_gaps()`.
+///
+/// Seeds 3·2^n + 1 primes at n = 2, 3, 5, 11 (out of order) plus a 5·2^n + 1
+/// prime that the family filter must exclude, and expects gaps 1, 2, 6.
+#[tokio::test]
+async fn prime_n_gaps_within_kbn_family() {
+    require_db!();
+    let db = setup().await;
+    for expr in [
+        "3*2^5 + 1",
+        "3*2^2 + 1",
+        "5*2^4 + 1",
+        "3*2^11 + 1",
+        "3*2^3 + 1",
+    ] {
+        db.insert_prime("kbn", expr, 4, "{}", "deterministic", None)
+            .await
+            .unwrap();
+    }
+
+    let family = serde_json::json!({"k": 3, "base": 2, "c": 1});
+    let gaps = db.prime_n_gaps("kbn", &family).await.unwrap();
+    assert_eq!(gaps, vec![(2, 3, 1), (3, 5, 2), (5, 11, 6)]);
+}
+
+/// Tests that compressed and plaintext certificates read back the same.
+///
+/// Exercises: `db.insert_prime()` with a plaintext certificate, a row holding
+/// a `gz1:` compressed certificate (as written with `CERT_COMPRESS=1`), and
+/// `db.get_prime_certificate()` decompressing transparently.
+#[tokio::test]
+async fn compressed_and_plaintext_certificates_read_back() {
+    require_db!();
+    let db = setup().await;
+
+    let json = r#"{"type":"Pocklington","factors":[{"factor":"2","base":3}]}"#;
+    let expected: serde_json::Value = serde_json::from_str(json).unwrap();
+    db.insert_prime("factorial", "5! + 1", 3, "{}", "deterministic", Some(json))
+        .await
+        .unwrap();
+    let compressed = darkreach::certificate::compress(json).unwrap();
+    sqlx::query(
+        "INSERT INTO primes (form, expression, digits, found_at, search_params, proof_method, certificate)
+         VALUES ('factorial', '7! + 1', 4, NOW(), '{}', 'deterministic', $1::jsonb)",
+    )
+    .bind(&compressed)
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let primes = db
+        .get_primes_filtered(10, 0, &PrimeFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(primes.len(), 2);
+    for prime in primes {
+        let certificate = db.get_prime_certificate(prime.id).await.unwrap();
+        assert_eq!(certificate, Some(expected.clone()), "{}", prime.expression);
+    }
+}
+
 /// Tests that duplicate prime expressions are silently ignored.
 ///
 /// Exercises: `primes` table UNIQUE constraint on `expression`, `db.insert_prime_ignore()`
@@ -145,6 +208,143 @@ async fn insert_duplicate_expression_ignored() {
     assert_eq!(primes[0].proof_method, "deterministic");
 }
 
+/// Tests that `db.insert_prime_sync()` skips a prime that is already stored.
+///
+/// Exercises: `insert_prime_sync` (INSERT ... ON CONFLICT DO NOTHING), the path
+/// search engines take through `PrimeSink`. A second report of the same
+/// (form, expression) must neither error nor replace the first row.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn insert_prime_sync_ignores_duplicates() {
+    require_db!();
+    let db = setup().await;
+    let rt = tokio::runtime::Handle::current();
+
+    let inserted = tokio::task::spawn_blocking(move || {
+        db.insert_prime_sync(&rt, "kbn", "3*2^5+1", 2, "{}", "deterministic", None)?;
+        db.insert_prime_sync(&rt, "kbn", "3*2^5+1", 2, "{}", "probabilistic", None)?;
+        anyhow::Ok(db)
+    })
+    .await
+    .unwrap();
+    let db = inserted.unwrap();
+
+    let primes = db
+        .get_primes_filtered(10, 0, &PrimeFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(primes.len(), 1);
+    assert_eq!(primes[0].proof_method, "deterministic");
+}
+
+/// Tests that `db.dedupe_primes()` collapses duplicate rows into the first one.
+///
+/// Exercises: `dedupe_primes` keeper selection (earliest `found_at`) and proof
+/// merging. The unique constraint is dropped to seed duplicates the way a
+/// pre-constraint import would have left them, and re-added afterwards, which
+/// also checks that no duplicates survive.
+#[tokio::test]
+async fn dedupe_primes_keeps_first_row_with_best_proof() {
+    require_db!();
+    let db = setup().await;
+    sqlx::query("ALTER TABLE primes DROP CONSTRAINT primes_form_expression_unique")
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+    let stale_certificate = r#"{"method":"pocklington","factors":["2"]}"#;
+    db.insert_prime(
+        "factorial",
+        "7! - 1",
+        4,
+        "{}",
+        "probabilistic",
+        Some(stale_certificate),
+    )
+    .await
+    .unwrap();
+    db.insert_prime("factorial", "7! - 1", 4, "{}", "PRP", None)
+        .await
+        .unwrap();
+    db.insert_prime("factorial", "7! - 1", 4, "{}", "deterministic (LLR)", None)
+        .await
+        .unwrap();
+    db.insert_prime("factorial", "3! - 1", 1, "{}", "deterministic", None)
+        .await
+        .unwrap();
+    let before = db
+        .get_primes_filtered(10, 0, &PrimeFilter::default())
+        .await
+        .unwrap();
+    let first_id = before
+        .iter()
+        .filter(|p| p.expression == "7! - 1")
+        .map(|p| p.id)
+        .min()
+        .unwrap();
+
+    let removed = db.dedupe_primes().await.unwrap();
+    let restored = sqlx::query(
+        "ALTER TABLE primes ADD CONSTRAINT primes_form_expression_unique UNIQUE (form, expression)",
+    )
+    .execute(db.pool())
+    .await;
+    assert_eq!(removed, 2);
+    restored.unwrap();
+
+    let primes = db
+        .get_primes_filtered(10, 0, &PrimeFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(primes.len(), 2);
+    let kept = primes.iter().find(|p| p.expression == "7! - 1").unwrap();
+    assert_eq!(kept.id, first_id);
+    assert_eq!(kept.proof_method, "deterministic (LLR)");
+    // The LLR row carried no certificate, so the probabilistic row's one must
+    // not survive next to the LLR proof method.
+    let certificate: Option<serde_json::Value> =
+        sqlx::query_scalar("SELECT certificate FROM primes WHERE id = $1")
+            .bind(kept.id)
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+    assert_eq!(certificate, None);
+    assert_eq!(db.dedupe_primes().await.unwrap(), 0);
+}
+
+/// Tests that probable primes parked by `--only-proven` stay out of `primes`.
+///
+/// Exercises: `prp_candidates` table UNIQUE constraint on (form, expression),
+/// `db.insert_prp_candidate()` (INSERT ... ON CONFLICT DO NOTHING).
+///
+/// Parks the same PRP twice and verifies a single unproven row exists while the
+/// `primes` table stays empty, so deferred results never count as discoveries.
+#[tokio::test]
+async fn insert_prp_candidate_deferred_from_primes() {
+    require_db!();
+    let db = setup().await;
+
+    db.insert_prp_candidate("factorial", "3610! - 1", 11277, "{}", "probabilistic")
+        .await
+        .unwrap();
+    db.insert_prp_candidate("factorial", "3610! - 1", 11277, "{}", "probabilistic")
+        .await
+        .unwrap();
+
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM prp_candidates WHERE expression = '3610! - 1' AND proven_at IS NULL",
+    )
+    .fetch_one(db.pool())
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+
+    let primes = db
+        .get_primes_filtered(10, 0, &PrimeFilter::default())
+        .await
+        .unwrap();
+    assert!(primes.is_empty());
+}
+
 /// Tests filtering primes by their mathematical form.
 ///
 /// Exercises: `PrimeFilter.form` field, SQL WHERE clause on `primes.form`.
@@ -368,6 +568,275 @@ async fn get_filtered_count() {
     assert_eq!(count, 2);
 }
 
+/// Tests that failed verifications store a failure code that groups by cause.
+///
+/// Exercises: `db.mark_verification_failed()`, `db.record_verification_error()`,
+/// `db.mark_verified()`, `db.count_verification_failures_by_code()`,
+/// `primes.verification_failure_code`.
+///
+/// Fails one prime and records a tool error on another, checks that the
+/// errored prime stays queued for verification, then re-verifies it and
+/// checks that its code is cleared from the grouping.
+#[tokio::test]
+async fn verification_failure_codes_group_by_cause() {
+    require_db!();
+    let db = setup().await;
+
+    db.insert_prime("factorial", "5! + 1", 3, "{}", "det", None)
+        .await
+        .unwrap();
+    db.insert_prime("kbn", "3*2^5 + 1", 2, "{}", "det", None)
+        .await
+        .unwrap();
+    let primes = db
+        .get_primes_filtered(10, 0, &PrimeFilter::default())
+        .await
+        .unwrap();
+    let (a, b) = (primes[0].id, primes[1].id);
+
+    db.mark_verification_failed(a, "Cannot reconstruct: bad", "reconstruct")
+        .await
+        .unwrap();
+    db.record_verification_error(b, "tool_error").await.unwrap();
+    let queued = db.get_unverified_primes(10).await.unwrap();
+    let errored = queued.iter().find(|p| p.id == b).unwrap();
+    assert_eq!(errored.proof_method, "det");
+
+    let from = chrono::Utc::now() - chrono::Duration::hours(1);
+    let to = chrono::Utc::now() + chrono::Duration::hours(1);
+    let by_code = db
+        .count_verification_failures_by_code(from, to)
+        .await
+        .unwrap();
+    assert_eq!(
+        by_code,
+        vec![
+            ("reconstruct".to_string(), 1),
+            ("tool_error".to_string(), 1)
+        ]
+    );
+
+    db.mark_verified(b, "tier2-bpsw+mr10", 2).await.unwrap();
+    let by_code = db
+        .count_verification_failures_by_code(from, to)
+        .await
+        .unwrap();
+    assert_eq!(by_code, vec![("reconstruct".to_string(), 1)]);
+}
+
+/// Tests that persisting primes bumps the per-form `primes_found` counter.
+///
+/// Exercises: `db.insert_prime()`, `db.insert_primes_batch()`,
+/// `prom_metrics::primes_found_total()`, `Metrics::process()` exposition.
+///
+/// Two kbn primes (one single, one batched) and one factorial are inserted;
+/// the counter is global to the test binary, so totals are checked as deltas.
+/// A batch repeating a stored prime skips it instead of failing, and the
+/// repeat is not counted.
+#[tokio::test]
+async fn inserted_primes_count_in_primes_found_total() {
+    require_db!();
+    let db = setup().await;
+    let count = |form: &str| {
+        darkreach::prom_metrics::primes_found_total()
+            .get_or_create(&darkreach::prom_metrics::FormLabel {
+                form: form.to_string(),
+            })
+            .get()
+    };
+    let (kbn, factorial) = (count("kbn"), count("factorial"));
+
+    db.insert_prime("kbn", "3*2^2 + 1", 2, "{}", "det", None)
+        .await
+        .unwrap();
+    db.insert_primes_batch(&[
+        darkreach::db::NewPrime {
+            form: "kbn".into(),
+            expression: "3*2^1 + 1".into(),
+            digits: 1,
+            search_params: "{}".into(),
+            proof_method: "det".into(),
+            certificate: None,
+        },
+        darkreach::db::NewPrime {
+            form: "factorial".into(),
+            expression: "3! + 1".into(),
+            digits: 1,
+            search_params: "{}".into(),
+            proof_method: "det".into(),
+            certificate: None,
+        },
+    ])
+    .await
+    .unwrap();
+
+    assert_eq!(count("kbn"), kbn + 2);
+    assert_eq!(count("factorial"), factorial + 1);
+
+    db.insert_primes_batch(&[
+        darkreach::db::NewPrime {
+            form: "kbn".into(),
+            expression: "3*2^2 + 1".into(),
+            digits: 2,
+            search_params: "{}".into(),
+            proof_method: "det".into(),
+            certificate: None,
+        },
+        darkreach::db::NewPrime {
+            form: "kbn".into(),
+            expression: "3*2^3 + 1".into(),
+            digits: 2,
+            search_params: "{}".into(),
+            proof_method: "det".into(),
+            certificate: None,
+        },
+    ])
+    .await
+    .unwrap();
+    assert_eq!(count("kbn"), kbn + 3);
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM primes WHERE form = 'kbn'")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(stored, 3);
+    let text = darkreach::prom_metrics::Metrics::process().encode();
+    assert!(text.contains(&format!(
+        "darkreach_primes_found_total{{form=\"kbn\"}} {}",
+        kbn + 3
+    )));
+}
+
+/// Tests that an interrupted re-verification run resumes where it stopped.
+///
+/// Exercises: `db.create_or_resume_verification_run()`,
+/// `db.record_verification_run_progress()`, `db.complete_verification_run()`,
+/// the `after_id` bound of `db.get_unverified_primes_filtered()`.
+///
+/// The first pass processes one batch of two and is "interrupted"; the second
+/// call resumes the same run, and the two passes together cover every prime
+/// exactly once. Once completed, the next call starts a fresh run.
+#[tokio::test]
+async fn verification_run_resumes_after_interruption() {
+    require_db!();
+    let db = setup().await;
+
+    for n in 1..=5u64 {
+        db.insert_prime("kbn", &format!("3*2^{} + 1", n), 1, "{}", "det", None)
+            .await
+            .unwrap();
+    }
+
+    let mut processed = Vec::new();
+    let run = db
+        .create_or_resume_verification_run(Some("kbn"), true, "default")
+        .await
+        .unwrap();
+    assert_eq!(run.last_processed_id, 0);
+    let batch = db
+        .get_unverified_primes_filtered(2, Some("kbn"), true, run.last_processed_id)
+        .await
+        .unwrap();
+    for (i, prime) in batch.iter().enumerate() {
+        processed.push(prime.id);
+        db.record_verification_run_progress(run.id, prime.id, i as i64 + 1, 0, 0)
+            .await
+            .unwrap();
+    }
+
+    let resumed = db
+        .create_or_resume_verification_run(Some("kbn"), true, "default")
+        .await
+        .unwrap();
+    assert_eq!(resumed.id, run.id);
+    assert_eq!(resumed.last_processed_id, batch[1].id);
+    assert_eq!(resumed.verified, 2);
+    let mut after_id = resumed.last_processed_id;
+    loop {
+        let batch = db
+            .get_unverified_primes_filtered(2, Some("kbn"), true, after_id)
+            .await
+            .unwrap();
+        let Some(last) = batch.last() else { break };
+        after_id = last.id;
+        processed.extend(batch.iter().map(|p| p.id));
+    }
+    db.complete_verification_run(run.id).await.unwrap();
+
+    let all: Vec<i64> = db
+        .get_unverified_primes_filtered(10, Some("kbn"), true, 0)
+        .await
+        .unwrap()
+        .iter()
+        .map(|p| p.id)
+        .collect();
+    assert_eq!(processed, all);
+
+    let fresh = db
+        .create_or_resume_verification_run(Some("kbn"), true, "default")
+        .await
+        .unwrap();
+    assert_ne!(fresh.id, run.id);
+    assert_eq!(fresh.last_processed_id, 0);
+}
+
+/// Tests expression substring search with form filter and pagination.
+///
+/// Exercises: `db.search_primes()`, `db.count_search_primes()`, the
+/// `idx_primes_expression_trgm` index (migration 036).
+///
+/// `3*2^` is a literal substring match, so `13*2^4 + 1` is returned along with
+/// the k=3, base=2 primes while `33*5^2 + 1` and `5! + 1` are not.
+#[tokio::test]
+async fn search_primes_matches_expression_substring() {
+    require_db!();
+    let db = setup().await;
+
+    for (form, expr) in [
+        ("kbn", "3*2^1 + 1"),
+        ("kbn", "3*2^2 - 1"),
+        ("kbn", "3*2^6 + 1"),
+        ("kbn", "13*2^4 + 1"),
+        ("kbn", "33*5^2 + 1"),
+        ("twin", "3*2^3 +/- 1"),
+        ("factorial", "5! + 1"),
+    ] {
+        db.insert_prime(form, expr, 2, "{}", "deterministic", None)
+            .await
+            .unwrap();
+    }
+
+    let all = db.search_primes("3*2^", None, 50, 0).await.unwrap();
+    let mut found: Vec<&str> = all.iter().map(|p| p.expression.as_str()).collect();
+    found.sort();
+    let expected = [
+        "13*2^4 + 1",
+        "3*2^1 + 1",
+        "3*2^2 - 1",
+        "3*2^3 +/- 1",
+        "3*2^6 + 1",
+    ];
+    assert_eq!(found, expected);
+    assert_eq!(db.count_search_primes("3*2^", None).await.unwrap(), 5);
+
+    let kbn = db.search_primes("3*2^", Some("kbn"), 50, 0).await.unwrap();
+    assert_eq!(kbn.len(), 4);
+    assert!(kbn.iter().all(|p| p.form == "kbn"));
+    let kbn_total = db.count_search_primes("3*2^", Some("kbn")).await.unwrap();
+    assert_eq!(kbn_total, 4);
+
+    // Pages are disjoint and together cover every match, newest first.
+    let page1 = db.search_primes("3*2^", None, 2, 0).await.unwrap();
+    let page2 = db.search_primes("3*2^", None, 2, 2).await.unwrap();
+    let page3 = db.search_primes("3*2^", None, 2, 4).await.unwrap();
+    assert_eq!((page1.len(), page2.len(), page3.len()), (2, 2, 1));
+    let pages = [page1, page2, page3].concat();
+    let ids: Vec<i64> = pages.iter().map(|p| p.id).collect();
+    assert!(ids.windows(2).all(|w| w[0] > w[1]));
+
+    // LIKE metacharacters are literal.
+    assert!(db.search_primes("%", None, 50, 0).await.unwrap().is_empty());
+}
+
 // == Worker Coordination =======================================================
 // Tests for the `workers` table: registration via upsert, deletion, command
 // dispatch (stop/reconfigure), heartbeat RPC, and stale worker pruning.
@@ -501,6 +970,29 @@ async fn worker_set_and_clear_command() {
     assert_eq!(cmd, None);
 }
 
+/// Tests the Rayon utilization columns reported alongside heartbeats.
+///
+/// Exercises: `db.set_worker_thread_utilization()`, `workers.rayon_threads`
+/// and `workers.threads_busy`, `db.get_all_workers()`.
+///
+/// A freshly registered worker has not reported yet, so both are NULL; after
+/// a sample they read back as stored.
+#[tokio::test]
+async fn worker_thread_utilization_round_trip() {
+    require_db!();
+    let db = setup().await;
+
+    db.upsert_worker("w1", "host1", 8, "kbn", "").await.unwrap();
+    let workers = db.get_all_workers().await.unwrap();
+    assert_eq!(workers[0].rayon_threads, None);
+    assert_eq!(workers[0].threads_busy, None);
+
+    db.set_worker_thread_utilization("w1", 8, 5).await.unwrap();
+    let workers = db.get_all_workers().await.unwrap();
+    assert_eq!(workers[0].rayon_threads, Some(8));
+    assert_eq!(workers[0].threads_busy, Some(5));
+}
+
 /// Tests automatic pruning of workers that have stopped heartbeating.
 ///
 /// Exercises: `db.prune_stale_workers(timeout_secs)`, SQL `DELETE WHERE
@@ -575,6 +1067,67 @@ async fn search_job_create_with_blocks() {
     assert_eq!(summary.completed, 0);
 }
 
+/// Tests that an identical live job is reused rather than duplicated.
+///
+/// Exercises: `db.find_or_create_search_job()`, the JSONB params match
+/// against non-terminal jobs.
+///
+/// Creating the same kbn search twice, with the params keys in a different
+/// order, returns the first job's id with `reused = true` and inserts no new
+/// job or blocks. `create_search_job` never dedups. Once the first job is
+/// cancelled, the same search creates a fresh job.
+#[tokio::test]
+async fn search_job_dedups_identical_params() {
+    require_db!();
+    let db = setup().await;
+
+    let first_params: serde_json::Value =
+        serde_json::from_str(r#"{"k": 3, "base": 2, "opts": {"a": 1, "b": 2}}"#).unwrap();
+    let second_params: serde_json::Value =
+        serde_json::from_str(r#"{"opts": {"b": 2, "a": 1}, "base": 2, "k": 3}"#).unwrap();
+
+    let (first, reused) = db
+        .find_or_create_search_job("kbn", &first_params, 1, 1000, 100)
+        .await
+        .unwrap();
+    assert!(!reused);
+    let (second, reused) = db
+        .find_or_create_search_job("kbn", &second_params, 1, 1000, 100)
+        .await
+        .unwrap();
+    assert!(reused);
+    assert_eq!(second, first);
+    assert_eq!(db.get_search_jobs().await.unwrap().len(), 1);
+    assert_eq!(db.get_job_block_summary(first).await.unwrap().available, 10);
+
+    // A different range is a different search.
+    let other = db
+        .create_search_job("kbn", &second_params, 1000, 2000, 100)
+        .await
+        .unwrap();
+    assert_ne!(other, first);
+
+    // Plain creation does not look for an existing job.
+    let duplicate = db
+        .create_search_job("kbn", &first_params, 1, 1000, 100)
+        .await
+        .unwrap();
+    assert_ne!(duplicate, first);
+
+    db.update_search_job_status(first, "cancelled", None)
+        .await
+        .unwrap();
+    db.update_search_job_status(duplicate, "cancelled", None)
+        .await
+        .unwrap();
+    let (fresh, reused) = db
+        .find_or_create_search_job("kbn", &second_params, 1, 1000, 100)
+        .await
+        .unwrap();
+    assert!(!reused);
+    assert_ne!(fresh, first);
+}
+
 /// Tests the search job status lifecycle: running -> cancelled.
 ///
 /// Exercises: `db.update_search_job_status()`, `search_jobs` table UPDATE,
@@ -653,6 +1206,42 @@ async fn get_job_block_summary_after_completion() {
     assert_eq!(summary.total_found, 2);
 }
 
+/// The survivor checksum round-trips through BIGINT bit-for-bit, including
+/// values with the top bit set.
+///
+/// Exercises: `db.complete_work_block_with_checksum()`.
+#[tokio::test]
+async fn complete_work_block_stores_checksum() {
+    require_db!();
+    let db = setup().await;
+    db.upsert_worker("checksum-worker", "host", 4, "kbn", "")
+        .await
+        .unwrap();
+    let params = serde_json::json!({"form": "kbn"});
+    let job_id = db
+        .create_search_job("kbn", &params, 1, 20, 10)
+        .await
+        .unwrap();
+
+    let checksum = 0xdead_beef_0123_4567u64;
+    let block = db
+        .claim_work_block(job_id, "checksum-worker")
+        .await
+        .unwrap()
+        .unwrap();
+    db.complete_work_block_with_checksum(block.block_id, 10, 0, Some(checksum))
+        .await
+        .unwrap();
+
+    let stored: Option<i64> =
+        sqlx::query_scalar("SELECT result_checksum FROM work_blocks WHERE id = $1")
+            .bind(block.block_id)
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+    assert_eq!(stored.map(|c| c as u64), Some(checksum));
+}
+
 // == Agent Task Management =====================================================
 // Tests for the AI agent task system: CRUD operations, status transitions
 // (pending -> in_progress -> completed/cancelled), and task listing by status.
@@ -1737,12 +2326,14 @@ async fn operator_credit_grant_and_accumulate() {
 /// Tests operator node registration, heartbeat, and upsert behavior.
 ///
 /// Exercises: `operator_nodes` table INSERT/UPDATE via `db.register_operator_node()`,
-/// `db.operator_node_heartbeat()`, `db.get_operator_leaderboard()`.
+/// `db.operator_node_heartbeat()`, `db.get_operator_nodes()`,
+/// `db.get_operator_leaderboard()`.
 ///
 /// Registers a worker node with full hardware capabilities (CPU, RAM, GPU, OS, arch),
 /// sends a heartbeat, then re-registers the same worker_id with updated specs
-/// (upsert behavior). Verifies the leaderboard shows exactly 1 worker for the
-/// operator (the upsert did not create a duplicate).
+/// (upsert behavior). Verifies the benchmark score survives a re-registration
+/// that omits it, and the leaderboard shows exactly 1 worker for the operator
+/// (the upsert did not create a duplicate).
 #[tokio::test]
 async fn operator_node_register_and_heartbeat() {
     require_db!();
@@ -1768,6 +2359,7 @@ async fn operator_node_register_and_heartbeat() {
         Some(16),
         Some("0.5.0"),
         Some("stable"),
+        Some(42.5),
     )
     .await
     .unwrap();
@@ -1790,10 +2382,16 @@ async fn operator_node_register_and_heartbeat() {
         None,
         Some("0.6.0"),
         Some("beta"),
+        None,
     )
     .await
     .unwrap();
 
+    // Re-registering without a score keeps the previous benchmark
+    let nodes = db.get_operator_nodes(op.id).await.unwrap();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].benchmark_score, Some(42.5));
+
     // Verify the leaderboard shows 1 worker for this operator
     let leaderboard = db.get_operator_leaderboard(10).await.unwrap();
     let entry = leaderboard.iter().find(|e| e.username == "noderunner");
@@ -1861,6 +2459,129 @@ async fn operator_block_claim_and_submit() {
     assert_eq!(summary.total_found, 3);
 }
 
+/// Tests core-proportional block sizing for operator nodes.
+///
+/// Exercises: `db.claim_operator_block_sized()`, `db.next_operator_job()`,
+/// `operator_block_size()`.
+///
+/// Registers a 1-core and a 32-core node for the same operator, then both
+/// claim from a two-block job. The 1-core node gets a 1/32 slice of the first
+/// block (the tail goes back into the pool as a new available block), and
+/// the 32-core node the whole second block, so block lengths track cores.
+#[tokio::test]
+async fn operator_block_sized_by_node_cores() {
+    require_db!();
+    let db = setup().await;
+
+    let op = db
+        .register_operator("sizer", "sizer@example.com")
+        .await
+        .unwrap();
+    for (worker_id, cores) in [("node-small", 1), ("node-big", 32)] {
+        db.register_operator_node(
+            op.id, worker_id, "host", cores, "cpu", None, None, None, None, None, None, None, None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let params = serde_json::json!({"form": "kbn", "k": 3, "base": 2});
+    let job_id = db
+        .create_search_job("kbn", &params, 1, 6401, 3200)
+        .await
+        .unwrap();
+    let caps = darkreach::db::operators::WorkerCapabilities {
+        cores: 1,
+        ram_gb: 0,
+        has_gpu: false,
+        os: None,
+        arch: None,
+    };
+    assert_eq!(db.next_operator_job(&caps).await.unwrap(), Some(job_id));
+
+    let small_size = darkreach::db::operators::operator_block_size(3200, 1);
+    let big_size = darkreach::db::operators::operator_block_size(3200, 32);
+    let small = db
+        .claim_operator_block_sized(job_id, "node-small", small_size)
+        .await
+        .unwrap()
+        .expect("1-core node should claim a block");
+    let big = db
+        .claim_operator_block_sized(job_id, "node-big", big_size)
+        .await
+        .unwrap()
+        .expect("32-core node should claim a block");
+
+    assert_eq!((small.block_start, small.block_end), (1, 101));
+    assert_eq!((big.block_start, big.block_end), (3201, 6401));
+    assert_eq!(
+        big.block_end - big.block_start,
+        32 * (small.block_end - small.block_start)
+    );
+    assert_eq!(small.search_type.as_deref(), Some("kbn"));
+
+    // The split-off tail [101, 3201) stays claimable
+    let summary = db.get_job_block_summary(job_id).await.unwrap();
+    assert_eq!(summary.claimed, 2);
+    assert_eq!(summary.available, 1);
+
+    assert!(db
+        .claim_operator_block_sized(job_id, "node-unknown", 100)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+/// Tests that core-proportional sizing leaves a checkpointed block whole.
+///
+/// Exercises: `db.claim_operator_block_sized()` with a `block_checkpoint`.
+///
+/// The job's first block was reclaimed with `last_tested` = 1500. A 1-core
+/// node asking for 100 candidates gets that block unsplit, starting just
+/// past the checkpoint, and no tail is added to the pool.
+#[tokio::test]
+async fn operator_block_sized_honors_checkpoint() {
+    require_db!();
+    let db = setup().await;
+
+    let op = db
+        .register_operator("resumer", "resumer@example.com")
+        .await
+        .unwrap();
+    db.register_operator_node(
+        op.id, "node-small", "host", 1, "cpu", None, None, None, None, None, None, None, None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let params = serde_json::json!({"form": "kbn", "k": 3, "base": 2});
+    let job_id = db
+        .create_search_job("kbn", &params, 1, 6401, 3200)
+        .await
+        .unwrap();
+    sqlx::query(
+        "UPDATE work_blocks SET block_checkpoint = '{\"last_tested\": 1500}'
+         WHERE search_job_id = $1 AND block_start = 1",
+    )
+    .bind(job_id)
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let block = db
+        .claim_operator_block_sized(job_id, "node-small", 100)
+        .await
+        .unwrap()
+        .expect("node should claim the checkpointed block");
+    assert_eq!((block.block_start, block.block_end), (1501, 3201));
+
+    let summary = db.get_job_block_summary(job_id).await.unwrap();
+    assert_eq!(summary.claimed, 1);
+    assert_eq!(summary.available, 1, "a checkpointed block is not split");
+}
+
 /// Tests leaderboard ordering by credit (descending).
 ///
 /// Exercises: `db.get_operator_leaderboard()`, `operators` table ORDER BY credit DESC.
@@ -1976,8 +2697,12 @@ async fn operator_stale_block_reclaim() {
     .unwrap();
 
     // Reclaim stale blocks with 24-hour timeout (86400 seconds)
-    let reclaimed = db.reclaim_stale_operator_blocks(86400).await.unwrap();
-    assert_eq!(reclaimed, 1, "Should reclaim 1 stale block");
+    let reclaimed = db
+        .reclaim_stale_operator_blocks(86400, darkreach::db::MAX_BLOCK_RECLAIMS)
+        .await
+        .unwrap();
+    assert_eq!(reclaimed.requeued, 1, "Should reclaim 1 stale block");
+    assert_eq!(reclaimed.quarantined, 0);
 
     // The block should now be available again
     let summary = db.get_job_block_summary(job_id).await.unwrap();
@@ -1985,6 +2710,376 @@ async fn operator_stale_block_reclaim() {
     assert_eq!(summary.claimed, 0, "No blocks should be claimed");
 }
 
+/// Tests that a block which keeps stalling is quarantined instead of requeued.
+///
+/// Exercises: `db.reclaim_stale_blocks(stale_seconds, max_reclaims)`,
+/// `work_blocks.reclaim_count` / `quarantine_reason`, and the `quarantined`
+/// count in `db.get_job_block_summary()`.
+///
+/// A single-block job is claimed by a worker that then goes silent, over and
+/// over. The first `MAX_BLOCK_RECLAIMS` reclaims hand the block back; the next
+/// one marks it 'failed' with a reason, and it can no longer be claimed.
+#[tokio::test]
+async fn repeatedly_stalled_block_is_quarantined() {
+    require_db!();
+    let db = setup().await;
+    let max = darkreach::db::MAX_BLOCK_RECLAIMS;
+
+    db.upsert_worker("hang-worker", "host", 4, "factorial", "")
+        .await
+        .unwrap();
+    let params = serde_json::json!({"form": "factorial"});
+    let job_id = db
+        .create_search_job("factorial", &params, 1, 11, 10)
+        .await
+        .unwrap();
+
+    for attempt in 1..=max + 1 {
+        let block = db
+            .claim_work_block(job_id, "hang-worker")
+            .await
+            .unwrap()
+            .expect("block should be claimable before quarantine");
+        // The worker hangs: its claim ages and its heartbeat stops.
+        let age_claim =
+            "UPDATE work_blocks SET claimed_at = NOW() - INTERVAL '10 minutes' WHERE id = $1";
+        sqlx::query(age_claim)
+            .bind(block.block_id)
+            .execute(db.pool())
+            .await
+            .unwrap();
+        sqlx::query("UPDATE workers SET last_heartbeat = NOW() - INTERVAL '10 minutes'")
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        let reclaimed = db.reclaim_stale_blocks(120, max).await.unwrap();
+        let summary = db.get_job_block_summary(job_id).await.unwrap();
+        if attempt <= max {
+            assert_eq!(reclaimed.requeued, 1, "reclaim {}", attempt);
+            assert_eq!(reclaimed.quarantined, 0);
+            assert_eq!(summary.available, 1);
+        } else {
+            assert_eq!(reclaimed.requeued, 0);
+            assert_eq!(reclaimed.quarantined, 1, "reclaim {}", attempt);
+            assert_eq!(summary.available, 0);
+            assert_eq!(summary.failed, 1);
+            assert_eq!(summary.quarantined, 1);
+        }
+    }
+
+    let (status, count, reason): (String, i32, Option<String>) = sqlx::query_as(
+        "SELECT status, reclaim_count, quarantine_reason FROM work_blocks WHERE search_job_id = $1",
+    )
+    .bind(job_id)
+    .fetch_one(db.pool())
+    .await
+    .unwrap();
+    assert_eq!(status, "failed");
+    assert_eq!(count, max + 1);
+    let reason = reason.expect("quarantined block should record a reason");
+    assert!(reason.contains("hang-worker"), "{}", reason);
+
+    let claim = db.claim_work_block(job_id, "hang-worker").await.unwrap();
+    assert!(claim.is_none(), "quarantined block was handed out");
+}
+
+/// Tests the adaptive block size: failures shrink a job's blocks, fast
+/// completions grow them, both within the multiplier bounds.
+///
+/// Exercises: `db.adapt_block_sizes()`, `db.claim_work_block()` splitting and
+/// merging blocks, `search_jobs.block_size_multiplier`.
+///
+/// A job of 100-candidate blocks fails its first five claims, adapting after
+/// each: the multiplier halves down to the minimum and claims follow it. Then
+/// blocks complete instantly (well inside the reclaim timeout) until the
+/// multiplier reaches the maximum, each claim absorbing the untouched blocks
+/// after it.
+#[tokio::test]
+async fn block_size_adapts_to_failures_and_fast_completions() {
+    require_db!();
+    let db = setup().await;
+    use darkreach::db::{
+        effective_block_size, MAX_BLOCK_SIZE_MULTIPLIER, MIN_BLOCK_SIZE_MULTIPLIER,
+    };
+
+    db.upsert_worker("adaptive-worker", "host", 4, "kbn", "")
+        .await
+        .unwrap();
+    let params = serde_json::json!({"form": "kbn"});
+    let job_id = db
+        .create_search_job("kbn", &params, 0, 20_000, 100)
+        .await
+        .unwrap();
+    let multiplier = || async {
+        sqlx::query_scalar::<_, f64>("SELECT block_size_multiplier FROM search_jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_one(db.pool())
+            .await
+            .unwrap()
+    };
+
+    let mut sizes = Vec::new();
+    for _ in 0..5 {
+        let block = db
+            .claim_work_block(job_id, "adaptive-worker")
+            .await
+            .unwrap()
+            .unwrap();
+        sizes.push(block.block_end - block.block_start);
+        db.fail_work_block(block.block_id).await.unwrap();
+        db.adapt_block_sizes().await.unwrap();
+    }
+    assert_eq!(sizes, vec![100, 50, 25, 13, 13]);
+    assert_eq!(multiplier().await, MIN_BLOCK_SIZE_MULTIPLIER);
+
+    let mut last_size = 0;
+    for _ in 0..20 {
+        let expected = effective_block_size(100, multiplier().await);
+        let block = db
+            .claim_work_block(job_id, "adaptive-worker")
+            .await
+            .unwrap()
+            .unwrap();
+        let size = block.block_end - block.block_start;
+        assert_eq!(size, expected);
+        assert!(size >= last_size, "{} after {}", size, last_size);
+        last_size = size;
+        db.complete_work_block(block.block_id, size, 0)
+            .await
+            .unwrap();
+        db.adapt_block_sizes().await.unwrap();
+    }
+    assert_eq!(multiplier().await, MAX_BLOCK_SIZE_MULTIPLIER);
+    assert_eq!(last_size, 400);
+
+    // Claims tile the range: no candidate lost or duplicated by resizing.
+    let (blocks, covered): (i64, Option<i64>) = sqlx::query_as(
+        "SELECT COUNT(*), SUM(block_end - block_start)::BIGINT
+         FROM work_blocks WHERE search_job_id = $1",
+    )
+    .bind(job_id)
+    .fetch_one(db.pool())
+    .await
+    .unwrap();
+    assert!(blocks > 0);
+    assert_eq!(covered, Some(20_000));
+}
+
+/// A stopping worker releases its claimed blocks for others to pick up,
+/// keeping the checkpoint it stored; the block it already completed, and one another worker has since
+/// reclaimed, stay as they are.
+#[tokio::test]
+async fn release_work_blocks_returns_claims_to_pool() {
+    require_db!();
+    let db = setup().await;
+
+    for worker in ["stopping-worker", "other-worker"] {
+        db.upsert_worker(worker, "host", 4, "kbn", "")
+            .await
+            .unwrap();
+    }
+    let params = serde_json::json!({"form": "kbn"});
+    let job_id = db
+        .create_search_job("kbn", &params, 0, 300, 100)
+        .await
+        .unwrap();
+    let blocks = db
+        .claim_work_blocks(job_id, "stopping-worker", 3)
+        .await
+        .unwrap();
+    assert_eq!(blocks.len(), 3);
+    db.complete_work_block(blocks[0].block_id, 100, 0)
+        .await
+        .unwrap();
+
+    sqlx::query("UPDATE work_blocks SET claimed_by = 'other-worker' WHERE id = $1")
+        .bind(blocks[2].block_id)
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+    let checkpoint = serde_json::json!({"last_tested": blocks[1].block_start + 40});
+    db.update_block_progress(blocks[1].block_id, 80, 0, Some(&checkpoint))
+        .await
+        .unwrap();
+
+    let ids: Vec<i64> = blocks.iter().map(|b| b.block_id).collect();
+    assert_eq!(
+        db.release_work_blocks("stopping-worker", &ids).await.unwrap(),
+        1
+    );
+
+    let summary = db.get_job_block_summary(job_id).await.unwrap();
+    assert_eq!(summary.claimed, 1);
+    assert_eq!(summary.available, 1);
+    let next = db
+        .claim_work_blocks(job_id, "other-worker", 1)
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(next.block_start, blocks[1].block_start);
+    assert_eq!(next.block_checkpoint, Some(checkpoint));
+}
+
+/// Coverage coalesces adjacent same-state blocks and reports a deleted block
+/// as a gap.
+///
+/// Exercises: `db.get_job_coverage()`, `work_blocks` SELECT by job.
+///
+/// A ten-block job over [0, 1000) gets blocks set to a mix of states and the
+/// [800, 900) block removed; the returned ranges must match exactly and,
+/// together with the gap, tile the job range without overlap.
+#[tokio::test]
+async fn job_coverage_coalesces_block_states() {
+    require_db!();
+    let db = setup().await;
+
+    let params = serde_json::json!({"form": "kbn"});
+    let job_id = db
+        .create_search_job("kbn", &params, 0, 1000, 100)
+        .await
+        .unwrap();
+    let states = [
+        (0, "completed"),
+        (100, "completed"),
+        (200, "completed"),
+        (300, "claimed"),
+        (400, "completed"),
+        (500, "failed"),
+        (600, "failed"),
+    ];
+    for (start, status) in states {
+        sqlx::query(
+            "UPDATE work_blocks SET status = $3 WHERE search_job_id = $1 AND block_start = $2",
+        )
+        .bind(job_id)
+        .bind(start as i64)
+        .bind(status)
+        .execute(db.pool())
+        .await
+        .unwrap();
+    }
+    sqlx::query("DELETE FROM work_blocks WHERE search_job_id = $1 AND block_start = 800")
+        .bind(job_id)
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+    let coverage = db.get_job_coverage(job_id).await.unwrap().unwrap();
+    let range = |start, end| darkreach::db::CoverageRange { start, end };
+    assert_eq!(coverage.completed, vec![range(0, 300), range(400, 500)]);
+    assert_eq!(coverage.claimed, vec![range(300, 400)]);
+    assert_eq!(coverage.failed, vec![range(500, 700)]);
+    assert_eq!(coverage.available, vec![range(700, 800), range(900, 1000)]);
+    assert_eq!(coverage.gaps, vec![range(800, 900)]);
+
+    let mut all: Vec<_> = [
+        &coverage.completed,
+        &coverage.claimed,
+        &coverage.available,
+        &coverage.failed,
+        &coverage.gaps,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    all.sort_by_key(|r| r.start);
+    assert_eq!(all.first().unwrap().start, 0);
+    assert_eq!(all.last().unwrap().end, 1000);
+    for pair in all.windows(2) {
+        assert_eq!(pair[0].end, pair[1].start, "{:?}", pair);
+    }
+
+    assert!(db.get_job_coverage(job_id + 1).await.unwrap().is_none());
+}
+/// Tests the time-decayed operator reliability score and its materialization.
+///
+/// Exercises: `db.record_block_result()`, `db.compute_node_reliability()`,
+/// `db.refresh_operator_reliability()`, `operator_trust.reliability_score`.
+///
+/// The operator runs two nodes. Recent history is 8 valid + 2 invalid blocks
+/// (weight ≈ 1 each); 10 more invalid blocks are back-dated 60 days, two
+/// 30-day half-lives, so each counts 0.25. The score is therefore
+/// 8 / (8 + 2 + 2.5) = 0.64. An operator with no history, or with a single
+/// matched block (below the 20-result floor), computes 1.0 (benefit of the
+/// doubt) but materializes as NULL, so it never lowers quorum.
+#[tokio::test]
+async fn operator_reliability_score_decays_and_materializes() {
+    require_db!();
+    let db = setup().await;
+
+    let op = db
+        .register_operator("mixed_history", "mixed@example.com")
+        .await
+        .unwrap();
+    let fresh = db
+        .register_operator("fresh_op", "fresh@example.com")
+        .await
+        .unwrap();
+    let lucky = db
+        .register_operator("one_match", "one@example.com")
+        .await
+        .unwrap();
+    for (owner, worker) in [
+        (op.id, "rel-node-a"),
+        (op.id, "rel-node-b"),
+        (lucky.id, "rel-node-c"),
+    ] {
+        db.register_operator_node(
+            owner,
+            worker,
+            "rel.local",
+            8,
+            "Test CPU",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    // Blocks 0-7 valid on node a, 8-19 invalid on node b (10-19 back-dated below)
+    let history = (0..8)
+        .map(|b| ("rel-node-a", b, true))
+        .chain((8..20).map(|b| ("rel-node-b", b, false)));
+    for (worker, block_id, valid) in history {
+        db.record_block_result(worker, block_id, valid)
+            .await
+            .unwrap();
+    }
+    db.record_block_result("rel-node-c", 20, true).await.unwrap();
+    sqlx::query(
+        "UPDATE node_block_results SET completed_at = NOW() - INTERVAL '60 days'
+         WHERE worker_id = 'rel-node-b' AND block_id >= 10",
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let score = db.compute_node_reliability(op.id).await.unwrap();
+    assert!((score - 0.64).abs() < 1e-4, "expected 0.64, got {}", score);
+    assert_eq!(db.compute_node_reliability(fresh.id).await.unwrap(), 1.0);
+
+    let updated = db.refresh_operator_reliability().await.unwrap();
+    assert!(updated >= 2, "both operators' trust rows should refresh");
+    let trust = db.get_operator_trust(op.id).await.unwrap().unwrap();
+    let stored = trust.reliability_score.expect("score materialized");
+    assert!((stored - score).abs() < 1e-4);
+    let fresh_trust = db.get_operator_trust(fresh.id).await.unwrap().unwrap();
+    assert_eq!(fresh_trust.reliability_score, None);
+    assert_eq!(db.compute_node_reliability(lucky.id).await.unwrap(), 1.0);
+    let lucky_trust = db.get_operator_trust(lucky.id).await.unwrap().unwrap();
+    assert_eq!(lucky_trust.reliability_score, None);
+}
+
 // == Project Management ========================================================
 // Tests for the multi-phase project system: project creation with phase
 // definitions, status transitions (draft -> active -> completed), phase
@@ -2026,6 +3121,7 @@ async fn project_create_and_phases() {
         competitive: None,
         strategy: darkreach::project::StrategyConfig {
             auto_strategy: false,
+            prove_phase: false,
             phases: vec![
                 darkreach::project::PhaseConfig {
                     name: "sweep".to_string(),
@@ -2127,6 +3223,7 @@ async fn project_phase_status_transitions() {
         competitive: None,
         strategy: darkreach::project::StrategyConfig {
             auto_strategy: false,
+            prove_phase: false,
             phases: vec![darkreach::project::PhaseConfig {
                 name: "sweep".to_string(),
                 description: "Single phase".to_string(),
@@ -2242,6 +3339,7 @@ async fn project_event_logging() {
         competitive: None,
         strategy: darkreach::project::StrategyConfig {
             auto_strategy: true,
+            prove_phase: false,
             phases: vec![],
         },
         infrastructure: None,
@@ -2304,3 +3402,662 @@ async fn project_event_logging() {
     let limited = db.get_project_events(project_id, 1).await.unwrap();
     assert_eq!(limited.len(), 1);
 }
+
+/// Tests that a project "prove" phase upgrades accumulated PRPs.
+///
+/// Exercises: `project::orchestrate_tick()` prove-phase dispatch,
+/// `db.get_unproven_primes()`, `db.update_prime_proof()`,
+/// `db.promote_prp_candidate()`.
+///
+/// Inserts 11! + 1 into `primes` as a probabilistic result and parks 7! - 1 in
+/// `prp_candidates`, then runs one orchestration tick over an active project
+/// whose only phase is a proof pass. The PRP must become deterministic, the
+/// parked candidate must be promoted into `primes` with a Morrison certificate,
+/// and the phase and project must both complete without creating a search job.
+#[tokio::test]
+async fn project_prove_phase_upgrades_prp_to_deterministic() {
+    require_db!();
+    let db = setup().await;
+
+    db.insert_prime("factorial", "11! + 1", 8, "{}", "probabilistic", None)
+        .await
+        .unwrap();
+    db.insert_prp_candidate("factorial", "7! - 1", 4, "{}", "probabilistic")
+        .await
+        .unwrap();
+
+    let config = darkreach::project::ProjectConfig {
+        project: darkreach::project::ProjectMeta {
+            name: "Prove Pass Test".to_string(),
+            description: "".to_string(),
+            objective: darkreach::project::Objective::Custom,
+            form: "factorial".to_string(),
+            author: "test".to_string(),
+            tags: vec![],
+        },
+        target: darkreach::project::TargetConfig::default(),
+        competitive: None,
+        strategy: darkreach::project::StrategyConfig {
+            auto_strategy: false,
+            prove_phase: false,
+            phases: vec![darkreach::project::PhaseConfig {
+                name: "prove".to_string(),
+                description: "Proof pass".to_string(),
+                search_params: serde_json::json!({
+                    "action": "prove",
+                    "search_type": "factorial",
+                }),
+                block_size: None,
+                depends_on: None,
+                activation_condition: None,
+                completion: "all_blocks_done".to_string(),
+            }],
+        },
+        infrastructure: None,
+        budget: None,
+        workers: None,
+    };
+    let project_id = db.create_project(&config, None).await.unwrap();
+    db.update_project_status(project_id, "active")
+        .await
+        .unwrap();
+
+    darkreach::project::orchestrate_tick(&db).await.unwrap();
+
+    let primes = db
+        .get_primes_filtered(10, 0, &PrimeFilter::default())
+        .await
+        .unwrap();
+    let upgraded = primes.iter().find(|p| p.expression == "11! + 1").unwrap();
+    assert_eq!(upgraded.proof_method, "deterministic (Pocklington N-1)");
+    let promoted = primes.iter().find(|p| p.expression == "7! - 1").unwrap();
+    assert_eq!(promoted.proof_method, "deterministic (Morrison N+1)");
+    let (has_cert,): (bool,) =
+        sqlx::query_as("SELECT certificate IS NOT NULL FROM primes WHERE id = $1")
+            .bind(promoted.id)
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+    assert!(has_cert);
+
+    let phases = db.get_project_phases(project_id).await.unwrap();
+    assert_eq!(phases[0].status, "completed");
+    assert!(phases[0].search_job_id.is_none());
+    assert_eq!(phases[0].total_tested, 2);
+    assert_eq!(phases[0].total_found, 1);
+
+    let project = db
+        .get_project_by_slug("prove-pass-test")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(project.status, "completed");
+}
+
+/// Tests that a proof pass stops starting proofs once its time budget is spent.
+///
+/// Exercises: `project::orchestrate_tick()` with `time_budget_secs = 0`.
+///
+/// With no budget nothing is attempted: the PRP stays probabilistic and the
+/// phase stays active (the tick is never held past its budget, and the PRP is
+/// left for a later pass rather than dropped).
+#[tokio::test]
+async fn project_prove_phase_respects_time_budget() {
+    require_db!();
+    let db = setup().await;
+
+    db.insert_prime("factorial", "11! + 1", 8, "{}", "probabilistic", None)
+        .await
+        .unwrap();
+
+    let config = darkreach::project::ProjectConfig {
+        project: darkreach::project::ProjectMeta {
+            name: "Prove Budget Test".to_string(),
+            description: "".to_string(),
+            objective: darkreach::project::Objective::Custom,
+            form: "factorial".to_string(),
+            author: "test".to_string(),
+            tags: vec![],
+        },
+        target: darkreach::project::TargetConfig::default(),
+        competitive: None,
+        strategy: darkreach::project::StrategyConfig {
+            auto_strategy: false,
+            prove_phase: false,
+            phases: vec![darkreach::project::PhaseConfig {
+                name: "prove".to_string(),
+                description: "Proof pass".to_string(),
+                search_params: serde_json::json!({
+                    "action": "prove",
+                    "search_type": "factorial",
+                    "time_budget_secs": 0,
+                }),
+                block_size: None,
+                depends_on: None,
+                activation_condition: None,
+                completion: "all_blocks_done".to_string(),
+            }],
+        },
+        infrastructure: None,
+        budget: None,
+        workers: None,
+    };
+    let project_id = db.create_project(&config, None).await.unwrap();
+    db.update_project_status(project_id, "active")
+        .await
+        .unwrap();
+
+    darkreach::project::orchestrate_tick(&db).await.unwrap();
+
+    let primes = db
+        .get_primes_filtered(10, 0, &PrimeFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(primes[0].proof_method, "probabilistic");
+
+    let phases = db.get_project_phases(project_id).await.unwrap();
+    assert_eq!(phases[0].status, "active");
+    assert_eq!(phases[0].total_tested, 0);
+    let project = db
+        .get_project_by_slug("prove-budget-test")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(project.status, "active");
+}
+
+/// Tests that a prove phase with more PRPs than one pass takes keeps going.
+///
+/// Exercises: `project::orchestrate_tick()` re-running an active prove phase,
+/// the `prove_cursor` in `search_params`, `db.update_phase_params()`.
+///
+/// With `batch_size = 1` and two PRPs, the first tick proves one and leaves
+/// the phase active; the second proves the other and completes the phase.
+#[tokio::test]
+async fn project_prove_phase_continues_until_no_prps_remain() {
+    require_db!();
+    let db = setup().await;
+
+    for expression in ["11! + 1", "27! + 1"] {
+        db.insert_prime("factorial", expression, 8, "{}", "probabilistic", None)
+            .await
+            .unwrap();
+    }
+
+    let config = darkreach::project::ProjectConfig {
+        project: darkreach::project::ProjectMeta {
+            name: "Prove Batches Test".to_string(),
+            description: "".to_string(),
+            objective: darkreach::project::Objective::Custom,
+            form: "factorial".to_string(),
+            author: "test".to_string(),
+            tags: vec![],
+        },
+        target: darkreach::project::TargetConfig::default(),
+        competitive: None,
+        strategy: darkreach::project::StrategyConfig {
+            auto_strategy: false,
+            prove_phase: false,
+            phases: vec![darkreach::project::PhaseConfig {
+                name: "prove".to_string(),
+                description: "Proof pass".to_string(),
+                search_params: serde_json::json!({
+                    "action": "prove",
+                    "search_type": "factorial",
+                    "batch_size": 1,
+                }),
+                block_size: None,
+                depends_on: None,
+                activation_condition: None,
+                completion: "all_blocks_done".to_string(),
+            }],
+        },
+        infrastructure: None,
+        budget: None,
+        workers: None,
+    };
+    let project_id = db.create_project(&config, None).await.unwrap();
+    db.update_project_status(project_id, "active")
+        .await
+        .unwrap();
+
+    async fn proven(db: &Database) -> usize {
+        db.get_primes_filtered(10, 0, &PrimeFilter::default())
+            .await
+            .unwrap()
+            .iter()
+            .filter(|p| p.proof_method.starts_with("deterministic"))
+            .count()
+    }
+
+    darkreach::project::orchestrate_tick(&db).await.unwrap();
+    assert_eq!(proven(&db).await, 1);
+    let phases = db.get_project_phases(project_id).await.unwrap();
+    assert_eq!(phases[0].status, "active");
+    assert_eq!(phases[0].total_tested, 1);
+
+    darkreach::project::orchestrate_tick(&db).await.unwrap();
+    assert_eq!(proven(&db).await, 2);
+    let phases = db.get_project_phases(project_id).await.unwrap();
+    assert_eq!(phases[0].status, "completed");
+    assert_eq!(phases[0].total_tested, 2);
+}
+
+/// Tests that activating a search phase reuses an identical live job.
+///
+/// Exercises: `project::orchestrate_tick()` phase activation,
+/// `db.find_or_create_search_job()`.
+///
+/// A running job already searches the phase's exact params and range (as one
+/// left by an activation that failed before linking it would). The tick must
+/// link the phase to that job rather than create a second one.
+#[tokio::test]
+async fn project_phase_activation_reuses_identical_job() {
+    require_db!();
+    let db = setup().await;
+
+    let config = darkreach::project::ProjectConfig {
+        project: darkreach::project::ProjectMeta {
+            name: "Reuse Job Test".to_string(),
+            description: "".to_string(),
+            objective: darkreach::project::Objective::Custom,
+            form: "factorial".to_string(),
+            author: "test".to_string(),
+            tags: vec![],
+        },
+        target: darkreach::project::TargetConfig::default(),
+        competitive: None,
+        strategy: darkreach::project::StrategyConfig {
+            auto_strategy: false,
+            prove_phase: false,
+            phases: vec![darkreach::project::PhaseConfig {
+                name: "sweep".to_string(),
+                description: "Sweep".to_string(),
+                search_params: serde_json::json!({
+                    "search_type": "factorial",
+                    "start": 1,
+                    "end": 500,
+                }),
+                block_size: Some(100),
+                depends_on: None,
+                activation_condition: None,
+                completion: "all_blocks_done".to_string(),
+            }],
+        },
+        infrastructure: None,
+        budget: None,
+        workers: None,
+    };
+    let project_id = db.create_project(&config, None).await.unwrap();
+    let phases = db.get_project_phases(project_id).await.unwrap();
+    let existing = db
+        .create_search_job("factorial", &phases[0].search_params, 1, 500, 100)
+        .await
+        .unwrap();
+    db.update_project_status(project_id, "active")
+        .await
+        .unwrap();
+
+    darkreach::project::orchestrate_tick(&db).await.unwrap();
+
+    let phases = db.get_project_phases(project_id).await.unwrap();
+    assert_eq!(phases[0].status, "active");
+    assert_eq!(phases[0].search_job_id, Some(existing));
+    assert_eq!(db.get_search_jobs().await.unwrap().len(), 1);
+}
+
+/// Tests the per-form best-digit lookup that seeds milestone events.
+///
+/// Exercises: `db.get_best_digits()`, `db.update_project_best_prime()`.
+///
+/// A form with no project has no tracked best; once a project records a best
+/// prime, the lookup returns its digit count.
+#[tokio::test]
+async fn get_best_digits_reads_project_best() {
+    require_db!();
+    let db = setup().await;
+
+    assert_eq!(db.get_best_digits("factorial").await.unwrap(), None);
+
+    let config = darkreach::project::ProjectConfig {
+        project: darkreach::project::ProjectMeta {
+            name: "Best Digits Test".to_string(),
+            description: "".to_string(),
+            objective: darkreach::project::Objective::Custom,
+            form: "factorial".to_string(),
+            author: "test".to_string(),
+            tags: vec![],
+        },
+        target: darkreach::project::TargetConfig::default(),
+        competitive: None,
+        strategy: darkreach::project::StrategyConfig::default(),
+        infrastructure: None,
+        budget: None,
+        workers: None,
+    };
+    let project_id = db.create_project(&config, None).await.unwrap();
+    assert_eq!(db.get_best_digits("factorial").await.unwrap(), Some(0));
+
+    db.update_project_best_prime(project_id, None, 5000)
+        .await
+        .unwrap();
+    assert_eq!(db.get_best_digits("factorial").await.unwrap(), Some(5000));
+    assert_eq!(db.get_best_digits("kbn").await.unwrap(), None);
+}
+
+/// Tests the record-proximity query behind `project show`.
+///
+/// Exercises: `db.upsert_record()`, `db.primes_within_digits_of_record()`,
+/// the `records` ⋈ `primes` join.
+///
+/// With a 10,000-digit factorial record (and a smaller second category that
+/// must not lower the bar), a 100-digit margin keeps the primes at 9,950 and
+/// 10,020 digits and drops the one at 9,800, the exact boundary at 9,900 is
+/// kept, and other forms are ignored.
+#[tokio::test]
+async fn primes_within_digits_of_record_respects_margin() {
+    require_db!();
+    let db = setup().await;
+
+    let records = [("overall", "n! + 1", 10_000), ("minus", "m! - 1", 5_000)];
+    for (category, expression, digits) in records {
+        db.upsert_record(
+            "factorial",
+            category,
+            expression,
+            digits,
+            None,
+            None,
+            None,
+            None,
+            None,
+            0,
+        )
+        .await
+        .unwrap();
+    }
+    for (form, expression, digits) in [
+        ("factorial", "a! + 1", 9_800),
+        ("factorial", "b! + 1", 9_900),
+        ("factorial", "c! + 1", 9_950),
+        ("factorial", "d! - 1", 10_020),
+        ("kbn", "3*2^33000 + 1", 9_990),
+    ] {
+        db.insert_prime(form, expression, digits, "{}", "deterministic", None)
+            .await
+            .unwrap();
+    }
+
+    let near = db
+        .primes_within_digits_of_record("factorial", 100)
+        .await
+        .unwrap();
+    let found: Vec<(&str, i64)> = near
+        .iter()
+        .map(|p| (p.expression.as_str(), p.digits))
+        .collect();
+    assert_eq!(
+        found,
+        vec![("d! - 1", 10_020), ("c! + 1", 9_950), ("b! + 1", 9_900)]
+    );
+
+    let no_record = db.primes_within_digits_of_record("kbn", 100).await.unwrap();
+    assert!(no_record.is_empty());
+}
+
+// == Housekeeping ==============================================================
+// Hourly maintenance run by the dashboard's background loop.
+// ==============================================================================
+
+/// Tests that the planner-statistics refresh runs against the migrated schema.
+///
+/// Exercises: `db.analyze_hot_tables()`.
+///
+/// Every table named in the `ANALYZE` must exist, so this also guards against
+/// a migration renaming one of the hot tables.
+#[tokio::test]
+async fn analyze_hot_tables_runs_on_migrated_schema() {
+    require_db!();
+    let db = setup().await;
+
+    db.analyze_hot_tables().await.unwrap();
+}
+
+// == Cost Calibration ==========================================================
+// On-demand refit of the per-form power-law cost model from work block timing.
+// ==============================================================================
+
+/// Tests that recalibration recovers a known power law from block timings.
+///
+/// Exercises: `db.get_block_timing_samples()`, `ai_engine::recalibrate_form()`,
+/// `cost_calibration` upsert.
+///
+/// Every block of a kbn job is completed with a claim-to-completion time of
+/// exactly `tested * 0.2 * (digits/1000)^2.3`, so the log-log fit should
+/// return those coefficients with near-zero error.
+#[tokio::test]
+async fn recalibrate_form_recovers_known_exponent() {
+    require_db!();
+    let db = setup().await;
+
+    let params = serde_json::json!({"search_type": "kbn", "k": 3, "base": 2});
+    let job_id = db
+        .create_search_job("kbn", &params, 10_000, 100_000, 10_000)
+        .await
+        .unwrap();
+    let blocks: Vec<(i64, i64, i64)> = sqlx::query_as(
+        "SELECT id, block_start, block_end FROM work_blocks WHERE search_job_id = $1",
+    )
+    .bind(job_id)
+    .fetch_all(db.pool())
+    .await
+    .unwrap();
+    assert!(blocks.len() >= 3, "Need at least 3 blocks to fit");
+
+    let tested = 100i64;
+    for &(id, start, end) in &blocks {
+        let mid = (start + end) as u64 / 2;
+        let digits = darkreach::project::estimate_digits_for_form("kbn", mid) as f64;
+        let elapsed = tested as f64 * 0.2 * (digits / 1000.0).powf(2.3);
+        sqlx::query(
+            "UPDATE work_blocks
+             SET status = 'completed', tested = $2, completed_at = NOW(),
+                 claimed_at = NOW() - make_interval(secs => $3)
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(tested)
+        .bind(elapsed)
+        .execute(db.pool())
+        .await
+        .unwrap();
+    }
+
+    let samples = db.get_block_timing_samples("kbn").await.unwrap();
+    assert_eq!(samples.len(), blocks.len());
+    let other = db.get_block_timing_samples("factorial").await.unwrap();
+    assert!(other.is_empty(), "Samples are filtered by form");
+
+    let fit = darkreach::ai_engine::recalibrate_form(&db, "kbn")
+        .await
+        .unwrap()
+        .expect("Should fit with one sample per block");
+    assert!((fit.coeff_b - 2.3).abs() < 0.01, "b = {}", fit.coeff_b);
+    assert!((fit.coeff_a - 0.2).abs() < 0.01, "a = {}", fit.coeff_a);
+    assert!(fit.avg_error_pct.unwrap() < 0.01);
+    assert_eq!(fit.sample_count, blocks.len() as i64);
+
+    let stored = db.get_cost_calibration("kbn").await.unwrap().unwrap();
+    assert_eq!(stored.coeff_b, fit.coeff_b);
+}
+
+/// Tests that recalibration declines to fit a form with no completed blocks.
+///
+/// Exercises: `ai_engine::recalibrate_form()` empty path.
+#[tokio::test]
+async fn recalibrate_form_without_samples_returns_none() {
+    require_db!();
+    let db = setup().await;
+
+    let fit = darkreach::ai_engine::recalibrate_form(&db, "wagstaff")
+        .await
+        .unwrap();
+    assert!(fit.is_none());
+    assert!(db.get_cost_calibration("wagstaff").await.unwrap().is_none());
+}
+
+// == Form Efficiency ===========================================================
+// Per-form discovery rate from search_job metric samples and stored primes.
+// ==============================================================================
+
+/// Tests per-form efficiency against a hand calculation.
+///
+/// Exercises: `db.get_form_efficiency()`, `metric_samples` latest-per-job
+/// selection, `primes` digit averages.
+///
+/// Two factorial jobs report 400k and 600k tested (older samples are
+/// superseded) with 1 + 2 found; one kbn job reports 250k tested and 1 found.
+/// Expected: factorial 3 per 1M tested and avg digits (100 + 200 + 600) / 3,
+/// kbn 4 per 1M with avg digits 50.
+#[tokio::test]
+async fn form_efficiency_matches_hand_calculation() {
+    require_db!();
+    let db = setup().await;
+
+    let params = serde_json::json!({"search_type": "factorial"});
+    let fact_a = db
+        .create_search_job("factorial", &params, 1, 100, 10)
+        .await
+        .unwrap();
+    let fact_b = db
+        .create_search_job("factorial", &params, 100, 200, 10)
+        .await
+        .unwrap();
+    let kbn_params = serde_json::json!({"search_type": "kbn", "k": 3, "base": 2});
+    let kbn = db
+        .create_search_job("kbn", &kbn_params, 1, 100, 10)
+        .await
+        .unwrap();
+
+    let now = chrono::Utc::now();
+    let sample = |job_id: i64, form: &str, metric: &str, value: f64, age_secs: i64| {
+        darkreach::db::MetricSample {
+            ts: now - chrono::Duration::seconds(age_secs),
+            scope: "search_job".to_string(),
+            metric: metric.to_string(),
+            value,
+            labels: Some(serde_json::json!({
+                "job_id": job_id.to_string(),
+                "search_type": form,
+                "status": "running",
+            })),
+        }
+    };
+    let (tested, found) = ("search_job.total_tested", "search_job.total_found");
+    db.insert_metric_samples(&[
+        // Superseded by the newer fact_a sample below
+        sample(fact_a, "factorial", tested, 100_000.0, 120),
+        sample(fact_a, "factorial", tested, 400_000.0, 60),
+        sample(fact_a, "factorial", found, 1.0, 60),
+        sample(fact_b, "factorial", tested, 600_000.0, 60),
+        sample(fact_b, "factorial", found, 2.0, 60),
+        sample(kbn, "kbn", tested, 250_000.0, 60),
+        sample(kbn, "kbn", found, 1.0, 60),
+    ])
+    .await
+    .unwrap();
+
+    for (form, expr, digits) in [
+        ("factorial", "11! + 1", 100u64),
+        ("factorial", "27! + 1", 200),
+        ("factorial", "37! + 1", 600),
+        ("kbn", "3*2^5-1", 50),
+    ] {
+        db.insert_prime(form, expr, digits, "{}", "probabilistic", None)
+            .await
+            .unwrap();
+    }
+
+    let rows = db.get_form_efficiency().await.unwrap();
+    assert_eq!(rows.len(), 2);
+
+    let fact = rows.iter().find(|r| r.form == "factorial").unwrap();
+    assert_eq!(fact.tested, 1_000_000);
+    assert_eq!(fact.found, 3);
+    assert!((fact.primes_per_million - 3.0).abs() < 1e-9);
+    assert!((fact.avg_digits.unwrap() - 300.0).abs() < 1e-9);
+
+    let kbn_row = rows.iter().find(|r| r.form == "kbn").unwrap();
+    assert_eq!(kbn_row.tested, 250_000);
+    assert_eq!(kbn_row.found, 1);
+    assert!((kbn_row.primes_per_million - 4.0).abs() < 1e-9);
+    assert_eq!(kbn_row.avg_digits, Some(50.0));
+}
+
+// == Schema Migrations =========================================================
+// Tests for the embedded migration runner behind `darkreach migrate`, which
+// also builds the test schema.
+// ==============================================================================
+
+/// Tests that migrating an empty schema applies everything once, and that a
+/// second run is a no-op.
+///
+/// Exercises: `migrations::run()`, `schema_migrations` bookkeeping.
+///
+/// Builds the schema from nothing in a scratch schema put first on the
+/// `search_path` (extensions stay reachable in `public`), so the shared test
+/// schema is untouched. Every embedded plain-PostgreSQL migration is recorded
+/// exactly once and the core tables are present.
+#[tokio::test]
+async fn migrate_twice_is_a_no_op() {
+    require_db!();
+    let db = setup().await;
+    let schema = "migrate_from_empty";
+    sqlx::raw_sql(&format!(
+        "DROP SCHEMA IF EXISTS {schema} CASCADE; CREATE SCHEMA {schema}"
+    ))
+    .execute(db.pool())
+    .await
+    .unwrap();
+    let options: sqlx::postgres::PgConnectOptions = common::test_db_url().parse().unwrap();
+    let pool = sqlx::postgres::PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.options([("search_path", format!("{schema},public"))]))
+        .await
+        .unwrap();
+
+    let embedded: Vec<&str> = darkreach::db::migrations::for_target(false)
+        .map(|m| m.name)
+        .collect();
+    let first = darkreach::db::migrations::run(&pool, false).await.unwrap();
+    assert_eq!(first, embedded);
+    let second = darkreach::db::migrations::run(&pool, false).await.unwrap();
+    assert!(second.is_empty(), "re-applied {:?}", second);
+
+    let recorded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(recorded as usize, embedded.len());
+    for table in [
+        "primes",
+        "workers",
+        "search_jobs",
+        "work_blocks",
+        "verification_queue",
+        "operator_nodes",
+        "prp_candidates",
+    ] {
+        let exists: bool = sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+            .bind(format!("{schema}.{table}"))
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(exists, "table {} missing", table);
+    }
+    pool.close().await;
+    sqlx::raw_sql(&format!("DROP SCHEMA {schema} CASCADE"))
+        .execute(db.pool())
+        .await
+        .unwrap();
+}
@@ -32,7 +32,7 @@
 //!
 //! - **Registration** (tests 1-4): `POST /api/v1/register` success, 400, 500, malformed JSON
 //! - **Work Claiming** (tests 5-9): `GET /api/v1/work` with assignments, 204, 401, capabilities, all search types
-//! - **Result Submission** (tests 10-12): `POST /api/v1/result` success, with primes, server error
+//! - **Result Submission** (tests 10-12b): `POST /api/v1/result` success, with primes, server error, retry
 //! - **Heartbeat** (tests 13-14): `POST /api/v1/worker/heartbeat` success, worker_id verification
 //! - **Update Check** (tests 15-17): `GET /api/v1/worker/latest` available, not available, offline
 //! - **Search Params** (tests 18-22): `to_args()`, `range()`, `default_block_size()`, roundtrip, error
@@ -509,6 +509,8 @@ async fn test_submit_result_success() {
         tested: 1000,
         found: 0,
         primes: vec![],
+        idempotency_key: None,
+        verification_id: None,
     };
 
     let result = darkreach::operator::submit_result(&config, &submission);
@@ -572,6 +574,8 @@ async fn test_submit_result_with_primes() {
                 certificate: None,
             },
         ],
+        idempotency_key: None,
+        verification_id: None,
     };
 
     let result = darkreach::operator::submit_result(&config, &submission);
@@ -608,6 +612,8 @@ async fn test_submit_result_server_error() {
         tested: 100,
         found: 0,
         primes: vec![],
+        idempotency_key: None,
+        verification_id: None,
     };
 
     let result = darkreach::operator::submit_result(&config, &submission);
@@ -619,6 +625,56 @@ async fn test_submit_result_server_error() {
     handle.abort();
 }
 
+/// Test 12b: A failed submission is retried with the same idempotency key.
+///
+/// The mock server fails the first request, as if the response to an applied
+/// result were lost, and accepts the second. Both requests must carry the
+/// submission's key so the coordinator can deduplicate the retry.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_result_retry_reuses_idempotency_key() {
+    let keys: Arc<Mutex<Vec<serde_json::Value>>> = Arc::new(Mutex::new(Vec::new()));
+    let keys_clone = keys.clone();
+    let app = Router::new().route(
+        "/api/v1/result",
+        post(move |Json(body): Json<serde_json::Value>| {
+            let keys_clone = keys_clone.clone();
+            async move {
+                let mut keys = keys_clone.lock().unwrap();
+                keys.push(body["idempotency_key"].clone());
+                if keys.len() == 1 {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                } else {
+                    StatusCode::OK
+                }
+            }
+        }),
+    );
+    let (url, handle) = start_mock_server(app).await;
+    let config = test_config(&url);
+
+    let key = uuid::Uuid::new_v4();
+    let submission = ResultSubmission {
+        block_id: 7,
+        tested: 100,
+        found: 0,
+        primes: vec![],
+        idempotency_key: Some(key),
+        verification_id: None,
+    };
+    darkreach::operator::submit_result_with_retry(
+        &config,
+        &submission,
+        std::time::Duration::from_millis(10),
+    )
+    .unwrap();
+
+    let keys = keys.lock().unwrap();
+    assert_eq!(keys.len(), 2);
+    assert!(keys.iter().all(|k| *k == serde_json::json!(key)));
+
+    handle.abort();
+}
+
 // ============================================================================
 // Heartbeat Tests (13-14)
 // ============================================================================
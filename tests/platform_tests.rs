@@ -15,23 +15,24 @@
 //! | **Tool Availability** | 13–16 | PFGW, PRST, tar, hostname command |
 //! | **GMP/rug Compatibility** | 17–20 | Large factorial, large power, known primes, thread safety |
 //! | **Resource Boundaries** | 21–23 | Large sieve, large checkpoint, bounded batch generation |
-//! | **Rayon Thread Pool** | 24–26 | Thread count, parallel primality, stop propagation |
-//! | **Signal Handling** | 27–28 | AtomicBool visibility, concurrent stop flag (Unix-only) |
+//! | **Rayon Thread Pool** | 24–27 | Thread count, parallel primality, stop propagation, busy threads |
+//! | **Signal Handling** | 28–29 | AtomicBool visibility, concurrent stop flag (Unix-only) |
 //!
 //! ## Platform-Specific Annotations
 //!
-//! - `#[cfg(unix)]`: Tests 27–28 (signal handling) — Unix-only AtomicBool + thread tests
+//! - `#[cfg(unix)]`: Tests 28–29 (signal handling) — Unix-only AtomicBool + thread tests
 //! - `#[cfg(target_os = "macos")]`: Not used directly; CPU model detection uses sysctl
 //!   on macOS and /proc/cpuinfo on Linux, but both are exercised by test 3
 //! - `#[ignore]`: Tests 13–14 require optional external tools (PFGW, PRST)
 //!
 //! ## Thread Safety Tests
 //!
-//! Tests 20, 25, 27, and 28 exercise concurrent access patterns:
+//! Tests 20, 25, 27, 28, and 29 exercise concurrent access patterns:
 //! - Test 20: Concurrent GMP operations via rayon (rug::Integer is thread-safe)
 //! - Test 25: Parallel primality testing via rayon::par_iter
-//! - Test 27: AtomicBool visibility across spawned threads (Ordering::SeqCst)
-//! - Test 28: Multiple threads reading/writing a shared AtomicBool stop flag
+//! - Test 27: Engine busy-thread count during a real kbn search
+//! - Test 28: AtomicBool visibility across spawned threads (Ordering::SeqCst)
+//! - Test 29: Multiple threads reading/writing a shared AtomicBool stop flag
 
 use darkreach::checkpoint;
 use darkreach::sieve;
@@ -197,6 +198,7 @@ fn test_checkpoint_path_with_spaces() {
         last_n: 500,
         min_n: Some(1),
         max_n: Some(1000),
+        sieve: None,
     };
 
     checkpoint::save(&path, &cp).unwrap();
@@ -223,6 +225,7 @@ fn test_checkpoint_path_with_unicode() {
     let cp = checkpoint::Checkpoint::Palindromic {
         digit_count: 7,
         half_value: "1234".to_string(),
+        order: None,
         min_digits: Some(1),
         max_digits: Some(99),
     };
@@ -408,6 +411,9 @@ fn test_pfgw_detection() {
             pfgw::PfgwResult::Unavailable { reason } => {
                 eprintln!("PFGW unavailable: {}", reason);
             }
+            pfgw::PfgwResult::Failed { reason, .. } => {
+                panic!("PFGW failed: {}", reason);
+            }
         }
     }
 }
@@ -676,6 +682,7 @@ fn test_checkpoint_large_state() {
             checkpoint::Checkpoint::Palindromic {
                 digit_count: u64::MAX,
                 half_value: "9".repeat(1000), // Large half_value string
+                order: Some("minimal-change".into()),
                 min_digits: Some(1),
                 max_digits: Some(u64::MAX),
             },
@@ -686,6 +693,7 @@ fn test_checkpoint_large_state() {
                 last_n: u64::MAX,
                 min_n: Some(0),
                 max_n: Some(u64::MAX),
+                sieve: None,
             },
         ),
         (
@@ -825,7 +833,7 @@ fn test_batch_generation_memory_bounded() {
 }
 
 // ============================================================================
-// Rayon Thread Pool Tests (24–26)
+// Rayon Thread Pool Tests (24–27)
 // ============================================================================
 
 /// Test 24: The rayon thread pool size should match or be close to the number of
@@ -947,11 +955,60 @@ fn test_rayon_stop_propagation() {
     );
 }
 
+/// Test 27: While a real kbn search tests candidates, the heartbeat's sample of
+/// the global pool shows busy threads, and none once the search returns. This
+/// is what `/api/fleet` reports as `threads_busy`. No other test in this binary
+/// runs an engine, so the process-wide counter is this search's alone.
+#[test]
+fn test_rayon_busy_threads_during_search() {
+    use darkreach::progress::{thread_utilization, Progress};
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let sink = darkreach::prime_sink::FilePrimeSink::open(&dir.path().join("primes.jsonl")).unwrap();
+    assert_eq!(thread_utilization().busy, 0);
+
+    let searching = Arc::new(AtomicBool::new(true));
+    let sampler = {
+        let searching = searching.clone();
+        std::thread::spawn(move || {
+            let mut peak = 0;
+            while searching.load(Ordering::Relaxed) {
+                peak = peak.max(thread_utilization().busy);
+                std::thread::yield_now();
+            }
+            peak
+        })
+    };
+    darkreach::kbn::search(
+        3,
+        2,
+        2_000,
+        3_000,
+        &Progress::new(),
+        &sink,
+        rt.handle(),
+        &dir.path().join("kbn.checkpoint"),
+        "{}",
+        25,
+        100_000,
+        None,
+        None,
+    )
+    .unwrap();
+    searching.store(false, Ordering::Relaxed);
+    let peak = sampler.join().unwrap();
+
+    assert!(peak > 0, "no busy threads sampled during the search");
+    assert!(peak <= rayon::current_num_threads());
+    assert_eq!(thread_utilization().busy, 0);
+}
+
 // ============================================================================
-// Signal Handling Tests — Unix-only (27–28)
+// Signal Handling Tests — Unix-only (28–29)
 // ============================================================================
 
-/// Test 27: An AtomicBool written by one thread must be visible to another thread
+/// Test 28: An AtomicBool written by one thread must be visible to another thread
 /// immediately (or within a single memory fence). This validates the pattern used
 /// by the stop flag: the coordinator thread sets the flag, and all rayon worker
 /// threads must observe it on their next check. Uses SeqCst ordering to guarantee
@@ -997,7 +1054,7 @@ fn test_stop_flag_atomic_visibility() {
     assert!(flag.load(Ordering::SeqCst), "Flag should remain set");
 }
 
-/// Test 28: Multiple threads simultaneously reading and writing a shared stop
+/// Test 29: Multiple threads simultaneously reading and writing a shared stop
 /// flag must not cause data races, panics, or undefined behavior. This validates
 /// that the AtomicBool used for stop propagation is safe under heavy contention.
 ///
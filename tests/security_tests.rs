@@ -15,6 +15,9 @@
 //! | Path traversal (doc slug, roadmap) | A01:2021 Broken Access Control / CWE-22 | Slug validation prevents file reads |
 //! | Negative block_size | A08:2021 Software Integrity / CWE-20 | Input validation rejects nonsensical values |
 //! | Malformed JSON | A08:2021 Software Integrity / CWE-20 | JSON parser rejects invalid payloads |
+//! | Replayed result submission | A08:2021 Software Integrity / CWE-837 | Idempotency keys apply a result only once |
+//! | Stop by form | A08:2021 Software Integrity / CWE-20 | A form-wide stop is validated and never touches other forms |
+//! | Worker block release | A01:2021 Broken Access Control / CWE-285 | Only admins release a worker's blocks, and never another worker's |
 //!
 //! # Prerequisites
 //!
@@ -77,6 +80,33 @@ async fn get(app: Router, uri: &str) -> (StatusCode, serde_json::Value) {
     (status, json)
 }
 
+/// Sends a request with an optional Bearer token and JSON body, returning the
+/// status code and parsed JSON body.
+async fn send_json(
+    app: Router,
+    method: Method,
+    uri: &str,
+    api_key: Option<&str>,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let mut builder = Request::builder().uri(uri).method(method);
+    if let Some(key) = api_key {
+        builder = builder.header("authorization", format!("Bearer {}", key));
+    }
+    let body = match body {
+        Some(json) => {
+            builder = builder.header("content-type", "application/json");
+            Body::from(serde_json::to_string(&json).unwrap())
+        }
+        None => Body::empty(),
+    };
+    let response = app.oneshot(builder.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap_or(serde_json::json!(null));
+    (status, json)
+}
+
 // == SQL Injection =============================================================
 // Tests that user-supplied query parameters cannot alter SQL query semantics.
 //
@@ -491,3 +521,317 @@ async fn malformed_json_returns_error() {
         response.status()
     );
 }
+
+// == Replay Protection =========================================================
+// Tests that an operator result submission cannot be applied twice.
+//
+// Operators retry `POST /api/v1/nodes/result` when a response is lost, so the
+// same result can arrive more than once. Without deduplication every replay
+// would grant credit and trust again. Clients attach an `idempotency_key` and
+// the server replays the stored response for repeats.
+//
+// References:
+// - OWASP: https://owasp.org/Top10/A08_2021-Software_and_Data_Integrity_Failures/
+// - CWE-837: https://cwe.mitre.org/data/definitions/837.html
+// ==============================================================================
+
+/// Tests that a result submitted twice with the same idempotency key is
+/// applied only once.
+///
+/// **Attack vector**: OWASP A08:2021 Software Integrity / CWE-837
+/// (Improper Enforcement of a Single, Unique Action).
+///
+/// Registers an operator, claims a block, and submits the same result twice
+/// with one key. Both submissions must return the original 200 response, and
+/// the operator's credit must reflect a single completed block.
+#[tokio::test]
+async fn result_submission_idempotency_key_applies_once() {
+    require_db!();
+    let router = app().await;
+
+    let (status, reg) = send_json(
+        router.clone(),
+        Method::POST,
+        "/api/v1/register",
+        None,
+        Some(serde_json::json!({
+            "username": "replay_worker",
+            "email": "replay@example.com"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let api_key = reg["api_key"].as_str().unwrap().to_string();
+
+    let (status, _) = send_json(
+        router.clone(),
+        Method::POST,
+        "/api/search_jobs",
+        None,
+        Some(serde_json::json!({
+            "search_type": "factorial",
+            "params": {"start": 1, "end": 100},
+            "range_start": 1,
+            "range_end": 100,
+            "block_size": 100
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, work) = send_json(
+        router.clone(),
+        Method::GET,
+        "/api/v1/nodes/work?cores=4&ram_gb=16",
+        Some(&api_key),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let block_id = work["block_id"].as_i64().unwrap();
+
+    let submission = serde_json::json!({
+        "block_id": block_id,
+        "tested": 42,
+        "found": 0,
+        "primes": [],
+        "idempotency_key": "6f1c2b9e-8a4d-4c3e-9f0a-1b2c3d4e5f60"
+    });
+    for attempt in 0..2 {
+        let (status, json) = send_json(
+            router.clone(),
+            Method::POST,
+            "/api/v1/nodes/result",
+            Some(&api_key),
+            Some(submission.clone()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "attempt {} failed", attempt);
+        assert_eq!(json, serde_json::json!({"ok": true}));
+    }
+
+    let (status, stats) = send_json(
+        router.clone(),
+        Method::GET,
+        "/api/v1/operators/stats",
+        Some(&api_key),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        stats["credit"], 42,
+        "Replayed submission must not grant credit twice"
+    );
+}
+
+// == Scoped Stop ===============================================================
+// Tests that `POST /api/searches/stop-by-form` only reaches the named form.
+//
+// The endpoint is an incident kill switch: one request cancels every running
+// job of a form and sends "stop" to every worker running it. An overly broad
+// match (or an unvalidated form) would halt the whole fleet. This is a
+// scoping and input-validation test; the endpoint has no per-user access rule.
+//
+// References:
+// - OWASP: https://owasp.org/Top10/A08_2021-Software_and_Data_Integrity_Failures/
+// - CWE-20: https://cwe.mitre.org/data/definitions/20.html
+// ==============================================================================
+
+/// Tests that stopping one form leaves every other form's jobs and workers
+/// running.
+///
+/// **Attack vector**: OWASP A08:2021 Software Integrity / CWE-20
+/// (Improper Input Validation).
+///
+/// Creates two kbn jobs and one factorial job, with one worker on each form.
+/// Stopping kbn must cancel exactly the two kbn jobs and signal only the kbn
+/// worker, whose next heartbeat returns "stop". An unknown form is rejected.
+#[tokio::test]
+async fn stop_by_form_is_scoped_to_named_form() {
+    require_db!();
+    let router = app().await;
+    let db = darkreach::db::Database::connect(&common::test_db_url())
+        .await
+        .unwrap();
+
+    let mut job_ids = Vec::new();
+    for (form, end) in [("kbn", 100), ("kbn", 200), ("factorial", 100)] {
+        let (status, json) = send_json(
+            router.clone(),
+            Method::POST,
+            "/api/search_jobs",
+            None,
+            Some(serde_json::json!({
+                "search_type": form,
+                "params": {"start": 1, "end": end},
+                "range_start": 1,
+                "range_end": end,
+                "block_size": 100
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        job_ids.push(json["id"].as_i64().unwrap());
+    }
+    for (worker, form) in [("kbn-worker", "kbn"), ("factorial-worker", "factorial")] {
+        let registered = db.upsert_worker(worker, worker, 4, form, "{}").await;
+        registered.unwrap();
+    }
+
+    let (status, json) = send_json(
+        router.clone(),
+        Method::POST,
+        "/api/searches/stop-by-form",
+        None,
+        Some(serde_json::json!({"form": "kbn"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let mut cancelled: Vec<i64> = json["cancelled"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_i64().unwrap())
+        .collect();
+    cancelled.sort();
+    assert_eq!(cancelled, job_ids[..2]);
+    assert_eq!(json["workers_signalled"], 1);
+
+    for (id, expected) in job_ids.iter().zip(["cancelled", "cancelled", "running"]) {
+        let (status, json) = get(router.clone(), &format!("/api/search_jobs/{}", id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["job"]["status"], expected, "job {}", id);
+    }
+    for (worker, form, expected) in [
+        ("kbn-worker", "kbn", Some("stop")),
+        ("factorial-worker", "factorial", None),
+    ] {
+        let command = db
+            .worker_heartbeat_rpc(worker, worker, 4, form, "{}", 0, 0, "", None, None)
+            .await
+            .unwrap();
+        assert_eq!(command.as_deref(), expected, "worker {}", worker);
+    }
+
+    let (status, _) = send_json(
+        router.clone(),
+        Method::POST,
+        "/api/searches/stop-by-form",
+        None,
+        Some(serde_json::json!({"form": "kbn' OR '1'='1"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// == Worker Block Release ======================================================
+// Tests that `POST /api/fleet/workers/{worker_id}/release-blocks` is admin-only
+// and only takes back the named worker's blocks.
+//
+// Releasing a bad worker's blocks puts its claimed work back in the pool and
+// queues its recent completions for re-verification. An anonymous caller could
+// use it to strip any worker of its blocks, and matching too broadly would do
+// the same to workers that are still running.
+//
+// References:
+// - OWASP: https://owasp.org/Top10/A01_2021-Broken_Access_Control/
+// - CWE-285: https://cwe.mitre.org/data/definitions/285.html
+// ==============================================================================
+
+/// A Supabase-style JWT for a user with no profile, who therefore has the
+/// default "operator" role. Signed with `SUPABASE_JWT_SECRET` when set
+/// (development mode skips signature checks).
+fn operator_jwt() -> String {
+    let claims = serde_json::json!({
+        "sub": "00000000-0000-4000-8000-000000000859",
+        "aud": "authenticated",
+        "role": "authenticated",
+        "exp": 4_102_444_800u64,
+    });
+    let secret = std::env::var("SUPABASE_JWT_SECRET").unwrap_or_default();
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+/// Tests that only an admin can release a worker's blocks, and that a
+/// release leaves every other worker's claims in place.
+///
+/// **Attack vector**: OWASP A01:2021 Broken Access Control / CWE-285
+/// (Improper Authorization).
+///
+/// The bad worker claims two blocks and completes one of them; a good worker
+/// claims a third. An anonymous request gets 401 and a non-admin user 403,
+/// and neither releases anything. The release itself then puts only the bad
+/// worker's open block back in the pool and queues its completed block for
+/// re-verification; the good worker still holds its block, and a new worker
+/// claims the released one.
+#[tokio::test]
+async fn release_worker_blocks_requires_admin_and_only_touches_that_worker() {
+    require_db!();
+    let router = app().await;
+    let db = darkreach::db::Database::connect(&common::test_db_url())
+        .await
+        .unwrap();
+
+    let (status, json) = send_json(
+        router.clone(),
+        Method::POST,
+        "/api/search_jobs",
+        None,
+        Some(serde_json::json!({
+            "search_type": "kbn",
+            "params": {"start": 1, "end": 300},
+            "range_start": 1,
+            "range_end": 300,
+            "block_size": 100
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let job_id = json["id"].as_i64().unwrap();
+    for worker in ["bad-worker", "good-worker", "new-worker"] {
+        let registered = db.upsert_worker(worker, worker, 4, "kbn", "{}").await;
+        registered.unwrap();
+    }
+
+    let claim = |worker: &'static str| {
+        let db = &db;
+        async move { db.claim_work_block(job_id, worker).await.unwrap().unwrap() }
+    };
+    let completed = claim("bad-worker").await.block_id;
+    db.complete_work_block(completed, 100, 0).await.unwrap();
+    let open = claim("bad-worker").await.block_id;
+    let kept = claim("good-worker").await.block_id;
+
+    let uri = "/api/fleet/workers/bad-worker/release-blocks";
+    let (status, _) = send_json(router.clone(), Method::POST, uri, None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let token = operator_jwt();
+    let (status, _) = send_json(router.clone(), Method::POST, uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let claimed_by = |id: i64| {
+        let db = &db;
+        async move {
+            let (claimed_by,): (Option<String>,) =
+                sqlx::query_as("SELECT claimed_by FROM work_blocks WHERE id = $1")
+                    .bind(id)
+                    .fetch_one(db.pool())
+                    .await
+                    .unwrap();
+            claimed_by
+        }
+    };
+    assert_eq!(claimed_by(open).await.as_deref(), Some("bad-worker"));
+
+    let release = db.release_worker_blocks("bad-worker", 24).await.unwrap();
+    assert_eq!(release.released, vec![open]);
+    assert_eq!(release.reverify_queued, vec![completed]);
+    assert_eq!(claimed_by(kept).await.as_deref(), Some("good-worker"));
+    assert_eq!(claim("new-worker").await.block_id, open);
+}